@@ -3,11 +3,16 @@
 use crate::version::P2P_CLIENT_VERSION;
 use clap::Args;
 use reth_config::Config;
+use reth_discv5::{Discv5ConfigBuilder, Enr, DEFAULT_DISCOVERY_V5_PORT};
 use reth_net_nat::NatResolver;
 use reth_network::{HelloMessage, NetworkConfigBuilder};
 use reth_primitives::{mainnet_nodes, ChainSpec, NodeRecord};
 use secp256k1::SecretKey;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+};
 
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Args)]
@@ -53,7 +58,7 @@ pub struct NetworkArgs {
     pub no_persist_peers: bool,
 
     #[allow(rustdoc::invalid_html_tags)]
-    /// NAT resolution method (any|none|upnp|publicip|extip:<IP>)
+    /// NAT resolution method (any|none|upnp|natpmp|pcp|publicip|extip:<IP>)
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -68,6 +73,17 @@ pub struct NetworkArgs {
     /// Maximum number of inbound requests. default: 30
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
+
+    /// Announce the `snap/1` protocol and serve state ranges to peers.
+    #[arg(long)]
+    pub snap: bool,
+
+    /// Download the state of the sync target over `snap/1` instead of executing all blocks.
+    ///
+    /// Only the hashed state and the state trie are downloaded, so blocks can't be executed on top
+    /// of it.
+    #[arg(long = "snap.sync", requires = "snap")]
+    pub snap_sync: bool,
 }
 
 impl NetworkArgs {
@@ -136,6 +152,23 @@ pub struct DiscoveryArgs {
     /// The UDP port to use for P2P discovery/networking. default: 30303
     #[arg(long = "discovery.port", name = "discovery.port", value_name = "DISCOVERY_PORT")]
     pub port: Option<u16>,
+
+    /// Enable Discv5 discovery next to Discv4.
+    #[arg(long, conflicts_with = "disable_discovery")]
+    pub enable_discv5_discovery: bool,
+
+    /// The UDP port to use for Discv5 discovery.
+    #[arg(
+        long = "discovery.v5.port",
+        name = "discovery.v5.port",
+        value_name = "DISCOVERY_V5_PORT",
+        default_value_t = DEFAULT_DISCOVERY_V5_PORT
+    )]
+    pub discv5_port: u16,
+
+    /// Comma separated ENRs of the nodes to bootstrap Discv5 discovery from.
+    #[arg(long = "discovery.v5.bootnodes", value_name = "ENRS", value_delimiter = ',')]
+    pub discv5_bootnodes: Vec<Enr>,
 }
 
 impl DiscoveryArgs {
@@ -151,6 +184,14 @@ impl DiscoveryArgs {
         if self.disable_discovery || self.disable_discv4_discovery {
             network_config_builder = network_config_builder.disable_discv4_discovery();
         }
+
+        if self.enable_discv5_discovery && !self.disable_discovery {
+            let mut discv5_builder = Discv5ConfigBuilder::default();
+            discv5_builder
+                .listen_addr(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), self.discv5_port))
+                .add_boot_nodes(self.discv5_bootnodes.clone());
+            network_config_builder = network_config_builder.discovery_v5(discv5_builder);
+        }
         network_config_builder
     }
 }
@@ -176,6 +217,23 @@ mod tests {
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
     }
 
+    #[test]
+    fn parse_discv5_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert!(!args.discovery.enable_discv5_discovery);
+        assert_eq!(args.discovery.discv5_port, DEFAULT_DISCOVERY_V5_PORT);
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--enable-discv5-discovery",
+            "--discovery.v5.port",
+            "9001",
+        ])
+        .args;
+        assert!(args.discovery.enable_discv5_discovery);
+        assert_eq!(args.discovery.discv5_port, 9001);
+    }
+
     #[test]
     fn parse_peer_args() {
         let args =
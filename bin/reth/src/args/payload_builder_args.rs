@@ -6,7 +6,7 @@ use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
-use reth_primitives::constants::MAXIMUM_EXTRA_DATA_SIZE;
+use reth_primitives::{constants::MAXIMUM_EXTRA_DATA_SIZE, Address};
 use std::{borrow::Cow, ffi::OsStr, time::Duration};
 
 /// Parameters for configuring the Payload Builder
@@ -36,6 +36,28 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", help_heading = "Builder", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Maximum number of transactions of a single sender to include in a block.
+    ///
+    /// Unlimited if not set.
+    #[arg(
+        long = "builder.max-txs-per-sender",
+        help_heading = "Builder",
+        value_name = "COUNT",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub max_transactions_per_sender: Option<usize>,
+
+    /// Senders whose transactions are included before the transactions of other senders.
+    ///
+    /// The limit of transactions per sender does not apply to these senders.
+    #[arg(
+        long = "builder.priority-senders",
+        help_heading = "Builder",
+        value_name = "ADDRESSES",
+        value_delimiter = ','
+    )]
+    pub priority_senders: Vec<Address>,
 }
 
 impl PayloadBuilderConfig for PayloadBuilderArgs {
@@ -58,6 +80,14 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn max_transactions_per_sender(&self) -> Option<usize> {
+        self.max_transactions_per_sender
+    }
+
+    fn priority_senders(&self) -> &[Address] {
+        &self.priority_senders
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -117,6 +147,23 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_transaction_selection() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.max-txs-per-sender",
+            "4",
+            "--builder.priority-senders",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+        ])
+        .args;
+        assert_eq!(args.max_transactions_per_sender, Some(4));
+        assert_eq!(
+            args.priority_senders,
+            vec![Address::from_low_u64_be(1), Address::from_low_u64_be(2)]
+        );
+    }
+
     #[test]
     fn test_default_extradata() {
         let extradata = default_extradata();
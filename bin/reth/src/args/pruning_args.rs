@@ -35,6 +35,11 @@ impl PruningArgs {
                         .map(|contract| PruneMode::Before(contract.block)),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    log_history: _chain_spec
+                        .deposit_contract
+                        .as_ref()
+                        .map(|contract| PruneMode::Before(contract.block)),
+                    bodies: None,
                     contract_logs_filter: ContractLogsPruneConfig(
                         _chain_spec
                             .deposit_contract
@@ -9,10 +9,12 @@ use clap::{
     Arg, Args, Command,
 };
 use futures::TryFutureExt;
+use reth_auto_seal_consensus::{AutoSealHandle, DevApi};
+use reth_interfaces::consensus::BadBlocks;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
     BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
-    HeaderProvider, StateProviderFactory,
+    HeaderProvider, HistoryReader, ImpersonatedSenders, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -22,8 +24,9 @@ use reth_rpc::{
         gas_oracle::GasPriceOracleConfig,
         RPC_DEFAULT_GAS_CAP,
     },
-    JwtError, JwtSecret,
+    JwtError, JwtSecret, DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_MAX_TRACE_FILTER_TRACES,
 };
+use reth_rpc_api::AnvilApiServer;
 use reth_rpc_builder::{
     auth::{AuthServerConfig, AuthServerHandle},
     constants,
@@ -138,6 +141,16 @@ pub struct RpcServerArgs {
     #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_TRACING_REQUESTS)]
     pub rpc_max_tracing_requests: u32,
 
+    /// Maximum number of blocks a `trace_filter` request can trace.
+    ///
+    /// When filtering by address, only the blocks in which one of the addresses changed count.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_TRACE_FILTER_BLOCKS)]
+    pub rpc_max_trace_filter_blocks: u64,
+
+    /// Maximum number of traces returned by a single `trace_filter` request.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_TRACE_FILTER_TRACES)]
+    pub rpc_max_trace_filter_traces: usize,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long,
@@ -222,6 +235,10 @@ impl RpcServerArgs {
     /// Returns the handles for the launched regular RPC server(s) (if any) and the server handle
     /// for the auth server that handles the `engine_` API that's accessed by the consensus
     /// layer.
+    ///
+    /// If a `dev_handle` is given, the dev chain methods are installed on all configured
+    /// transports, replacing the `eth_sendTransaction` of the `eth` namespace. The
+    /// `impersonated_senders` are consulted when serving transactions sealed without a signature.
     #[allow(clippy::too_many_arguments)]
     pub async fn start_servers<Provider, Pool, Network, Tasks, Events, Engine, Conf>(
         &self,
@@ -232,6 +249,9 @@ impl RpcServerArgs {
         events: Events,
         engine_api: Engine,
         jwt_secret: JwtSecret,
+        bad_blocks: BadBlocks,
+        impersonated_senders: ImpersonatedSenders,
+        dev_handle: Option<AutoSealHandle>,
         conf: &mut Conf,
     ) -> eyre::Result<(RpcServerHandle, AuthServerHandle)>
     where
@@ -241,6 +261,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + HistoryReader
             + Clone
             + Unpin
             + 'static,
@@ -262,11 +283,19 @@ impl RpcServerArgs {
             .with_network(network)
             .with_events(events)
             .with_executor(executor)
+            .with_bad_blocks(bad_blocks)
+            .with_impersonated_senders(impersonated_senders)
             .build_with_auth_server(module_config, engine_api);
 
         // apply configured customization
         conf.extend_rpc_modules(self, &mut registry, &mut rpc_modules)?;
 
+        // install the dev chain methods, available in dev mode only
+        if let Some(dev_handle) = dev_handle {
+            let dev_api = DevApi::new(dev_handle, registry.eth_api()).into_rpc();
+            rpc_modules.replace_configured(dev_api)?;
+        }
+
         let server_config = self.rpc_server_config();
         let launch_rpc = rpc_modules.start_server(server_config).map_ok(|handle| {
             if let Some(url) = handle.ipc_endpoint() {
@@ -307,6 +336,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + HistoryReader
             + Clone
             + Unpin
             + 'static,
@@ -342,6 +372,7 @@ impl RpcServerArgs {
             + ChainSpecProvider
             + EvmEnvProvider
             + HeaderProvider
+            + HistoryReader
             + StateProviderFactory
             + Clone
             + Unpin
@@ -456,6 +487,8 @@ impl RethRpcConfig for RpcServerArgs {
     fn eth_config(&self) -> EthConfig {
         EthConfig::default()
             .max_tracing_requests(self.rpc_max_tracing_requests)
+            .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
+            .max_trace_filter_traces(self.rpc_max_trace_filter_traces)
             .rpc_gas_cap(self.rpc_gas_cap)
             .gpo_config(self.gas_price_oracle_config())
     }
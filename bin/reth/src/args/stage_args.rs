@@ -17,5 +17,6 @@ pub enum StageEnum {
     History,
     AccountHistory,
     StorageHistory,
+    LogHistory,
     TotalDifficulty,
 }
@@ -1,10 +1,15 @@
 //! Transaction pool arguments
 
+use crate::args::utils::parse_duration_from_secs;
 use clap::Args;
+use reth_primitives::Address;
 use reth_transaction_pool::{
-    PoolConfig, SubPoolLimit, DEFAULT_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    maintain::LocalTransactionJournalConfig, LocalTransactionConfig, PoolConfig, PriceBumpConfig,
+    SubPoolLimit, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    TXPOOL_MAX_QUEUED_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::{path::PathBuf, time::Duration};
 
 /// Parameters for debugging purposes
 #[derive(Debug, Args, PartialEq, Default)]
@@ -30,13 +35,58 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.queued_max_size", help_heading = "TxPool", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
     pub queued_max_size: usize,
 
+    /// Max number of transaction in the blob sub-pool
+    #[arg(long = "txpool.blob_max_count", help_heading = "TxPool", default_value_t = TXPOOL_SUBPOOL_MAX_TXS_DEFAULT)]
+    pub blob_max_count: usize,
+    /// Max size of the blob sub-pool in megabytes.
+    #[arg(long = "txpool.blob_max_size", help_heading = "TxPool", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
+    pub blob_max_size: usize,
+
     /// Max number of executable transaction slots guaranteed per account
     #[arg(long = "txpool.max_account_slots", help_heading = "TxPool", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
+    /// Max number of transactions per account in the queued sub-pool.
+    ///
+    /// The queued transactions with the highest nonces of accounts that exceed this limit are
+    /// evicted.
+    #[arg(long = "txpool.max_queued_account_slots", help_heading = "TxPool", default_value_t = TXPOOL_MAX_QUEUED_ACCOUNT_SLOTS_PER_SENDER)]
+    pub max_queued_account_slots: usize,
+
+    /// Comma separated list of addresses whose transactions are treated as local.
+    #[arg(
+        long = "txpool.locals",
+        help_heading = "TxPool",
+        value_name = "ADDRESSES",
+        value_delimiter = ','
+    )]
+    pub locals: Vec<Address>,
+
+    /// Disables the exemption of local transactions from the pool limits.
+    #[arg(long = "txpool.nolocals", help_heading = "TxPool")]
+    pub no_locals: bool,
+
     /// Price bump (in %) for the transaction pool underpriced check.
     #[arg(long = "txpool.price_bump", help_heading = "TxPool", default_value_t = DEFAULT_PRICE_BUMP)]
     pub price_bump: u128,
+
+    /// Price bump percentage to replace an already existing blob transaction
+    #[arg(long = "txpool.blobpool_price_bump", help_heading = "TxPool", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
+    pub blob_transaction_price_bump: u128,
+
+    /// Disables the journal of local transactions.
+    #[arg(long = "txpool.nojournal", help_heading = "TxPool")]
+    pub no_journal: bool,
+
+    /// Path to the journal of local transactions, used to restore them after a restart.
+    ///
+    /// Defaults to `transactions.rlp` in the chain specific data directory.
+    #[arg(long = "txpool.journal", help_heading = "TxPool", value_name = "FILE")]
+    pub journal: Option<PathBuf>,
+
+    /// Interval in seconds at which the journal of local transactions is rewritten.
+    #[arg(long = "txpool.rejournal", help_heading = "TxPool", value_parser = parse_duration_from_secs, default_value = "3600", value_name = "SECONDS")]
+    pub rejournal: Duration,
 }
 
 impl TxPoolArgs {
@@ -55,8 +105,33 @@ impl TxPoolArgs {
                 max_txs: self.queued_max_count,
                 max_size: self.queued_max_size * 1024 * 1024,
             },
+            blob_limit: SubPoolLimit {
+                max_txs: self.blob_max_count,
+                max_size: self.blob_max_size * 1024 * 1024,
+            },
             max_account_slots: self.max_account_slots,
-            price_bump: self.price_bump,
+            max_queued_account_slots: self.max_queued_account_slots,
+            price_bumps: PriceBumpConfig {
+                default_price_bump: self.price_bump,
+                replace_blob_tx_price_bump: self.blob_transaction_price_bump,
+            },
+            local_transactions_config: LocalTransactionConfig {
+                no_exemptions: self.no_locals,
+                local_addresses: self.locals.iter().copied().collect(),
+            },
+        }
+    }
+
+    /// Returns the configuration of the local transactions journal, or `None` if it is disabled.
+    ///
+    /// The given path is used if no journal path was configured.
+    pub fn journal_config(&self, default_path: PathBuf) -> Option<LocalTransactionJournalConfig> {
+        if self.no_journal {
+            return None
         }
+        Some(LocalTransactionJournalConfig {
+            journal_path: self.journal.clone().unwrap_or(default_path),
+            rejournal_interval: self.rejournal,
+        })
     }
 }
@@ -1,7 +1,8 @@
 //! Clap parser utilities
 
+use crate::dirs::config_dir;
 use reth_primitives::{
-    fs, AllGenesisFormats, BlockHashOrNumber, ChainSpec, DEV, GOERLI, MAINNET, SEPOLIA,
+    fs, named_chain_spec, register_named_chain, AllGenesisFormats, BlockHashOrNumber, ChainSpec,
 };
 use reth_revm::primitives::B256 as H256;
 use std::{
@@ -18,35 +19,54 @@ pub fn parse_duration_from_secs(arg: &str) -> eyre::Result<Duration, std::num::P
     Ok(Duration::from_secs(seconds))
 }
 
-/// Clap value parser for [ChainSpec]s that takes either a built-in chainspec or the path
-/// to a custom one.
+/// Clap value parser for [ChainSpec]s that takes either a built-in chainspec, the name of a
+/// chain in the `chains` directory of the config directory or the path to a custom one.
 pub fn chain_spec_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
-    Ok(match s {
-        "mainnet" => MAINNET.clone(),
-        "goerli" => GOERLI.clone(),
-        "sepolia" => SEPOLIA.clone(),
-        "dev" => DEV.clone(),
-        _ => {
-            let raw = fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned()))?;
-            serde_json::from_str(&raw)?
-        }
-    })
+    if let Some(spec) = named_chain_spec(s) {
+        return Ok(spec)
+    }
+
+    if let Some(path) = named_chain_file(s) {
+        let spec: Arc<ChainSpec> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        register_named_chain(s, spec.clone()).map_err(|err| eyre::eyre!(err))?;
+        return Ok(spec)
+    }
+
+    let raw = fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned()))?;
+    Ok(serde_json::from_str(&raw)?)
 }
 
-/// Clap value parser for [ChainSpec]s that takes either a built-in genesis format or the path
-/// to a custom one.
+/// Clap value parser for [ChainSpec]s that takes either a built-in genesis format, the name of a
+/// chain in the `chains` directory of the config directory or the path to a custom one.
 pub fn genesis_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
-    Ok(match s {
-        "mainnet" => MAINNET.clone(),
-        "goerli" => GOERLI.clone(),
-        "sepolia" => SEPOLIA.clone(),
-        "dev" => DEV.clone(),
-        _ => {
-            let raw = fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned()))?;
-            let genesis: AllGenesisFormats = serde_json::from_str(&raw)?;
-            Arc::new(genesis.into())
-        }
-    })
+    if let Some(spec) = named_chain_spec(s) {
+        return Ok(spec)
+    }
+
+    if let Some(path) = named_chain_file(s) {
+        let genesis: AllGenesisFormats = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let spec = Arc::new(ChainSpec::from(genesis));
+        register_named_chain(s, spec.clone()).map_err(|err| eyre::eyre!(err))?;
+        return Ok(spec)
+    }
+
+    let raw = fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned()))?;
+    let genesis: AllGenesisFormats = serde_json::from_str(&raw)?;
+    Ok(Arc::new(genesis.into()))
+}
+
+/// Returns the path of the `<name>.json` file in the `chains` directory of the config directory,
+/// if `name` is a plain chain name and the file exists.
+///
+/// Files in this directory make new networks available by name, without a built-in chainspec.
+fn named_chain_file(name: &str) -> Option<PathBuf> {
+    let is_name =
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_name {
+        return None
+    }
+    let path = config_dir()?.join("chains").join(format!("{name}.json"));
+    path.is_file().then_some(path)
 }
 
 /// Parse [BlockHashOrNumber]
@@ -110,7 +130,7 @@ mod tests {
 
     #[test]
     fn parse_chain_spec() {
-        for chain in ["mainnet", "sepolia", "goerli"] {
+        for chain in ["mainnet", "sepolia", "goerli", "holesky"] {
             chain_spec_value_parser(chain).unwrap();
             genesis_value_parser(chain).unwrap();
         }
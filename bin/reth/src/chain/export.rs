@@ -0,0 +1,193 @@
+use crate::{
+    args::{utils::genesis_value_parser, DatabaseArgs},
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use eyre::Context;
+use reth_db::open_db_read_only;
+use reth_era::{era1_file_name, Era1Block, Era1Writer, MAX_BLOCKS_PER_ERA1};
+use reth_primitives::{stage::StageId, BlockBody, BlockNumber, ChainSpec, Hardfork};
+use reth_provider::{
+    BlockReader, HeaderProvider, ProviderFactory, ReceiptProvider, StageCheckpointReader,
+    StaticFileProvider,
+};
+use std::{fs::File, io::BufWriter, ops::RangeInclusive, path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// Exports the chain history into era1 files.
+#[derive(Debug, Parser)]
+pub struct ExportCommand {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    /// - holesky
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The first block to export, which must be the first block of an epoch of 8192 blocks.
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export. Defaults to the highest fully synced block.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// The directory to write the era1 files to.
+    ///
+    /// Each file contains the blocks of one epoch of 8192 blocks, which are named after the
+    /// chain, the epoch and the accumulator root of the file, e.g. `mainnet-00000-5ec1ffb8.era1`.
+    /// Only blocks before the merge can be exported, the export stops at the first block after
+    /// the terminal total difficulty.
+    #[arg(value_name = "EXPORT_DIR", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl ExportCommand {
+    /// Execute the `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+        info!(target: "reth::cli", path = ?db_path, "Opening database");
+        let db = Arc::new(open_db_read_only(&db_path, self.db.log_level)?);
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
+        let factory = ProviderFactory::new(db, self.chain.clone()).with_static_files(static_files);
+
+        let to = match self.to {
+            Some(to) => to,
+            None => factory
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|checkpoint| checkpoint.block_number)
+                .unwrap_or_default(),
+        };
+        if self.from > to {
+            eyre::bail!("nothing to export, the first block #{} is after #{to}", self.from);
+        }
+        let epoch_len = MAX_BLOCKS_PER_ERA1 as u64;
+        if self.from % epoch_len != 0 {
+            eyre::bail!(
+                "the first block #{} is not the first block of an epoch of {epoch_len} blocks",
+                self.from
+            );
+        }
+
+        std::fs::create_dir_all(&self.path)?;
+
+        let mut start = self.from;
+        while start <= to {
+            let epoch = start / epoch_len;
+            let end = ((epoch + 1) * epoch_len - 1).min(to);
+            let Some((path, last)) = self.export_epoch(&factory, epoch, start..=end)? else {
+                info!(target: "reth::cli", block = start, "Reached the merge, export finished");
+                break
+            };
+            info!(target: "reth::cli", ?path, blocks = ?(start..=last), "Exported era1 file");
+            if last < end {
+                info!(target: "reth::cli", block = last + 1, "Reached the merge, export finished");
+                break
+            }
+            start = end + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the blocks of the range, which lies within the epoch, into an era1 file.
+    ///
+    /// Only the blocks before the merge are written. Returns the path of the file and the number
+    /// of the last written block, or `None` if the range starts after the merge.
+    fn export_epoch<DB: reth_db::database::Database>(
+        &self,
+        factory: &ProviderFactory<DB>,
+        epoch: u64,
+        range: RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<Option<(PathBuf, BlockNumber)>> {
+        let provider = factory.provider()?;
+        let paris = self.chain.fork(Hardfork::Paris);
+
+        let tmp_path = self.path.join(format!("{}-{epoch:05}.era1.tmp", self.chain.chain));
+        let mut writer = None;
+        let mut last = None;
+        for number in range {
+            let block = provider
+                .block(number.into())?
+                .ok_or_else(|| eyre::eyre!("block #{number} not found"))?;
+            let total_difficulty = provider
+                .header_td_by_number(number)?
+                .ok_or_else(|| eyre::eyre!("total difficulty of block #{number} not found"))?;
+            // the parent of the block reached the terminal total difficulty
+            if paris.active_at_ttd(total_difficulty, block.difficulty) {
+                break
+            }
+            if block.withdrawals.is_some() {
+                eyre::bail!("block #{number} is after the Shanghai hardfork");
+            }
+            let receipts = provider
+                .receipts_by_block(number.into())?
+                .filter(|receipts| receipts.len() == block.body.len())
+                .ok_or_else(|| eyre::eyre!("receipts of block #{number} are missing"))?;
+
+            if writer.is_none() {
+                writer = Some(Era1Writer::new(BufWriter::new(File::create(&tmp_path)?))?);
+            }
+            writer.as_mut().expect("writer was created").append(&Era1Block {
+                header: block.header,
+                body: BlockBody {
+                    transactions: block.body,
+                    ommers: block.ommers,
+                    withdrawals: None,
+                },
+                receipts,
+                total_difficulty,
+            })?;
+            last = Some(number);
+        }
+        let (Some(writer), Some(last)) = (writer, last) else { return Ok(None) };
+        let (_, root) = writer.finish()?;
+
+        let path = self.path.join(era1_file_name(&self.chain.chain.to_string(), epoch, root));
+        std::fs::rename(&tmp_path, &path)
+            .wrap_err_with(|| format!("Could not move era1 file to {}", path.display()))?;
+        Ok(Some((path, last)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_export_command_args() {
+        let args: ExportCommand =
+            ExportCommand::parse_from(["reth", "--from", "10", "--to", "20", "out"]);
+        assert_eq!(args.from, 10);
+        assert_eq!(args.to, Some(20));
+        assert_eq!(args.path, std::path::Path::new("out"));
+    }
+}
@@ -8,7 +8,7 @@ use clap::Parser;
 use eyre::Context;
 use futures::{Stream, StreamExt};
 use reth_beacon_consensus::BeaconConsensus;
-use reth_provider::{ProviderFactory, StageCheckpointReader};
+use reth_provider::{ProviderFactory, StageCheckpointReader, StaticFileProvider};
 
 use crate::args::{utils::genesis_value_parser, DatabaseArgs};
 use reth_config::Config;
@@ -17,6 +17,7 @@ use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder, test_utils::FileClient,
 };
+use reth_era::{Era1, Era1Block, TrustedAccumulators, ERA1_EXTENSION};
 use reth_interfaces::consensus::Consensus;
 use reth_primitives::{stage::StageId, ChainSpec, H256};
 use reth_stages::{
@@ -26,11 +27,14 @@ use reth_stages::{
         TotalDifficultyStage,
     },
 };
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::watch;
 use tracing::{debug, info};
 
-/// Syncs RLP encoded blocks from a file.
+/// Syncs RLP encoded blocks or era1 archives from a file.
 #[derive(Debug, Parser)]
 pub struct ImportCommand {
     /// The path to the configuration file to use.
@@ -49,12 +53,15 @@ pub struct ImportCommand {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -67,10 +74,21 @@ pub struct ImportCommand {
     #[clap(flatten)]
     db: DatabaseArgs,
 
+    /// The path to the trusted accumulator roots of the chain, required to import era1 archives.
+    ///
+    /// The file contains one hex encoded root per line, starting with the root of epoch 0. Every
+    /// imported archive has to cover a whole epoch and match the trusted root of the epoch.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    accumulators: Option<PathBuf>,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
     /// remaining stages are executed.
+    ///
+    /// The path is either a file of RLP encoded blocks, an `.era1` archive or a directory of
+    /// `.era1` archives. Era1 archives are imported one after another in the order of their file
+    /// names, archives with blocks that were already imported are skipped.
     #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
     path: PathBuf,
 }
@@ -96,47 +114,120 @@ impl ImportCommand {
         debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
 
         init_genesis(db.clone(), self.chain.clone())?;
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
 
         let consensus = Arc::new(BeaconConsensus::new(self.chain.clone()));
         info!(target: "reth::cli", "Consensus engine initialized");
 
-        // create a new FileClient
-        info!(target: "reth::cli", "Importing chain file");
-        let file_client = Arc::new(FileClient::new(&self.path).await?);
+        if let Some(era1_files) = era1_files(&self.path)? {
+            let Some(accumulators) = &self.accumulators else {
+                eyre::bail!("importing era1 archives requires the trusted --accumulators");
+            };
+            let accumulators = TrustedAccumulators::read(accumulators)
+                .wrap_err_with(|| format!("Could not read accumulators {accumulators:?}"))?;
+
+            for path in era1_files {
+                info!(target: "reth::cli", ?path, "Importing era1 file");
+                let era1 = Era1::read(&path)
+                    .wrap_err_with(|| format!("Could not read era1 file {path:?}"))?;
+                let epoch = accumulators
+                    .verify(&era1)
+                    .wrap_err_with(|| format!("Could not verify era1 file {path:?}"))?;
+                debug!(target: "reth::cli", ?path, epoch, "Verified era1 file");
+
+                let latest_block_number = self.latest_block_number(&db)?.unwrap_or_default();
+                let mut blocks = Vec::with_capacity(era1.blocks.len());
+                for block in era1.blocks.into_iter().map(Era1Block::into_block) {
+                    if block.number == 0 && block.hash_slow() != self.chain.genesis_hash() {
+                        eyre::bail!("era1 file {path:?} does not belong to the chain");
+                    }
+                    if block.number > latest_block_number {
+                        blocks.push(block);
+                    }
+                }
+                if blocks.is_empty() {
+                    info!(target: "reth::cli", ?path, "Blocks already imported, skipping");
+                    continue
+                }
+
+                let file_client = Arc::new(FileClient::from_blocks(blocks));
+                if !self
+                    .run_import(
+                        config.clone(),
+                        Arc::clone(&db),
+                        static_files.clone(),
+                        &consensus,
+                        file_client,
+                    )
+                    .await?
+                {
+                    break
+                }
+            }
+        } else {
+            // create a new FileClient
+            info!(target: "reth::cli", "Importing chain file");
+            let file_client = Arc::new(FileClient::new(&self.path).await?);
+            info!(target: "reth::cli", "Chain file imported");
+
+            self.run_import(config, Arc::clone(&db), static_files, &consensus, file_client).await?;
+        }
+
+        info!(target: "reth::cli", "Finishing up");
+        Ok(())
+    }
 
+    /// Runs the import pipeline until all blocks of the file client are synced.
+    ///
+    /// Returns `false` if the import was interrupted.
+    async fn run_import<DB, C>(
+        &self,
+        config: Config,
+        db: DB,
+        static_files: StaticFileProvider,
+        consensus: &Arc<C>,
+        file_client: Arc<FileClient>,
+    ) -> eyre::Result<bool>
+    where
+        DB: Database + Clone + Unpin + 'static,
+        C: Consensus + 'static,
+    {
         // override the tip
         let tip = file_client.tip().expect("file client has no tip");
-        info!(target: "reth::cli", "Chain file imported");
 
-        let (mut pipeline, events) =
-            self.build_import_pipeline(config, Arc::clone(&db), &consensus, file_client).await?;
+        let (mut pipeline, events) = self
+            .build_import_pipeline(config, db.clone(), static_files, consensus, file_client)
+            .await?;
 
         // override the tip
         pipeline.set_tip(tip);
         debug!(target: "reth::cli", ?tip, "Tip manually set");
 
-        let factory = ProviderFactory::new(&db, self.chain.clone());
-        let provider = factory.provider().map_err(PipelineError::Interface)?;
-
-        let latest_block_number =
-            provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number);
+        let latest_block_number = self.latest_block_number(&db)?;
         tokio::spawn(handle_events(None, latest_block_number, events));
 
         // Run pipeline
         info!(target: "reth::cli", "Starting sync pipeline");
         tokio::select! {
             res = pipeline.run() => res?,
-            _ = tokio::signal::ctrl_c() => {},
+            _ = tokio::signal::ctrl_c() => return Ok(false),
         };
 
-        info!(target: "reth::cli", "Finishing up");
-        Ok(())
+        Ok(true)
+    }
+
+    /// Returns the highest block that went through all stages.
+    fn latest_block_number<DB: Database>(&self, db: DB) -> eyre::Result<Option<u64>> {
+        let factory = ProviderFactory::new(db, self.chain.clone());
+        let provider = factory.provider().map_err(PipelineError::Interface)?;
+        Ok(provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number))
     }
 
     async fn build_import_pipeline<DB, C>(
         &self,
         config: Config,
         db: DB,
+        static_files: StaticFileProvider,
         consensus: &Arc<C>,
         file_client: Arc<FileClient>,
     ) -> eyre::Result<(Pipeline<DB>, impl Stream<Item = NodeEvent>)>
@@ -161,6 +252,7 @@ impl ImportCommand {
 
         let max_block = file_client.max_block().unwrap_or(0);
         let mut pipeline = Pipeline::builder()
+            .with_static_files(static_files)
             .with_tip_sender(tip_tx)
             // we want to sync all blocks the file client provides or 0 if empty
             .with_max_block(max_block)
@@ -208,6 +300,31 @@ impl ImportCommand {
     }
 }
 
+/// Returns the era1 files to import if the path is an era1 file or a directory, sorted by their
+/// file names.
+fn era1_files(path: &Path) -> eyre::Result<Option<Vec<PathBuf>>> {
+    let is_era1 = |path: &Path| path.extension().map_or(false, |ext| ext == ERA1_EXTENSION);
+
+    if path.is_dir() {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_file() && is_era1(&path) {
+                files.push(path);
+            }
+        }
+        if files.is_empty() {
+            eyre::bail!("no era1 files found in {path:?}");
+        }
+        files.sort();
+        Ok(Some(files))
+    } else if is_era1(path) {
+        Ok(Some(vec![path.to_path_buf()]))
+    } else {
+        Ok(None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -219,4 +336,12 @@ mod tests {
             assert_eq!(args.chain.chain, chain.parse().unwrap());
         }
     }
+
+    #[test]
+    fn parse_era1_import_command_args() {
+        let args: ImportCommand =
+            ImportCommand::parse_from(["reth", "--accumulators", "roots.txt", "era1"]);
+        assert_eq!(args.accumulators, Some(PathBuf::from("roots.txt")));
+        assert_eq!(args.path, PathBuf::from("era1"));
+    }
 }
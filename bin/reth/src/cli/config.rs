@@ -1,5 +1,6 @@
 //! Config traits for various node components.
 
+use reth_primitives::Address;
 use reth_revm::primitives::bytes::BytesMut;
 use reth_rlp::Encodable;
 use reth_rpc_builder::EthConfig;
@@ -45,4 +46,10 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// Maximum number of transactions of a single sender to include in a block.
+    fn max_transactions_per_sender(&self) -> Option<usize>;
+
+    /// Senders whose transactions are included before the transactions of other senders.
+    fn priority_senders(&self) -> &[Address];
 }
@@ -8,11 +8,11 @@ use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_primitives::ChainSpec;
 use reth_provider::{
     BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
-    StateProviderFactory,
+    HistoryReader, StateProviderFactory,
 };
 use reth_rpc_builder::{RethModuleRegistry, TransportRpcModules};
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{ConfiguredSelection, TransactionPool};
 use std::{fmt, sync::Arc};
 
 /// A trait that allows for extending parts of the CLI with additional functionality.
@@ -53,6 +53,7 @@ pub trait RethNodeCommandConfig: fmt::Debug {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + HistoryReader
             + Clone
             + Unpin
             + 'static,
@@ -83,6 +84,12 @@ pub trait RethNodeCommandConfig: fmt::Debug {
         Pool: TransactionPool + Unpin + 'static,
         Tasks: TaskSpawner + Clone + Unpin + 'static,
     {
+        let transaction_selection = ConfiguredSelection {
+            max_transactions_per_sender: conf.max_transactions_per_sender(),
+            priority_senders: conf.priority_senders().iter().copied().collect(),
+            ..Default::default()
+        };
+
         let payload_generator = BasicPayloadJobGenerator::new(
             provider,
             pool,
@@ -92,7 +99,8 @@ pub trait RethNodeCommandConfig: fmt::Debug {
                 .deadline(conf.deadline())
                 .max_payload_tasks(conf.max_payload_tasks())
                 .extradata(conf.extradata_rlp_bytes())
-                .max_gas_limit(conf.max_gas_limit()),
+                .max_gas_limit(conf.max_gas_limit())
+                .transaction_selection(transaction_selection),
             chain_spec,
         );
         let (payload_service, payload_builder) = PayloadBuilderService::new(payload_generator);
@@ -176,6 +184,7 @@ impl<T: RethNodeCommandConfig> RethNodeCommandConfig for NoArgs<T> {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + HistoryReader
             + Clone
             + Unpin
             + 'static,
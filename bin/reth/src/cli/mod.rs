@@ -34,12 +34,15 @@ pub struct Cli<Ext: RethCliExt = ()> {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -71,6 +74,7 @@ impl<Ext: RethCliExt> Cli<Ext> {
             Commands::Node(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
@@ -113,9 +117,12 @@ pub enum Commands<Ext: RethCliExt = ()> {
     /// Initialize the database from a genesis file.
     #[command(name = "init")]
     Init(chain::InitCommand),
-    /// This syncs RLP encoded blocks from a file.
+    /// This syncs RLP encoded blocks or era1 archives from a file.
     #[command(name = "import")]
     Import(chain::ImportCommand),
+    /// Export the chain history into era1 files.
+    #[command(name = "export")]
+    Export(chain::ExportCommand),
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
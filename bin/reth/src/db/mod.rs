@@ -42,12 +42,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
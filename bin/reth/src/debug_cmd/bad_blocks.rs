@@ -0,0 +1,74 @@
+//! Command for dumping the bad blocks recorded by a running node.
+use crate::utils::parse_path;
+use clap::Parser;
+use eyre::Context;
+use jsonrpsee::{core::client::ClientT, http_client::HttpClientBuilder, rpc_params};
+use reth_primitives::fs;
+use serde_json::Value;
+use std::path::PathBuf;
+use tracing::*;
+
+/// `reth debug bad-blocks` command
+/// Fetches the blocks rejected by the consensus engine of a running node via `debug_getBadBlocks`
+/// and writes them to the output directory.
+///
+/// For every bad block two files are written: `<number>-<hash>.json` with the full block, including
+/// the rejection reason, and `<number>-<hash>.rlp` with the raw RLP-encoded block.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The HTTP RPC endpoint of the node to query.
+    #[arg(long, value_name = "URL", default_value = "http://localhost:8545")]
+    rpc_url: String,
+
+    /// The directory to write the bad blocks to.
+    #[arg(long, short, value_name = "DIR", value_parser = parse_path)]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `debug bad-blocks` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let client = HttpClientBuilder::default()
+            .build(&self.rpc_url)
+            .wrap_err_with(|| format!("failed to connect to {}", self.rpc_url))?;
+
+        // the rpc `RichBlock` is flattened on the wire, so the response is handled as raw json
+        let blocks: Vec<Value> = client.request("debug_getBadBlocks", rpc_params![]).await?;
+        if blocks.is_empty() {
+            info!(target: "reth::cli", "No bad blocks reported by the node");
+            return Ok(())
+        }
+
+        fs::create_dir_all(&self.output)?;
+
+        for block in blocks {
+            let hash =
+                block["hash"].as_str().ok_or_else(|| eyre::eyre!("bad block without hash"))?;
+            let number = block["number"]
+                .as_str()
+                .and_then(|number| u64::from_str_radix(number.trim_start_matches("0x"), 16).ok())
+                .ok_or_else(|| eyre::eyre!("bad block {hash} without number"))?;
+            let name = format!("{number}-{hash}");
+
+            if let Some(rlp) = block["rlp"].as_str() {
+                let rlp = hex::decode(rlp.trim_start_matches("0x"))
+                    .wrap_err_with(|| format!("invalid rlp for bad block {hash}"))?;
+                fs::write(self.output.join(format!("{name}.rlp")), rlp)?;
+            }
+            fs::write(
+                self.output.join(format!("{name}.json")),
+                serde_json::to_string_pretty(&block)?,
+            )?;
+
+            info!(
+                target: "reth::cli",
+                number,
+                hash,
+                reason = block["reason"].as_str().unwrap_or_default(),
+                "Dumped bad block"
+            );
+        }
+
+        Ok(())
+    }
+}
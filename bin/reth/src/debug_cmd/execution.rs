@@ -24,7 +24,9 @@ use reth_interfaces::{
 use reth_network::NetworkHandle;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{fs, stage::StageId, BlockHashOrNumber, BlockNumber, ChainSpec, H256};
-use reth_provider::{BlockExecutionWriter, ProviderFactory, StageCheckpointReader};
+use reth_provider::{
+    BlockExecutionWriter, ProviderFactory, StageCheckpointReader, StaticFileProvider,
+};
 use reth_stages::{
     sets::DefaultStages,
     stages::{
@@ -57,12 +59,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -95,6 +100,7 @@ impl Command {
         client: Client,
         consensus: Arc<dyn Consensus>,
         db: DB,
+        static_files: StaticFileProvider,
         task_executor: &TaskExecutor,
     ) -> eyre::Result<Pipeline<DB>>
     where
@@ -117,6 +123,7 @@ impl Command {
 
         let header_mode = HeaderSyncMode::Tip(tip_rx);
         let pipeline = Pipeline::builder()
+            .with_static_files(static_files)
             .with_tip_sender(tip_tx)
             .add_stages(
                 DefaultStages::new(
@@ -153,7 +160,7 @@ impl Command {
         &self,
         config: &Config,
         task_executor: TaskExecutor,
-        db: Arc<DatabaseEnv>,
+        provider_factory: ProviderFactory<Arc<DatabaseEnv>>,
         network_secret_path: PathBuf,
         default_peers_path: PathBuf,
     ) -> eyre::Result<NetworkHandle> {
@@ -170,7 +177,7 @@ impl Command {
                 Ipv4Addr::UNSPECIFIED,
                 self.network.discovery.port.unwrap_or(DEFAULT_DISCOVERY_PORT),
             )))
-            .build(ProviderFactory::new(db, self.chain.clone()))
+            .build(provider_factory)
             .start_network()
             .await?;
         info!(target: "reth::cli", peer_id = %network.peer_id(), local_addr = %network.local_addr(), "Connected to P2P network");
@@ -208,6 +215,7 @@ impl Command {
 
         debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
         init_genesis(db.clone(), self.chain.clone())?;
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
 
         let consensus: Arc<dyn Consensus> = Arc::new(BeaconConsensus::new(Arc::clone(&self.chain)));
 
@@ -218,7 +226,8 @@ impl Command {
             .build_network(
                 &config,
                 ctx.task_executor.clone(),
-                db.clone(),
+                ProviderFactory::new(db.clone(), self.chain.clone())
+                    .with_static_files(static_files.clone()),
                 network_secret_path,
                 data_dir.known_peers_path(),
             )
@@ -231,10 +240,11 @@ impl Command {
             fetch_client.clone(),
             Arc::clone(&consensus),
             db.clone(),
+            static_files.clone(),
             &ctx.task_executor,
         )?;
 
-        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let factory = ProviderFactory::new(&db, self.chain.clone()).with_static_files(static_files);
         let provider = factory.provider().map_err(PipelineError::Interface)?;
 
         let latest_block_number =
@@ -15,7 +15,8 @@ use reth_network_api::NetworkInfo;
 use reth_primitives::{fs, stage::StageId, BlockHashOrNumber, ChainSpec};
 use reth_provider::{
     AccountExtReader, BlockExecutor, BlockWriter, ExecutorFactory, HashingWriter, HeaderProvider,
-    LatestStateProviderRef, ProviderFactory, StageCheckpointReader, StorageReader,
+    LatestStateProviderRef, ProviderFactory, StageCheckpointReader, StaticFileProvider,
+    StorageReader,
 };
 use reth_tasks::TaskExecutor;
 use reth_trie::{hashed_cursor::HashedPostStateCursorFactory, updates::TrieKey, StateRoot};
@@ -44,12 +45,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -79,7 +83,7 @@ impl Command {
         &self,
         config: &Config,
         task_executor: TaskExecutor,
-        db: Arc<DatabaseEnv>,
+        provider_factory: ProviderFactory<Arc<DatabaseEnv>>,
         network_secret_path: PathBuf,
         default_peers_path: PathBuf,
     ) -> eyre::Result<NetworkHandle> {
@@ -96,7 +100,7 @@ impl Command {
                 Ipv4Addr::UNSPECIFIED,
                 self.network.discovery.port.unwrap_or(DEFAULT_DISCOVERY_PORT),
             )))
-            .build(ProviderFactory::new(db, self.chain.clone()))
+            .build(provider_factory)
             .start_network()
             .await?;
         info!(target: "reth::cli", peer_id = %network.peer_id(), local_addr = %network.local_addr(), "Connected to P2P network");
@@ -115,7 +119,9 @@ impl Command {
 
         // initialize the database
         let db = Arc::new(init_db(db_path, self.db.log_level)?);
-        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
+        let factory =
+            ProviderFactory::new(&db, self.chain.clone()).with_static_files(static_files.clone());
         let provider = factory.provider()?;
 
         // Look up merkle checkpoint
@@ -132,7 +138,8 @@ impl Command {
             .build_network(
                 &config,
                 ctx.task_executor.clone(),
-                db.clone(),
+                ProviderFactory::new(db.clone(), self.chain.clone())
+                    .with_static_files(static_files),
                 network_secret_path,
                 data_dir.known_peers_path(),
             )
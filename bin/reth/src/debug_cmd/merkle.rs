@@ -19,7 +19,7 @@ use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     BlockHashOrNumber, ChainSpec, PruneModes,
 };
-use reth_provider::{BlockWriter, ProviderFactory, StageCheckpointReader};
+use reth_provider::{BlockWriter, ProviderFactory, StageCheckpointReader, StaticFileProvider};
 use reth_stages::{
     stages::{
         AccountHashingStage, ExecutionStage, ExecutionStageThresholds, MerkleStage,
@@ -50,12 +50,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -89,7 +92,7 @@ impl Command {
         &self,
         config: &Config,
         task_executor: TaskExecutor,
-        db: Arc<DatabaseEnv>,
+        provider_factory: ProviderFactory<Arc<DatabaseEnv>>,
         network_secret_path: PathBuf,
         default_peers_path: PathBuf,
     ) -> eyre::Result<NetworkHandle> {
@@ -106,7 +109,7 @@ impl Command {
                 Ipv4Addr::UNSPECIFIED,
                 self.network.discovery.port.unwrap_or(DEFAULT_DISCOVERY_PORT),
             )))
-            .build(ProviderFactory::new(db, self.chain.clone()))
+            .build(provider_factory)
             .start_network()
             .await?;
         info!(target: "reth::cli", peer_id = %network.peer_id(), local_addr = %network.local_addr(), "Connected to P2P network");
@@ -125,7 +128,9 @@ impl Command {
 
         // initialize the database
         let db = Arc::new(init_db(db_path, self.db.log_level)?);
-        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
+        let factory =
+            ProviderFactory::new(&db, self.chain.clone()).with_static_files(static_files.clone());
         let provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
         // Configure and build network
@@ -135,7 +140,8 @@ impl Command {
             .build_network(
                 &config,
                 ctx.task_executor.clone(),
-                db.clone(),
+                ProviderFactory::new(db.clone(), self.chain.clone())
+                    .with_static_files(static_files),
                 network_secret_path,
                 data_dir.known_peers_path(),
             )
@@ -1,6 +1,6 @@
 //! reth data directories.
 use crate::utils::parse_path;
-use reth_primitives::Chain;
+use reth_primitives::{registered_chain_name, Chain};
 use std::{
     env::VarError,
     fmt::{Debug, Display, Formatter},
@@ -16,6 +16,10 @@ pub fn config_path_prefix(chain: Chain) -> String {
         "goerli".to_string()
     } else if chain == Chain::sepolia() {
         "sepolia".to_string()
+    } else if chain == Chain::holesky() {
+        "holesky".to_string()
+    } else if let Some(name) = registered_chain_name(chain) {
+        name
     } else {
         chain.id().to_string()
     }
@@ -279,6 +283,21 @@ impl<D> ChainPath<D> {
         self.0.join("known-peers.json").into()
     }
 
+    /// Returns the path to the journal of local transactions for this chain.
+    pub fn txpool_transactions_path(&self) -> PathBuf {
+        self.0.join("transactions.rlp").into()
+    }
+
+    /// Returns the path to the blob store directory for this chain.
+    pub fn blobstore_path(&self) -> PathBuf {
+        self.0.join("blobstore").into()
+    }
+
+    /// Returns the path to the static files directory for this chain.
+    pub fn static_files_path(&self) -> PathBuf {
+        self.0.join("static_files").into()
+    }
+
     /// Returns the path to the config file for this chain.
     pub fn config_path(&self) -> PathBuf {
         self.0.join("reth.toml").into()
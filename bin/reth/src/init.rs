@@ -186,7 +186,8 @@ mod tests {
     };
     use reth_primitives::{
         Address, Chain, ForkTimestamps, Genesis, GenesisAccount, IntegerList, GOERLI,
-        GOERLI_GENESIS, MAINNET, MAINNET_GENESIS, SEPOLIA, SEPOLIA_GENESIS,
+        GOERLI_GENESIS, HOLESKY, HOLESKY_GENESIS, MAINNET, MAINNET_GENESIS, SEPOLIA,
+        SEPOLIA_GENESIS,
     };
     use std::collections::HashMap;
 
@@ -228,6 +229,15 @@ mod tests {
         assert_eq!(genesis_hash, SEPOLIA_GENESIS);
     }
 
+    #[test]
+    fn success_init_genesis_holesky() {
+        let db = create_test_rw_db();
+        let genesis_hash = init_genesis(db, HOLESKY.clone()).unwrap();
+
+        // actual, expected
+        assert_eq!(genesis_hash, HOLESKY_GENESIS);
+    }
+
     #[test]
     fn fail_init_inconsistent_db() {
         let db = create_test_rw_db();
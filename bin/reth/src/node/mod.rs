@@ -31,7 +31,7 @@ use reth_db::{database::Database, init_db, DatabaseEnv};
 use reth_discv4::DEFAULT_DISCOVERY_PORT;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
-    headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    headers::reverse_headers::ReverseHeadersDownloaderBuilder, snap::state::SnapStateDownloader,
 };
 use reth_interfaces::{
     consensus::Consensus,
@@ -39,17 +39,22 @@ use reth_interfaces::{
         bodies::{client::BodiesClient, downloader::BodyDownloader},
         either::EitherDownloader,
         headers::{client::HeadersClient, downloader::HeaderDownloader},
+        snap::downloader::StateDownloader,
     },
 };
-use reth_network::{error::NetworkError, NetworkConfig, NetworkHandle, NetworkManager};
+use reth_network::{
+    error::NetworkError, snap_protocol::SnapFetchClient, NetworkConfig, NetworkHandle,
+    NetworkManager,
+};
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
-    stage::StageId, BlockHashOrNumber, BlockNumber, ChainSpec, DisplayHardforks, Head,
-    SealedHeader, H256,
+    stage::StageId, BlockHashOrNumber, BlockNumber, ChainSpec, DisplayHardforks, ForkCondition,
+    Hardfork, Head, SealedHeader, H256,
 };
 use reth_provider::{
-    providers::BlockchainProvider, BlockHashReader, BlockReader, CanonStateSubscriptions,
-    HeaderProvider, ProviderFactory, StageCheckpointReader,
+    providers::BlockchainProvider, BlockHashReader, BlockIdReader, BlockReader,
+    CanonStateSubscriptions, HeaderProvider, ProviderFactory, SnapStateReader,
+    StageCheckpointReader, StaticFileProducer, StaticFileProvider,
 };
 use reth_prune::BatchSizes;
 use reth_revm::Factory;
@@ -59,13 +64,16 @@ use reth_stages::{
     prelude::*,
     stages::{
         AccountHashingStage, ExecutionStage, ExecutionStageThresholds, HeaderSyncMode,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TotalDifficultyStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexLogHistoryStage, IndexStorageHistoryStage, MerkleStage,
+        SenderRecoveryStage, SnapStateStage, StorageHashingStage, TotalDifficultyStage,
+        TransactionLookupStage,
     },
     MetricEventsSender, MetricsListener,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{EthTransactionValidator, TransactionPool};
+use reth_transaction_pool::{
+    blobstore::DiskFileBlobStore, EthTransactionValidator, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::{
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
@@ -97,12 +105,15 @@ pub struct NodeCommand<Ext: RethCliExt = ()> {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     /// - dev
     #[arg(
         long,
@@ -237,13 +248,18 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         let metrics_listener = MetricsListener::new(metrics_rx);
         ctx.task_executor.spawn_critical("metrics listener task", metrics_listener);
 
+        let static_files_path = data_dir.static_files_path();
+        debug!(target: "reth::cli", path = ?static_files_path, "Opening static files");
+        let static_files = StaticFileProvider::new(static_files_path)?;
+
         // configure blockchain tree
         let tree_externals = TreeExternals::new(
             db.clone(),
             Arc::clone(&consensus),
             Factory::new(self.chain.clone()),
             Arc::clone(&self.chain),
-        );
+        )
+        .with_static_files(static_files.clone());
         let tree_config = BlockchainTreeConfig::default();
         // The size of the broadcast is twice the maximum reorg depth, because at maximum reorg
         // depth at least N blocks must be sent at once.
@@ -259,16 +275,17 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         );
 
         // setup the blockchain provider
-        let factory = ProviderFactory::new(Arc::clone(&db), Arc::clone(&self.chain));
-        let blockchain_db = BlockchainProvider::new(factory, blockchain_tree.clone())?;
+        let factory = ProviderFactory::new(Arc::clone(&db), Arc::clone(&self.chain))
+            .with_static_files(static_files.clone());
+        let blockchain_db = BlockchainProvider::new(factory.clone(), blockchain_tree.clone())?;
 
+        let blob_store = DiskFileBlobStore::open(data_dir.blobstore_path())?;
         let transaction_pool = reth_transaction_pool::Pool::eth_pool(
-            EthTransactionValidator::with_additional_tasks(
-                blockchain_db.clone(),
-                Arc::clone(&self.chain),
-                ctx.task_executor.clone(),
-                1,
-            ),
+            EthTransactionValidator::builder(Arc::clone(&self.chain))
+                .set_cancun(self.chain.fork(Hardfork::Cancun) != ForkCondition::Never)
+                .with_additional_tasks(1)
+                .build(blockchain_db.clone(), ctx.task_executor.clone()),
+            blob_store,
             self.txpool.pool_config(),
         );
         info!(target: "reth::cli", "Transaction pool initialized");
@@ -278,6 +295,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             let pool = transaction_pool.clone();
             let chain_events = blockchain_db.canonical_state_stream();
             let client = blockchain_db.clone();
+            let journal_config = self.txpool.journal_config(data_dir.txpool_transactions_path());
             ctx.task_executor.spawn_critical(
                 "txpool maintenance task",
                 reth_transaction_pool::maintain::maintain_transaction_pool_future(
@@ -285,10 +303,67 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                     pool,
                     chain_events,
                     ctx.task_executor.clone(),
-                    Default::default(),
+                    reth_transaction_pool::maintain::MaintainPoolConfig {
+                        local_transactions_journal: journal_config.clone(),
+                        ..Default::default()
+                    },
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
+
+            if let Some(journal_config) = journal_config {
+                let pool = transaction_pool.clone();
+                ctx.task_executor.spawn_critical_with_graceful_shutdown_signal(
+                    "local transactions journal task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::write_journal_on_shutdown_future(
+                            pool,
+                            journal_config,
+                            shutdown,
+                        )
+                    },
+                );
+                debug!(target: "reth::cli", "Spawned local transactions journal task");
+            }
+        }
+
+        if config.static_files.enabled {
+            let producer = Arc::new(StaticFileProducer::new(
+                factory.clone(),
+                static_files.clone(),
+                config.static_files.blocks_per_file,
+            ));
+            let provider = blockchain_db.clone();
+            let mut chain_events = blockchain_db.canonical_state_stream();
+            ctx.task_executor.spawn_critical("static file producer task", async move {
+                while chain_events.next().await.is_some() {
+                    // Only blocks finalized by the consensus layer can't be reorged anymore and
+                    // can be moved out of the database.
+                    let finalized_block = match provider.finalized_block_number() {
+                        Ok(Some(finalized_block)) => finalized_block,
+                        Ok(None) => continue,
+                        Err(err) => {
+                            error!(target: "reth::cli", %err, "Failed to get the finalized block");
+                            continue
+                        }
+                    };
+                    let producer = Arc::clone(&producer);
+                    match tokio::task::spawn_blocking(move || producer.run(finalized_block)).await
+                    {
+                        Ok(Ok(0)) => {}
+                        Ok(Ok(files)) => {
+                            info!(target: "reth::cli", files, "Moved finalized blocks to static files")
+                        }
+                        Ok(Err(err)) => {
+                            error!(target: "reth::cli", %err, "Failed to write static files")
+                        }
+                        Err(err) => {
+                            error!(target: "reth::cli", %err, "Static file producer panicked")
+                        }
+                    }
+                }
+            });
+            debug!(target: "reth::cli", "Spawned static file producer task");
         }
 
         info!(target: "reth::cli", "Connecting to P2P network");
@@ -297,16 +372,16 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         debug!(target: "reth::cli", ?network_secret_path, "Loading p2p key file");
         let secret_key = get_secret_key(&network_secret_path)?;
         let default_peers_path = data_dir.known_peers_path();
-        let head = self.lookup_head(Arc::clone(&db)).expect("the head block is missing");
+        let head = self.lookup_head(factory.clone()).expect("the head block is missing");
         let network_config = self.load_network_config(
             &config,
-            Arc::clone(&db),
+            factory.clone(),
             ctx.task_executor.clone(),
             head,
             secret_key,
             default_peers_path.clone(),
         );
-        let network = self
+        let (network, snap_client) = self
             .start_network(
                 network_config,
                 &ctx.task_executor,
@@ -332,7 +407,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         let max_block = if let Some(block) = self.debug.max_block {
             Some(block)
         } else if let Some(tip) = self.debug.tip {
-            Some(self.lookup_or_fetch_tip(&db, &network_client, tip).await?)
+            Some(self.lookup_or_fetch_tip(&factory, &network_client, tip).await?)
         } else {
             None
         };
@@ -341,7 +416,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             self.pruning.prune_config(Arc::clone(&self.chain))?.or(config.prune.clone());
 
         // Configure the pipeline
-        let (mut pipeline, client) = if self.dev.dev {
+        let (mut pipeline, client, dev_handle, impersonated_senders) = if self.dev.dev {
             info!(target: "reth::cli", "Starting Reth in dev mode");
 
             let mining_mode = if let Some(interval) = self.dev.block_time {
@@ -359,12 +434,15 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             let (_, client, mut task) = AutoSealBuilder::new(
                 Arc::clone(&self.chain),
                 blockchain_db.clone(),
+                factory.clone(),
                 transaction_pool.clone(),
                 consensus_engine_tx.clone(),
                 canon_state_notification_sender,
                 mining_mode,
             )
             .build();
+            let dev_handle = task.handle();
+            let impersonated_senders = task.impersonated_senders();
 
             let mut pipeline = self
                 .build_networked_pipeline(
@@ -372,10 +450,12 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                     client.clone(),
                     Arc::clone(&consensus),
                     db.clone(),
+                    static_files.clone(),
                     &ctx.task_executor,
                     metrics_tx,
                     prune_config.clone(),
                     max_block,
+                    None,
                 )
                 .await?;
 
@@ -384,7 +464,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             debug!(target: "reth::cli", "Spawning auto mine task");
             ctx.task_executor.spawn(Box::pin(task));
 
-            (pipeline, EitherDownloader::Left(client))
+            (pipeline, EitherDownloader::Left(client), Some(dev_handle), impersonated_senders)
         } else {
             let pipeline = self
                 .build_networked_pipeline(
@@ -392,14 +472,16 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                     network_client.clone(),
                     Arc::clone(&consensus),
                     db.clone(),
+                    static_files.clone(),
                     &ctx.task_executor,
                     metrics_tx,
                     prune_config.clone(),
                     max_block,
+                    snap_client.filter(|_| self.network.snap_sync),
                 )
                 .await?;
 
-            (pipeline, EitherDownloader::Right(network_client))
+            (pipeline, EitherDownloader::Right(network_client), None, Default::default())
         };
 
         let pipeline_events = pipeline.events();
@@ -413,6 +495,9 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             // This will allow the downloader to start
             debug!(target: "reth::cli", "Continuous sync mode enabled");
             Some(genesis_hash)
+        } else if config.stages.index_log_history.enabled {
+            // Run the pipeline to the current head if the log index needs to catch up.
+            self.lookup_log_index_target(factory.clone())?
         } else {
             None
         };
@@ -426,6 +511,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                 prune_config.parts,
                 BatchSizes::default(),
             )
+            .with_static_files(static_files.clone())
         });
 
         // Configure the consensus engine
@@ -444,6 +530,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             consensus_engine_rx,
             pruner,
         )?;
+        let bad_blocks = beacon_consensus_engine.bad_blocks();
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -488,6 +575,9 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                 blockchain_tree,
                 engine_api,
                 jwt_secret,
+                bad_blocks,
+                impersonated_senders,
+                dev_handle,
                 &mut self.ext,
             )
             .await?;
@@ -521,10 +611,12 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         client: Client,
         consensus: Arc<dyn Consensus>,
         db: DB,
+        static_files: StaticFileProvider,
         task_executor: &TaskExecutor,
         metrics_tx: MetricEventsSender,
         prune_config: Option<PruneConfig>,
         max_block: Option<BlockNumber>,
+        snap_client: Option<SnapFetchClient>,
     ) -> eyre::Result<Pipeline<DB>>
     where
         DB: Database + Unpin + Clone + 'static,
@@ -539,9 +631,14 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             .build(client, Arc::clone(&consensus), db.clone())
             .into_task_with(task_executor);
 
+        // the state is downloaded at the sync target, so the initial root is replaced by the stage
+        let snap_downloader =
+            snap_client.map(|client| SnapStateDownloader::new(Arc::new(client), H256::zero()));
+
         let pipeline = self
             .build_pipeline(
                 db,
+                static_files,
                 config,
                 header_downloader,
                 body_downloader,
@@ -550,6 +647,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                 self.debug.continuous,
                 metrics_tx,
                 prune_config,
+                snap_downloader,
             )
             .await?;
 
@@ -585,23 +683,36 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
 
     /// Spawns the configured network and associated tasks and returns the [NetworkHandle] connected
     /// to that network.
+    ///
+    /// If `snap/1` is enabled, this also returns the [SnapFetchClient] for `snap` requests.
     async fn start_network<C, Pool>(
         &self,
         config: NetworkConfig<C>,
         task_executor: &TaskExecutor,
         pool: Pool,
         default_peers_path: PathBuf,
-    ) -> Result<NetworkHandle, NetworkError>
+    ) -> Result<(NetworkHandle, Option<SnapFetchClient>), NetworkError>
     where
-        C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+        C: BlockReader + HeaderProvider + SnapStateReader + Clone + Unpin + 'static,
         Pool: TransactionPool + Unpin + 'static,
     {
         let client = config.client.clone();
-        let (handle, network, txpool, eth) = NetworkManager::builder(config)
+        let serving_capacity = config.sessions_config.serving_capacity;
+        let mut builder = NetworkManager::builder(config)
             .await?
             .transactions(pool)
-            .request_handler(client)
-            .split_with_handle();
+            .request_handler(client.clone())
+            .serving_capacity(serving_capacity);
+
+        let mut snap_client = None;
+        if self.network.snap {
+            let (network_builder, snap, fetch_client) = builder.snap_request_handler(client);
+            builder = network_builder;
+            snap_client = Some(fetch_client);
+            task_executor.spawn_critical("p2p snap request handler", snap);
+        }
+
+        let (handle, network, txpool, eth) = builder.split_with_handle();
 
         task_executor.spawn_critical("p2p txpool", txpool);
         task_executor.spawn_critical("p2p eth request handler", eth);
@@ -611,11 +722,33 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             run_network_until_shutdown(shutdown, network, known_peers_file)
         });
 
-        Ok(handle)
+        Ok((handle, snap_client))
     }
 
-    fn lookup_head(&self, db: Arc<DatabaseEnv>) -> Result<Head, reth_interfaces::Error> {
-        let factory = ProviderFactory::new(db, self.chain.clone());
+    /// Returns the hash of the current head if the log index stage is behind it.
+    fn lookup_log_index_target(
+        &self,
+        factory: ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> Result<Option<H256>, reth_interfaces::Error> {
+        let provider = factory.provider()?;
+
+        let head = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        let log_index = provider
+            .get_stage_checkpoint(StageId::IndexLogHistory)?
+            .unwrap_or_default()
+            .block_number;
+        if log_index >= head {
+            return Ok(None)
+        }
+
+        debug!(target: "reth::cli", head, log_index, "Log index is behind the head");
+        provider.block_hash(head)
+    }
+
+    fn lookup_head(
+        &self,
+        factory: ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> Result<Head, reth_interfaces::Error> {
         let provider = factory.provider()?;
 
         let head = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
@@ -647,7 +780,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
     /// NOTE: The download is attempted with infinite retries.
     async fn lookup_or_fetch_tip<DB, Client>(
         &self,
-        db: &DB,
+        factory: &ProviderFactory<DB>,
         client: Client,
         tip: H256,
     ) -> Result<u64, reth_interfaces::Error>
@@ -655,7 +788,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         DB: Database,
         Client: HeadersClient,
     {
-        Ok(self.fetch_tip(db, client, BlockHashOrNumber::Hash(tip)).await?.number)
+        Ok(self.fetch_tip(factory, client, BlockHashOrNumber::Hash(tip)).await?.number)
     }
 
     /// Attempt to look up the block with the given number and return the header.
@@ -663,7 +796,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
     /// NOTE: The download is attempted with infinite retries.
     async fn fetch_tip<DB, Client>(
         &self,
-        db: &DB,
+        factory: &ProviderFactory<DB>,
         client: Client,
         tip: BlockHashOrNumber,
     ) -> Result<SealedHeader, reth_interfaces::Error>
@@ -671,7 +804,6 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         DB: Database,
         Client: HeadersClient,
     {
-        let factory = ProviderFactory::new(db, self.chain.clone());
         let provider = factory.provider()?;
 
         let header = provider.header_by_hash_or_number(tip)?;
@@ -699,7 +831,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
     fn load_network_config(
         &self,
         config: &Config,
-        db: Arc<DatabaseEnv>,
+        factory: ProviderFactory<Arc<DatabaseEnv>>,
         executor: TaskExecutor,
         head: Head,
         secret_key: SecretKey,
@@ -717,13 +849,14 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                 Ipv4Addr::UNSPECIFIED,
                 self.network.discovery.port.unwrap_or(DEFAULT_DISCOVERY_PORT),
             )))
-            .build(ProviderFactory::new(db, self.chain.clone()))
+            .build(factory)
     }
 
     #[allow(clippy::too_many_arguments)]
-    async fn build_pipeline<DB, H, B>(
+    async fn build_pipeline<DB, H, B, S>(
         &self,
         db: DB,
+        static_files: StaticFileProvider,
         config: &Config,
         header_downloader: H,
         body_downloader: B,
@@ -732,11 +865,13 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         continuous: bool,
         metrics_tx: MetricEventsSender,
         prune_config: Option<PruneConfig>,
+        snap_downloader: Option<S>,
     ) -> eyre::Result<Pipeline<DB>>
     where
         DB: Database + Clone + 'static,
         H: HeaderDownloader + 'static,
         B: BodyDownloader + 'static,
+        S: StateDownloader + 'static,
     {
         let stage_config = &config.stages;
 
@@ -768,57 +903,79 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
 
         let header_mode =
             if continuous { HeaderSyncMode::Continuous } else { HeaderSyncMode::Tip(tip_rx) };
+        let mut stages = DefaultStages::new(
+            header_mode,
+            Arc::clone(&consensus),
+            header_downloader,
+            body_downloader,
+            factory.clone(),
+        )
+        .set(
+            TotalDifficultyStage::new(consensus)
+                .with_commit_threshold(stage_config.total_difficulty.commit_threshold),
+        )
+        .set(SenderRecoveryStage {
+            commit_threshold: stage_config.sender_recovery.commit_threshold,
+        })
+        .set(
+            ExecutionStage::new(
+                factory,
+                ExecutionStageThresholds {
+                    max_blocks: stage_config.execution.max_blocks,
+                    max_changes: stage_config.execution.max_changes,
+                },
+                stage_config
+                    .merkle
+                    .clean_threshold
+                    .max(stage_config.account_hashing.clean_threshold)
+                    .max(stage_config.storage_hashing.clean_threshold),
+                prune_config.map(|prune| prune.parts).unwrap_or_default(),
+            )
+            .with_metrics_tx(metrics_tx.clone()),
+        )
+        .set(AccountHashingStage::new(
+            stage_config.account_hashing.clean_threshold,
+            stage_config.account_hashing.commit_threshold,
+        ))
+        .set(StorageHashingStage::new(
+            stage_config.storage_hashing.clean_threshold,
+            stage_config.storage_hashing.commit_threshold,
+        ))
+        .set(
+            MerkleStage::new_execution(stage_config.merkle.clean_threshold)
+                .with_parallel_storage_roots(stage_config.merkle.parallel_storage_roots),
+        )
+        .set(TransactionLookupStage::new(stage_config.transaction_lookup.commit_threshold))
+        .set(IndexAccountHistoryStage::new(stage_config.index_account_history.commit_threshold))
+        .set(IndexStorageHistoryStage::new(stage_config.index_storage_history.commit_threshold))
+        .add_after(
+            IndexLogHistoryStage::new(stage_config.index_log_history.commit_threshold),
+            StageId::IndexAccountHistory,
+        )
+        .disable_if(StageId::IndexLogHistory, || !stage_config.index_log_history.enabled);
+
+        if let Some(snap_downloader) = snap_downloader {
+            // the downloaded state replaces the state built by executing the blocks
+            stages = stages.add_after(SnapStateStage::new(snap_downloader), StageId::Bodies);
+            for stage_id in [
+                StageId::Execution,
+                StageId::MerkleUnwind,
+                StageId::AccountHashing,
+                StageId::StorageHashing,
+                StageId::MerkleExecute,
+                StageId::IndexAccountHistory,
+                StageId::IndexStorageHistory,
+                StageId::IndexLogHistory,
+            ] {
+                stages = stages.disable(stage_id);
+            }
+        }
+
         let pipeline = builder
+            .with_static_files(static_files)
             .with_tip_sender(tip_tx)
-            .with_metrics_tx(metrics_tx.clone())
-            .add_stages(
-                DefaultStages::new(
-                    header_mode,
-                    Arc::clone(&consensus),
-                    header_downloader,
-                    body_downloader,
-                    factory.clone(),
-                )
-                .set(
-                    TotalDifficultyStage::new(consensus)
-                        .with_commit_threshold(stage_config.total_difficulty.commit_threshold),
-                )
-                .set(SenderRecoveryStage {
-                    commit_threshold: stage_config.sender_recovery.commit_threshold,
-                })
-                .set(
-                    ExecutionStage::new(
-                        factory,
-                        ExecutionStageThresholds {
-                            max_blocks: stage_config.execution.max_blocks,
-                            max_changes: stage_config.execution.max_changes,
-                        },
-                        stage_config
-                            .merkle
-                            .clean_threshold
-                            .max(stage_config.account_hashing.clean_threshold)
-                            .max(stage_config.storage_hashing.clean_threshold),
-                        prune_config.map(|prune| prune.parts).unwrap_or_default(),
-                    )
-                    .with_metrics_tx(metrics_tx),
-                )
-                .set(AccountHashingStage::new(
-                    stage_config.account_hashing.clean_threshold,
-                    stage_config.account_hashing.commit_threshold,
-                ))
-                .set(StorageHashingStage::new(
-                    stage_config.storage_hashing.clean_threshold,
-                    stage_config.storage_hashing.commit_threshold,
-                ))
-                .set(MerkleStage::new_execution(stage_config.merkle.clean_threshold))
-                .set(TransactionLookupStage::new(stage_config.transaction_lookup.commit_threshold))
-                .set(IndexAccountHistoryStage::new(
-                    stage_config.index_account_history.commit_threshold,
-                ))
-                .set(IndexStorageHistoryStage::new(
-                    stage_config.index_storage_history.commit_threshold,
-                )),
-            )
+            .with_metrics_tx(metrics_tx)
+            .add_stages(stages)
             .build(db, self.chain.clone());
 
         Ok(pipeline)
@@ -842,7 +999,7 @@ async fn run_network_until_shutdown<C>(
     }
 
     if let Some(file_path) = persistent_peers_file {
-        let known_peers = network.all_peers().collect::<Vec<_>>();
+        let known_peers = network.persisted_peers();
         if let Ok(known_peers) = serde_json::to_string_pretty(&known_peers) {
             trace!(target : "reth::cli", peers_file =?file_path, num_peers=%known_peers.len(), "Saving current peers");
             let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
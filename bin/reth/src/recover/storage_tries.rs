@@ -11,7 +11,9 @@ use reth_db::{
     transaction::DbTx,
 };
 use reth_primitives::ChainSpec;
-use reth_provider::{BlockNumReader, HeaderProvider, ProviderError, ProviderFactory};
+use reth_provider::{
+    BlockNumReader, HeaderProvider, ProviderError, ProviderFactory, StaticFileProvider,
+};
 use reth_trie::StateRoot;
 use std::{fs, sync::Arc};
 use tracing::*;
@@ -31,12 +33,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -58,7 +63,8 @@ impl Command {
         debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
         init_genesis(db.clone(), self.chain.clone())?;
 
-        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
+        let factory = ProviderFactory::new(&db, self.chain.clone()).with_static_files(static_files);
         let mut provider = factory.provider_rw()?;
         let best_block = provider.best_block_number()?;
         let best_header = provider
@@ -26,12 +26,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -147,6 +150,12 @@ impl Command {
                         Default::default(),
                     )?;
                 }
+                StageEnum::LogHistory => {
+                    tx.clear::<tables::LogAddressHistory>()?;
+                    tx.clear::<tables::LogTopicHistory>()?;
+                    // Removing the checkpoint marks the optional index as not maintained.
+                    tx.delete::<tables::SyncStage>(StageId::IndexLogHistory.to_string(), None)?;
+                }
                 StageEnum::TotalDifficulty => {
                     tx.clear::<tables::HeaderTD>()?;
                     tx.put::<tables::SyncStage>(
@@ -33,7 +33,7 @@ async fn unwind_and_copy<DB: Database>(
     tip_block_number: u64,
     output_db: &DatabaseEnv,
 ) -> eyre::Result<()> {
-    let factory = ProviderFactory::new(db_tool.db, db_tool.chain.clone());
+    let factory = db_tool.provider_factory();
     let provider = factory.provider_rw()?;
 
     let mut exec_stage = StorageHashingStage::default();
@@ -48,7 +48,7 @@ async fn unwind_and_copy<DB: Database>(
     output_db: &DatabaseEnv,
 ) -> eyre::Result<()> {
     let (from, to) = range;
-    let factory = ProviderFactory::new(db_tool.db, db_tool.chain.clone());
+    let factory = db_tool.provider_factory();
     let provider = factory.provider_rw()?;
 
     let unwind = UnwindInput {
@@ -9,6 +9,7 @@ use reth_db::{
     transaction::DbTx, DatabaseEnv,
 };
 use reth_primitives::ChainSpec;
+use reth_provider::StaticFileProvider;
 use std::{path::PathBuf, sync::Arc};
 use tracing::info;
 
@@ -40,12 +41,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -104,7 +108,8 @@ impl Command {
         let db = Arc::new(init_db(db_path, self.db.log_level)?);
         info!(target: "reth::cli", "Database opened");
 
-        let tool = DbTool::new(&db, self.chain.clone())?;
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
+        let tool = DbTool::new(&db, self.chain.clone())?.with_static_files(static_files);
 
         match &self.command {
             Stages::Execution(StageCommand { output_db, from, to, dry_run, .. }) => {
@@ -13,12 +13,12 @@ use reth_config::Config;
 use reth_db::init_db;
 use reth_downloaders::bodies::bodies::BodiesDownloaderBuilder;
 use reth_primitives::ChainSpec;
-use reth_provider::{ProviderFactory, StageCheckpointReader};
+use reth_provider::{ProviderFactory, StageCheckpointReader, StaticFileProvider};
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, ExecutionStageThresholds,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexLogHistoryStage, IndexStorageHistoryStage, MerkleStage,
+        SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, PipelineError, Stage, UnwindInput,
 };
@@ -44,12 +44,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
     long,
     value_name = "CHAIN_OR_PATH",
@@ -125,7 +128,9 @@ impl Command {
         let db = Arc::new(init_db(db_path, self.db.log_level)?);
         info!(target: "reth::cli", "Database opened");
 
-        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
+        let factory =
+            ProviderFactory::new(&db, self.chain.clone()).with_static_files(static_files.clone());
         let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
         if let Some(listen_addr) = self.metrics {
@@ -170,7 +175,10 @@ impl Command {
                             p2p_secret_key,
                             default_peers_path,
                         )
-                        .build(Arc::new(ProviderFactory::new(db.clone(), self.chain.clone())))
+                        .build(Arc::new(
+                            ProviderFactory::new(db.clone(), self.chain.clone())
+                                .with_static_files(static_files.clone()),
+                        ))
                         .start_network()
                         .await?;
                     let fetch_client = Arc::new(network.fetch_client().await?);
@@ -215,12 +223,17 @@ impl Command {
                 StageEnum::StorageHashing => {
                     (Box::new(StorageHashingStage::new(1, batch_size)), None)
                 }
-                StageEnum::Merkle => (
-                    Box::new(MerkleStage::default_execution()),
-                    Some(Box::new(MerkleStage::default_unwind())),
-                ),
+                StageEnum::Merkle => {
+                    (
+                        Box::new(MerkleStage::default_execution().with_parallel_storage_roots(
+                            config.stages.merkle.parallel_storage_roots,
+                        )),
+                        Some(Box::new(MerkleStage::default_unwind())),
+                    )
+                }
                 StageEnum::AccountHistory => (Box::<IndexAccountHistoryStage>::default(), None),
                 StageEnum::StorageHistory => (Box::<IndexStorageHistoryStage>::default(), None),
+                StageEnum::LogHistory => (Box::<IndexLogHistoryStage>::default(), None),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -7,7 +7,7 @@ use crate::{
 use clap::{Parser, Subcommand};
 use reth_db::{cursor::DbCursorRO, database::Database, open_db, tables, transaction::DbTx};
 use reth_primitives::{BlockHashOrNumber, ChainSpec};
-use reth_provider::{BlockExecutionWriter, ProviderFactory};
+use reth_provider::{BlockExecutionWriter, ProviderFactory, StaticFileProvider};
 use std::{ops::RangeInclusive, sync::Arc};
 
 /// `reth stage unwind` command
@@ -25,12 +25,15 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain, the name of a `<name>.json` chain specification
+    /// file in the `chains` directory of the reth config directory or the path to a chain
+    /// specification file.
     ///
     /// Built-in chains:
     /// - mainnet
     /// - goerli
     /// - sepolia
+    /// - holesky
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
@@ -66,7 +69,8 @@ impl Command {
             eyre::bail!("Cannot unwind genesis block")
         }
 
-        let factory = ProviderFactory::new(&db, self.chain.clone());
+        let static_files = StaticFileProvider::new(data_dir.static_files_path())?;
+        let factory = ProviderFactory::new(&db, self.chain.clone()).with_static_files(static_files);
         let provider = factory.provider_rw()?;
 
         let blocks_and_execution = provider
@@ -18,6 +18,7 @@ use reth_interfaces::p2p::{
 use reth_primitives::{
     fs, BlockHashOrNumber, ChainSpec, HeadersDirection, SealedBlock, SealedHeader,
 };
+use reth_provider::{ProviderFactory, StaticFileProvider};
 use std::{
     env::VarError,
     path::{Path, PathBuf},
@@ -96,12 +97,29 @@ where
 pub struct DbTool<'a, DB: Database> {
     pub(crate) db: &'a DB,
     pub(crate) chain: Arc<ChainSpec>,
+    pub(crate) static_files: Option<StaticFileProvider>,
 }
 
 impl<'a, DB: Database> DbTool<'a, DB> {
     /// Takes a DB where the tables have already been created.
     pub(crate) fn new(db: &'a DB, chain: Arc<ChainSpec>) -> eyre::Result<Self> {
-        Ok(Self { db, chain })
+        Ok(Self { db, chain, static_files: None })
+    }
+
+    /// Reads the headers, transactions and receipts that were moved out of the database from the
+    /// given static files.
+    pub(crate) fn with_static_files(mut self, static_files: StaticFileProvider) -> Self {
+        self.static_files = Some(static_files);
+        self
+    }
+
+    /// Returns a provider factory over the database and the static files, if any.
+    pub(crate) fn provider_factory(&self) -> ProviderFactory<&'a DB> {
+        let factory = ProviderFactory::new(self.db, self.chain.clone());
+        match self.static_files.clone() {
+            Some(static_files) => factory.with_static_files(static_files),
+            None => factory,
+        }
     }
 
     /// Grabs the contents of the table within a certain index range and places the
@@ -1043,7 +1043,8 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         let provider = DatabaseProvider::new_rw(
             self.externals.db.tx_mut()?,
             self.externals.chain_spec.clone(),
-        );
+        )
+        .with_static_files(self.externals.static_files.clone());
 
         let (blocks, state) = chain.into_inner();
 
@@ -1084,7 +1085,8 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         let provider = DatabaseProvider::new_rw(
             self.externals.db.tx_mut()?,
             self.externals.chain_spec.clone(),
-        );
+        )
+        .with_static_files(self.externals.static_files.clone());
 
         let tip = provider.last_block_number()?;
         let revert_range = (revert_until + 1)..=tip;
@@ -15,7 +15,6 @@ use reth_primitives::{
 };
 use reth_provider::{
     providers::PostStateProvider, BlockExecutor, Chain, ExecutorFactory, PostStateDataProvider,
-    StateRootProvider,
 };
 use std::{
     collections::BTreeMap,
@@ -201,15 +200,23 @@ impl AppendableChain {
         let canonical_fork = post_state_data_provider.canonical_fork();
         let state_provider = db.history_by_block_number(canonical_fork.number)?;
 
+        // The state of the chain on top of the canonical head, required for the state root check.
+        let mut chain_state =
+            block_kind.extends_canonical_head().then(|| post_state_data_provider.state().clone());
+
         let provider = PostStateProvider::new(state_provider, post_state_data_provider);
 
         let mut executor = externals.executor_factory.with_sp(&provider);
         let post_state = executor.execute_and_verify_receipt(&block, U256::MAX, Some(senders))?;
 
         // check state root if the block extends the canonical chain.
-        if block_kind.extends_canonical_head() {
-            // check state root
-            let state_root = provider.state_root(post_state.clone())?;
+        if let Some(chain_state) = chain_state.as_mut() {
+            // check state root, the canonical fork is the canonical head so the storage roots can
+            // be computed in parallel on top of the latest database state.
+            chain_state.extend(post_state.clone());
+            let state_root = chain_state
+                .state_root_parallel(&externals.db)
+                .map_err(|err| Error::Database(err.into()))?;
             if block.state_root != state_root {
                 return Err(ConsensusError::BodyStateRootDiff {
                     got: state_root,
@@ -2,7 +2,7 @@
 
 use reth_db::database::Database;
 use reth_primitives::ChainSpec;
-use reth_provider::ProviderFactory;
+use reth_provider::{ProviderFactory, StaticFileProvider};
 use std::sync::Arc;
 
 /// A container for external components.
@@ -14,6 +14,7 @@ use std::sync::Arc;
 /// - A handle to the consensus engine
 /// - The executor factory to execute blocks with
 /// - The chain spec
+/// - The static files of frozen chain data, if any
 #[derive(Debug)]
 pub struct TreeExternals<DB, C, EF> {
     /// The database, used to commit the canonical chain, or unwind it.
@@ -24,18 +25,30 @@ pub struct TreeExternals<DB, C, EF> {
     pub(crate) executor_factory: EF,
     /// The chain spec.
     pub(crate) chain_spec: Arc<ChainSpec>,
+    /// The static files of frozen headers, transactions and receipts.
+    pub(crate) static_files: Option<StaticFileProvider>,
 }
 
 impl<DB, C, EF> TreeExternals<DB, C, EF> {
     /// Create new tree externals.
     pub fn new(db: DB, consensus: C, executor_factory: EF, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { db, consensus, executor_factory, chain_spec }
+        Self { db, consensus, executor_factory, chain_spec, static_files: None }
+    }
+
+    /// Reads the chain data that was moved out of the database from the given static files.
+    pub fn with_static_files(mut self, static_files: StaticFileProvider) -> Self {
+        self.static_files = Some(static_files);
+        self
     }
 }
 
 impl<DB: Database, C, EF> TreeExternals<DB, C, EF> {
     /// Return shareable database helper structure.
     pub fn database(&self) -> ProviderFactory<&DB> {
-        ProviderFactory::new(&self.db, self.chain_spec.clone())
+        let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
+        match &self.static_files {
+            Some(static_files) => factory.with_static_files(static_files.clone()),
+            None => factory,
+        }
     }
 }
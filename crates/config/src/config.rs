@@ -24,6 +24,8 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for static files.
+    pub static_files: StaticFilesConfig,
 }
 
 impl Config {
@@ -45,6 +47,7 @@ impl Config {
         NetworkConfigBuilder::new(secret_key)
             .sessions_config(self.sessions.clone())
             .peer_config(peer_config)
+            .nat(Some(nat_resolution_method))
             .discovery(discv4)
     }
 }
@@ -75,6 +78,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Log History stage configuration.
+    pub index_log_history: IndexLogHistoryConfig,
 }
 
 /// Header stage configuration.
@@ -242,11 +247,14 @@ pub struct MerkleConfig {
     /// The threshold (in number of blocks) for switching from incremental trie building of changes
     /// to whole rebuild.
     pub clean_threshold: u64,
+    /// Whether to compute the storage roots of changed accounts in parallel when updating the
+    /// trie incrementally.
+    pub parallel_storage_roots: bool,
 }
 
 impl Default for MerkleConfig {
     fn default() -> Self {
-        Self { clean_threshold: 50_000 }
+        Self { clean_threshold: 50_000, parallel_storage_roots: false }
     }
 }
 
@@ -278,6 +286,22 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Index Log History stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct IndexLogHistoryConfig {
+    /// Whether the log address and topic index is built. Disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogHistoryConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
@@ -294,6 +318,23 @@ impl Default for PruneConfig {
     }
 }
 
+/// Static files configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct StaticFilesConfig {
+    /// Whether finalized headers, transactions and receipts are moved from the database into
+    /// static files. Disabled by default.
+    pub enabled: bool,
+    /// The number of blocks covered by a single static file.
+    pub blocks_per_file: u64,
+}
+
+impl Default for StaticFilesConfig {
+    fn default() -> Self {
+        Self { enabled: false, blocks_per_file: 500_000 }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Config;
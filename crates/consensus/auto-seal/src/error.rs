@@ -0,0 +1,56 @@
+use jsonrpsee_types::error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE};
+use reth_interfaces::executor::BlockExecutionError;
+use reth_primitives::Address;
+
+/// Errors returned by the [MiningTask](crate::MiningTask) for requests sent via the
+/// [AutoSealHandle](crate::AutoSealHandle).
+#[derive(Debug, thiserror::Error)]
+pub enum AutoSealError {
+    /// The mining task is no longer running.
+    #[error("mining task is unavailable")]
+    TaskUnavailable,
+    /// The requested timestamp is not after the timestamp of the latest block.
+    #[error("timestamp {timestamp} is not after the latest block timestamp {latest}")]
+    TimestampTooLow {
+        /// The requested timestamp.
+        timestamp: u64,
+        /// The timestamp of the latest block.
+        latest: u64,
+    },
+    /// The sender of an unsigned transaction is not impersonated.
+    #[error("account {0:?} is not impersonated")]
+    NotImpersonated(Address),
+    /// The unsigned transaction request is missing its sender.
+    #[error("transaction request is missing the sender")]
+    MissingSender,
+    /// The transaction request sets conflicting fee fields.
+    #[error("both gasPrice and (maxFeePerGas or maxPriorityFeePerGas) specified")]
+    ConflictingFeeFields,
+    /// Failed to execute the new block.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// Failed to read from or write to the database.
+    #[error(transparent)]
+    Internal(#[from] reth_interfaces::Error),
+}
+
+impl From<AutoSealError> for jsonrpsee_types::error::ErrorObject<'static> {
+    fn from(error: AutoSealError) -> Self {
+        let code = match error {
+            AutoSealError::TimestampTooLow { .. } |
+            AutoSealError::NotImpersonated(_) |
+            AutoSealError::MissingSender |
+            AutoSealError::ConflictingFeeFields => INVALID_PARAMS_CODE,
+            AutoSealError::TaskUnavailable |
+            AutoSealError::Execution(_) |
+            AutoSealError::Internal(_) => INTERNAL_ERROR_CODE,
+        };
+        jsonrpsee_types::error::ErrorObject::owned(code, error.to_string(), None::<()>)
+    }
+}
+
+impl From<AutoSealError> for jsonrpsee_core::error::Error {
+    fn from(error: AutoSealError) -> Self {
+        jsonrpsee_core::error::Error::Call(error.into())
+    }
+}
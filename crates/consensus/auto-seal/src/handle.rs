@@ -0,0 +1,123 @@
+//! Handle for controlling the dev chain at runtime.
+
+use crate::{message::AutoSealMessage, AutoSealError, StateChange};
+use reth_primitives::{Address, Bytes, H256, U256};
+use reth_rpc_types::TransactionRequest;
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+
+/// A cloneable handle to the [MiningTask](crate::MiningTask).
+///
+/// Requests are processed in order, between the blocks the task mines on its own.
+#[derive(Debug, Clone)]
+pub struct AutoSealHandle {
+    to_task: UnboundedSender<AutoSealMessage>,
+}
+
+// === impl AutoSealHandle ===
+
+impl AutoSealHandle {
+    /// Creates a new handle that sends requests to the mining task.
+    pub(crate) fn new(to_task: UnboundedSender<AutoSealMessage>) -> Self {
+        Self { to_task }
+    }
+
+    /// Mines `blocks` blocks, including the best pool transactions in the first one.
+    ///
+    /// If a timestamp is given, it is used for the first block. Mining zero blocks is a no-op.
+    pub async fn mine(&self, blocks: u64, timestamp: Option<u64>) -> Result<(), AutoSealError> {
+        self.request(|tx| AutoSealMessage::Mine { blocks, timestamp, interval: None, tx }).await?
+    }
+
+    /// Mines `blocks` blocks like [Self::mine], with the given number of seconds between the
+    /// timestamps of consecutive blocks.
+    pub async fn mine_with_interval(
+        &self,
+        blocks: u64,
+        interval: Option<u64>,
+    ) -> Result<(), AutoSealError> {
+        self.request(|tx| AutoSealMessage::Mine { blocks, timestamp: None, interval, tx }).await?
+    }
+
+    /// Sets the timestamp of the next block, later blocks continue from it.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), AutoSealError> {
+        self.request(|tx| AutoSealMessage::SetNextBlockTimestamp { timestamp, tx }).await?
+    }
+
+    /// Moves the time of the next blocks forward and returns the total time offset in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> Result<i64, AutoSealError> {
+        self.request(|tx| AutoSealMessage::IncreaseTime { seconds, tx }).await
+    }
+
+    /// Queues the state change, the next mined block applies it before its transactions.
+    pub async fn set_state(&self, change: StateChange) -> Result<(), AutoSealError> {
+        self.request(|tx| AutoSealMessage::SetState { change, tx }).await?
+    }
+
+    /// Sets the balance of the account.
+    pub async fn set_balance(&self, address: Address, balance: U256) -> Result<(), AutoSealError> {
+        self.set_state(StateChange::Balance { address, balance }).await
+    }
+
+    /// Sets the nonce of the account.
+    pub async fn set_nonce(&self, address: Address, nonce: u64) -> Result<(), AutoSealError> {
+        self.set_state(StateChange::Nonce { address, nonce }).await
+    }
+
+    /// Sets the code of the account.
+    pub async fn set_code(&self, address: Address, code: Bytes) -> Result<(), AutoSealError> {
+        self.set_state(StateChange::Code { address, code }).await
+    }
+
+    /// Sets a storage slot of the account.
+    pub async fn set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: U256,
+    ) -> Result<(), AutoSealError> {
+        self.set_state(StateChange::Storage { address, slot, value }).await
+    }
+
+    /// Allows mining unsigned transactions sent from the account.
+    pub async fn impersonate_account(&self, address: Address) -> Result<(), AutoSealError> {
+        self.request(|tx| AutoSealMessage::Impersonate { address, enabled: true, tx }).await
+    }
+
+    /// Stops impersonating the account.
+    pub async fn stop_impersonating_account(&self, address: Address) -> Result<(), AutoSealError> {
+        self.request(|tx| AutoSealMessage::Impersonate { address, enabled: false, tx }).await
+    }
+
+    /// Mines the unsigned transaction, whose sender must be impersonated, in a new block.
+    ///
+    /// Missing fields of the request are filled in: the nonce of the sender, the gas limit of the
+    /// block and the base fee of the block as gas price.
+    pub async fn send_unsigned_transaction(
+        &self,
+        request: TransactionRequest,
+    ) -> Result<H256, AutoSealError> {
+        self.request(|tx| AutoSealMessage::SendUnsignedTransaction { request, tx }).await?
+    }
+
+    /// Takes a snapshot of the current chain and returns its id.
+    pub async fn snapshot(&self) -> Result<U256, AutoSealError> {
+        self.request(|tx| AutoSealMessage::Snapshot { tx }).await
+    }
+
+    /// Reverts the chain to the snapshot by unwinding all blocks mined after it.
+    ///
+    /// Returns `false` if there is no snapshot with this id.
+    pub async fn revert(&self, id: U256) -> Result<bool, AutoSealError> {
+        self.request(|tx| AutoSealMessage::Revert { id, tx }).await?
+    }
+
+    /// Sends the message to the task and waits for the response.
+    async fn request<T>(
+        &self,
+        message: impl FnOnce(oneshot::Sender<T>) -> AutoSealMessage,
+    ) -> Result<T, AutoSealError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_task.send(message(tx)).map_err(|_| AutoSealError::TaskUnavailable)?;
+        rx.await.map_err(|_| AutoSealError::TaskUnavailable)
+    }
+}
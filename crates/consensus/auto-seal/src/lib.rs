@@ -23,31 +23,47 @@
 use reth_beacon_consensus::BeaconEngineMessage;
 use reth_interfaces::{
     consensus::{Consensus, ConsensusError},
-    executor::{BlockExecutionError, BlockValidationError},
+    executor::BlockExecutionError,
 };
 use reth_primitives::{
     constants::{EMPTY_RECEIPTS, EMPTY_TRANSACTIONS, ETHEREUM_BLOCK_GAS_LIMIT},
-    proofs, Address, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, Bloom, ChainSpec,
-    Header, ReceiptWithBloom, SealedBlock, SealedHeader, TransactionSigned, EMPTY_OMMER_ROOT, H256,
-    U256,
+    keccak256, proofs, Address, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, Bloom,
+    ChainSpec, Hardfork, Header, ReceiptWithBloom, SealedBlock, SealedHeader, TransactionSigned,
+    EMPTY_OMMER_ROOT, H256, KECCAK_EMPTY, U256,
+};
+use reth_provider::{
+    BlockReaderIdExt, CanonStateNotificationSender, PostState, ProviderFactory, StateProvider,
+};
+use reth_revm::{
+    executor::{commit_state_changes, Executor},
+    revm::{
+        primitives::{hash_map, Account as RevmAccount, Bytecode as RevmBytecode, StorageSlot},
+        Database,
+    },
 };
-use reth_provider::{BlockReaderIdExt, CanonStateNotificationSender, PostState, StateProvider};
-use reth_revm::executor::Executor;
 use reth_transaction_pool::TransactionPool;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockReadGuard, RwLockWriteGuard};
-use tracing::{trace, warn};
+use tracing::trace;
 
 mod client;
+mod error;
+mod handle;
+mod message;
 mod mode;
+mod rpc;
 mod task;
 
 pub use crate::client::AutoSealClient;
+pub use error::AutoSealError;
+pub use handle::AutoSealHandle;
+pub use message::StateChange;
 pub use mode::{FixedBlockTimeMiner, MiningMode, ReadyTransactionMiner};
+pub use rpc::DevApi;
 pub use task::MiningTask;
 
 /// A consensus implementation intended for local development and testing purposes.
@@ -92,8 +108,9 @@ impl Consensus for AutoSealConsensus {
 }
 
 /// Builder type for configuring the setup
-pub struct AutoSealBuilder<Client, Pool> {
+pub struct AutoSealBuilder<DB, Client, Pool> {
     client: Client,
+    database: ProviderFactory<DB>,
     consensus: AutoSealConsensus,
     pool: Pool,
     mode: MiningMode,
@@ -104,14 +121,17 @@ pub struct AutoSealBuilder<Client, Pool> {
 
 // === impl AutoSealBuilder ===
 
-impl<Client, Pool: TransactionPool> AutoSealBuilder<Client, Pool>
+impl<DB, Client, Pool: TransactionPool> AutoSealBuilder<DB, Client, Pool>
 where
     Client: BlockReaderIdExt,
 {
     /// Creates a new builder instance to configure all parts.
+    ///
+    /// Mined blocks are written to the `database` directly.
     pub fn new(
         chain_spec: Arc<ChainSpec>,
         client: Client,
+        database: ProviderFactory<DB>,
         pool: Pool,
         to_engine: UnboundedSender<BeaconEngineMessage>,
         canon_state_notification: CanonStateNotificationSender,
@@ -126,6 +146,7 @@ where
         Self {
             storage: Storage::new(latest_header),
             client,
+            database,
             consensus: AutoSealConsensus::new(chain_spec),
             pool,
             mode,
@@ -142,9 +163,17 @@ where
 
     /// Consumes the type and returns all components
     #[track_caller]
-    pub fn build(self) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool>) {
-        let Self { client, consensus, pool, mode, storage, to_engine, canon_state_notification } =
-            self;
+    pub fn build(self) -> (AutoSealConsensus, AutoSealClient, MiningTask<DB, Client, Pool>) {
+        let Self {
+            client,
+            database,
+            consensus,
+            pool,
+            mode,
+            storage,
+            to_engine,
+            canon_state_notification,
+        } = self;
         let auto_client = AutoSealClient::new(storage.clone());
         let task = MiningTask::new(
             Arc::clone(&consensus.chain_spec),
@@ -153,6 +182,7 @@ where
             canon_state_notification,
             storage,
             client,
+            database,
             pool,
         );
         (consensus, auto_client, task)
@@ -176,7 +206,8 @@ impl Storage {
             best_block: header.number,
             ..Default::default()
         };
-        storage.headers.insert(0, header);
+        storage.hash_to_number.insert(best_hash, header.number);
+        storage.headers.insert(header.number, header);
         storage.bodies.insert(best_hash, BlockBody::default());
         Self { inner: Arc::new(RwLock::new(storage)) }
     }
@@ -207,6 +238,31 @@ pub(crate) struct StorageInner {
     pub(crate) best_hash: H256,
     /// The total difficulty of the chain until this block
     pub(crate) total_difficulty: U256,
+    /// Offset in seconds that is added to the current time for the timestamp of new blocks
+    pub(crate) time_offset: i64,
+    /// Timestamp of the next block, if set explicitly
+    pub(crate) next_timestamp: Option<u64>,
+    /// Accounts whose unsigned transactions are mined
+    pub(crate) impersonated: HashSet<Address>,
+    /// State changes that are applied by the next block, before its transactions
+    pub(crate) pending_state_changes: Vec<StateChange>,
+    /// Snapshots of the chain by id
+    pub(crate) snapshots: BTreeMap<U256, Snapshot>,
+    /// Id of the next snapshot
+    pub(crate) next_snapshot_id: U256,
+}
+
+/// A snapshot of the dev chain that can be reverted to.
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshot {
+    /// Number of the latest block when the snapshot was taken
+    pub(crate) number: BlockNumber,
+    /// The time offset when the snapshot was taken
+    pub(crate) time_offset: i64,
+    /// The explicit timestamp of the next block when the snapshot was taken
+    pub(crate) next_timestamp: Option<u64>,
+    /// The state changes that were not mined yet when the snapshot was taken
+    pub(crate) pending_state_changes: Vec<StateChange>,
 }
 
 // === impl StorageInner ===
@@ -244,18 +300,125 @@ impl StorageInner {
         self.hash_to_number.insert(self.best_hash, self.best_block);
     }
 
+    /// Returns the timestamp of the best block.
+    pub(crate) fn best_timestamp(&self) -> u64 {
+        self.headers.get(&self.best_block).map(|header| header.timestamp).unwrap_or_default()
+    }
+
+    /// Returns the base fee of the next block.
+    pub(crate) fn next_block_base_fee(&self, chain_spec: &ChainSpec) -> Option<u64> {
+        self.headers
+            .get(&self.best_block)
+            .and_then(|parent| parent.next_block_base_fee(chain_spec.base_fee_params))
+    }
+
+    /// Sets the timestamp of the next block.
+    pub(crate) fn set_next_block_timestamp(&mut self, timestamp: u64) -> Result<(), AutoSealError> {
+        let latest = self.best_timestamp();
+        if timestamp <= latest {
+            return Err(AutoSealError::TimestampTooLow { timestamp, latest })
+        }
+        self.next_timestamp = Some(timestamp);
+        Ok(())
+    }
+
+    /// Moves the time of the next blocks forward and returns the new time offset.
+    pub(crate) fn increase_time(&mut self, seconds: u64) -> i64 {
+        self.time_offset = self.time_offset.saturating_add(seconds.try_into().unwrap_or(i64::MAX));
+        self.time_offset
+    }
+
+    /// Returns the timestamp for the next block.
+    ///
+    /// An explicitly set timestamp is used once, and the time offset is adjusted so that later
+    /// blocks continue from it. Otherwise the timestamp is always greater than the timestamp of
+    /// the best block, even if blocks are sealed within the same second.
+    pub(crate) fn next_block_timestamp(&mut self) -> u64 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        match self.next_timestamp.take() {
+            Some(timestamp) => {
+                self.time_offset = timestamp as i64 - now as i64;
+                timestamp
+            }
+            None => now
+                .saturating_add_signed(self.time_offset)
+                .max(self.best_timestamp().saturating_add(1)),
+        }
+    }
+
+    /// Takes a snapshot of the current chain and returns its id.
+    pub(crate) fn snapshot(&mut self) -> U256 {
+        let id = self.next_snapshot_id;
+        self.next_snapshot_id += U256::from(1);
+        self.snapshots.insert(
+            id,
+            Snapshot {
+                number: self.best_block,
+                time_offset: self.time_offset,
+                next_timestamp: self.next_timestamp,
+                pending_state_changes: self.pending_state_changes.clone(),
+            },
+        );
+        id
+    }
+
+    /// Removes the snapshot with the given id and all snapshots taken after it.
+    ///
+    /// Returns the snapshot, if it exists.
+    pub(crate) fn take_snapshot(&mut self, id: U256) -> Option<Snapshot> {
+        if !self.snapshots.contains_key(&id) {
+            return None
+        }
+        self.snapshots.split_off(&id).remove(&id)
+    }
+
+    /// Reverts the storage to the given header of an earlier block and restores the time settings
+    /// and pending state changes of the snapshot.
+    pub(crate) fn revert_to(&mut self, header: SealedHeader, snapshot: Snapshot) {
+        let number = header.number;
+        let reverted = self.headers.keys().filter(|n| **n > number).copied().collect::<Vec<_>>();
+        for n in reverted {
+            if let Some(header) = self.headers.remove(&n) {
+                self.total_difficulty = self.total_difficulty.saturating_sub(header.difficulty);
+            }
+        }
+        self.hash_to_number.retain(|hash, n| {
+            if *n > number {
+                self.bodies.remove(hash);
+                return false
+            }
+            true
+        });
+
+        let (header, hash) = header.split();
+        trace!(target: "consensus::auto", num=number, ?hash, "reverted to block");
+        self.best_block = number;
+        self.best_hash = hash;
+        self.headers.insert(number, header);
+        self.hash_to_number.insert(hash, number);
+        self.time_offset = snapshot.time_offset;
+        self.next_timestamp = snapshot.next_timestamp;
+        self.pending_state_changes = snapshot.pending_state_changes;
+    }
+
+    /// Returns the nonce the pending state changes set for the account, if any.
+    pub(crate) fn pending_nonce(&self, address: Address) -> Option<u64> {
+        self.pending_state_changes.iter().rev().find_map(|change| match change {
+            StateChange::Nonce { address: account, nonce } if *account == address => Some(*nonce),
+            _ => None,
+        })
+    }
+
     /// Fills in pre-execution header fields based on the current best block and given
     /// transactions.
     pub(crate) fn build_header_template(
         &self,
         transactions: &Vec<TransactionSigned>,
+        timestamp: u64,
         chain_spec: Arc<ChainSpec>,
     ) -> Header {
         // check previous block for base fee
-        let base_fee_per_gas = self
-            .headers
-            .get(&self.best_block)
-            .and_then(|parent| parent.next_block_base_fee(chain_spec.base_fee_params));
+        let base_fee_per_gas = self.next_block_base_fee(&chain_spec);
 
         let mut header = Header {
             parent_hash: self.best_hash,
@@ -270,7 +433,7 @@ impl StorageInner {
             number: self.best_block + 1,
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             gas_used: 0,
-            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            timestamp,
             mix_hash: Default::default(),
             nonce: 0,
             base_fee_per_gas,
@@ -288,6 +451,66 @@ impl StorageInner {
         header
     }
 
+    /// Applies the state changes to the database of the [Executor] and records them in a new
+    /// [PostState].
+    ///
+    /// This is done before the transactions of the block are executed, so they see the changes.
+    pub(crate) fn apply_state_changes<DB: StateProvider>(
+        &self,
+        executor: &mut Executor<DB>,
+        block_number: BlockNumber,
+        state_changes: Vec<StateChange>,
+    ) -> Result<PostState, BlockExecutionError> {
+        let has_state_clear_eip =
+            executor.chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(block_number);
+        let db = executor.db();
+        let mut post_state = PostState::new();
+
+        for change in state_changes {
+            let address = change.address();
+            // load the account first, so that the change is recorded against its current state
+            let mut info = db
+                .load_account(address)
+                .map_err(|_| BlockExecutionError::ProviderError)?
+                .info
+                .clone();
+            let mut storage = hash_map::HashMap::default();
+
+            match change {
+                StateChange::Balance { balance, .. } => info.balance = balance,
+                StateChange::Nonce { nonce, .. } => info.nonce = nonce,
+                StateChange::Code { code, .. } => {
+                    info.code_hash = if code.is_empty() { KECCAK_EMPTY } else { keccak256(&code) };
+                    info.code = Some(RevmBytecode::new_raw(code.0));
+                }
+                StateChange::Storage { slot, value, .. } => {
+                    let original_value = db
+                        .storage(address, slot)
+                        .map_err(|_| BlockExecutionError::ProviderError)?;
+                    storage.insert(slot, StorageSlot { original_value, present_value: value });
+                }
+            }
+
+            let account = RevmAccount {
+                info,
+                storage,
+                is_destroyed: false,
+                is_touched: true,
+                storage_cleared: false,
+                is_not_existing: false,
+            };
+            commit_state_changes(
+                db,
+                &mut post_state,
+                block_number,
+                hash_map::HashMap::from_iter([(address, account)]),
+                has_state_clear_eip,
+            );
+        }
+
+        Ok(post_state)
+    }
+
     /// Executes the block with the given block and senders, on the provided [Executor].
     ///
     /// This returns the poststate from execution and post-block changes, as well as the gas used.
@@ -336,26 +559,33 @@ impl StorageInner {
         header
     }
 
-    /// Builds and executes a new block with the given transactions, on the provided [Executor].
+    /// Builds and executes a new block with the given transactions and state changes, on the
+    /// provided [Executor].
     ///
-    /// This returns the header of the executed block, as well as the poststate from execution.
+    /// The state changes are applied before the transactions, which are executed with the given
+    /// senders. This returns the header of the executed block, as well as the poststate from
+    /// execution.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn build_and_execute<DB: StateProvider>(
         &mut self,
         transactions: Vec<TransactionSigned>,
+        senders: Vec<Address>,
+        state_changes: Vec<StateChange>,
+        timestamp: u64,
         executor: &mut Executor<DB>,
         chain_spec: Arc<ChainSpec>,
     ) -> Result<(SealedHeader, PostState), BlockExecutionError> {
-        let header = self.build_header_template(&transactions, chain_spec);
+        let header = self.build_header_template(&transactions, timestamp, chain_spec);
 
         let block = Block { header, body: transactions, ommers: vec![], withdrawals: None };
 
-        let senders = TransactionSigned::recover_signers(&block.body, block.body.len())
-            .ok_or(BlockExecutionError::Validation(BlockValidationError::SenderRecoveryError))?;
-
-        trace!(target: "consensus::auto", transactions=?&block.body, "executing transactions");
+        trace!(target: "consensus::auto", ?state_changes, "applying state changes");
 
-        // now execute the block
-        let (post_state, gas_used) = self.execute(&block, executor, senders)?;
+        // apply the state changes first, then execute the new block on top of them
+        let mut post_state =
+            self.apply_state_changes(executor, block.header.number, state_changes)?;
+        let (tx_post_state, gas_used) = self.execute(&block, executor, senders)?;
+        post_state.extend(tx_post_state);
 
         let Block { header, body, .. } = block;
         let body = BlockBody { transactions: body, ommers: vec![], withdrawals: None };
@@ -0,0 +1,117 @@
+use crate::AutoSealError;
+use reth_primitives::{Address, Bytes, H256, U256};
+use reth_rpc_types::TransactionRequest;
+use tokio::sync::oneshot;
+
+/// A change to the state of an account that is applied by the next mined block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateChange {
+    /// Sets the balance of the account.
+    Balance {
+        /// The account to modify.
+        address: Address,
+        /// The new balance.
+        balance: U256,
+    },
+    /// Sets the nonce of the account.
+    Nonce {
+        /// The account to modify.
+        address: Address,
+        /// The new nonce.
+        nonce: u64,
+    },
+    /// Sets the code of the account.
+    Code {
+        /// The account to modify.
+        address: Address,
+        /// The new code, empty to remove the code.
+        code: Bytes,
+    },
+    /// Sets a storage slot of the account.
+    Storage {
+        /// The account to modify.
+        address: Address,
+        /// The storage slot.
+        slot: U256,
+        /// The new value of the slot.
+        value: U256,
+    },
+}
+
+impl StateChange {
+    /// Returns the account that is modified.
+    pub fn address(&self) -> Address {
+        match self {
+            StateChange::Balance { address, .. } |
+            StateChange::Nonce { address, .. } |
+            StateChange::Code { address, .. } |
+            StateChange::Storage { address, .. } => *address,
+        }
+    }
+}
+
+/// Requests sent to the [MiningTask](crate::MiningTask) via the
+/// [AutoSealHandle](crate::AutoSealHandle).
+#[derive(Debug)]
+pub(crate) enum AutoSealMessage {
+    /// Mine blocks, including the best pool transactions in the first one.
+    Mine {
+        /// Number of blocks to mine.
+        blocks: u64,
+        /// Timestamp of the first block.
+        timestamp: Option<u64>,
+        /// Seconds between the timestamps of consecutive blocks.
+        interval: Option<u64>,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<Result<(), AutoSealError>>,
+    },
+    /// Set the timestamp of the next block.
+    SetNextBlockTimestamp {
+        /// The timestamp.
+        timestamp: u64,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<Result<(), AutoSealError>>,
+    },
+    /// Move the time of the next blocks forward.
+    IncreaseTime {
+        /// Number of seconds to add.
+        seconds: u64,
+        /// The sender for returning the total time offset.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Queue a state change for the next block.
+    SetState {
+        /// The state change.
+        change: StateChange,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<Result<(), AutoSealError>>,
+    },
+    /// Start or stop impersonating an account.
+    Impersonate {
+        /// The impersonated account.
+        address: Address,
+        /// Whether to start or stop impersonating.
+        enabled: bool,
+        /// The sender for acknowledging the change.
+        tx: oneshot::Sender<()>,
+    },
+    /// Mine an unsigned transaction from an impersonated account.
+    SendUnsignedTransaction {
+        /// The transaction request.
+        request: TransactionRequest,
+        /// The sender for returning the transaction hash.
+        tx: oneshot::Sender<Result<H256, AutoSealError>>,
+    },
+    /// Take a snapshot of the current chain.
+    Snapshot {
+        /// The sender for returning the snapshot id.
+        tx: oneshot::Sender<U256>,
+    },
+    /// Revert the chain to a snapshot.
+    Revert {
+        /// The snapshot id.
+        id: U256,
+        /// The sender for returning whether the snapshot existed.
+        tx: oneshot::Sender<Result<bool, AutoSealError>>,
+    },
+}
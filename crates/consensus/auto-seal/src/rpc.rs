@@ -0,0 +1,138 @@
+//! Implementation of the dev chain rpc methods.
+
+use crate::{AutoSealError, AutoSealHandle};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_primitives::{Address, Bytes, H256, U256, U64};
+use reth_rpc_api::{AnvilApiServer, EthApiServer};
+use reth_rpc_types::{MineOptions, TransactionRequest};
+use tracing::trace;
+
+/// `anvil_` and `evm_` API implementation.
+///
+/// This type provides the functionality for controlling the dev chain built by the
+/// [MiningTask](crate::MiningTask). Transactions of accounts that are not impersonated are sent via
+/// the `eth` API.
+#[derive(Debug, Clone)]
+pub struct DevApi<Eth> {
+    /// Handle to the mining task.
+    handle: AutoSealHandle,
+    /// The `eth` API that signs and submits transactions of accounts that are not impersonated.
+    eth: Eth,
+}
+
+// === impl DevApi ===
+
+impl<Eth> DevApi<Eth> {
+    /// Creates a new instance of `DevApi`.
+    pub fn new(handle: AutoSealHandle, eth: Eth) -> Self {
+        Self { handle, eth }
+    }
+}
+
+#[async_trait]
+impl<Eth> AnvilApiServer for DevApi<Eth>
+where
+    Eth: EthApiServer,
+{
+    /// Handler for `evm_mine`
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String> {
+        trace!(target: "rpc::anvil", ?opts, "Serving evm_mine");
+        let opts = opts.unwrap_or_default();
+        self.handle.mine(opts.blocks(), opts.timestamp()).await?;
+        Ok("0x0".to_string())
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?blocks, ?interval, "Serving anvil_mine");
+        let blocks = blocks.map_or(1, |blocks| blocks.saturating_to::<u64>());
+        let interval = interval.map(|interval| interval.saturating_to::<u64>());
+        Ok(self.handle.mine_with_interval(blocks, interval).await?)
+    }
+
+    /// Handler for `evm_setNextBlockTimestamp`
+    async fn evm_set_next_block_timestamp(&self, timestamp: U64) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?timestamp, "Serving evm_setNextBlockTimestamp");
+        Ok(self.handle.set_next_block_timestamp(timestamp.as_u64()).await?)
+    }
+
+    /// Handler for `evm_increaseTime`
+    async fn evm_increase_time(&self, seconds: U64) -> RpcResult<U64> {
+        trace!(target: "rpc::anvil", ?seconds, "Serving evm_increaseTime");
+        let offset = self.handle.increase_time(seconds.as_u64()).await?;
+        Ok(U64::from(offset.max(0) as u64))
+    }
+
+    /// Handler for `evm_snapshot`
+    async fn evm_snapshot(&self) -> RpcResult<U256> {
+        trace!(target: "rpc::anvil", "Serving evm_snapshot");
+        Ok(self.handle.snapshot().await?)
+    }
+
+    /// Handler for `evm_revert`
+    async fn evm_revert(&self, id: U256) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?id, "Serving evm_revert");
+        Ok(self.handle.revert(id).await?)
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, ?balance, "Serving anvil_setBalance");
+        Ok(self.handle.set_balance(address, balance).await?)
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_setCode");
+        Ok(self.handle.set_code(address, code).await?)
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: H256,
+    ) -> RpcResult<bool> {
+        trace!(target: "rpc::anvil", ?address, ?slot, ?value, "Serving anvil_setStorageAt");
+        self.handle.set_storage_at(address, slot, U256::from_be_bytes(value.0)).await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, ?nonce, "Serving anvil_setNonce");
+        Ok(self.handle.set_nonce(address, nonce.saturating_to::<u64>()).await?)
+    }
+
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_impersonateAccount");
+        Ok(self.handle.impersonate_account(address).await?)
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        trace!(target: "rpc::anvil", ?address, "Serving anvil_stopImpersonatingAccount");
+        Ok(self.handle.stop_impersonating_account(address).await?)
+    }
+
+    /// Handler for `eth_sendUnsignedTransaction`
+    async fn eth_send_unsigned_transaction(&self, request: TransactionRequest) -> RpcResult<H256> {
+        trace!(target: "rpc::anvil", ?request, "Serving eth_sendUnsignedTransaction");
+        Ok(self.handle.send_unsigned_transaction(request).await?)
+    }
+
+    /// Handler for `eth_sendTransaction`
+    async fn eth_send_transaction(&self, request: TransactionRequest) -> RpcResult<H256> {
+        trace!(target: "rpc::anvil", ?request, "Serving eth_sendTransaction");
+        match self.handle.send_unsigned_transaction(request.clone()).await {
+            // sign the transaction with a local signer instead
+            Err(AutoSealError::NotImpersonated(_) | AutoSealError::MissingSender) => {
+                EthApiServer::send_transaction(&self.eth, request).await
+            }
+            res => Ok(res?),
+        }
+    }
+}
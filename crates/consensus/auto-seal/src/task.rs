@@ -1,13 +1,23 @@
-use crate::{mode::MiningMode, Storage};
-use futures_util::{future::BoxFuture, FutureExt};
+use crate::{message::AutoSealMessage, mode::MiningMode, AutoSealError, AutoSealHandle, Storage};
+use futures_util::{future::BoxFuture, FutureExt, StreamExt};
 use reth_beacon_consensus::{BeaconEngineMessage, ForkchoiceStatus};
-use reth_interfaces::consensus::ForkchoiceState;
-use reth_primitives::{Block, ChainSpec, IntoRecoveredTransaction, SealedBlockWithSenders};
-use reth_provider::{CanonChainTracker, CanonStateNotificationSender, Chain, StateProviderFactory};
+use reth_db::database::Database;
+use reth_interfaces::{
+    blockchain_tree::BlockchainTreeEngine, consensus::ForkchoiceState, provider::ProviderError,
+};
+use reth_primitives::{
+    constants::ETHEREUM_BLOCK_GAS_LIMIT, Address, Block, ChainSpec, IntoRecoveredTransaction,
+    SealedBlockWithSenders, SealedHeader, Signature, TransactionSigned, H256, U128, U256,
+};
+use reth_provider::{
+    BlockWriter, CanonChainTracker, CanonStateNotificationSender, Chain, HeaderProvider,
+    ImpersonatedSenders, ProviderFactory, StateProviderFactory,
+};
 use reth_revm::{
     database::{State, SubState},
     executor::Executor,
 };
+use reth_rpc_types::TransactionRequest;
 use reth_stages::PipelineEvent;
 use reth_transaction_pool::{TransactionPool, ValidPoolTransaction};
 use std::{
@@ -17,16 +27,21 @@ use std::{
     sync::Arc,
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedSender},
+    oneshot,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, error, warn};
 
 /// A Future that listens for new ready transactions and puts new blocks into storage
-pub struct MiningTask<Client, Pool: TransactionPool> {
+pub struct MiningTask<DB, Client, Pool: TransactionPool> {
     /// The configured chain spec
     chain_spec: Arc<ChainSpec>,
     /// The client used to interact with the state
     client: Client,
+    /// The database new blocks are written to
+    database: ProviderFactory<DB>,
     /// The active miner
     miner: MiningMode,
     /// Single active future that inserts a new block into `storage`
@@ -43,12 +58,19 @@ pub struct MiningTask<Client, Pool: TransactionPool> {
     canon_state_notification: CanonStateNotificationSender,
     /// The pipeline events to listen on
     pipe_line_events: Option<UnboundedReceiverStream<PipelineEvent>>,
+    /// Sender half of the channel used by [AutoSealHandle]s
+    to_task: UnboundedSender<AutoSealMessage>,
+    /// Requests sent via [AutoSealHandle]s
+    from_handle: UnboundedReceiverStream<AutoSealMessage>,
+    /// Senders of the mined unsigned transactions of impersonated accounts
+    impersonated_senders: ImpersonatedSenders,
 }
 
 // === impl MiningTask ===
 
-impl<Client, Pool: TransactionPool> MiningTask<Client, Pool> {
+impl<DB, Client, Pool: TransactionPool> MiningTask<DB, Client, Pool> {
     /// Creates a new instance of the task
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         chain_spec: Arc<ChainSpec>,
         miner: MiningMode,
@@ -56,11 +78,14 @@ impl<Client, Pool: TransactionPool> MiningTask<Client, Pool> {
         canon_state_notification: CanonStateNotificationSender,
         storage: Storage,
         client: Client,
+        database: ProviderFactory<DB>,
         pool: Pool,
     ) -> Self {
+        let (to_task, from_handle) = unbounded_channel();
         Self {
             chain_spec,
             client,
+            database,
             miner,
             insert_task: None,
             storage,
@@ -69,6 +94,9 @@ impl<Client, Pool: TransactionPool> MiningTask<Client, Pool> {
             canon_state_notification,
             queued: Default::default(),
             pipe_line_events: None,
+            to_task,
+            from_handle: UnboundedReceiverStream::new(from_handle),
+            impersonated_senders: Default::default(),
         }
     }
 
@@ -76,11 +104,47 @@ impl<Client, Pool: TransactionPool> MiningTask<Client, Pool> {
     pub fn set_pipeline_events(&mut self, events: UnboundedReceiverStream<PipelineEvent>) {
         self.pipe_line_events = Some(events);
     }
+
+    /// Returns a new [AutoSealHandle] for controlling the chain the task builds.
+    pub fn handle(&self) -> AutoSealHandle {
+        AutoSealHandle::new(self.to_task.clone())
+    }
+
+    /// Returns the senders of the unsigned transactions of impersonated accounts the task mines.
+    ///
+    /// Their senders can't be recovered from the signature, so this must be consulted wherever the
+    /// transactions of the chain are served, e.g. by the RPC.
+    pub fn impersonated_senders(&self) -> ImpersonatedSenders {
+        self.impersonated_senders.clone()
+    }
+}
+
+impl<DB, Client, Pool> MiningTask<DB, Client, Pool>
+where
+    DB: Clone,
+    Client: Clone,
+    Pool: TransactionPool + Clone,
+{
+    /// Returns the [BlockSealer] that is moved into a new insert task.
+    fn sealer(&self) -> BlockSealer<DB, Client, Pool> {
+        BlockSealer {
+            chain_spec: Arc::clone(&self.chain_spec),
+            client: self.client.clone(),
+            database: self.database.clone(),
+            pool: self.pool.clone(),
+            storage: self.storage.clone(),
+            to_engine: self.to_engine.clone(),
+            canon_state_notification: self.canon_state_notification.clone(),
+            impersonated_senders: self.impersonated_senders.clone(),
+        }
+    }
 }
 
-impl<Client, Pool> Future for MiningTask<Client, Pool>
+impl<DB, Client, Pool> Future for MiningTask<DB, Client, Pool>
 where
-    Client: StateProviderFactory + CanonChainTracker + Clone + Unpin + 'static,
+    DB: Database + Clone + Unpin + 'static,
+    Client:
+        StateProviderFactory + CanonChainTracker + BlockchainTreeEngine + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     <Pool as TransactionPool>::Transaction: IntoRecoveredTransaction,
 {
@@ -97,124 +161,44 @@ where
             }
 
             if this.insert_task.is_none() {
-                if this.queued.is_empty() {
-                    // nothing to insert
-                    break
-                }
-
-                // ready to queue in new insert task
-                let storage = this.storage.clone();
-                let transactions = this.queued.pop_front().expect("not empty");
-
-                let to_engine = this.to_engine.clone();
-                let client = this.client.clone();
-                let chain_spec = Arc::clone(&this.chain_spec);
-                let pool = this.pool.clone();
-                let events = this.pipe_line_events.take();
-                let canon_state_notification = this.canon_state_notification.clone();
-
-                // Create the mining future that creates a block, notifies the engine that drives
-                // the pipeline
-                this.insert_task = Some(Box::pin(async move {
-                    let mut storage = storage.write().await;
-
-                    let (transactions, senders): (Vec<_>, Vec<_>) = transactions
-                        .into_iter()
-                        .map(|tx| {
-                            let recovered = tx.to_recovered_transaction();
-                            let signer = recovered.signer();
-                            (recovered.into_signed(), signer)
-                        })
-                        .unzip();
-
-                    // execute the new block
-                    let substate = SubState::new(State::new(client.latest().unwrap()));
-                    let mut executor = Executor::new(Arc::clone(&chain_spec), substate);
-
-                    match storage.build_and_execute(transactions.clone(), &mut executor, chain_spec)
-                    {
-                        Ok((new_header, post_state)) => {
-                            // clear all transactions from pool
-                            pool.remove_transactions(
-                                transactions.iter().map(|tx| tx.hash()).collect(),
-                            );
-
-                            let state = ForkchoiceState {
-                                head_block_hash: new_header.hash,
-                                finalized_block_hash: new_header.hash,
-                                safe_block_hash: new_header.hash,
-                            };
-                            drop(storage);
-
-                            // TODO: make this a future
-                            // await the fcu call rx for SYNCING, then wait for a VALID response
-                            loop {
-                                // send the new update to the engine, this will trigger the engine
-                                // to download and execute the block we just inserted
-                                let (tx, rx) = oneshot::channel();
-                                let _ = to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
-                                    state,
-                                    payload_attrs: None,
-                                    tx,
-                                });
-                                debug!(target: "consensus::auto", ?state, "Sent fork choice update");
-
-                                match rx.await.unwrap() {
-                                    Ok(fcu_response) => {
-                                        match fcu_response.forkchoice_status() {
-                                            ForkchoiceStatus::Valid => break,
-                                            ForkchoiceStatus::Invalid => {
-                                                error!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned invalid response");
-                                                return None
-                                            }
-                                            ForkchoiceStatus::Syncing => {
-                                                debug!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned SYNCING, waiting for VALID");
-                                                // wait for the next fork choice update
-                                                continue
-                                            }
-                                        }
-                                    }
-                                    Err(err) => {
-                                        error!(target: "consensus::auto", ?err, "Autoseal fork choice update failed");
-                                        return None
-                                    }
-                                }
-                            }
+                let sealer = this.sealer();
 
-                            // seal the block
-                            let block = Block {
-                                header: new_header.clone().unseal(),
-                                body: transactions,
-                                ommers: vec![],
-                                withdrawals: None,
-                            };
-                            let sealed_block = block.seal_slow();
-
-                            let sealed_block_with_senders =
-                                SealedBlockWithSenders::new(sealed_block, senders)
-                                    .expect("senders are valid");
-
-                            // update canon chain for rpc
-                            client.set_canonical_head(new_header.clone());
-                            client.set_safe(new_header.clone());
-                            client.set_finalized(new_header.clone());
+                // requests of the handle are processed before queued transactions
+                if let Poll::Ready(Some(message)) = this.from_handle.poll_next_unpin(cx) {
+                    let events = this.pipe_line_events.take();
+                    this.insert_task = Some(Box::pin(async move {
+                        sealer.on_message(message).await;
+                        events
+                    }));
+                } else {
+                    if this.queued.is_empty() {
+                        // nothing to insert
+                        break
+                    }
 
-                            debug!(target: "consensus::auto", header=?sealed_block_with_senders.hash(), "sending block notification");
+                    // ready to queue in new insert task
+                    let transactions = this.queued.pop_front().expect("not empty");
+                    let events = this.pipe_line_events.take();
 
-                            let chain =
-                                Arc::new(Chain::new(vec![(sealed_block_with_senders, post_state)]));
+                    // Create the mining future that creates a block, commits it and notifies the
+                    // engine
+                    this.insert_task = Some(Box::pin(async move {
+                        let (transactions, senders) = recover_pool_transactions(transactions);
+                        let hashes = transactions.iter().map(|tx| tx.hash()).collect();
 
-                            // send block notification
-                            let _ = canon_state_notification
-                                .send(reth_provider::CanonStateNotification::Commit { new: chain });
-                        }
-                        Err(err) => {
-                            warn!(target: "consensus::auto", ?err, "failed to execute block")
+                        match sealer.seal_block(transactions, senders, None).await {
+                            Ok(_) => {
+                                // clear all transactions from pool
+                                sealer.pool.remove_transactions(hashes);
+                            }
+                            Err(err) => {
+                                warn!(target: "consensus::auto", ?err, "failed to execute block")
+                            }
                         }
-                    }
 
-                    events
-                }));
+                        events
+                    }));
+                }
             }
 
             if let Some(mut fut) = this.insert_task.take() {
@@ -234,8 +218,488 @@ where
     }
 }
 
-impl<Client, Pool: TransactionPool> std::fmt::Debug for MiningTask<Client, Pool> {
+impl<DB, Client, Pool: TransactionPool> std::fmt::Debug for MiningTask<DB, Client, Pool> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MiningTask").finish_non_exhaustive()
     }
 }
+
+/// Splits the pool transactions into signed transactions and their senders.
+fn recover_pool_transactions<T: IntoRecoveredTransaction>(
+    transactions: Vec<Arc<ValidPoolTransaction<T>>>,
+) -> (Vec<TransactionSigned>, Vec<Address>) {
+    transactions
+        .into_iter()
+        .map(|tx| {
+            let recovered = tx.to_recovered_transaction();
+            let signer = recovered.signer();
+            (recovered.into_signed(), signer)
+        })
+        .unzip()
+}
+
+/// Returns the placeholder signature of an unsigned transaction of the impersonated `sender`.
+///
+/// The signature is part of the transaction hash, deriving it from the sender keeps the hashes of
+/// otherwise identical transactions of different senders apart.
+fn impersonated_signature(sender: Address) -> Signature {
+    Signature { r: U256::from_be_slice(sender.as_bytes()), s: U256::from(1), odd_y_parity: false }
+}
+
+/// Everything an insert task needs to seal new blocks and make them canonical.
+struct BlockSealer<DB, Client, Pool> {
+    chain_spec: Arc<ChainSpec>,
+    client: Client,
+    database: ProviderFactory<DB>,
+    pool: Pool,
+    storage: Storage,
+    to_engine: UnboundedSender<BeaconEngineMessage>,
+    canon_state_notification: CanonStateNotificationSender,
+    impersonated_senders: ImpersonatedSenders,
+}
+
+impl<DB, Client, Pool> BlockSealer<DB, Client, Pool>
+where
+    DB: Database,
+    Client: StateProviderFactory + CanonChainTracker + BlockchainTreeEngine,
+    Pool: TransactionPool,
+    <Pool as TransactionPool>::Transaction: IntoRecoveredTransaction,
+{
+    /// Handles a request sent via an [AutoSealHandle].
+    async fn on_message(self, message: AutoSealMessage) {
+        match message {
+            AutoSealMessage::Mine { blocks, timestamp, interval, tx } => {
+                let _ = tx.send(self.mine(blocks, timestamp, interval).await);
+            }
+            AutoSealMessage::SetNextBlockTimestamp { timestamp, tx } => {
+                let _ = tx.send(self.storage.write().await.set_next_block_timestamp(timestamp));
+            }
+            AutoSealMessage::IncreaseTime { seconds, tx } => {
+                let _ = tx.send(self.storage.write().await.increase_time(seconds));
+            }
+            AutoSealMessage::SetState { change, tx } => {
+                self.storage.write().await.pending_state_changes.push(change);
+                let _ = tx.send(Ok(()));
+            }
+            AutoSealMessage::Impersonate { address, enabled, tx } => {
+                let mut storage = self.storage.write().await;
+                if enabled {
+                    storage.impersonated.insert(address);
+                } else {
+                    storage.impersonated.remove(&address);
+                }
+                let _ = tx.send(());
+            }
+            AutoSealMessage::SendUnsignedTransaction { request, tx } => {
+                let _ = tx.send(self.send_unsigned_transaction(request).await);
+            }
+            AutoSealMessage::Snapshot { tx } => {
+                let _ = tx.send(self.storage.write().await.snapshot());
+            }
+            AutoSealMessage::Revert { id, tx } => {
+                let _ = tx.send(self.revert(id).await);
+            }
+        }
+    }
+
+    /// Mines `blocks` blocks, the first one includes the best transactions of the pool.
+    ///
+    /// If an interval is given, the timestamps of consecutive blocks are that many seconds apart.
+    async fn mine(
+        &self,
+        blocks: u64,
+        timestamp: Option<u64>,
+        interval: Option<u64>,
+    ) -> Result<(), AutoSealError> {
+        if blocks == 0 {
+            return Ok(())
+        }
+        if let Some(timestamp) = timestamp {
+            self.storage.write().await.set_next_block_timestamp(timestamp)?;
+        }
+
+        let (transactions, senders) =
+            recover_pool_transactions(self.pool.best_transactions().collect());
+        let hashes = transactions.iter().map(|tx| tx.hash()).collect();
+        let mut header = self.seal_block(transactions, senders, None).await?;
+        self.pool.remove_transactions(hashes);
+
+        for _ in 1..blocks {
+            let timestamp = interval.map(|interval| header.timestamp.saturating_add(interval));
+            header = self.seal_block(Vec::new(), Vec::new(), timestamp).await?;
+        }
+        Ok(())
+    }
+
+    /// Mines the unsigned transaction of an impersonated account in a new block.
+    async fn send_unsigned_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> Result<H256, AutoSealError> {
+        let from = request.from.ok_or(AutoSealError::MissingSender)?;
+        let (base_fee, pending_nonce) = {
+            let storage = self.storage.read().await;
+            if !storage.impersonated.contains(&from) {
+                return Err(AutoSealError::NotImpersonated(from))
+            }
+            (
+                storage.next_block_base_fee(&self.chain_spec).unwrap_or_default(),
+                storage.pending_nonce(from),
+            )
+        };
+
+        if request.nonce.is_none() {
+            let nonce = match pending_nonce {
+                Some(nonce) => nonce,
+                None => self.client.latest()?.account_nonce(from)?.unwrap_or_default(),
+            };
+            request.nonce = Some(U256::from(nonce));
+        }
+        if request.gas.is_none() {
+            request.gas = Some(U256::from(ETHEREUM_BLOCK_GAS_LIMIT));
+        }
+        if request.gas_price.is_none() && request.max_fee_per_gas.is_none() {
+            request.max_fee_per_gas = Some(U128::from(base_fee));
+        }
+
+        let mut transaction = request
+            .into_typed_request()
+            .ok_or(AutoSealError::ConflictingFeeFields)?
+            .into_transaction();
+        transaction.set_chain_id(self.chain_spec.chain.id());
+
+        // the signature is never checked, the sender is passed to the executor, stored alongside
+        // the block and recorded for the RPC
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            transaction,
+            impersonated_signature(from),
+        );
+        let hash = transaction.hash();
+        self.impersonated_senders.insert(hash, from);
+
+        self.seal_block(vec![transaction], vec![from], None).await?;
+        Ok(hash)
+    }
+
+    /// Reverts the chain to the snapshot by unwinding all blocks that were mined after it.
+    async fn revert(&self, id: U256) -> Result<bool, AutoSealError> {
+        let mut storage = self.storage.write().await;
+        let Some(snapshot) = storage.take_snapshot(id) else { return Ok(false) };
+
+        if snapshot.number < storage.best_block {
+            self.client.unwind(snapshot.number)?;
+        }
+        let header = self
+            .database
+            .provider()?
+            .sealed_header(snapshot.number)?
+            .ok_or(ProviderError::HeaderNotFound(snapshot.number.into()))?;
+        storage.revert_to(header.clone(), snapshot);
+        drop(storage);
+
+        self.update_forkchoice(header).await;
+        Ok(true)
+    }
+
+    /// Builds and executes a new block on top of the best block, commits it to the database and
+    /// makes it the canonical head.
+    ///
+    /// The pending state changes are applied before the transactions. If no timestamp is given,
+    /// the next timestamp of the storage is used.
+    async fn seal_block(
+        &self,
+        transactions: Vec<TransactionSigned>,
+        senders: Vec<Address>,
+        timestamp: Option<u64>,
+    ) -> Result<SealedHeader, AutoSealError> {
+        let mut storage = self.storage.write().await;
+        let timestamp = timestamp.unwrap_or_else(|| storage.next_block_timestamp());
+        let state_changes = storage.pending_state_changes.clone();
+
+        // execute the new block
+        let substate = SubState::new(State::new(self.client.latest()?));
+        let mut executor = Executor::new(Arc::clone(&self.chain_spec), substate);
+        let (new_header, post_state) = storage.build_and_execute(
+            transactions.clone(),
+            senders.clone(),
+            state_changes,
+            timestamp,
+            &mut executor,
+            Arc::clone(&self.chain_spec),
+        )?;
+
+        // seal the block
+        let block = Block {
+            header: new_header.clone().unseal(),
+            body: transactions,
+            ommers: vec![],
+            withdrawals: None,
+        };
+        let sealed_block_with_senders = SealedBlockWithSenders::new(block.seal_slow(), senders)
+            .expect("one sender per transaction");
+
+        // The block is committed directly instead of being downloaded and re-executed by the
+        // engine: re-execution would lose the state changes and the senders of impersonated
+        // transactions.
+        let provider = self.database.provider_rw()?;
+        provider.append_blocks_with_post_state(
+            vec![sealed_block_with_senders.clone()],
+            post_state.clone(),
+        )?;
+        provider.commit()?;
+        storage.pending_state_changes.clear();
+        drop(storage);
+
+        // let the tree pick up the new canonical block
+        self.client.restore_canonical_hashes()?;
+        self.update_forkchoice(new_header.clone()).await;
+
+        debug!(target: "consensus::auto", header=?sealed_block_with_senders.hash(), "sending block notification");
+
+        let chain = Arc::new(Chain::new(vec![(sealed_block_with_senders, post_state)]));
+
+        // send block notification
+        let _ = self
+            .canon_state_notification
+            .send(reth_provider::CanonStateNotification::Commit { new: chain });
+
+        Ok(new_header)
+    }
+
+    /// Sends a forkchoice update for the new head to the engine and updates the canonical chain
+    /// tracked for the RPC.
+    async fn update_forkchoice(&self, head: SealedHeader) {
+        let state = ForkchoiceState {
+            head_block_hash: head.hash,
+            finalized_block_hash: head.hash,
+            safe_block_hash: head.hash,
+        };
+
+        // TODO: make this a future
+        // await the fcu call rx for SYNCING, then wait for a VALID response
+        loop {
+            // send the new update to the engine, the block is already canonical, so this only
+            // updates the forkchoice state of the engine
+            let (tx, rx) = oneshot::channel();
+            let _ = self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
+                state,
+                payload_attrs: None,
+                tx,
+            });
+            debug!(target: "consensus::auto", ?state, "Sent fork choice update");
+
+            match rx.await {
+                Ok(Ok(fcu_response)) => match fcu_response.forkchoice_status() {
+                    ForkchoiceStatus::Valid => break,
+                    ForkchoiceStatus::Invalid => {
+                        error!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned invalid response");
+                        break
+                    }
+                    ForkchoiceStatus::Syncing => {
+                        debug!(target: "consensus::auto", ?fcu_response, "Forkchoice update returned SYNCING, waiting for VALID");
+                        // wait for the next fork choice update
+                        continue
+                    }
+                },
+                Ok(Err(err)) => {
+                    error!(target: "consensus::auto", ?err, "Autoseal fork choice update failed");
+                    break
+                }
+                Err(_) => {
+                    error!(target: "consensus::auto", "Engine dropped the fork choice update");
+                    break
+                }
+            }
+        }
+
+        // update canon chain for rpc
+        self.client.set_canonical_head(head.clone());
+        self.client.set_safe(head.clone());
+        self.client.set_finalized(head);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AutoSealBuilder, AutoSealConsensus};
+    use reth_blockchain_tree::{
+        BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
+    };
+    use reth_db::{test_utils::create_test_rw_db, DatabaseEnv};
+    use reth_primitives::{ChainSpecBuilder, Genesis, SealedBlock};
+    use reth_provider::{providers::BlockchainProvider, BlockHashReader, BlockNumReader};
+    use reth_revm::Factory;
+    use reth_transaction_pool::test_utils::testing_pool;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    type TestProvider = BlockchainProvider<
+        Arc<DatabaseEnv>,
+        ShareableBlockchainTree<Arc<DatabaseEnv>, AutoSealConsensus, Factory>,
+    >;
+
+    /// Spawns a mining task that only seals blocks on request, on top of an empty genesis.
+    fn spawn_mining_task() -> (AutoSealHandle, TestProvider, ProviderFactory<Arc<DatabaseEnv>>) {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .chain(reth_primitives::Chain::dev())
+                .genesis(Genesis::default())
+                .london_activated()
+                .build(),
+        );
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.clone(), Arc::clone(&chain_spec));
+
+        let genesis = SealedBlock {
+            header: chain_spec.sealed_genesis_header(),
+            body: vec![],
+            ommers: vec![],
+            withdrawals: None,
+        };
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(genesis.clone(), Some(vec![])).unwrap();
+        provider.commit().unwrap();
+
+        let consensus = AutoSealConsensus::new(Arc::clone(&chain_spec));
+        let externals = TreeExternals::new(
+            db.clone(),
+            consensus,
+            Factory::new(Arc::clone(&chain_spec)),
+            Arc::clone(&chain_spec),
+        );
+        let (canon_state_notification, _) = tokio::sync::broadcast::channel(16);
+        let tree = ShareableBlockchainTree::new(
+            BlockchainTree::new(
+                externals,
+                canon_state_notification.clone(),
+                BlockchainTreeConfig::default(),
+            )
+            .unwrap(),
+        );
+        let client = BlockchainProvider::with_latest(factory.clone(), tree, genesis.header);
+
+        // no engine is running, the forkchoice updates of the task are dropped
+        let (to_engine, _) = unbounded_channel();
+        let (_, _, task) = AutoSealBuilder::new(
+            chain_spec,
+            client.clone(),
+            factory.clone(),
+            testing_pool(),
+            to_engine,
+            canon_state_notification,
+            MiningMode::None,
+        )
+        .build();
+        let handle = task.handle();
+        tokio::spawn(task);
+
+        (handle, client, factory)
+    }
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn seal_blocks_with_timestamps() {
+        let (handle, _, factory) = spawn_mining_task();
+        let start = now();
+
+        handle.mine(2, None).await.unwrap();
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 2);
+        let first = provider.sealed_header(1).unwrap().unwrap();
+        let second = provider.sealed_header(2).unwrap().unwrap();
+        assert!(first.timestamp >= start);
+        // blocks sealed within the same second still have increasing timestamps
+        assert!(second.timestamp > first.timestamp);
+        assert_eq!(second.parent_hash, first.hash);
+        assert_eq!(provider.block_hash(2).unwrap(), Some(second.hash));
+        drop(provider);
+
+        // an explicit timestamp is used for the next block
+        let timestamp = start + 1_000;
+        handle.set_next_block_timestamp(timestamp).await.unwrap();
+        handle.mine(1, None).await.unwrap();
+        let header = factory.provider().unwrap().sealed_header(3).unwrap().unwrap();
+        assert_eq!(header.timestamp, timestamp);
+
+        // the timestamp must be greater than the one of the best block
+        let err = handle.set_next_block_timestamp(timestamp).await.unwrap_err();
+        assert!(
+            matches!(err, AutoSealError::TimestampTooLow { latest, .. } if latest == timestamp)
+        );
+
+        // later blocks continue from the explicit timestamp, moved forward by the time increase
+        handle.increase_time(3_600).await.unwrap();
+        handle.mine(1, None).await.unwrap();
+        let header = factory.provider().unwrap().sealed_header(4).unwrap().unwrap();
+        assert!(header.timestamp >= timestamp + 3_600);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn snapshot_mine_revert() {
+        let (handle, client, factory) = spawn_mining_task();
+        let address = Address::random();
+
+        handle.mine(1, None).await.unwrap();
+        let snapshot_hash = factory.provider().unwrap().block_hash(1).unwrap().unwrap();
+        let id = handle.snapshot().await.unwrap();
+
+        handle.set_balance(address, U256::from(1_000)).await.unwrap();
+        handle.mine(2, None).await.unwrap();
+        assert_eq!(factory.provider().unwrap().last_block_number().unwrap(), 3);
+        assert_eq!(
+            client.latest().unwrap().account_balance(address).unwrap(),
+            Some(U256::from(1_000))
+        );
+
+        assert!(handle.revert(id).await.unwrap());
+
+        // the blocks mined after the snapshot and their state are gone
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 1);
+        assert_eq!(provider.block_hash(1).unwrap(), Some(snapshot_hash));
+        assert_eq!(provider.block_hash(2).unwrap(), None);
+        drop(provider);
+        assert_eq!(client.latest().unwrap().account_balance(address).unwrap(), None);
+        assert_eq!(client.chain_info().unwrap().best_hash, snapshot_hash);
+
+        // a snapshot can only be reverted to once
+        assert!(!handle.revert(id).await.unwrap());
+
+        // new blocks are mined on top of the snapshot
+        handle.mine(1, None).await.unwrap();
+        let header = factory.provider().unwrap().sealed_header(2).unwrap().unwrap();
+        assert_eq!(header.parent_hash, snapshot_hash);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn state_changes_apply_to_next_block() {
+        let (handle, client, factory) = spawn_mining_task();
+        let address = Address::random();
+
+        handle.set_balance(address, U256::from(1_000)).await.unwrap();
+        handle.set_nonce(address, 5).await.unwrap();
+
+        // the state changes don't mine a block
+        assert_eq!(factory.provider().unwrap().last_block_number().unwrap(), 0);
+        assert_eq!(client.latest().unwrap().account_balance(address).unwrap(), None);
+
+        // mining zero blocks is a no-op
+        handle.mine(0, None).await.unwrap();
+        assert_eq!(factory.provider().unwrap().last_block_number().unwrap(), 0);
+
+        handle.mine_with_interval(2, Some(60)).await.unwrap();
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 2);
+        let first = provider.sealed_header(1).unwrap().unwrap();
+        let second = provider.sealed_header(2).unwrap().unwrap();
+        assert_eq!(second.timestamp, first.timestamp + 60);
+        drop(provider);
+
+        let state = client.latest().unwrap();
+        assert_eq!(state.account_balance(address).unwrap(), Some(U256::from(1_000)));
+        assert_eq!(state.account_nonce(address).unwrap(), Some(5));
+    }
+}
@@ -14,7 +14,7 @@ use reth_interfaces::{
         error::{InsertBlockError, InsertBlockErrorKind},
         BlockStatus, BlockchainTreeEngine, CanonicalOutcome, InsertPayloadOk,
     },
-    consensus::ForkchoiceState,
+    consensus::{BadBlock, BadBlocks, ForkchoiceState, DEFAULT_MAX_BAD_BLOCKS},
     executor::{BlockExecutionError, BlockValidationError},
     p2p::{bodies::client::BodiesClient, headers::client::HeadersClient},
     sync::{NetworkSyncUpdater, SyncState},
@@ -182,6 +182,9 @@ where
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
+    /// The most recent invalid blocks that were rejected by the engine, together with the
+    /// validation error.
+    bad_blocks: BadBlocks,
     /// Consensus engine metrics.
     metrics: EngineMetrics,
     /// After downloading a block corresponding to a recent forkchoice update, the engine will
@@ -293,6 +296,7 @@ where
             payload_builder,
             listeners: EventListeners::default(),
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
+            bad_blocks: BadBlocks::new(DEFAULT_MAX_BAD_BLOCKS),
             metrics: EngineMetrics::default(),
             pipeline_run_threshold,
             prune,
@@ -362,6 +366,12 @@ where
         self.handle.clone()
     }
 
+    /// Returns a shareable handle to the most recent bad blocks that were rejected by this
+    /// [`BeaconConsensusEngine`].
+    pub fn bad_blocks(&self) -> BadBlocks {
+        self.bad_blocks.clone()
+    }
+
     /// Returns true if the distance from the local tip to the block is greater than the configured
     /// threshold.
     ///
@@ -1223,7 +1233,8 @@ where
             // all of these occurred if the payload is invalid
             let parent_hash = block.parent_hash;
 
-            // keep track of the invalid header
+            // keep track of the invalid block and its header
+            self.bad_blocks.insert(BadBlock { block: block.clone(), reason: error.to_string() });
             self.invalid_headers.insert(block.header);
 
             let latest_valid_hash =
@@ -1326,6 +1337,8 @@ where
                     let (block, err) = err.split();
                     warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash, ?err, "Marking block as invalid");
 
+                    self.bad_blocks
+                        .insert(BadBlock { block: block.clone(), reason: err.to_string() });
                     self.invalid_headers.insert(block.header);
                 }
             }
@@ -2477,6 +2490,7 @@ mod tests {
                 [&data.genesis, &block1].into_iter(),
             );
 
+            let bad_blocks = consensus_engine.bad_blocks();
             let mut engine_rx = spawn_consensus_engine(consensus_engine);
 
             // Send forkchoice
@@ -2506,6 +2520,11 @@ mod tests {
             .with_latest_valid_hash(H256::zero());
             assert_eq!(result, expected_result);
 
+            // the rejected payload is kept as a bad block
+            let bad = bad_blocks.all();
+            assert_eq!(bad.len(), 1);
+            assert_eq!(bad[0].block.hash, block2.hash);
+
             assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
         }
     }
@@ -1,8 +1,9 @@
 use async_trait::async_trait;
+use parking_lot::RwLock;
 use reth_primitives::{
     BlockHash, BlockNumber, Header, InvalidTransactionError, SealedBlock, SealedHeader, H256, U256,
 };
-use std::fmt::Debug;
+use std::{collections::VecDeque, fmt::Debug, sync::Arc};
 
 /// Re-export fork choice state
 pub use reth_rpc_types::engine::ForkchoiceState;
@@ -160,3 +161,95 @@ pub enum ConsensusError {
     #[error(transparent)]
     InvalidTransaction(#[from] InvalidTransactionError),
 }
+
+/// The default number of bad blocks that are kept in [BadBlocks].
+pub const DEFAULT_MAX_BAD_BLOCKS: usize = 10;
+
+/// A block that was rejected because it is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadBlock {
+    /// The rejected block.
+    pub block: SealedBlock,
+    /// The reason the block was rejected, e.g. the validation error.
+    pub reason: String,
+}
+
+/// A bounded, shareable list of the most recent [BadBlock]s.
+///
+/// Once the limit is reached, the oldest bad block is evicted.
+#[derive(Debug, Clone)]
+pub struct BadBlocks {
+    inner: Arc<RwLock<VecDeque<BadBlock>>>,
+    max_blocks: usize,
+}
+
+// === impl BadBlocks ===
+
+impl BadBlocks {
+    /// Creates a new, empty instance that keeps at most `max_blocks` bad blocks.
+    pub fn new(max_blocks: usize) -> Self {
+        Self { inner: Default::default(), max_blocks }
+    }
+
+    /// Adds a bad block.
+    ///
+    /// This is a noop if the block is already tracked.
+    pub fn insert(&self, bad_block: BadBlock) {
+        if self.max_blocks == 0 {
+            return
+        }
+        let mut blocks = self.inner.write();
+        if blocks.iter().any(|existing| existing.block.hash == bad_block.block.hash) {
+            return
+        }
+        if blocks.len() == self.max_blocks {
+            blocks.pop_front();
+        }
+        blocks.push_back(bad_block);
+    }
+
+    /// Returns all tracked bad blocks, most recent first.
+    pub fn all(&self) -> Vec<BadBlock> {
+        self.inner.read().iter().rev().cloned().collect()
+    }
+
+    /// Returns the number of tracked bad blocks.
+    pub fn len(&self) -> usize {
+        self.inner.read().len()
+    }
+
+    /// Returns `true` if no bad blocks are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().is_empty()
+    }
+}
+
+impl Default for BadBlocks {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BAD_BLOCKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_blocks_bounded() {
+        let bad_blocks = BadBlocks::new(2);
+        let bad_block = |number| {
+            let mut block = SealedBlock::default();
+            block.header = Header { number, ..Default::default() }.seal_slow();
+            BadBlock { block, reason: format!("bad block {number}") }
+        };
+
+        bad_blocks.insert(bad_block(1));
+        bad_blocks.insert(bad_block(1));
+        assert_eq!(bad_blocks.len(), 1);
+
+        bad_blocks.insert(bad_block(2));
+        bad_blocks.insert(bad_block(3));
+        let numbers = bad_blocks.all().iter().map(|bad| bad.block.number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![3, 2]);
+    }
+}
@@ -18,6 +18,9 @@ pub mod full_block;
 /// [`HeadersClient`]: crate::p2p::headers::client::HeadersClient
 pub mod headers;
 
+/// Traits for implementing `snap` protocol clients.
+pub mod snap;
+
 /// Error types broadly used by p2p interfaces for any operation which may produce an error when
 /// interacting with the network implementation
 pub mod error;
@@ -0,0 +1,72 @@
+use crate::p2p::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
+use futures::Future;
+pub use reth_eth_wire::{
+    AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+    StorageRanges, TrieNodes,
+};
+
+/// A client capable of requesting state ranges over the `snap` protocol.
+///
+/// The `request_id` of the requests is assigned by the client.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SnapClient: DownloadClient {
+    /// The output of the request future for querying account ranges.
+    type AccountRangeOutput: Future<Output = PeerRequestResult<AccountRange>> + Sync + Send + Unpin;
+    /// The output of the request future for querying storage ranges.
+    type StorageRangesOutput: Future<Output = PeerRequestResult<StorageRanges>>
+        + Sync
+        + Send
+        + Unpin;
+    /// The output of the request future for querying bytecodes.
+    type ByteCodesOutput: Future<Output = PeerRequestResult<ByteCodes>> + Sync + Send + Unpin;
+    /// The output of the request future for querying trie nodes.
+    type TrieNodesOutput: Future<Output = PeerRequestResult<TrieNodes>> + Sync + Send + Unpin;
+
+    /// Fetches a range of accounts.
+    fn get_account_range(&self, request: GetAccountRange) -> Self::AccountRangeOutput {
+        self.get_account_range_with_priority(request, Priority::Normal)
+    }
+
+    /// Fetches a range of accounts with priority.
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRange,
+        priority: Priority,
+    ) -> Self::AccountRangeOutput;
+
+    /// Fetches ranges of storage slots.
+    fn get_storage_ranges(&self, request: GetStorageRanges) -> Self::StorageRangesOutput {
+        self.get_storage_ranges_with_priority(request, Priority::Normal)
+    }
+
+    /// Fetches ranges of storage slots with priority.
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRanges,
+        priority: Priority,
+    ) -> Self::StorageRangesOutput;
+
+    /// Fetches bytecodes.
+    fn get_byte_codes(&self, request: GetByteCodes) -> Self::ByteCodesOutput {
+        self.get_byte_codes_with_priority(request, Priority::Normal)
+    }
+
+    /// Fetches bytecodes with priority.
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodes,
+        priority: Priority,
+    ) -> Self::ByteCodesOutput;
+
+    /// Fetches trie nodes.
+    fn get_trie_nodes(&self, request: GetTrieNodes) -> Self::TrieNodesOutput {
+        self.get_trie_nodes_with_priority(request, Priority::Normal)
+    }
+
+    /// Fetches trie nodes with priority.
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodes,
+        priority: Priority,
+    ) -> Self::TrieNodesOutput;
+}
@@ -0,0 +1,32 @@
+use futures::Stream;
+use reth_primitives::{Account, Bytecode, StorageEntry, H256};
+
+/// A verified chunk of the state downloaded over the `snap` protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapStateChunk {
+    /// Accounts keyed by their hashed address, in ascending order.
+    Accounts(Vec<(H256, Account)>),
+    /// Storage slots of a single account keyed by their hashed slot, in ascending order.
+    ///
+    /// The slots of an account with a large storage trie may be split across multiple chunks.
+    Storage {
+        /// The hashed address of the account.
+        hashed_address: H256,
+        /// The hashed storage entries.
+        entries: Vec<StorageEntry>,
+    },
+    /// Bytecodes keyed by their hash.
+    ByteCodes(Vec<(H256, Bytecode)>),
+}
+
+/// A downloader capable of fetching and yielding the entire state at a given root.
+///
+/// A downloader represents a distinct strategy for submitting requests to download the state,
+/// while a [SnapClient][crate::p2p::snap::client::SnapClient] represents a client capable of
+/// fulfilling these requests.
+///
+/// The stream ends once the state has been downloaded completely.
+pub trait StateDownloader: Send + Sync + Stream<Item = SnapStateChunk> + Unpin {
+    /// Sets the root of the state to download, discarding the progress of any previous root.
+    fn set_state_root(&mut self, state_root: H256);
+}
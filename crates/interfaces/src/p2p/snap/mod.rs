@@ -0,0 +1,5 @@
+/// Traits and types for `snap` protocol clients.
+pub mod client;
+
+/// A downloader that fetches the state over the `snap` protocol.
+pub mod downloader;
@@ -96,4 +96,18 @@ pub enum ProviderError {
     },
     #[error("State at block #{0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// The body of the block was removed by history expiry.
+    #[error("Body of block #{0} is pruned")]
+    BlockBodyPruned(BlockNumber),
+    /// Reading or writing a static file failed.
+    #[error("Static file error: {0}")]
+    StaticFile(String),
+    /// Unwinding would remove blocks that were already moved to static files.
+    #[error("Can't unwind to block #{unwind_to}, blocks up to #{highest_static_block} are in static files")]
+    UnwindBelowStaticFiles {
+        /// Target block of the unwind
+        unwind_to: BlockNumber,
+        /// Highest block in the static files
+        highest_static_block: BlockNumber,
+    },
 }
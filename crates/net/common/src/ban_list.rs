@@ -45,6 +45,16 @@ impl BanList {
         Self { banned_ips, banned_peers }
     }
 
+    /// Returns all banned peers and until when they're banned, `None` if indefinitely.
+    pub fn banned_peers(&self) -> &HashMap<PeerId, Option<Instant>> {
+        &self.banned_peers
+    }
+
+    /// Returns all banned ips and until when they're banned, `None` if indefinitely.
+    pub fn banned_ips(&self) -> &HashMap<IpAddr, Option<Instant>> {
+        &self.banned_ips
+    }
+
     /// Removes all peers that are no longer banned.
     pub fn evict_peers(&mut self, now: Instant) -> Vec<PeerId> {
         let mut evicted = Vec::new();
@@ -105,8 +105,15 @@ impl Discv4Config {
 
     /// Returns the corresponding [`ResolveNatInterval`], if a [NatResolver] and an interval was
     /// configured
+    ///
+    /// Returns `None` if the [NatResolver] maps ports, because the external ip is then resolved
+    /// with the port mappings of the network, see
+    /// [Discv4::set_external_port_mappings](crate::Discv4::set_external_port_mappings).
     pub fn resolve_external_ip_interval(&self) -> Option<ResolveNatInterval> {
         let resolver = self.external_ip_resolver?;
+        if resolver.maps_ports() {
+            return None
+        }
         let interval = self.resolve_external_ip_interval?;
         Some(ResolveNatInterval::interval(resolver, interval))
     }
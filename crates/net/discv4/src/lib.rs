@@ -47,7 +47,6 @@ use reth_primitives::{
     bytes::{Bytes, BytesMut},
     ForkId, PeerId, H256,
 };
-use reth_rlp::{RlpDecodable, RlpEncodable};
 use secp256k1::SecretKey;
 use std::{
     cell::RefCell,
@@ -81,15 +80,15 @@ use node::{kad_key, NodeKey};
 mod table;
 
 // reexport NodeRecord primitive
-pub use reth_primitives::NodeRecord;
+pub use reth_primitives::{EnrForkIdEntry, NodeRecord};
 
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
 
 use crate::table::PongTable;
-use reth_net_nat::ResolveNatInterval;
 /// reexport to get public ip.
 pub use reth_net_nat::{external_ip, NatResolver};
+use reth_net_nat::{MappingProtocol, PortMapping, ResolveNatInterval};
 
 /// The default port for discv4 via UDP
 ///
@@ -336,6 +335,15 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Sets the external addresses of the given port mappings in the local [`NodeRecord`] and the
+    /// EIP-868 [`Enr`] of the node.
+    ///
+    /// See also [`Discv4Service::set_external_port_mappings`].
+    pub fn set_external_port_mappings(&self, mappings: Vec<PortMapping>) {
+        let cmd = Discv4Command::SetExternalPortMappings(mappings);
+        self.send_to_service(cmd);
+    }
+
     /// Sets the pair in the EIP-868 [`Enr`] of the node.
     ///
     /// If the key already exists, this will update it.
@@ -572,6 +580,47 @@ impl Discv4Service {
         }
     }
 
+    /// Sets the external addresses of the given port mappings in the node record announced in
+    /// discovery
+    ///
+    /// UDP mappings of ports other than the port of the discovery socket are ignored.
+    pub fn set_external_port_mappings(&mut self, mappings: &[PortMapping]) {
+        for mapping in mappings {
+            if mapping.protocol == MappingProtocol::Udp &&
+                mapping.internal_port != self.local_address.port()
+            {
+                continue
+            }
+            self.set_external_ip_addr(mapping.external_addr.ip());
+
+            let port = mapping.external_addr.port();
+            let is_ipv4 = self.local_node_record.address.is_ipv4();
+            match mapping.protocol {
+                MappingProtocol::Udp if self.local_node_record.udp_port != port => {
+                    debug!(target : "discv4",  external_port=?port, "Updating external udp port");
+                    self.local_node_record.udp_port = port;
+                    let _ = if is_ipv4 {
+                        self.local_eip_868_enr.set_udp4(port, &self.secret_key)
+                    } else {
+                        self.local_eip_868_enr.set_udp6(port, &self.secret_key)
+                    };
+                }
+                MappingProtocol::Tcp if self.local_node_record.tcp_port != port => {
+                    debug!(target : "discv4",  external_port=?port, "Updating external tcp port");
+                    self.local_node_record.tcp_port = port;
+                    let _ = if is_ipv4 {
+                        self.local_eip_868_enr.set_tcp4(port, &self.secret_key)
+                    } else {
+                        self.local_eip_868_enr.set_tcp6(port, &self.secret_key)
+                    };
+                }
+                _ => continue,
+            }
+            let mut lock = self.shared_node_record.lock();
+            *lock = self.local_node_record;
+        }
+    }
+
     /// Returns the [PeerId] that identifies this node
     pub fn local_peer_id(&self) -> &PeerId {
         &self.local_node_record.id
@@ -1537,6 +1586,9 @@ impl Discv4Service {
                             let _ = self.local_eip_868_enr.set_tcp6(port, &self.secret_key);
                         }
                     }
+                    Discv4Command::SetExternalPortMappings(mappings) => {
+                        self.set_external_port_mappings(&mappings);
+                    }
                 }
             }
 
@@ -1689,6 +1741,7 @@ pub(crate) async fn receive_loop(udp: Arc<UdpSocket>, tx: IngressSender, local_i
 enum Discv4Command {
     Add(NodeRecord),
     SetTcpPort(u16),
+    SetExternalPortMappings(Vec<PortMapping>),
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
     BanPeer(PeerId),
@@ -2029,26 +2082,6 @@ pub enum DiscoveryUpdate {
     Batch(Vec<DiscoveryUpdate>),
 }
 
-/// Represents a forward-compatible ENR entry for including the forkid in a node record via
-/// EIP-868. Forward compatibility is achieved by allowing trailing fields.
-///
-/// See:
-/// <https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/discovery.go#L27-L38>
-///
-/// for how geth implements ForkId values and forward compatibility.
-#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
-#[rlp(trailing)]
-pub struct EnrForkIdEntry {
-    /// The inner forkid
-    pub fork_id: ForkId,
-}
-
-impl From<ForkId> for EnrForkIdEntry {
-    fn from(fork_id: ForkId) -> Self {
-        Self { fork_id }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2342,6 +2375,81 @@ mod tests {
         let _ = discv4.lookup_self().await;
     }
 
+    /// Spawns a stand-in NAT-PMP gateway with the given external ip that maps every port to the
+    /// port plus one.
+    async fn spawn_natpmp_gateway(external_ip: Ipv4Addr) -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 64];
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                let req = &buf[..len];
+                // version, opcode of the response, result code and seconds since start of epoch
+                let mut resp = vec![0, 128 + req[1], 0, 0, 0, 0, 0, 1];
+                if req[1] == 0 {
+                    resp.extend_from_slice(&external_ip.octets());
+                } else {
+                    let internal_port = u16::from_be_bytes([req[4], req[5]]);
+                    resp.extend_from_slice(&req[4..6]);
+                    resp.extend_from_slice(&(internal_port + 1).to_be_bytes());
+                    resp.extend_from_slice(&req[8..12]);
+                }
+                socket.send_to(&resp, from).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_set_external_port_mappings() {
+        reth_tracing::init_test_tracing();
+
+        let external_ip = Ipv4Addr::new(1, 2, 3, 4);
+        let gateway = spawn_natpmp_gateway(external_ip).await;
+
+        let config = Discv4Config::builder().external_ip_resolver(None).build();
+        let (discv4, mut service) = create_discv4_with_config(config).await;
+        let udp_port = service.local_addr().port();
+        let tcp_port = 30303;
+
+        let mut port_mapping = reth_net_nat::PortMappingInterval::new(
+            NatResolver::NatPmp,
+            [(MappingProtocol::Udp, udp_port), (MappingProtocol::Tcp, tcp_port)],
+            Duration::from_secs(7200),
+        )
+        .unwrap()
+        .with_gateway(gateway);
+        let mut mappings = port_mapping.tick().await.unwrap();
+        assert_eq!(mappings.len(), 2);
+
+        // mappings of another UDP socket are ignored
+        mappings.push(PortMapping {
+            protocol: MappingProtocol::Udp,
+            internal_port: udp_port.wrapping_add(10),
+            external_addr: SocketAddr::new(external_ip.into(), 1),
+            lifetime: Duration::from_secs(7200),
+        });
+
+        discv4.set_external_port_mappings(mappings);
+        poll_fn(|cx| {
+            let _ = service.poll(cx);
+            Poll::Ready(())
+        })
+        .await;
+
+        let record = discv4.node_record();
+        assert_eq!(record.address, IpAddr::V4(external_ip));
+        assert_eq!(record.udp_port, udp_port + 1);
+        assert_eq!(record.tcp_port, tcp_port + 1);
+        assert_eq!(service.local_enr(), record);
+
+        let enr = &service.local_eip_868_enr;
+        assert_eq!(enr.ip4(), Some(external_ip));
+        assert_eq!(enr.udp4(), Some(udp_port + 1));
+        assert_eq!(enr.tcp4(), Some(tcp_port + 1));
+    }
+
     // sends a PING packet with wrong 'to' field and expects a PONG response.
     #[tokio::test(flavor = "multi_thread")]
     async fn test_check_wrong_to() {
@@ -0,0 +1,205 @@
+//! A set of configuration parameters to tune the discv5 service.
+
+use crate::Enr;
+use reth_primitives::{ForkFilter, ForkId};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+/// The default UDP port of the discv5 service.
+///
+/// This is the port commonly used by consensus layer clients.
+pub const DEFAULT_DISCOVERY_V5_PORT: u16 = 9000;
+
+/// The default TCP port advertised in the local ENR.
+pub const DEFAULT_TCP_PORT: u16 = 30303;
+
+/// The default number of discovered nodes the service remembers, see
+/// [`Discv5Config::max_discovered_nodes`].
+pub const DEFAULT_MAX_DISCOVERED_NODES: u32 = 4096;
+
+/// The default ENR key under which the `eth` fork id is stored, see [EIP-868](https://eips.ethereum.org/EIPS/eip-868).
+pub const ETH_ENR_KEY: &[u8] = b"eth";
+
+/// Configuration parameters of the discv5 service.
+#[derive(Clone, Debug)]
+pub struct Discv5Config {
+    /// The socket address the service listens on.
+    ///
+    /// The address is advertised in the local ENR, unless it is unspecified. In that case the
+    /// address is learned from the peers.
+    pub listen_addr: SocketAddr,
+    /// The configuration of the underlying discv5 protocol.
+    pub discv5_config: discv5::Discv5Config,
+    /// Nodes to boot from.
+    pub bootstrap_nodes: Vec<Enr>,
+    /// The rate at which random lookups are triggered.
+    pub lookup_interval: Duration,
+    /// The TCP port advertised in the local ENR, on which the node accepts RLPx connections.
+    pub tcp_port: u16,
+    /// The ENR key that identifies nodes of the execution layer and holds their fork id.
+    ///
+    /// Discovered nodes without this key are ignored, which filters out nodes of other networks
+    /// that share the DHT, for example consensus layer clients.
+    pub enr_fork_key: Vec<u8>,
+    /// The fork id to advertise in the local ENR under [`Self::enr_fork_key`].
+    pub fork_id: Option<ForkId>,
+    /// If set, discovered nodes with a fork id that is incompatible with this filter are ignored.
+    pub fork_filter: Option<ForkFilter>,
+    /// The maximum number of reported nodes the service remembers.
+    ///
+    /// Nodes are only reported once while they're remembered. If the limit is reached, the least
+    /// recently reported node is forgotten and will be reported again once it's rediscovered.
+    pub max_discovered_nodes: u32,
+}
+
+impl Discv5Config {
+    /// Returns a new default builder instance
+    pub fn builder() -> Discv5ConfigBuilder {
+        Default::default()
+    }
+
+    /// Returns whether a node that advertises the given fork id in its ENR should be reported.
+    pub fn is_fork_id_accepted(&self, fork_id: Option<ForkId>) -> bool {
+        is_fork_id_accepted(self.fork_filter.as_ref(), fork_id)
+    }
+}
+
+impl Default for Discv5Config {
+    fn default() -> Self {
+        Discv5ConfigBuilder::default().build()
+    }
+}
+
+/// Builder type for [`Discv5Config`]
+#[derive(Clone, Debug)]
+pub struct Discv5ConfigBuilder {
+    listen_addr: SocketAddr,
+    discv5_config: Option<discv5::Discv5Config>,
+    bootstrap_nodes: Vec<Enr>,
+    lookup_interval: Duration,
+    tcp_port: u16,
+    enr_fork_key: Vec<u8>,
+    fork_id: Option<ForkId>,
+    fork_filter: Option<ForkFilter>,
+    max_discovered_nodes: u32,
+}
+
+impl Discv5ConfigBuilder {
+    /// Sets the socket the service listens on.
+    pub fn listen_addr(&mut self, listen_addr: SocketAddr) -> &mut Self {
+        self.listen_addr = listen_addr;
+        self
+    }
+
+    /// Sets the configuration of the underlying discv5 protocol.
+    ///
+    /// Its listen config must match the configured [`Self::listen_addr`].
+    pub fn discv5_config(&mut self, config: discv5::Discv5Config) -> &mut Self {
+        self.discv5_config = Some(config);
+        self
+    }
+
+    /// Adds a boot node
+    pub fn add_boot_node(&mut self, node: Enr) -> &mut Self {
+        self.bootstrap_nodes.push(node);
+        self
+    }
+
+    /// Adds multiple boot nodes
+    pub fn add_boot_nodes(&mut self, nodes: impl IntoIterator<Item = Enr>) -> &mut Self {
+        self.bootstrap_nodes.extend(nodes);
+        self
+    }
+
+    /// Sets the rate at which random lookups are triggered.
+    pub fn lookup_interval(&mut self, lookup_interval: Duration) -> &mut Self {
+        self.lookup_interval = lookup_interval;
+        self
+    }
+
+    /// Sets the TCP port advertised in the local ENR.
+    pub fn tcp_port(&mut self, tcp_port: u16) -> &mut Self {
+        self.tcp_port = tcp_port;
+        self
+    }
+
+    /// Sets the ENR key that holds the fork id of execution layer nodes.
+    pub fn enr_fork_key(&mut self, key: impl AsRef<[u8]>) -> &mut Self {
+        self.enr_fork_key = key.as_ref().to_vec();
+        self
+    }
+
+    /// Sets the fork id to advertise in the local ENR.
+    pub fn fork_id(&mut self, fork_id: ForkId) -> &mut Self {
+        self.fork_id = Some(fork_id);
+        self
+    }
+
+    /// Sets the filter that discovered nodes must be compatible with.
+    pub fn fork_filter(&mut self, fork_filter: ForkFilter) -> &mut Self {
+        self.fork_filter = Some(fork_filter);
+        self
+    }
+
+    /// Sets the maximum number of reported nodes the service remembers.
+    pub fn max_discovered_nodes(&mut self, max_discovered_nodes: u32) -> &mut Self {
+        self.max_discovered_nodes = max_discovered_nodes;
+        self
+    }
+
+    /// Returns the configured [`Discv5Config`]
+    pub fn build(&self) -> Discv5Config {
+        let discv5_config = self.discv5_config.clone().unwrap_or_else(|| {
+            let listen_config =
+                discv5::ListenConfig::from_ip(self.listen_addr.ip(), self.listen_addr.port());
+            discv5::Discv5ConfigBuilder::new(listen_config).build()
+        });
+
+        Discv5Config {
+            listen_addr: self.listen_addr,
+            discv5_config,
+            bootstrap_nodes: self.bootstrap_nodes.clone(),
+            lookup_interval: self.lookup_interval,
+            tcp_port: self.tcp_port,
+            enr_fork_key: self.enr_fork_key.clone(),
+            fork_id: self.fork_id,
+            fork_filter: self.fork_filter.clone(),
+            max_discovered_nodes: self.max_discovered_nodes,
+        }
+    }
+}
+
+impl Default for Discv5ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            listen_addr: SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                DEFAULT_DISCOVERY_V5_PORT,
+            ),
+            discv5_config: None,
+            bootstrap_nodes: Vec::new(),
+            lookup_interval: Duration::from_secs(20),
+            tcp_port: DEFAULT_TCP_PORT,
+            enr_fork_key: ETH_ENR_KEY.to_vec(),
+            fork_id: None,
+            fork_filter: None,
+            max_discovered_nodes: DEFAULT_MAX_DISCOVERED_NODES,
+        }
+    }
+}
+
+/// Returns whether a node that advertises the given fork id in its ENR is compatible with the
+/// filter.
+pub(crate) fn is_fork_id_accepted(
+    fork_filter: Option<&ForkFilter>,
+    fork_id: Option<ForkId>,
+) -> bool {
+    match (fork_filter, fork_id) {
+        (Some(filter), Some(fork_id)) => filter.validate(fork_id).is_ok(),
+        // the entry exists but can't be decoded
+        (Some(_), None) => false,
+        (None, _) => true,
+    }
+}
@@ -0,0 +1,519 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxzy/reth/issues/"
+)]
+#![warn(missing_docs, unused_crate_dependencies)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![doc(test(
+    no_crate_inject,
+    attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
+))]
+
+//! Discovery v5 support: <https://github.com/ethereum/devp2p/blob/master/discv5/discv5.md>
+//!
+//! This wraps the [discv5](https://github.com/sigp/discv5) implementation, which is also used by
+//! consensus layer clients, so that the execution layer can take part in the same DHT.
+//!
+//! This consists of a [`Discv5`] and [`Discv5Service`] pair. The underlying discv5 server drives
+//! the UDP socket, the service periodically looks up random nodes and translates discovered ENRs
+//! into [`Discv5Update`]s that listeners will receive. The [`Discv5`] handle serves as the frontend
+//! to interact with the server.
+//!
+//! Only nodes that advertise the configured [fork id key](Discv5Config::enr_fork_key), `eth` by
+//! default, are reported. If a [`ForkFilter`](reth_primitives::ForkFilter) is configured, nodes
+//! with an incompatible fork id are ignored as well.
+
+use discv5::{
+    enr::{CombinedKey, EnrBuilder, EnrPublicKey, NodeId},
+    Discv5Event,
+};
+use parking_lot::RwLock;
+use reth_primitives::{
+    bytes::Bytes, keccak256, EnrForkIdEntry, ForkFilter, ForkId, ForkTransition, Head, NodeRecord,
+    PeerId,
+};
+use reth_rlp::{Decodable, Encodable};
+use schnellru::{ByLength, LruMap};
+use secp256k1::SecretKey;
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+use tokio::{
+    sync::mpsc::{self, error::TrySendError},
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, trace};
+
+pub mod config;
+pub mod error;
+
+pub use config::{
+    Discv5Config, Discv5ConfigBuilder, DEFAULT_DISCOVERY_V5_PORT, DEFAULT_MAX_DISCOVERED_NODES,
+    ETH_ENR_KEY,
+};
+pub use error::Discv5Error;
+
+/// The ENR type of the discv5 protocol.
+pub type Enr = discv5::Enr;
+
+/// The frontend to interact with the discv5 service.
+#[derive(Clone)]
+pub struct Discv5 {
+    /// The underlying discv5 server.
+    inner: Arc<discv5::Discv5>,
+    /// The address the server listens on.
+    local_addr: SocketAddr,
+    /// The ENR key that holds the fork id.
+    enr_fork_key: Vec<u8>,
+    /// The filter discovered nodes are checked against, shared with the [`Discv5Service`].
+    fork_filter: Arc<RwLock<Option<ForkFilter>>>,
+}
+
+// === impl Discv5 ===
+
+impl Discv5 {
+    /// Binds a new UDP socket and starts the discv5 server, returning the handle and the service
+    /// that needs to be spawned.
+    ///
+    /// The local ENR is signed with the given secret key, so the node's discv5 identity matches
+    /// its RLPx identity.
+    pub async fn bind(
+        secret_key: SecretKey,
+        config: Discv5Config,
+    ) -> Result<(Self, Discv5Service), Discv5Error> {
+        let key = CombinedKey::secp256k1_from_bytes(&mut secret_key.secret_bytes())
+            .map_err(Discv5Error::InvalidSecretKey)?;
+        let local_enr = build_local_enr(&key, &config)?;
+
+        let mut discv5 = discv5::Discv5::new(local_enr, key, config.discv5_config.clone())
+            .map_err(Discv5Error::Init)?;
+        discv5.start().await.map_err(Discv5Error::Start)?;
+        trace!(target: "discv5", local_addr = ?config.listen_addr, "started discv5 server");
+
+        for node in &config.bootstrap_nodes {
+            if let Err(err) = discv5.add_enr(node.clone()) {
+                debug!(target: "discv5", ?node, %err, "failed to add boot node");
+            }
+        }
+
+        let events = discv5.event_stream().await.map_err(Discv5Error::Start)?;
+        let discv5 = Self {
+            inner: Arc::new(discv5),
+            local_addr: config.listen_addr,
+            enr_fork_key: config.enr_fork_key.clone(),
+            fork_filter: Arc::new(RwLock::new(config.fork_filter.clone())),
+        };
+        let service = Discv5Service {
+            discv5: discv5.clone(),
+            events,
+            discovered: LruMap::new(ByLength::new(config.max_discovered_nodes)),
+            config,
+            update_listeners: Vec::new(),
+        };
+        Ok((discv5, service))
+    }
+
+    /// Returns the address the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the local ENR of the node.
+    pub fn local_enr(&self) -> Enr {
+        self.inner.local_enr()
+    }
+
+    /// Updates the fork id advertised in the local ENR.
+    pub fn set_fork_id(&self, fork_id: ForkId) {
+        let Ok(key) = std::str::from_utf8(&self.enr_fork_key) else {
+            debug!(target: "discv5", key = ?self.enr_fork_key, "fork id key is not valid utf-8");
+            return
+        };
+        let mut rlp = Vec::new();
+        EnrForkIdEntry::from(fork_id).encode(&mut rlp);
+        if let Err(err) = self.inner.enr_insert(key, &rlp) {
+            debug!(target: "discv5", ?fork_id, ?err, "failed to update fork id of local ENR");
+        }
+    }
+
+    /// Updates the external UDP or TCP socket advertised in the local ENR, e.g. after a port was
+    /// mapped on the gateway.
+    pub fn set_external_socket(&self, socket: SocketAddr, is_tcp: bool) {
+        if !self.inner.update_local_enr_socket(socket, is_tcp) {
+            debug!(target: "discv5", ?socket, is_tcp, "failed to update socket of local ENR");
+        }
+    }
+
+    /// Updates the head of the fork filter that discovered nodes are checked against.
+    ///
+    /// Returns a [`ForkTransition`] if the new head activated another fork. This is a no-op if no
+    /// [fork filter](Discv5Config::fork_filter) is configured.
+    pub fn set_head(&self, head: Head) -> Option<ForkTransition> {
+        self.fork_filter.write().as_mut()?.set_head(head)
+    }
+
+    /// Adds the node to the routing table.
+    pub fn add_node(&self, enr: Enr) {
+        if let Err(err) = self.inner.add_enr(enr) {
+            debug!(target: "discv5", %err, "failed to add node");
+        }
+    }
+
+    /// Returns the number of peers the server is connected to.
+    pub fn num_connected_peers(&self) -> usize {
+        self.inner.connected_peers()
+    }
+
+    /// Adds the ip to the ban list indefinitely
+    pub fn ban_ip(&self, ip: IpAddr) {
+        self.inner.ban_ip(ip, None);
+    }
+
+    /// Adds the peer and its ip to the ban list indefinitely
+    pub fn ban(&self, peer_id: PeerId, ip: IpAddr) {
+        self.inner.ban_node(&node_id(peer_id), None);
+        self.inner.ban_ip(ip, None);
+    }
+
+    /// Looks up a random node in the DHT.
+    ///
+    /// All nodes found on the way are reported via [`Discv5Update`]s.
+    async fn lookup_random(&self) {
+        match self.inner.find_node(NodeId::random()).await {
+            Ok(nodes) => trace!(target: "discv5", found = nodes.len(), "lookup finished"),
+            Err(err) => debug!(target: "discv5", ?err, "lookup failed"),
+        }
+    }
+}
+
+impl fmt::Debug for Discv5 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Discv5").field("local_enr", &self.local_enr()).finish_non_exhaustive()
+    }
+}
+
+/// Drives the lookups of the discv5 server and reports discovered nodes.
+#[must_use = "Service does nothing unless spawned"]
+pub struct Discv5Service {
+    /// The handle to the discv5 server.
+    discv5: Discv5,
+    /// Events emitted by the discv5 server.
+    events: mpsc::Receiver<Discv5Event>,
+    /// The configuration of the service.
+    config: Discv5Config,
+    /// The most recently reported nodes, bounded by [`Discv5Config::max_discovered_nodes`].
+    discovered: LruMap<NodeId, PeerId, ByLength>,
+    /// All subscribers for table updates
+    update_listeners: Vec<mpsc::Sender<Discv5Update>>,
+}
+
+// === impl Discv5Service ===
+
+impl Discv5Service {
+    /// Returns the handle to the discv5 server.
+    pub fn handle(&self) -> Discv5 {
+        self.discv5.clone()
+    }
+
+    /// Creates a new channel for [`Discv5Update`]s
+    pub fn update_stream(&mut self) -> ReceiverStream<Discv5Update> {
+        let (tx, rx) = mpsc::channel(512);
+        self.update_listeners.push(tx);
+        ReceiverStream::new(rx)
+    }
+
+    /// Spawns this service onto a new task
+    ///
+    /// Note: requires a running runtime
+    pub fn spawn(mut self) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            let mut lookup_interval = tokio::time::interval(self.config.lookup_interval);
+            let mut lookup: Option<JoinHandle<()>> = None;
+
+            loop {
+                tokio::select! {
+                    _ = lookup_interval.tick() => {
+                        // only one lookup at a time
+                        if lookup.as_ref().map_or(true, JoinHandle::is_finished) {
+                            let discv5 = self.discv5.clone();
+                            lookup = Some(tokio::task::spawn(async move {
+                                discv5.lookup_random().await
+                            }));
+                        }
+                    }
+                    event = self.events.recv() => match event {
+                        Some(event) => self.on_event(event),
+                        None => break,
+                    },
+                }
+            }
+
+            if let Some(lookup) = lookup {
+                lookup.abort();
+            }
+        })
+    }
+
+    fn on_event(&mut self, event: Discv5Event) {
+        match event {
+            Discv5Event::Discovered(enr) | Discv5Event::SessionEstablished(enr, _) => {
+                self.on_discovered(&enr)
+            }
+            Discv5Event::NodeInserted { replaced: Some(node_id), .. } => {
+                if let Some(peer_id) = self.discovered.remove(&node_id) {
+                    self.notify(Discv5Update::Removed(peer_id));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Reports the node if it passes the configured filters and wasn't reported yet.
+    fn on_discovered(&mut self, enr: &Enr) {
+        if self.discovered.get(&enr.node_id()).is_some() {
+            return
+        }
+        let accepted = {
+            let fork_filter = self.discv5.fork_filter.read();
+            filter_enr(&self.config.enr_fork_key, fork_filter.as_ref(), enr)
+        };
+        let Some((record, fork_id)) = accepted else {
+            trace!(target: "discv5", ?enr, "ignoring discovered node");
+            return
+        };
+        self.discovered.insert(enr.node_id(), record.id);
+        self.notify(Discv5Update::Added { record, fork_id });
+    }
+
+    /// Sends the update to all listeners.
+    fn notify(&mut self, update: Discv5Update) {
+        self.update_listeners.retain_mut(|listener| match listener.try_send(update.clone()) {
+            Ok(()) => true,
+            Err(err) => match err {
+                TrySendError::Full(_) => true,
+                TrySendError::Closed(_) => false,
+            },
+        });
+    }
+}
+
+impl fmt::Debug for Discv5Service {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Discv5Service")
+            .field("discv5", &self.discv5)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Updates of the nodes discovered via discv5.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discv5Update {
+    /// A new node was discovered.
+    Added {
+        /// The RLPx endpoint of the node.
+        record: NodeRecord,
+        /// The fork id the node advertises in its ENR.
+        fork_id: Option<ForkId>,
+    },
+    /// A previously discovered node was removed from the routing table.
+    Removed(PeerId),
+}
+
+/// Returns the [`NodeRecord`] of the given ENR.
+///
+/// Returns `None` if the ENR doesn't advertise an address, a TCP and a UDP port, or isn't signed
+/// with a secp256k1 key.
+pub fn enr_to_node_record(enr: &Enr) -> Option<NodeRecord> {
+    // the uncompressed encoding omits the `0x04` prefix
+    let public_key = enr.public_key().encode_uncompressed();
+    if public_key.len() != PeerId::len_bytes() {
+        return None
+    }
+
+    let record = NodeRecord {
+        address: enr.ip4().map(IpAddr::from).or_else(|| enr.ip6().map(IpAddr::from))?,
+        tcp_port: enr.tcp4().or_else(|| enr.tcp6())?,
+        udp_port: enr.udp4().or_else(|| enr.udp6())?,
+        id: PeerId::from_slice(&public_key),
+    }
+    .into_ipv4_mapped();
+    Some(record)
+}
+
+/// Returns the [`NodeRecord`] and fork id of the given ENR, if it advertises the fork id key and
+/// passes the fork filter.
+fn filter_enr(
+    enr_fork_key: &[u8],
+    fork_filter: Option<&ForkFilter>,
+    enr: &Enr,
+) -> Option<(NodeRecord, Option<ForkId>)> {
+    let mut entry = enr.get_raw_rlp(enr_fork_key)?;
+    let fork_id = EnrForkIdEntry::decode(&mut entry).ok().map(|entry| entry.fork_id);
+    if !config::is_fork_id_accepted(fork_filter, fork_id) {
+        return None
+    }
+    Some((enr_to_node_record(enr)?, fork_id))
+}
+
+/// Builds the local ENR from the configured endpoint and fork id.
+fn build_local_enr(key: &CombinedKey, config: &Discv5Config) -> Result<Enr, Discv5Error> {
+    let mut builder = EnrBuilder::new("v4");
+    let ip = config.listen_addr.ip();
+    if ip.is_ipv4() {
+        builder.tcp4(config.tcp_port);
+    } else {
+        builder.tcp6(config.tcp_port);
+    }
+    if !ip.is_unspecified() {
+        builder.ip(ip);
+        if ip.is_ipv4() {
+            builder.udp4(config.listen_addr.port());
+        } else {
+            builder.udp6(config.listen_addr.port());
+        }
+    }
+    if let Some(fork_id) = config.fork_id {
+        let mut rlp = Vec::new();
+        EnrForkIdEntry::from(fork_id).encode(&mut rlp);
+        builder.add_value_rlp(&config.enr_fork_key, Bytes::from(rlp));
+    }
+    Ok(builder.build(key)?)
+}
+
+/// Returns the discv5 [`NodeId`] of the peer, which is the hash of its public key.
+fn node_id(peer_id: PeerId) -> NodeId {
+    NodeId::new(&keccak256(peer_id).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{ForkHash, Hardfork, Head, MAINNET};
+    use secp256k1::SECP256K1;
+    use std::{net::Ipv4Addr, time::Duration};
+    use tokio_stream::StreamExt;
+
+    fn enr_with_fork_id(key: &CombinedKey, fork_id: Option<ForkId>) -> Enr {
+        let mut builder = EnrBuilder::new("v4");
+        builder.ip4(Ipv4Addr::new(10, 0, 0, 1)).tcp4(30303).udp4(9000);
+        if let Some(fork_id) = fork_id {
+            let mut rlp = Vec::new();
+            EnrForkIdEntry::from(fork_id).encode(&mut rlp);
+            builder.add_value_rlp(ETH_ENR_KEY, Bytes::from(rlp));
+        }
+        builder.build(key).unwrap()
+    }
+
+    #[test]
+    fn test_enr_to_node_record() {
+        let secret_key = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let key = CombinedKey::secp256k1_from_bytes(&mut secret_key.secret_bytes()).unwrap();
+        let enr = enr_with_fork_id(&key, None);
+
+        let record = enr_to_node_record(&enr).unwrap();
+        let public_key = secret_key.public_key(SECP256K1);
+        assert_eq!(record.id, PeerId::from_slice(&public_key.serialize_uncompressed()[1..]));
+        assert_eq!(record.address, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(record.tcp_port, 30303);
+        assert_eq!(record.udp_port, 9000);
+        assert_eq!(node_id(record.id), enr.node_id());
+    }
+
+    #[test]
+    fn test_filter_enr_fork_id() {
+        let head = Head { number: 17_034_870, timestamp: 1_681_338_455, ..Default::default() };
+        let filter = MAINNET.fork_filter(head);
+        let key = CombinedKey::generate_secp256k1();
+
+        // nodes that don't advertise the `eth` key are ignored
+        assert!(filter_enr(ETH_ENR_KEY, Some(&filter), &enr_with_fork_id(&key, None)).is_none());
+
+        let fork_id = Hardfork::Shanghai.fork_id(&MAINNET).unwrap();
+        let enr = enr_with_fork_id(&key, Some(fork_id));
+        let (_, decoded) = filter_enr(ETH_ENR_KEY, Some(&filter), &enr).unwrap();
+        assert_eq!(decoded, Some(fork_id));
+
+        let other = ForkId { hash: ForkHash([0xde, 0xad, 0xbe, 0xef]), next: 0 };
+        let enr = enr_with_fork_id(&key, Some(other));
+        assert!(filter_enr(ETH_ENR_KEY, Some(&filter), &enr).is_none());
+
+        // without a filter any fork id is accepted
+        assert!(filter_enr(ETH_ENR_KEY, None, &enr).is_some());
+    }
+
+    /// Returns a config that listens on a free local port.
+    fn local_config(fork_id: ForkId) -> Discv5Config {
+        let port = std::net::UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        Discv5Config::builder()
+            .listen_addr(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port))
+            .lookup_interval(Duration::from_millis(100))
+            .fork_id(fork_id)
+            .build()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_discover_two_nodes() {
+        let fork_id = Hardfork::Shanghai.fork_id(&MAINNET).unwrap();
+
+        let sk_1 = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let (discv5_1, mut service_1) = Discv5::bind(sk_1, local_config(fork_id)).await.unwrap();
+        let mut updates_1 = service_1.update_stream();
+        let _handle_1 = service_1.spawn();
+
+        let sk_2 = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let mut config_2 = local_config(fork_id);
+        config_2.bootstrap_nodes.push(discv5_1.local_enr());
+        let (discv5_2, mut service_2) = Discv5::bind(sk_2, config_2).await.unwrap();
+        let mut updates_2 = service_2.update_stream();
+        let _handle_2 = service_2.spawn();
+
+        let record_1 = enr_to_node_record(&discv5_1.local_enr()).unwrap();
+        let record_2 = enr_to_node_record(&discv5_2.local_enr()).unwrap();
+
+        // the boot node is discovered by the lookup of the second node
+        let update =
+            tokio::time::timeout(Duration::from_secs(10), updates_2.next()).await.unwrap().unwrap();
+        assert_eq!(update, Discv5Update::Added { record: record_1, fork_id: Some(fork_id) });
+
+        // and learns about the second node from the established session
+        let update =
+            tokio::time::timeout(Duration::from_secs(10), updates_1.next()).await.unwrap().unwrap();
+        assert_eq!(update, Discv5Update::Added { record: record_2, fork_id: Some(fork_id) });
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_fork_id_and_head() {
+        let head = Head { number: 17_034_870, timestamp: 1_681_338_455, ..Default::default() };
+        // the head before shanghai
+        let filter = MAINNET.fork_filter(Head { timestamp: 0, ..head });
+        let fork_id = filter.current();
+        let mut config = local_config(fork_id);
+        config.fork_filter = Some(filter);
+
+        let sk = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let (discv5, _service) = Discv5::bind(sk, config).await.unwrap();
+
+        let (_, advertised) = filter_enr(ETH_ENR_KEY, None, &discv5.local_enr()).unwrap();
+        assert_eq!(advertised, Some(fork_id));
+
+        // shanghai is activated by the new head
+        let transition = discv5.set_head(head).unwrap();
+        let shanghai = Hardfork::Shanghai.fork_id(&MAINNET).unwrap();
+        assert_eq!(transition.current, shanghai);
+
+        discv5.set_fork_id(transition.current);
+        let (_, advertised) = filter_enr(ETH_ENR_KEY, None, &discv5.local_enr()).unwrap();
+        assert_eq!(advertised, Some(shanghai));
+        assert_eq!(discv5.local_enr().seq(), 2);
+    }
+}
@@ -0,0 +1,2 @@
+/// A downloader for the state at a given root over the `snap` protocol.
+pub mod state;
@@ -0,0 +1,726 @@
+//! Downloads the state at a given root over the `snap` protocol.
+//!
+//! The downloader fetches all accounts of the state trie in consecutive ranges, followed by the
+//! storage slots and bytecodes of the accounts it received. Every range is verified against the
+//! state root (or the storage root of its account) with the Merkle proof attached to the response,
+//! and every bytecode against its hash.
+//!
+//! The `snap` protocol only serves the hashed state. It does not carry the preimages of the hashed
+//! addresses and storage keys, so the downloaded chunks can fill the hashed state and bytecode
+//! tables, but not the plain state.
+//!
+//! On a node, the requests are sent with the `SnapFetchClient` of the network, which is returned
+//! when the `snap` protocol is registered with the network builder. The downloaded chunks are
+//! written to the database by the `SnapStateStage` of the pipeline.
+
+use futures::Stream;
+use futures_util::FutureExt;
+use reth_interfaces::p2p::{
+    error::PeerRequestResult,
+    snap::{
+        client::{
+            AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, SnapClient,
+            StorageRanges,
+        },
+        downloader::{SnapStateChunk, StateDownloader},
+    },
+};
+use reth_primitives::{keccak256, Bytecode, Bytes, StorageEntry, H256, U256};
+use reth_rlp::{Decodable, DecodeError, Encodable};
+use reth_trie::{account::EthAccount, verify_range_proof, RangeProofError};
+use std::{
+    collections::{HashSet, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use thiserror::Error;
+use tokio::time::Sleep;
+use tracing::{debug, trace};
+
+/// The default soft limit for the size of a response in bytes.
+pub const DEFAULT_RESPONSE_BYTES: u64 = 512 * 1024;
+
+/// The default maximum number of accounts to request storage ranges for at once.
+pub const DEFAULT_MAX_STORAGE_ACCOUNTS: usize = 128;
+
+/// The default maximum number of bytecodes to request at once.
+pub const DEFAULT_MAX_BYTECODES: usize = 64;
+
+/// The delay before a failed request is sent again, doubled after every consecutive failure.
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// The maximum delay before a failed request is sent again.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Downloads the entire state at a given root.
+///
+/// Requests are sent one at a time. Failed requests are sent again after a delay that grows with
+/// every consecutive failure, so the stream only ends once the state has been downloaded
+/// completely.
+///
+/// Responses that violate the protocol, for example ranges that don't match their proof, are
+/// reported to the network. Empty responses are not, since that is how peers signal that they
+/// don't serve the requested state root (anymore).
+#[must_use = "Stream does nothing unless polled"]
+#[derive(Debug)]
+pub struct SnapStateDownloader<C: SnapClient> {
+    /// The snap client.
+    client: Arc<C>,
+    /// The root of the state to download.
+    state_root: H256,
+    /// The soft limit for the size of a response in bytes.
+    response_bytes: u64,
+    /// The maximum number of accounts to request storage ranges for at once.
+    max_storage_accounts: usize,
+    /// The maximum number of bytecodes to request at once.
+    max_bytecodes: usize,
+    /// The origin of the next account range, `None` once all accounts were downloaded.
+    next_account: Option<H256>,
+    /// Accounts whose storage still needs to be downloaded.
+    pending_storages: VecDeque<PendingStorage>,
+    /// Bytecodes that still need to be downloaded.
+    pending_bytecodes: Vec<H256>,
+    /// All bytecode hashes that were queued so far.
+    seen_bytecodes: HashSet<H256>,
+    /// The id of the next request.
+    next_request_id: u64,
+    /// The request in progress.
+    in_progress: Option<SnapStateRequest<C>>,
+    /// Verified chunks that can be returned.
+    queued_chunks: VecDeque<SnapStateChunk>,
+    /// The delay before the next request is sent, set after a failed request.
+    retry_delay: Option<Pin<Box<Sleep>>>,
+    /// The delay applied after the next failed request.
+    next_retry_delay: Duration,
+}
+
+impl<C: SnapClient> SnapStateDownloader<C> {
+    /// Creates a new downloader for the state with the given root.
+    pub fn new(client: Arc<C>, state_root: H256) -> Self {
+        Self {
+            client,
+            state_root,
+            response_bytes: DEFAULT_RESPONSE_BYTES,
+            max_storage_accounts: DEFAULT_MAX_STORAGE_ACCOUNTS,
+            max_bytecodes: DEFAULT_MAX_BYTECODES,
+            next_account: Some(H256::zero()),
+            pending_storages: VecDeque::new(),
+            pending_bytecodes: Vec::new(),
+            seen_bytecodes: HashSet::new(),
+            next_request_id: 0,
+            in_progress: None,
+            queued_chunks: VecDeque::new(),
+            retry_delay: None,
+            next_retry_delay: MIN_RETRY_DELAY,
+        }
+    }
+
+    /// Sets the soft limit for the size of a response in bytes.
+    pub fn with_response_bytes(mut self, response_bytes: u64) -> Self {
+        self.response_bytes = response_bytes;
+        self
+    }
+
+    /// Sets the maximum number of accounts to request storage ranges for at once.
+    pub fn with_max_storage_accounts(mut self, max_storage_accounts: usize) -> Self {
+        self.max_storage_accounts = max_storage_accounts.max(1);
+        self
+    }
+
+    /// Sets the maximum number of bytecodes to request at once.
+    pub fn with_max_bytecodes(mut self, max_bytecodes: usize) -> Self {
+        self.max_bytecodes = max_bytecodes.max(1);
+        self
+    }
+
+    /// Returns the root of the state that is downloaded.
+    pub fn state_root(&self) -> H256 {
+        self.state_root
+    }
+
+    fn next_request_id(&mut self) -> u64 {
+        let id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+        id
+    }
+
+    /// Returns the next request to send, or `None` if the state has been downloaded.
+    ///
+    /// Storage ranges and bytecodes are requested before the next account range to bound the
+    /// amount of pending work.
+    fn next_request(&mut self) -> Option<SnapStateRequest<C>> {
+        if let Some(first) = self.pending_storages.front() {
+            // the starting hash only applies to the first account, so a partially downloaded
+            // storage trie is requested on its own
+            let count = if first.start.is_zero() {
+                self.pending_storages
+                    .iter()
+                    .take(self.max_storage_accounts)
+                    .take_while(|storage| storage.start.is_zero())
+                    .count()
+            } else {
+                1
+            };
+            let accounts = self.pending_storages.drain(..count).collect::<Vec<_>>();
+            let start = accounts[0].start;
+            let starting_hash =
+                if start.is_zero() { Bytes::default() } else { start.as_bytes().to_vec().into() };
+            let request = GetStorageRanges {
+                request_id: self.next_request_id(),
+                root_hash: self.state_root,
+                account_hashes: accounts.iter().map(|storage| storage.hashed_address).collect(),
+                starting_hash,
+                limit_hash: Bytes::default(),
+                response_bytes: self.response_bytes,
+            };
+            trace!(
+                target: "downloaders::snap",
+                accounts = accounts.len(),
+                "Requesting storage ranges"
+            );
+            let fut = self.client.get_storage_ranges(request);
+            return Some(SnapStateRequest::StorageRanges { accounts, fut })
+        }
+
+        if !self.pending_bytecodes.is_empty() {
+            let count = self.pending_bytecodes.len().min(self.max_bytecodes);
+            let hashes = self.pending_bytecodes.split_off(self.pending_bytecodes.len() - count);
+            let request = GetByteCodes {
+                request_id: self.next_request_id(),
+                hashes: hashes.clone(),
+                response_bytes: self.response_bytes,
+            };
+            trace!(target: "downloaders::snap", codes = hashes.len(), "Requesting bytecodes");
+            let fut = self.client.get_byte_codes(request);
+            return Some(SnapStateRequest::ByteCodes { hashes, fut })
+        }
+
+        let origin = self.next_account?;
+        let request = GetAccountRange {
+            request_id: self.next_request_id(),
+            root_hash: self.state_root,
+            starting_hash: origin,
+            limit_hash: H256::repeat_byte(0xff),
+            response_bytes: self.response_bytes,
+        };
+        trace!(target: "downloaders::snap", ?origin, "Requesting account range");
+        let fut = self.client.get_account_range(request);
+        Some(SnapStateRequest::AccountRange { origin, fut })
+    }
+
+    /// Puts the work of a failed request back into the queue and delays the next request.
+    fn retry(&mut self, request: SnapStateRequest<C>) {
+        self.retry_delay = Some(Box::pin(tokio::time::sleep(self.next_retry_delay)));
+        self.next_retry_delay = (self.next_retry_delay * 2).min(MAX_RETRY_DELAY);
+
+        match request {
+            // the origin only advances once a range was verified
+            SnapStateRequest::AccountRange { .. } => {}
+            SnapStateRequest::StorageRanges { accounts, .. } => {
+                for storage in accounts.into_iter().rev() {
+                    self.pending_storages.push_front(storage);
+                }
+            }
+            SnapStateRequest::ByteCodes { hashes, .. } => self.pending_bytecodes.extend(hashes),
+        }
+    }
+
+    /// Verifies the response to the given request and queues the downloaded chunks.
+    fn on_response(
+        &mut self,
+        request: &SnapStateRequest<C>,
+        response: SnapStateResponse,
+    ) -> Result<(), InvalidSnapResponse> {
+        match (request, response) {
+            (
+                SnapStateRequest::AccountRange { origin, .. },
+                SnapStateResponse::AccountRange(response),
+            ) => self.on_account_range(*origin, response),
+            (
+                SnapStateRequest::StorageRanges { accounts, .. },
+                SnapStateResponse::StorageRanges(response),
+            ) => self.on_storage_ranges(accounts, response),
+            (
+                SnapStateRequest::ByteCodes { hashes, .. },
+                SnapStateResponse::ByteCodes(response),
+            ) => self.on_byte_codes(hashes, response),
+            _ => unreachable!("response matches the request"),
+        }
+    }
+
+    fn on_account_range(
+        &mut self,
+        origin: H256,
+        response: AccountRange,
+    ) -> Result<(), InvalidSnapResponse> {
+        let leaves = response
+            .accounts
+            .iter()
+            .map(|data| {
+                let account = EthAccount::from(data.body.into_account())
+                    .with_storage_root(data.body.storage_root);
+                let mut rlp = Vec::new();
+                account.encode(&mut rlp);
+                (data.hash, rlp)
+            })
+            .collect::<Vec<_>>();
+        // peers that don't serve the root respond without accounts and proof
+        if leaves.is_empty() && response.proof.is_empty() {
+            return Err(InvalidSnapResponse::EmptyResponse)
+        }
+        let has_more = verify_range_proof(self.state_root, origin, &leaves, &response.proof)?;
+
+        let Some(last) = response.accounts.last() else {
+            // an empty range is only valid if there are no accounts past the origin
+            if has_more {
+                return Err(InvalidSnapResponse::MissingRange)
+            }
+            self.next_account = None;
+            return Ok(())
+        };
+        self.next_account = if has_more { next_hash(last.hash) } else { None };
+
+        let mut accounts = Vec::with_capacity(response.accounts.len());
+        for data in response.accounts {
+            if data.body.has_storage() {
+                self.pending_storages.push_back(PendingStorage {
+                    hashed_address: data.hash,
+                    storage_root: data.body.storage_root,
+                    start: H256::zero(),
+                });
+            }
+            if data.body.has_bytecode() && self.seen_bytecodes.insert(data.body.code_hash) {
+                self.pending_bytecodes.push(data.body.code_hash);
+            }
+            accounts.push((data.hash, data.body.into_account()));
+        }
+        self.queued_chunks.push_back(SnapStateChunk::Accounts(accounts));
+        Ok(())
+    }
+
+    fn on_storage_ranges(
+        &mut self,
+        accounts: &[PendingStorage],
+        response: StorageRanges,
+    ) -> Result<(), InvalidSnapResponse> {
+        let StorageRanges { mut slots, proof, .. } = response;
+        // the proof of an empty range past the last slot is sent without slots
+        if slots.is_empty() && !proof.is_empty() {
+            slots.push(Vec::new());
+        }
+        if slots.is_empty() {
+            return Err(InvalidSnapResponse::EmptyResponse)
+        }
+        if slots.len() > accounts.len() {
+            return Err(InvalidSnapResponse::TooManyItems)
+        }
+
+        // verify all ranges before queueing any of them
+        let last_index = slots.len() - 1;
+        let mut chunks = Vec::with_capacity(slots.len());
+        let mut unfinished = None;
+        for (index, (storage, slots)) in accounts.iter().zip(slots).enumerate() {
+            let origin = if index == 0 { storage.start } else { H256::zero() };
+            let proof = if index == last_index { &proof[..] } else { &[] };
+            let leaves = slots.into_iter().map(|slot| (slot.hash, slot.data)).collect::<Vec<_>>();
+            let has_more = verify_range_proof(storage.storage_root, origin, &leaves, proof)?;
+
+            let entries = leaves
+                .iter()
+                .map(|(key, data)| {
+                    let value = U256::decode(&mut &data[..])?;
+                    Ok(StorageEntry { key: *key, value })
+                })
+                .collect::<Result<Vec<_>, DecodeError>>()?;
+
+            if has_more {
+                let last = entries.last().ok_or(InvalidSnapResponse::MissingRange)?;
+                unfinished = next_hash(last.key).map(|start| PendingStorage { start, ..*storage });
+            }
+            chunks
+                .push(SnapStateChunk::Storage { hashed_address: storage.hashed_address, entries });
+        }
+
+        // accounts that were not served at all are requested again
+        for storage in accounts[chunks.len()..].iter().rev() {
+            self.pending_storages.push_front(*storage);
+        }
+        if let Some(storage) = unfinished {
+            self.pending_storages.push_front(storage);
+        }
+        self.queued_chunks.extend(chunks);
+        Ok(())
+    }
+
+    fn on_byte_codes(
+        &mut self,
+        hashes: &[H256],
+        response: ByteCodes,
+    ) -> Result<(), InvalidSnapResponse> {
+        if response.codes.is_empty() {
+            return Err(InvalidSnapResponse::EmptyResponse)
+        }
+
+        let mut missing = hashes.iter().copied().collect::<HashSet<_>>();
+        let mut codes = Vec::with_capacity(response.codes.len());
+        for code in response.codes {
+            let hash = keccak256(&code);
+            if !missing.remove(&hash) {
+                return Err(InvalidSnapResponse::UnexpectedByteCode(hash))
+            }
+            codes.push((hash, Bytecode::new_raw_with_hash(code.0, hash)));
+        }
+
+        self.pending_bytecodes.extend(hashes.iter().filter(|hash| missing.contains(*hash)));
+        self.queued_chunks.push_back(SnapStateChunk::ByteCodes(codes));
+        Ok(())
+    }
+}
+
+impl<C> StateDownloader for SnapStateDownloader<C>
+where
+    C: SnapClient + 'static,
+{
+    fn set_state_root(&mut self, state_root: H256) {
+        trace!(target: "downloaders::snap", ?state_root, "Setting new state root");
+        self.state_root = state_root;
+        self.next_account = Some(H256::zero());
+        self.pending_storages.clear();
+        self.pending_bytecodes.clear();
+        self.seen_bytecodes.clear();
+        self.in_progress = None;
+        self.queued_chunks.clear();
+        self.retry_delay = None;
+        self.next_retry_delay = MIN_RETRY_DELAY;
+    }
+}
+
+impl<C> Stream for SnapStateDownloader<C>
+where
+    C: SnapClient + 'static,
+{
+    type Item = SnapStateChunk;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(chunk) = this.queued_chunks.pop_front() {
+                return Poll::Ready(Some(chunk))
+            }
+
+            // back off after a failed request, so a client that fails immediately is not polled in
+            // a busy loop
+            if let Some(delay) = this.retry_delay.as_mut() {
+                ready!(delay.as_mut().poll(cx));
+                this.retry_delay = None;
+            }
+
+            if this.in_progress.is_none() {
+                match this.next_request() {
+                    Some(request) => this.in_progress = Some(request),
+                    None => return Poll::Ready(None),
+                }
+            }
+
+            let request = this.in_progress.as_mut().expect("request in progress");
+            let result = ready!(request.poll_response(cx));
+            let request = this.in_progress.take().expect("request in progress");
+
+            match result {
+                Ok(response) => {
+                    let (peer_id, response) = response.split();
+                    match this.on_response(&request, response) {
+                        Ok(()) => this.next_retry_delay = MIN_RETRY_DELAY,
+                        Err(error) => {
+                            debug!(
+                                target: "downloaders::snap",
+                                ?peer_id,
+                                %error,
+                                "Received invalid snap response"
+                            );
+                            if error.is_peer_fault() {
+                                this.client.report_bad_message(peer_id);
+                            }
+                            this.retry(request);
+                        }
+                    }
+                }
+                Err(error) => {
+                    debug!(target: "downloaders::snap", %error, "Snap request failed");
+                    this.retry(request);
+                }
+            }
+        }
+    }
+}
+
+/// An account whose storage still needs to be downloaded.
+#[derive(Debug, Clone, Copy)]
+struct PendingStorage {
+    /// The hashed address of the account.
+    hashed_address: H256,
+    /// The storage root of the account.
+    storage_root: H256,
+    /// The hashed slot to continue from.
+    start: H256,
+}
+
+/// A request in progress.
+enum SnapStateRequest<C: SnapClient> {
+    AccountRange { origin: H256, fut: C::AccountRangeOutput },
+    StorageRanges { accounts: Vec<PendingStorage>, fut: C::StorageRangesOutput },
+    ByteCodes { hashes: Vec<H256>, fut: C::ByteCodesOutput },
+}
+
+impl<C: SnapClient> SnapStateRequest<C> {
+    fn poll_response(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<PeerRequestResult<SnapStateResponse>> {
+        let response = match self {
+            Self::AccountRange { fut, .. } => {
+                ready!(fut.poll_unpin(cx)).map(|resp| resp.map(SnapStateResponse::AccountRange))
+            }
+            Self::StorageRanges { fut, .. } => {
+                ready!(fut.poll_unpin(cx)).map(|resp| resp.map(SnapStateResponse::StorageRanges))
+            }
+            Self::ByteCodes { fut, .. } => {
+                ready!(fut.poll_unpin(cx)).map(|resp| resp.map(SnapStateResponse::ByteCodes))
+            }
+        };
+        Poll::Ready(response)
+    }
+}
+
+impl<C: SnapClient> std::fmt::Debug for SnapStateRequest<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccountRange { origin, .. } => {
+                f.debug_struct("AccountRange").field("origin", origin).finish_non_exhaustive()
+            }
+            Self::StorageRanges { accounts, .. } => {
+                f.debug_struct("StorageRanges").field("accounts", accounts).finish_non_exhaustive()
+            }
+            Self::ByteCodes { hashes, .. } => {
+                f.debug_struct("ByteCodes").field("hashes", hashes).finish_non_exhaustive()
+            }
+        }
+    }
+}
+
+/// A response to a [`SnapStateRequest`].
+enum SnapStateResponse {
+    AccountRange(AccountRange),
+    StorageRanges(StorageRanges),
+    ByteCodes(ByteCodes),
+}
+
+/// Reasons for rejecting a response.
+#[derive(Debug, Error)]
+enum InvalidSnapResponse {
+    #[error(transparent)]
+    RangeProof(#[from] RangeProofError),
+    #[error("invalid storage value: {0}")]
+    StorageValue(#[from] DecodeError),
+    #[error("received an empty response")]
+    EmptyResponse,
+    #[error("received an empty range that is not the last one")]
+    MissingRange,
+    #[error("received more items than requested")]
+    TooManyItems,
+    #[error("received unrequested bytecode {0:?}")]
+    UnexpectedByteCode(H256),
+}
+
+impl InvalidSnapResponse {
+    /// Returns `true` if the peer violated the protocol and should be penalized.
+    ///
+    /// An empty response means the peer doesn't serve the requested root, which is not a fault.
+    fn is_peer_fault(&self) -> bool {
+        !matches!(self, Self::EmptyResponse)
+    }
+}
+
+/// Returns the hash following the given hash, or `None` if it is the last possible hash.
+fn next_hash(hash: H256) -> Option<H256> {
+    U256::from_be_bytes(hash.0).checked_add(U256::from(1)).map(|next| H256(next.to_be_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::{self, Ready};
+    use futures_util::StreamExt;
+    use reth_eth_wire::{AccountData, SnapAccount, TrieNodes};
+    use reth_interfaces::p2p::{
+        download::DownloadClient, priority::Priority, snap::client::GetTrieNodes,
+    };
+    use reth_primitives::{
+        trie::{HashBuilder, Nibbles},
+        Account, PeerId, WithPeerId,
+    };
+    use std::sync::Mutex;
+
+    /// Serves the queued account ranges in order.
+    #[derive(Debug, Default)]
+    struct TestSnapClient {
+        account_ranges: Mutex<VecDeque<AccountRange>>,
+        reported: Mutex<Vec<PeerId>>,
+    }
+
+    impl DownloadClient for TestSnapClient {
+        fn report_bad_message(&self, peer_id: PeerId) {
+            self.reported.lock().unwrap().push(peer_id);
+        }
+
+        fn num_connected_peers(&self) -> usize {
+            1
+        }
+    }
+
+    impl SnapClient for TestSnapClient {
+        type AccountRangeOutput = Ready<PeerRequestResult<AccountRange>>;
+        type StorageRangesOutput = Ready<PeerRequestResult<StorageRanges>>;
+        type ByteCodesOutput = Ready<PeerRequestResult<ByteCodes>>;
+        type TrieNodesOutput = Ready<PeerRequestResult<TrieNodes>>;
+
+        fn get_account_range_with_priority(
+            &self,
+            request: GetAccountRange,
+            _priority: Priority,
+        ) -> Self::AccountRangeOutput {
+            let mut response = self.account_ranges.lock().unwrap().pop_front().unwrap();
+            response.request_id = request.request_id;
+            future::ready(Ok(WithPeerId::new(PeerId::random(), response)))
+        }
+
+        fn get_storage_ranges_with_priority(
+            &self,
+            _request: GetStorageRanges,
+            _priority: Priority,
+        ) -> Self::StorageRangesOutput {
+            unreachable!("no account has storage")
+        }
+
+        fn get_byte_codes_with_priority(
+            &self,
+            _request: GetByteCodes,
+            _priority: Priority,
+        ) -> Self::ByteCodesOutput {
+            unreachable!("no account has bytecode")
+        }
+
+        fn get_trie_nodes_with_priority(
+            &self,
+            _request: GetTrieNodes,
+            _priority: Priority,
+        ) -> Self::TrieNodesOutput {
+            unreachable!("trie nodes are not requested")
+        }
+    }
+
+    /// Returns the accounts of a state sorted by hashed address, and the state root.
+    fn test_state() -> (Vec<AccountData>, H256) {
+        let mut accounts = (0..16u64)
+            .map(|i| AccountData {
+                hash: keccak256(H256::from_low_u64_be(i)),
+                body: SnapAccount::new(
+                    Account { nonce: i, balance: U256::from(i), bytecode_hash: None },
+                    reth_primitives::proofs::EMPTY_ROOT,
+                ),
+            })
+            .collect::<Vec<_>>();
+        accounts.sort_by_key(|data| data.hash);
+
+        let mut hash_builder = HashBuilder::default();
+        for data in &accounts {
+            let mut rlp = Vec::new();
+            EthAccount::from(data.body.into_account()).encode(&mut rlp);
+            hash_builder.add_leaf(Nibbles::unpack(data.hash), &rlp);
+        }
+        (accounts, hash_builder.root())
+    }
+
+    #[tokio::test]
+    async fn downloads_accounts() {
+        let (accounts, root) = test_state();
+        let client = Arc::new(TestSnapClient::default());
+        client.account_ranges.lock().unwrap().push_back(AccountRange {
+            request_id: 0,
+            accounts: accounts.clone(),
+            proof: vec![],
+        });
+
+        let mut downloader = SnapStateDownloader::new(client.clone(), root);
+        let expected =
+            accounts.iter().map(|data| (data.hash, data.body.into_account())).collect::<Vec<_>>();
+        assert_eq!(downloader.next().await, Some(SnapStateChunk::Accounts(expected)));
+        assert_eq!(downloader.next().await, None);
+        assert!(client.reported.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retries_invalid_account_range() {
+        let (accounts, root) = test_state();
+        let mut invalid = accounts.clone();
+        invalid[3].body.balance += U256::from(1);
+
+        let client = Arc::new(TestSnapClient::default());
+        {
+            let mut ranges = client.account_ranges.lock().unwrap();
+            ranges.push_back(AccountRange { request_id: 0, accounts: invalid, proof: vec![] });
+            ranges.push_back(AccountRange { request_id: 0, accounts, proof: vec![] });
+        }
+
+        let mut downloader = SnapStateDownloader::new(client.clone(), root);
+        assert_matches::assert_matches!(downloader.next().await, Some(SnapStateChunk::Accounts(_)));
+        assert_eq!(downloader.next().await, None);
+        assert_eq!(client.reported.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_empty_account_range_without_penalty() {
+        let (accounts, root) = test_state();
+        let client = Arc::new(TestSnapClient::default());
+        {
+            let mut ranges = client.account_ranges.lock().unwrap();
+            // a peer that doesn't serve the root responds without accounts and proof
+            ranges.push_back(AccountRange::default());
+            ranges.push_back(AccountRange { request_id: 0, accounts, proof: vec![] });
+        }
+
+        let mut downloader = SnapStateDownloader::new(client.clone(), root);
+        assert_matches::assert_matches!(downloader.next().await, Some(SnapStateChunk::Accounts(_)));
+        assert_eq!(downloader.next().await, None);
+        assert!(client.reported.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn restarts_download_for_new_root() {
+        let (accounts, root) = test_state();
+        let client = Arc::new(TestSnapClient::default());
+        {
+            let mut ranges = client.account_ranges.lock().unwrap();
+            ranges.push_back(AccountRange {
+                request_id: 0,
+                accounts: accounts.clone(),
+                proof: vec![],
+            });
+            ranges.push_back(AccountRange { request_id: 0, accounts, proof: vec![] });
+        }
+
+        let mut downloader = SnapStateDownloader::new(client.clone(), H256::zero());
+        downloader.set_state_root(root);
+        assert_matches::assert_matches!(downloader.next().await, Some(SnapStateChunk::Accounts(_)));
+        assert_eq!(downloader.next().await, None);
+
+        // the state is downloaded again once the root is set
+        downloader.set_state_root(root);
+        assert_matches::assert_matches!(downloader.next().await, Some(SnapStateChunk::Accounts(_)));
+        assert_eq!(downloader.next().await, None);
+    }
+}
@@ -85,38 +85,51 @@ impl FileClient {
         let mut reader = vec![];
         file.read_to_end(&mut reader).await.unwrap();
 
-        let mut headers = HashMap::new();
-        let mut hash_to_number = HashMap::new();
-        let mut bodies = HashMap::new();
+        let mut client = Self::from_blocks([]);
 
         // use with_capacity to make sure the internal buffer contains the entire file
         let mut stream = FramedRead::with_capacity(&reader[..], BlockFileCodec, file_len as usize);
 
         while let Some(block_res) = stream.next().await {
-            let block = block_res?;
-            let block_hash = block.header.hash_slow();
-
-            // add to the internal maps
-            headers.insert(block.header.number, block.header.clone());
-            hash_to_number.insert(block_hash, block.header.number);
-            bodies.insert(
-                block_hash,
-                BlockBody {
-                    transactions: block.body,
-                    ommers: block.ommers,
-                    withdrawals: block.withdrawals,
-                },
-            );
+            client.insert_block(block_res?);
         }
 
-        trace!(blocks = headers.len(), "Initialized file client");
+        trace!(blocks = client.headers.len(), "Initialized file client");
 
-        Ok(Self { headers, hash_to_number, bodies })
+        Ok(client)
+    }
+
+    /// Create a new file client from already decoded blocks, e.g. read from an era1 archive.
+    pub fn from_blocks(blocks: impl IntoIterator<Item = Block>) -> Self {
+        let mut client = Self {
+            headers: HashMap::new(),
+            hash_to_number: HashMap::new(),
+            bodies: HashMap::new(),
+        };
+        for block in blocks {
+            client.insert_block(block);
+        }
+        client
+    }
+
+    /// Adds the block to the internal maps.
+    fn insert_block(&mut self, block: Block) {
+        let block_hash = block.header.hash_slow();
+        self.headers.insert(block.header.number, block.header.clone());
+        self.hash_to_number.insert(block_hash, block.header.number);
+        self.bodies.insert(
+            block_hash,
+            BlockBody {
+                transactions: block.body,
+                ommers: block.ommers,
+                withdrawals: block.withdrawals,
+            },
+        );
     }
 
     /// Get the tip hash of the chain.
     pub fn tip(&self) -> Option<H256> {
-        self.headers.get(&(self.headers.len() as u64)).map(|h| h.hash_slow())
+        self.max_block().and_then(|number| self.headers.get(&number)).map(|h| h.hash_slow())
     }
 
     /// Returns the highest block number of this client has or `None` if empty
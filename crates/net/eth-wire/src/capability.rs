@@ -1,6 +1,6 @@
 //! All capability related types
 
-use crate::{version::ParseVersionError, EthMessage, EthVersion};
+use crate::{version::ParseVersionError, EthMessage, EthVersion, SNAP_MESSAGE_COUNT, SNAP_VERSION};
 use reth_codecs::add_arbitrary_tests;
 use reth_primitives::bytes::{BufMut, Bytes};
 use reth_rlp::{Decodable, DecodeError, Encodable, RlpDecodable, RlpEncodable};
@@ -70,6 +70,51 @@ impl Capability {
     pub fn is_eth_v68(&self) -> bool {
         self.name == "eth" && self.version == 68
     }
+
+    /// Whether this is snap v1.
+    #[inline]
+    pub fn is_snap_v1(&self) -> bool {
+        self.name == "snap" && self.version == SNAP_VERSION as usize
+    }
+}
+
+/// A sub-protocol capability together with the number of messages it reserves.
+///
+/// The message count determines the message id range of the capability once it is shared with a
+/// peer, so it must be known for every capability that is announced in the `Hello` message.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Protocol {
+    /// The capability of the sub-protocol.
+    pub cap: Capability,
+    /// The number of messages used by the sub-protocol.
+    pub messages: u8,
+}
+
+impl Protocol {
+    /// Create a new `Protocol` from the given capability and message count.
+    pub fn new(cap: Capability, messages: u8) -> Self {
+        Self { cap, messages }
+    }
+
+    /// Returns the `eth` protocol of the given version.
+    pub fn eth(version: EthVersion) -> Self {
+        Self::new(version.into(), version.total_messages())
+    }
+
+    /// Returns the `snap/1` protocol.
+    pub fn snap() -> Self {
+        Self::new(Capability::new("snap".into(), SNAP_VERSION as usize), SNAP_MESSAGE_COUNT)
+    }
+
+    /// Returns the built-in protocol for the given capability, if it is a supported `eth` or
+    /// `snap` version.
+    pub fn known(cap: &Capability) -> Option<Self> {
+        match cap.name.as_str() {
+            "eth" => EthVersion::try_from(cap.version as u8).ok().map(Self::eth),
+            "snap" if cap.is_snap_v1() => Some(Self::snap()),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(any(test, feature = "arbitrary"))]
@@ -104,6 +149,7 @@ pub struct Capabilities {
     eth_66: bool,
     eth_67: bool,
     eth_68: bool,
+    snap_1: bool,
 }
 
 impl Capabilities {
@@ -142,6 +188,12 @@ impl Capabilities {
     pub fn supports_eth_v68(&self) -> bool {
         self.eth_68
     }
+
+    /// Whether this peer supports the `snap/1` sub-protocol.
+    #[inline]
+    pub fn supports_snap(&self) -> bool {
+        self.snap_1
+    }
 }
 
 impl From<Vec<Capability>> for Capabilities {
@@ -150,6 +202,7 @@ impl From<Vec<Capability>> for Capabilities {
             eth_66: value.iter().any(Capability::is_eth_v66),
             eth_67: value.iter().any(Capability::is_eth_v67),
             eth_68: value.iter().any(Capability::is_eth_v68),
+            snap_1: value.iter().any(Capability::is_snap_v1),
             inner: value,
         }
     }
@@ -169,6 +222,7 @@ impl Decodable for Capabilities {
             eth_66: inner.iter().any(Capability::is_eth_v66),
             eth_67: inner.iter().any(Capability::is_eth_v67),
             eth_68: inner.iter().any(Capability::is_eth_v68),
+            snap_1: inner.iter().any(Capability::is_snap_v1),
             inner,
         })
     }
@@ -181,16 +235,42 @@ pub enum SharedCapability {
     /// The `eth` capability.
     Eth { version: EthVersion, offset: u8 },
 
-    /// An unknown capability.
-    UnknownCapability { name: SmolStr, version: u8, offset: u8 },
+    /// The `snap` capability.
+    Snap { version: u8, offset: u8 },
+
+    /// Any other capability, with the number of messages it reserves.
+    UnknownCapability { name: SmolStr, version: u8, offset: u8, messages: u8 },
 }
 
 impl SharedCapability {
     /// Creates a new [`SharedCapability`] based on the given name, offset, and version.
+    ///
+    /// Returns an error if the capability is neither `eth` nor `snap`, because the number of
+    /// messages of other capabilities is unknown, see [`SharedCapability::from_protocol`].
     pub(crate) fn new(name: &str, version: u8, offset: u8) -> Result<Self, SharedCapabilityError> {
         match name {
             "eth" => Ok(Self::Eth { version: EthVersion::try_from(version)?, offset }),
-            _ => Ok(Self::UnknownCapability { name: name.into(), version, offset }),
+            "snap" if version == SNAP_VERSION => Ok(Self::Snap { version, offset }),
+            _ => Err(SharedCapabilityError::UnknownCapability),
+        }
+    }
+
+    /// Creates a new [`SharedCapability`] for the given [`Protocol`] and offset.
+    ///
+    /// The message count of the protocol is only used if it's neither `eth` nor `snap`.
+    pub(crate) fn from_protocol(
+        protocol: &Protocol,
+        offset: u8,
+    ) -> Result<Self, SharedCapabilityError> {
+        let Protocol { cap, messages } = protocol;
+        match Self::new(&cap.name, cap.version as u8, offset) {
+            Err(SharedCapabilityError::UnknownCapability) => Ok(Self::UnknownCapability {
+                name: cap.name.clone(),
+                version: cap.version as u8,
+                offset,
+                messages: *messages,
+            }),
+            res => res,
         }
     }
 
@@ -198,6 +278,7 @@ impl SharedCapability {
     pub fn name(&self) -> &str {
         match self {
             SharedCapability::Eth { .. } => "eth",
+            SharedCapability::Snap { .. } => "snap",
             SharedCapability::UnknownCapability { name, .. } => name,
         }
     }
@@ -206,6 +287,7 @@ impl SharedCapability {
     pub fn version(&self) -> u8 {
         match self {
             SharedCapability::Eth { version, .. } => *version as u8,
+            SharedCapability::Snap { version, .. } => *version,
             SharedCapability::UnknownCapability { version, .. } => *version,
         }
     }
@@ -214,17 +296,83 @@ impl SharedCapability {
     pub fn offset(&self) -> u8 {
         match self {
             SharedCapability::Eth { offset, .. } => *offset,
+            SharedCapability::Snap { offset, .. } => *offset,
             SharedCapability::UnknownCapability { offset, .. } => *offset,
         }
     }
 
     /// Returns the number of protocol messages supported by this capability.
-    pub fn num_messages(&self) -> Result<u8, SharedCapabilityError> {
+    pub fn num_messages(&self) -> u8 {
         match self {
-            SharedCapability::Eth { version, .. } => Ok(version.total_messages()),
-            _ => Err(SharedCapabilityError::UnknownCapability),
+            SharedCapability::Eth { version, .. } => version.total_messages(),
+            SharedCapability::Snap { .. } => SNAP_MESSAGE_COUNT,
+            SharedCapability::UnknownCapability { messages, .. } => *messages,
         }
     }
+
+    /// Whether this is the `eth` capability.
+    #[inline]
+    pub fn is_eth(&self) -> bool {
+        matches!(self, SharedCapability::Eth { .. })
+    }
+
+    /// Whether this is the given capability.
+    pub fn is(&self, cap: &Capability) -> bool {
+        self.name() == cap.name && self.version() as usize == cap.version
+    }
+
+    /// Whether the given absolute message id is in the message id range of this capability.
+    pub fn contains_message_id(&self, id: u8) -> bool {
+        id >= self.offset() && (id - self.offset()) < self.num_messages()
+    }
+}
+
+/// All capabilities shared with a peer, ordered by their message id offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedCapabilities(Vec<SharedCapability>);
+
+impl SharedCapabilities {
+    /// Returns an iterator over all shared capabilities.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &SharedCapability> + '_ {
+        self.0.iter()
+    }
+
+    /// Returns the number of shared capabilities.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no capability is shared.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the shared `eth` capability, if any.
+    pub fn eth(&self) -> Option<&SharedCapability> {
+        self.iter().find(|cap| cap.is_eth())
+    }
+
+    /// Returns the shared capability with the given name.
+    ///
+    /// Only the highest shared version of a capability is ever shared.
+    pub fn find_by_name(&self, name: &str) -> Option<&SharedCapability> {
+        self.iter().find(|cap| cap.name() == name)
+    }
+
+    /// Returns the shared capability whose message id range contains the given absolute message
+    /// id.
+    pub fn find_by_message_id(&self, id: u8) -> Option<&SharedCapability> {
+        self.iter().find(|cap| cap.contains_message_id(id))
+    }
+}
+
+impl From<Vec<SharedCapability>> for SharedCapabilities {
+    fn from(value: Vec<SharedCapability>) -> Self {
+        Self(value)
+    }
 }
 
 /// An error that may occur while creating a [`SharedCapability`].
@@ -233,7 +381,8 @@ pub enum SharedCapabilityError {
     /// Unsupported `eth` version.
     #[error(transparent)]
     UnsupportedVersion(#[from] ParseVersionError),
-    /// Cannot determine the number of messages for unknown capabilities.
+    /// Cannot determine the number of messages for an unknown capability without its
+    /// [`Protocol`].
     #[error("cannot determine the number of messages for unknown capabilities")]
     UnknownCapability,
 }
@@ -269,6 +418,41 @@ mod tests {
         assert_eq!(capability, SharedCapability::Eth { version: EthVersion::Eth66, offset: 0 });
     }
 
+    #[test]
+    fn from_snap_1() {
+        let capability = SharedCapability::new("snap", 1, 0x11).unwrap();
+
+        assert_eq!(capability.name(), "snap");
+        assert_eq!(capability.version(), 1);
+        assert_eq!(capability.num_messages(), 8);
+        assert_eq!(capability, SharedCapability::Snap { version: 1, offset: 0x11 });
+    }
+
+    #[test]
+    fn from_custom_protocol() {
+        let protocol = Protocol::new(Capability::new("gossip".into(), 2), 5);
+        let capability = SharedCapability::from_protocol(&protocol, 0x20).unwrap();
+
+        assert_eq!(capability.name(), "gossip");
+        assert_eq!(capability.version(), 2);
+        assert_eq!(capability.num_messages(), 5);
+        assert!(capability.contains_message_id(0x24));
+        assert!(!capability.contains_message_id(0x25));
+        assert!(!capability.contains_message_id(0x1f));
+        assert!(SharedCapability::new("gossip", 2, 0x20).is_err());
+    }
+
+    #[test]
+    fn known_protocols() {
+        assert_eq!(
+            Protocol::known(&EthVersion::Eth68.into()),
+            Some(Protocol::eth(EthVersion::Eth68))
+        );
+        assert_eq!(Protocol::known(&Capability::new("snap".into(), 1)), Some(Protocol::snap()));
+        assert_eq!(Protocol::known(&Capability::new("eth".into(), 65)), None);
+        assert_eq!(Protocol::known(&Capability::new("gossip".into(), 1)), None);
+    }
+
     #[test]
     fn capabilities_supports_eth() {
         let capabilities: Capabilities = vec![
@@ -282,5 +466,15 @@ mod tests {
         assert!(capabilities.supports_eth_v66());
         assert!(capabilities.supports_eth_v67());
         assert!(capabilities.supports_eth_v68());
+        assert!(!capabilities.supports_snap());
+    }
+
+    #[test]
+    fn capabilities_supports_snap() {
+        let capabilities: Capabilities =
+            vec![Capability::new("eth".into(), 68), Capability::new("snap".into(), 1)].into();
+
+        assert!(capabilities.supports_eth());
+        assert!(capabilities.supports_snap());
     }
 }
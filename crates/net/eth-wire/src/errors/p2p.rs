@@ -35,6 +35,8 @@ pub enum P2PStreamError {
     PingBeforeHandshake,
     #[error("too many messages buffered before sending")]
     SendBufferFull,
+    #[error("too many messages buffered for the {0} sub-protocol")]
+    SatelliteBufferFull(String),
     #[error("disconnected")]
     Disconnected(DisconnectReason),
     #[error("unknown disconnect reason: {0}")]
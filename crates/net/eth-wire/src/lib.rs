@@ -23,6 +23,7 @@ mod disconnect;
 pub mod errors;
 mod ethstream;
 mod hello;
+mod multiplex;
 mod p2pstream;
 mod pinger;
 pub use builder::*;
@@ -38,5 +39,6 @@ pub use crate::{
     disconnect::{CanDisconnect, DisconnectReason},
     ethstream::{EthStream, UnauthedEthStream, MAX_MESSAGE_SIZE},
     hello::HelloMessage,
+    multiplex::{ProtocolSender, ProtocolStream, SATELLITE_CHANNEL_CAPACITY},
     p2pstream::{P2PMessage, P2PMessageID, P2PStream, ProtocolVersion, UnauthedP2PStream},
 };
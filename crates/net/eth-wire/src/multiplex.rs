@@ -0,0 +1,104 @@
+//! Multiplexing of satellite sub-protocols over a [`P2PStream`](crate::P2PStream).
+//!
+//! The primary `eth` capability is exposed through the `Stream` and `Sink` implementations of the
+//! [`P2PStream`](crate::P2PStream). Every other shared capability can be installed as a
+//! [`ProtocolStream`] which receives the messages of its own message id range.
+
+use crate::capability::SharedCapability;
+use futures::Stream;
+use reth_primitives::bytes::{Bytes, BytesMut};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+
+/// The maximum number of messages buffered per direction for a satellite sub-protocol.
+///
+/// The session is disconnected if the sub-protocol does not keep up with the messages of the peer,
+/// and [`ProtocolSender::send`] fails if the messages of the sub-protocol can not be sent fast
+/// enough.
+pub const SATELLITE_CHANNEL_CAPACITY: usize = 256;
+
+/// Creates the channel pair of a satellite sub-protocol.
+pub(crate) fn satellite_channel(
+    capability: SharedCapability,
+) -> (SatelliteChannel, ProtocolStream) {
+    let (to_protocol, from_wire) = mpsc::channel(SATELLITE_CHANNEL_CAPACITY);
+    let (to_wire, from_protocol) = mpsc::channel(SATELLITE_CHANNEL_CAPACITY);
+    let channel = SatelliteChannel { capability: capability.clone(), to_protocol, from_protocol };
+    let stream = ProtocolStream { capability, from_wire, to_wire: ProtocolSender { to_wire } };
+    (channel, stream)
+}
+
+/// The [`P2PStream`](crate::P2PStream) half of an installed satellite sub-protocol.
+#[derive(Debug)]
+pub(crate) struct SatelliteChannel {
+    /// The shared capability of the sub-protocol.
+    pub(crate) capability: SharedCapability,
+    /// Delivers the messages received from the peer, with protocol relative message ids.
+    pub(crate) to_protocol: Sender<BytesMut>,
+    /// Messages queued by the sub-protocol, with protocol relative message ids.
+    pub(crate) from_protocol: Receiver<Bytes>,
+}
+
+/// A per-session stream of a satellite sub-protocol.
+///
+/// Yields the messages the peer sent for this sub-protocol. The first byte of each message is the
+/// message id relative to the sub-protocol, so the first message of the sub-protocol has the id
+/// `0`. The stream ends once the session is closed.
+#[derive(Debug)]
+pub struct ProtocolStream {
+    /// The shared capability of the sub-protocol.
+    capability: SharedCapability,
+    /// Messages received from the peer.
+    from_wire: Receiver<BytesMut>,
+    /// Sends messages to the peer.
+    to_wire: ProtocolSender,
+}
+
+impl ProtocolStream {
+    /// Returns the shared capability of the sub-protocol.
+    pub fn capability(&self) -> &SharedCapability {
+        &self.capability
+    }
+
+    /// Queues a message for sending to the peer.
+    ///
+    /// See also [`ProtocolSender::send`].
+    pub fn send(&self, msg: Bytes) -> Result<(), TrySendError<Bytes>> {
+        self.to_wire.send(msg)
+    }
+
+    /// Returns a new [`ProtocolSender`] that can send messages to the peer.
+    pub fn sender(&self) -> ProtocolSender {
+        self.to_wire.clone()
+    }
+}
+
+impl Stream for ProtocolStream {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().from_wire.poll_recv(cx)
+    }
+}
+
+/// Sends the messages of a satellite sub-protocol to the peer.
+#[derive(Debug, Clone)]
+pub struct ProtocolSender {
+    to_wire: Sender<Bytes>,
+}
+
+impl ProtocolSender {
+    /// Queues a message for sending to the peer.
+    ///
+    /// The first byte of the message must be the message id relative to the sub-protocol. Messages
+    /// with an id outside of the message range of the sub-protocol are dropped.
+    ///
+    /// Returns an error if the session is closed, or if [`SATELLITE_CHANNEL_CAPACITY`] messages are
+    /// already queued, in which case the message is not sent.
+    pub fn send(&self, msg: Bytes) -> Result<(), TrySendError<Bytes>> {
+        self.to_wire.try_send(msg)
+    }
+}
@@ -1,8 +1,9 @@
 #![allow(dead_code, unreachable_pub, missing_docs, unused_variables)]
 use crate::{
-    capability::{Capability, SharedCapability},
+    capability::{Capability, Protocol, SharedCapabilities, SharedCapability},
     disconnect::CanDisconnect,
     errors::{P2PHandshakeError, P2PStreamError},
+    multiplex::{satellite_channel, ProtocolStream, SatelliteChannel},
     pinger::{Pinger, PingerEvent},
     DisconnectReason, HelloMessage,
 };
@@ -16,12 +17,13 @@ use reth_primitives::{
 };
 use reth_rlp::{Decodable, DecodeError, Encodable, EMPTY_LIST_CODE};
 use std::{
-    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    collections::{BTreeSet, HashMap, VecDeque},
     io,
     pin::Pin,
     task::{ready, Context, Poll},
     time::Duration,
 };
+use tokio::sync::mpsc::error::TrySendError;
 use tokio_stream::Stream;
 
 #[cfg(feature = "serde")]
@@ -83,9 +85,25 @@ where
 {
     /// Consumes the `UnauthedP2PStream` and returns a `P2PStream` after the `Hello` handshake is
     /// completed successfully. This also returns the `Hello` message sent by the remote peer.
+    ///
+    /// Only the `eth` and `snap` capabilities of the `Hello` message are negotiated, see also
+    /// [`UnauthedP2PStream::handshake_with_protocols`].
     pub async fn handshake(
+        self,
+        hello: HelloMessage,
+    ) -> Result<(P2PStream<S>, HelloMessage), P2PStreamError> {
+        self.handshake_with_protocols(hello, &[]).await
+    }
+
+    /// Same as [`UnauthedP2PStream::handshake`], but also negotiates the given additional
+    /// sub-protocols if they are announced in the `Hello` message.
+    ///
+    /// Shared additional sub-protocols can be installed on the returned [`P2PStream`] with
+    /// [`P2PStream::install_protocol`].
+    pub async fn handshake_with_protocols(
         mut self,
         hello: HelloMessage,
+        protocols: &[Protocol],
     ) -> Result<(P2PStream<S>, HelloMessage), P2PStreamError> {
         tracing::trace!(?hello, "sending p2p hello to peer");
 
@@ -145,20 +163,34 @@ where
             })
         }
 
-        // determine shared capabilities (currently returns only one capability)
+        // the message count of every local capability is required to determine the offsets
+        let local_protocols = hello
+            .capabilities
+            .iter()
+            .filter_map(|cap| {
+                let protocol = Protocol::known(cap)
+                    .or_else(|| protocols.iter().find(|protocol| protocol.cap == *cap).cloned());
+                if protocol.is_none() {
+                    tracing::debug!(?cap, "ignoring local capability with unknown message count");
+                }
+                protocol
+            })
+            .collect();
+
+        // determine shared capabilities
         let capability_res =
-            set_capability_offsets(hello.capabilities, their_hello.capabilities.clone());
+            set_capability_offsets(local_protocols, their_hello.capabilities.clone());
 
-        let shared_capability = match capability_res {
+        let shared_capabilities = match capability_res {
             Err(err) => {
                 // we don't share any capabilities, send a disconnect message
                 self.send_disconnect(DisconnectReason::UselessPeer).await?;
                 Err(err)
             }
-            Ok(cap) => Ok(cap),
+            Ok(caps) => Ok(caps),
         }?;
 
-        let stream = P2PStream::new(self.inner, shared_capability);
+        let stream = P2PStream::new(self.inner, shared_capabilities);
 
         Ok((stream, their_hello))
     }
@@ -210,9 +242,15 @@ pub struct P2PStream<S> {
     /// The state machine used for keeping track of the peer's ping status.
     pinger: Pinger,
 
-    /// The supported capability for this stream.
+    /// The primary capability of this stream, this is the shared `eth` capability.
     shared_capability: SharedCapability,
 
+    /// All capabilities shared with the peer.
+    shared_capabilities: SharedCapabilities,
+
+    /// The installed satellite sub-protocols, see [`P2PStream::install_protocol`].
+    satellites: Vec<SatelliteChannel>,
+
     /// Outgoing messages buffered for sending to the underlying stream.
     outgoing_messages: VecDeque<Bytes>,
 
@@ -229,13 +267,27 @@ impl<S> P2PStream<S> {
     /// Create a new [`P2PStream`] from the provided stream.
     /// New [`P2PStream`]s are assumed to have completed the `p2p` handshake successfully and are
     /// ready to send and receive subprotocol messages.
-    pub fn new(inner: S, capability: SharedCapability) -> Self {
+    ///
+    /// The shared `eth` capability is the primary capability of the stream, messages of all other
+    /// shared capabilities are only delivered to installed sub-protocols.
+    ///
+    /// # Panics
+    ///
+    /// If no capability is shared.
+    pub fn new(inner: S, shared_capabilities: SharedCapabilities) -> Self {
+        let shared_capability = shared_capabilities
+            .eth()
+            .or_else(|| shared_capabilities.iter().next())
+            .cloned()
+            .expect("at least one shared capability");
         Self {
             inner,
             encoder: snap::raw::Encoder::new(),
             decoder: snap::raw::Decoder::new(),
             pinger: Pinger::new(PING_INTERVAL, PING_TIMEOUT),
-            shared_capability: capability,
+            shared_capability,
+            shared_capabilities,
+            satellites: Vec::new(),
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
@@ -251,11 +303,35 @@ impl<S> P2PStream<S> {
         self.outgoing_message_buffer_capacity = capacity;
     }
 
-    /// Returns the shared capability for this stream.
+    /// Returns the primary shared capability for this stream.
     pub fn shared_capability(&self) -> &SharedCapability {
         &self.shared_capability
     }
 
+    /// Returns all capabilities shared with the peer.
+    pub fn shared_capabilities(&self) -> &SharedCapabilities {
+        &self.shared_capabilities
+    }
+
+    /// Installs a [`ProtocolStream`] for the given shared satellite capability.
+    ///
+    /// All messages the peer sends in the message id range of the capability are delivered to the
+    /// returned stream, and messages sent via the stream are multiplexed into this stream.
+    ///
+    /// Returns `None` if the capability isn't shared with the peer, is the primary capability or
+    /// is already installed.
+    pub fn install_protocol(&mut self, cap: &Capability) -> Option<ProtocolStream> {
+        let capability = self.shared_capabilities.iter().find(|shared| shared.is(cap))?;
+        if *capability == self.shared_capability ||
+            self.satellites.iter().any(|satellite| satellite.capability == *capability)
+        {
+            return None
+        }
+        let (satellite, stream) = satellite_channel(capability.clone());
+        self.satellites.push(satellite);
+        Some(stream)
+    }
+
     /// Returns `true` if the connection is about to disconnect.
     pub fn is_disconnecting(&self) -> bool {
         self.disconnecting
@@ -440,7 +516,39 @@ where
                     //  * `eth/67` is reserved message IDs 0x10 - 0x19.
                     //  * `qrs/65` is reserved message IDs 0x1a - 0x21.
                     //
-                    decompress_buf[0] = bytes[0] - this.shared_capability.offset();
+                    // Messages of satellite capabilities are delivered to the installed
+                    // sub-protocol, all other messages are returned for the primary capability.
+                    if !this.shared_capability.contains_message_id(id) {
+                        if let Some(cap) = this.shared_capabilities.find_by_message_id(id) {
+                            decompress_buf[0] = id - cap.offset();
+                            match this.satellites.iter().find(|sat| sat.capability == *cap) {
+                                Some(satellite) => {
+                                    // the sub-protocol can't keep up with the peer
+                                    if let Err(TrySendError::Full(_)) =
+                                        satellite.to_protocol.try_send(decompress_buf)
+                                    {
+                                        return Poll::Ready(Some(Err(
+                                            P2PStreamError::SatelliteBufferFull(
+                                                cap.name().to_string(),
+                                            ),
+                                        )))
+                                    }
+                                }
+                                None => {
+                                    tracing::trace!(
+                                        capability=%cap.name(),
+                                        id,
+                                        "dropping message of uninstalled capability"
+                                    );
+                                }
+                            }
+                            continue
+                        }
+                    }
+
+                    // message ids outside of all shared ranges are invalid for the primary
+                    // capability either way
+                    decompress_buf[0] = id.wrapping_sub(this.shared_capability.offset());
 
                     return Poll::Ready(Some(Ok(decompress_buf)))
                 }
@@ -502,25 +610,11 @@ where
 
         let this = self.project();
 
-        let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(item.len() - 1));
-        let compressed_size =
-            this.encoder.compress(&item[1..], &mut compressed[1..]).map_err(|err| {
-                tracing::debug!(
-                    ?err,
-                    msg=%hex::encode(&item[1..]),
-                    "error compressing p2p message"
-                );
-                err
-            })?;
-
-        // truncate the compressed buffer to the actual compressed size (plus one for the message
-        // id)
-        compressed.truncate(compressed_size + 1);
-
         // all messages sent in this stream are subprotocol messages, so we need to switch the
         // message id based on the offset
-        compressed[0] = item[0] + this.shared_capability.offset();
-        this.outgoing_messages.push_back(compressed.freeze());
+        let compressed =
+            compress_subprotocol_message(this.encoder, &item, this.shared_capability.offset())?;
+        this.outgoing_messages.push_back(compressed);
 
         Ok(())
     }
@@ -533,8 +627,15 @@ where
             match ready!(this.inner.as_mut().poll_flush(cx)) {
                 Err(err) => return Poll::Ready(Err(err.into())),
                 Ok(()) => {
-                    let Some(message) = this.outgoing_messages.pop_front() else {
-                        return Poll::Ready(Ok(()))
+                    // messages of the primary capability take precedence over the messages of
+                    // installed satellite sub-protocols
+                    let message = match this.outgoing_messages.pop_front() {
+                        Some(message) => message,
+                        None if *this.disconnecting => return Poll::Ready(Ok(())),
+                        None => match poll_satellite_message(this.satellites, this.encoder, cx) {
+                            Some(message) => message,
+                            None => return Poll::Ready(Ok(())),
+                        },
                     };
                     if let Err(err) = this.inner.as_mut().start_send(message) {
                         return Poll::Ready(Err(err.into()))
@@ -551,18 +652,75 @@ where
     }
 }
 
+/// Snappy compresses the given subprotocol message and shifts its message id by the offset of its
+/// capability.
+fn compress_subprotocol_message(
+    encoder: &mut snap::raw::Encoder,
+    item: &[u8],
+    offset: u8,
+) -> Result<Bytes, snap::Error> {
+    let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(item.len() - 1));
+    let compressed_size = encoder.compress(&item[1..], &mut compressed[1..]).map_err(|err| {
+        tracing::debug!(
+            ?err,
+            msg=%hex::encode(&item[1..]),
+            "error compressing p2p message"
+        );
+        err
+    })?;
+
+    // truncate the compressed buffer to the actual compressed size (plus one for the message id)
+    compressed.truncate(compressed_size + 1);
+    compressed[0] = item[0] + offset;
+
+    Ok(compressed.freeze())
+}
+
+/// Returns the next message queued by one of the installed satellite sub-protocols, compressed and
+/// with the message id offset of its capability.
+///
+/// Messages with an id outside of the message id range of the capability are dropped.
+fn poll_satellite_message(
+    satellites: &mut [SatelliteChannel],
+    encoder: &mut snap::raw::Encoder,
+    cx: &mut Context<'_>,
+) -> Option<Bytes> {
+    for satellite in satellites.iter_mut() {
+        while let Poll::Ready(Some(item)) = satellite.from_protocol.poll_recv(cx) {
+            let capability = &satellite.capability;
+            if item.first().map_or(true, |id| *id >= capability.num_messages()) {
+                tracing::debug!(
+                    capability=%capability.name(),
+                    msg=%hex::encode(&item),
+                    "dropping satellite message with invalid id"
+                );
+                continue
+            }
+            if let Ok(compressed) =
+                compress_subprotocol_message(encoder, &item, capability.offset())
+            {
+                return Some(compressed)
+            }
+        }
+    }
+    None
+}
+
 /// Determines the offsets for each shared capability between the input list of peer
-/// capabilities and the input list of locally supported capabilities.
+/// capabilities and the input list of locally supported protocols.
 ///
-/// Currently only `eth` versions 66 and 67 are supported.
+/// All shared capabilities are returned, an `eth` capability must be shared.
 /// Additionally, the `p2p` capability version 5 is supported, but is
-/// expected _not_ to be in neither `local_capabilities` or `peer_capabilities`.
+/// expected _not_ to be in neither `local_protocols` or `peer_capabilities`.
 pub fn set_capability_offsets(
-    local_capabilities: Vec<Capability>,
+    local_protocols: Vec<Protocol>,
     peer_capabilities: Vec<Capability>,
-) -> Result<SharedCapability, P2PStreamError> {
-    // find intersection of capabilities
-    let our_capabilities = local_capabilities.into_iter().collect::<HashSet<_>>();
+) -> Result<SharedCapabilities, P2PStreamError> {
+    // find intersection of capabilities, alongside their message counts
+    let our_capabilities = local_protocols
+        .into_iter()
+        .map(|protocol| (protocol.cap, protocol.messages))
+        .collect::<HashMap<_, _>>();
 
     // map of capability name to version
     let mut shared_capabilities = HashMap::new();
@@ -584,7 +742,7 @@ pub fn set_capability_offsets(
     // find highest shared version of each shared capability
     for peer_capability in peer_capabilities {
         // if this is Some, we share this capability
-        if our_capabilities.contains(&peer_capability) {
+        if our_capabilities.contains_key(&peer_capability) {
             // If multiple versions are shared of the same (equal name) capability, the numerically
             // highest wins, others are ignored
 
@@ -612,35 +770,24 @@ pub fn set_capability_offsets(
     // alphabetic order.
     let mut offset = MAX_RESERVED_MESSAGE_ID + 1;
     for name in shared_capability_names {
-        let version = shared_capabilities.get(&name).unwrap();
+        let version = *shared_capabilities.get(&name).unwrap();
+        let cap = Capability::new(name, version);
+        let messages = our_capabilities[&cap];
 
-        let shared_capability = SharedCapability::new(&name, *version as u8, offset)?;
+        let shared_capability =
+            SharedCapability::from_protocol(&Protocol::new(cap, messages), offset)?;
+        offset += shared_capability.num_messages();
 
-        match shared_capability {
-            SharedCapability::UnknownCapability { .. } => {
-                // Capabilities which are not shared are ignored
-                tracing::debug!("unknown capability: name={:?}, version={}", name, version,);
-            }
-            SharedCapability::Eth { .. } => {
-                // increment the offset if the capability is known
-                offset += shared_capability.num_messages()?;
+        shared_with_offsets.push(shared_capability);
+    }
 
-                shared_with_offsets.push(shared_capability);
-            }
-        }
+    // `eth` is the primary capability of the stream, all other capabilities are satellite
+    // protocols that can't be negotiated without `eth`.
+    if !shared_with_offsets.iter().any(SharedCapability::is_eth) {
+        return Err(P2PStreamError::HandshakeError(P2PHandshakeError::NoSharedCapabilities))
     }
 
-    // TODO: support multiple capabilities - we would need a new Stream type to go on top of
-    // `P2PStream` containing its capability. `P2PStream` would still send pings and handle
-    // pongs, but instead contain a map of capabilities to their respective stream / channel.
-    // Each channel would be responsible for containing the offset for that stream and would
-    // only increment / decrement message IDs.
-    // NOTE: since the `P2PStream` currently only supports one capability, we set the
-    // capability with the lowest offset.
-    Ok(shared_with_offsets
-        .first()
-        .ok_or(P2PStreamError::HandshakeError(P2PHandshakeError::NoSharedCapabilities))?
-        .clone())
+    Ok(shared_with_offsets.into())
 }
 
 /// This represents only the reserved `p2p` subprotocol messages.
@@ -826,7 +973,7 @@ impl Decodable for ProtocolVersion {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{DisconnectReason, EthVersion};
+    use crate::{multiplex::SATELLITE_CHANNEL_CAPACITY, DisconnectReason, EthVersion};
     use reth_ecies::util::pk2id;
     use secp256k1::{SecretKey, SECP256K1};
     use tokio::net::{TcpListener, TcpStream};
@@ -984,27 +1131,205 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_multiplex_satellite_protocol() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let gossip = Protocol::new(Capability::new("gossip".into(), 1), 2);
+        let hello = || {
+            let (mut hello, _) = eth_hello();
+            hello.capabilities.push(gossip.cap.clone());
+            hello
+        };
+
+        let server_hello = hello();
+        let server_protocols = vec![gossip.clone()];
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+
+            let (mut p2p_stream, _) = UnauthedP2PStream::new(stream)
+                .handshake_with_protocols(server_hello, &server_protocols)
+                .await
+                .unwrap();
+            let mut gossip_stream = p2p_stream.install_protocol(&server_protocols[0].cap).unwrap();
+            assert!(p2p_stream.install_protocol(&server_protocols[0].cap).is_none());
+
+            // echo the satellite message and forward the primary message
+            let primary = p2p_stream.next().await.unwrap().unwrap();
+            let satellite = gossip_stream.next().await.unwrap();
+            gossip_stream.send(satellite.freeze()).unwrap();
+            p2p_stream.send(primary.freeze()).await.unwrap();
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = crate::PassthroughCodec::default().framed(outgoing);
+
+        let (mut p2p_stream, _) = UnauthedP2PStream::new(sink)
+            .handshake_with_protocols(hello(), &[gossip.clone()])
+            .await
+            .unwrap();
+        assert_eq!(p2p_stream.shared_capabilities().len(), 2);
+        assert!(p2p_stream.install_protocol(&EthVersion::Eth67.into()).is_none());
+        let mut gossip_stream = p2p_stream.install_protocol(&gossip.cap).unwrap();
+
+        // satellite message with an id outside of the protocol's range is dropped
+        gossip_stream.send(Bytes::from_static(&[0x02, 0xc0])).unwrap();
+        gossip_stream.send(Bytes::from_static(&[0x01, 0xc1, 0x80])).unwrap();
+        p2p_stream.send(Bytes::from_static(&[0x00, 0xc0])).await.unwrap();
+
+        let primary = p2p_stream.next().await.unwrap().unwrap();
+        assert_eq!(&primary[..], &[0x00, 0xc0]);
+        let satellite = gossip_stream.next().await.unwrap();
+        assert_eq!(&satellite[..], &[0x01, 0xc1, 0x80]);
+
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_satellite_buffer_full() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let gossip = Protocol::new(Capability::new("gossip".into(), 1), 2);
+        let hello = || {
+            let (mut hello, _) = eth_hello();
+            hello.capabilities.push(gossip.cap.clone());
+            hello
+        };
+
+        let server_hello = hello();
+        let server_protocols = vec![gossip.clone()];
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+
+            let (mut p2p_stream, _) = UnauthedP2PStream::new(stream)
+                .handshake_with_protocols(server_hello, &server_protocols)
+                .await
+                .unwrap();
+            // the satellite messages are never read
+            let _gossip_stream = p2p_stream.install_protocol(&server_protocols[0].cap).unwrap();
+
+            let err = p2p_stream.next().await.unwrap().unwrap_err();
+            assert!(matches!(err, P2PStreamError::SatelliteBufferFull(_)), "{err:?}");
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = crate::PassthroughCodec::default().framed(outgoing);
+
+        let (mut p2p_stream, _) = UnauthedP2PStream::new(sink)
+            .handshake_with_protocols(hello(), &[gossip.clone()])
+            .await
+            .unwrap();
+        let gossip_stream = p2p_stream.install_protocol(&gossip.cap).unwrap();
+
+        // messages are queued until the stream is flushed
+        let msg = Bytes::from_static(&[0x01, 0xc0]);
+        for _ in 0..SATELLITE_CHANNEL_CAPACITY {
+            gossip_stream.send(msg.clone()).unwrap();
+        }
+        assert!(matches!(gossip_stream.send(msg.clone()), Err(TrySendError::Full(_))));
+        p2p_stream.flush().await.unwrap();
+
+        // one more message than the peer buffers
+        gossip_stream.send(msg).unwrap();
+        p2p_stream.flush().await.unwrap();
+
+        handle.await.unwrap();
+    }
+
     #[test]
     fn test_peer_lower_capability_version() {
-        let local_capabilities: Vec<Capability> =
-            vec![EthVersion::Eth66.into(), EthVersion::Eth67.into(), EthVersion::Eth68.into()];
+        let local_protocols = vec![
+            Protocol::eth(EthVersion::Eth66),
+            Protocol::eth(EthVersion::Eth67),
+            Protocol::eth(EthVersion::Eth68),
+        ];
         let peer_capabilities: Vec<Capability> = vec![EthVersion::Eth66.into()];
 
-        let shared_capability =
-            set_capability_offsets(local_capabilities, peer_capabilities).unwrap();
+        let shared_capabilities =
+            set_capability_offsets(local_protocols, peer_capabilities).unwrap();
 
         assert_eq!(
-            shared_capability,
-            SharedCapability::Eth {
+            shared_capabilities.eth().unwrap(),
+            &SharedCapability::Eth {
                 version: EthVersion::Eth66,
                 offset: MAX_RESERVED_MESSAGE_ID + 1
             }
         )
     }
 
+    #[test]
+    fn test_shared_snap_capability() {
+        let local_protocols = vec![Protocol::eth(EthVersion::Eth68), Protocol::snap()];
+        let peer_capabilities: Vec<Capability> =
+            vec![Capability::new("snap".into(), 1), EthVersion::Eth68.into()];
+
+        let shared_capabilities =
+            set_capability_offsets(local_protocols, peer_capabilities).unwrap();
+
+        assert_eq!(
+            shared_capabilities.iter().cloned().collect::<Vec<_>>(),
+            vec![
+                SharedCapability::Eth {
+                    version: EthVersion::Eth68,
+                    offset: MAX_RESERVED_MESSAGE_ID + 1
+                },
+                SharedCapability::Snap {
+                    version: 1,
+                    offset: MAX_RESERVED_MESSAGE_ID + 1 + EthVersion::Eth68.total_messages()
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_shared_custom_capability() {
+        let gossip = Protocol::new(Capability::new("abc".into(), 1), 3);
+        let local_protocols = vec![Protocol::eth(EthVersion::Eth68), gossip.clone()];
+        let peer_capabilities: Vec<Capability> = vec![EthVersion::Eth68.into(), gossip.cap];
+
+        let shared_capabilities =
+            set_capability_offsets(local_protocols, peer_capabilities).unwrap();
+
+        // `abc` is ordered before `eth`
+        assert_eq!(
+            shared_capabilities.iter().cloned().collect::<Vec<_>>(),
+            vec![
+                SharedCapability::UnknownCapability {
+                    name: "abc".into(),
+                    version: 1,
+                    offset: MAX_RESERVED_MESSAGE_ID + 1,
+                    messages: 3
+                },
+                SharedCapability::Eth {
+                    version: EthVersion::Eth68,
+                    offset: MAX_RESERVED_MESSAGE_ID + 4
+                },
+            ]
+        );
+        assert_eq!(shared_capabilities.find_by_message_id(0x12).unwrap().name(), "abc");
+        assert_eq!(shared_capabilities.find_by_message_id(0x13).unwrap().name(), "eth");
+    }
+
+    #[test]
+    fn test_snap_without_eth() {
+        let local_capabilities = vec![Protocol::snap()];
+        let peer_capabilities: Vec<Capability> = vec![Capability::new("snap".into(), 1)];
+
+        let shared_capability = set_capability_offsets(local_capabilities, peer_capabilities);
+
+        assert!(matches!(
+            shared_capability,
+            Err(P2PStreamError::HandshakeError(P2PHandshakeError::NoSharedCapabilities))
+        ))
+    }
+
     #[test]
     fn test_peer_capability_version_too_low() {
-        let local_capabilities: Vec<Capability> = vec![EthVersion::Eth67.into()];
+        let local_capabilities = vec![Protocol::eth(EthVersion::Eth67)];
         let peer_capabilities: Vec<Capability> = vec![EthVersion::Eth66.into()];
 
         let shared_capability = set_capability_offsets(local_capabilities, peer_capabilities);
@@ -1017,7 +1342,7 @@ mod tests {
 
     #[test]
     fn test_peer_capability_version_too_high() {
-        let local_capabilities: Vec<Capability> = vec![EthVersion::Eth66.into()];
+        let local_capabilities = vec![Protocol::eth(EthVersion::Eth66)];
         let peer_capabilities: Vec<Capability> = vec![EthVersion::Eth67.into()];
 
         let shared_capability = set_capability_offsets(local_capabilities, peer_capabilities);
@@ -23,3 +23,6 @@ pub use state::*;
 
 pub mod receipts;
 pub use receipts::*;
+
+pub mod snap_sync;
+pub use snap_sync::*;
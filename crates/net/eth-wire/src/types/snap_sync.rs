@@ -0,0 +1,594 @@
+//! Implements the `snap/1` protocol message types.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+use reth_primitives::{
+    bytes::{Buf, BufMut},
+    proofs::EMPTY_ROOT,
+    Account, Bytes, H256, KECCAK_EMPTY, U256,
+};
+use reth_rlp::{
+    Decodable, DecodeError, Encodable, Header, RlpDecodable, RlpEncodable, EMPTY_STRING_CODE,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The only `snap` protocol version that is currently specified.
+pub const SNAP_VERSION: u8 = 1;
+
+/// Number of message ids reserved by the `snap/1` protocol.
+pub const SNAP_MESSAGE_COUNT: u8 = 8;
+
+/// Requests an unknown number of accounts from a given account trie, starting at the specified
+/// account hash and capped by the maximum allowed response size in bytes.
+///
+/// The returned range must be inclusive of `starting_hash` and should stop at `limit_hash` or
+/// once the response reaches `response_bytes`, whichever comes first.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetAccountRange {
+    /// Id of the request, echoed back in the response.
+    pub request_id: u64,
+    /// Root hash of the account trie to serve.
+    pub root_hash: H256,
+    /// Account hash of the first account to retrieve.
+    pub starting_hash: H256,
+    /// Account hash after which to stop serving data.
+    pub limit_hash: H256,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetAccountRange`]: a consecutive range of accounts and the Merkle proofs
+/// for the first and, if present, the last account of the range.
+#[derive(Clone, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountRange {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// List of consecutive accounts from the trie.
+    pub accounts: Vec<AccountData>,
+    /// List of trie nodes proving the account range.
+    pub proof: Vec<Bytes>,
+}
+
+/// A single account of an [`AccountRange`] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountData {
+    /// Hash of the account address.
+    pub hash: H256,
+    /// The account body in the slim format.
+    pub body: SnapAccount,
+}
+
+/// An account in the `snap` slim format.
+///
+/// This is identical to the account representation in the state trie, except that the empty
+/// storage root and the empty code hash are encoded as empty byte strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapAccount {
+    /// Account nonce.
+    pub nonce: u64,
+    /// Account balance.
+    pub balance: U256,
+    /// Root of the account's storage trie.
+    pub storage_root: H256,
+    /// Hash of the account's bytecode.
+    pub code_hash: H256,
+}
+
+impl SnapAccount {
+    /// Creates a new slim account from the given account and its storage root.
+    pub fn new(account: Account, storage_root: H256) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+        }
+    }
+
+    /// Converts the slim account into a database [`Account`], dropping the storage root.
+    pub fn into_account(self) -> Account {
+        Account {
+            nonce: self.nonce,
+            balance: self.balance,
+            bytecode_hash: (self.code_hash != KECCAK_EMPTY).then_some(self.code_hash),
+        }
+    }
+
+    /// Whether the account has a non-empty storage trie.
+    pub fn has_storage(&self) -> bool {
+        self.storage_root != EMPTY_ROOT
+    }
+
+    /// Whether the account has bytecode.
+    pub fn has_bytecode(&self) -> bool {
+        self.code_hash != KECCAK_EMPTY
+    }
+
+    /// Returns the slim encoding of the given hash: empty if it equals the `empty` placeholder.
+    fn slim_hash(hash: &H256, empty: H256) -> Option<&H256> {
+        (*hash != empty).then_some(hash)
+    }
+
+    fn payload_length(&self) -> usize {
+        let hash_length = |hash: Option<&H256>| hash.map_or(1, Encodable::length);
+        self.nonce.length() +
+            self.balance.length() +
+            hash_length(Self::slim_hash(&self.storage_root, EMPTY_ROOT)) +
+            hash_length(Self::slim_hash(&self.code_hash, KECCAK_EMPTY))
+    }
+}
+
+impl Encodable for SnapAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        for hash in [
+            Self::slim_hash(&self.storage_root, EMPTY_ROOT),
+            Self::slim_hash(&self.code_hash, KECCAK_EMPTY),
+        ] {
+            match hash {
+                Some(hash) => hash.encode(out),
+                None => out.put_u8(EMPTY_STRING_CODE),
+            }
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + reth_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SnapAccount {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(DecodeError::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let decode_hash = |buf: &mut &[u8], empty: H256| -> Result<H256, DecodeError> {
+            let bytes = Bytes::decode(buf)?;
+            match bytes.len() {
+                0 => Ok(empty),
+                32 => Ok(H256::from_slice(&bytes)),
+                _ => Err(DecodeError::Custom("invalid slim account hash length")),
+            }
+        };
+
+        let this = Self {
+            nonce: u64::decode(buf)?,
+            balance: U256::decode(buf)?,
+            storage_root: decode_hash(buf, EMPTY_ROOT)?,
+            code_hash: decode_hash(buf, KECCAK_EMPTY)?,
+        };
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(DecodeError::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(this)
+    }
+}
+
+/// Requests the storage slots of multiple accounts' storage tries.
+///
+/// Since the number of accounts requested is unbounded, the response may be capped, in which case
+/// only the storage of a prefix of the requested accounts is returned.
+///
+/// `starting_hash` and `limit_hash` are only meaningful if a single account is requested, they are
+/// encoded as possibly empty byte strings: an empty origin denotes the zero hash and an empty
+/// limit denotes the maximum hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetStorageRanges {
+    /// Id of the request, echoed back in the response.
+    pub request_id: u64,
+    /// Root hash of the account trie to serve.
+    pub root_hash: H256,
+    /// Account hashes of the storage tries to serve.
+    pub account_hashes: Vec<H256>,
+    /// Storage slot hash of the first slot to retrieve.
+    pub starting_hash: Bytes,
+    /// Storage slot hash after which to stop serving data.
+    pub limit_hash: Bytes,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+impl GetStorageRanges {
+    /// Returns the first slot hash to serve, defaulting to the zero hash.
+    pub fn starting_hash(&self) -> H256 {
+        decode_range_bound(&self.starting_hash).unwrap_or_default()
+    }
+
+    /// Returns the last slot hash to serve, defaulting to the maximum hash.
+    pub fn limit_hash(&self) -> H256 {
+        decode_range_bound(&self.limit_hash).unwrap_or(H256::repeat_byte(0xff))
+    }
+}
+
+/// Decodes a possibly empty or shorter than 32 bytes range bound into a right padded hash.
+fn decode_range_bound(bound: &Bytes) -> Option<H256> {
+    if bound.is_empty() {
+        return None
+    }
+    let mut hash = H256::zero();
+    let len = bound.len().min(32);
+    hash[..len].copy_from_slice(&bound[..len]);
+    Some(hash)
+}
+
+/// The response to [`GetStorageRanges`]: consecutive storage slots for the requested accounts and
+/// optionally the Merkle proof for the last, possibly partial, range.
+#[derive(Clone, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StorageRanges {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// List of lists of consecutive slots, one list per requested account.
+    pub slots: Vec<Vec<StorageData>>,
+    /// List of trie nodes proving the last slot range.
+    pub proof: Vec<Bytes>,
+}
+
+/// A single storage slot of a [`StorageRanges`] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StorageData {
+    /// Hash of the storage slot key.
+    pub hash: H256,
+    /// The RLP encoded storage slot value.
+    pub data: Bytes,
+}
+
+/// Requests a number of contract bytecodes by hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetByteCodes {
+    /// Id of the request, echoed back in the response.
+    pub request_id: u64,
+    /// Code hashes to retrieve the code for.
+    pub hashes: Vec<H256>,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetByteCodes`], containing the requested bytecodes in request order.
+///
+/// Missing codes are skipped, so the response may be a subsequence of the requested hashes.
+#[derive(Clone, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ByteCodes {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// The requested bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// Requests a number of state trie nodes by path.
+///
+/// Every path set consists of a compact encoded account trie path, optionally followed by a
+/// number of compact encoded storage trie paths of that account.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetTrieNodes {
+    /// Id of the request, echoed back in the response.
+    pub request_id: u64,
+    /// Root hash of the account trie to serve.
+    pub root_hash: H256,
+    /// Trie paths to retrieve the nodes for, grouped by account.
+    pub paths: Vec<Vec<Bytes>>,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetTrieNodes`], containing the requested trie nodes in request order.
+#[derive(Clone, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrieNodes {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// The requested trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+/// Represents message IDs for `snap` protocol messages.
+///
+/// These are relative to the offset of the `snap` capability of the connection.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SnapMessageId {
+    /// Identifier of [`GetAccountRange`]
+    GetAccountRange = 0x00,
+    /// Identifier of [`AccountRange`]
+    AccountRange = 0x01,
+    /// Identifier of [`GetStorageRanges`]
+    GetStorageRanges = 0x02,
+    /// Identifier of [`StorageRanges`]
+    StorageRanges = 0x03,
+    /// Identifier of [`GetByteCodes`]
+    GetByteCodes = 0x04,
+    /// Identifier of [`ByteCodes`]
+    ByteCodes = 0x05,
+    /// Identifier of [`GetTrieNodes`]
+    GetTrieNodes = 0x06,
+    /// Identifier of [`TrieNodes`]
+    TrieNodes = 0x07,
+}
+
+impl Encodable for SnapMessageId {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for SnapMessageId {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let id = buf.first().ok_or(DecodeError::InputTooShort)?;
+        let id = match id {
+            0x00 => SnapMessageId::GetAccountRange,
+            0x01 => SnapMessageId::AccountRange,
+            0x02 => SnapMessageId::GetStorageRanges,
+            0x03 => SnapMessageId::StorageRanges,
+            0x04 => SnapMessageId::GetByteCodes,
+            0x05 => SnapMessageId::ByteCodes,
+            0x06 => SnapMessageId::GetTrieNodes,
+            0x07 => SnapMessageId::TrieNodes,
+            _ => return Err(DecodeError::Custom("Invalid message ID")),
+        };
+        buf.advance(1);
+        Ok(id)
+    }
+}
+
+/// Represents a message in the `snap/1` protocol.
+///
+/// All `snap` messages are request-response pairs, and unlike the `eth` messages the request id is
+/// part of the flat message payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SnapMessage {
+    /// Request for a range of accounts
+    GetAccountRange(GetAccountRange),
+    /// Response to [`SnapMessage::GetAccountRange`]
+    AccountRange(AccountRange),
+    /// Request for ranges of storage slots
+    GetStorageRanges(GetStorageRanges),
+    /// Response to [`SnapMessage::GetStorageRanges`]
+    StorageRanges(StorageRanges),
+    /// Request for contract bytecodes
+    GetByteCodes(GetByteCodes),
+    /// Response to [`SnapMessage::GetByteCodes`]
+    ByteCodes(ByteCodes),
+    /// Request for trie nodes
+    GetTrieNodes(GetTrieNodes),
+    /// Response to [`SnapMessage::GetTrieNodes`]
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Decodes a message from the message id byte followed by the RLP encoded payload.
+    pub fn decode_message(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let message = match SnapMessageId::decode(buf)? {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(Decodable::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(Decodable::decode(buf)?),
+            SnapMessageId::GetStorageRanges => Self::GetStorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::StorageRanges => Self::StorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(Decodable::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(Decodable::decode(buf)?),
+        };
+        Ok(message)
+    }
+
+    /// Returns the message's ID.
+    pub fn message_id(&self) -> SnapMessageId {
+        match self {
+            SnapMessage::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            SnapMessage::AccountRange(_) => SnapMessageId::AccountRange,
+            SnapMessage::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            SnapMessage::StorageRanges(_) => SnapMessageId::StorageRanges,
+            SnapMessage::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            SnapMessage::ByteCodes(_) => SnapMessageId::ByteCodes,
+            SnapMessage::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            SnapMessage::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the request id of the message.
+    pub fn request_id(&self) -> u64 {
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.request_id,
+            SnapMessage::AccountRange(msg) => msg.request_id,
+            SnapMessage::GetStorageRanges(msg) => msg.request_id,
+            SnapMessage::StorageRanges(msg) => msg.request_id,
+            SnapMessage::GetByteCodes(msg) => msg.request_id,
+            SnapMessage::ByteCodes(msg) => msg.request_id,
+            SnapMessage::GetTrieNodes(msg) => msg.request_id,
+            SnapMessage::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Returns true if this is a request message.
+    pub fn is_request(&self) -> bool {
+        (self.message_id() as u8) % 2 == 0
+    }
+
+    fn payload_length(&self) -> usize {
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.length(),
+            SnapMessage::AccountRange(msg) => msg.length(),
+            SnapMessage::GetStorageRanges(msg) => msg.length(),
+            SnapMessage::StorageRanges(msg) => msg.length(),
+            SnapMessage::GetByteCodes(msg) => msg.length(),
+            SnapMessage::ByteCodes(msg) => msg.length(),
+            SnapMessage::GetTrieNodes(msg) => msg.length(),
+            SnapMessage::TrieNodes(msg) => msg.length(),
+        }
+    }
+}
+
+/// Encodes the message id as a single byte, followed by the RLP encoded message payload.
+impl Encodable for SnapMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.message_id().encode(out);
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.encode(out),
+            SnapMessage::AccountRange(msg) => msg.encode(out),
+            SnapMessage::GetStorageRanges(msg) => msg.encode(out),
+            SnapMessage::StorageRanges(msg) => msg.encode(out),
+            SnapMessage::GetByteCodes(msg) => msg.encode(out),
+            SnapMessage::ByteCodes(msg) => msg.encode(out),
+            SnapMessage::GetTrieNodes(msg) => msg.encode(out),
+            SnapMessage::TrieNodes(msg) => msg.encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        1 + self.payload_length()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hex_literal::hex;
+
+    fn roundtrip(message: SnapMessage) {
+        let mut encoded = vec![];
+        message.encode(&mut encoded);
+        assert_eq!(encoded.len(), message.length());
+        let decoded = SnapMessage::decode_message(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn encode_get_byte_codes() {
+        let request = GetByteCodes { request_id: 1, hashes: vec![], response_bytes: 1024 };
+        let mut data = vec![];
+        SnapMessage::GetByteCodes(request).encode(&mut data);
+        assert_eq!(data, hex!("04c501c0820400"));
+    }
+
+    #[test]
+    fn encode_empty_slim_account() {
+        let account = SnapAccount::new(Account::default(), EMPTY_ROOT);
+        let mut data = vec![];
+        account.encode(&mut data);
+        assert_eq!(data, hex!("c480808080"));
+        assert_eq!(account.length(), data.len());
+        assert_eq!(SnapAccount::decode(&mut &data[..]).unwrap(), account);
+        assert_eq!(account.into_account(), Account::default());
+    }
+
+    #[test]
+    fn slim_account_with_storage_and_code() {
+        let account = SnapAccount {
+            nonce: 7,
+            balance: U256::from(1_000_000u64),
+            storage_root: H256::repeat_byte(0x11),
+            code_hash: H256::repeat_byte(0x22),
+        };
+        let mut data = vec![];
+        account.encode(&mut data);
+        assert_eq!(account.length(), data.len());
+        let decoded = SnapAccount::decode(&mut &data[..]).unwrap();
+        assert_eq!(decoded, account);
+        assert!(decoded.has_storage());
+        assert!(decoded.has_bytecode());
+    }
+
+    #[test]
+    fn storage_range_bounds() {
+        let mut request = GetStorageRanges {
+            request_id: 0,
+            root_hash: H256::zero(),
+            account_hashes: vec![H256::zero()],
+            starting_hash: Bytes::default(),
+            limit_hash: Bytes::default(),
+            response_bytes: 0,
+        };
+        assert_eq!(request.starting_hash(), H256::zero());
+        assert_eq!(request.limit_hash(), H256::repeat_byte(0xff));
+
+        request.starting_hash = Bytes::from(vec![0xab]);
+        let mut expected = H256::zero();
+        expected[0] = 0xab;
+        assert_eq!(request.starting_hash(), expected);
+    }
+
+    #[test]
+    fn snap_message_roundtrip() {
+        roundtrip(SnapMessage::GetAccountRange(GetAccountRange {
+            request_id: 1,
+            root_hash: H256::repeat_byte(1),
+            starting_hash: H256::zero(),
+            limit_hash: H256::repeat_byte(0xff),
+            response_bytes: 512 * 1024,
+        }));
+        roundtrip(SnapMessage::AccountRange(AccountRange {
+            request_id: 1,
+            accounts: vec![AccountData {
+                hash: H256::repeat_byte(2),
+                body: SnapAccount::new(Account::default(), EMPTY_ROOT),
+            }],
+            proof: vec![Bytes::from(vec![0xc0])],
+        }));
+        roundtrip(SnapMessage::GetStorageRanges(GetStorageRanges {
+            request_id: 2,
+            root_hash: H256::repeat_byte(1),
+            account_hashes: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+            starting_hash: Bytes::default(),
+            limit_hash: Bytes::default(),
+            response_bytes: 512 * 1024,
+        }));
+        roundtrip(SnapMessage::StorageRanges(StorageRanges {
+            request_id: 2,
+            slots: vec![
+                vec![StorageData { hash: H256::repeat_byte(4), data: Bytes::from(vec![0x01]) }],
+                vec![],
+            ],
+            proof: vec![],
+        }));
+        roundtrip(SnapMessage::GetByteCodes(GetByteCodes {
+            request_id: 3,
+            hashes: vec![KECCAK_EMPTY],
+            response_bytes: 1024,
+        }));
+        roundtrip(SnapMessage::ByteCodes(ByteCodes {
+            request_id: 3,
+            codes: vec![Bytes::from(vec![0x60, 0x00])],
+        }));
+        roundtrip(SnapMessage::GetTrieNodes(GetTrieNodes {
+            request_id: 4,
+            root_hash: H256::repeat_byte(1),
+            paths: vec![
+                vec![Bytes::from(vec![0x00])],
+                vec![Bytes::default(), Bytes::from(vec![0x12])],
+            ],
+            response_bytes: 1024,
+        }));
+        roundtrip(SnapMessage::TrieNodes(TrieNodes { request_id: 4, nodes: vec![] }));
+    }
+
+    #[test]
+    fn reject_invalid_message_id() {
+        let data = hex!("08c0");
+        assert!(SnapMessage::decode_message(&mut &data[..]).is_err());
+    }
+}
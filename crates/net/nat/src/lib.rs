@@ -11,12 +11,18 @@
     attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
 ))]
 
-//! Helpers for resolving the external IP.
+//! Helpers for resolving the external IP and mapping ports on the gateway.
 //!
 //! ## Feature Flags
 //!
 //! - `serde` (default): Enable serde support
 
+pub mod mapping;
+pub mod natpmp;
+pub mod pcp;
+
+pub use mapping::{MappingProtocol, PortMapping, PortMappingError, PortMappingInterval};
+
 use igd::aio::search_gateway;
 use pin_project_lite::pin_project;
 use std::{
@@ -42,6 +48,16 @@ pub enum NatResolver {
     Any,
     /// Resolve via Upnp
     Upnp,
+    /// Resolve via NAT-PMP and map ports, see [PortMappingInterval]
+    ///
+    /// Note: the ports are mapped by the network, which advertises the mappings via discv4 and
+    /// discv5.
+    NatPmp,
+    /// Resolve via PCP and map ports, see [PortMappingInterval]
+    ///
+    /// Note: the ports are mapped by the network, which advertises the mappings via discv4 and
+    /// discv5.
+    Pcp,
     /// Resolve external IP via [public_ip::Resolver]
     PublicIp,
     /// Use the given [IpAddr]
@@ -57,6 +73,11 @@ impl NatResolver {
     pub async fn external_addr(self) -> Option<IpAddr> {
         external_addr_with(self).await
     }
+
+    /// Returns `true` if this resolver maps ports on the gateway.
+    pub fn maps_ports(&self) -> bool {
+        matches!(self, NatResolver::NatPmp | NatResolver::Pcp)
+    }
 }
 
 impl fmt::Display for NatResolver {
@@ -64,6 +85,8 @@ impl fmt::Display for NatResolver {
         match self {
             NatResolver::Any => f.write_str("any"),
             NatResolver::Upnp => f.write_str("upnp"),
+            NatResolver::NatPmp => f.write_str("natpmp"),
+            NatResolver::Pcp => f.write_str("pcp"),
             NatResolver::PublicIp => f.write_str("publicip"),
             NatResolver::ExternalIp(ip) => write!(f, "extip:{ip}"),
             NatResolver::None => f.write_str("none"),
@@ -89,6 +112,8 @@ impl FromStr for NatResolver {
         let r = match s {
             "any" => NatResolver::Any,
             "upnp" => NatResolver::Upnp,
+            "natpmp" | "nat-pmp" => NatResolver::NatPmp,
+            "pcp" => NatResolver::Pcp,
             "none" => NatResolver::None,
             "publicip" | "public-ip" => NatResolver::PublicIp,
             s => {
@@ -186,6 +211,8 @@ pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
             .await
         }
         NatResolver::Upnp => resolve_external_ip_upnp().await,
+        NatResolver::NatPmp => resolve_external_ip_natpmp().await,
+        NatResolver::Pcp => resolve_external_ip_pcp().await,
         NatResolver::PublicIp => resolve_external_ip().await,
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::None => None,
@@ -253,6 +280,32 @@ async fn resolve_external_ip_upnp() -> Option<IpAddr> {
         .ok()
 }
 
+async fn resolve_external_ip_natpmp() -> Option<IpAddr> {
+    let client = natpmp::NatPmpClient::default_gateway().await;
+    let ip = match client {
+        Ok(client) => client.external_ip().await.map(IpAddr::V4),
+        Err(err) => Err(err),
+    };
+    ip.map_err(|err| {
+        debug!(target: "net::nat", ?err, "Failed to resolve external IP via NAT-PMP");
+        err
+    })
+    .ok()
+}
+
+async fn resolve_external_ip_pcp() -> Option<IpAddr> {
+    let client = pcp::PcpClient::default_gateway().await;
+    let ip = match client {
+        Ok(client) => client.external_ip().await,
+        Err(err) => Err(err),
+    };
+    ip.map_err(|err| {
+        debug!(target: "net::nat", ?err, "Failed to resolve external IP via PCP");
+        err
+    })
+    .ok()
+}
+
 async fn resolve_external_ip() -> Option<IpAddr> {
     public_ip::addr().await
 }
@@ -286,6 +339,8 @@ mod tests {
     fn test_from_str() {
         assert_eq!(NatResolver::Any, "any".parse().unwrap());
         assert_eq!(NatResolver::None, "none".parse().unwrap());
+        assert_eq!(NatResolver::NatPmp, "natpmp".parse().unwrap());
+        assert_eq!(NatResolver::Pcp, "pcp".parse().unwrap());
 
         let ip = NatResolver::ExternalIp(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         let s = "extip:0.0.0.0";
@@ -0,0 +1,430 @@
+//! Port mappings on the gateway via NAT-PMP or PCP.
+//!
+//! Both protocols talk to the gateway over UDP on port [`GATEWAY_PORT`]. A mapping is only valid
+//! for the lifetime granted by the gateway and must be renewed before it expires, see
+//! [`PortMappingInterval`].
+
+use crate::{natpmp::NatPmpClient, pcp::PcpClient, NatResolver};
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{net::UdpSocket, time::Instant};
+
+/// The UDP port NAT-PMP and PCP servers listen on.
+pub const GATEWAY_PORT: u16 = 5351;
+
+/// The default lifetime requested for port mappings, as recommended by RFC 6886.
+pub const DEFAULT_PORT_MAPPING_LIFETIME: Duration = Duration::from_secs(60 * 60 * 2);
+
+/// How often a request is sent before giving up on the gateway.
+const MAX_ATTEMPTS: u32 = 4;
+
+/// The time to wait for the first response, doubled with every retransmission, see RFC 6886.
+const INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// The delay before the first retry of failed mappings, doubled with every failed retry.
+const INITIAL_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The maximum delay between retries of failed mappings.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// The minimum delay between renewals, in case the gateway grants (almost) no lifetime.
+const MIN_RENEWAL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MappingProtocol {
+    /// A TCP port, used by RLPx.
+    Tcp,
+    /// A UDP port, used by discovery.
+    Udp,
+}
+
+impl fmt::Display for MappingProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingProtocol::Tcp => f.write_str("tcp"),
+            MappingProtocol::Udp => f.write_str("udp"),
+        }
+    }
+}
+
+/// A port mapping granted by the gateway.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PortMapping {
+    /// The transport protocol of the mapping.
+    pub protocol: MappingProtocol,
+    /// The local port that is mapped.
+    pub internal_port: u16,
+    /// The address under which the local port is reachable from outside.
+    pub external_addr: SocketAddr,
+    /// How long the mapping is valid.
+    pub lifetime: Duration,
+}
+
+/// Errors that can occur when mapping ports on the gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// Failed to talk to the gateway.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// No gateway configured and the default gateway could not be determined.
+    #[error("no gateway found")]
+    NoGateway,
+    /// The gateway did not respond.
+    #[error("gateway did not respond")]
+    Timeout,
+    /// The gateway responded with a malformed message.
+    #[error("invalid response from gateway")]
+    InvalidResponse,
+    /// The gateway rejected the request.
+    #[error("gateway rejected request with result code {0}")]
+    Rejected(u16),
+    /// The protocol does not support the address family of the gateway.
+    #[error("unsupported gateway address {0}")]
+    UnsupportedGateway(IpAddr),
+}
+
+/// Returns the default IPv4 gateway of this host, if it can be determined.
+///
+/// This is only supported on linux.
+pub fn default_gateway() -> Option<Ipv4Addr> {
+    #[cfg(target_os = "linux")]
+    {
+        let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+        parse_default_gateway(&routes)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Parses the default gateway from the contents of `/proc/net/route`.
+///
+/// Addresses are hex encoded in host byte order.
+#[cfg_attr(not(any(test, target_os = "linux")), allow(dead_code))]
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace().skip(1);
+        let destination = fields.next()?;
+        let gateway = fields.next()?;
+        if destination != "00000000" {
+            return None
+        }
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        Some(Ipv4Addr::from(gateway.to_le_bytes()))
+    })
+}
+
+/// Returns the address of the NAT-PMP or PCP server on the default gateway.
+pub(crate) fn default_gateway_addr() -> Result<SocketAddr, PortMappingError> {
+    let gateway = default_gateway().ok_or(PortMappingError::NoGateway)?;
+    Ok(SocketAddr::new(gateway.into(), GATEWAY_PORT))
+}
+
+/// Binds a new UDP socket that only talks to the gateway.
+pub(crate) async fn connect(gateway: SocketAddr) -> Result<UdpSocket, PortMappingError> {
+    let local: SocketAddr = match gateway {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (std::net::Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local).await?;
+    socket.connect(gateway).await?;
+    Ok(socket)
+}
+
+/// Sends the request to the gateway until a message is received for which `is_response` returns
+/// `true`.
+///
+/// The request is retransmitted with an exponentially increasing timeout.
+pub(crate) async fn request(
+    socket: &UdpSocket,
+    request: &[u8],
+    buf: &mut [u8],
+    is_response: impl Fn(&[u8]) -> bool,
+) -> Result<usize, PortMappingError> {
+    let mut timeout = INITIAL_TIMEOUT;
+    for _ in 0..MAX_ATTEMPTS {
+        socket.send(request).await?;
+        let deadline = Instant::now() + timeout;
+        while let Ok(len) = tokio::time::timeout_at(deadline, socket.recv(buf)).await {
+            let len = len?;
+            // ignore stray messages, e.g. responses to previous attempts or announcements
+            if is_response(&buf[..len]) {
+                return Ok(len)
+            }
+        }
+        timeout *= 2;
+    }
+    Err(PortMappingError::Timeout)
+}
+
+/// The protocol used to map ports.
+#[derive(Debug, Clone, Copy)]
+enum PortMapper {
+    NatPmp,
+    Pcp,
+}
+
+/// The outcome of mapping every port, or the error if the gateway could not be reached at all.
+type MapPortsResult = Result<Vec<Result<PortMapping, PortMappingError>>, PortMappingError>;
+
+type MapPortsFut = Pin<Box<dyn Future<Output = MapPortsResult> + Send>>;
+
+/// Maps a set of local ports on the gateway and renews the mappings on an interval basis.
+///
+/// The mappings are renewed after half of the shortest lifetime granted by the gateway, which may
+/// be shorter than the requested lifetime. If mapping a port fails, all ports are retried with an
+/// exponential backoff, starting at a few seconds.
+///
+/// The mappings are deleted on the gateway when the interval is dropped inside a tokio runtime.
+#[must_use = "Does nothing unless polled"]
+pub struct PortMappingInterval {
+    mapper: PortMapper,
+    /// The gateway to use, the default gateway if not set.
+    gateway: Option<SocketAddr>,
+    /// The local ports to map.
+    ports: Vec<(MappingProtocol, u16)>,
+    /// The lifetime to request.
+    lifetime: Duration,
+    /// Identifies the mappings of this interval for PCP, so they can be renewed.
+    nonce: [u8; 12],
+    /// The most recent mappings, the external ports are requested again on renewal.
+    mappings: Vec<PortMapping>,
+    future: Option<MapPortsFut>,
+    /// Fires when the ports are due for renewal or a retry.
+    delay: Pin<Box<tokio::time::Sleep>>,
+    /// The delay before the next retry if mapping fails again.
+    retry_interval: Duration,
+}
+
+// === impl PortMappingInterval ===
+
+impl PortMappingInterval {
+    /// Creates a new [PortMappingInterval] that maps the given local ports with the requested
+    /// lifetime.
+    ///
+    /// Returns `None` if the [NatResolver] does not map ports, see [NatResolver::maps_ports].
+    ///
+    /// # Panics
+    ///
+    /// If the lifetime is zero.
+    #[track_caller]
+    pub fn new(
+        resolver: NatResolver,
+        ports: impl IntoIterator<Item = (MappingProtocol, u16)>,
+        lifetime: Duration,
+    ) -> Option<Self> {
+        let mapper = match resolver {
+            NatResolver::NatPmp => PortMapper::NatPmp,
+            NatResolver::Pcp => PortMapper::Pcp,
+            _ => return None,
+        };
+        assert!(!lifetime.is_zero(), "port mapping lifetime must be non-zero");
+        Some(Self {
+            mapper,
+            gateway: None,
+            ports: ports.into_iter().collect(),
+            lifetime,
+            nonce: rand::random(),
+            mappings: Vec::new(),
+            future: None,
+            delay: Box::pin(tokio::time::sleep(Duration::ZERO)),
+            retry_interval: INITIAL_RETRY_INTERVAL,
+        })
+    }
+
+    /// Uses the given gateway instead of the default gateway.
+    pub fn with_gateway(mut self, gateway: SocketAddr) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+
+    /// Returns the most recent mappings.
+    pub fn mappings(&self) -> &[PortMapping] {
+        &self.mappings
+    }
+
+    /// Returns the time at which the ports are mapped next, either to renew the mappings or to
+    /// retry after a failure.
+    pub fn next_renewal(&self) -> Instant {
+        self.delay.deadline()
+    }
+
+    /// Completes when the ports have been mapped or renewed.
+    pub async fn tick(&mut self) -> Result<Vec<PortMapping>, PortMappingError> {
+        poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    /// Polls for the ports to be mapped or renewed.
+    ///
+    /// This method can return the following values:
+    ///
+    ///  * `Poll::Pending` if the ports are not due for renewal or the gateway has not responded
+    ///    yet.
+    ///  * `Poll::Ready(Ok(mappings))` if all ports have been mapped.
+    ///  * `Poll::Ready(Err(err))` if mapping a port failed. The ports that were mapped successfully
+    ///    are still updated in [Self::mappings], and all ports are retried after a short backoff.
+    pub fn poll_tick(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Vec<PortMapping>, PortMappingError>> {
+        if self.future.is_none() && self.delay.as_mut().poll(cx).is_ready() {
+            let requests = self
+                .ports
+                .iter()
+                .map(|&(protocol, port)| {
+                    let external_port = self
+                        .mappings
+                        .iter()
+                        .find(|m| m.protocol == protocol && m.internal_port == port)
+                        .map(|m| m.external_addr.port())
+                        .unwrap_or(port);
+                    (protocol, port, external_port)
+                })
+                .collect();
+            self.future = Some(Box::pin(map_ports(
+                self.mapper,
+                self.gateway,
+                requests,
+                self.lifetime,
+                self.nonce,
+            )));
+        }
+
+        if let Some(mut fut) = self.future.take() {
+            match fut.as_mut().poll(cx) {
+                Poll::Ready(res) => return Poll::Ready(self.on_mapped(res)),
+                Poll::Pending => {
+                    self.future = Some(fut);
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Records the new mappings and schedules the next renewal, or a retry if a port failed.
+    fn on_mapped(&mut self, res: MapPortsResult) -> Result<Vec<PortMapping>, PortMappingError> {
+        let mut first_err = None;
+        let mut mapped = Vec::with_capacity(self.ports.len());
+        match res {
+            Ok(results) => {
+                for res in results {
+                    match res {
+                        Ok(mapping) => mapped.push(mapping),
+                        Err(err) => {
+                            first_err.get_or_insert(err);
+                        }
+                    }
+                }
+            }
+            Err(err) => first_err = Some(err),
+        }
+
+        // keep the previous mappings of failed ports, their external ports are requested again
+        for mapping in &mapped {
+            match self.mappings.iter_mut().find(|m| {
+                m.protocol == mapping.protocol && m.internal_port == mapping.internal_port
+            }) {
+                Some(existing) => *existing = *mapping,
+                None => self.mappings.push(*mapping),
+            }
+        }
+
+        if let Some(err) = first_err {
+            let retry = self.retry_interval;
+            self.retry_interval = (retry * 2).min(MAX_RETRY_INTERVAL);
+            self.delay.as_mut().reset(Instant::now() + retry);
+            return Err(err)
+        }
+
+        self.retry_interval = INITIAL_RETRY_INTERVAL;
+        let lifetime = mapped.iter().map(|m| m.lifetime).min().unwrap_or(self.lifetime);
+        self.delay.as_mut().reset(Instant::now() + (lifetime / 2).max(MIN_RENEWAL_INTERVAL));
+        Ok(mapped)
+    }
+}
+
+impl Drop for PortMappingInterval {
+    fn drop(&mut self) {
+        if self.mappings.is_empty() {
+            return
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else { return };
+        // a lifetime of zero deletes the mapping, the suggested external port must be zero
+        let requests = self.mappings.iter().map(|m| (m.protocol, m.internal_port, 0)).collect();
+        handle.spawn(map_ports(self.mapper, self.gateway, requests, Duration::ZERO, self.nonce));
+    }
+}
+
+impl fmt::Debug for PortMappingInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PortMappingInterval")
+            .field("mapper", &self.mapper)
+            .field("gateway", &self.gateway)
+            .field("ports", &self.ports)
+            .field("lifetime", &self.lifetime)
+            .field("mappings", &self.mappings)
+            .field("next_renewal", &self.next_renewal())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Maps all `(protocol, internal port, suggested external port)` on the gateway.
+///
+/// Every port is attempted, even if mapping a previous port failed.
+async fn map_ports(
+    mapper: PortMapper,
+    gateway: Option<SocketAddr>,
+    requests: Vec<(MappingProtocol, u16, u16)>,
+    lifetime: Duration,
+    nonce: [u8; 12],
+) -> MapPortsResult {
+    let gateway = match gateway {
+        Some(gateway) => gateway,
+        None => default_gateway_addr()?,
+    };
+    let mut mappings = Vec::with_capacity(requests.len());
+    match mapper {
+        PortMapper::NatPmp => {
+            let client = NatPmpClient::new(gateway).await?;
+            for (protocol, internal_port, external_port) in requests {
+                mappings
+                    .push(client.map_port(protocol, internal_port, external_port, lifetime).await);
+            }
+        }
+        PortMapper::Pcp => {
+            let client = PcpClient::new(gateway).await?.with_nonce(nonce);
+            for (protocol, internal_port, external_port) in requests {
+                mappings
+                    .push(client.map_port(protocol, internal_port, external_port, lifetime).await);
+            }
+        }
+    }
+    Ok(mappings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_gateway() {
+        let routes = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t0000A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+eth0\t00000000\t0100A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+";
+        assert_eq!(parse_default_gateway(routes), Some(Ipv4Addr::new(192, 168, 0, 1)));
+        let without_default = routes.lines().take(2).collect::<Vec<_>>().join("\n");
+        assert_eq!(parse_default_gateway(&without_default), None);
+    }
+}
@@ -0,0 +1,283 @@
+//! NAT-PMP client, see [RFC 6886](https://datatracker.ietf.org/doc/html/rfc6886).
+
+use crate::mapping::{self, MappingProtocol, PortMapping, PortMappingError};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// The protocol version of NAT-PMP.
+const VERSION: u8 = 0;
+
+/// Opcode of the external address request.
+const OP_EXTERNAL_ADDRESS: u8 = 0;
+
+/// Opcode of a UDP mapping request.
+const OP_MAP_UDP: u8 = 1;
+
+/// Opcode of a TCP mapping request.
+const OP_MAP_TCP: u8 = 2;
+
+/// Added to the opcode of the request in the response.
+const RESPONSE_FLAG: u8 = 128;
+
+/// Result code of a successful request.
+const RESULT_SUCCESS: u16 = 0;
+
+/// A NAT-PMP client that talks to a single gateway.
+///
+/// NAT-PMP only supports IPv4.
+#[derive(Debug)]
+pub struct NatPmpClient {
+    socket: UdpSocket,
+}
+
+// === impl NatPmpClient ===
+
+impl NatPmpClient {
+    /// Creates a new client for the NAT-PMP server at the given address.
+    pub async fn new(gateway: SocketAddr) -> Result<Self, PortMappingError> {
+        if !gateway.is_ipv4() {
+            return Err(PortMappingError::UnsupportedGateway(gateway.ip()))
+        }
+        Ok(Self { socket: mapping::connect(gateway).await? })
+    }
+
+    /// Creates a new client for the NAT-PMP server on the default gateway.
+    pub async fn default_gateway() -> Result<Self, PortMappingError> {
+        Self::new(mapping::default_gateway_addr()?).await
+    }
+
+    /// Requests the external IP address of the gateway.
+    pub async fn external_ip(&self) -> Result<Ipv4Addr, PortMappingError> {
+        let mut buf = [0u8; 16];
+        let len =
+            mapping::request(&self.socket, &[VERSION, OP_EXTERNAL_ADDRESS], &mut buf, |msg| {
+                is_response_to(msg, OP_EXTERNAL_ADDRESS)
+            })
+            .await?;
+        let msg = &buf[..len];
+        if msg.len() < 12 {
+            return Err(PortMappingError::InvalidResponse)
+        }
+        check_result_code(msg)?;
+        Ok(Ipv4Addr::new(msg[8], msg[9], msg[10], msg[11]))
+    }
+
+    /// Maps the local port on the gateway, or renews an existing mapping.
+    ///
+    /// The gateway tries to map the suggested external port, but is free to pick a different one.
+    /// A lifetime of zero removes the mapping.
+    pub async fn map_port(
+        &self,
+        protocol: MappingProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: Duration,
+    ) -> Result<PortMapping, PortMappingError> {
+        let op = match protocol {
+            MappingProtocol::Udp => OP_MAP_UDP,
+            MappingProtocol::Tcp => OP_MAP_TCP,
+        };
+        let lifetime = u32::try_from(lifetime.as_secs()).unwrap_or(u32::MAX);
+
+        let mut req = [0u8; 12];
+        req[0] = VERSION;
+        req[1] = op;
+        req[4..6].copy_from_slice(&internal_port.to_be_bytes());
+        req[6..8].copy_from_slice(&external_port.to_be_bytes());
+        req[8..12].copy_from_slice(&lifetime.to_be_bytes());
+
+        let mut buf = [0u8; 16];
+        let len = mapping::request(&self.socket, &req, &mut buf, |msg| {
+            // the response echoes the internal port
+            is_response_to(msg, op) && msg.len() >= 10 && msg[8..10] == req[4..6]
+        })
+        .await?;
+        let msg = &buf[..len];
+        if msg.len() < 16 {
+            return Err(PortMappingError::InvalidResponse)
+        }
+        check_result_code(msg)?;
+        let external_port = u16::from_be_bytes([msg[10], msg[11]]);
+        let lifetime = u32::from_be_bytes([msg[12], msg[13], msg[14], msg[15]]);
+
+        let external_ip = self.external_ip().await?;
+        Ok(PortMapping {
+            protocol,
+            internal_port,
+            external_addr: SocketAddr::new(IpAddr::V4(external_ip), external_port),
+            lifetime: Duration::from_secs(lifetime as u64),
+        })
+    }
+}
+
+/// Returns `true` if the message is a response to a request with the given opcode.
+fn is_response_to(msg: &[u8], op: u8) -> bool {
+    msg.len() >= 4 && msg[0] == VERSION && msg[1] == RESPONSE_FLAG + op
+}
+
+/// Returns an error if the response does not indicate success.
+fn check_result_code(msg: &[u8]) -> Result<(), PortMappingError> {
+    let code = u16::from_be_bytes([msg[2], msg[3]]);
+    if code != RESULT_SUCCESS {
+        return Err(PortMappingError::Rejected(code))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mapping::PortMappingInterval, NatResolver};
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+    const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(1, 2, 3, 4);
+
+    /// Spawns a stand-in NAT-PMP gateway that maps every port to the port plus one.
+    async fn spawn_gateway() -> SocketAddr {
+        spawn_gateway_with(u32::MAX, None).await.0
+    }
+
+    /// Spawns a stand-in NAT-PMP gateway that grants at most `max_lifetime` seconds and rejects
+    /// mapping the `rejected` port.
+    ///
+    /// Every mapping request is reported as `(internal port, suggested external port, lifetime)`.
+    async fn spawn_gateway_with(
+        max_lifetime: u32,
+        rejected: Option<u16>,
+    ) -> (SocketAddr, UnboundedReceiver<(u16, u16, u32)>) {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        let (requests_tx, requests_rx) = unbounded_channel();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 64];
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                let req = &buf[..len];
+                let mut resp = vec![VERSION, RESPONSE_FLAG + req[1], 0, 0, 0, 0, 0, 42];
+                match req[1] {
+                    OP_EXTERNAL_ADDRESS => resp.extend_from_slice(&EXTERNAL_IP.octets()),
+                    OP_MAP_UDP | OP_MAP_TCP => {
+                        let internal_port = u16::from_be_bytes([req[4], req[5]]);
+                        let external_port = u16::from_be_bytes([req[6], req[7]]);
+                        let lifetime = u32::from_be_bytes([req[8], req[9], req[10], req[11]]);
+                        let _ = requests_tx.send((internal_port, external_port, lifetime));
+                        resp.extend_from_slice(&req[4..6]);
+                        resp.extend_from_slice(&(internal_port + 1).to_be_bytes());
+                        resp.extend_from_slice(&lifetime.min(max_lifetime).to_be_bytes());
+                        if rejected == Some(internal_port) {
+                            // NOT_AUTHORIZED
+                            resp[3] = 2;
+                        }
+                    }
+                    _ => resp[3] = 5,
+                }
+                socket.send_to(&resp, from).await.unwrap();
+            }
+        });
+        (addr, requests_rx)
+    }
+
+    #[tokio::test]
+    async fn test_map_port() {
+        let gateway = spawn_gateway().await;
+        let client = NatPmpClient::new(gateway).await.unwrap();
+
+        assert_eq!(client.external_ip().await.unwrap(), EXTERNAL_IP);
+
+        let mapping = client
+            .map_port(MappingProtocol::Tcp, 30303, 30303, Duration::from_secs(7200))
+            .await
+            .unwrap();
+        assert_eq!(mapping.external_addr, SocketAddr::new(EXTERNAL_IP.into(), 30304));
+        assert_eq!(mapping.lifetime, Duration::from_secs(7200));
+    }
+
+    #[tokio::test]
+    async fn test_port_mapping_interval() {
+        let gateway = spawn_gateway().await;
+        let mut interval = PortMappingInterval::new(
+            NatResolver::NatPmp,
+            [(MappingProtocol::Udp, 30303), (MappingProtocol::Tcp, 30303)],
+            Duration::from_secs(7200),
+        )
+        .unwrap()
+        .with_gateway(gateway);
+
+        let mappings = interval.tick().await.unwrap();
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(mappings[0].protocol, MappingProtocol::Udp);
+        assert_eq!(mappings[1].external_addr, SocketAddr::new(EXTERNAL_IP.into(), 30304));
+        assert_eq!(interval.mappings(), mappings.as_slice());
+
+        assert!(PortMappingInterval::new(NatResolver::Upnp, [], Duration::from_secs(1)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_port_mapping_interval_granted_lifetime() {
+        // the gateway grants only 10 minutes instead of the requested 2 hours
+        let (gateway, _) = spawn_gateway_with(600, None).await;
+        let mut interval = PortMappingInterval::new(
+            NatResolver::NatPmp,
+            [(MappingProtocol::Udp, 30303), (MappingProtocol::Tcp, 30303)],
+            Duration::from_secs(7200),
+        )
+        .unwrap()
+        .with_gateway(gateway);
+
+        let mappings = interval.tick().await.unwrap();
+        assert!(mappings.iter().all(|m| m.lifetime == Duration::from_secs(600)));
+
+        // renewed after half of the granted lifetime
+        let renewal = interval.next_renewal() - tokio::time::Instant::now();
+        assert!(renewal <= Duration::from_secs(300), "{renewal:?}");
+        assert!(renewal > Duration::from_secs(290), "{renewal:?}");
+    }
+
+    #[tokio::test]
+    async fn test_port_mapping_interval_partial_failure() {
+        let (gateway, _) = spawn_gateway_with(u32::MAX, Some(30304)).await;
+        let mut interval = PortMappingInterval::new(
+            NatResolver::NatPmp,
+            [(MappingProtocol::Tcp, 30304), (MappingProtocol::Udp, 30303)],
+            Duration::from_secs(7200),
+        )
+        .unwrap()
+        .with_gateway(gateway);
+
+        let err = interval.tick().await.unwrap_err();
+        assert!(matches!(err, PortMappingError::Rejected(2)), "{err:?}");
+
+        // the port after the rejected one is still mapped
+        assert_eq!(interval.mappings().len(), 1);
+        assert_eq!(interval.mappings()[0].protocol, MappingProtocol::Udp);
+        assert_eq!(
+            interval.mappings()[0].external_addr,
+            SocketAddr::new(EXTERNAL_IP.into(), 30304)
+        );
+
+        // retried after a short backoff instead of half the lifetime
+        let retry = interval.next_renewal() - tokio::time::Instant::now();
+        assert!(retry <= Duration::from_secs(5), "{retry:?}");
+    }
+
+    #[tokio::test]
+    async fn test_port_mapping_interval_deleted_on_drop() {
+        let (gateway, mut requests) = spawn_gateway_with(u32::MAX, None).await;
+        let mut interval = PortMappingInterval::new(
+            NatResolver::NatPmp,
+            [(MappingProtocol::Udp, 30303)],
+            Duration::from_secs(7200),
+        )
+        .unwrap()
+        .with_gateway(gateway);
+
+        interval.tick().await.unwrap();
+        assert_eq!(requests.recv().await, Some((30303, 30303, 7200)));
+
+        drop(interval);
+        assert_eq!(requests.recv().await, Some((30303, 0, 0)));
+    }
+}
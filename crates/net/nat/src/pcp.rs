@@ -0,0 +1,203 @@
+//! PCP client, see [RFC 6887](https://datatracker.ietf.org/doc/html/rfc6887).
+
+use crate::mapping::{self, MappingProtocol, PortMapping, PortMappingError};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// The protocol version of PCP.
+const VERSION: u8 = 2;
+
+/// Opcode of a mapping request.
+const OP_MAP: u8 = 1;
+
+/// Set on the opcode of responses.
+const RESPONSE_FLAG: u8 = 0x80;
+
+/// Result code of a successful request.
+const RESULT_SUCCESS: u8 = 0;
+
+/// Size of the request and response header.
+const HEADER_SIZE: usize = 24;
+
+/// Size of a request or response with the MAP opcode.
+const MAP_SIZE: usize = HEADER_SIZE + 36;
+
+/// IANA protocol number of TCP.
+const PROTOCOL_TCP: u8 = 6;
+
+/// IANA protocol number of UDP.
+const PROTOCOL_UDP: u8 = 17;
+
+/// The shortest lifetime a PCP server grants, used when only resolving the external address.
+const MIN_LIFETIME: Duration = Duration::from_secs(120);
+
+/// A PCP client that talks to a single gateway.
+#[derive(Debug)]
+pub struct PcpClient {
+    socket: UdpSocket,
+    /// The local address the gateway sees requests from.
+    client_ip: IpAddr,
+    /// Identifies the mappings of this client, a mapping can only be renewed with the same nonce.
+    nonce: [u8; 12],
+}
+
+// === impl PcpClient ===
+
+impl PcpClient {
+    /// Creates a new client for the PCP server at the given address with a random nonce.
+    pub async fn new(gateway: SocketAddr) -> Result<Self, PortMappingError> {
+        let socket = mapping::connect(gateway).await?;
+        let client_ip = socket.local_addr()?.ip();
+        Ok(Self { socket, client_ip, nonce: rand::random() })
+    }
+
+    /// Creates a new client for the PCP server on the default gateway.
+    pub async fn default_gateway() -> Result<Self, PortMappingError> {
+        Self::new(mapping::default_gateway_addr()?).await
+    }
+
+    /// Sets the nonce that identifies the mappings of this client.
+    ///
+    /// Mappings that were created with a nonce can only be renewed with the same nonce.
+    pub fn with_nonce(mut self, nonce: [u8; 12]) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Resolves the external IP address of the gateway.
+    ///
+    /// PCP has no dedicated request for this, so the local port of the client is mapped briefly.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        let port = self.socket.local_addr()?.port();
+        let mapping = self.map_port(MappingProtocol::Udp, port, port, MIN_LIFETIME).await?;
+        // best effort, the mapping expires anyway
+        let _ = self.map_port(MappingProtocol::Udp, port, port, Duration::ZERO).await;
+        Ok(mapping.external_addr.ip())
+    }
+
+    /// Maps the local port on the gateway, or renews an existing mapping.
+    ///
+    /// The gateway tries to map the suggested external port, but is free to pick a different one.
+    /// A lifetime of zero removes the mapping.
+    pub async fn map_port(
+        &self,
+        protocol: MappingProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: Duration,
+    ) -> Result<PortMapping, PortMappingError> {
+        let protocol_number = match protocol {
+            MappingProtocol::Tcp => PROTOCOL_TCP,
+            MappingProtocol::Udp => PROTOCOL_UDP,
+        };
+        let lifetime = u32::try_from(lifetime.as_secs()).unwrap_or(u32::MAX);
+        // no preference for the external address, in the address family of the client
+        let suggested_ip = match self.client_ip {
+            IpAddr::V4(_) => Ipv4Addr::UNSPECIFIED.to_ipv6_mapped(),
+            IpAddr::V6(_) => Ipv6Addr::UNSPECIFIED,
+        };
+
+        let mut req = [0u8; MAP_SIZE];
+        req[0] = VERSION;
+        req[1] = OP_MAP;
+        req[4..8].copy_from_slice(&lifetime.to_be_bytes());
+        req[8..24].copy_from_slice(&to_ipv6(self.client_ip).octets());
+        req[24..36].copy_from_slice(&self.nonce);
+        req[36] = protocol_number;
+        req[40..42].copy_from_slice(&internal_port.to_be_bytes());
+        req[42..44].copy_from_slice(&external_port.to_be_bytes());
+        req[44..60].copy_from_slice(&suggested_ip.octets());
+
+        let mut buf = [0u8; 1100];
+        let len = mapping::request(&self.socket, &req, &mut buf, |msg| {
+            msg.len() >= MAP_SIZE &&
+                msg[0] == VERSION &&
+                msg[1] == RESPONSE_FLAG | OP_MAP &&
+                // nonce, protocol and internal port identify the mapping
+                msg[24..37] == req[24..37] &&
+                msg[40..42] == req[40..42]
+        })
+        .await?;
+        let msg = &buf[..len];
+        if msg[3] != RESULT_SUCCESS {
+            return Err(PortMappingError::Rejected(msg[3] as u16))
+        }
+        let lifetime = u32::from_be_bytes([msg[4], msg[5], msg[6], msg[7]]);
+        let external_port = u16::from_be_bytes([msg[42], msg[43]]);
+        let external_ip = Ipv6Addr::from(<[u8; 16]>::try_from(&msg[44..60]).expect("16 bytes"));
+        let external_ip = match external_ip.to_ipv4_mapped() {
+            Some(ip) => IpAddr::V4(ip),
+            None => IpAddr::V6(external_ip),
+        };
+
+        Ok(PortMapping {
+            protocol,
+            internal_port,
+            external_addr: SocketAddr::new(external_ip, external_port),
+            lifetime: Duration::from_secs(lifetime as u64),
+        })
+    }
+}
+
+/// Encodes the address as IPv6, IPv4 addresses are IPv4-mapped.
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(1, 2, 3, 4);
+
+    /// Spawns a stand-in PCP gateway that maps every port to the port plus one.
+    ///
+    /// Requests from a client address other than the source address are rejected.
+    async fn spawn_gateway() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1100];
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                let req = &buf[..len];
+                let mut resp = [0u8; MAP_SIZE];
+                resp[0] = VERSION;
+                resp[1] = RESPONSE_FLAG | req[1];
+                resp[4..8].copy_from_slice(&req[4..8]);
+                resp[24..44].copy_from_slice(&req[24..44]);
+                if req[8..24] != to_ipv6(from.ip()).octets() {
+                    // ADDRESS_MISMATCH
+                    resp[3] = 12;
+                }
+                let internal_port = u16::from_be_bytes([req[40], req[41]]);
+                resp[42..44].copy_from_slice(&(internal_port + 1).to_be_bytes());
+                resp[44..60].copy_from_slice(&EXTERNAL_IP.to_ipv6_mapped().octets());
+                socket.send_to(&resp, from).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_map_port() {
+        let gateway = spawn_gateway().await;
+        let client = PcpClient::new(gateway).await.unwrap();
+
+        let mapping = client
+            .map_port(MappingProtocol::Udp, 30303, 30303, Duration::from_secs(7200))
+            .await
+            .unwrap();
+        assert_eq!(mapping.protocol, MappingProtocol::Udp);
+        assert_eq!(mapping.external_addr, SocketAddr::new(EXTERNAL_IP.into(), 30304));
+        assert_eq!(mapping.lifetime, Duration::from_secs(7200));
+
+        assert_eq!(client.external_ip().await.unwrap(), IpAddr::V4(EXTERNAL_IP));
+    }
+}
@@ -21,8 +21,8 @@
 use async_trait::async_trait;
 use reth_eth_wire::DisconnectReason;
 use reth_primitives::{NodeRecord, PeerId};
-use reth_rpc_types::NetworkStatus;
-use std::net::SocketAddr;
+use reth_rpc_types::{BanEntry, BanTarget, NetworkStatus};
+use std::{net::SocketAddr, time::Duration};
 
 pub use error::NetworkError;
 pub use reputation::{Reputation, ReputationChangeKind};
@@ -95,6 +95,17 @@ pub trait Peers: PeersInfo {
 
     /// Get the reputation of a peer.
     async fn reputation_by_id(&self, peer_id: PeerId) -> Result<Option<Reputation>, NetworkError>;
+
+    /// Bans the peer id or IP address, indefinitely if no duration is given.
+    ///
+    /// Connected peers that match the ban are disconnected.
+    fn ban(&self, target: BanTarget, duration: Option<Duration>);
+
+    /// Lifts the ban of the peer id or IP address.
+    fn unban(&self, target: BanTarget);
+
+    /// Returns all entries of the ban list.
+    async fn bans(&self) -> Result<Vec<BanEntry>, NetworkError>;
 }
 
 /// Represents the kind of peer
@@ -9,8 +9,11 @@ use crate::{
 use async_trait::async_trait;
 use reth_eth_wire::{DisconnectReason, ProtocolVersion};
 use reth_primitives::{Chain, NodeRecord, PeerId};
-use reth_rpc_types::{EthProtocolInfo, NetworkStatus};
-use std::net::{IpAddr, SocketAddr};
+use reth_rpc_types::{BanEntry, BanTarget, EthProtocolInfo, NetworkStatus};
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 /// A type that implements all network trait that does nothing.
 ///
@@ -76,4 +79,12 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    fn ban(&self, _target: BanTarget, _duration: Option<Duration>) {}
+
+    fn unban(&self, _target: BanTarget) {}
+
+    async fn bans(&self) -> Result<Vec<BanEntry>, NetworkError> {
+        Ok(Vec::new())
+    }
 }
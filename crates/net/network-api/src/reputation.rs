@@ -30,6 +30,11 @@ pub enum ReputationChangeKind {
     FailedToConnect,
     /// Connection dropped by peer.
     Dropped,
+    /// Incoming connection from the peer's IP address or subnet exceeded the configured session
+    /// limits.
+    ExceededConnectionLimit,
+    /// Peer repeatedly requested more data than its serving budget allows.
+    ExceededRequestBudget,
     /// Reset the reputation to the default value.
     Reset,
     /// Apply a reputation change by value
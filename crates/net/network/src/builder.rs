@@ -1,8 +1,12 @@
 //! Builder support for configuring the entire setup.
 
 use crate::{
-    eth_requests::EthRequestHandler, transactions::TransactionsManager, NetworkHandle,
-    NetworkManager,
+    eth_requests::{EthRequestHandler, ServingCapacity},
+    protocol::ProtocolHandler,
+    snap_protocol::{SnapFetchClient, SnapProtocolHandler, SNAP_REQUEST_CHANNEL_CAPACITY},
+    snap_requests::SnapRequestHandler,
+    transactions::TransactionsManager,
+    NetworkHandle, NetworkManager,
 };
 use reth_transaction_pool::TransactionPool;
 use tokio::sync::mpsc;
@@ -34,6 +38,15 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
         (handle, network, transactions, request_handler)
     }
 
+    /// Registers an additional RLPx sub-protocol.
+    ///
+    /// The capability of the sub-protocol is announced to all peers, and the handler is invoked
+    /// for every session that shares the sub-protocol, see [`ProtocolHandler`].
+    pub fn add_rlpx_sub_protocol(mut self, handler: impl ProtocolHandler) -> Self {
+        self.network.add_rlpx_sub_protocol(handler);
+        self
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
@@ -56,7 +69,32 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
         let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let request_handler = EthRequestHandler::new(client, peers, rx)
+            .with_serving_budgets(network.handle().serving_budgets().clone());
         NetworkBuilder { network, request_handler, transactions }
     }
+
+    /// Registers the `snap/1` sub-protocol and creates a new [`SnapRequestHandler`] that serves
+    /// the `snap` requests of all peers that share it.
+    ///
+    /// The returned [`SnapFetchClient`] sends `snap` requests to these peers.
+    pub fn snap_request_handler<Client>(
+        self,
+        client: Client,
+    ) -> (Self, SnapRequestHandler<Client>, SnapFetchClient) {
+        let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+        let peers = self.network.handle().peers_handle().clone();
+        let protocol = SnapProtocolHandler::new(tx, peers.clone(), self.network.executor());
+        let fetch_client = protocol.fetch_client();
+        let request_handler = SnapRequestHandler::new(client, peers, rx);
+        (self.add_rlpx_sub_protocol(protocol), request_handler, fetch_client)
+    }
+}
+
+impl<C, Tx, Client> NetworkBuilder<C, Tx, EthRequestHandler<Client>> {
+    /// Sets the capacity of the [`EthRequestHandler`] for serving requests to a single peer.
+    pub fn serving_capacity(mut self, capacity: ServingCapacity) -> Self {
+        self.request_handler = self.request_handler.with_serving_capacity(capacity);
+        self
+    }
 }
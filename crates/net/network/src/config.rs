@@ -8,9 +8,11 @@ use crate::{
     NetworkHandle, NetworkManager,
 };
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, DEFAULT_DISCOVERY_PORT};
+use reth_discv5::{Discv5Config, Discv5ConfigBuilder};
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_ecies::util::pk2id;
 use reth_eth_wire::{HelloMessage, Status};
+use reth_net_nat::{mapping::DEFAULT_PORT_MAPPING_LIFETIME, NatResolver};
 use reth_primitives::{
     mainnet_nodes, sepolia_nodes, ChainSpec, ForkFilter, Head, NodeRecord, PeerId, MAINNET,
 };
@@ -21,6 +23,7 @@ use std::{
     collections::HashSet,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     sync::Arc,
+    time::Duration,
 };
 // re-export for convenience
 pub use secp256k1::SecretKey;
@@ -45,10 +48,17 @@ pub struct NetworkConfig<C> {
     pub dns_discovery_config: Option<DnsDiscoveryConfig>,
     /// How to set up discovery.
     pub discovery_v4_config: Option<Discv4Config>,
+    /// How to set up discovery over discv5, next to discv4.
+    pub discovery_v5_config: Option<Discv5Config>,
     /// Address to use for discovery
     pub discovery_addr: SocketAddr,
     /// Address to listen for incoming connections
     pub listener_addr: SocketAddr,
+    /// How to map the RLPx and discovery ports on the gateway, if the resolver maps ports, see
+    /// [NatResolver::maps_ports].
+    pub nat: Option<NatResolver>,
+    /// The lifetime to request for port mappings, renewed after half of the lifetime.
+    pub port_mapping_lifetime: Duration,
     /// How to instantiate peer manager.
     pub peers_config: PeersConfig,
     /// How to configure the [SessionManager](crate::session::SessionManager).
@@ -93,6 +103,12 @@ impl<C> NetworkConfig<C> {
         self
     }
 
+    /// Sets the config to use for the discovery v5 protocol.
+    pub fn set_discovery_v5(mut self, discovery_config: Discv5Config) -> Self {
+        self.discovery_v5_config = Some(discovery_config);
+        self
+    }
+
     /// Sets the address for the incoming connection listener.
     pub fn set_listener_addr(mut self, listener_addr: SocketAddr) -> Self {
         self.listener_addr = listener_addr;
@@ -128,12 +144,20 @@ pub struct NetworkConfigBuilder {
     dns_discovery_config: Option<DnsDiscoveryConfig>,
     /// How to set up discovery.
     discovery_v4_builder: Option<Discv4ConfigBuilder>,
+    /// How to set up discovery over discv5.
+    #[serde(skip)]
+    discovery_v5_builder: Option<Discv5ConfigBuilder>,
     /// All boot nodes to start network discovery with.
     boot_nodes: HashSet<NodeRecord>,
     /// Address to use for discovery
     discovery_addr: Option<SocketAddr>,
     /// Listener for incoming connections
     listener_addr: Option<SocketAddr>,
+    /// How to map the RLPx and discovery ports on the gateway.
+    nat: Option<NatResolver>,
+    /// The lifetime to request for port mappings.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    port_mapping_lifetime: Duration,
     /// How to instantiate peer manager.
     peers_config: Option<PeersConfig>,
     /// How to configure the sessions manager
@@ -160,9 +184,12 @@ impl NetworkConfigBuilder {
             secret_key,
             dns_discovery_config: Some(Default::default()),
             discovery_v4_builder: Some(Default::default()),
+            discovery_v5_builder: None,
             boot_nodes: Default::default(),
             discovery_addr: None,
             listener_addr: None,
+            nat: None,
+            port_mapping_lifetime: DEFAULT_PORT_MAPPING_LIFETIME,
             peers_config: None,
             sessions_config: None,
             chain_spec: MAINNET.clone(),
@@ -271,12 +298,35 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the [NatResolver] that maps the RLPx and discovery ports on the gateway.
+    ///
+    /// Ports are only mapped if the resolver supports it, see [NatResolver::maps_ports]. The
+    /// external addresses of the mappings are advertised via discv4 and discv5.
+    pub fn nat(mut self, nat: Option<NatResolver>) -> Self {
+        self.nat = nat;
+        self
+    }
+
+    /// Sets the lifetime to request for port mappings.
+    pub fn port_mapping_lifetime(mut self, port_mapping_lifetime: Duration) -> Self {
+        self.port_mapping_lifetime = port_mapping_lifetime;
+        self
+    }
+
     /// Sets the discv4 config to use.
     pub fn discovery(mut self, builder: Discv4ConfigBuilder) -> Self {
         self.discovery_v4_builder = Some(builder);
         self
     }
 
+    /// Sets the discv5 config to use.
+    ///
+    /// Discv5 is disabled by default.
+    pub fn discovery_v5(mut self, builder: Discv5ConfigBuilder) -> Self {
+        self.discovery_v5_builder = Some(builder);
+        self
+    }
+
     /// Sets the dns discovery config to use.
     pub fn dns_discovery(mut self, config: DnsDiscoveryConfig) -> Self {
         self.dns_discovery_config = Some(config);
@@ -307,7 +357,7 @@ impl NetworkConfigBuilder {
 
     /// Disables all discovery.
     pub fn disable_discovery(self) -> Self {
-        self.disable_discv4_discovery().disable_dns_discovery()
+        self.disable_discv4_discovery().disable_discv5_discovery().disable_dns_discovery()
     }
 
     /// Disables all discovery if the given condition is true.
@@ -325,6 +375,12 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Disable the Discv5 discovery.
+    pub fn disable_discv5_discovery(mut self) -> Self {
+        self.discovery_v5_builder = None;
+        self
+    }
+
     /// Disable the DNS discovery if the given condition is true.
     pub fn disable_dns_discovery_if(self, disable: bool) -> Self {
         if disable {
@@ -355,9 +411,12 @@ impl NetworkConfigBuilder {
             secret_key,
             mut dns_discovery_config,
             discovery_v4_builder,
+            discovery_v5_builder,
             boot_nodes,
             discovery_addr,
             listener_addr,
+            nat,
+            port_mapping_lifetime,
             peers_config,
             sessions_config,
             chain_spec,
@@ -406,10 +465,13 @@ impl NetworkConfigBuilder {
             boot_nodes,
             dns_discovery_config,
             discovery_v4_config: discovery_v4_builder.map(|builder| builder.build()),
+            discovery_v5_config: discovery_v5_builder.map(|builder| builder.build()),
             discovery_addr: discovery_addr.unwrap_or_else(|| {
                 SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, DEFAULT_DISCOVERY_PORT))
             }),
             listener_addr,
+            nat,
+            port_mapping_lifetime,
             peers_config: peers_config.unwrap_or_default(),
             sessions_config: sessions_config.unwrap_or_default(),
             chain_spec,
@@ -6,10 +6,12 @@ use crate::{
 };
 use futures::StreamExt;
 use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config, EnrForkIdEntry};
+use reth_discv5::{Discv5, Discv5Config, Discv5Update};
 use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
-use reth_primitives::{ForkId, NodeRecord, PeerId};
+use reth_net_nat::{MappingProtocol, PortMapping};
+use reth_primitives::{ForkId, Head, NodeRecord, PeerId};
 use secp256k1::SecretKey;
 use std::{
     collections::{hash_map::Entry, HashMap, VecDeque},
@@ -38,6 +40,12 @@ pub struct Discovery {
     discv4_updates: Option<ReceiverStream<DiscoveryUpdate>>,
     /// The handle to the spawned discv4 service
     _discv4_service: Option<JoinHandle<()>>,
+    /// Handler to interact with the Discovery v5 service
+    discv5: Option<Discv5>,
+    /// All updates from the discv5 service.
+    discv5_updates: Option<ReceiverStream<Discv5Update>>,
+    /// The handle to the spawned discv5 service
+    _discv5_service: Option<JoinHandle<()>>,
     /// Handler to interact with the DNS discovery service
     _dns_discovery: Option<DnsDiscoveryHandle>,
     /// Updates from the DNS discovery service.
@@ -53,12 +61,13 @@ pub struct Discovery {
 impl Discovery {
     /// Spawns the discovery service.
     ///
-    /// This will spawn the [`reth_discv4::Discv4Service`] onto a new task and establish a listener
-    /// channel to receive all discovered nodes.
+    /// This will spawn the [`reth_discv4::Discv4Service`] and the [`reth_discv5::Discv5Service`]
+    /// onto new tasks and establish listener channels to receive all discovered nodes.
     pub async fn new(
         discovery_addr: SocketAddr,
         sk: SecretKey,
         discv4_config: Option<Discv4Config>,
+        discv5_config: Option<Discv5Config>,
         dns_discovery_config: Option<DnsDiscoveryConfig>,
     ) -> Result<Self, NetworkError> {
         // setup discv4
@@ -76,6 +85,17 @@ impl Discovery {
             (None, None, None)
         };
 
+        // setup discv5
+        let (discv5, discv5_updates, _discv5_service) = if let Some(disc_config) = discv5_config {
+            let (discv5, mut discv5_service) = Discv5::bind(sk, disc_config).await?;
+            let discv5_updates = discv5_service.update_stream();
+            // spawn the service
+            let _discv5_service = discv5_service.spawn();
+            (Some(discv5), Some(discv5_updates), Some(_discv5_service))
+        } else {
+            (None, None, None)
+        };
+
         // setup DNS discovery
         let (_dns_discovery, dns_discovery_updates, _dns_disc_service) =
             if let Some(dns_config) = dns_discovery_config {
@@ -96,6 +116,9 @@ impl Discovery {
             discv4,
             discv4_updates,
             _discv4_service,
+            discv5,
+            discv5_updates,
+            _discv5_service,
             discovered_nodes: Default::default(),
             queued_events: Default::default(),
             _dns_disc_service,
@@ -115,27 +138,77 @@ impl Discovery {
         self.discovery_listeners.retain_mut(|listener| listener.send(event.clone()).is_ok());
     }
 
-    /// Updates the `eth:ForkId` field in discv4.
-    #[allow(unused)]
+    /// Updates the `eth:ForkId` field in discv4 and discv5.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         if let Some(discv4) = &self.discv4 {
             // use forward-compatible forkid entry
             discv4.set_eip868_rlp("eth".as_bytes().to_vec(), EnrForkIdEntry::from(fork_id))
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.set_fork_id(fork_id)
+        }
+    }
+
+    /// Updates the head of the fork filter discv5 checks discovered nodes against.
+    pub(crate) fn update_head(&self, head: Head) {
+        if let Some(discv5) = &self.discv5 {
+            discv5.set_head(head);
+        }
     }
 
-    /// Bans the [`IpAddr`] in the discovery service.
+    /// Returns the UDP ports of the discovery services.
+    pub(crate) fn udp_ports(&self) -> Vec<u16> {
+        let mut ports = Vec::new();
+        if let Some(discv4) = &self.discv4 {
+            ports.push(discv4.local_addr().port());
+        }
+        if let Some(discv5) = &self.discv5 {
+            ports.push(discv5.local_addr().port());
+        }
+        ports.retain(|port| *port != 0);
+        ports.dedup();
+        ports
+    }
+
+    /// Advertises the external addresses of the given port mappings in discv4 and discv5.
+    ///
+    /// Every discovery service only advertises the UDP mapping of its own port.
+    pub(crate) fn set_external_port_mappings(&self, mappings: &[PortMapping]) {
+        if let Some(discv4) = &self.discv4 {
+            discv4.set_external_port_mappings(mappings.to_vec())
+        }
+        if let Some(discv5) = &self.discv5 {
+            let udp_port = discv5.local_addr().port();
+            for mapping in mappings {
+                match mapping.protocol {
+                    MappingProtocol::Tcp => discv5.set_external_socket(mapping.external_addr, true),
+                    MappingProtocol::Udp if mapping.internal_port == udp_port => {
+                        discv5.set_external_socket(mapping.external_addr, false)
+                    }
+                    MappingProtocol::Udp => {}
+                }
+            }
+        }
+    }
+
+    /// Bans the [`IpAddr`] in the discovery services.
     pub(crate) fn ban_ip(&self, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
             discv4.ban_ip(ip)
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.ban_ip(ip)
+        }
     }
 
-    /// Bans the [`PeerId`] and [`IpAddr`] in the discovery service.
+    /// Bans the [`PeerId`] and [`IpAddr`] in the discovery services.
     pub(crate) fn ban(&self, peer_id: PeerId, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
             discv4.ban(peer_id, ip)
         }
+        if let Some(discv5) = &self.discv5 {
+            discv5.ban(peer_id, ip)
+        }
     }
 
     /// Returns the id with which the local identifies itself in the network
@@ -187,6 +260,17 @@ impl Discovery {
         }
     }
 
+    fn on_discv5_update(&mut self, update: Discv5Update) {
+        match update {
+            Discv5Update::Added { record, fork_id } => {
+                self.on_node_record_update(record, fork_id);
+            }
+            Discv5Update::Removed(node) => {
+                self.discovered_nodes.remove(&node);
+            }
+        }
+    }
+
     pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<DiscoveryEvent> {
         loop {
             // Drain all buffered events first
@@ -202,6 +286,12 @@ impl Discovery {
                 self.on_discv4_update(update)
             }
 
+            while let Some(Poll::Ready(Some(update))) =
+                self.discv5_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
+            {
+                self.on_discv5_update(update)
+            }
+
             while let Some(Poll::Ready(Some(update))) =
                 self.dns_discovery_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
@@ -237,6 +327,9 @@ impl Discovery {
             discv4_updates: Default::default(),
             queued_events: Default::default(),
             _discv4_service: Default::default(),
+            discv5: None,
+            discv5_updates: None,
+            _discv5_service: None,
             _dns_discovery: None,
             dns_discovery_updates: None,
             _dns_disc_service: None,
@@ -266,9 +359,14 @@ mod tests {
         let mut rng = thread_rng();
         let (secret_key, _) = SECP256K1.generate_keypair(&mut rng);
         let discovery_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0));
-        let _discovery =
-            Discovery::new(discovery_addr, secret_key, Default::default(), Default::default())
-                .await
-                .unwrap();
+        let _discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
+        .await
+        .unwrap();
     }
 }
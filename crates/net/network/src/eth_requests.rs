@@ -2,22 +2,28 @@
 
 use crate::{metrics::EthRequestHandlerMetrics, peers::PeersHandle};
 use futures::StreamExt;
+use parking_lot::Mutex;
 use reth_eth_wire::{
     BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetReceipts, NodeData,
     Receipts,
 };
 use reth_interfaces::p2p::error::RequestResult;
+use reth_network_api::ReputationChangeKind;
 use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection, PeerId};
 use reth_provider::{BlockReader, HeaderProvider, ReceiptProvider};
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     future::Future,
     hash::Hash,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
 
@@ -50,6 +56,42 @@ const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 /// Estimated size in bytes of an RLP encoded header.
 const APPROX_HEADER_SIZE: usize = 500;
 
+/// The cost every request is charged on top of the cost of the served items.
+///
+/// Costs are measured in estimated response bytes, so a served item costs its approximate size.
+const REQUEST_BASE_COST: u64 = 1024;
+
+/// How often the budgets of peers that have fully recharged are removed.
+const BUDGET_PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The serving capacity of the [`EthRequestHandler`] per peer.
+///
+/// Similar to LES flow control, every peer has a buffer of cost units that is drained by the
+/// requests served to the peer and recharged at a constant rate. A request is only served up to
+/// the items the remaining buffer can pay for, and rejected with an empty response if the buffer
+/// can not pay for a single item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ServingCapacity {
+    /// The maximum cost a peer can accumulate, this limits the cost of a burst of requests.
+    pub buffer_limit: u64,
+    /// The cost recharged per second.
+    pub recharge_per_second: u64,
+    /// How many requests in a row can exceed the budget before the peer is penalized.
+    pub max_exceeded_requests: u32,
+}
+
+impl Default for ServingCapacity {
+    fn default() -> Self {
+        Self {
+            buffer_limit: 4 * SOFT_RESPONSE_LIMIT as u64,
+            recharge_per_second: SOFT_RESPONSE_LIMIT as u64,
+            max_exceeded_requests: 16,
+        }
+    }
+}
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -58,11 +100,11 @@ pub struct EthRequestHandler<C> {
     /// The client type that can interact with the chain.
     client: C,
     /// Used for reporting peers.
-    #[allow(unused)]
-    // TODO use to report spammers
     peers: PeersHandle,
     /// Incoming request from the [NetworkManager](crate::NetworkManager).
     incoming_requests: ReceiverStream<IncomingEthRequest>,
+    /// The serving budgets of all peers, shared with the [`NetworkHandle`](crate::NetworkHandle).
+    budgets: Arc<Mutex<ServingBudgets>>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
 }
@@ -72,7 +114,57 @@ impl<C> EthRequestHandler<C> {
     /// Create a new instance
     pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingEthRequest>) -> Self {
         let metrics = Default::default();
-        Self { client, peers, incoming_requests: ReceiverStream::new(incoming), metrics }
+        Self {
+            client,
+            peers,
+            incoming_requests: ReceiverStream::new(incoming),
+            budgets: Arc::new(Mutex::new(ServingBudgets::new(ServingCapacity::default()))),
+            metrics,
+        }
+    }
+
+    /// Sets the serving capacity per peer.
+    pub fn with_serving_capacity(self, capacity: ServingCapacity) -> Self {
+        self.budgets.lock().set_capacity(capacity);
+        self
+    }
+
+    /// Tracks the serving budgets in the given shared budgets, which keep their capacity.
+    pub(crate) fn with_serving_budgets(mut self, budgets: Arc<Mutex<ServingBudgets>>) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    /// Returns how many of the requested items can be served to the peer.
+    ///
+    /// Requests for more than `max_serve` items are only served up to `max_serve` items anyway,
+    /// so only these are checked against the budget.
+    ///
+    /// Penalizes the peer if it keeps exceeding its budget.
+    fn admit(
+        &mut self,
+        peer_id: PeerId,
+        requested: usize,
+        max_serve: usize,
+        item_cost: usize,
+    ) -> usize {
+        let requested = requested.min(max_serve);
+        let admission =
+            self.budgets.lock().admit(peer_id, requested, item_cost as u64, Instant::now());
+        if admission.exceeded {
+            self.metrics.over_budget_requests.increment(1);
+        }
+        if admission.penalize {
+            trace!(target: "net::eth", ?peer_id, "peer keeps exceeding its serving budget");
+            self.peers.reputation_change(peer_id, ReputationChangeKind::ExceededRequestBudget);
+        }
+        admission.max_items
+    }
+
+    /// Charges the peer for the served items.
+    fn charge(&mut self, peer_id: PeerId, items: usize, item_cost: usize) {
+        let cost = self.budgets.lock().charge(peer_id, items as u64 * item_cost as u64);
+        self.metrics.serving_cost.increment(cost);
     }
 }
 
@@ -80,8 +172,8 @@ impl<C> EthRequestHandler<C>
 where
     C: BlockReader + HeaderProvider + ReceiptProvider,
 {
-    /// Returns the list of requested headers
-    fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<Header> {
+    /// Returns the list of requested headers, at most `max_headers`.
+    fn get_headers_response(&self, request: GetBlockHeaders, max_headers: usize) -> Vec<Header> {
         let GetBlockHeaders { start_block, limit, skip, direction } = request;
 
         let mut headers = Vec::new();
@@ -128,7 +220,7 @@ where
 
                 headers.push(header);
 
-                if headers.len() >= MAX_HEADERS_SERVE {
+                if headers.len() >= MAX_HEADERS_SERVE.min(max_headers) {
                     break
                 }
 
@@ -147,27 +239,35 @@ where
 
     fn on_headers_request(
         &mut self,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         request: GetBlockHeaders,
         response: oneshot::Sender<RequestResult<BlockHeaders>>,
     ) {
         self.metrics.received_headers_requests.increment(1);
-        let headers = self.get_headers_response(request);
+        let max_headers =
+            self.admit(peer_id, request.limit as usize, MAX_HEADERS_SERVE, APPROX_HEADER_SIZE);
+        let headers = if max_headers == 0 {
+            Vec::new()
+        } else {
+            self.get_headers_response(request, max_headers)
+        };
+        self.charge(peer_id, headers.len(), APPROX_HEADER_SIZE);
         let _ = response.send(Ok(BlockHeaders(headers)));
     }
 
     fn on_bodies_request(
         &mut self,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         request: GetBlockBodies,
         response: oneshot::Sender<RequestResult<BlockBodies>>,
     ) {
         self.metrics.received_bodies_requests.increment(1);
+        let max_bodies = self.admit(peer_id, request.0.len(), MAX_BODIES_SERVE, APPROX_BODY_SIZE);
         let mut bodies = Vec::new();
 
         let mut total_bytes = APPROX_BODY_SIZE;
 
-        for hash in request.0 {
+        for hash in request.0.into_iter().take(max_bodies) {
             if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
                 let body = BlockBody {
                     transactions: block.body,
@@ -187,24 +287,30 @@ where
                     break
                 }
             } else {
+                // the block is unknown or its body was pruned by history expiry, respond with the
+                // bodies found so far
                 break
             }
         }
 
+        self.charge(peer_id, bodies.len(), APPROX_BODY_SIZE);
         let _ = response.send(Ok(BlockBodies(bodies)));
     }
 
     fn on_receipts_request(
         &mut self,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         request: GetReceipts,
         response: oneshot::Sender<RequestResult<Receipts>>,
     ) {
+        self.metrics.received_receipts_requests.increment(1);
+        let max_receipts =
+            self.admit(peer_id, request.0.len(), MAX_RECEIPTS_SERVE, APPROX_RECEIPT_SIZE);
         let mut receipts = Vec::new();
 
         let mut total_bytes = APPROX_RECEIPT_SIZE;
 
-        for hash in request.0 {
+        for hash in request.0.into_iter().take(max_receipts) {
             if let Some(receipts_by_block) =
                 self.client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
             {
@@ -229,6 +335,7 @@ where
             }
         }
 
+        self.charge(peer_id, receipts.len(), APPROX_RECEIPT_SIZE);
         let _ = response.send(Ok(Receipts(receipts)));
     }
 }
@@ -266,6 +373,149 @@ where
     }
 }
 
+/// Tracks the serving budget of every peer, see [`ServingCapacity`].
+#[derive(Debug)]
+pub(crate) struct ServingBudgets {
+    capacity: ServingCapacity,
+    peers: HashMap<PeerId, PeerBudget>,
+    /// When budgets were last pruned.
+    last_prune: Instant,
+}
+
+// === impl ServingBudgets ===
+
+impl ServingBudgets {
+    pub(crate) fn new(capacity: ServingCapacity) -> Self {
+        Self { capacity, peers: Default::default(), last_prune: Instant::now() }
+    }
+
+    /// Sets the serving capacity per peer.
+    fn set_capacity(&mut self, capacity: ServingCapacity) {
+        self.capacity = capacity;
+    }
+
+    /// Returns the serving statistics of the peer, if it has a budget.
+    pub(crate) fn stats(&self, peer_id: &PeerId) -> Option<PeerServingStats> {
+        self.peers.get(peer_id).map(|budget| budget.stats)
+    }
+
+    /// Checks a request for `requested` items at `item_cost` each against the budget of the peer.
+    fn admit(
+        &mut self,
+        peer_id: PeerId,
+        requested: usize,
+        item_cost: u64,
+        now: Instant,
+    ) -> Admission {
+        self.prune(now);
+
+        let capacity = self.capacity;
+        let budget = self.peers.entry(peer_id).or_insert_with(|| PeerBudget::new(capacity));
+        budget.recharge(&capacity, now);
+
+        let affordable = budget.buffer.saturating_sub(REQUEST_BASE_COST) / item_cost.max(1);
+        let max_items = requested.min(usize::try_from(affordable).unwrap_or(usize::MAX));
+        let exceeded = max_items < requested;
+
+        let mut penalize = false;
+        if exceeded {
+            budget.stats.over_budget_requests += 1;
+            budget.exceeded += 1;
+            if budget.exceeded >= capacity.max_exceeded_requests {
+                budget.exceeded = 0;
+                penalize = true;
+            }
+        } else {
+            budget.exceeded = 0;
+        }
+
+        Admission { max_items, exceeded, penalize }
+    }
+
+    /// Charges the peer for a served request with the given cost of the served items.
+    ///
+    /// Returns the charged cost.
+    fn charge(&mut self, peer_id: PeerId, items_cost: u64) -> u64 {
+        let Some(budget) = self.peers.get_mut(&peer_id) else { return 0 };
+        let cost = REQUEST_BASE_COST.saturating_add(items_cost);
+        budget.buffer = budget.buffer.saturating_sub(cost);
+        budget.stats.serving_cost = budget.stats.serving_cost.saturating_add(cost);
+        cost
+    }
+
+    /// Removes the budgets of peers that have fully recharged, those are equivalent to a new
+    /// budget.
+    fn prune(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_prune) < BUDGET_PRUNE_INTERVAL {
+            return
+        }
+        self.last_prune = now;
+        let capacity = self.capacity;
+        self.peers.retain(|_, budget| {
+            budget.recharge(&capacity, now);
+            budget.buffer < capacity.buffer_limit || budget.exceeded > 0
+        });
+    }
+}
+
+/// The serving budget of a single peer.
+#[derive(Debug)]
+struct PeerBudget {
+    /// The remaining cost that can be served.
+    buffer: u64,
+    /// When the buffer was last recharged.
+    last_recharge: Instant,
+    /// Number of requests in a row that exceeded the budget.
+    exceeded: u32,
+    stats: PeerServingStats,
+}
+
+// === impl PeerBudget ===
+
+impl PeerBudget {
+    fn new(capacity: ServingCapacity) -> Self {
+        Self {
+            buffer: capacity.buffer_limit,
+            last_recharge: Instant::now(),
+            exceeded: 0,
+            stats: Default::default(),
+        }
+    }
+
+    /// Recharges the buffer for the time since the last recharge.
+    fn recharge(&mut self, capacity: &ServingCapacity, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_recharge);
+        let recharged = (elapsed.as_secs_f64() * capacity.recharge_per_second as f64) as u64;
+        if recharged > 0 {
+            self.buffer = self.buffer.saturating_add(recharged).min(capacity.buffer_limit);
+            self.last_recharge = now;
+        }
+    }
+}
+
+/// Statistics about the requests served to a single peer.
+///
+/// These are tracked per peer instead of as labeled metrics, which would grow with every peer that
+/// ever sent a request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PeerServingStats {
+    /// Total cost of the requests served to the peer.
+    pub serving_cost: u64,
+    /// Number of requests that exceeded the serving budget of the peer.
+    pub over_budget_requests: u64,
+}
+
+/// The outcome of checking a request against the serving budget of a peer.
+#[derive(Debug)]
+struct Admission {
+    /// The maximum number of items to serve.
+    max_items: usize,
+    /// Whether the request asked for more items than the budget allows.
+    exceeded: bool,
+    /// Whether the peer should be penalized for repeatedly exceeding its budget.
+    penalize: bool,
+}
+
 /// Represents a handled [`GetBlockHeaders`] requests
 ///
 /// This is the key type for spam detection cache. The counter is ignored during `PartialEq` and
@@ -319,3 +569,87 @@ pub enum IncomingEthRequest {
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serving_budget() {
+        let capacity = ServingCapacity {
+            buffer_limit: 2 * REQUEST_BASE_COST + 10 * APPROX_HEADER_SIZE as u64,
+            recharge_per_second: APPROX_HEADER_SIZE as u64,
+            max_exceeded_requests: 2,
+        };
+        let mut budgets = ServingBudgets::new(capacity);
+        let peer_id = PeerId::random();
+        let item_cost = APPROX_HEADER_SIZE as u64;
+        let now = Instant::now();
+
+        let admission = budgets.admit(peer_id, 8, item_cost, now);
+        assert_eq!(admission.max_items, 8);
+        assert!(!admission.exceeded);
+        assert_eq!(budgets.charge(peer_id, 8 * item_cost), REQUEST_BASE_COST + 8 * item_cost);
+
+        // only the remaining budget is served
+        let admission = budgets.admit(peer_id, 8, item_cost, now);
+        assert_eq!(admission.max_items, 2);
+        assert!(admission.exceeded && !admission.penalize);
+        budgets.charge(peer_id, 2 * item_cost);
+
+        // rejected and penalized after exceeding the budget twice in a row
+        let admission = budgets.admit(peer_id, 8, item_cost, now);
+        assert_eq!(admission.max_items, 0);
+        assert!(admission.penalize);
+        assert_eq!(
+            budgets.peers[&peer_id].stats,
+            PeerServingStats {
+                serving_cost: 2 * REQUEST_BASE_COST + 10 * item_cost,
+                over_budget_requests: 2,
+            }
+        );
+
+        // recharged over time, up to the limit
+        let later = now + Duration::from_secs(4);
+        let admission = budgets.admit(peer_id, 8, item_cost, later);
+        assert_eq!(admission.max_items, 1);
+        let admission = budgets.admit(peer_id, 8, item_cost, later + Duration::from_secs(3600));
+        assert_eq!(admission.max_items, 8);
+
+        // fully recharged budgets are pruned
+        budgets.prune(later + BUDGET_PRUNE_INTERVAL * 100);
+        assert!(budgets.peers.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peers::PeersManager;
+    use reth_provider::test_utils::NoopProvider;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn admit_requests_up_to_serve_limits() {
+        let (_tx, rx) = mpsc::channel(1);
+        let peers = PeersManager::default().handle();
+        let mut handler = EthRequestHandler::new(NoopProvider::default(), peers, rx);
+        let peer_id = PeerId::random();
+
+        // more headers than are ever served, which the budget can't pay for
+        let request = GetBlockHeaders {
+            start_block: BlockHashOrNumber::Number(0),
+            limit: 100_000,
+            skip: 0,
+            direction: HeadersDirection::Rising,
+        };
+        let (response, _rx) = oneshot::channel();
+        handler.on_headers_request(peer_id, request, response);
+
+        let stats = handler.budgets.lock().stats(&peer_id).unwrap();
+        assert_eq!(
+            stats,
+            PeerServingStats { serving_cost: REQUEST_BASE_COST, over_budget_requests: 0 }
+        );
+    }
+}
@@ -138,7 +138,10 @@ mod message;
 mod metrics;
 mod network;
 pub mod peers;
+pub mod protocol;
 mod session;
+pub mod snap_protocol;
+pub mod snap_requests;
 mod state;
 mod swarm;
 pub mod transactions;
@@ -152,9 +155,10 @@ pub use message::PeerRequest;
 pub use network::NetworkHandle;
 pub use peers::PeersConfig;
 pub use session::{
-    ActiveSessionHandle, ActiveSessionMessage, Direction, PeerInfo, PendingSessionEvent,
-    PendingSessionHandle, PendingSessionHandshakeError, SessionCommand, SessionEvent, SessionId,
-    SessionLimits, SessionManager, SessionsConfig,
+    ActiveSessionHandle, ActiveSessionMessage, Direction, InboundRateLimit,
+    InboundSessionLimitError, PeerInfo, PendingSessionEvent, PendingSessionHandle,
+    PendingSessionHandshakeError, SessionCommand, SessionEvent, SessionId, SessionLimits,
+    SessionManager, SessionsConfig,
 };
 
 pub use reth_eth_wire::{DisconnectReason, HelloBuilder, HelloMessage};
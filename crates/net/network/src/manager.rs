@@ -25,7 +25,8 @@ use crate::{
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
     metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     network::{NetworkHandle, NetworkHandleMessage},
-    peers::{PeersHandle, PeersManager},
+    peers::{PeersHandle, PeersManager, PersistedPeers},
+    protocol::ProtocolHandler,
     session::SessionManager,
     state::NetworkState,
     swarm::{NetworkConnectionState, Swarm, SwarmEvent},
@@ -40,10 +41,12 @@ use reth_eth_wire::{
 };
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_net_common::bandwidth_meter::BandwidthMeter;
+use reth_net_nat::{MappingProtocol, PortMappingInterval};
 use reth_network_api::ReputationChangeKind;
 use reth_primitives::{listener::EventListeners, ForkId, NodeRecord, PeerId, H256};
 use reth_provider::{BlockNumReader, BlockReader};
 use reth_rpc_types::{EthProtocolInfo, NetworkStatus};
+use reth_tasks::TaskSpawner;
 use std::{
     net::SocketAddr,
     pin::Pin,
@@ -119,6 +122,11 @@ pub struct NetworkManager<C> {
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
     /// Updated by the `NetworkWorker` and loaded by the `NetworkService`.
     num_active_peers: Arc<AtomicUsize>,
+    /// Maps the RLPx and discovery ports on the gateway and renews the mappings, if the
+    /// configured [`NatResolver`](reth_net_nat::NatResolver) maps ports.
+    ///
+    /// The mappings are deleted when the network is dropped.
+    port_mapping: Option<PortMappingInterval>,
     /// Metrics for the Network
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
@@ -140,6 +148,17 @@ impl<C> NetworkManager<C> {
         self.to_eth_request_handler = Some(tx);
     }
 
+    /// Adds an additional RLPx sub-protocol that is negotiated with all peers that connect
+    /// afterwards.
+    pub fn add_rlpx_sub_protocol(&mut self, handler: impl ProtocolHandler) {
+        self.swarm.sessions_mut().add_rlpx_sub_protocol(handler)
+    }
+
+    /// Returns the executor the network spawns its tasks on.
+    pub(crate) fn executor(&self) -> Box<dyn TaskSpawner> {
+        self.swarm.sessions().executor()
+    }
+
     /// Returns the [`NetworkHandle`] that can be cloned and shared.
     ///
     /// The [`NetworkHandle`] can be used to interact with this [`NetworkManager`]
@@ -167,8 +186,11 @@ where
             client,
             secret_key,
             mut discovery_v4_config,
+            mut discovery_v5_config,
             discovery_addr,
             listener_addr,
+            nat,
+            port_mapping_lifetime,
             peers_config,
             sessions_config,
             chain_spec,
@@ -198,12 +220,35 @@ where
             disc_config
         });
 
-        let discovery =
-            Discovery::new(discovery_addr, secret_key, discovery_v4_config, dns_discovery_config)
-                .await?;
+        discovery_v5_config = discovery_v5_config.map(|mut disc_config| {
+            // advertise the RLPx endpoint and only report peers on the same chain
+            disc_config.tcp_port = incoming.local_address().port();
+            disc_config.fork_id = Some(status.forkid);
+            disc_config.fork_filter = Some(fork_filter.clone());
+            disc_config
+        });
+
+        let discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            discovery_v4_config,
+            discovery_v5_config,
+            dns_discovery_config,
+        )
+        .await?;
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
 
+        // map the ports of all discovery services and the listener, independent of which discovery
+        // services are enabled
+        let port_mapping = nat.and_then(|nat| {
+            let ports =
+                discovery.udp_ports().into_iter().map(|port| (MappingProtocol::Udp, port)).chain(
+                    std::iter::once((MappingProtocol::Tcp, incoming.local_address().port())),
+                );
+            PortMappingInterval::new(nat, ports, port_mapping_lifetime)
+        });
+
         let num_active_peers = Arc::new(AtomicUsize::new(0));
         let bandwidth_meter: BandwidthMeter = BandwidthMeter::default();
 
@@ -249,6 +294,7 @@ where
             to_transactions_manager: None,
             to_eth_request_handler: None,
             num_active_peers,
+            port_mapping,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
         })
@@ -319,6 +365,12 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns the state of the peer set that should be persisted across restarts, see
+    /// [`PeersConfig::with_persisted_peers`](crate::PeersConfig::with_persisted_peers).
+    pub fn persisted_peers(&self) -> PersistedPeers {
+        self.swarm.state().peers().persisted_peers()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -573,6 +625,7 @@ where
                 let _ = tx.send(self.status());
             }
             NetworkHandleMessage::StatusUpdate { head } => {
+                self.swarm.state_mut().update_discovery_head(head);
                 if let Some(transition) = self.swarm.sessions_mut().on_status_update(head) {
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
@@ -615,6 +668,19 @@ where
             };
         }
 
+        // map or renew the ports on the gateway
+        if let Some(Poll::Ready(res)) = this.port_mapping.as_mut().map(|m| m.poll_tick(cx)) {
+            let mappings = match res {
+                Ok(mappings) => mappings,
+                Err(err) => {
+                    debug!(target: "net", ?err, "Failed to map ports");
+                    // the remaining ports may still have been mapped
+                    this.port_mapping.as_ref().map(|m| m.mappings().to_vec()).unwrap_or_default()
+                }
+            };
+            this.swarm.state_mut().set_external_port_mappings(&mappings);
+        }
+
         // This loop drives the entire state of network and does a lot of work.
         // Under heavy load (many messages/events), data may arrive faster than it can be processed
         // (incoming messages/requests -> events), and it is possible that more data has already
@@ -51,6 +51,12 @@ pub struct NetworkMetrics {
 pub struct SessionManagerMetrics {
     /// Number of dials that resulted in a peer being added to the peerset
     pub(crate) total_dial_successes: Counter,
+    /// Number of incoming connections rejected because of the per IP limit
+    pub(crate) inbound_rejected_ip_limit: Counter,
+    /// Number of incoming connections rejected because of the per subnet limit
+    pub(crate) inbound_rejected_subnet_limit: Counter,
+    /// Number of incoming connections rejected because of the rate limit
+    pub(crate) inbound_rejected_rate_limit: Counter,
 }
 
 /// Metrics for the TransactionsManager
@@ -149,4 +155,30 @@ pub struct EthRequestHandlerMetrics {
 
     /// Number of received bodies requests
     pub(crate) received_bodies_requests: Counter,
+
+    /// Number of received receipts requests
+    pub(crate) received_receipts_requests: Counter,
+
+    /// Number of requests that exceeded the serving budget of the peer
+    pub(crate) over_budget_requests: Counter,
+
+    /// Total cost of the served requests
+    pub(crate) serving_cost: Counter,
+}
+
+/// Metrics for the SnapRequestHandler
+#[derive(Metrics)]
+#[metrics(scope = "network")]
+pub struct SnapRequestHandlerMetrics {
+    /// Number of received account range requests
+    pub(crate) received_account_range_requests: Counter,
+
+    /// Number of received storage ranges requests
+    pub(crate) received_storage_ranges_requests: Counter,
+
+    /// Number of received bytecodes requests
+    pub(crate) received_byte_codes_requests: Counter,
+
+    /// Number of received trie nodes requests
+    pub(crate) received_trie_nodes_requests: Counter,
 }
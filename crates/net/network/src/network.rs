@@ -1,6 +1,12 @@
 use crate::{
-    config::NetworkMode, discovery::DiscoveryEvent, manager::NetworkEvent, message::PeerRequest,
-    peers::PeersHandle, session::PeerInfo, FetchClient,
+    config::NetworkMode,
+    discovery::DiscoveryEvent,
+    eth_requests::{PeerServingStats, ServingBudgets, ServingCapacity},
+    manager::NetworkEvent,
+    message::PeerRequest,
+    peers::PeersHandle,
+    session::PeerInfo,
+    FetchClient,
 };
 use async_trait::async_trait;
 use parking_lot::Mutex;
@@ -11,13 +17,14 @@ use reth_network_api::{
     NetworkError, NetworkInfo, PeerKind, Peers, PeersInfo, Reputation, ReputationChangeKind,
 };
 use reth_primitives::{Head, NodeRecord, PeerId, TransactionSigned, H256};
-use reth_rpc_types::NetworkStatus;
+use reth_rpc_types::{BanEntry, BanTarget, NetworkStatus};
 use std::{
     net::SocketAddr,
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -57,6 +64,7 @@ impl NetworkHandle {
             is_syncing: Arc::new(AtomicBool::new(false)),
             initial_sync_done: Arc::new(AtomicBool::new(false)),
             chain_id,
+            serving_budgets: Arc::new(Mutex::new(ServingBudgets::new(ServingCapacity::default()))),
         };
         Self { inner: Arc::new(inner) }
     }
@@ -73,6 +81,20 @@ impl NetworkHandle {
         &self.inner.peers
     }
 
+    /// Returns the statistics about the `eth` requests served to the peer.
+    ///
+    /// Returns `None` if the peer has not been served recently: the statistics are tracked with
+    /// the serving budget of the peer, which is removed once it has fully recharged.
+    pub fn peer_serving_stats(&self, peer_id: &PeerId) -> Option<PeerServingStats> {
+        self.inner.serving_budgets.lock().stats(peer_id)
+    }
+
+    /// Returns the serving budgets of the
+    /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
+    pub(crate) fn serving_budgets(&self) -> &Arc<Mutex<ServingBudgets>> {
+        &self.inner.serving_budgets
+    }
+
     fn manager(&self) -> &UnboundedSender<NetworkHandleMessage> {
         &self.inner.to_manager_tx
     }
@@ -227,6 +249,20 @@ impl Peers for NetworkHandle {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    /// Sends a message to the peer set to ban the peer id or ip address.
+    fn ban(&self, target: BanTarget, duration: Option<Duration>) {
+        self.peers_handle().ban(target, duration)
+    }
+
+    /// Sends a message to the peer set to lift the ban of the peer id or ip address.
+    fn unban(&self, target: BanTarget) {
+        self.peers_handle().unban(target)
+    }
+
+    async fn bans(&self) -> Result<Vec<BanEntry>, NetworkError> {
+        Ok(self.peers_handle().bans().await)
+    }
 }
 
 #[async_trait]
@@ -305,6 +341,9 @@ struct NetworkInner {
     initial_sync_done: Arc<AtomicBool>,
     /// The chain id
     chain_id: Arc<AtomicU64>,
+    /// The serving budgets of the peers, tracked by the
+    /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
+    serving_budgets: Arc<Mutex<ServingBudgets>>,
 }
 
 /// Internal messages that can be passed to the  [`NetworkManager`](crate::NetworkManager).
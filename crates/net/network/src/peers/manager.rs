@@ -11,6 +11,7 @@ use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_net_common::ban_list::BanList;
 use reth_network_api::{PeerKind, ReputationChangeKind};
 use reth_primitives::{ForkId, NodeRecord, PeerId};
+use reth_rpc_types::{BanEntry, BanTarget};
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     fmt::Display,
@@ -18,7 +19,7 @@ use std::{
     net::{IpAddr, SocketAddr},
     path::Path,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::{
@@ -72,6 +73,24 @@ impl PeersHandle {
 
         rx.await.unwrap_or_default()
     }
+
+    /// Bans the peer id or ip address, indefinitely if no duration is given.
+    pub fn ban(&self, target: BanTarget, duration: Option<Duration>) {
+        self.send(PeerCommand::Ban(target, duration));
+    }
+
+    /// Lifts the ban of the peer id or ip address.
+    pub fn unban(&self, target: BanTarget) {
+        self.send(PeerCommand::Unban(target));
+    }
+
+    /// Returns all entries of the ban list.
+    pub async fn bans(&self) -> Vec<BanEntry> {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetBans(tx));
+
+        rx.await.unwrap_or_default()
+    }
 }
 
 /// Maintains the state of _all_ the peers known to the network.
@@ -121,13 +140,14 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            mut ban_list,
             ban_duration,
             backoff_durations,
             trusted_nodes,
             connect_trusted_nodes_only,
             basic_nodes,
             max_backoff_count,
+            persisted_peers,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -135,7 +155,24 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
-        let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
+        let PersistedPeers { peers: persisted_peers, bans } = persisted_peers;
+        for BanEntry { target, until } in bans {
+            let until = match until {
+                Some(until) => match instant_from_unix_timestamp(until) {
+                    Some(until) => until,
+                    // the ban expired while the node was offline
+                    None => continue,
+                },
+                None => None,
+            };
+            match target {
+                BanTarget::PeerId(peer_id) => ban_list.ban_peer_with(peer_id, until),
+                BanTarget::Ip(ip) => ban_list.ban_ip_with(ip, until),
+            }
+        }
+
+        let mut peers =
+            HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len() + persisted_peers.len());
 
         for NodeRecord { address, tcp_port, udp_port: _, id } in trusted_nodes {
             peers.entry(id).or_insert_with(|| Peer::trusted(SocketAddr::from((address, tcp_port))));
@@ -145,6 +182,20 @@ impl PeersManager {
             peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
         }
 
+        for PersistedPeer { record, reputation, fork_id, last_seen } in persisted_peers {
+            let NodeRecord { address, tcp_port, udp_port: _, id } = record;
+            let peer =
+                peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
+            // a banned reputation is only restored if the peer is still on the ban list
+            peer.reputation = if is_banned_reputation(reputation) && !ban_list.is_banned_peer(&id) {
+                DEFAULT_REPUTATION
+            } else {
+                reputation
+            };
+            peer.fork_id = fork_id;
+            peer.last_seen = last_seen.map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        }
+
         Self {
             peers,
             manager_tx,
@@ -183,6 +234,42 @@ impl PeersManager {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
     }
 
+    /// Returns all entries of the ban list.
+    pub(crate) fn bans(&self) -> Vec<BanEntry> {
+        let peers = self.ban_list.banned_peers().iter().map(|(peer_id, until)| BanEntry {
+            target: BanTarget::PeerId(*peer_id),
+            until: until.map(unix_timestamp),
+        });
+        let ips = self.ban_list.banned_ips().iter().map(|(ip, until)| BanEntry {
+            target: BanTarget::Ip(*ip),
+            until: until.map(unix_timestamp),
+        });
+        peers.chain(ips).collect()
+    }
+
+    /// Returns the state of the peer set that should be persisted across restarts.
+    ///
+    /// Currently connected peers are considered to be seen now.
+    pub(crate) fn persisted_peers(&self) -> PersistedPeers {
+        let now = SystemTime::now();
+        let peers = self
+            .peers
+            .iter()
+            .map(|(peer_id, peer)| {
+                let last_seen = if peer.state.is_connected() { Some(now) } else { peer.last_seen };
+                PersistedPeer {
+                    record: NodeRecord::new(peer.addr, *peer_id),
+                    reputation: peer.reputation,
+                    fork_id: peer.fork_id,
+                    last_seen: last_seen.map(|last_seen| {
+                        last_seen.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+                    }),
+                }
+            })
+            .collect();
+        PersistedPeers { peers, bans: self.bans() }
+    }
+
     /// Returns the number of currently active inbound connections.
     #[inline]
     pub(crate) fn num_inbound_connections(&self) -> usize {
@@ -220,12 +307,35 @@ impl PeersManager {
         Ok(())
     }
 
+    /// Returns `true` if the IP address belongs to a trusted peer.
+    pub(crate) fn is_trusted_ip(&self, ip: IpAddr) -> bool {
+        self.peers.values().any(|peer| peer.is_trusted() && peer.addr.ip() == ip)
+    }
+
     /// Invoked when a previous call to [Self::on_incoming_pending_session] succeeded but it was
     /// rejected.
     pub(crate) fn on_incoming_pending_session_rejected_internally(&mut self) {
         self.connection_info.decr_in();
     }
 
+    /// Invoked when a previous call to [Self::on_incoming_pending_session] succeeded but the
+    /// connection was rejected because the remote host exceeded the configured session limits.
+    ///
+    /// This penalizes all known peers with the given IP address.
+    pub(crate) fn on_incoming_session_limit_exceeded(&mut self, addr: IpAddr) {
+        self.connection_info.decr_in();
+
+        let peers = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.addr.ip() == addr)
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+        for peer_id in peers {
+            self.apply_reputation_change(&peer_id, ReputationChangeKind::ExceededConnectionLimit);
+        }
+    }
+
     /// Invoked when a pending session was closed.
     pub(crate) fn on_incoming_pending_session_gracefully_closed(&mut self) {
         self.connection_info.decr_in()
@@ -255,6 +365,9 @@ impl PeersManager {
     ///
     /// If the reputation of the peer is below the `BANNED_REPUTATION` threshold, a disconnect will
     /// be scheduled.
+    ///
+    /// A disconnect is also scheduled if the peer is not trusted and only the inbound slots
+    /// reserved for trusted peers are left, see [`PeersConfig::with_trusted_inbound_slots`].
     pub(crate) fn on_incoming_session_established(&mut self, peer_id: PeerId, addr: SocketAddr) {
         // we only need to check the peer id here as the ip address will have been checked at
         // on_inbound_pending_session. We also check if the peer is in the backoff list here.
@@ -284,6 +397,22 @@ impl PeersManager {
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
         }
+
+        let untrusted_inbound = self
+            .peers
+            .values()
+            .filter(|peer| peer.state == PeerConnectionState::In && !peer.is_trusted())
+            .count();
+        if untrusted_inbound > self.connection_info.max_untrusted_inbound() {
+            if let Some(peer) = self.peers.get_mut(&peer_id).filter(|peer| !peer.is_trusted()) {
+                trace!(target: "net::peers", ?peer_id, "no inbound slot left for untrusted peer");
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id,
+                    reason: Some(DisconnectReason::TooManyPeers),
+                });
+            }
+        }
     }
 
     /// Bans the peer temporarily with the configured ban timeout
@@ -313,6 +442,48 @@ impl PeersManager {
         self.queued_actions.push_back(PeerAction::UnBanPeer { peer_id });
     }
 
+    /// Manually bans the peer id or ip address, indefinitely if no duration is given or if the
+    /// duration is too large to be represented as an instant.
+    ///
+    /// Connected peers that match the ban are disconnected. Like all ip bans, this does not ban
+    /// non-global ips.
+    pub(crate) fn ban_target(&mut self, target: BanTarget, duration: Option<Duration>) {
+        let until = duration.and_then(|duration| std::time::Instant::now().checked_add(duration));
+        match target {
+            BanTarget::PeerId(peer_id) => {
+                self.ban_list.ban_peer_with(peer_id, until);
+                self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
+            }
+            BanTarget::Ip(ip) => self.ban_list.ban_ip_with(ip, until),
+        }
+
+        for (peer_id, peer) in self.peers.iter_mut() {
+            if !peer.state.is_connected() || !self.ban_list.is_banned(peer_id, &peer.addr.ip()) {
+                continue
+            }
+            peer.state.disconnect();
+            self.queued_actions.push_back(PeerAction::Disconnect {
+                peer_id: *peer_id,
+                reason: Some(DisconnectReason::DisconnectRequested),
+            });
+        }
+    }
+
+    /// Lifts a manual ban of the peer id or ip address.
+    ///
+    /// If the peer is banned because of its reputation, its reputation is reset.
+    pub(crate) fn unban_target(&mut self, target: BanTarget) {
+        match target {
+            BanTarget::PeerId(peer_id) => {
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    peer.unban();
+                }
+                self.unban_peer(peer_id);
+            }
+            BanTarget::Ip(ip) => self.ban_list.unban_ip(&ip),
+        }
+    }
+
     /// Tick function to update reputation of all connected peers.
     /// Peers are rewarded with reputation increases for the time they are connected since the last
     /// tick. This is to prevent peers from being disconnected eventually due to slashed
@@ -406,6 +577,7 @@ impl PeersManager {
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
+                    entry.get_mut().last_seen = Some(SystemTime::now());
                     return
                 }
             }
@@ -490,6 +662,9 @@ impl PeersManager {
                     peer.reputation = peer.reputation.saturating_add(reputation_change.as_i32());
                 };
 
+                if peer.state.is_connected() {
+                    peer.last_seen = Some(SystemTime::now());
+                }
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
 
@@ -644,9 +819,10 @@ impl PeersManager {
     ///
     /// Returns `None` if no peer is available.
     fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
-        let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
+        let mut unconnected = self.peers.iter_mut().filter(|(peer_id, peer)| {
             peer.state.is_unconnected() &&
                 !peer.is_banned() &&
+                !self.ban_list.is_banned(peer_id, &peer.addr.ip()) &&
                 !peer.is_backed_off() &&
                 (!self.connect_trusted_nodes_only || peer.is_trusted())
         });
@@ -730,6 +906,11 @@ impl PeersManager {
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::Ban(target, duration) => self.ban_target(target, duration),
+                    PeerCommand::Unban(target) => self.unban_target(target),
+                    PeerCommand::GetBans(tx) => {
+                        let _ = tx.send(self.bans());
+                    }
                 }
             }
 
@@ -792,6 +973,9 @@ pub struct ConnectionInfo {
     max_outbound: usize,
     /// Maximum allowed inbound connections.
     max_inbound: usize,
+    /// Number of inbound connections reserved for trusted peers.
+    #[cfg_attr(feature = "serde", serde(default))]
+    trusted_inbound_slots: usize,
 }
 
 // === impl ConnectionInfo ===
@@ -807,6 +991,11 @@ impl ConnectionInfo {
         self.num_inbound < self.max_inbound
     }
 
+    /// Returns the maximum number of active inbound connections to untrusted peers.
+    fn max_untrusted_inbound(&self) -> usize {
+        self.max_inbound.saturating_sub(self.trusted_inbound_slots)
+    }
+
     fn decr_state(&mut self, state: PeerConnectionState) {
         match state {
             PeerConnectionState::Idle => {}
@@ -839,6 +1028,7 @@ impl Default for ConnectionInfo {
             num_inbound: 0,
             max_outbound: DEFAULT_MAX_PEERS_OUTBOUND,
             max_inbound: DEFAULT_MAX_PEERS_INBOUND,
+            trusted_inbound_slots: 0,
         }
     }
 }
@@ -862,6 +1052,8 @@ pub struct Peer {
     backed_off: bool,
     /// Counts number of times the peer was backed off due to a severe [BackoffKind].
     severe_backoff_counter: u32,
+    /// When the last session to the peer was closed.
+    last_seen: Option<SystemTime>,
 }
 
 // === impl Peer ===
@@ -885,6 +1077,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            last_seen: None,
         }
     }
 
@@ -1022,6 +1215,12 @@ pub(crate) enum PeerCommand {
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
     GetPeers(oneshot::Sender<Vec<NodeRecord>>),
+    /// Ban the peer id or ip address, indefinitely if no duration is set.
+    Ban(BanTarget, Option<Duration>),
+    /// Lift the ban of the peer id or ip address.
+    Unban(BanTarget),
+    /// Get all entries of the ban list.
+    GetBans(oneshot::Sender<Vec<BanEntry>>),
 }
 
 /// Actions the peer manager can trigger.
@@ -1096,6 +1295,9 @@ pub struct PeersConfig {
     ///
     /// The backoff duration increases with number of backoff attempts.
     pub backoff_durations: PeerBackoffDurations,
+    /// The peer set and bans restored from a previous run.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: PersistedPeers,
 }
 
 impl Default for PeersConfig {
@@ -1112,6 +1314,7 @@ impl Default for PeersConfig {
             connect_trusted_nodes_only: false,
             basic_nodes: Default::default(),
             max_backoff_count: 5,
+            persisted_peers: Default::default(),
         }
     }
 }
@@ -1163,6 +1366,15 @@ impl PeersConfig {
         self
     }
 
+    /// Number of inbound connections reserved for trusted peers.
+    ///
+    /// Inbound connections from untrusted peers are disconnected once only the reserved slots are
+    /// left.
+    pub fn with_trusted_inbound_slots(mut self, slots: usize) -> Self {
+        self.connection_info.trusted_inbound_slots = slots;
+        self
+    }
+
     /// Nodes to always connect to.
     pub fn with_trusted_nodes(mut self, nodes: HashSet<NodeRecord>) -> Self {
         self.trusted_nodes = nodes;
@@ -1181,6 +1393,12 @@ impl PeersConfig {
         self
     }
 
+    /// The peer set and bans of a previous run, see [`PersistedPeers`].
+    pub fn with_persisted_peers(mut self, persisted_peers: PersistedPeers) -> Self {
+        self.persisted_peers = persisted_peers;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub fn with_max_backoff_count(mut self, max_backoff_count: u32) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -1188,6 +1406,8 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// The file contains either the [`PersistedPeers`] of a previous run or a list of nodes.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -1199,11 +1419,74 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        let peers = match serde_json::from_reader(reader)? {
+            PeersFile::Persisted(peers) => self.with_persisted_peers(peers),
+            PeersFile::Nodes(nodes) => self.with_basic_nodes(nodes),
+        };
+        Ok(peers)
     }
 }
 
+/// The contents of a known peers file.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PeersFile {
+    /// The persisted state of the peer set.
+    Persisted(PersistedPeers),
+    /// Only the node records of the peers.
+    Nodes(HashSet<NodeRecord>),
+}
+
+/// A peer of the peer set that is persisted across restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedPeer {
+    /// Where to reach the peer.
+    pub record: NodeRecord,
+    /// The reputation of the peer.
+    pub reputation: i32,
+    /// The [`ForkId`] that the peer announced via discovery.
+    pub fork_id: Option<ForkId>,
+    /// Unix timestamp in seconds of the last time the peer was connected.
+    pub last_seen: Option<u64>,
+}
+
+/// The state of the peer set that is persisted across restarts, see
+/// [`PeersConfig::with_persisted_peers`].
+///
+/// Trusted peers are configured separately and are not persisted as such.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PersistedPeers {
+    /// All known peers.
+    pub peers: Vec<PersistedPeer>,
+    /// The entries of the ban list.
+    pub bans: Vec<BanEntry>,
+}
+
+/// Converts an instant of the ban list to a unix timestamp in seconds.
+///
+/// Saturates at `u64::MAX` if the instant can't be represented as a system time.
+fn unix_timestamp(instant: std::time::Instant) -> u64 {
+    let remaining = instant.saturating_duration_since(std::time::Instant::now());
+    SystemTime::now()
+        .checked_add(remaining)
+        .map_or(u64::MAX, |time| time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs())
+}
+
+/// Converts a unix timestamp in seconds to an instant of the ban list.
+///
+/// Returns `None` if the timestamp is in the past, and `Some(None)` if it is too far in the
+/// future to be represented as an instant, in which case the ban is indefinite.
+fn instant_from_unix_timestamp(timestamp: u64) -> Option<Option<std::time::Instant>> {
+    let Some(until) = UNIX_EPOCH.checked_add(Duration::from_secs(timestamp)) else {
+        return Some(None)
+    };
+    let remaining = until.duration_since(SystemTime::now()).ok()?;
+    Some(std::time::Instant::now().checked_add(remaining))
+}
+
 /// The durations to use when a backoff should be applied to a peer.
 ///
 /// See also [`BackoffKind`](BackoffKind).
@@ -1280,8 +1563,8 @@ mod test {
         error::BackoffKind,
         peers::{
             manager::{ConnectionInfo, PeerBackoffDurations, PeerConnectionState},
-            reputation::DEFAULT_REPUTATION,
-            PeerAction,
+            reputation::{ReputationChangeWeights, DEFAULT_REPUTATION},
+            PeerAction, PersistedPeers,
         },
         session::PendingSessionHandshakeError,
         PeersConfig,
@@ -1294,6 +1577,7 @@ mod test {
     use reth_net_common::ban_list::BanList;
     use reth_network_api::ReputationChangeKind;
     use reth_primitives::{PeerId, H512};
+    use reth_rpc_types::{BanEntry, BanTarget};
     use std::{
         collections::HashSet,
         future::{poll_fn, Future},
@@ -2153,6 +2437,74 @@ mod test {
         assert!(peers.peers.get(&peer_id).is_none())
     }
 
+    #[tokio::test]
+    async fn test_trusted_inbound_slots() {
+        let trusted_peer = PeerId::random();
+        let trusted_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 1)), 8008);
+        let config = PeersConfig::default()
+            .with_max_inbound(2)
+            .with_trusted_inbound_slots(1)
+            .with_trusted_nodes(HashSet::from([NodeRecord {
+                address: trusted_addr.ip(),
+                tcp_port: trusted_addr.port(),
+                udp_port: trusted_addr.port(),
+                id: trusted_peer,
+            }]));
+        let mut peers = PeersManager::new(config);
+        let is_disconnected = |peers: &PeersManager, id: PeerId| {
+            peers.queued_actions.iter().any(|action| {
+                matches!(
+                    action,
+                    PeerAction::Disconnect { peer_id, reason: Some(DisconnectReason::TooManyPeers) }
+                    if *peer_id == id
+                )
+            })
+        };
+
+        let first = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8009);
+        peers.on_incoming_pending_session(addr.ip()).unwrap();
+        peers.on_incoming_session_established(first, addr);
+        assert!(!is_disconnected(&peers, first));
+
+        // the remaining slot is reserved
+        let second = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8009);
+        peers.on_incoming_pending_session(addr.ip()).unwrap();
+        peers.on_incoming_session_established(second, addr);
+        assert!(is_disconnected(&peers, second));
+        assert_eq!(peers.peers.get(&second).unwrap().state, PeerConnectionState::DisconnectingIn);
+        peers.on_active_session_gracefully_closed(second);
+
+        peers.on_incoming_pending_session(trusted_addr.ip()).unwrap();
+        peers.on_incoming_session_established(trusted_peer, trusted_addr);
+        assert!(!is_disconnected(&peers, trusted_peer));
+        assert_eq!(peers.peers.get(&trusted_peer).unwrap().state, PeerConnectionState::In);
+    }
+
+    #[tokio::test]
+    async fn test_incoming_session_limit_exceeded() {
+        let peer_id = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8009);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer_id, addr, None);
+
+        peers.on_incoming_pending_session(addr.ip()).unwrap();
+        assert_eq!(peers.connection_info.num_inbound, 1);
+        peers.on_incoming_session_limit_exceeded(addr.ip());
+        assert_eq!(peers.connection_info.num_inbound, 0);
+
+        let peer = peers.peers.get(&peer_id).unwrap();
+        let change = ReputationChangeWeights::default()
+            .change(ReputationChangeKind::ExceededConnectionLimit)
+            .as_i32();
+        assert_eq!(peer.reputation, DEFAULT_REPUTATION + change);
+        assert!(!peers.is_trusted_ip(addr.ip()));
+
+        peers.add_trusted_peer(PeerId::random(), addr);
+        assert!(peers.is_trusted_ip(addr.ip()));
+    }
+
     #[tokio::test]
     async fn test_keep_incoming_after_disconnect_if_discovered() {
         let peer_id = PeerId::random();
@@ -2174,4 +2526,103 @@ mod test {
         assert_eq!(peer.state, PeerConnectionState::Idle);
         assert!(!peer.remove_after_disconnect);
     }
+
+    #[tokio::test]
+    async fn test_manual_ban_disconnects() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, socket_addr, None);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        peers.ban_target(BanTarget::Ip(socket_addr.ip()), None);
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(
+            peers.bans(),
+            vec![BanEntry { target: BanTarget::Ip(socket_addr.ip()), until: None }]
+        );
+
+        peers.on_active_session_gracefully_closed(peer);
+        assert!(peers.peers.get(&peer).unwrap().last_seen.is_some());
+        assert!(peers.best_unconnected().is_none());
+
+        peers.unban_target(BanTarget::Ip(socket_addr.ip()));
+        assert!(peers.bans().is_empty());
+        assert_eq!(peers.best_unconnected().unwrap().0, peer);
+    }
+
+    #[tokio::test]
+    async fn test_manual_ban_duration_overflow() {
+        let peer = PeerId::random();
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let mut peers = PeersManager::default();
+
+        peers.ban_target(BanTarget::PeerId(peer), Some(Duration::from_secs(u64::MAX)));
+        peers.ban_target(BanTarget::Ip(ip), Some(Duration::MAX));
+        assert!(peers.ban_list.is_banned_peer(&peer));
+        assert!(peers.ban_list.is_banned_ip(&ip));
+        assert!(peers.bans().iter().all(|entry| entry.until.is_none()));
+
+        assert_eq!(instant_from_unix_timestamp(u64::MAX), Some(None));
+    }
+
+    #[tokio::test]
+    async fn test_restore_persisted_peers() {
+        let banned = PeerId::random();
+        let unbanned = PeerId::random();
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+
+        let mut peers = PeersManager::default();
+        peers.add_peer(banned, socket_addr, None);
+        peers.add_peer(unbanned, socket_addr, None);
+        peers.apply_reputation_change(&banned, ReputationChangeKind::BadProtocol);
+        peers.apply_reputation_change(&unbanned, ReputationChangeKind::BadProtocol);
+        // the ban of this peer expired
+        peers.unban_peer(unbanned);
+        peers.ban_target(BanTarget::Ip(ip), Some(Duration::from_secs(60)));
+
+        let persisted = peers.persisted_peers();
+        assert_eq!(persisted.peers.len(), 2);
+        assert_eq!(persisted.bans.len(), 2);
+
+        let serialized = serde_json::to_string(&persisted).unwrap();
+        let deserialized: PersistedPeers = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, persisted);
+
+        let peers = PeersManager::new(PeersConfig::default().with_persisted_peers(deserialized));
+        assert!(peers.ban_list.is_banned_peer(&banned));
+        assert!(peers.ban_list.is_banned_ip(&ip));
+        assert!(peers.peers.get(&banned).unwrap().is_banned());
+        assert_eq!(peers.get_reputation(&unbanned), Some(DEFAULT_REPUTATION));
+    }
+
+    #[tokio::test]
+    async fn test_read_legacy_peers_file() {
+        let url = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303?discport=30301";
+        let record: NodeRecord = url.parse().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("known-peers.json");
+        std::fs::write(&file, serde_json::to_string(&vec![record]).unwrap()).unwrap();
+
+        let config = PeersConfig::default().with_basic_nodes_from_file(Some(&file)).unwrap();
+        assert_eq!(config.basic_nodes, HashSet::from([record]));
+    }
 }
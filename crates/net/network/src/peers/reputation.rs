@@ -20,6 +20,13 @@ const FAILED_TO_CONNECT_REPUTATION_CHANGE: i32 = 25 * REPUTATION_UNIT;
 /// The reputation change to apply to a peer that failed to respond in time.
 const TIMEOUT_REPUTATION_CHANGE: i32 = 4 * REPUTATION_UNIT;
 
+/// The reputation change to apply to a peer whose IP address or subnet exceeded the session
+/// limits.
+const EXCEEDED_CONNECTION_LIMIT_REPUTATION_CHANGE: i32 = 4 * REPUTATION_UNIT;
+
+/// The reputation change to apply to a peer that repeatedly exceeded its request serving budget.
+const EXCEEDED_REQUEST_BUDGET_REPUTATION_CHANGE: i32 = 4 * REPUTATION_UNIT;
+
 /// The reputation change to apply to a peer that sent a bad message.
 const BAD_MESSAGE_REPUTATION_CHANGE: i32 = 16 * REPUTATION_UNIT;
 
@@ -42,6 +49,7 @@ pub(crate) fn is_banned_reputation(reputation: i32) -> bool {
 /// How the [`ReputationChangeKind`] are weighted.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ReputationChangeWeights {
     /// Weight for [`ReputationChangeKind::BadMessage`]
     pub bad_message: Reputation,
@@ -59,6 +67,10 @@ pub struct ReputationChangeWeights {
     pub failed_to_connect: Reputation,
     /// Weight for [`ReputationChangeKind::Dropped`]
     pub dropped: Reputation,
+    /// Weight for [`ReputationChangeKind::ExceededConnectionLimit`]
+    pub exceeded_connection_limit: Reputation,
+    /// Weight for [`ReputationChangeKind::ExceededRequestBudget`]
+    pub exceeded_request_budget: Reputation,
 }
 
 // === impl ReputationChangeWeights ===
@@ -76,6 +88,8 @@ impl ReputationChangeWeights {
             ReputationChangeKind::BadProtocol => self.bad_protocol.into(),
             ReputationChangeKind::FailedToConnect => self.failed_to_connect.into(),
             ReputationChangeKind::Dropped => self.dropped.into(),
+            ReputationChangeKind::ExceededConnectionLimit => self.exceeded_connection_limit.into(),
+            ReputationChangeKind::ExceededRequestBudget => self.exceeded_request_budget.into(),
             ReputationChangeKind::Reset => DEFAULT_REPUTATION.into(),
             ReputationChangeKind::Other(val) => val.into(),
         }
@@ -93,6 +107,8 @@ impl Default for ReputationChangeWeights {
             bad_protocol: BAD_PROTOCOL_REPUTATION_CHANGE,
             failed_to_connect: FAILED_TO_CONNECT_REPUTATION_CHANGE,
             dropped: REMOTE_DISCONNECT_REPUTATION_CHANGE,
+            exceeded_connection_limit: EXCEEDED_CONNECTION_LIMIT_REPUTATION_CHANGE,
+            exceeded_request_budget: EXCEEDED_REQUEST_BUDGET_REPUTATION_CHANGE,
         }
     }
 }
@@ -0,0 +1,56 @@
+//! Support for additional RLPx sub-protocols.
+//!
+//! Additional sub-protocols are announced in the `Hello` message and run over the same RLPx
+//! connections as `eth`. Each sub-protocol that is shared with a peer gets its own
+//! [`ProtocolStream`] per session, see [`ProtocolHandler`].
+
+use crate::session::Direction;
+use reth_eth_wire::{capability::Protocol, ProtocolStream};
+use reth_primitives::PeerId;
+use std::{fmt, sync::Arc};
+
+/// A handler for an additional RLPx sub-protocol.
+///
+/// The handler is registered with the [`NetworkBuilder`](crate::NetworkBuilder) and is invoked
+/// for every established session that shares the sub-protocol with the peer.
+pub trait ProtocolHandler: fmt::Debug + Send + Sync + 'static {
+    /// Returns the capability of the sub-protocol and the number of messages it uses.
+    fn protocol(&self) -> Protocol;
+
+    /// Invoked when a session with a peer that shares the sub-protocol is established.
+    ///
+    /// The [`ProtocolStream`] yields the messages of this sub-protocol and ends once the session
+    /// is closed. It is installed right after the `Hello` handshake, so it also yields the
+    /// messages the peer sent before the session was established.
+    fn on_connection(&self, peer_id: PeerId, direction: Direction, stream: ProtocolStream);
+}
+
+/// The registered additional RLPx sub-protocols.
+#[derive(Debug, Clone, Default)]
+pub struct RlpxSubProtocols {
+    handlers: Vec<Arc<dyn ProtocolHandler>>,
+}
+
+// === impl RlpxSubProtocols ===
+
+impl RlpxSubProtocols {
+    /// Adds a new sub-protocol handler.
+    pub fn push(&mut self, handler: impl ProtocolHandler) {
+        self.handlers.push(Arc::new(handler));
+    }
+
+    /// Returns an iterator over all registered sub-protocol handlers.
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn ProtocolHandler>> + '_ {
+        self.handlers.iter()
+    }
+
+    /// Returns the protocols of all registered handlers.
+    pub fn protocols(&self) -> Vec<Protocol> {
+        self.handlers.iter().map(|handler| handler.protocol()).collect()
+    }
+
+    /// Returns `true` if no sub-protocol is registered.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
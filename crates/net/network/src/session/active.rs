@@ -822,6 +822,7 @@ mod tests {
                 remote_addr,
                 self.secret_key,
                 self.hello.clone(),
+                Vec::new(),
                 self.status,
                 self.fork_filter.clone(),
             ));
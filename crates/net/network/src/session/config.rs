@@ -1,10 +1,15 @@
 //! Configuration types for [SessionManager](crate::session::SessionManager).
 
 use crate::{
+    eth_requests::ServingCapacity,
     peers::{DEFAULT_MAX_PEERS_INBOUND, DEFAULT_MAX_PEERS_OUTBOUND},
-    session::{Direction, ExceedsSessionLimit},
+    session::{Direction, ExceedsSessionLimit, InboundSessionLimitError},
+};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::{Duration, Instant},
 };
-use std::time::Duration;
 
 /// Default request timeout for a single request.
 ///
@@ -38,6 +43,8 @@ pub struct SessionsConfig {
     /// `PROTOCOL_BREACH_REQUEST_TIMEOUT`) this is considered a protocol violation and results in a
     /// dropped session.
     pub protocol_breach_request_timeout: Duration,
+    /// The capacity for serving `eth` requests to a single peer.
+    pub serving_capacity: ServingCapacity,
 }
 
 impl Default for SessionsConfig {
@@ -56,6 +63,7 @@ impl Default for SessionsConfig {
             limits: Default::default(),
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
+            serving_capacity: Default::default(),
         }
     }
 }
@@ -77,11 +85,19 @@ impl SessionsConfig {
 /// By default, no session limits will be enforced
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct SessionLimits {
     max_pending_inbound: Option<u32>,
     max_pending_outbound: Option<u32>,
     max_established_inbound: Option<u32>,
     max_established_outbound: Option<u32>,
+    /// Maximum number of pending and active incoming sessions from a single IP address.
+    max_inbound_per_ip: Option<u32>,
+    /// Maximum number of pending and active incoming sessions from a single subnet, a /24 for
+    /// IPv4 and a /64 for IPv6 addresses.
+    max_inbound_per_subnet: Option<u32>,
+    /// Rate limit for new incoming sessions.
+    inbound_rate_limit: Option<InboundRateLimit>,
 }
 
 impl SessionLimits {
@@ -108,6 +124,90 @@ impl SessionLimits {
         self.max_established_outbound = Some(limit);
         self
     }
+
+    /// Sets the maximum number of pending and active incoming sessions from a single IP address.
+    pub fn with_max_inbound_per_ip(mut self, limit: u32) -> Self {
+        self.max_inbound_per_ip = Some(limit);
+        self
+    }
+
+    /// Sets the maximum number of pending and active incoming sessions from a single /24 (IPv4)
+    /// or /64 (IPv6) subnet.
+    pub fn with_max_inbound_per_subnet(mut self, limit: u32) -> Self {
+        self.max_inbound_per_subnet = Some(limit);
+        self
+    }
+
+    /// Sets the rate limit for new incoming sessions.
+    pub fn with_inbound_rate_limit(mut self, limit: InboundRateLimit) -> Self {
+        self.inbound_rate_limit = Some(limit);
+        self
+    }
+}
+
+/// A token bucket rate limit for new incoming sessions.
+///
+/// Every incoming session takes a token, tokens are refilled at a constant rate up to the burst
+/// size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InboundRateLimit {
+    /// Maximum number of incoming sessions accepted at once.
+    pub burst: u32,
+    /// Number of incoming sessions accepted per second on average.
+    pub per_second: u32,
+}
+
+/// Tracks the tokens of an [InboundRateLimit].
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    limit: InboundRateLimit,
+    /// Currently available tokens.
+    tokens: f64,
+    /// When the tokens were last refilled.
+    last_refill: Instant,
+}
+
+// === impl TokenBucket ===
+
+impl TokenBucket {
+    fn new(limit: InboundRateLimit) -> Self {
+        Self { limit, tokens: limit.burst as f64, last_refill: Instant::now() }
+    }
+
+    /// Refills the bucket and returns `true` if a token is available.
+    fn has_token(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.limit.per_second as f64).min(self.limit.burst as f64);
+        self.last_refill = now;
+        self.tokens >= 1.0
+    }
+
+    /// Takes a token, see [Self::has_token].
+    fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+}
+
+/// Returns the /24 (IPv4) or /64 (IPv6) subnet of the given address.
+///
+/// IPv4-mapped IPv6 addresses, as reported by dual-stack listeners, are treated as IPv4 addresses.
+fn subnet(ip: IpAddr) -> IpAddr {
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        ip => ip,
+    };
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            IpAddr::V4(Ipv4Addr::new(a, b, c, 0))
+        }
+        IpAddr::V6(ip) => {
+            let [a, b, c, d, ..] = ip.segments();
+            IpAddr::V6(Ipv6Addr::new(a, b, c, d, 0, 0, 0, 0))
+        }
+    }
 }
 
 /// Keeps track of all sessions.
@@ -123,58 +223,99 @@ pub struct SessionCounter {
     active_inbound: u32,
     /// Number of active outbound sessions.
     active_outbound: u32,
+    /// Number of pending and active incoming sessions per IP address.
+    inbound_per_ip: HashMap<IpAddr, u32>,
+    /// Number of pending and active incoming sessions per subnet.
+    inbound_per_subnet: HashMap<IpAddr, u32>,
+    /// Rate limits new incoming sessions.
+    inbound_rate_limit: Option<TokenBucket>,
 }
 
 // === impl SessionCounter ===
 
 impl SessionCounter {
     pub(crate) fn new(limits: SessionLimits) -> Self {
+        let inbound_rate_limit = limits.inbound_rate_limit.map(TokenBucket::new);
         Self {
             limits,
             pending_inbound: 0,
             pending_outbound: 0,
             active_inbound: 0,
             active_outbound: 0,
+            inbound_per_ip: Default::default(),
+            inbound_per_subnet: Default::default(),
+            inbound_rate_limit,
         }
     }
 
-    pub(crate) fn inc_pending_inbound(&mut self) {
+    /// Counts a new incoming session, sessions from trusted peers don't count against the rate
+    /// limit.
+    pub(crate) fn inc_pending_inbound(&mut self, ip: IpAddr, trusted: bool) {
         self.pending_inbound += 1;
+        *self.inbound_per_ip.entry(ip).or_default() += 1;
+        *self.inbound_per_subnet.entry(subnet(ip)).or_default() += 1;
+        if trusted {
+            return
+        }
+        if let Some(bucket) = self.inbound_rate_limit.as_mut() {
+            bucket.take();
+        }
     }
 
     pub(crate) fn inc_pending_outbound(&mut self) {
         self.pending_outbound += 1;
     }
 
-    pub(crate) fn dec_pending(&mut self, direction: &Direction) {
+    /// Decrements the pending sessions.
+    ///
+    /// This must be followed by [Self::inc_active] if the session was established.
+    pub(crate) fn dec_pending(&mut self, direction: &Direction, ip: IpAddr) {
         match direction {
             Direction::Outgoing(_) => {
                 self.pending_outbound -= 1;
             }
             Direction::Incoming => {
                 self.pending_inbound -= 1;
+                self.dec_inbound(ip);
             }
         }
     }
 
-    pub(crate) fn inc_active(&mut self, direction: &Direction) {
+    pub(crate) fn inc_active(&mut self, direction: &Direction, ip: IpAddr) {
         match direction {
             Direction::Outgoing(_) => {
                 self.active_outbound += 1;
             }
             Direction::Incoming => {
                 self.active_inbound += 1;
+                *self.inbound_per_ip.entry(ip).or_default() += 1;
+                *self.inbound_per_subnet.entry(subnet(ip)).or_default() += 1;
             }
         }
     }
 
-    pub(crate) fn dec_active(&mut self, direction: &Direction) {
+    pub(crate) fn dec_active(&mut self, direction: &Direction, ip: IpAddr) {
         match direction {
             Direction::Outgoing(_) => {
                 self.active_outbound -= 1;
             }
             Direction::Incoming => {
                 self.active_inbound -= 1;
+                self.dec_inbound(ip);
+            }
+        }
+    }
+
+    /// Removes an incoming session from the per IP and per subnet counts.
+    fn dec_inbound(&mut self, ip: IpAddr) {
+        for (map, key) in
+            [(&mut self.inbound_per_ip, ip), (&mut self.inbound_per_subnet, subnet(ip))]
+        {
+            if let Some(count) = map.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    map.remove(&key);
+                }
             }
         }
     }
@@ -188,6 +329,35 @@ impl SessionCounter {
         Self::ensure(self.pending_inbound, self.limits.max_pending_inbound)
     }
 
+    /// Ensures that a new incoming session from the given IP address is within all limits.
+    ///
+    /// The IP address of a trusted peer is exempt from the per IP, per subnet and rate limits.
+    pub(crate) fn ensure_inbound(
+        &mut self,
+        ip: IpAddr,
+        trusted: bool,
+    ) -> Result<(), InboundSessionLimitError> {
+        self.ensure_pending_inbound()?;
+        if trusted {
+            return Ok(())
+        }
+
+        let from_ip = self.inbound_per_ip.get(&ip).copied().unwrap_or_default();
+        Self::ensure(from_ip, self.limits.max_inbound_per_ip)
+            .map_err(|err| InboundSessionLimitError::ExceedsIpLimit(err.0))?;
+
+        let from_subnet = self.inbound_per_subnet.get(&subnet(ip)).copied().unwrap_or_default();
+        Self::ensure(from_subnet, self.limits.max_inbound_per_subnet)
+            .map_err(|err| InboundSessionLimitError::ExceedsSubnetLimit(err.0))?;
+
+        if let Some(bucket) = self.inbound_rate_limit.as_mut() {
+            if !bucket.has_token(Instant::now()) {
+                return Err(InboundSessionLimitError::RateLimited)
+            }
+        }
+        Ok(())
+    }
+
     fn ensure(current: u32, limit: Option<u32>) -> Result<(), ExceedsSessionLimit> {
         if let Some(limit) = limit {
             if current >= limit {
@@ -206,9 +376,98 @@ mod tests {
     fn test_limits() {
         let mut limits = SessionCounter::new(SessionLimits::default().with_max_pending_inbound(2));
         assert!(limits.ensure_pending_outbound().is_ok());
-        limits.inc_pending_inbound();
+        limits.inc_pending_inbound(IpAddr::V4(Ipv4Addr::LOCALHOST), false);
         assert!(limits.ensure_pending_inbound().is_ok());
-        limits.inc_pending_inbound();
+        limits.inc_pending_inbound(IpAddr::V4(Ipv4Addr::LOCALHOST), false);
         assert!(limits.ensure_pending_inbound().is_err());
     }
+
+    #[test]
+    fn test_inbound_limits_per_ip_and_subnet() {
+        let limits =
+            SessionLimits::default().with_max_inbound_per_ip(1).with_max_inbound_per_subnet(2);
+        let mut counter = SessionCounter::new(limits);
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+
+        assert!(counter.ensure_inbound(ip, false).is_ok());
+        counter.inc_pending_inbound(ip, false);
+        assert!(matches!(
+            counter.ensure_inbound(ip, false),
+            Err(InboundSessionLimitError::ExceedsIpLimit(1))
+        ));
+        // trusted peers are exempt
+        assert!(counter.ensure_inbound(ip, true).is_ok());
+
+        // the session is still counted once established
+        counter.dec_pending(&Direction::Incoming, ip);
+        counter.inc_active(&Direction::Incoming, ip);
+        assert!(counter.ensure_inbound(ip, false).is_err());
+
+        let neighbour = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 5));
+        assert!(counter.ensure_inbound(neighbour, false).is_ok());
+        counter.inc_pending_inbound(neighbour, false);
+        assert!(matches!(
+            counter.ensure_inbound(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 6)), false),
+            Err(InboundSessionLimitError::ExceedsSubnetLimit(2))
+        ));
+        assert!(counter.ensure_inbound(IpAddr::V4(Ipv4Addr::new(1, 2, 4, 6)), false).is_ok());
+
+        counter.dec_active(&Direction::Incoming, ip);
+        assert!(counter.ensure_inbound(ip, false).is_ok());
+        assert!(counter.inbound_per_ip.get(&ip).is_none());
+    }
+
+    #[test]
+    fn test_subnet_of_ipv4_mapped_address() {
+        let mapped = IpAddr::V6(Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped());
+        assert_eq!(subnet(mapped), IpAddr::V4(Ipv4Addr::new(1, 2, 3, 0)));
+        assert_eq!(subnet(mapped), subnet(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 200))));
+
+        let limits = SessionLimits::default().with_max_inbound_per_subnet(1);
+        let mut counter = SessionCounter::new(limits);
+        counter.inc_pending_inbound(mapped, false);
+        let err = counter.ensure_inbound(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 5)), false).unwrap_err();
+        assert!(matches!(err, InboundSessionLimitError::ExceedsSubnetLimit(1)));
+        assert!(err.is_penalized());
+
+        let v6 = IpAddr::V6("2001:db8:1:2:3:4:5:6".parse().unwrap());
+        assert_eq!(subnet(v6), IpAddr::V6("2001:db8:1:2::".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_inbound_rate_limit() {
+        let limits = SessionLimits::default()
+            .with_inbound_rate_limit(InboundRateLimit { burst: 2, per_second: 1 });
+        let mut counter = SessionCounter::new(limits);
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+
+        for _ in 0..2 {
+            assert!(counter.ensure_inbound(ip, false).is_ok());
+            counter.inc_pending_inbound(ip, false);
+        }
+        let err = counter.ensure_inbound(ip, false).unwrap_err();
+        assert!(matches!(err, InboundSessionLimitError::RateLimited));
+        // the bucket is shared by all hosts, so the host is not penalized
+        assert!(!err.is_penalized());
+
+        // trusted peers are exempt and don't use up tokens
+        let bucket = counter.inbound_rate_limit.as_ref().unwrap();
+        let tokens = bucket.tokens;
+        assert!(counter.ensure_inbound(ip, true).is_ok());
+        counter.inc_pending_inbound(ip, true);
+        assert_eq!(counter.inbound_rate_limit.as_ref().unwrap().tokens, tokens);
+
+        // refilled after a second
+        let bucket = counter.inbound_rate_limit.as_mut().unwrap();
+        assert!(bucket.has_token(bucket.last_refill + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_subnet() {
+        assert_eq!(subnet("1.2.3.4".parse().unwrap()), "1.2.3.0".parse::<IpAddr>().unwrap());
+        assert_eq!(
+            subnet("2001:db8:1:2:3:4:5:6".parse().unwrap()),
+            "2001:db8:1:2::".parse::<IpAddr>().unwrap()
+        );
+    }
 }
@@ -7,7 +7,7 @@ use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::{Capabilities, CapabilityMessage},
     errors::EthStreamError,
-    DisconnectReason, EthStream, EthVersion, P2PStream, Status,
+    DisconnectReason, EthStream, EthVersion, P2PStream, ProtocolStream, Status,
 };
 use reth_net_common::bandwidth_meter::MeteredStream;
 use reth_primitives::PeerId;
@@ -30,6 +30,8 @@ pub struct PendingSessionHandle {
     pub(crate) disconnect_tx: Option<oneshot::Sender<()>>,
     /// The direction of the session
     pub(crate) direction: Direction,
+    /// The address of the remote peer.
+    pub(crate) remote_addr: SocketAddr,
 }
 
 // === impl PendingSessionHandle ===
@@ -171,6 +173,11 @@ pub enum PendingSessionEvent {
         /// The actual connection stream which can be used to send and receive `eth` protocol
         /// messages
         conn: EthStream<P2PStream<ECIESStream<MeteredStream<TcpStream>>>>,
+        /// The streams of the shared additional sub-protocols.
+        ///
+        /// These are installed right after the `Hello` handshake, so that messages the peer sends
+        /// during the `eth` status handshake are buffered until the handlers take over.
+        protocols: Vec<ProtocolStream>,
         /// The direction of the session, either `Inbound` or `Outgoing`
         direction: Direction,
         /// The remote node's user agent, usually containing the client name and version
@@ -2,6 +2,7 @@
 use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
+    protocol::{ProtocolHandler, RlpxSubProtocols},
     session::{active::ActiveSession, config::SessionCounter},
 };
 pub use crate::{message::PeerRequestSender, session::handle::PeerInfo};
@@ -9,7 +10,7 @@ use fnv::FnvHashMap;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
-    capability::{Capabilities, CapabilityMessage},
+    capability::{Capabilities, CapabilityMessage, Protocol},
     errors::EthStreamError,
     DisconnectReason, EthVersion, HelloMessage, Status, UnauthedEthStream, UnauthedP2PStream,
 };
@@ -40,7 +41,7 @@ use tracing::{instrument, trace};
 mod active;
 mod config;
 mod handle;
-pub use config::{SessionLimits, SessionsConfig};
+pub use config::{InboundRateLimit, SessionLimits, SessionsConfig};
 pub use handle::{
     ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
     SessionCommand,
@@ -101,6 +102,8 @@ pub struct SessionManager {
     bandwidth_meter: BandwidthMeter,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
+    /// Additional RLPx sub-protocols that are negotiated next to `eth`.
+    extra_protocols: RlpxSubProtocols,
 }
 
 // === impl SessionManager ===
@@ -138,9 +141,27 @@ impl SessionManager {
             active_session_rx: ReceiverStream::new(active_session_rx),
             bandwidth_meter,
             metrics: Default::default(),
+            extra_protocols: Default::default(),
         }
     }
 
+    /// Registers an additional RLPx sub-protocol and announces its capability in the `Hello`
+    /// message.
+    ///
+    /// This only affects sessions that are established afterwards.
+    pub(crate) fn add_rlpx_sub_protocol(&mut self, handler: impl ProtocolHandler) {
+        let cap = handler.protocol().cap;
+        if !self.hello_message.capabilities.contains(&cap) {
+            self.hello_message.capabilities.push(cap);
+        }
+        self.extra_protocols.push(handler);
+    }
+
+    /// Returns the executor for spawned tasks.
+    pub(crate) fn executor(&self) -> Box<dyn TaskSpawner> {
+        self.executor.clone()
+    }
+
     /// Check whether the provided [`ForkId`] is compatible based on the validation rules in
     /// `EIP-2124`.
     pub fn is_valid_fork_id(&self, fork_id: ForkId) -> bool {
@@ -186,13 +207,29 @@ impl SessionManager {
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
-    /// Returns an error if the configured limit has been reached.
+    /// Returns an error if a configured limit has been reached, see [`SessionLimits`]. Connections
+    /// from the IP address of a `trusted` peer are exempt from the per host and rate limits.
     pub(crate) fn on_incoming(
         &mut self,
         stream: TcpStream,
         remote_addr: SocketAddr,
-    ) -> Result<SessionId, ExceedsSessionLimit> {
-        self.counter.ensure_pending_inbound()?;
+        trusted: bool,
+    ) -> Result<SessionId, InboundSessionLimitError> {
+        if let Err(err) = self.counter.ensure_inbound(remote_addr.ip(), trusted) {
+            match err {
+                InboundSessionLimitError::Exceeds(_) => {}
+                InboundSessionLimitError::ExceedsIpLimit(_) => {
+                    self.metrics.inbound_rejected_ip_limit.increment(1)
+                }
+                InboundSessionLimitError::ExceedsSubnetLimit(_) => {
+                    self.metrics.inbound_rejected_subnet_limit.increment(1)
+                }
+                InboundSessionLimitError::RateLimited => {
+                    self.metrics.inbound_rejected_rate_limit.increment(1)
+                }
+            }
+            return Err(err)
+        }
 
         let session_id = self.next_id();
 
@@ -210,6 +247,7 @@ impl SessionManager {
         let hello_message = self.hello_message.clone();
         let status = self.status;
         let fork_filter = self.fork_filter.clone();
+        let extra_protocols = self.extra_protocols.protocols();
         self.spawn(start_pending_incoming_session(
             disconnect_rx,
             session_id,
@@ -218,6 +256,7 @@ impl SessionManager {
             remote_addr,
             secret_key,
             hello_message,
+            extra_protocols,
             status,
             fork_filter,
         ));
@@ -225,9 +264,10 @@ impl SessionManager {
         let handle = PendingSessionHandle {
             disconnect_tx: Some(disconnect_tx),
             direction: Direction::Incoming,
+            remote_addr,
         };
         self.pending_sessions.insert(session_id, handle);
-        self.counter.inc_pending_inbound();
+        self.counter.inc_pending_inbound(remote_addr.ip(), trusted);
         Ok(session_id)
     }
 
@@ -243,6 +283,7 @@ impl SessionManager {
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
             let band_with_meter = self.bandwidth_meter.clone();
+            let extra_protocols = self.extra_protocols.protocols();
             self.spawn(start_pending_outbound_session(
                 disconnect_rx,
                 pending_events,
@@ -251,6 +292,7 @@ impl SessionManager {
                 remote_peer_id,
                 secret_key,
                 hello_message,
+                extra_protocols,
                 status,
                 fork_filter,
                 band_with_meter,
@@ -259,6 +301,7 @@ impl SessionManager {
             let handle = PendingSessionHandle {
                 disconnect_tx: Some(disconnect_tx),
                 direction: Direction::Outgoing(remote_peer_id),
+                remote_addr,
             };
             self.pending_sessions.insert(session_id, handle);
             self.counter.inc_pending_outbound();
@@ -317,14 +360,14 @@ impl SessionManager {
     /// Removes the [`PendingSessionHandle`] if it exists.
     fn remove_pending_session(&mut self, id: &SessionId) -> Option<PendingSessionHandle> {
         let session = self.pending_sessions.remove(id)?;
-        self.counter.dec_pending(&session.direction);
+        self.counter.dec_pending(&session.direction, session.remote_addr.ip());
         Some(session)
     }
 
     /// Removes the [`PendingSessionHandle`] if it exists.
     fn remove_active_session(&mut self, id: &PeerId) -> Option<ActiveSessionHandle> {
         let session = self.active_sessions.remove(id)?;
-        self.counter.dec_active(&session.direction);
+        self.counter.dec_active(&session.direction, session.remote_addr.ip());
         Some(session)
     }
 
@@ -391,6 +434,7 @@ impl SessionManager {
                 peer_id,
                 capabilities,
                 conn,
+                protocols,
                 status,
                 direction,
                 client_id,
@@ -422,6 +466,17 @@ impl SessionManager {
                     })
                 }
 
+                // hand the shared additional sub-protocols over to their handlers
+                for stream in protocols {
+                    if let Some(handler) = self
+                        .extra_protocols
+                        .iter()
+                        .find(|handler| stream.capability().is(&handler.protocol().cap))
+                    {
+                        handler.on_connection(peer_id, direction, stream);
+                    }
+                }
+
                 let (commands_to_session, commands_rx) = mpsc::channel(self.session_command_buffer);
 
                 let (to_session_tx, messages_rx) = mpsc::channel(self.session_command_buffer);
@@ -472,7 +527,7 @@ impl SessionManager {
                 };
 
                 self.active_sessions.insert(peer_id, handle);
-                self.counter.inc_active(&direction);
+                self.counter.inc_active(&direction, remote_addr.ip());
 
                 if direction.is_outgoing() {
                     self.metrics.total_dial_successes.increment(1);
@@ -749,6 +804,35 @@ impl std::fmt::Display for Direction {
 #[error("Session limit reached {0}")]
 pub struct ExceedsSessionLimit(pub(crate) u32);
 
+/// The error thrown when an incoming connection is rejected because of the configured
+/// [`SessionLimits`].
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum InboundSessionLimitError {
+    /// The limit of pending incoming sessions has been reached.
+    #[error(transparent)]
+    Exceeds(#[from] ExceedsSessionLimit),
+    /// The limit of incoming sessions from the remote IP address has been reached.
+    #[error("Session limit per IP reached {0}")]
+    ExceedsIpLimit(u32),
+    /// The limit of incoming sessions from the subnet of the remote IP address has been reached.
+    #[error("Session limit per subnet reached {0}")]
+    ExceedsSubnetLimit(u32),
+    /// Too many incoming sessions were accepted recently.
+    #[error("Incoming sessions rate limited")]
+    RateLimited,
+}
+
+impl InboundSessionLimitError {
+    /// Returns `true` if the remote host should be penalized for the rejected connection, because
+    /// it exceeded a per host limit.
+    ///
+    /// Reaching the total number of pending sessions or the rate limit is not penalized, since
+    /// these limits are shared by all hosts.
+    pub fn is_penalized(&self) -> bool {
+        matches!(self, Self::ExceedsIpLimit(_) | Self::ExceedsSubnetLimit(_))
+    }
+}
+
 /// Starts the authentication process for a connection initiated by a remote peer.
 ///
 /// This will wait for the _incoming_ handshake request and answer it.
@@ -761,6 +845,7 @@ pub(crate) async fn start_pending_incoming_session(
     remote_addr: SocketAddr,
     secret_key: SecretKey,
     hello: HelloMessage,
+    extra_protocols: Vec<Protocol>,
     status: Status,
     fork_filter: ForkFilter,
 ) {
@@ -773,6 +858,7 @@ pub(crate) async fn start_pending_incoming_session(
         secret_key,
         Direction::Incoming,
         hello,
+        extra_protocols,
         status,
         fork_filter,
     )
@@ -790,6 +876,7 @@ async fn start_pending_outbound_session(
     remote_peer_id: PeerId,
     secret_key: SecretKey,
     hello: HelloMessage,
+    extra_protocols: Vec<Protocol>,
     status: Status,
     fork_filter: ForkFilter,
     bandwidth_meter: BandwidthMeter,
@@ -817,6 +904,7 @@ async fn start_pending_outbound_session(
         secret_key,
         Direction::Outgoing(remote_peer_id),
         hello,
+        extra_protocols,
         status,
         fork_filter,
     )
@@ -834,6 +922,7 @@ async fn authenticate(
     secret_key: SecretKey,
     direction: Direction,
     hello: HelloMessage,
+    extra_protocols: Vec<Protocol>,
     status: Status,
     fork_filter: ForkFilter,
 ) {
@@ -860,6 +949,7 @@ async fn authenticate(
         remote_addr,
         direction,
         hello,
+        extra_protocols,
         status,
         fork_filter,
     )
@@ -907,21 +997,30 @@ async fn authenticate_stream(
     remote_addr: SocketAddr,
     direction: Direction,
     hello: HelloMessage,
+    extra_protocols: Vec<Protocol>,
     status: Status,
     fork_filter: ForkFilter,
 ) -> PendingSessionEvent {
     // conduct the p2p handshake and return the authenticated stream
-    let (p2p_stream, their_hello) = match stream.handshake(hello).await {
-        Ok(stream_res) => stream_res,
-        Err(err) => {
-            return PendingSessionEvent::Disconnected {
-                remote_addr,
-                session_id,
-                direction,
-                error: Some(err.into()),
+    let (mut p2p_stream, their_hello) =
+        match stream.handshake_with_protocols(hello, &extra_protocols).await {
+            Ok(stream_res) => stream_res,
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(err.into()),
+                }
             }
-        }
-    };
+        };
+
+    // install the shared additional sub-protocols before the status handshake, so that their
+    // messages are buffered rather than dropped if the peer sends them right after the hello
+    let protocols = extra_protocols
+        .iter()
+        .filter_map(|protocol| p2p_stream.install_protocol(&protocol.cap))
+        .collect();
 
     // if the hello handshake was successful we can try status handshake
     //
@@ -946,6 +1045,7 @@ async fn authenticate_stream(
         capabilities: Arc::new(Capabilities::from(their_hello.capabilities)),
         status: their_status,
         conn: eth_stream,
+        protocols,
         direction,
         client_id: their_hello.client_version,
     }
@@ -0,0 +1,520 @@
+//! The `snap/1` sub-protocol.
+//!
+//! The [`SnapProtocolHandler`] runs the `snap` protocol over the [`ProtocolStream`] of every
+//! session that shares it. Requests of the peer are delegated to the
+//! [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler), and the responses of the peer
+//! are routed back to the requests sent by the [`SnapFetchClient`].
+
+use crate::{
+    flattened_response::FlattenedResponse, peers::PeersHandle, protocol::ProtocolHandler,
+    session::Direction, snap_requests::IncomingSnapRequest,
+};
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    capability::Protocol, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+    GetTrieNodes, ProtocolSender, ProtocolStream, SnapMessage, StorageRanges, TrieNodes,
+};
+use reth_interfaces::p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    priority::Priority,
+    snap::client::SnapClient,
+};
+use reth_network_api::ReputationChangeKind;
+use reth_primitives::{bytes::BytesMut, PeerId, WithPeerId};
+use reth_rlp::Encodable;
+use reth_tasks::TaskSpawner;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::trace;
+
+/// The max channel capacity of the
+/// [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler).
+pub(crate) const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
+/// Maximum number of requests the [`SnapFetchClient`] queues for a single session.
+const SNAP_SESSION_REQUEST_BUFFER: usize = 32;
+
+/// Time to wait for the response of a peer before the request fails with
+/// [`RequestError::Timeout`].
+const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The sessions that share the `snap` protocol, keyed by peer.
+type SnapSessions = Arc<Mutex<HashMap<PeerId, SnapSessionHandle>>>;
+
+/// Runs the `snap/1` sub-protocol on all sessions that share it.
+///
+/// Registered with
+/// [`NetworkBuilder::snap_request_handler`](crate::NetworkBuilder::snap_request_handler).
+#[derive(Debug)]
+pub struct SnapProtocolHandler {
+    /// Delegates the requests of peers to the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// Used for reporting peers that send bad messages.
+    peers: PeersHandle,
+    /// All sessions that share the `snap` protocol.
+    sessions: SnapSessions,
+    /// Spawns the per-session tasks.
+    executor: Box<dyn TaskSpawner>,
+}
+
+// === impl SnapProtocolHandler ===
+
+impl SnapProtocolHandler {
+    /// Creates a new handler that delegates the requests of peers to the given channel.
+    pub(crate) fn new(
+        to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+        peers: PeersHandle,
+        executor: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self { to_request_handler, peers, sessions: Default::default(), executor }
+    }
+
+    /// Returns a new [`SnapFetchClient`] that sends requests to the sessions of this handler.
+    pub fn fetch_client(&self) -> SnapFetchClient {
+        SnapFetchClient { sessions: self.sessions.clone(), peers: self.peers.clone() }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    fn protocol(&self) -> Protocol {
+        Protocol::snap()
+    }
+
+    fn on_connection(&self, peer_id: PeerId, _direction: Direction, stream: ProtocolStream) {
+        let (to_session, from_client) = mpsc::channel(SNAP_SESSION_REQUEST_BUFFER);
+        let inflight = Arc::new(AtomicUsize::new(0));
+        self.sessions
+            .lock()
+            .insert(peer_id, SnapSessionHandle { to_session, inflight: inflight.clone() });
+
+        let session = SnapSession {
+            peer_id,
+            sender: stream.sender(),
+            to_request_handler: self.to_request_handler.clone(),
+            peers: self.peers.clone(),
+            inflight_count: inflight,
+            inflight: HashMap::new(),
+            next_request_id: 0,
+        };
+        let sessions = self.sessions.clone();
+        self.executor.spawn(
+            async move {
+                session.run(stream, from_client).await;
+                let mut sessions = sessions.lock();
+                // a new session of the same peer may have replaced this one already
+                if sessions.get(&peer_id).map_or(false, |handle| handle.to_session.is_closed()) {
+                    sessions.remove(&peer_id);
+                }
+            }
+            .boxed(),
+        );
+    }
+}
+
+/// The [`SnapFetchClient`] half of a session that shares the `snap` protocol.
+#[derive(Debug)]
+struct SnapSessionHandle {
+    /// Sends requests to the session.
+    to_session: mpsc::Sender<OutgoingSnapRequest>,
+    /// Number of requests awaiting a response from the peer.
+    inflight: Arc<AtomicUsize>,
+}
+
+/// A request of the [`SnapFetchClient`], sent to a session.
+#[derive(Debug)]
+struct OutgoingSnapRequest {
+    /// The request message.
+    request: SnapMessage,
+    /// Receives the response of the peer.
+    response: PendingSnapResponse,
+}
+
+/// The response channel of a request sent to a peer.
+#[derive(Debug)]
+enum PendingSnapResponse {
+    AccountRange(oneshot::Sender<PeerRequestResult<AccountRange>>),
+    StorageRanges(oneshot::Sender<PeerRequestResult<StorageRanges>>),
+    ByteCodes(oneshot::Sender<PeerRequestResult<ByteCodes>>),
+    TrieNodes(oneshot::Sender<PeerRequestResult<TrieNodes>>),
+}
+
+// === impl PendingSnapResponse ===
+
+impl PendingSnapResponse {
+    /// Sends the response message of the peer, with the `request_id` of the original request.
+    ///
+    /// Returns `false` if the message does not answer the request, in which case the request fails
+    /// with [`RequestError::BadResponse`].
+    fn send(self, peer_id: PeerId, request_id: u64, msg: SnapMessage) -> bool {
+        match (self, msg) {
+            (Self::AccountRange(tx), SnapMessage::AccountRange(mut resp)) => {
+                resp.request_id = request_id;
+                let _ = tx.send(Ok(WithPeerId::new(peer_id, resp)));
+            }
+            (Self::StorageRanges(tx), SnapMessage::StorageRanges(mut resp)) => {
+                resp.request_id = request_id;
+                let _ = tx.send(Ok(WithPeerId::new(peer_id, resp)));
+            }
+            (Self::ByteCodes(tx), SnapMessage::ByteCodes(mut resp)) => {
+                resp.request_id = request_id;
+                let _ = tx.send(Ok(WithPeerId::new(peer_id, resp)));
+            }
+            (Self::TrieNodes(tx), SnapMessage::TrieNodes(mut resp)) => {
+                resp.request_id = request_id;
+                let _ = tx.send(Ok(WithPeerId::new(peer_id, resp)));
+            }
+            (this, _) => {
+                this.send_err(RequestError::BadResponse);
+                return false
+            }
+        }
+        true
+    }
+
+    /// Fails the request with the given error.
+    fn send_err(self, err: RequestError) {
+        match self {
+            Self::AccountRange(tx) => {
+                let _ = tx.send(Err(err));
+            }
+            Self::StorageRanges(tx) => {
+                let _ = tx.send(Err(err));
+            }
+            Self::ByteCodes(tx) => {
+                let _ = tx.send(Err(err));
+            }
+            Self::TrieNodes(tx) => {
+                let _ = tx.send(Err(err));
+            }
+        }
+    }
+}
+
+/// A request sent to the peer that awaits its response.
+#[derive(Debug)]
+struct InflightSnapRequest {
+    /// The `request_id` the request was created with.
+    request_id: u64,
+    /// Receives the response.
+    response: PendingSnapResponse,
+    /// When the request times out.
+    deadline: Instant,
+}
+
+/// The `snap` protocol state of a single session.
+struct SnapSession {
+    /// The peer of the session.
+    peer_id: PeerId,
+    /// Sends messages to the peer.
+    sender: ProtocolSender,
+    /// Delegates the requests of the peer to the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// Used for reporting the peer.
+    peers: PeersHandle,
+    /// Number of requests awaiting a response, shared with the [`SnapFetchClient`].
+    inflight_count: Arc<AtomicUsize>,
+    /// Requests sent to the peer, keyed by the `request_id` they were sent with.
+    inflight: HashMap<u64, InflightSnapRequest>,
+    /// The `request_id` of the next request sent to the peer.
+    next_request_id: u64,
+}
+
+// === impl SnapSession ===
+
+impl SnapSession {
+    /// Drives the session until it is closed.
+    ///
+    /// Outstanding requests fail with [`RequestError::ConnectionDropped`] once the session is
+    /// closed.
+    async fn run(
+        mut self,
+        mut stream: ProtocolStream,
+        mut from_client: mpsc::Receiver<OutgoingSnapRequest>,
+    ) {
+        let mut responses = FuturesUnordered::<BoxFuture<'static, Option<SnapMessage>>>::new();
+        let mut timeouts = tokio::time::interval(Duration::from_secs(1));
+
+        loop {
+            tokio::select! {
+                msg = stream.next() => {
+                    let Some(msg) = msg else { break };
+                    self.on_message(msg, &mut responses);
+                }
+                Some(request) = from_client.recv() => self.on_client_request(request),
+                Some(response) = responses.next(), if !responses.is_empty() => {
+                    if let Some(response) = response {
+                        self.send(response);
+                    }
+                }
+                _ = timeouts.tick() => self.on_timeout_tick(),
+            }
+        }
+
+        from_client.close();
+        for (_, request) in self.inflight.drain() {
+            request.response.send_err(RequestError::ConnectionDropped);
+        }
+        self.inflight_count.store(0, Ordering::Relaxed);
+    }
+
+    /// Handles a message of the peer.
+    fn on_message(
+        &mut self,
+        msg: BytesMut,
+        responses: &mut FuturesUnordered<BoxFuture<'static, Option<SnapMessage>>>,
+    ) {
+        let msg = match SnapMessage::decode_message(&mut &msg[..]) {
+            Ok(msg) => msg,
+            Err(err) => {
+                trace!(target: "net::snap", peer_id=?self.peer_id, ?err, "Failed to decode snap message");
+                self.peers.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+                return
+            }
+        };
+
+        if msg.is_request() {
+            if let Some(response) = self.delegate_request(msg) {
+                responses.push(response);
+            }
+            return
+        }
+
+        let Some(request) = self.inflight.remove(&msg.request_id()) else {
+            trace!(target: "net::snap", peer_id=?self.peer_id, request_id=msg.request_id(), "Received unsolicited snap response");
+            self.peers.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+            return
+        };
+        self.inflight_count.fetch_sub(1, Ordering::Relaxed);
+        if !request.response.send(self.peer_id, request.request_id, msg) {
+            self.peers.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+        }
+    }
+
+    /// Delegates a request of the peer to the request handler.
+    ///
+    /// Returns a future that resolves to the response, or `None` if the request handler is busy.
+    fn delegate_request(
+        &self,
+        msg: SnapMessage,
+    ) -> Option<BoxFuture<'static, Option<SnapMessage>>> {
+        let peer_id = self.peer_id;
+        let (incoming, response) = match msg {
+            SnapMessage::GetAccountRange(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response },
+                    rx.map(|res| Some(SnapMessage::AccountRange(res.ok()?.ok()?))).boxed(),
+                )
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response },
+                    rx.map(|res| Some(SnapMessage::StorageRanges(res.ok()?.ok()?))).boxed(),
+                )
+            }
+            SnapMessage::GetByteCodes(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response },
+                    rx.map(|res| Some(SnapMessage::ByteCodes(res.ok()?.ok()?))).boxed(),
+                )
+            }
+            SnapMessage::GetTrieNodes(request) => {
+                let (response, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response },
+                    rx.map(|res| Some(SnapMessage::TrieNodes(res.ok()?.ok()?))).boxed(),
+                )
+            }
+            _ => return None,
+        };
+
+        if self.to_request_handler.try_send(incoming).is_err() {
+            trace!(target: "net::snap", ?peer_id, "Dropping snap request, request handler is busy");
+            return None
+        }
+        Some(response)
+    }
+
+    /// Sends a request of the [`SnapFetchClient`] to the peer.
+    ///
+    /// The request is sent with a session unique `request_id` and the response is returned with the
+    /// original one.
+    fn on_client_request(&mut self, request: OutgoingSnapRequest) {
+        let OutgoingSnapRequest { mut request, response } = request;
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+
+        let original_request_id = match &mut request {
+            SnapMessage::GetAccountRange(req) => std::mem::replace(&mut req.request_id, request_id),
+            SnapMessage::GetStorageRanges(req) => {
+                std::mem::replace(&mut req.request_id, request_id)
+            }
+            SnapMessage::GetByteCodes(req) => std::mem::replace(&mut req.request_id, request_id),
+            SnapMessage::GetTrieNodes(req) => std::mem::replace(&mut req.request_id, request_id),
+            _ => {
+                self.inflight_count.fetch_sub(1, Ordering::Relaxed);
+                response.send_err(RequestError::BadResponse);
+                return
+            }
+        };
+
+        if !self.send(request) {
+            self.inflight_count.fetch_sub(1, Ordering::Relaxed);
+            response.send_err(RequestError::ConnectionDropped);
+            return
+        }
+
+        self.inflight.insert(
+            request_id,
+            InflightSnapRequest {
+                request_id: original_request_id,
+                response,
+                deadline: Instant::now() + SNAP_REQUEST_TIMEOUT,
+            },
+        );
+    }
+
+    /// Fails all requests that did not receive a response in time.
+    fn on_timeout_tick(&mut self) {
+        let now = Instant::now();
+        let timed_out = self
+            .inflight
+            .iter()
+            .filter(|(_, request)| request.deadline <= now)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in timed_out {
+            if let Some(request) = self.inflight.remove(&id) {
+                self.inflight_count.fetch_sub(1, Ordering::Relaxed);
+                request.response.send_err(RequestError::Timeout);
+            }
+        }
+    }
+
+    /// Queues a message for sending to the peer.
+    ///
+    /// Returns `false` if the session is closed.
+    fn send(&self, msg: SnapMessage) -> bool {
+        let mut buf = BytesMut::with_capacity(msg.length());
+        msg.encode(&mut buf);
+        self.sender.send(buf.freeze()).is_ok()
+    }
+}
+
+/// Front-end API for fetching state from peers over the `snap` protocol.
+///
+/// Requests are sent to the connected `snap` peer with the fewest outstanding requests. The
+/// [`Priority`] of a request is ignored.
+#[derive(Debug, Clone)]
+pub struct SnapFetchClient {
+    /// All sessions that share the `snap` protocol.
+    sessions: SnapSessions,
+    /// Used for reporting peers.
+    peers: PeersHandle,
+}
+
+// === impl SnapFetchClient ===
+
+impl SnapFetchClient {
+    /// Sends the request to the least busy `snap` peer.
+    ///
+    /// The request fails with [`RequestError::ChannelClosed`] if no session can take it.
+    fn send_request(&self, request: SnapMessage, response: PendingSnapResponse) {
+        let sessions = self.sessions.lock();
+        let mut candidates = sessions.values().collect::<Vec<_>>();
+        candidates.sort_by_key(|session| session.inflight.load(Ordering::Relaxed));
+
+        let mut request = OutgoingSnapRequest { request, response };
+        for session in candidates {
+            session.inflight.fetch_add(1, Ordering::Relaxed);
+            match session.to_session.try_send(request) {
+                Ok(()) => return,
+                Err(err) => {
+                    session.inflight.fetch_sub(1, Ordering::Relaxed);
+                    request = err.into_inner();
+                }
+            }
+        }
+        request.response.send_err(RequestError::ChannelClosed);
+    }
+}
+
+impl DownloadClient for SnapFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.peers.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.sessions.lock().len()
+    }
+}
+
+impl SnapClient for SnapFetchClient {
+    type AccountRangeOutput = FlattenedResponse<PeerRequestResult<AccountRange>>;
+    type StorageRangesOutput = FlattenedResponse<PeerRequestResult<StorageRanges>>;
+    type ByteCodesOutput = FlattenedResponse<PeerRequestResult<ByteCodes>>;
+    type TrieNodesOutput = FlattenedResponse<PeerRequestResult<TrieNodes>>;
+
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRange,
+        _priority: Priority,
+    ) -> Self::AccountRangeOutput {
+        let (response, rx) = oneshot::channel();
+        self.send_request(
+            SnapMessage::GetAccountRange(request),
+            PendingSnapResponse::AccountRange(response),
+        );
+        rx.into()
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRanges,
+        _priority: Priority,
+    ) -> Self::StorageRangesOutput {
+        let (response, rx) = oneshot::channel();
+        self.send_request(
+            SnapMessage::GetStorageRanges(request),
+            PendingSnapResponse::StorageRanges(response),
+        );
+        rx.into()
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodes,
+        _priority: Priority,
+    ) -> Self::ByteCodesOutput {
+        let (response, rx) = oneshot::channel();
+        self.send_request(
+            SnapMessage::GetByteCodes(request),
+            PendingSnapResponse::ByteCodes(response),
+        );
+        rx.into()
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodes,
+        _priority: Priority,
+    ) -> Self::TrieNodesOutput {
+        let (response, rx) = oneshot::channel();
+        self.send_request(
+            SnapMessage::GetTrieNodes(request),
+            PendingSnapResponse::TrieNodes(response),
+        );
+        rx.into()
+    }
+}
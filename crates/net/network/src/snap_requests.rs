@@ -0,0 +1,345 @@
+//! State range management for the `snap` protocol.
+//!
+//! Serves the requests of the `snap/1` protocol from the latest hashed state and the state trie
+//! tables. The `snap` capability is negotiated alongside `eth`, and the requests of peers are
+//! delegated to the [`SnapRequestHandler`] by the
+//! [`SnapProtocolHandler`](crate::snap_protocol::SnapProtocolHandler).
+//!
+//! Only the latest state is served. Requests for any other state root, including the roots of
+//! recent blocks, are answered with an empty response. The trie tables only hold the nodes of the
+//! latest state, and serving an older root would require reverting the hashed state and
+//! recomputing the trie for every request. An empty response is how the protocol signals that a
+//! root is not available, syncing peers move on to another peer or to a newer pivot block.
+
+use crate::{metrics::SnapRequestHandlerMetrics, peers::PeersHandle};
+use futures::StreamExt;
+use reth_eth_wire::{
+    AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+    GetTrieNodes, SnapAccount, StorageData, StorageRanges, TrieNodes,
+};
+use reth_interfaces::p2p::error::RequestResult;
+use reth_primitives::{trie::Nibbles, Bytes, PeerId, H256};
+use reth_provider::SnapStateReader;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/v1.12.0/eth/protocols/snap/handler.go#L34-L57>
+
+/// Maximum size of replies to data retrievals.
+///
+/// Requested response sizes are capped to this limit.
+const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Maximum number of bytecode lookups to perform.
+const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie node lookups to perform.
+const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Estimated size in bytes of an account in the slim format, including its hash.
+const APPROX_ACCOUNT_SIZE: usize = 110;
+
+/// Estimated size in bytes of a storage slot, including its hash.
+const APPROX_SLOT_SIZE: usize = 70;
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can read the state.
+    client: C,
+    /// Used for reporting peers.
+    #[allow(unused)]
+    peers: PeersHandle,
+    /// Incoming request from the [NetworkManager](crate::NetworkManager).
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        let metrics = Default::default();
+        Self { client, peers, incoming_requests: ReceiverStream::new(incoming), metrics }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: SnapStateReader,
+{
+    /// Returns the requested range of accounts.
+    ///
+    /// The response is empty if the requested state root is not the latest state root.
+    fn get_account_range_response(&self, request: GetAccountRange) -> AccountRange {
+        let GetAccountRange { request_id, root_hash, starting_hash, limit_hash, response_bytes } =
+            request;
+        let max_accounts = response_limit(response_bytes) / APPROX_ACCOUNT_SIZE;
+
+        let Some(range) = self
+            .client
+            .account_range(root_hash, starting_hash, limit_hash, max_accounts.max(1))
+            .unwrap_or_default()
+        else {
+            return AccountRange { request_id, ..Default::default() }
+        };
+
+        let accounts = range
+            .accounts
+            .into_iter()
+            .map(|(hash, account, storage_root)| AccountData {
+                hash,
+                body: SnapAccount::new(account, storage_root),
+            })
+            .collect();
+        AccountRange { request_id, accounts, proof: range.proof }
+    }
+
+    /// Returns the requested storage ranges.
+    ///
+    /// The response is empty if the requested state root is not the latest state root.
+    fn get_storage_ranges_response(&self, request: GetStorageRanges) -> StorageRanges {
+        let max_slots = response_limit(request.response_bytes) / APPROX_SLOT_SIZE;
+
+        let Some(ranges) = self
+            .client
+            .storage_ranges(
+                request.root_hash,
+                &request.account_hashes,
+                request.starting_hash(),
+                request.limit_hash(),
+                max_slots.max(1),
+            )
+            .unwrap_or_default()
+        else {
+            return StorageRanges { request_id: request.request_id, ..Default::default() }
+        };
+
+        let slots = ranges
+            .slots
+            .into_iter()
+            .map(|slots| {
+                slots
+                    .into_iter()
+                    .map(|(hash, value)| StorageData {
+                        hash,
+                        data: reth_rlp::encode_fixed_size(&value).to_vec().into(),
+                    })
+                    .collect()
+            })
+            .collect();
+        StorageRanges { request_id: request.request_id, slots, proof: ranges.proof }
+    }
+
+    /// Returns the requested bytecodes.
+    fn get_byte_codes_response(&self, request: GetByteCodes) -> ByteCodes {
+        let GetByteCodes { request_id, hashes, response_bytes } = request;
+        let hashes = &hashes[..hashes.len().min(MAX_CODE_LOOKUPS)];
+        let codes =
+            self.client.bytecodes(hashes, response_limit(response_bytes)).unwrap_or_default();
+        ByteCodes { request_id, codes }
+    }
+
+    /// Returns the requested trie nodes.
+    ///
+    /// Stops at the first path set that can't be served, since the response must match the order
+    /// of the requested paths.
+    fn get_trie_nodes_response(&self, request: GetTrieNodes) -> TrieNodes {
+        let GetTrieNodes { request_id, root_hash, paths, response_bytes } = request;
+        let max_bytes = response_limit(response_bytes);
+
+        let mut nodes = Vec::new();
+        let mut total_bytes = 0;
+        let mut lookups = 0;
+        for path_set in paths {
+            if total_bytes >= max_bytes || lookups >= MAX_TRIE_NODE_LOOKUPS {
+                break
+            }
+
+            // a set with storage paths starts with the account hash
+            let requested = if path_set.len() > 1 { path_set.len() - 1 } else { path_set.len() };
+            let Some(served) = self.get_trie_node_set(root_hash, path_set) else { break };
+            lookups += requested;
+            total_bytes += served.iter().map(Bytes::len).sum::<usize>();
+
+            // the set is only partially served if a node is missing
+            let is_complete = served.len() == requested;
+            nodes.extend(served);
+            if !is_complete {
+                break
+            }
+        }
+
+        TrieNodes { request_id, nodes }
+    }
+
+    /// Returns the nodes of a single path set.
+    ///
+    /// A set with a single path refers to the account trie. Otherwise the first element is the
+    /// hash of the account and the remaining paths refer to its storage trie.
+    fn get_trie_node_set(&self, root: H256, path_set: Vec<Bytes>) -> Option<Vec<Bytes>> {
+        let decode_path = |path: &Bytes| Nibbles::decode_path(path).map(|(path, _)| path);
+
+        let (first, storage_paths) = path_set.split_first()?;
+        if storage_paths.is_empty() {
+            let path = decode_path(first)?;
+            return self.client.account_trie_nodes(root, vec![path]).ok().flatten()
+        }
+
+        if first.len() != H256::len_bytes() {
+            return None
+        }
+        let paths = storage_paths.iter().map(decode_path).collect::<Option<Vec<_>>>()?;
+        self.client.storage_trie_nodes(root, H256::from_slice(first), paths).ok().flatten()
+    }
+
+    /// Handles [`GetAccountRange`] queries.
+    fn on_account_range_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetAccountRange,
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    ) {
+        self.metrics.received_account_range_requests.increment(1);
+        trace!(target: "net::snap", ?peer_id, ?request, "received account range request");
+        let _ = response.send(Ok(self.get_account_range_response(request)));
+    }
+
+    /// Handles [`GetStorageRanges`] queries.
+    fn on_storage_ranges_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetStorageRanges,
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    ) {
+        self.metrics.received_storage_ranges_requests.increment(1);
+        trace!(
+            target: "net::snap",
+            ?peer_id,
+            accounts = request.account_hashes.len(),
+            "received storage ranges request"
+        );
+        let _ = response.send(Ok(self.get_storage_ranges_response(request)));
+    }
+
+    /// Handles [`GetByteCodes`] queries.
+    fn on_byte_codes_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetByteCodes,
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    ) {
+        self.metrics.received_byte_codes_requests.increment(1);
+        trace!(
+            target: "net::snap",
+            ?peer_id,
+            codes = request.hashes.len(),
+            "received bytecodes request"
+        );
+        let _ = response.send(Ok(self.get_byte_codes_response(request)));
+    }
+
+    /// Handles [`GetTrieNodes`] queries.
+    fn on_trie_nodes_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetTrieNodes,
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    ) {
+        self.metrics.received_trie_nodes_requests.increment(1);
+        trace!(
+            target: "net::snap",
+            ?peer_id,
+            sets = request.paths.len(),
+            "received trie nodes request"
+        );
+        let _ = response.send(Ok(self.get_trie_nodes_response(request)));
+    }
+}
+
+/// Returns the response size limit for the requested number of bytes.
+fn response_limit(response_bytes: u64) -> usize {
+    usize::try_from(response_bytes).unwrap_or(usize::MAX).min(SOFT_RESPONSE_LIMIT)
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: SnapStateReader + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            match this.incoming_requests.poll_next_unpin(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Ready(Some(incoming)) => match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_byte_codes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// All `snap` requests delegated by the network.
+#[derive(Debug)]
+#[allow(missing_docs)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        peer_id: PeerId,
+        request: GetAccountRange,
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    },
+    /// Request ranges of storage slots from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        peer_id: PeerId,
+        request: GetStorageRanges,
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    },
+    /// Request bytecodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        peer_id: PeerId,
+        request: GetByteCodes,
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        peer_id: PeerId,
+        request: GetTrieNodes,
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    },
+}
@@ -15,8 +15,9 @@ use crate::{
 use reth_eth_wire::{
     capability::Capabilities, BlockHashNumber, DisconnectReason, NewBlockHashes, Status,
 };
+use reth_net_nat::PortMapping;
 use reth_network_api::PeerKind;
-use reth_primitives::{ForkId, PeerId, H256};
+use reth_primitives::{ForkId, Head, PeerId, H256};
 use reth_provider::BlockNumReader;
 use std::{
     collections::{HashMap, VecDeque},
@@ -238,6 +239,16 @@ where
         self.discovery.update_fork_id(fork_id)
     }
 
+    /// Updates the head of the fork filter used by discovery.
+    pub(crate) fn update_discovery_head(&mut self, head: Head) {
+        self.discovery.update_head(head)
+    }
+
+    /// Advertises the external addresses of the port mappings via discovery.
+    pub(crate) fn set_external_port_mappings(&mut self, mappings: &[PortMapping]) {
+        self.discovery.set_external_port_mappings(mappings)
+    }
+
     /// Invoked after a `NewBlock` message was received by the peer.
     ///
     /// This will keep track of blocks we know a peer has
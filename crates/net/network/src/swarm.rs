@@ -219,16 +219,20 @@ where
                     return None
                 }
 
-                match self.sessions.on_incoming(stream, remote_addr) {
+                let trusted = self.state().peers().is_trusted_ip(remote_addr.ip());
+                match self.sessions.on_incoming(stream, remote_addr, trusted) {
                     Ok(session_id) => {
                         trace!(target: "net", ?remote_addr, "Incoming connection");
                         return Some(SwarmEvent::IncomingTcpConnection { session_id, remote_addr })
                     }
                     Err(err) => {
-                        debug!(target: "net", ?err, "Incoming connection rejected, capacity already reached.");
-                        self.state_mut()
-                            .peers_mut()
-                            .on_incoming_pending_session_rejected_internally();
+                        debug!(target: "net", ?err, ?remote_addr, "Incoming connection rejected, session limit reached.");
+                        let peers = self.state_mut().peers_mut();
+                        if err.is_penalized() {
+                            peers.on_incoming_session_limit_exceeded(remote_addr.ip());
+                        } else {
+                            peers.on_incoming_pending_session_rejected_internally();
+                        }
                     }
                 }
             }
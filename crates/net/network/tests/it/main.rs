@@ -1,8 +1,10 @@
 mod clique;
 mod connect;
 mod geth;
+mod protocol;
 mod requests;
 mod session;
+mod snap;
 mod startup;
 
 fn main() {}
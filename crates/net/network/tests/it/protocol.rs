@@ -0,0 +1,91 @@
+//! Tests for additional RLPx sub-protocols
+
+use futures::StreamExt;
+use reth_eth_wire::{
+    capability::{Capability, Protocol},
+    ProtocolStream,
+};
+use reth_network::{
+    protocol::ProtocolHandler, test_utils::NetworkEventStream, Direction, NetworkConfigBuilder,
+    NetworkHandle, NetworkManager,
+};
+use reth_network_api::{NetworkInfo, Peers};
+use reth_primitives::{
+    bytes::{Bytes, BytesMut},
+    PeerId,
+};
+use reth_provider::test_utils::NoopProvider;
+use secp256k1::SecretKey;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use tokio::sync::mpsc;
+
+/// The message a [`PingProtocol`] sends as soon as a session is established.
+const PING: [u8; 2] = [0x00, 0xc0];
+
+/// A sub-protocol that sends a ping right after the session is established and reports all
+/// messages it receives.
+#[derive(Debug)]
+struct PingProtocol {
+    received: mpsc::UnboundedSender<(PeerId, BytesMut)>,
+}
+
+impl ProtocolHandler for PingProtocol {
+    fn protocol(&self) -> Protocol {
+        Protocol::new(Capability::new("ping".into(), 1), 2)
+    }
+
+    fn on_connection(&self, peer_id: PeerId, _direction: Direction, mut stream: ProtocolStream) {
+        stream.send(Bytes::from_static(&PING)).unwrap();
+        let received = self.received.clone();
+        tokio::task::spawn(async move {
+            while let Some(msg) = stream.next().await {
+                let _ = received.send((peer_id, msg));
+            }
+        });
+    }
+}
+
+/// Launches a network with the [`PingProtocol`] installed.
+async fn launch_ping_network() -> (NetworkHandle, mpsc::UnboundedReceiver<(PeerId, BytesMut)>) {
+    let secret_key = SecretKey::new(&mut rand::thread_rng());
+    let config = NetworkConfigBuilder::new(secret_key)
+        .listener_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
+        .discovery_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
+        .disable_dns_discovery()
+        .disable_discv4_discovery()
+        .build(NoopProvider::default());
+    let (received, rx) = mpsc::unbounded_channel();
+    let (handle, network, _, _) = NetworkManager::new(config)
+        .await
+        .unwrap()
+        .into_builder()
+        .add_rlpx_sub_protocol(PingProtocol { received })
+        .split_with_handle();
+
+    tokio::task::spawn(network);
+
+    (handle, rx)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rlpx_sub_protocol_messages_after_hello() {
+    reth_tracing::init_test_tracing();
+
+    let (handle0, mut received0) = launch_ping_network().await;
+    let (handle1, mut received1) = launch_ping_network().await;
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    // both peers send their ping as soon as their side of the session is established, which can
+    // be before the other side completed the status handshake, neither ping may be dropped
+    let (peer_id, msg) = received0.recv().await.unwrap();
+    assert_eq!(peer_id, *handle1.peer_id());
+    assert_eq!(&msg[..], &PING[..]);
+
+    let (peer_id, msg) = received1.recv().await.unwrap();
+    assert_eq!(peer_id, *handle0.peer_id());
+    assert_eq!(&msg[..], &PING[..]);
+}
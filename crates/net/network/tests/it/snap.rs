@@ -0,0 +1,78 @@
+//! Tests for the `snap` sub-protocol
+
+use reth_interfaces::p2p::{
+    download::DownloadClient,
+    error::RequestError,
+    snap::client::{GetAccountRange, GetByteCodes, SnapClient},
+};
+use reth_network::{
+    snap_protocol::SnapFetchClient, test_utils::NetworkEventStream, NetworkConfigBuilder,
+    NetworkHandle, NetworkManager,
+};
+use reth_network_api::{NetworkInfo, Peers};
+use reth_primitives::H256;
+use reth_provider::test_utils::NoopProvider;
+use secp256k1::SecretKey;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+/// Launches a network that serves the `snap` protocol from an empty state.
+async fn launch_snap_network() -> (NetworkHandle, SnapFetchClient) {
+    let secret_key = SecretKey::new(&mut rand::thread_rng());
+    let config = NetworkConfigBuilder::new(secret_key)
+        .listener_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
+        .discovery_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)))
+        .disable_dns_discovery()
+        .disable_discv4_discovery()
+        .build(NoopProvider::default());
+    let (builder, snap, client) = NetworkManager::new(config)
+        .await
+        .unwrap()
+        .into_builder()
+        .snap_request_handler(NoopProvider::default());
+    let (handle, network, _, _) = builder.split_with_handle();
+
+    tokio::task::spawn(network);
+    tokio::task::spawn(snap);
+
+    (handle, client)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snap_requests() {
+    reth_tracing::init_test_tracing();
+
+    let (handle0, client0) = launch_snap_network().await;
+    let (handle1, _client1) = launch_snap_network().await;
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    // without a `snap` peer the request fails immediately
+    let request =
+        GetByteCodes { request_id: 7, hashes: vec![H256::random()], response_bytes: 1024 };
+    let err = client0.get_byte_codes(request.clone()).await.unwrap_err();
+    assert_eq!(err, RequestError::ChannelClosed);
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+    assert_eq!(client0.num_connected_peers(), 1);
+
+    // the request is served by the peer and answered with the original request id
+    let response = client0
+        .get_account_range(GetAccountRange {
+            request_id: 42,
+            root_hash: H256::random(),
+            starting_hash: H256::zero(),
+            limit_hash: H256::repeat_byte(0xff),
+            response_bytes: 512 * 1024,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.peer_id(), *handle1.peer_id());
+    let range = response.into_data();
+    assert_eq!(range.request_id, 42);
+    assert!(range.accounts.is_empty());
+
+    let response = client0.get_byte_codes(request).await.unwrap();
+    assert_eq!(response.peer_id(), *handle1.peer_id());
+    assert_eq!(response.into_data().request_id, 7);
+}
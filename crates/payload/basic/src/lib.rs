@@ -23,8 +23,8 @@ use reth_payload_builder::{
 use reth_primitives::{
     bytes::{Bytes, BytesMut},
     constants::{
-        BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS, EMPTY_WITHDRAWALS,
-        ETHEREUM_BLOCK_GAS_LIMIT, RETH_CLIENT_VERSION, SLOT_DURATION,
+        eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
+        EMPTY_WITHDRAWALS, ETHEREUM_BLOCK_GAS_LIMIT, RETH_CLIENT_VERSION, SLOT_DURATION,
     },
     proofs, Block, BlockNumberOrTag, ChainSpec, Header, IntoRecoveredTransaction, Receipt,
     SealedBlock, Withdrawal, EMPTY_OMMER_ROOT, H256, U256,
@@ -40,7 +40,9 @@ use reth_revm::{
 };
 use reth_rlp::Encodable;
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{
+    select_best_transactions, PoolOrderSelection, TransactionPool, TransactionSelection,
+};
 use revm::{
     db::{CacheDB, DatabaseRef},
     primitives::{BlockEnv, CfgEnv, EVMError, Env, InvalidTransaction, ResultAndState},
@@ -160,6 +162,7 @@ where
             extra_data: self.config.extradata.clone(),
             attributes,
             chain_spec: Arc::clone(&self.chain_spec),
+            transaction_selection: Arc::clone(&self.config.transaction_selection),
         };
 
         let until = tokio::time::Instant::now() + self.config.deadline;
@@ -207,6 +210,8 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// The strategy for selecting the transactions of a payload from the pool.
+    transaction_selection: Arc<dyn TransactionSelection>,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -250,6 +255,14 @@ impl BasicPayloadJobGeneratorConfig {
         self.max_gas_limit = max_gas_limit;
         self
     }
+
+    /// Sets the strategy for selecting the transactions of a payload from the pool.
+    ///
+    /// Defaults to [PoolOrderSelection], which keeps the order of the pool.
+    pub fn transaction_selection(mut self, selection: impl TransactionSelection) -> Self {
+        self.transaction_selection = Arc::new(selection);
+        self
+    }
 }
 
 impl Default for BasicPayloadJobGeneratorConfig {
@@ -263,6 +276,7 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            transaction_selection: Arc::new(PoolOrderSelection::default()),
         }
     }
 }
@@ -542,6 +556,8 @@ struct PayloadConfig {
     attributes: PayloadBuilderAttributes,
     /// The chain spec.
     chain_spec: Arc<ChainSpec>,
+    /// The strategy for selecting the transactions of the payload.
+    transaction_selection: Arc<dyn TransactionSelection>,
 }
 
 /// The possible outcomes of a payload building attempt.
@@ -642,6 +658,7 @@ where
         extra_data,
         attributes,
         chain_spec,
+        transaction_selection,
     } = config;
 
     debug!(parent_hash=?parent_block.hash, parent_number=parent_block.number, "building new payload");
@@ -651,11 +668,17 @@ where
     let mut post_state = PostState::default();
 
     let mut cumulative_gas_used = 0;
+    let mut sum_blob_gas_used = 0;
     let block_gas_limit: u64 = initialized_block_env.gas_limit.try_into().unwrap_or(u64::MAX);
     let base_fee = initialized_block_env.basefee.to::<u64>();
 
     let mut executed_txs = Vec::new();
-    let mut best_txs = pool.best_transactions_with_base_fee(base_fee);
+    let mut sidecars = Vec::new();
+    let mut best_txs = select_best_transactions(
+        transaction_selection,
+        &pool,
+        pool.best_transactions_with_base_fee(base_fee),
+    );
 
     let mut total_fees = U256::ZERO;
 
@@ -679,6 +702,33 @@ where
         // convert tx to a signed transaction
         let tx = pool_tx.to_recovered_transaction();
 
+        // There's only limited amount of blob space available per block, so we need to check if
+        // the EIP-4844 transaction can still fit in the block
+        let blob_gas_used = tx.blob_gas_used();
+        if let Some(blob_gas_used) = blob_gas_used {
+            if sum_blob_gas_used + blob_gas_used > MAX_DATA_GAS_PER_BLOCK {
+                // we can't fit this _blob_ transaction into the block, so we mark it as invalid,
+                // which removes its dependent transactions from the iterator. This is similar to
+                // the gas limit condition for regular transactions above.
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+        }
+
+        // a blob transaction can only be included together with its sidecar
+        let sidecar = if blob_gas_used.is_some() {
+            match pool.get_blob(tx.hash()) {
+                Ok(Some(sidecar)) => Some(sidecar),
+                res => {
+                    trace!(?res, ?tx, "skipping blob transaction without sidecar");
+                    best_txs.mark_invalid(&pool_tx);
+                    continue
+                }
+            }
+        } else {
+            None
+        };
+
         // Configure the environment for the block.
         let env = Env {
             cfg: initialized_cfg.clone(),
@@ -721,6 +771,15 @@ where
         // add gas used by the transaction to cumulative gas used, before creating the receipt
         cumulative_gas_used += gas_used;
 
+        // count the transaction towards the per-sender cap of the selection
+        best_txs.mark_included(&pool_tx);
+
+        // keep track of the included blob transactions, their sidecars and their blob gas
+        if let Some(blob_gas_used) = blob_gas_used {
+            sum_blob_gas_used += blob_gas_used;
+        }
+        sidecars.extend(sidecar);
+
         // Push transaction changeset and calculate header bloom filter for receipt.
         post_state.add_receipt(
             block_number,
@@ -765,6 +824,14 @@ where
     // create the block header
     let transactions_root = proofs::calculate_transaction_root(&executed_txs);
 
+    let mut blob_gas_used = None;
+    let mut excess_blob_gas = None;
+    if chain_spec.is_cancun_activated_at_timestamp(attributes.timestamp) {
+        blob_gas_used = Some(sum_blob_gas_used);
+        // pre-cancun parents don't have any excess blob gas
+        excess_blob_gas = Some(parent_block.next_block_excess_blob_gas().unwrap_or_default());
+    }
+
     let header = Header {
         parent_hash: parent_block.hash,
         ommers_hash: EMPTY_OMMER_ROOT,
@@ -783,18 +850,18 @@ where
         difficulty: U256::ZERO,
         gas_used: cumulative_gas_used,
         extra_data: extra_data.into(),
-        blob_gas_used: None,
-        excess_blob_gas: None,
+        blob_gas_used,
+        excess_blob_gas,
     };
 
     // seal the block
     let block = Block { header, body: executed_txs, ommers: vec![], withdrawals };
 
     let sealed_block = block.seal_slow();
-    Ok(BuildOutcome::Better {
-        payload: BuiltPayload::new(attributes.id, sealed_block, total_fees),
-        cached_reads,
-    })
+    let mut payload = BuiltPayload::new(attributes.id, sealed_block, total_fees);
+    payload.extend_sidecars(sidecars);
+
+    Ok(BuildOutcome::Better { payload, cached_reads })
 }
 
 /// Builds an empty payload without any transactions.
@@ -836,6 +903,13 @@ where
     // calculate the state root
     let state_root = db.db.0.state_root(post_state)?;
 
+    let mut blob_gas_used = None;
+    let mut excess_blob_gas = None;
+    if chain_spec.is_cancun_activated_at_timestamp(attributes.timestamp) {
+        blob_gas_used = Some(0);
+        excess_blob_gas = Some(parent_block.next_block_excess_blob_gas().unwrap_or_default());
+    }
+
     let header = Header {
         parent_hash: parent_block.hash,
         ommers_hash: EMPTY_OMMER_ROOT,
@@ -853,8 +927,8 @@ where
         gas_limit: block_gas_limit,
         difficulty: U256::ZERO,
         gas_used: 0,
-        blob_gas_used: None,
-        excess_blob_gas: None,
+        blob_gas_used,
+        excess_blob_gas,
         extra_data: extra_data.into(),
     };
 
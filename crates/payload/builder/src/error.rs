@@ -22,6 +22,19 @@ pub enum PayloadBuilderError {
     /// Thrown if the payload requests withdrawals before Shanghai activation.
     #[error("withdrawals set before Shanghai activation")]
     WithdrawalsBeforeShanghai,
+    /// Any other payload building errors.
+    #[error(transparent)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl PayloadBuilderError {
+    /// Create a new error from a boxed error.
+    pub fn other<E>(error: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        PayloadBuilderError::Other(Box::new(error))
+    }
 }
 
 impl From<oneshot::error::RecvError> for PayloadBuilderError {
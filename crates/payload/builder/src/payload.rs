@@ -1,10 +1,12 @@
 //! Contains types required for building a payload.
 
-use reth_primitives::{Address, ChainSpec, Header, SealedBlock, Withdrawal, H256, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, ChainSpec, Header, SealedBlock, Withdrawal, H256, U256,
+};
 use reth_revm_primitives::config::revm_spec_by_timestamp_after_merge;
 use reth_rlp::Encodable;
 use reth_rpc_types::engine::{
-    ExecutionPayload, ExecutionPayloadEnvelope, PayloadAttributes, PayloadId,
+    BlobsBundleV1, ExecutionPayload, ExecutionPayloadEnvelope, PayloadAttributes, PayloadId,
 };
 use revm_primitives::{BlockEnv, CfgEnv};
 
@@ -21,6 +23,9 @@ pub struct BuiltPayload {
     pub(crate) block: SealedBlock,
     /// The fees of the block
     pub(crate) fees: U256,
+    /// The blobs, proofs, and commitments in the block. If the block is pre-cancun, this will be
+    /// empty.
+    pub(crate) sidecars: Vec<BlobTransactionSidecar>,
 }
 
 // === impl BuiltPayload ===
@@ -28,7 +33,7 @@ pub struct BuiltPayload {
 impl BuiltPayload {
     /// Initializes the payload with the given initial block.
     pub fn new(id: PayloadId, block: SealedBlock, fees: U256) -> Self {
-        Self { id, block, fees }
+        Self { id, block, fees, sidecars: Vec::new() }
     }
 
     /// Returns the identifier of the payload.
@@ -46,6 +51,16 @@ impl BuiltPayload {
         self.fees
     }
 
+    /// Returns the blob sidecars of the blob transactions included in the block.
+    pub fn sidecars(&self) -> &[BlobTransactionSidecar] {
+        &self.sidecars
+    }
+
+    /// Adds sidecars to the payload.
+    pub fn extend_sidecars(&mut self, sidecars: Vec<BlobTransactionSidecar>) {
+        self.sidecars.extend(sidecars)
+    }
+
     /// Converts the type into the response expected by `engine_getPayloadV1`
     pub fn into_v1_payload(self) -> ExecutionPayload {
         self.into()
@@ -53,6 +68,12 @@ impl BuiltPayload {
 
     /// Converts the type into the response expected by `engine_getPayloadV2`
     pub fn into_v2_payload(self) -> ExecutionPayloadEnvelope {
+        let BuiltPayload { block, fees, .. } = self;
+        ExecutionPayloadEnvelope { block_value: fees, payload: block.into(), blobs_bundle: None }
+    }
+
+    /// Converts the type into the response expected by `engine_getPayloadV3`
+    pub fn into_v3_payload(self) -> ExecutionPayloadEnvelope {
         self.into()
     }
 }
@@ -64,12 +85,16 @@ impl From<BuiltPayload> for ExecutionPayload {
     }
 }
 
-// V2 engine_getPayloadV2 response
+// V3 engine_getPayloadV3 response
 impl From<BuiltPayload> for ExecutionPayloadEnvelope {
     fn from(value: BuiltPayload) -> Self {
-        let BuiltPayload { block, fees, .. } = value;
+        let BuiltPayload { block, fees, sidecars, .. } = value;
 
-        ExecutionPayloadEnvelope { block_value: fees, payload: block.into() }
+        ExecutionPayloadEnvelope {
+            block_value: fees,
+            payload: block.into(),
+            blobs_bundle: Some(sidecars.into()),
+        }
     }
 }
 
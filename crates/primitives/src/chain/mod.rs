@@ -1,5 +1,5 @@
 use crate::{
-    net::{goerli_nodes, mainnet_nodes, sepolia_nodes},
+    net::{goerli_nodes, holesky_nodes, mainnet_nodes, sepolia_nodes},
     NodeRecord, U256, U64,
 };
 use reth_codecs::add_arbitrary_tests;
@@ -11,9 +11,13 @@ use std::{fmt, str::FromStr};
 mod spec;
 pub use spec::{
     AllGenesisFormats, BaseFeeParams, ChainSpec, ChainSpecBuilder, DisplayHardforks, ForkCondition,
-    ForkTimestamps, DEV, GOERLI, MAINNET, SEPOLIA,
+    ForkTimestamps, DEV, GOERLI, HOLESKY, MAINNET, SEPOLIA,
 };
 
+// The named chain registry module.
+mod registry;
+pub use registry::{named_chain_spec, register_named_chain, registered_chain_name, BUILTIN_CHAINS};
+
 // The chain info module.
 mod info;
 pub use info::ChainInfo;
@@ -44,6 +48,11 @@ impl Chain {
         Chain::Named(ethers_core::types::Chain::Sepolia)
     }
 
+    /// Returns the holesky chain.
+    pub const fn holesky() -> Self {
+        Chain::Id(17000)
+    }
+
     /// Returns the dev chain.
     pub const fn dev() -> Self {
         Chain::Named(ethers_core::types::Chain::Dev)
@@ -73,6 +82,10 @@ impl Chain {
         use ethers_core::types::Chain::*;
         const DNS_PREFIX: &str = "enrtree://AKA3AM6LPBYEUDMVNU3BSVQJ5AD45Y7YPOHJLEF6W26QOE4VTUDPE@";
 
+        if self == Chain::holesky() {
+            return Some(format!("{DNS_PREFIX}all.holesky.ethdisco.net"))
+        }
+
         let named: ethers_core::types::Chain = self.try_into().ok()?;
 
         if matches!(named, Mainnet | Goerli | Sepolia | Ropsten | Rinkeby) {
@@ -84,6 +97,10 @@ impl Chain {
     /// Returns bootnodes for the given chain.
     pub fn bootnodes(self) -> Option<Vec<NodeRecord>> {
         use ethers_core::types::Chain::*;
+        if self == Chain::holesky() {
+            return Some(holesky_nodes())
+        }
+
         match self.try_into().ok()? {
             Mainnet => Some(mainnet_nodes()),
             Goerli => Some(goerli_nodes()),
@@ -100,6 +117,10 @@ impl fmt::Display for Chain {
             Chain::Id(id) => {
                 if let Ok(chain) = ethers_core::types::Chain::try_from(*id) {
                     chain.fmt(f)
+                } else if *self == Chain::holesky() {
+                    f.write_str("holesky")
+                } else if let Some(name) = registered_chain_name(*self) {
+                    f.write_str(&name)
                 } else {
                     id.fmt(f)
                 }
@@ -164,6 +185,8 @@ impl FromStr for Chain {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(chain) = ethers_core::types::Chain::from_str(s) {
             Ok(Chain::Named(chain))
+        } else if let Some(spec) = named_chain_spec(s) {
+            Ok(spec.chain)
         } else {
             s.parse::<u64>()
                 .map(Chain::Id)
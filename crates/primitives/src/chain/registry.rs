@@ -0,0 +1,87 @@
+use crate::{Chain, ChainSpec, DEV, GOERLI, HOLESKY, MAINNET, SEPOLIA};
+use once_cell::sync::Lazy;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+/// Names of the chains that ship with a built-in [ChainSpec].
+pub const BUILTIN_CHAINS: [&str; 5] = ["mainnet", "goerli", "sepolia", "holesky", "dev"];
+
+/// Chains registered at runtime via [register_named_chain].
+static NAMED_CHAINS: Lazy<RwLock<BTreeMap<String, Arc<ChainSpec>>>> = Lazy::new(Default::default);
+
+/// Registers the [ChainSpec] under the given name, so that it can be looked up with
+/// [named_chain_spec].
+///
+/// This allows new networks to be selected by name without adding a built-in spec. A spec that
+/// was registered under the same name before is replaced and returned.
+///
+/// Returns an error if the name is taken by one of the [BUILTIN_CHAINS].
+pub fn register_named_chain(
+    name: impl Into<String>,
+    spec: Arc<ChainSpec>,
+) -> Result<Option<Arc<ChainSpec>>, String> {
+    let name = name.into().to_lowercase();
+    if BUILTIN_CHAINS.contains(&name.as_str()) {
+        return Err(format!("Chain name {name} is reserved for a built-in chain"))
+    }
+    Ok(NAMED_CHAINS.write().unwrap_or_else(PoisonError::into_inner).insert(name, spec))
+}
+
+/// Returns the [ChainSpec] of a built-in or registered chain by name.
+pub fn named_chain_spec(name: &str) -> Option<Arc<ChainSpec>> {
+    let name = name.to_lowercase();
+    let spec = match name.as_str() {
+        "mainnet" => MAINNET.clone(),
+        "goerli" => GOERLI.clone(),
+        "sepolia" => SEPOLIA.clone(),
+        "holesky" => HOLESKY.clone(),
+        "dev" => DEV.clone(),
+        _ => return NAMED_CHAINS.read().unwrap_or_else(PoisonError::into_inner).get(&name).cloned(),
+    };
+    Some(spec)
+}
+
+/// Returns the name a chain was registered under via [register_named_chain].
+///
+/// If the chain was registered under multiple names, the first one in alphabetical order is
+/// returned.
+pub fn registered_chain_name(chain: Chain) -> Option<String> {
+    NAMED_CHAINS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|(_, spec)| spec.chain == chain)
+        .map(|(name, _)| name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChainSpecBuilder;
+
+    #[test]
+    fn register_chain() {
+        let spec = Arc::new(
+            ChainSpecBuilder::mainnet().chain(Chain::Id(1_337_001)).london_activated().build(),
+        );
+
+        assert!(named_chain_spec("devnet-1").is_none());
+        assert!(register_named_chain("Devnet-1", spec.clone()).unwrap().is_none());
+
+        assert!(Arc::ptr_eq(&named_chain_spec("devnet-1").unwrap(), &spec));
+        assert_eq!(registered_chain_name(Chain::Id(1_337_001)).as_deref(), Some("devnet-1"));
+        assert_eq!(Chain::Id(1_337_001).to_string(), "devnet-1");
+        assert_eq!("devnet-1".parse::<Chain>(), Ok(Chain::Id(1_337_001)));
+    }
+
+    #[test]
+    fn builtin_chains_are_reserved() {
+        assert!(register_named_chain("holesky", MAINNET.clone()).is_err());
+        for name in BUILTIN_CHAINS {
+            assert!(named_chain_spec(name).is_some());
+        }
+        assert_eq!(named_chain_spec("holesky").unwrap().chain, Chain::holesky());
+    }
+}
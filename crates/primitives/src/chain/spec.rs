@@ -154,6 +154,46 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
     .into()
 });
 
+/// The Holesky spec
+pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
+    ChainSpec {
+        chain: Chain::holesky(),
+        genesis: serde_json::from_str(include_str!("../../res/genesis/holesky.json"))
+            .expect("Can't deserialize Holesky genesis json"),
+        genesis_hash: Some(H256(hex!(
+            "b5f7f912443c940f21fd611f12828d75b534364ed9e95ca4e307729a4661bde4"
+        ))),
+        paris_block_and_final_difficulty: Some((0, U256::from(1))),
+        fork_timestamps: ForkTimestamps::default().shanghai(1696000704),
+        hardforks: BTreeMap::from([
+            (Hardfork::Frontier, ForkCondition::Block(0)),
+            (Hardfork::Homestead, ForkCondition::Block(0)),
+            (Hardfork::Dao, ForkCondition::Block(0)),
+            (Hardfork::Tangerine, ForkCondition::Block(0)),
+            (Hardfork::SpuriousDragon, ForkCondition::Block(0)),
+            (Hardfork::Byzantium, ForkCondition::Block(0)),
+            (Hardfork::Constantinople, ForkCondition::Block(0)),
+            (Hardfork::Petersburg, ForkCondition::Block(0)),
+            (Hardfork::Istanbul, ForkCondition::Block(0)),
+            (Hardfork::Berlin, ForkCondition::Block(0)),
+            (Hardfork::London, ForkCondition::Block(0)),
+            (
+                Hardfork::Paris,
+                ForkCondition::TTD { fork_block: Some(0), total_difficulty: U256::from(0) },
+            ),
+            (Hardfork::Shanghai, ForkCondition::Timestamp(1696000704)),
+        ]),
+        // the deposit contract is part of the genesis allocation
+        deposit_contract: Some(DepositContract::new(
+            H160(hex!("4242424242424242424242424242424242424242")),
+            0,
+            H256(hex!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5")),
+        )),
+        ..Default::default()
+    }
+    .into()
+});
+
 /// Dev testnet specification
 ///
 /// Includes 20 prefunded accounts with 10_000 ETH each derived from mnemonic "test test test test
@@ -1039,8 +1079,8 @@ impl DepositContract {
 mod tests {
     use crate::{
         Address, AllGenesisFormats, Chain, ChainSpec, ChainSpecBuilder, DisplayHardforks,
-        ForkCondition, ForkHash, ForkId, Genesis, Hardfork, Head, DEV, GOERLI, H256, MAINNET,
-        SEPOLIA, U256,
+        ForkCondition, ForkHash, ForkId, Genesis, Hardfork, Head, DEV, GOERLI, H256, HOLESKY,
+        HOLESKY_GENESIS, MAINNET, SEPOLIA, U256,
     };
     use bytes::BytesMut;
     use ethers_core::types as EtherType;
@@ -1340,6 +1380,37 @@ Post-merge hard forks (timestamp based):
         );
     }
 
+    #[test]
+    fn holesky_forkids() {
+        test_fork_ids(
+            &HOLESKY,
+            &[
+                (
+                    Head { number: 0, ..Default::default() },
+                    ForkId { hash: ForkHash([0xc6, 0x1a, 0x60, 0x98]), next: 1696000704 },
+                ),
+                (
+                    Head { number: 123, timestamp: 1696000703, ..Default::default() },
+                    ForkId { hash: ForkHash([0xc6, 0x1a, 0x60, 0x98]), next: 1696000704 },
+                ),
+                (
+                    Head { number: 123, timestamp: 1696000704, ..Default::default() },
+                    ForkId { hash: ForkHash([0xfd, 0x4f, 0x01, 0x6b]), next: 0 },
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn holesky_genesis() {
+        let header = HOLESKY.genesis_header();
+        let expected_state_root: H256 =
+            hex_literal::hex!("69d8c9d72f6fa4ad42d4702b433707212f90db395eb54dc20bc85de253788783")
+                .into();
+        assert_eq!(header.state_root, expected_state_root);
+        assert_eq!(header.hash_slow(), HOLESKY_GENESIS);
+    }
+
     #[test]
     fn dev_forkids() {
         test_fork_ids(
@@ -28,6 +28,9 @@ pub const TARGET_BLOBS_PER_BLOCK: u64 = TARGET_DATA_GAS_PER_BLOCK / DATA_GAS_PER
 /// Used to determine the price for next data blob
 pub const BLOB_GASPRICE_UPDATE_FRACTION: u64 = 3_338_477u64; // 3338477
 
+/// Minimum gas price for a data blob
+pub const BLOB_TX_MIN_BLOB_GASPRICE: u64 = 1u64;
+
 /// KZG Trusted setup raw
 const TRUSTED_SETUP_RAW: &str = include_str!("../../res/eip4844/trusted_setup.txt");
 
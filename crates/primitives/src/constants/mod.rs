@@ -84,6 +84,10 @@ pub const GOERLI_GENESIS: H256 =
 pub const SEPOLIA_GENESIS: H256 =
     H256(hex!("25a5cc106eea7138acab33231d7160d69cb777ee0c2c553fcddf5138993e6dd9"));
 
+/// Holesky genesis hash.
+pub const HOLESKY_GENESIS: H256 =
+    H256(hex!("b5f7f912443c940f21fd611f12828d75b534364ed9e95ca4e307729a4661bde4"));
+
 /// Testnet genesis hash.
 pub const DEV_GENESIS: H256 =
     H256(hex!("2f980576711e3617a5e4d83dd539548ec0f7792007d505a3d2e9674833af2d7c"));
@@ -1,8 +1,11 @@
 //! Helpers for working with EIP-4844 blob fee
 use crate::{
-    constants::eip4844::{TARGET_DATA_GAS_PER_BLOCK, VERSIONED_HASH_VERSION_KZG},
+    constants::eip4844::{
+        BLOB_GASPRICE_UPDATE_FRACTION, BLOB_TX_MIN_BLOB_GASPRICE, TARGET_DATA_GAS_PER_BLOCK,
+        VERSIONED_HASH_VERSION_KZG,
+    },
     kzg::KzgCommitment,
-    H256,
+    H256, U256,
 };
 use sha2::{Digest, Sha256};
 
@@ -23,3 +26,93 @@ pub fn calculate_excess_blob_gas(parent_excess_blob_gas: u64, parent_blob_gas_us
     let excess_blob_gas = parent_excess_blob_gas + parent_blob_gas_used;
     excess_blob_gas.saturating_sub(TARGET_DATA_GAS_PER_BLOCK)
 }
+
+/// Calculates the blob gas price from the header's excess blob gas field.
+///
+/// Specified in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+pub fn calculate_blob_gas_price(excess_blob_gas: u64) -> u64 {
+    fake_exponential(BLOB_TX_MIN_BLOB_GASPRICE, excess_blob_gas, BLOB_GASPRICE_UPDATE_FRACTION)
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
+///
+/// This is used to calculate the blob price.
+///
+/// The result saturates at `u64::MAX` if it does not fit into a `u64`.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers).
+///
+/// # Panics
+///
+/// This function panics if `denominator` is zero.
+pub fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> u64 {
+    assert_ne!(denominator, 0, "attempt to divide by zero");
+    let factor = factor as u128;
+    let numerator = numerator as u128;
+    let denominator = denominator as u128;
+
+    let mut i = 1;
+    let mut output: u128 = 0;
+    let mut numerator_accum = factor * denominator;
+    while numerator_accum > 0 {
+        // If the accumulated output exceeds `u128::MAX`, `output / denominator` exceeds
+        // `u64::MAX`.
+        let Some(sum) = output.checked_add(numerator_accum) else { return u64::MAX };
+        output = sum;
+
+        // The product of two u128 values fits into a U256. If the next term does not fit into a
+        // u128, neither does the output.
+        // Denominator is asserted as not zero at the start of the function.
+        let next =
+            U256::from(numerator_accum) * U256::from(numerator) / U256::from(denominator * i);
+        let Ok(next) = u128::try_from(next) else { return u64::MAX };
+        numerator_accum = next;
+        i += 1;
+    }
+    u64::try_from(output / denominator).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L78
+    #[test]
+    fn fake_exp() {
+        for &(factor, numerator, denominator, expected) in &[
+            (1u64, 0u64, 1u64, 1u64),
+            (38493, 0, 1000, 38493),
+            (0, 1234, 2345, 0),
+            (1, 2, 1, 6), // approximate 7.389
+            (1, 4, 2, 6),
+            (1, 3, 1, 16), // approximate 20.09
+            (1, 6, 2, 18),
+            (1, 4, 1, 49), // approximate 54.60
+            (1, 8, 2, 50),
+            (10, 8, 2, 542), // approximate 540.598
+            (11, 8, 2, 596), // approximate 600.58
+            (1, 5, 1, 136),  // approximate 148.4
+            (1, 5, 2, 11),   // approximate 12.18
+            (2, 5, 2, 23),   // approximate 24.36
+            (1, 50000000, 2225652, 5709098764),
+        ] {
+            let actual = fake_exponential(factor, numerator, denominator);
+            assert_eq!(actual, expected, "test: {factor}, {numerator}, {denominator}");
+        }
+    }
+
+    #[test]
+    fn fake_exp_saturates() {
+        // the largest excess blob gas whose price still fits into a u64
+        assert_eq!(calculate_blob_gas_price(148_099_578), 18_446_739_238_971_471_609);
+        assert_eq!(calculate_blob_gas_price(148_099_579), u64::MAX);
+        assert_eq!(calculate_blob_gas_price(u64::MAX), u64::MAX);
+        assert_eq!(fake_exponential(u64::MAX, u64::MAX, 1), u64::MAX);
+        assert_eq!(fake_exponential(u64::MAX, u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn min_blob_gas_price() {
+        assert_eq!(calculate_blob_gas_price(0), BLOB_TX_MIN_BLOB_GASPRICE);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     basefee::calculate_next_block_base_fee,
-    eip4844::calculate_excess_blob_gas,
+    eip4844::{calculate_blob_gas_price, calculate_excess_blob_gas},
     keccak256,
     proofs::{EMPTY_LIST_HASH, EMPTY_ROOT},
     BaseFeeParams, BlockBodyRoots, BlockHash, BlockNumHash, BlockNumber, Bloom, Bytes, H160, H256,
@@ -193,6 +193,13 @@ impl Header {
         Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 
+    /// Calculate the blob fee for the next block according to the EIP-4844 spec.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn next_block_blob_fee(&self) -> Option<u64> {
+        Some(calculate_blob_gas_price(self.next_block_excess_blob_gas()?))
+    }
+
     /// Seal the header with a known hash.
     ///
     /// WARNING: This method does not perform validation whether the hash is correct.
@@ -60,15 +60,17 @@ pub use block::{
 };
 pub use bloom::Bloom;
 pub use chain::{
-    AllGenesisFormats, BaseFeeParams, Chain, ChainInfo, ChainSpec, ChainSpecBuilder,
-    DisplayHardforks, ForkCondition, ForkTimestamps, DEV, GOERLI, MAINNET, SEPOLIA,
+    named_chain_spec, register_named_chain, registered_chain_name, AllGenesisFormats,
+    BaseFeeParams, Chain, ChainInfo, ChainSpec, ChainSpecBuilder, DisplayHardforks, ForkCondition,
+    ForkTimestamps, BUILTIN_CHAINS, DEV, GOERLI, HOLESKY, MAINNET, SEPOLIA,
 };
 pub use compression::*;
 pub use constants::{
-    DEV_GENESIS, EMPTY_OMMER_ROOT, GOERLI_GENESIS, KECCAK_EMPTY, MAINNET_GENESIS, SEPOLIA_GENESIS,
+    DEV_GENESIS, EMPTY_OMMER_ROOT, GOERLI_GENESIS, HOLESKY_GENESIS, KECCAK_EMPTY, MAINNET_GENESIS,
+    SEPOLIA_GENESIS,
 };
-pub use eip4844::{calculate_excess_blob_gas, kzg_to_versioned_hash};
-pub use forkid::{ForkFilter, ForkHash, ForkId, ForkTransition, ValidationError};
+pub use eip4844::{calculate_blob_gas_price, calculate_excess_blob_gas, kzg_to_versioned_hash};
+pub use forkid::{EnrForkIdEntry, ForkFilter, ForkHash, ForkId, ForkTransition, ValidationError};
 pub use genesis::{Genesis, GenesisAccount};
 pub use hardfork::Hardfork;
 pub use header::{Head, Header, HeadersDirection, SealedHeader};
@@ -76,8 +78,8 @@ pub use hex_bytes::Bytes;
 pub use integer_list::IntegerList;
 pub use log::Log;
 pub use net::{
-    goerli_nodes, mainnet_nodes, sepolia_nodes, NodeRecord, GOERLI_BOOTNODES, MAINNET_BOOTNODES,
-    SEPOLIA_BOOTNODES,
+    goerli_nodes, holesky_nodes, mainnet_nodes, sepolia_nodes, NodeRecord, GOERLI_BOOTNODES,
+    HOLESKY_BOOTNODES, MAINNET_BOOTNODES, SEPOLIA_BOOTNODES,
 };
 pub use peer::{PeerId, WithPeerId};
 pub use prune::{
@@ -91,11 +93,11 @@ pub use storage::StorageEntry;
 pub use transaction::{
     util::secp256k1::{public_key_to_address, recover_signer, sign_message},
     AccessList, AccessListItem, AccessListWithGasUsed, BlobTransaction, BlobTransactionSidecar,
-    FromRecoveredTransaction, IntoRecoveredTransaction, InvalidTransactionError,
-    PooledTransactionsElement, Signature, Transaction, TransactionKind, TransactionMeta,
-    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxEip1559, TxEip2930,
-    TxEip4844, TxLegacy, TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
-    LEGACY_TX_TYPE_ID,
+    BlobTransactionValidationError, FromRecoveredTransaction, IntoRecoveredTransaction,
+    InvalidTransactionError, PooledTransactionsElement, Signature, Transaction, TransactionKind,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
+    TxEip1559, TxEip2930, TxEip4844, TxLegacy, TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID,
+    EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
 };
 pub use withdrawal::Withdrawal;
 
@@ -134,6 +134,11 @@ pub static SEPOLIA_BOOTNODES : [&str; 5] = [
 	"enode://9e9492e2e8836114cc75f5b929784f4f46c324ad01daf87d956f98b3b6c5fcba95524d6e5cf9861dc96a2c8a171ea7105bb554a197455058de185fa870970c7c@138.68.123.152:30303", // sepolia-bootnode-1-ams3
 ];
 
+/// Ethereum Foundation HOLESKY BOOTNODES
+pub static HOLESKY_BOOTNODES : [&str; 1] = [
+    "enode://ac906289e4b7f12df423d654c5a962b6ebe5b3a74cc9e06292a85221f9a64a6f1cfdd6b714ed6dacef51578f92b34c60ee91e9ede9c7f8fadc4d347326d95e2b@146.190.13.128:30303",
+];
+
 /// GOERLI bootnodes
 pub static GOERLI_BOOTNODES : [&str; 7] = [
     // Upstream bootnodes
@@ -165,6 +170,11 @@ pub fn sepolia_nodes() -> Vec<NodeRecord> {
     parse_nodes(&SEPOLIA_BOOTNODES[..])
 }
 
+/// Returns parsed holesky nodes
+pub fn holesky_nodes() -> Vec<NodeRecord> {
+    parse_nodes(&HOLESKY_BOOTNODES[..])
+}
+
 /// Parses all the nodes
 fn parse_nodes(nodes: impl IntoIterator<Item = impl AsRef<str>>) -> Vec<NodeRecord> {
     nodes.into_iter().map(|s| s.as_ref().parse().unwrap()).collect()
@@ -18,6 +18,12 @@ pub enum PrunePart {
     AccountHistory,
     /// Prune part responsible for the `StorageChangeSet` and `StorageHistory` tables.
     StorageHistory,
+    /// Prune part responsible for the `LogAddressHistory` and `LogTopicHistory` tables.
+    LogHistory,
+    /// Prune part responsible for the `Transactions`, `BlockBodyIndices`, `BlockOmmers` and
+    /// `BlockWithdrawals` tables, i.e. the block bodies, together with the `TransactionBlock`,
+    /// `TxHashNumber`, `TxSenders` and `Receipts` entries of their transactions.
+    Bodies,
 }
 
 /// PrunePart error type.
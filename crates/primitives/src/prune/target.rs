@@ -42,6 +42,22 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<64, _>"
     )]
     pub storage_history: Option<PruneMode>,
+    /// Log History pruning configuration. Only has an effect if the log index is enabled.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<64, _>"
+    )]
+    pub log_history: Option<PruneMode>,
+    /// Block Bodies pruning configuration, also known as history expiry (EIP-4444). Headers are
+    /// kept, but transactions, receipts, ommers and withdrawals of pruned blocks are removed.
+    ///
+    /// Leaves at least [MINIMUM_PRUNING_DISTANCE] blocks, because the blockchain tree needs them
+    /// to handle reorgs.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<128, _>"
+    )]
+    pub bodies: Option<PruneMode>,
     /// Retains only those receipts that contain logs emitted by the specified addresses,
     /// discarding all others. Note that this setting is overridden by `PruneModes::receipts`.
     ///
@@ -108,6 +124,8 @@ impl PruneModes {
         (transaction_lookup, TransactionLookup, None),
         (receipts, Receipts, Some(64)),
         (account_history, AccountHistory, Some(64)),
-        (storage_history, StorageHistory, Some(64))
+        (storage_history, StorageHistory, Some(64)),
+        (log_history, LogHistory, Some(64)),
+        (bodies, Bodies, Some(MINIMUM_PRUNING_DISTANCE))
     );
 }
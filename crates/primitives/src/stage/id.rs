@@ -16,12 +16,17 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    IndexLogHistory,
+    SnapState,
     Finish,
     Other(&'static str),
 }
 
 impl StageId {
     /// All supported Stages
+    ///
+    /// Optional stages like [StageId::IndexLogHistory] or [StageId::SnapState] are not included,
+    /// since they might not be part of the pipeline.
     pub const ALL: [StageId; 13] = [
         StageId::Headers,
         StageId::TotalDifficulty,
@@ -53,6 +58,8 @@ impl StageId {
             StageId::TransactionLookup => "TransactionLookup",
             StageId::IndexAccountHistory => "IndexAccountHistory",
             StageId::IndexStorageHistory => "IndexStorageHistory",
+            StageId::IndexLogHistory => "IndexLogHistory",
+            StageId::SnapState => "SnapState",
             StageId::Finish => "Finish",
             StageId::Other(s) => s,
         }
@@ -92,6 +99,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexLogHistory.to_string(), "IndexLogHistory");
+        assert_eq!(StageId::SnapState.to_string(), "SnapState");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
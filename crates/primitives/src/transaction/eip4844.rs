@@ -110,6 +110,57 @@ impl TxEip4844 {
         self.blob_versioned_hashes.len() as u64 * DATA_GAS_PER_BLOB
     }
 
+    /// Verifies that the given blob data, commitments, and proofs are all valid for this
+    /// transaction.
+    ///
+    /// Takes as input the [KzgSettings], which should contain the the parameters derived from the
+    /// KZG trusted setup.
+    ///
+    /// Returns `false` if any blob KZG proof in the sidecar fails to verify, or if the versioned
+    /// hashes in the transaction do not match the actual commitment versioned hashes.
+    pub fn validate_blob(
+        &self,
+        sidecar: &BlobTransactionSidecar,
+        proof_settings: &KzgSettings,
+    ) -> Result<bool, BlobTransactionValidationError> {
+        // Ensure the versioned hashes and commitments have the same length
+        if self.blob_versioned_hashes.len() != sidecar.commitments.len() {
+            return Err(kzg::Error::MismatchLength(format!(
+                "There are {} versioned commitment hashes and {} commitments",
+                self.blob_versioned_hashes.len(),
+                sidecar.commitments.len()
+            ))
+            .into())
+        }
+
+        // zip and iterate, calculating versioned hashes
+        for (versioned_hash, commitment) in
+            self.blob_versioned_hashes.iter().zip(sidecar.commitments.iter())
+        {
+            // convert to KzgCommitment
+            let commitment = KzgCommitment::from(*commitment.deref());
+
+            // Calculate the versioned hash
+            //
+            // TODO: should this method distinguish the type of validation failure? For example
+            // whether a certain versioned hash does not match, or whether the blob proof
+            // validation failed?
+            let calculated_versioned_hash = kzg_to_versioned_hash(commitment);
+            if *versioned_hash != calculated_versioned_hash {
+                return Ok(false)
+            }
+        }
+
+        // Verify as a batch
+        KzgProof::verify_blob_kzg_proof_batch(
+            sidecar.blobs.as_slice(),
+            sidecar.commitments.as_slice(),
+            sidecar.proofs.as_slice(),
+            proof_settings,
+        )
+        .map_err(Into::into)
+    }
+
     /// Decodes the inner [TxEip4844] fields from RLP bytes.
     ///
     /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
@@ -216,42 +267,7 @@ impl BlobTransaction {
             }
         };
 
-        // Ensure the versioned hashes and commitments have the same length
-        if inner_tx.blob_versioned_hashes.len() != self.sidecar.commitments.len() {
-            return Err(kzg::Error::MismatchLength(format!(
-                "There are {} versioned commitment hashes and {} commitments",
-                inner_tx.blob_versioned_hashes.len(),
-                self.sidecar.commitments.len()
-            ))
-            .into())
-        }
-
-        // zip and iterate, calculating versioned hashes
-        for (versioned_hash, commitment) in
-            inner_tx.blob_versioned_hashes.iter().zip(self.sidecar.commitments.iter())
-        {
-            // convert to KzgCommitment
-            let commitment = KzgCommitment::from(*commitment.deref());
-
-            // Calculate the versioned hash
-            //
-            // TODO: should this method distinguish the type of validation failure? For example
-            // whether a certain versioned hash does not match, or whether the blob proof
-            // validation failed?
-            let calculated_versioned_hash = kzg_to_versioned_hash(commitment);
-            if *versioned_hash != calculated_versioned_hash {
-                return Ok(false)
-            }
-        }
-
-        // Verify as a batch
-        KzgProof::verify_blob_kzg_proof_batch(
-            self.sidecar.blobs.as_slice(),
-            self.sidecar.commitments.as_slice(),
-            self.sidecar.proofs.as_slice(),
-            proof_settings,
-        )
-        .map_err(Into::into)
+        inner_tx.validate_blob(&self.sidecar, proof_settings)
     }
 
     /// Splits the [BlobTransaction] into its [TransactionSigned] and [BlobTransactionSidecar]
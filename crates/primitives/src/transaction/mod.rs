@@ -22,7 +22,9 @@ pub use tx_type::{
 
 pub use eip1559::TxEip1559;
 pub use eip2930::TxEip2930;
-pub use eip4844::{BlobTransaction, BlobTransactionSidecar, TxEip4844};
+pub use eip4844::{
+    BlobTransaction, BlobTransactionSidecar, BlobTransactionValidationError, TxEip4844,
+};
 pub use legacy::TxLegacy;
 pub use pooled::PooledTransactionsElement;
 
@@ -230,6 +232,18 @@ impl Transaction {
         }
     }
 
+    /// Returns the blob gas used for all blobs of the EIP-4844 transaction if it is an EIP-4844
+    /// transaction.
+    ///
+    /// This is the number of blobs times the
+    /// [DATA_GAS_PER_BLOB](crate::constants::eip4844::DATA_GAS_PER_BLOB) a single blob consumes.
+    pub fn blob_gas_used(&self) -> Option<u64> {
+        match self {
+            Transaction::Eip4844(tx) => Some(tx.blob_gas()),
+            _ => None,
+        }
+    }
+
     /// Return the max priority fee per gas if the transaction is an EIP-1559 transaction, and
     /// otherwise return the gas price.
     ///
@@ -2,8 +2,11 @@ use super::{
     nodes::{rlp_hash, BranchNode, ExtensionNode, LeafNode},
     BranchNodeCompact, Nibbles, TrieMask,
 };
-use crate::{keccak256, proofs::EMPTY_ROOT, H256};
-use std::{collections::HashMap, fmt::Debug};
+use crate::{keccak256, proofs::EMPTY_ROOT, Bytes, H256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+};
 
 mod state;
 pub use state::HashBuilderState;
@@ -11,6 +14,9 @@ pub use state::HashBuilderState;
 mod value;
 pub use value::HashBuilderValue;
 
+mod proof_retainer;
+pub use proof_retainer::ProofRetainer;
+
 /// A component used to construct the root hash of the trie. The primary purpose of a Hash Builder
 /// is to build the Merkle proof that is essential for verifying the integrity and authenticity of
 /// the trie's contents. It achieves this by constructing the root hash from the hashes of child
@@ -47,6 +53,7 @@ pub struct HashBuilder {
     stored_in_database: bool,
 
     updated_branch_nodes: Option<HashMap<Nibbles, BranchNodeCompact>>,
+    proof_retainer: Option<ProofRetainer>,
 
     rlp_buf: Vec<u8>,
 }
@@ -62,6 +69,7 @@ impl From<HashBuilderState> for HashBuilder {
             hash_masks: state.hash_masks,
             stored_in_database: state.stored_in_database,
             updated_branch_nodes: None,
+            proof_retainer: None,
             rlp_buf: Vec::with_capacity(32),
         }
     }
@@ -99,12 +107,28 @@ impl HashBuilder {
         }
     }
 
+    /// Enables the Hash Builder to retain the proofs of the nodes on the paths to the targets of
+    /// the retainer.
+    ///
+    /// Call [HashBuilder::take_proofs] to get the retained proofs.
+    pub fn with_proof_retainer(mut self, retainer: ProofRetainer) -> Self {
+        self.proof_retainer = Some(retainer);
+        self
+    }
+
     /// Splits the [HashBuilder] into a [HashBuilder] and hash builder updates.
     pub fn split(mut self) -> (Self, HashMap<Nibbles, BranchNodeCompact>) {
         let updates = self.updated_branch_nodes.take();
         (self, updates.unwrap_or_default())
     }
 
+    /// Takes the proofs retained so far, keyed by the path of the node in the trie.
+    ///
+    /// Returns an empty map if [Self::with_proof_retainer] was not called.
+    pub fn take_proofs(&mut self) -> BTreeMap<Nibbles, Bytes> {
+        self.proof_retainer.take().map(ProofRetainer::into_proofs).unwrap_or_default()
+    }
+
     /// The number of total updates accrued.
     /// Returns `0` if [Self::with_updates] was not called.
     pub fn updates_len(&self) -> usize {
@@ -251,7 +275,9 @@ impl HashBuilder {
                         }, "leaf node rlp");
 
                         self.rlp_buf.clear();
-                        self.stack.push(leaf_node.rlp(&mut self.rlp_buf));
+                        let rlp = leaf_node.rlp(&mut self.rlp_buf);
+                        self.retain_proof_from_buf(&current.slice(0, len_from));
+                        self.stack.push(rlp);
                     }
                     HashBuilderValue::Hash(hash) => {
                         tracing::debug!(target: "trie::hash_builder", ?hash, "pushing branch node hash");
@@ -280,7 +306,9 @@ impl HashBuilder {
                     hex::encode(&extension_node.rlp(&mut self.rlp_buf))
                 }, "extension node rlp");
                 self.rlp_buf.clear();
-                self.stack.push(extension_node.rlp(&mut self.rlp_buf));
+                let rlp = extension_node.rlp(&mut self.rlp_buf);
+                self.retain_proof_from_buf(&current.slice(0, len_from));
+                self.stack.push(rlp);
                 self.resize_masks(len_from);
             }
 
@@ -292,7 +320,7 @@ impl HashBuilder {
             // Insert branch nodes in the stack
             if !succeeding.is_empty() || preceding_exists {
                 // Pushes the corresponding branch node to the stack
-                let children = self.push_branch_node(len);
+                let children = self.push_branch_node(&current, len);
                 // Need to store the branch node in an efficient format
                 // outside of the hash builder
                 self.store_branch_node(&current, len, children);
@@ -323,7 +351,7 @@ impl HashBuilder {
     /// Given the size of the longest common prefix, it proceeds to create a branch node
     /// from the state mask and existing stack state, and store its RLP to the top of the stack,
     /// after popping all the relevant elements from the stack.
-    fn push_branch_node(&mut self, len: usize) -> Vec<H256> {
+    fn push_branch_node(&mut self, current: &Nibbles, len: usize) -> Vec<H256> {
         let state_mask = self.groups[len];
         let hash_mask = self.hash_masks[len];
         let branch_node = BranchNode::new(&self.stack);
@@ -331,6 +359,7 @@ impl HashBuilder {
 
         self.rlp_buf.clear();
         let rlp = branch_node.rlp(state_mask, &mut self.rlp_buf);
+        self.retain_proof_from_buf(&current.slice(0, len));
 
         // Clears the stack from the branch node elements
         let first_child_idx = self.stack.len() - state_mask.count_ones() as usize;
@@ -387,6 +416,14 @@ impl HashBuilder {
         }
     }
 
+    /// Retains the RLP encoded node in the buffer at the given path if the proof retainer is
+    /// enabled.
+    fn retain_proof_from_buf(&mut self, prefix: &Nibbles) {
+        if let Some(retainer) = self.proof_retainer.as_mut() {
+            retainer.retain(prefix, &self.rlp_buf);
+        }
+    }
+
     fn update_masks(&mut self, current: &Nibbles, len_from: usize) {
         if len_from > 0 {
             let flag = TrieMask::from_nibble(current[len_from - 1]);
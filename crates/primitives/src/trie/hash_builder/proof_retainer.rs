@@ -0,0 +1,46 @@
+use crate::{trie::Nibbles, Bytes};
+use std::collections::BTreeMap;
+
+/// Proof retainer is used to store proofs during merkle trie construction.
+/// It is intended to be used within the [`HashBuilder`](crate::trie::HashBuilder).
+#[derive(Debug, Default)]
+pub struct ProofRetainer {
+    /// The nibbles of the target trie keys to retain proofs for.
+    targets: Vec<Nibbles>,
+    /// The map of retained proofs (RLP serialized trie nodes)
+    /// with their corresponding key in the trie.
+    proofs: BTreeMap<Nibbles, Bytes>,
+}
+
+impl FromIterator<Nibbles> for ProofRetainer {
+    fn from_iter<T: IntoIterator<Item = Nibbles>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl ProofRetainer {
+    /// Create new retainer with target nibbles.
+    pub fn new(targets: Vec<Nibbles>) -> Self {
+        Self { targets, proofs: Default::default() }
+    }
+
+    /// Returns `true` if the given prefix matches the retainer target.
+    pub fn matches(&self, prefix: &Nibbles) -> bool {
+        self.targets.iter().any(|target| target.has_prefix(prefix))
+    }
+
+    /// Returns all collected proofs.
+    pub fn into_proofs(self) -> BTreeMap<Nibbles, Bytes> {
+        self.proofs
+    }
+
+    /// Retain the proof if the key matches any of the targets.
+    ///
+    /// Nodes that are shorter than 32 bytes are embedded in their parent node and are only
+    /// retained if they are the root node.
+    pub fn retain(&mut self, prefix: &Nibbles, proof: &[u8]) {
+        if (prefix.is_empty() || proof.len() >= 32) && self.matches(prefix) {
+            self.proofs.insert(prefix.clone(), Bytes::from(proof.to_vec()));
+        }
+    }
+}
@@ -207,6 +207,25 @@ impl Nibbles {
         encoded
     }
 
+    /// Decodes a path encoded with [Nibbles::encode_path_leaf], returning the path and whether it
+    /// is the path of a leaf node.
+    ///
+    /// Returns `None` if the encoding is empty or has an invalid flag.
+    pub fn decode_path(encoded: &[u8]) -> Option<(Nibbles, bool)> {
+        let (first, rest) = encoded.split_first()?;
+        let flag = first >> 4;
+        if flag > 3 {
+            return None
+        }
+
+        let mut hex = Vec::with_capacity(rest.len() * 2 + 1);
+        if flag & 1 == 1 {
+            hex.push(first & 0x0f);
+        }
+        hex.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+        Some((Nibbles::from_hex(hex), flag & 2 == 2))
+    }
+
     /// Increments the nibble sequence by one.
     pub fn increment(&self) -> Option<Nibbles> {
         let mut incremented = self.hex_data.to_vec();
@@ -354,5 +373,19 @@ mod tests {
                 assert_eq!(extension_flag & 0x0f, *input.first().unwrap());
             }
         }
+
+        #[test]
+        fn decode_path_roundtrip(
+            input in any::<Vec<u8>>(),
+            odd in any::<bool>(),
+            is_leaf in any::<bool>()
+        ) {
+            let mut input = Nibbles::unpack(input);
+            if odd {
+                input.extend([0x0a]);
+            }
+            let decoded = Nibbles::decode_path(&input.encode_path_leaf(is_leaf));
+            prop_assert_eq!(decoded, Some((input, is_leaf)));
+        }
     }
 }
@@ -16,7 +16,7 @@ use reth_primitives::{
 };
 use reth_provider::{
     BlockReader, DatabaseProviderRW, ProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
-    TransactionsProvider,
+    StaticFileProvider, StaticFileSegment, TransactionsProvider,
 };
 use std::{ops::RangeInclusive, sync::Arc, time::Instant};
 use tracing::{debug, instrument, trace};
@@ -33,6 +33,8 @@ pub struct BatchSizes {
     transaction_senders: usize,
     account_history: usize,
     storage_history: usize,
+    log_history: usize,
+    bodies: usize,
 }
 
 impl Default for BatchSizes {
@@ -43,6 +45,8 @@ impl Default for BatchSizes {
             transaction_senders: 10000,
             account_history: 10000,
             storage_history: 10000,
+            log_history: 10000,
+            bodies: 10000,
         }
     }
 }
@@ -80,6 +84,13 @@ impl<DB: Database> Pruner<DB> {
         }
     }
 
+    /// Reads the headers, transactions and receipts that were moved out of the database from the
+    /// given static files.
+    pub fn with_static_files(mut self, static_files: StaticFileProvider) -> Self {
+        self.provider_factory = self.provider_factory.with_static_files(static_files);
+        self
+    }
+
     /// Run the pruner
     pub fn run(&mut self, tip_block_number: BlockNumber) -> PrunerResult {
         trace!(
@@ -146,6 +157,30 @@ impl<DB: Database> Pruner<DB> {
                 .record(part_start.elapsed())
         }
 
+        if let Some((to_block, prune_mode)) =
+            self.modes.prune_target_block_log_history(tip_block_number)?
+        {
+            let part_start = Instant::now();
+            self.prune_log_history(&provider, to_block, prune_mode)?;
+            self.metrics
+                .get_prune_part_metrics(PrunePart::LogHistory)
+                .duration_seconds
+                .record(part_start.elapsed())
+        }
+
+        // Bodies are pruned last, because the other parts need the block body indices to find the
+        // transactions of the blocks they prune.
+        if let Some((to_block, prune_mode)) =
+            self.modes.prune_target_block_bodies(tip_block_number)?
+        {
+            let part_start = Instant::now();
+            self.prune_bodies(&provider, to_block, prune_mode)?;
+            self.metrics
+                .get_prune_part_metrics(PrunePart::Bodies)
+                .duration_seconds
+                .record(part_start.elapsed())
+        }
+
         provider.commit()?;
         self.last_pruned_block_number = Some(tip_block_number);
 
@@ -189,6 +224,8 @@ impl<DB: Database> Pruner<DB> {
     /// 1. If checkpoint exists, get next block body and return its first tx number.
     /// 2. If checkpoint doesn't exist, return 0.
     ///
+    /// Blocks with pruned bodies are skipped, as all of their transaction data is already removed.
+    ///
     /// To get the range end: get last tx number for the provided `to_block`.
     fn get_next_tx_num_range_from_checkpoint(
         &self,
@@ -202,6 +239,15 @@ impl<DB: Database> Pruner<DB> {
             .map(|checkpoint| checkpoint.block_number + 1)
             // No checkpoint exists, prune from genesis
             .unwrap_or(0);
+        let from_block_number = if prune_part == PrunePart::Bodies {
+            from_block_number
+        } else {
+            provider
+                .get_prune_checkpoint(PrunePart::Bodies)?
+                .map_or(from_block_number, |checkpoint| {
+                    from_block_number.max(checkpoint.block_number + 1)
+                })
+        };
 
         // Get first transaction
         let from_tx_num =
@@ -280,14 +326,47 @@ impl<DB: Database> Pruner<DB> {
                 return Ok(())
             }
         };
-        let last_tx_num = *range.end();
         let total = range.clone().count();
+
+        self.prune_transaction_hash_numbers(
+            provider,
+            range,
+            self.batch_sizes.transaction_lookup,
+            |rows, processed| {
+                trace!(
+                    target: "pruner",
+                    %rows,
+                    progress = format!("{:.1}%", 100.0 * processed as f64 / total as f64),
+                    "Pruned transaction lookup"
+                );
+            },
+        )?;
+
+        provider.save_prune_checkpoint(
+            PrunePart::TransactionLookup,
+            PruneCheckpoint { block_number: to_block, prune_mode },
+        )?;
+
+        Ok(())
+    }
+
+    /// Prune the `TxHashNumber` entries of the transactions in the range, calling
+    /// `batch_callback` after every batch with the number of rows pruned in the batch and in
+    /// total.
+    fn prune_transaction_hash_numbers(
+        &self,
+        provider: &DatabaseProviderRW<'_, DB>,
+        range: RangeInclusive<TxNumber>,
+        batch_size: usize,
+        batch_callback: impl Fn(usize, usize),
+    ) -> PrunerResult {
+        let last_tx_num = *range.end();
         let mut processed = 0;
 
-        for i in range.step_by(self.batch_sizes.transaction_lookup) {
+        for i in range.step_by(batch_size) {
             // The `min` ensures that the transaction range doesn't exceed the last transaction
             // number. `last_tx_num + 1` is used to include the last transaction in the range.
-            let tx_range = i..(i + self.batch_sizes.transaction_lookup as u64).min(last_tx_num + 1);
+            let tx_range = i..(i + batch_size as u64).min(last_tx_num + 1);
 
             // Retrieve transactions in the range and calculate their hashes in parallel
             let mut hashes = provider
@@ -309,19 +388,9 @@ impl<DB: Database> Pruner<DB> {
 
             let rows = provider.prune_table_with_iterator::<tables::TxHashNumber>(hashes)?;
             processed += rows;
-            trace!(
-                target: "pruner",
-                %rows,
-                progress = format!("{:.1}%", 100.0 * processed as f64 / total as f64),
-                "Pruned transaction lookup"
-            );
+            batch_callback(rows, processed);
         }
 
-        provider.save_prune_checkpoint(
-            PrunePart::TransactionLookup,
-            PruneCheckpoint { block_number: to_block, prune_mode },
-        )?;
-
         Ok(())
     }
 
@@ -470,6 +539,160 @@ impl<DB: Database> Pruner<DB> {
         Ok(())
     }
 
+    /// Prune log address and topic history up to the provided block, inclusive.
+    #[instrument(level = "trace", skip(self, provider), target = "pruner")]
+    fn prune_log_history(
+        &self,
+        provider: &DatabaseProviderRW<'_, DB>,
+        to_block: BlockNumber,
+        prune_mode: PruneMode,
+    ) -> PrunerResult {
+        self.prune_history_indices::<tables::LogAddressHistory, _>(
+            provider,
+            to_block,
+            |a, b| a.key == b.key,
+            |key| ShardedKey::last(key.key),
+            self.batch_sizes.log_history,
+            |rows| {
+                trace!(
+                    target: "pruner",
+                    rows,
+                    "Pruned log history (addresses)"
+                );
+            },
+        )?;
+
+        self.prune_history_indices::<tables::LogTopicHistory, _>(
+            provider,
+            to_block,
+            |a, b| a.key == b.key,
+            |key| ShardedKey::last(key.key),
+            self.batch_sizes.log_history,
+            |rows| {
+                trace!(
+                    target: "pruner",
+                    rows,
+                    "Pruned log history (topics)"
+                );
+            },
+        )?;
+
+        provider.save_prune_checkpoint(
+            PrunePart::LogHistory,
+            PruneCheckpoint { block_number: to_block, prune_mode },
+        )?;
+
+        Ok(())
+    }
+
+    /// Prune block bodies up to the provided block, inclusive. Besides the bodies themselves,
+    /// the lookup, sender and receipt entries of their transactions are pruned, as they can't be
+    /// reached anymore without the body indices.
+    #[instrument(level = "trace", skip(self, provider), target = "pruner")]
+    fn prune_bodies(
+        &self,
+        provider: &DatabaseProviderRW<'_, DB>,
+        to_block: BlockNumber,
+        prune_mode: PruneMode,
+    ) -> PrunerResult {
+        let from_block = provider
+            .get_prune_checkpoint(PrunePart::Bodies)?
+            .map(|checkpoint| checkpoint.block_number + 1)
+            .unwrap_or_default();
+
+        if let Some(range) =
+            self.get_next_tx_num_range_from_checkpoint(provider, PrunePart::Bodies, to_block)?
+        {
+            let total = range.clone().count();
+
+            // The hashes are calculated from the transactions, so the lookup entries have to be
+            // pruned before the transactions.
+            self.prune_transaction_hash_numbers(
+                provider,
+                range.clone(),
+                self.batch_sizes.bodies,
+                |rows, processed| {
+                    trace!(
+                        target: "pruner",
+                        %rows,
+                        progress = format!("{:.1}%", 100.0 * processed as f64 / total as f64),
+                        "Pruned transaction lookup of bodies"
+                    );
+                },
+            )?;
+
+            self.prune_tx_table::<tables::TxSenders>(provider, range.clone(), total)?;
+            self.prune_tx_table::<tables::Receipts>(provider, range.clone(), total)?;
+            self.prune_tx_table::<tables::TransactionBlock>(provider, range.clone(), total)?;
+            self.prune_tx_table::<tables::Transactions>(provider, range, total)?;
+        }
+
+        let range = from_block..=to_block;
+        self.prune_block_table::<tables::BlockOmmers>(provider, range.clone())?;
+        self.prune_block_table::<tables::BlockWithdrawals>(provider, range.clone())?;
+        self.prune_block_table::<tables::BlockBodyIndices>(provider, range)?;
+
+        // Transactions and receipts of frozen blocks are not in the database anymore, so the
+        // static files of fully pruned block ranges are pruned instead.
+        if let Some(static_files) = provider.static_files() {
+            for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+                let files = static_files.prune(segment, to_block)?;
+                trace!(target: "pruner", %segment, %files, "Pruned static files of bodies");
+            }
+        }
+
+        provider.save_prune_checkpoint(
+            PrunePart::Bodies,
+            PruneCheckpoint { block_number: to_block, prune_mode },
+        )?;
+
+        Ok(())
+    }
+
+    /// Prune the entries of a table keyed by transaction number in the range.
+    fn prune_tx_table<T: Table<Key = TxNumber>>(
+        &self,
+        provider: &DatabaseProviderRW<'_, DB>,
+        range: RangeInclusive<TxNumber>,
+        total: usize,
+    ) -> PrunerResult {
+        provider.prune_table_with_range_in_batches::<T>(
+            range,
+            self.batch_sizes.bodies,
+            |_, rows| {
+                trace!(
+                    target: "pruner",
+                    table = T::NAME,
+                    %rows,
+                    progress = format!("{:.1}%", 100.0 * rows as f64 / total as f64),
+                    "Pruned bodies"
+                );
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Prune the entries of a table keyed by block number in the range.
+    fn prune_block_table<T: Table<Key = BlockNumber>>(
+        &self,
+        provider: &DatabaseProviderRW<'_, DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> PrunerResult {
+        provider.prune_table_with_range_in_batches::<T>(
+            range,
+            self.batch_sizes.bodies,
+            |_, rows| {
+                trace!(
+                    target: "pruner",
+                    table = T::NAME,
+                    %rows,
+                    "Pruned bodies"
+                );
+            },
+        )?;
+        Ok(())
+    }
+
     /// Prune history indices up to the provided block, inclusive.
     fn prune_history_indices<T, SK>(
         &self,
@@ -576,16 +799,20 @@ mod tests {
     use crate::{pruner::BatchSizes, Pruner};
     use assert_matches::assert_matches;
     use reth_db::{tables, test_utils::create_test_rw_db, BlockNumberList};
-    use reth_interfaces::test_utils::{
-        generators,
-        generators::{
-            random_block_range, random_changeset_range, random_eoa_account_range, random_receipt,
+    use reth_interfaces::{
+        provider::ProviderError,
+        test_utils::{
+            generators,
+            generators::{
+                random_block_range, random_changeset_range, random_eoa_account_range,
+                random_receipt,
+            },
         },
     };
     use reth_primitives::{
         BlockNumber, PruneCheckpoint, PruneMode, PruneModes, PrunePart, H256, MAINNET,
     };
-    use reth_provider::PruneCheckpointReader;
+    use reth_provider::{BlockReader, PruneCheckpointReader};
     use reth_stages::test_utils::TestTransaction;
     use std::{collections::BTreeMap, ops::AddAssign};
 
@@ -798,6 +1025,74 @@ mod tests {
         test_prune(20);
     }
 
+    #[test]
+    fn prune_bodies() {
+        let tx = TestTransaction::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=100, H256::zero(), 0..10);
+        tx.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let mut tx_hash_numbers = Vec::new();
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body {
+                tx_hash_numbers.push((transaction.hash, tx_hash_numbers.len() as u64));
+                receipts
+                    .push((receipts.len() as u64, random_receipt(&mut rng, transaction, Some(0))));
+            }
+        }
+        tx.insert_tx_hash_numbers(tx_hash_numbers).expect("insert tx hash numbers");
+        tx.insert_receipts(receipts).expect("insert receipts");
+
+        let test_prune = |to_block: BlockNumber| {
+            let prune_mode = PruneMode::Before(to_block);
+            let pruner = Pruner::new(
+                tx.inner_raw(),
+                MAINNET.clone(),
+                5,
+                PruneModes { bodies: Some(prune_mode), ..Default::default() },
+                BatchSizes {
+                    // Less than total amount of blocks to prune to test the batching logic
+                    bodies: 10,
+                    ..Default::default()
+                },
+            );
+
+            let provider = tx.inner_rw();
+            assert_matches!(pruner.prune_bodies(&provider, to_block, prune_mode), Ok(()));
+            provider.commit().expect("commit");
+
+            let remaining_transactions =
+                blocks[to_block as usize + 1..].iter().map(|block| block.body.len()).sum::<usize>();
+            assert_eq!(tx.table::<tables::Transactions>().unwrap().len(), remaining_transactions);
+            assert_eq!(tx.table::<tables::TxHashNumber>().unwrap().len(), remaining_transactions);
+            assert_eq!(tx.table::<tables::Receipts>().unwrap().len(), remaining_transactions);
+            assert_eq!(
+                tx.table::<tables::BlockBodyIndices>().unwrap().len(),
+                blocks.len() - to_block as usize - 1
+            );
+            assert_eq!(
+                tx.inner().get_prune_checkpoint(PrunePart::Bodies).unwrap(),
+                Some(PruneCheckpoint { block_number: to_block, prune_mode })
+            );
+
+            // Expired bodies are reported as pruned instead of missing
+            assert_matches!(
+                tx.inner().block(to_block.into()),
+                Err(reth_interfaces::Error::Provider(ProviderError::BlockBodyPruned(number)))
+                    if number == to_block
+            );
+            assert_matches!(tx.inner().block((to_block + 1).into()), Ok(Some(_)));
+        };
+
+        // Pruning first time ever, no previous checkpoint is present
+        test_prune(10);
+        // Prune second time, previous checkpoint is present, should continue pruning from where
+        // ended last time
+        test_prune(20);
+    }
+
     #[test]
     fn prune_account_history() {
         let tx = TestTransaction::default();
@@ -73,6 +73,26 @@ impl PushTraceKind {
     }
 }
 
+#[cfg(test)]
+impl CallTraceArena {
+    /// Builds an arena from the given traces in execution order, the way the
+    /// [TracingInspector](crate::tracing::TracingInspector) records them.
+    ///
+    /// Calls to precompiles are not attached to their parent.
+    pub(crate) fn from_traces(traces: impl IntoIterator<Item = CallTrace>) -> Self {
+        let mut arena = Self::default();
+        for trace in traces {
+            let kind = if trace.maybe_precompile.unwrap_or(false) {
+                PushTraceKind::PushOnly
+            } else {
+                PushTraceKind::PushAndAttachToParent
+            };
+            arena.push_trace(0, kind, trace);
+        }
+        arena
+    }
+}
+
 impl Default for CallTraceArena {
     fn default() -> Self {
         // The first node is the root node
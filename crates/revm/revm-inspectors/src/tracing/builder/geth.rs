@@ -1,13 +1,17 @@
 //! Geth trace builder
 
 use crate::tracing::{
+    builder::parity::ParityTraceBuilder,
     types::{CallTraceNode, CallTraceStepStackItem},
     TracingInspectorConfig,
 };
-use reth_primitives::{Address, Bytes, H256, U256};
-use reth_rpc_types::trace::geth::{
-    AccountState, CallConfig, CallFrame, DefaultFrame, DiffMode, GethDefaultTracingOptions,
-    PreStateConfig, PreStateFrame, PreStateMode, StructLog,
+use reth_primitives::{hex, Address, Bytes, H256, U256};
+use reth_rpc_types::{
+    trace::geth::{
+        AccountState, CallConfig, CallFrame, DefaultFrame, DiffMode, FlatCallFrame, FourByteFrame,
+        GethDefaultTracingOptions, PreStateConfig, PreStateFrame, PreStateMode, StructLog,
+    },
+    TransactionInfo,
 };
 use revm::{db::DatabaseRef, primitives::ResultAndState};
 use std::collections::{BTreeMap, HashMap, VecDeque};
@@ -152,6 +156,31 @@ impl GethTraceBuilder {
         }
     }
 
+    /// Generate Parity-style flat call traces for the flat call tracer.
+    ///
+    /// The traces are localized with the given [TransactionInfo]. Whether calls to precompiles are
+    /// included depends on the [TracingInspectorConfig] the traces were recorded with.
+    pub fn geth_flat_call_traces(&self, info: TransactionInfo) -> FlatCallFrame {
+        ParityTraceBuilder::new(self.nodes.clone(), None, self._config)
+            .into_localized_transaction_traces(info)
+    }
+
+    /// Generate the 4byte tracer result from the recorded call traces.
+    ///
+    /// This counts the function selectors and calldata sizes of all calls, like the
+    /// [FourByteInspector](crate::tracing::FourByteInspector) does.
+    pub fn geth_four_byte_traces(&self) -> FourByteFrame {
+        let mut frame = FourByteFrame::default();
+        for node in self.nodes.iter().filter(|node| !node.kind().is_any_create()) {
+            let input = &node.trace.data;
+            if input.len() >= 4 {
+                let key = format!("0x{}-{}", hex::encode(&input[..4]), input.len() - 4);
+                *frame.0.entry(key).or_default() += 1;
+            }
+        }
+        frame
+    }
+
     ///  Returns the accounts necessary for transaction execution.
     ///
     /// The prestate mode returns the accounts necessary to execute a given transaction.
@@ -223,3 +252,163 @@ impl GethTraceBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracing::{
+        types::{CallKind, CallTrace},
+        CallTraceArena,
+    };
+    use reth_rpc_types::trace::parity::{Action, CallType};
+    use revm::interpreter::InstructionResult;
+
+    fn address(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn calldata(selector: [u8; 4], len: usize) -> reth_primitives::bytes::Bytes {
+        let mut data = selector.to_vec();
+        data.resize(4 + len, 0);
+        data.into()
+    }
+
+    /// Returns a builder for the following call tree, in execution order:
+    ///
+    /// ```text
+    /// 0: CALL 1 -> 2          transfer(address,uint256)
+    /// 1:   STATICCALL 2 -> 3  balanceOf(address)
+    /// 2:   CALL 2 -> 3        balanceOf(address)
+    /// 3:     CALL 3 -> 5      3 bytes of calldata, reverts
+    /// 4:   CALL 2 -> 9        precompile
+    /// 5:   CREATE 2 -> 4      init code that starts like a selector
+    /// ```
+    fn builder() -> GethTraceBuilder {
+        let transfer = [0xa9, 0x05, 0x9c, 0xbb];
+        let balance_of = [0x70, 0xa0, 0x82, 0x31];
+        let traces = vec![
+            CallTrace {
+                caller: address(1),
+                address: address(2),
+                data: calldata(transfer, 64),
+                success: true,
+                status: InstructionResult::Stop,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(3),
+                kind: CallKind::StaticCall,
+                data: calldata(balance_of, 32),
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(3),
+                data: calldata(balance_of, 32),
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 2,
+                caller: address(3),
+                address: address(5),
+                data: vec![1, 2, 3].into(),
+                success: false,
+                status: InstructionResult::Revert,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(9),
+                maybe_precompile: Some(true),
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(4),
+                kind: CallKind::Create,
+                data: calldata([0x60, 0x80, 0x60, 0x40], 8),
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+        ];
+
+        GethTraceBuilder::new(
+            CallTraceArena::from_traces(traces).arena,
+            TracingInspectorConfig::default_geth(),
+        )
+    }
+
+    #[test]
+    fn four_byte_traces() {
+        let frame = builder().geth_four_byte_traces();
+        assert_eq!(
+            frame,
+            FourByteFrame(BTreeMap::from([
+                ("0xa9059cbb-64".to_string(), 1),
+                ("0x70a08231-32".to_string(), 2),
+            ]))
+        );
+    }
+
+    #[test]
+    fn flat_call_traces() {
+        let info = TransactionInfo {
+            hash: Some(H256::repeat_byte(0xaa)),
+            index: Some(3),
+            block_hash: Some(H256::repeat_byte(0xbb)),
+            block_number: Some(100),
+            base_fee: None,
+        };
+        let frame = builder().geth_flat_call_traces(info);
+
+        assert!(frame.iter().all(|trace| {
+            trace.transaction_hash == info.hash &&
+                trace.transaction_position == info.index &&
+                trace.block_hash == info.block_hash &&
+                trace.block_number == info.block_number
+        }));
+
+        let summary = frame
+            .iter()
+            .map(|trace| {
+                let trace = &trace.trace;
+                let call_type = match &trace.action {
+                    Action::Call(call) => Some(call.call_type.clone()),
+                    _ => None,
+                };
+                (trace.trace_address.clone(), trace.subtraces, call_type, trace.error.clone())
+            })
+            .collect::<Vec<_>>();
+        // the call to the precompile is not included
+        assert_eq!(
+            summary,
+            vec![
+                (vec![], 3, Some(CallType::Call), None),
+                (vec![0], 0, Some(CallType::StaticCall), None),
+                (vec![1], 1, Some(CallType::Call), None),
+                (vec![1, 0], 0, Some(CallType::Call), Some("Reverted".to_string())),
+                (vec![2], 0, None, None),
+            ]
+        );
+
+        match &frame[4].trace.action {
+            Action::Create(create) => {
+                assert_eq!(create.from, address(2));
+                assert_eq!(create.init, calldata([0x60, 0x80, 0x60, 0x40], 8));
+            }
+            action => panic!("expected create action, got {action:?}"),
+        }
+    }
+}
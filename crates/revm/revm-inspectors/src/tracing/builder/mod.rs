@@ -3,6 +3,9 @@
 /// Geth style trace builders for `debug_` namespace
 pub mod geth;
 
+/// Otterscan style trace builders for `ots_` namespace
+pub mod otterscan;
+
 /// Parity style trace builders for `trace_` namespace
 pub mod parity;
 
@@ -0,0 +1,336 @@
+//! Otterscan trace builder
+
+use crate::tracing::{
+    types::{CallKind, CallTraceNode},
+    TracingInspectorConfig,
+};
+use reth_primitives::{Address, U256};
+use reth_rpc_types::{InternalOperation, OperationType, TraceEntry};
+
+/// A type for creating otterscan style traces for the `ots_` namespace
+///
+/// Note: Calls to precompiles are ignored.
+#[derive(Clone, Debug)]
+pub struct OtterscanTraceBuilder {
+    /// Recorded trace nodes.
+    nodes: Vec<CallTraceNode>,
+    /// How the traces were recorded
+    _config: TracingInspectorConfig,
+}
+
+impl OtterscanTraceBuilder {
+    /// Returns a new instance of the builder
+    pub(crate) fn new(nodes: Vec<CallTraceNode>, _config: TracingInspectorConfig) -> Self {
+        Self { nodes, _config }
+    }
+
+    /// Returns an iterator over all nodes to trace
+    ///
+    /// This excludes nodes that represent calls to precompiles.
+    fn iter_traceable_nodes(&self) -> impl Iterator<Item = &CallTraceNode> {
+        self.nodes.iter().filter(|node| !node.is_precompile())
+    }
+
+    /// Returns true if the given address appears anywhere in the recorded call tree.
+    ///
+    /// This checks callers, callees, created contracts and selfdestruct beneficiaries.
+    pub fn contains_address(&self, address: Address) -> bool {
+        self.iter_traceable_nodes().any(|node| {
+            node.trace.caller == address ||
+                node.trace.address == address ||
+                node.trace.selfdestruct_refund_target == Some(address)
+        })
+    }
+
+    /// Returns all `(creator, contract)` pairs of contracts that were successfully created.
+    ///
+    /// The creator is the address that executed the `CREATE` or `CREATE2`, which is not
+    /// necessarily the sender of the transaction.
+    pub fn created_contracts(&self) -> impl Iterator<Item = (Address, Address)> + '_ {
+        self.iter_traceable_nodes()
+            .filter(|node| node.kind().is_any_create() && node.trace.success)
+            .map(|node| (node.trace.caller, node.trace.address))
+    }
+
+    /// Returns the flattened call tree for `ots_traceTransaction`.
+    ///
+    /// Entries are in execution order. A `SELFDESTRUCT` entry follows the entry of the call that
+    /// self-destructed.
+    pub fn into_trace_entries(self) -> Vec<TraceEntry> {
+        let mut entries = Vec::with_capacity(self.nodes.len());
+        for node in self.iter_traceable_nodes() {
+            let trace = &node.trace;
+            let value = match trace.kind {
+                CallKind::StaticCall | CallKind::DelegateCall => None,
+                _ => Some(trace.value),
+            };
+            entries.push(TraceEntry {
+                r#type: trace.kind.to_string(),
+                depth: trace.depth as u32,
+                from: trace.caller,
+                to: trace.address,
+                value,
+                input: trace.data.clone().into(),
+                output: trace.output.clone().into(),
+            });
+
+            if node.is_selfdestruct() {
+                entries.push(TraceEntry {
+                    r#type: "SELFDESTRUCT".to_string(),
+                    depth: trace.depth as u32 + 1,
+                    from: trace.address,
+                    to: trace.selfdestruct_refund_target.unwrap_or_default(),
+                    value: Some(trace.value),
+                    input: Default::default(),
+                    output: Default::default(),
+                });
+            }
+        }
+        entries
+    }
+
+    /// Returns the internal value transfers, contract creations and selfdestructs for
+    /// `ots_getInternalOperations`.
+    ///
+    /// The root call is not an internal operation, so its value transfer or creation is skipped.
+    pub fn into_internal_operations(self) -> Vec<InternalOperation> {
+        let mut operations = Vec::new();
+        for node in self.iter_traceable_nodes() {
+            let trace = &node.trace;
+            if node.idx != 0 {
+                let op = match trace.kind {
+                    CallKind::Call if trace.value != U256::ZERO => Some(OperationType::OpTransfer),
+                    CallKind::Create => Some(OperationType::OpCreate),
+                    CallKind::Create2 => Some(OperationType::OpCreate2),
+                    _ => None,
+                };
+                if let Some(r#type) = op {
+                    operations.push(InternalOperation {
+                        r#type,
+                        from: trace.caller,
+                        to: trace.address,
+                        value: trace.value,
+                    });
+                }
+            }
+
+            if node.is_selfdestruct() {
+                operations.push(InternalOperation {
+                    r#type: OperationType::OpSelfDestruct,
+                    from: trace.address,
+                    to: trace.selfdestruct_refund_target.unwrap_or_default(),
+                    value: trace.value,
+                });
+            }
+        }
+        operations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracing::{types::CallTrace, CallTraceArena};
+    use reth_primitives::bytes::Bytes;
+    use revm::interpreter::InstructionResult;
+
+    fn address(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn builder(traces: Vec<CallTrace>) -> OtterscanTraceBuilder {
+        OtterscanTraceBuilder::new(
+            CallTraceArena::from_traces(traces).arena,
+            TracingInspectorConfig::default_geth(),
+        )
+    }
+
+    #[test]
+    fn trace_entries_nested_calls() {
+        let builder = builder(vec![
+            CallTrace {
+                caller: address(1),
+                address: address(2),
+                value: U256::from(10),
+                data: Bytes::from_static(&[0xaa]),
+                output: Bytes::from_static(&[0xbb]),
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(3),
+                kind: CallKind::StaticCall,
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 2,
+                caller: address(3),
+                address: address(4),
+                kind: CallKind::DelegateCall,
+                success: true,
+                status: InstructionResult::Stop,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(5),
+                value: U256::from(3),
+                success: true,
+                status: InstructionResult::Stop,
+                ..Default::default()
+            },
+            // calls to precompiles are ignored
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(9),
+                maybe_precompile: Some(true),
+                success: true,
+                ..Default::default()
+            },
+        ]);
+
+        assert!(builder.contains_address(address(4)));
+        assert!(!builder.contains_address(address(9)));
+
+        let entries = builder.into_trace_entries();
+        let summary = entries
+            .iter()
+            .map(|entry| (entry.r#type.as_str(), entry.depth, entry.from, entry.to, entry.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("CALL", 0, address(1), address(2), Some(U256::from(10))),
+                ("STATICCALL", 1, address(2), address(3), None),
+                ("DELEGATECALL", 2, address(3), address(4), None),
+                ("CALL", 1, address(2), address(5), Some(U256::from(3))),
+            ]
+        );
+        assert_eq!(entries[0].input, vec![0xaa]);
+        assert_eq!(entries[0].output, vec![0xbb]);
+    }
+
+    #[test]
+    fn trace_entries_create() {
+        let init_code = Bytes::from_static(&[0x60, 0x00]);
+        let runtime_code = Bytes::from_static(&[0x00]);
+        let builder = builder(vec![
+            CallTrace {
+                caller: address(1),
+                address: address(2),
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(3),
+                kind: CallKind::Create2,
+                value: U256::from(1),
+                data: init_code.clone(),
+                output: runtime_code.clone(),
+                success: true,
+                status: InstructionResult::Return,
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(builder.created_contracts().collect::<Vec<_>>(), vec![(address(2), address(3))]);
+
+        let operations = builder.clone().into_internal_operations();
+        assert_eq!(
+            operations,
+            vec![InternalOperation {
+                r#type: OperationType::OpCreate2,
+                from: address(2),
+                to: address(3),
+                value: U256::from(1),
+            }]
+        );
+
+        let entries = builder.into_trace_entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].r#type, "CREATE2");
+        assert_eq!(entries[1].depth, 1);
+        assert_eq!(entries[1].from, address(2));
+        assert_eq!(entries[1].to, address(3));
+        assert_eq!(entries[1].value, Some(U256::from(1)));
+        assert_eq!(entries[1].input, init_code);
+        assert_eq!(entries[1].output, runtime_code);
+    }
+
+    #[test]
+    fn trace_entries_reverts() {
+        let revert_data = Bytes::from_static(&[0x08, 0xc3, 0x79, 0xa0]);
+        let builder = builder(vec![
+            CallTrace {
+                caller: address(1),
+                address: address(2),
+                success: true,
+                status: InstructionResult::Stop,
+                ..Default::default()
+            },
+            // a create that reverted doesn't create a contract
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(3),
+                kind: CallKind::Create,
+                success: false,
+                status: InstructionResult::Revert,
+                output: revert_data.clone(),
+                ..Default::default()
+            },
+            // a reverted call is still part of the trace
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(4),
+                success: false,
+                status: InstructionResult::Revert,
+                output: revert_data.clone(),
+                ..Default::default()
+            },
+            CallTrace {
+                depth: 1,
+                caller: address(2),
+                address: address(5),
+                value: U256::from(7),
+                selfdestruct_refund_target: Some(address(6)),
+                success: true,
+                status: InstructionResult::SelfDestruct,
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(builder.created_contracts().count(), 0);
+        assert!(builder.contains_address(address(6)));
+
+        let entries = builder.into_trace_entries();
+        let summary = entries
+            .iter()
+            .map(|entry| (entry.r#type.as_str(), entry.depth, entry.from, entry.to))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("CALL", 0, address(1), address(2)),
+                ("CREATE", 1, address(2), address(3)),
+                ("CALL", 1, address(2), address(4)),
+                ("CALL", 1, address(2), address(5)),
+                ("SELFDESTRUCT", 2, address(5), address(6)),
+            ]
+        );
+        assert_eq!(entries[1].output, revert_data);
+        assert_eq!(entries[2].output, revert_data);
+        assert_eq!(entries[4].value, Some(U256::from(7)));
+    }
+}
@@ -29,6 +29,7 @@ use crate::tracing::{
 };
 pub use builder::{
     geth::{self, GethTraceBuilder},
+    otterscan::{self, OtterscanTraceBuilder},
     parity::{self, ParityTraceBuilder},
 };
 pub use config::TracingInspectorConfig;
@@ -92,6 +93,11 @@ impl TracingInspector {
         GethTraceBuilder::new(self.traces.arena, self.config)
     }
 
+    /// Consumes the Inspector and returns an [OtterscanTraceBuilder].
+    pub fn into_otterscan_builder(self) -> OtterscanTraceBuilder {
+        OtterscanTraceBuilder::new(self.traces.arena, self.config)
+    }
+
     /// Returns true if we're no longer in the context of the root call.
     fn is_deep(&self) -> bool {
         // the root call will always be the first entry in the trace stack
@@ -646,8 +646,9 @@ mod tests {
     use once_cell::sync::Lazy;
     use reth_consensus_common::calc;
     use reth_primitives::{
-        constants::ETH_TO_WEI, hex_literal::hex, keccak256, Account, Address, BlockNumber,
-        Bytecode, Bytes, ChainSpecBuilder, ForkCondition, StorageKey, H256, MAINNET, U256,
+        constants::ETH_TO_WEI, hex_literal::hex, keccak256, trie::AccountProof, Account, Address,
+        BlockNumber, Bytecode, Bytes, ChainSpecBuilder, ForkCondition, StorageKey, H256, MAINNET,
+        U256,
     };
     use reth_provider::{
         post_state::{AccountChanges, Storage, StorageTransition, StorageWipe},
@@ -742,7 +743,7 @@ mod tests {
             &self,
             _address: Address,
             _keys: &[H256],
-        ) -> reth_interfaces::Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
+        ) -> reth_interfaces::Result<AccountProof> {
             todo!()
         }
     }
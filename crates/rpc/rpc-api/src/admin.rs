@@ -1,6 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::NodeRecord;
-use reth_rpc_types::NodeInfo;
+use reth_rpc_types::{BanEntry, BanTarget, NodeInfo};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -28,6 +28,21 @@ pub trait AdminApi {
     #[method(name = "removeTrustedPeer")]
     fn remove_trusted_peer(&self, record: NodeRecord) -> RpcResult<bool>;
 
+    /// Returns all banned peer ids and IP addresses.
+    #[method(name = "bannedPeers")]
+    async fn banned_peers(&self) -> RpcResult<Vec<BanEntry>>;
+
+    /// Bans the peer id or IP address for the given number of seconds, or indefinitely if no
+    /// duration is given.
+    ///
+    /// Connected peers that match the ban are disconnected.
+    #[method(name = "banPeer")]
+    fn ban_peer(&self, target: BanTarget, duration: Option<u64>) -> RpcResult<bool>;
+
+    /// Lifts the ban of the peer id or IP address.
+    #[method(name = "unbanPeer")]
+    fn unban_peer(&self, target: BanTarget) -> RpcResult<bool>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
     name = "peerEvents",
@@ -0,0 +1,87 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::{Address, Bytes, H256, U256, U64};
+use reth_rpc_types::{MineOptions, TransactionRequest};
+
+/// Dev chain rpc interface, compatible with the `anvil_` and `evm_` methods of Anvil and Hardhat.
+///
+/// The methods span several namespaces, so they are declared with their full names.
+#[cfg_attr(not(feature = "client"), rpc(server))]
+#[cfg_attr(feature = "client", rpc(server, client))]
+#[async_trait::async_trait]
+pub trait AnvilApi {
+    /// Mines one or more blocks, including all pending transactions in the first block.
+    #[method(name = "evm_mine")]
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String>;
+
+    /// Mines the given number of blocks, one by default, including all pending transactions in the
+    /// first block.
+    ///
+    /// If an interval is given, the timestamps of consecutive blocks are that many seconds apart.
+    /// Mining zero blocks is a no-op.
+    #[method(name = "anvil_mine")]
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()>;
+
+    /// Sets the timestamp of the next block.
+    ///
+    /// Blocks mined afterwards continue from this timestamp.
+    #[method(name = "evm_setNextBlockTimestamp")]
+    async fn evm_set_next_block_timestamp(&self, timestamp: U64) -> RpcResult<()>;
+
+    /// Moves the time of the next blocks forward by the given number of seconds.
+    ///
+    /// Returns the total time offset in seconds.
+    #[method(name = "evm_increaseTime")]
+    async fn evm_increase_time(&self, seconds: U64) -> RpcResult<U64>;
+
+    /// Takes a snapshot of the current chain and returns its id.
+    #[method(name = "evm_snapshot")]
+    async fn evm_snapshot(&self) -> RpcResult<U256>;
+
+    /// Reverts the chain to the snapshot with the given id by unwinding all later blocks.
+    ///
+    /// The snapshot and all snapshots taken after it are removed. Returns `false` if the snapshot
+    /// does not exist.
+    #[method(name = "evm_revert")]
+    async fn evm_revert(&self, id: U256) -> RpcResult<bool>;
+
+    /// Sets the balance of the account.
+    #[method(name = "anvil_setBalance")]
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()>;
+
+    /// Sets the code of the account.
+    #[method(name = "anvil_setCode")]
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()>;
+
+    /// Sets the value of a storage slot of the account.
+    #[method(name = "anvil_setStorageAt")]
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: H256,
+    ) -> RpcResult<bool>;
+
+    /// Sets the nonce of the account.
+    #[method(name = "anvil_setNonce")]
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()>;
+
+    /// Allows sending transactions on behalf of the account via `eth_sendTransaction` and
+    /// `eth_sendUnsignedTransaction`, without its private key.
+    #[method(name = "anvil_impersonateAccount")]
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()>;
+
+    /// Stops impersonating the account.
+    #[method(name = "anvil_stopImpersonatingAccount")]
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()>;
+
+    /// Mines a transaction sent from an impersonated account.
+    #[method(name = "eth_sendUnsignedTransaction")]
+    async fn eth_send_unsigned_transaction(&self, request: TransactionRequest) -> RpcResult<H256>;
+
+    /// Sends a transaction like the `eth_sendTransaction` of the `eth` namespace, but mines
+    /// transactions of impersonated accounts without signing them.
+    ///
+    /// This replaces the `eth_sendTransaction` method of the `eth` namespace on the dev chain.
+    #[method(name = "eth_sendTransaction")]
+    async fn eth_send_transaction(&self, request: TransactionRequest) -> RpcResult<H256>;
+}
@@ -0,0 +1,13 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_types::{CallBundle, CallBundleResponse};
+
+/// Bundle simulation rpc interface.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+#[async_trait::async_trait]
+pub trait EthCallBundleApi {
+    /// Simulates a bundle of signed transactions on top of a block, in the style of the Flashbots
+    /// `eth_callBundle` method.
+    #[method(name = "callBundle")]
+    async fn call_bundle(&self, bundle: CallBundle) -> RpcResult<CallBundleResponse>;
+}
@@ -3,8 +3,8 @@ use reth_primitives::{BlockId, BlockNumberOrTag, Bytes, H256};
 use reth_rpc_types::{
     state::StateOverride,
     trace::geth::{
-        BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
-        TraceResult,
+        BlockTraceResult, DebugSubscriptionKind, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace, TraceResult,
     },
     Bundle, CallRequest, RichBlock, StateContext,
 };
@@ -33,14 +33,27 @@ pub trait DebugApi {
     #[method(name = "getBadBlocks")]
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>>;
 
-    /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
-    async fn debug_trace_chain(
+    /// Creates a `debug` subscription.
+    ///
+    /// The only supported kind is `traceChain`, which returns the structured logs created during
+    /// the execution of EVM between two blocks (excluding start). The blocks are traced in
+    /// parallel and a [BlockTraceResult] is streamed for every block, in order, as soon as it is
+    /// traced. If a block can't be traced, the error is sent to the subscriber and the
+    /// subscription is closed. For the last parameter see [GethDebugTracingOptions] reference.
+    ///
+    /// Subscriptions are only available over WS and IPC.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = BlockTraceResult
+    )]
+    async fn debug_subscribe(
         &self,
+        kind: DebugSubscriptionKind,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -20,6 +20,8 @@
 //! - `client`: Enables JSON-RPC client support.
 
 mod admin;
+mod anvil;
+mod bundle;
 mod debug;
 mod engine;
 mod eth;
@@ -40,6 +42,8 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
+        bundle::EthCallBundleApiServer,
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
         eth::EthApiServer,
@@ -64,6 +68,8 @@ pub use clients::*;
 pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
+        anvil::AnvilApiClient,
+        bundle::EthCallBundleApiClient,
         debug::DebugApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
         eth::EthApiClient,
@@ -1,8 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, H256};
+use reth_primitives::{Address, BlockId, Bytes, TxHash, H256};
 use reth_rpc_types::{
     BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
-    Transaction, TransactionsWithReceipts,
+    TransactionsWithReceipts,
 };
 
 /// Otterscan rpc interface.
@@ -24,21 +24,20 @@ pub trait Otterscan {
     async fn get_internal_operations(&self, tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>>;
 
     /// Given a transaction hash, returns its raw revert reason.
+    ///
+    /// Returns empty bytes if the transaction did not revert.
     #[method(name = "getTransactionError")]
-    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<String>;
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Bytes>;
 
     /// Extract all variations of calls, contract creation and self-destructs and returns a call
     /// tree.
     #[method(name = "traceTransaction")]
-    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<TraceEntry>;
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>>;
 
     /// Tailor-made and expanded version of eth_getBlockByNumber for block details page in
     /// Otterscan.
     #[method(name = "getBlockDetails")]
-    async fn get_block_details(
-        &self,
-        block_number: BlockNumberOrTag,
-    ) -> RpcResult<Option<BlockDetails>>;
+    async fn get_block_details(&self, block_number: u64) -> RpcResult<Option<BlockDetails>>;
 
     /// Tailor-made and expanded version of eth_getBlockByHash for block details page in Otterscan.
     #[method(name = "getBlockDetailsByHash")]
@@ -48,26 +47,31 @@ pub trait Otterscan {
     #[method(name = "getBlockTransactions")]
     async fn get_block_transactions(
         &self,
-        block_number: BlockNumberOrTag,
+        block_number: u64,
         page_number: usize,
         page_size: usize,
-    ) -> RpcResult<OtsBlockTransactions>;
+    ) -> RpcResult<Option<OtsBlockTransactions>>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Searches backwards, starting before the given block. A block number of `0` starts the search
+    /// at the tip of the chain.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
         address: Address,
-        block_number: BlockNumberOrTag,
+        block_number: u64,
         page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Searches forwards, starting after the given block.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
         address: Address,
-        block_number: BlockNumberOrTag,
+        block_number: u64,
         page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts>;
 
@@ -77,7 +81,7 @@ pub trait Otterscan {
         &self,
         sender: Address,
         nonce: u64,
-    ) -> RpcResult<Option<Transaction>>;
+    ) -> RpcResult<Option<TxHash>>;
 
     /// Gets the transaction hash and the address who created a contract.
     #[method(name = "getContractCreator")]
@@ -12,8 +12,8 @@ use jsonrpsee::{
 };
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, HistoryReader,
+    ReceiptProviderIdExt, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
@@ -44,6 +44,7 @@ where
         + ChainSpecProvider
         + EvmEnvProvider
         + HeaderProvider
+        + HistoryReader
         + ReceiptProviderIdExt
         + StateProviderFactory
         + Clone
@@ -67,6 +68,7 @@ where
         EthConfig::default().rpc_gas_cap,
         Box::new(executor.clone()),
         TracingCallPool::build().expect("failed to build tracing pool"),
+        Default::default(),
     );
     let eth_filter = EthFilter::new(
         provider,
@@ -91,6 +93,7 @@ where
         + ChainSpecProvider
         + EvmEnvProvider
         + HeaderProvider
+        + HistoryReader
         + StateProviderFactory
         + Clone
         + Unpin
@@ -4,7 +4,8 @@ use reth_rpc::{
         gas_oracle::GasPriceOracleConfig,
         RPC_DEFAULT_GAS_CAP,
     },
-    EthApi, EthFilter, EthPubSub, TracingCallPool,
+    EthApi, EthFilter, EthPubSub, TraceFilterLimits, TracingCallPool,
+    DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_MAX_TRACE_FILTER_TRACES,
 };
 use serde::{Deserialize, Serialize};
 
@@ -40,6 +41,10 @@ pub struct EthConfig {
     pub max_tracing_requests: u32,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of blocks a `trace_filter` request can trace.
+    pub max_trace_filter_blocks: u64,
+    /// Maximum number of traces that can be returned in a single `trace_filter` response.
+    pub max_trace_filter_traces: usize,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [RPC_DEFAULT_GAS_CAP]
@@ -53,6 +58,8 @@ impl Default for EthConfig {
             gas_oracle: GasPriceOracleConfig::default(),
             max_tracing_requests: DEFAULT_MAX_TRACING_REQUESTS,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_trace_filter_traces: DEFAULT_MAX_TRACE_FILTER_TRACES,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
         }
     }
@@ -83,9 +90,29 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of blocks a `trace_filter` request can trace
+    pub fn max_trace_filter_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_trace_filter_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the maximum number of traces per `trace_filter` response
+    pub fn max_trace_filter_traces(mut self, max_traces: usize) -> Self {
+        self.max_trace_filter_traces = max_traces;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Returns the configured limits for `trace_filter` requests
+    pub(crate) fn trace_filter_limits(&self) -> TraceFilterLimits {
+        TraceFilterLimits {
+            max_blocks: self.max_trace_filter_blocks,
+            max_traces: self.max_trace_filter_traces,
+        }
+    }
 }
@@ -31,13 +31,13 @@
 //!
 //! ```
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader};
+//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader, HistoryReader};
 //! use reth_rpc_builder::{RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig};
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::TransactionPool;
 //! pub async fn launch<Provider, Pool, Network, Events>(provider: Provider, pool: Pool, network: Network, events: Events)
 //! where
-//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
+//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + HistoryReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
 //!     Pool: TransactionPool + Clone + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions +  Clone + 'static,
@@ -64,7 +64,7 @@
 //! ```
 //! use tokio::try_join;
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader};
+//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader, HistoryReader};
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_builder::{RethRpcModule, RpcModuleBuilder, RpcServerConfig, TransportRpcModuleConfig};
 //! use reth_tasks::TokioTaskExecutor;
@@ -73,7 +73,7 @@
 //! use reth_rpc_builder::auth::AuthServerConfig;
 //! pub async fn launch<Provider, Pool, Network, Events, EngineApi>(provider: Provider, pool: Pool, network: Network, events: Events, engine_api: EngineApi)
 //! where
-//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
+//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + HistoryReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
 //!     Pool: TransactionPool + Clone + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions +  Clone + 'static,
@@ -110,20 +110,21 @@ use jsonrpsee::{
     server::{IdProvider, Server, ServerHandle},
     Methods, RpcModule,
 };
+use reth_interfaces::consensus::BadBlocks;
 use reth_ipc::server::IpcServer;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
     BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, StateProviderFactory,
+    EvmEnvProvider, HistoryReader, ImpersonatedSenders, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
         cache::{cache_new_blocks_task, EthStateCache},
         gas_oracle::GasPriceOracle,
     },
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider,
-    NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TracingCallGuard, TracingCallPool, TxPoolApi,
-    Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthApi, EthBundle, EthFilter, EthPubSub,
+    EthSubscriptionIdProvider, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TracingCallGuard,
+    TracingCallPool, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -181,6 +182,7 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
         + Clone
         + Unpin
         + 'static,
@@ -212,6 +214,10 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events> {
     executor: Tasks,
     /// Provides access to chain events, such as new blocks, required by pubsub.
     events: Events,
+    /// Blocks rejected by the consensus engine, served by `debug_getBadBlocks`.
+    bad_blocks: BadBlocks,
+    /// Senders of transactions that were sealed without a signature, e.g. by the dev chain.
+    impersonated_senders: ImpersonatedSenders,
 }
 
 // === impl RpcBuilder ===
@@ -227,7 +233,27 @@ impl<Provider, Pool, Network, Tasks, Events>
         executor: Tasks,
         events: Events,
     ) -> Self {
-        Self { provider, pool, network, executor, events }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            bad_blocks: Default::default(),
+            impersonated_senders: Default::default(),
+        }
+    }
+
+    /// Configure the store of bad blocks reported by the consensus engine.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
+    }
+
+    /// Configure the senders of impersonated transactions, see [ImpersonatedSenders].
+    pub fn with_impersonated_senders(mut self, impersonated_senders: ImpersonatedSenders) -> Self {
+        self.impersonated_senders = impersonated_senders;
+        self
     }
 
     /// Configure the provider instance.
@@ -235,8 +261,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, executor, events, bad_blocks, impersonated_senders, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            bad_blocks,
+            impersonated_senders,
+        }
     }
 
     /// Configure the transaction pool instance.
@@ -244,8 +278,17 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, network, executor, events, bad_blocks, impersonated_senders, .. } =
+            self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            bad_blocks,
+            impersonated_senders,
+        }
     }
 
     /// Configure a [NoopTransactionPool] instance.
@@ -256,13 +299,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     pub fn with_noop_pool(
         self,
     ) -> RpcModuleBuilder<Provider, NoopTransactionPool, Network, Tasks, Events> {
-        let Self { provider, executor, events, network, .. } = self;
+        let Self { provider, executor, events, network, bad_blocks, impersonated_senders, .. } =
+            self;
         RpcModuleBuilder {
             provider,
             executor,
             events,
             network,
             pool: NoopTransactionPool::default(),
+            bad_blocks,
+            impersonated_senders,
         }
     }
 
@@ -271,8 +317,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, events, bad_blocks, impersonated_senders, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            bad_blocks,
+            impersonated_senders,
+        }
     }
 
     /// Configure a [NoopNetwork] instance.
@@ -281,8 +335,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     /// This is only intended for allow easier setup of namespaces that depend on the [EthApi] which
     /// requires a [NetworkInfo] implementation.
     pub fn with_noop_network(self) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events> {
-        let Self { provider, pool, executor, events, .. } = self;
-        RpcModuleBuilder { provider, pool, executor, events, network: NoopNetwork::default() }
+        let Self { provider, pool, executor, events, bad_blocks, impersonated_senders, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            pool,
+            executor,
+            events,
+            network: NoopNetwork::default(),
+            bad_blocks,
+            impersonated_senders,
+        }
     }
 
     /// Configure the task executor to use for additional tasks.
@@ -293,8 +355,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { pool, network, provider, events, bad_blocks, impersonated_senders, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            bad_blocks,
+            impersonated_senders,
+        }
     }
 
     /// Configure [TokioTaskExecutor] as the task executor to use for additional tasks.
@@ -304,8 +374,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     pub fn with_tokio_executor(
         self,
     ) -> RpcModuleBuilder<Provider, Pool, Network, TokioTaskExecutor, Events> {
-        let Self { pool, network, provider, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, events, executor: TokioTaskExecutor::default() }
+        let Self { pool, network, provider, events, bad_blocks, impersonated_senders, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            events,
+            executor: TokioTaskExecutor::default(),
+            bad_blocks,
+            impersonated_senders,
+        }
     }
 
     /// Configure the event subscriber instance
@@ -313,8 +391,16 @@ impl<Provider, Pool, Network, Tasks, Events>
     where
         E: CanonStateSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events }
+        let Self { provider, pool, executor, network, bad_blocks, impersonated_senders, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            bad_blocks,
+            impersonated_senders,
+        }
     }
 }
 
@@ -326,6 +412,7 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
         + Clone
         + Unpin
         + 'static,
@@ -353,7 +440,8 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, bad_blocks, impersonated_senders } =
+            self;
 
         let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
 
@@ -364,7 +452,9 @@ where
             executor,
             events,
             config.unwrap_or_default(),
-        );
+        )
+        .with_bad_blocks(bad_blocks)
+        .with_impersonated_senders(impersonated_senders);
 
         modules.config = module_config;
         modules.http = registry.maybe_module(http.as_ref());
@@ -383,7 +473,8 @@ where
     pub fn build(self, module_config: TransportRpcModuleConfig) -> TransportRpcModules<()> {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events } = self;
+        let Self { provider, pool, network, executor, events, bad_blocks, impersonated_senders } =
+            self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -395,7 +486,9 @@ where
                 executor,
                 events,
                 config.unwrap_or_default(),
-            );
+            )
+            .with_bad_blocks(bad_blocks)
+            .with_impersonated_senders(impersonated_senders);
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -554,6 +647,7 @@ impl RpcModuleSelection {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + HistoryReader
             + Clone
             + Unpin
             + 'static,
@@ -661,6 +755,8 @@ pub enum RethRpcModule {
     Reth,
     /// `ots_` module
     Ots,
+    /// `eth_callBundle` bundle simulation
+    EthCallBundle,
 }
 
 // === impl RethRpcModule ===
@@ -702,6 +798,10 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events> {
     tracing_call_guard: TracingCallGuard,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
+    /// Blocks rejected by the consensus engine.
+    bad_blocks: BadBlocks,
+    /// Senders of transactions that were sealed without a signature.
+    impersonated_senders: ImpersonatedSenders,
 }
 
 // === impl RethModuleRegistry ===
@@ -728,9 +828,23 @@ impl<Provider, Pool, Network, Tasks, Events>
             tracing_call_guard: TracingCallGuard::new(config.eth.max_tracing_requests),
             config,
             events,
+            bad_blocks: Default::default(),
+            impersonated_senders: Default::default(),
         }
     }
 
+    /// Configures the store of bad blocks served by the `debug` namespace.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
+    }
+
+    /// Configures the senders of impersonated transactions consulted by the `eth` namespace.
+    pub fn with_impersonated_senders(mut self, impersonated_senders: ImpersonatedSenders) -> Self {
+        self.impersonated_senders = impersonated_senders;
+        self
+    }
+
     /// Returns a reference to the pool
     pub fn pool(&self) -> &Pool {
         &self.pool
@@ -794,6 +908,7 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
         + Clone
         + Unpin
         + 'static,
@@ -812,7 +927,20 @@ where
     /// Register Otterscan Namespace
     pub fn register_ots(&mut self) -> &mut Self {
         let eth_api = self.eth_api();
-        self.modules.insert(RethRpcModule::Ots, OtterscanApi::new(eth_api).into_rpc().into());
+        self.modules.insert(
+            RethRpcModule::Ots,
+            OtterscanApi::new(self.provider.clone(), eth_api).into_rpc().into(),
+        );
+        self
+    }
+
+    /// Register the `eth_callBundle` bundle simulation endpoint
+    pub fn register_eth_call_bundle(&mut self) -> &mut Self {
+        let eth_api = self.eth_api();
+        self.modules.insert(
+            RethRpcModule::EthCallBundle,
+            EthBundle::new(eth_api, self.tracing_call_guard.clone()).into_rpc().into(),
+        );
         self
     }
 
@@ -826,6 +954,7 @@ where
                 eth_api,
                 Box::new(self.executor.clone()),
                 self.tracing_call_guard.clone(),
+                self.bad_blocks.clone(),
             )
             .into_rpc()
             .into(),
@@ -838,9 +967,14 @@ where
         let eth = self.eth_handlers();
         self.modules.insert(
             RethRpcModule::Trace,
-            TraceApi::new(self.provider.clone(), eth.api, self.tracing_call_guard.clone())
-                .into_rpc()
-                .into(),
+            TraceApi::new(
+                self.provider.clone(),
+                eth.api,
+                self.tracing_call_guard.clone(),
+                self.config.eth.trace_filter_limits(),
+            )
+            .into_rpc()
+            .into(),
         );
         self
     }
@@ -937,6 +1071,7 @@ where
                             eth_api.clone(),
                             Box::new(self.executor.clone()),
                             self.tracing_call_guard.clone(),
+                            self.bad_blocks.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -955,6 +1090,7 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.tracing_call_guard.clone(),
+                            self.config.eth.trace_filter_limits(),
                         )
                         .into_rpc()
                         .into(),
@@ -970,12 +1106,21 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
+                                .into_rpc()
+                                .into()
+                        }
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::EthCallBundle => {
+                            EthBundle::new(eth_api.clone(), self.tracing_call_guard.clone())
+                                .into_rpc()
+                                .into()
+                        }
                     })
                     .clone()
             })
@@ -1026,6 +1171,7 @@ where
                 self.config.eth.rpc_gas_cap,
                 executor.clone(),
                 tracing_call_pool.clone(),
+                self.impersonated_senders.clone(),
             );
             let filter = EthFilter::new(
                 self.provider.clone(),
@@ -1536,6 +1682,21 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Merge the given Methods in all configured methods, replacing configured methods with the
+    /// same name.
+    pub fn replace_configured(
+        &mut self,
+        other: impl Into<Methods>,
+    ) -> Result<(), jsonrpsee::core::error::Error> {
+        let other = other.into();
+        for module in [&mut self.http, &mut self.ws, &mut self.ipc].into_iter().flatten() {
+            for name in other.method_names() {
+                module.remove(name);
+            }
+        }
+        self.merge_configured(other)
+    }
+
     /// Convenience function for starting a server
     pub async fn start_server(self, builder: RpcServerConfig) -> Result<RpcServerHandle, RpcError> {
         builder.start(self).await
@@ -1872,6 +2033,7 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "eth-call-bundle" => RethRpcModule::EthCallBundle,
             );
     }
 
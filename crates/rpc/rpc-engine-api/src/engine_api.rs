@@ -189,6 +189,28 @@ where
             .map(|payload| (*payload).clone().into_v2_payload())?)
     }
 
+    /// Returns the most recent version of the payload that is available in the corresponding
+    /// payload build process at the time of receiving this call.
+    ///
+    /// This also includes the blobs bundle of all blob transactions included in the payload.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getpayloadv3>
+    ///
+    /// Note:
+    /// > Provider software MAY stop the corresponding build process after serving this call.
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> EngineApiResult<ExecutionPayloadEnvelope> {
+        Ok(self
+            .inner
+            .payload_store
+            .resolve(payload_id)
+            .await
+            .ok_or(EngineApiError::UnknownPayload)?
+            .map(|payload| (*payload).clone().into_v3_payload())?)
+    }
+
     /// Returns the execution payload bodies by the range starting at `start`, containing `count`
     /// blocks.
     ///
@@ -449,8 +471,18 @@ where
         Ok(EngineApi::get_payload_v2(self, payload_id).await?)
     }
 
-    async fn get_payload_v3(&self, _payload_id: PayloadId) -> RpcResult<ExecutionPayloadEnvelope> {
-        Err(jsonrpsee_types::error::ErrorCode::MethodNotFound.into())
+    /// Handler for `engine_getPayloadV3`
+    ///
+    /// Returns the most recent version of the payload that is available in the corresponding
+    /// payload build process at the time of receiving this call.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getpayloadv3>
+    ///
+    /// Note:
+    /// > Provider software MAY stop the corresponding build process after serving this call.
+    async fn get_payload_v3(&self, payload_id: PayloadId) -> RpcResult<ExecutionPayloadEnvelope> {
+        trace!(target: "rpc::engine", "Serving engine_getPayloadV3");
+        Ok(EngineApi::get_payload_v3(self, payload_id).await?)
     }
 
     /// Handler for `engine_getPayloadBodiesByHashV1`
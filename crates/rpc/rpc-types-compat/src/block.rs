@@ -1,7 +1,10 @@
 //! Compatibility functions for rpc `Block` type.
 
 use crate::transaction::from_recovered_with_block_context;
-use reth_primitives::{Block as PrimitiveBlock, Header as PrimitiveHeader, H256, U256};
+use reth_primitives::{
+    Block as PrimitiveBlock, BlockWithSenders, Header as PrimitiveHeader,
+    TransactionSignedEcRecovered, H256, U256,
+};
 use reth_rlp::Encodable;
 use reth_rpc_types::{Block, BlockError, BlockTransactions, BlockTransactionsKind, Header};
 
@@ -10,14 +13,14 @@ use reth_rpc_types::{Block, BlockError, BlockTransactions, BlockTransactionsKind
 ///
 /// If a `block_hash` is provided, then this is used, otherwise the block hash is computed.
 pub fn from_block(
-    block: PrimitiveBlock,
+    block: BlockWithSenders,
     total_difficulty: U256,
     kind: BlockTransactionsKind,
     block_hash: Option<H256>,
 ) -> Result<Block, BlockError> {
     match kind {
         BlockTransactionsKind::Hashes => {
-            Ok(from_block_with_tx_hashes(block, total_difficulty, block_hash))
+            Ok(from_block_with_tx_hashes(block.block, total_difficulty, block_hash))
         }
         BlockTransactionsKind::Full => from_block_full(block, total_difficulty, block_hash),
     }
@@ -49,16 +52,22 @@ pub fn from_block_with_tx_hashes(
 ///
 /// This will populate the `transactions` field with the _full_
 /// [Transaction](reth_rpc_types::Transaction) objects: [BlockTransactions::Full]
+///
+/// The senders of the block are used as the `from` field of the transactions.
 pub fn from_block_full(
-    block: PrimitiveBlock,
+    block: BlockWithSenders,
     total_difficulty: U256,
     block_hash: Option<H256>,
 ) -> Result<Block, BlockError> {
+    let (block, senders) = block.into_components();
+    if block.body.len() != senders.len() {
+        return Err(BlockError::InvalidSignature)
+    }
     let block_hash = block_hash.unwrap_or_else(|| block.header.hash_slow());
     let block_number = block.number;
     let mut transactions = Vec::with_capacity(block.body.len());
-    for (idx, tx) in block.body.iter().enumerate() {
-        let signed_tx = tx.clone().into_ecrecovered().ok_or(BlockError::InvalidSignature)?;
+    for (idx, (tx, sender)) in block.body.iter().zip(senders).enumerate() {
+        let signed_tx = TransactionSignedEcRecovered::from_signed_transaction(tx.clone(), sender);
         transactions.push(from_recovered_with_block_context(
             signed_tx,
             block_hash,
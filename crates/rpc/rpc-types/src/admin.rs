@@ -88,6 +88,27 @@ pub struct EthProtocolInfo {
     pub genesis: H256,
 }
 
+/// A peer id or IP address on the node's ban list.
+///
+/// Serialized as the plain peer id or IP address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BanTarget {
+    /// The node with the given id.
+    PeerId(PeerId),
+    /// All nodes with the given IP address.
+    Ip(IpAddr),
+}
+
+/// An entry of the node's ban list, see `admin_bannedPeers`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanEntry {
+    /// The banned peer id or IP address.
+    pub target: BanTarget,
+    /// Unix timestamp in seconds until the ban expires, `None` if the ban doesn't expire.
+    pub until: Option<u64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +122,15 @@ mod tests {
         let de_serialized: NodeInfo = serde_json::from_str(&serialized).unwrap();
         assert_eq!(info, de_serialized)
     }
+
+    #[test]
+    fn test_parse_ban_target() {
+        let ip: BanTarget = serde_json::from_str(r#""1.1.1.1""#).unwrap();
+        assert_eq!(ip, BanTarget::Ip(IpAddr::from([1, 1, 1, 1])));
+
+        let peer_id = PeerId::random();
+        let serialized = serde_json::to_string(&BanTarget::PeerId(peer_id)).unwrap();
+        let target: BanTarget = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(target, BanTarget::PeerId(peer_id));
+    }
 }
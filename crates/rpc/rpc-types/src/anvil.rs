@@ -0,0 +1,70 @@
+//! Types for the Anvil and Hardhat compatible dev chain methods.
+
+use reth_primitives::U64;
+use serde::{Deserialize, Serialize};
+
+/// Options for `evm_mine`.
+///
+/// Hardhat only accepts an optional timestamp, Anvil additionally accepts an object with the
+/// timestamp of the first block and the number of blocks to mine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MineOptions {
+    /// Mine a number of blocks.
+    Options {
+        /// Timestamp of the first mined block.
+        timestamp: Option<U64>,
+        /// Number of blocks to mine, defaults to one.
+        blocks: Option<U64>,
+    },
+    /// Mine a single block with the given timestamp.
+    Timestamp(Option<U64>),
+}
+
+// === impl MineOptions ===
+
+impl MineOptions {
+    /// Returns the timestamp of the first block to mine, if set.
+    pub fn timestamp(&self) -> Option<u64> {
+        match self {
+            MineOptions::Options { timestamp, .. } | MineOptions::Timestamp(timestamp) => {
+                timestamp.map(|timestamp| timestamp.as_u64())
+            }
+        }
+    }
+
+    /// Returns the number of blocks to mine.
+    pub fn blocks(&self) -> u64 {
+        match self {
+            MineOptions::Options { blocks, .. } => blocks.map_or(1, |blocks| blocks.as_u64()),
+            MineOptions::Timestamp(_) => 1,
+        }
+    }
+}
+
+impl Default for MineOptions {
+    fn default() -> Self {
+        MineOptions::Timestamp(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_mine_options() {
+        let opts: MineOptions = serde_json::from_str("\"0x64\"").unwrap();
+        assert_eq!(opts.timestamp(), Some(100));
+        assert_eq!(opts.blocks(), 1);
+
+        let opts: MineOptions =
+            serde_json::from_str(r#"{"timestamp":"0x64","blocks":"0x3"}"#).unwrap();
+        assert_eq!(opts.timestamp(), Some(100));
+        assert_eq!(opts.blocks(), 3);
+
+        let opts: MineOptions = serde_json::from_str(r#"{"blocks":"0x2"}"#).unwrap();
+        assert_eq!(opts.timestamp(), None);
+        assert_eq!(opts.blocks(), 2);
+    }
+}
@@ -0,0 +1,112 @@
+//! Types for bundle simulation via `eth_callBundle`.
+
+use crate::Log;
+use reth_primitives::{Address, BlockNumberOrTag, Bytes, H256, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// Bundle of signed transactions that are simulated on top of a block, see
+/// <https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#eth_callbundle>
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundle {
+    /// The raw, signed transactions of the bundle, executed in order.
+    pub txs: Vec<Bytes>,
+    /// The number of the block the bundle is simulated for.
+    pub block_number: U64,
+    /// The block whose state the bundle is executed on.
+    pub state_block_number: BlockNumberOrTag,
+    /// Timestamp of the simulated block, defaults to the timestamp of the state block plus 12
+    /// seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<U64>,
+    /// Beneficiary of the simulated block, defaults to the beneficiary of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    /// Gas limit of the simulated block, defaults to the gas limit of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    /// Difficulty of the simulated block, defaults to the difficulty of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<U256>,
+    /// Base fee of the simulated block, defaults to the base fee that follows the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee: Option<U256>,
+}
+
+/// Result of a simulated bundle.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResponse {
+    /// Hash of the bundle, the keccak256 of the concatenated transaction hashes.
+    pub bundle_hash: H256,
+    /// Average gas price paid to the coinbase per unit of gas, `coinbase_diff / total_gas_used`.
+    pub bundle_gas_price: U256,
+    /// Balance change of the coinbase caused by the bundle.
+    pub coinbase_diff: U256,
+    /// Ether that was transferred to the coinbase directly, i.e. not as gas fees.
+    pub eth_sent_to_coinbase: U256,
+    /// Priority fees paid by the transactions of the bundle.
+    pub gas_fees: U256,
+    /// Results of the individual transactions.
+    pub results: Vec<CallBundleTransactionResult>,
+    /// Number of the block whose state the bundle was executed on.
+    pub state_block_number: U64,
+    /// Gas used by all transactions of the bundle.
+    pub total_gas_used: U64,
+}
+
+/// Result of a single transaction of a simulated bundle.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleTransactionResult {
+    /// Hash of the transaction.
+    pub tx_hash: H256,
+    /// Sender of the transaction.
+    pub from_address: Address,
+    /// Recipient of the transaction, `None` for contract creations.
+    pub to_address: Option<Address>,
+    /// Gas used by the transaction.
+    pub gas_used: U64,
+    /// Average gas price paid to the coinbase per unit of gas, `coinbase_diff / gas_used`.
+    pub gas_price: U256,
+    /// Gas price the transaction paid, including the base fee.
+    pub effective_gas_price: U256,
+    /// Balance change of the coinbase caused by the transaction.
+    pub coinbase_diff: U256,
+    /// Ether that was transferred to the coinbase directly, i.e. not as gas fees.
+    pub eth_sent_to_coinbase: U256,
+    /// Priority fees paid by the transaction.
+    pub gas_fees: U256,
+    /// Output of the transaction if it succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// Error message if the transaction reverted or halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Decoded revert reason if the transaction reverted with one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<String>,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_call_bundle() {
+        let s = r#"{
+            "txs": ["0x01", "0x02"],
+            "blockNumber": "0x10",
+            "stateBlockNumber": "latest",
+            "timestamp": "0x64"
+        }"#;
+        let bundle: CallBundle = serde_json::from_str(s).unwrap();
+        assert_eq!(bundle.txs.len(), 2);
+        assert_eq!(bundle.block_number, U64::from(16));
+        assert_eq!(bundle.state_block_number, BlockNumberOrTag::Latest);
+        assert_eq!(bundle.timestamp, Some(U64::from(100)));
+        assert_eq!(bundle.coinbase, None);
+    }
+}
@@ -9,12 +9,13 @@ mod transition;
 pub use self::{forkchoice::*, payload::*, transition::*};
 
 /// The list of supported Engine capabilities
-pub const CAPABILITIES: [&str; 9] = [
+pub const CAPABILITIES: [&str; 10] = [
     "engine_forkchoiceUpdatedV1",
     "engine_forkchoiceUpdatedV2",
     "engine_exchangeTransitionConfigurationV1",
     "engine_getPayloadV1",
     "engine_getPayloadV2",
+    "engine_getPayloadV3",
     "engine_newPayloadV1",
     "engine_newPayloadV2",
     "engine_getPayloadBodiesByHashV1",
@@ -1,8 +1,8 @@
 use reth_primitives::{
     constants::{MAXIMUM_EXTRA_DATA_SIZE, MIN_PROTOCOL_BASE_FEE_U256},
     proofs::{self, EMPTY_LIST_HASH},
-    Address, Block, Bloom, Bytes, Header, SealedBlock, TransactionSigned, UintTryTo, Withdrawal,
-    H256, H64, U256, U64,
+    Address, BlobTransactionSidecar, Block, Bloom, Bytes, Header, SealedBlock, TransactionSigned,
+    UintTryTo, Withdrawal, H256, H64, U256, U64,
 };
 use reth_rlp::Decodable;
 use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
@@ -29,9 +29,11 @@ impl std::fmt::Display for PayloadId {
     }
 }
 
-/// This structure maps for the return value of `engine_getPayloadV2` of the beacon chain spec.
+/// This structure maps for the return value of `engine_getPayloadV2` and `engine_getPayloadV3` of
+/// the beacon chain spec.
 ///
 /// See also: <https://github.com/ethereum/execution-apis/blob/main/src/engine/shanghai.md#engine_getpayloadv2>
+/// and <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getpayloadv3>
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutionPayloadEnvelope {
     /// Execution payload, which could be either V1 or V2
@@ -46,10 +48,11 @@ pub struct ExecutionPayloadEnvelope {
     /// The expected value to be received by the feeRecipient in wei
     #[serde(rename = "blockValue")]
     pub block_value: U256,
-    //
-    // // TODO(mattsse): for V3
-    // #[serde(rename = "blobsBundle", skip_serializing_if = "Option::is_none")]
-    // pub blobs_bundle: Option<BlobsBundleV1>,
+    /// The blobs, commitments, and proofs associated with the executed payload.
+    ///
+    /// Only set for `engine_getPayloadV3`.
+    #[serde(rename = "blobsBundle", default, skip_serializing_if = "Option::is_none")]
+    pub blobs_bundle: Option<BlobsBundleV1>,
 }
 
 impl ExecutionPayloadEnvelope {
@@ -207,6 +210,20 @@ pub struct BlobsBundleV1 {
     pub blobs: Vec<Bytes>,
 }
 
+impl From<Vec<BlobTransactionSidecar>> for BlobsBundleV1 {
+    fn from(sidecars: Vec<BlobTransactionSidecar>) -> Self {
+        let mut bundle = BlobsBundleV1 { commitments: vec![], proofs: vec![], blobs: vec![] };
+        for sidecar in sidecars {
+            bundle
+                .commitments
+                .extend(sidecar.commitments.iter().map(|commitment| commitment.to_vec().into()));
+            bundle.proofs.extend(sidecar.proofs.iter().map(|proof| proof.to_vec().into()));
+            bundle.blobs.extend(sidecar.blobs.iter().map(|blob| blob.to_vec().into()));
+        }
+        bundle
+    }
+}
+
 /// Error that can occur when handling payloads.
 #[derive(thiserror::Error, Debug)]
 pub enum PayloadError {
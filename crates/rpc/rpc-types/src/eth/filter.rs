@@ -90,6 +90,11 @@ impl<T: Eq + Hash> FilterSet<T> {
     pub fn matches(&self, value: &T) -> bool {
         self.is_empty() || self.0.contains(value)
     }
+
+    /// Returns an iterator over the values of the filter
+    pub fn iter(&self) -> std::collections::hash_set::Iter<'_, T> {
+        self.0.iter()
+    }
 }
 
 impl<T: AsRef<[u8]> + Eq + Hash> FilterSet<T> {
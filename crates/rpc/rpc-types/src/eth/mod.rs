@@ -2,6 +2,7 @@
 
 mod account;
 mod block;
+mod bundle;
 mod call;
 pub mod engine;
 pub mod error;
@@ -19,6 +20,7 @@ mod work;
 
 pub use account::*;
 pub use block::*;
+pub use bundle::{CallBundle, CallBundleResponse, CallBundleTransactionResult};
 pub use call::{Bundle, CallInput, CallInputError, CallRequest, EthCallResponse, StateContext};
 pub use fee::{FeeHistory, TxGasAndReward};
 pub use filter::*;
@@ -1,9 +1,11 @@
 //! `trace_filter` types and support
+use super::parity::{Action, TraceOutput, TransactionTrace};
 use reth_primitives::{serde_helper::num::u64_hex_or_decimal_opt, Address};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Trace filter.
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
 pub struct TraceFilter {
@@ -23,9 +25,63 @@ pub struct TraceFilter {
     pub count: Option<u64>,
 }
 
+// === impl TraceFilter ===
+
+impl TraceFilter {
+    /// Returns all addresses of the filter, from and to.
+    pub fn addresses(&self) -> impl Iterator<Item = Address> + '_ {
+        self.from_address.iter().chain(self.to_address.iter()).flatten().copied()
+    }
+
+    /// Returns a [TraceFilterMatcher] for this filter.
+    pub fn matcher(&self) -> TraceFilterMatcher {
+        TraceFilterMatcher {
+            from_addresses: self.from_address.iter().flatten().copied().collect(),
+            to_addresses: self.to_address.iter().flatten().copied().collect(),
+        }
+    }
+}
+
+/// Helper type for matching [TransactionTrace]s against the addresses of a [TraceFilter].
+///
+/// A trace matches if its sender is in the set of `from` addresses _and_ its recipient is in the
+/// set of `to` addresses. An empty set matches any address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceFilterMatcher {
+    from_addresses: HashSet<Address>,
+    to_addresses: HashSet<Address>,
+}
+
+impl TraceFilterMatcher {
+    /// Returns true if the trace matches the filter.
+    pub fn matches(&self, trace: &TransactionTrace) -> bool {
+        let (from, to) = match &trace.action {
+            Action::Call(call) => (Some(call.from), Some(call.to)),
+            Action::Create(create) => {
+                let created = match &trace.result {
+                    Some(TraceOutput::Create(output)) => Some(output.address),
+                    _ => None,
+                };
+                (Some(create.from), created)
+            }
+            Action::Selfdestruct(selfdestruct) => {
+                (Some(selfdestruct.address), Some(selfdestruct.refund_address))
+            }
+            Action::Reward(reward) => (None, Some(reward.author)),
+        };
+
+        let from_matches = self.from_addresses.is_empty() ||
+            from.map_or(false, |from| self.from_addresses.contains(&from));
+        let to_matches =
+            self.to_addresses.is_empty() || to.map_or(false, |to| self.to_addresses.contains(&to));
+        from_matches && to_matches
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::eth::trace::parity::{CallAction, CallType};
 
     #[test]
     fn test_parse_filter() {
@@ -34,4 +90,39 @@ mod tests {
         assert_eq!(filter.from_block, Some(3));
         assert_eq!(filter.to_block, Some(5));
     }
+
+    #[test]
+    fn test_filter_matcher() {
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+        let trace = TransactionTrace {
+            action: Action::Call(CallAction {
+                from,
+                call_type: CallType::Call,
+                gas: Default::default(),
+                input: Default::default(),
+                to,
+                value: Default::default(),
+            }),
+            error: None,
+            result: None,
+            subtraces: 0,
+            trace_address: vec![],
+        };
+
+        assert!(TraceFilter::default().matcher().matches(&trace));
+
+        let filter = TraceFilter { from_address: Some(vec![from]), ..Default::default() };
+        assert!(filter.matcher().matches(&trace));
+
+        let filter = TraceFilter { to_address: Some(vec![from]), ..Default::default() };
+        assert!(!filter.matcher().matches(&trace));
+
+        let filter = TraceFilter {
+            from_address: Some(vec![from]),
+            to_address: Some(vec![to]),
+            ..Default::default()
+        };
+        assert!(filter.matcher().matches(&trace));
+    }
 }
@@ -0,0 +1,79 @@
+use crate::trace::parity::LocalizedTransactionTrace;
+use serde::{Deserialize, Serialize};
+
+/// The response object for `debug_traceTransaction` with `"tracer": "flatCallTracer"`
+///
+/// This is the list of Parity-style flat call traces of the transaction.
+///
+/// <https://github.com/ethereum/go-ethereum/blob/0b1438c9d5a1b0ebdc1dc7d5afcd3ddba2b04f6e/eth/tracers/native/call_flat.go#L115>
+pub type FlatCallFrame = Vec<LocalizedTransactionTrace>;
+
+/// Config for the flat call tracer.
+///
+/// Note: errors are always reported in the Parity format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallConfig {
+    /// If true, calls to precompiles are included in the traces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_precompiles: Option<bool>,
+}
+
+impl FlatCallConfig {
+    /// Returns true if calls to precompiles should be traced.
+    pub fn is_include_precompiles(&self) -> bool {
+        self.include_precompiles.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::geth::*;
+
+    const DEFAULT: &str = r#"[{
+        "action": {
+            "from": "0x3b873a919aa0512d5a0f09e6dcceaa4a6727fafe",
+            "callType": "call",
+            "gas": "0x4ddd6",
+            "input": "0x",
+            "to": "0x0000000000000000000000000000000000000004",
+            "value": "0x0"
+        },
+        "blockHash": "0xb05fde4eb63a5e1d7dd1ba0d6bf29d4c7d9bdfe7ccc1d0e0c4b7dd0d0e7a4a64",
+        "blockNumber": 1,
+        "result": {
+            "gasUsed": "0x0",
+            "output": "0x"
+        },
+        "subtraces": 0,
+        "traceAddress": [],
+        "transactionHash": "0xc4f4f3fcfa2a95d8b5e6c5b14e8d6c5fc22fe3b0c6fab1c8f2b0b4a2d0f5e5d4",
+        "transactionPosition": 0,
+        "type": "call"
+    }]"#;
+
+    #[test]
+    fn test_serialize_flat_call_trace() {
+        let mut opts = GethDebugTracingCallOptions::default();
+        opts.tracing_options.tracer =
+            Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::FlatCallTracer));
+        opts.tracing_options.tracer_config =
+            serde_json::to_value(FlatCallConfig { include_precompiles: Some(true) })
+                .unwrap()
+                .into();
+
+        assert_eq!(
+            serde_json::to_string(&opts).unwrap(),
+            r#"{"tracer":"flatCallTracer","tracerConfig":{"includePrecompiles":true}}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_flat_call_trace() {
+        let trace: FlatCallFrame = serde_json::from_str(DEFAULT).unwrap();
+        assert_eq!(trace.len(), 1);
+        let trace: GethTrace = serde_json::from_str(DEFAULT).unwrap();
+        assert!(matches!(trace, GethTrace::FlatCallTracer(_)));
+    }
+}
@@ -9,13 +9,17 @@ use std::collections::BTreeMap;
 // re-exports
 pub use self::{
     call::{CallConfig, CallFrame, CallLogFrame},
+    flat_call::{FlatCallConfig, FlatCallFrame},
     four_byte::FourByteFrame,
+    mux::{MuxConfig, MuxFrame},
     noop::NoopFrame,
     pre_state::{AccountState, DiffMode, PreStateConfig, PreStateFrame, PreStateMode},
 };
 
 mod call;
+mod flat_call;
 mod four_byte;
+mod mux;
 mod noop;
 mod pre_state;
 
@@ -34,6 +38,16 @@ pub struct BlockTraceResult {
     pub traces: Vec<TraceResult>,
 }
 
+/// The kind of a `debug_subscribe` subscription.
+///
+/// Ref <https://github.com/ethereum/go-ethereum/blob/ee530c0d5aa70d2c00ab5691a89ab431b73f8165/eth/tracers/api.go#L229-L230>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugSubscriptionKind {
+    /// Streams a [BlockTraceResult] for every block of a block range.
+    TraceChain,
+}
+
 /// Geth Default struct log trace frame
 ///
 /// <https://github.com/ethereum/go-ethereum/blob/a9ef135e2dd53682d106c6a2aede9187026cc1de/eth/tracers/logger/logger.go#L406-L411>
@@ -107,8 +121,12 @@ pub enum GethTrace {
     FourByteTracer(FourByteFrame),
     /// The response for pre-state byte tracer
     PreStateTracer(PreStateFrame),
+    /// The response for flat call tracer
+    FlatCallTracer(FlatCallFrame),
     /// An empty json response
     NoopTracer(NoopFrame),
+    /// The response for mux tracer
+    MuxTracer(MuxFrame),
     /// Any other trace response, such as custom javascript response objects
     JS(serde_json::Value),
 }
@@ -137,16 +155,28 @@ impl From<PreStateFrame> for GethTrace {
     }
 }
 
+impl From<FlatCallFrame> for GethTrace {
+    fn from(value: FlatCallFrame) -> Self {
+        GethTrace::FlatCallTracer(value)
+    }
+}
+
 impl From<NoopFrame> for GethTrace {
     fn from(value: NoopFrame) -> Self {
         GethTrace::NoopTracer(value)
     }
 }
 
+impl From<MuxFrame> for GethTrace {
+    fn from(value: MuxFrame) -> Self {
+        GethTrace::MuxTracer(value)
+    }
+}
+
 /// Available built-in tracers
 ///
 /// See <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Deserialize, Serialize)]
 pub enum GethDebugBuiltInTracerType {
     /// The 4byteTracer collects the function selectors of every function executed in the lifetime
     /// of a transaction, along with the size of the supplied call data. The result is a
@@ -172,6 +202,16 @@ pub enum GethDebugBuiltInTracerType {
     /// This tracer is noop. It returns an empty object and is only meant for testing the setup.
     #[serde(rename = "noopTracer")]
     NoopTracer,
+    /// The flatCallTracer tracks all the call frames executed during a transaction like the
+    /// callTracer, but returns them as a flat list of Parity-style call traces. The result is a
+    /// [FlatCallFrame].
+    #[serde(rename = "flatCallTracer")]
+    FlatCallTracer,
+    /// The muxTracer runs multiple built-in tracers in a single pass over the transaction. The
+    /// result is a [MuxFrame] that holds the result of every configured tracer, keyed by tracer
+    /// type.
+    #[serde(rename = "muxTracer")]
+    MuxTracer,
 }
 
 /// Available tracers
@@ -227,6 +267,22 @@ impl GethDebugTracerConfig {
         }
         self.from_value()
     }
+
+    /// Returns the [FlatCallConfig] if it is a flat call config.
+    pub fn into_flat_call_config(self) -> Result<FlatCallConfig, serde_json::Error> {
+        if self.0.is_null() {
+            return Ok(Default::default())
+        }
+        self.from_value()
+    }
+
+    /// Returns the [MuxConfig] if it is a mux config.
+    pub fn into_mux_config(self) -> Result<MuxConfig, serde_json::Error> {
+        if self.0.is_null() {
+            return Ok(Default::default())
+        }
+        self.from_value()
+    }
 }
 
 impl From<serde_json::Value> for GethDebugTracerConfig {
@@ -255,7 +311,8 @@ pub struct GethDebugTracingOptions {
     /// tracerConfig is slated for Geth v1.11.0
     /// See <https://github.com/ethereum/go-ethereum/issues/26513>
     ///
-    /// This could be [CallConfig] or [PreStateConfig] depending on the tracer.
+    /// This could be [CallConfig], [PreStateConfig], [FlatCallConfig] or [MuxConfig] depending on
+    /// the tracer.
     #[serde(default, skip_serializing_if = "GethDebugTracerConfig::is_null")]
     pub tracer_config: GethDebugTracerConfig,
     /// A string of decimal integers that overrides the JavaScript-based tracing calls default
@@ -0,0 +1,63 @@
+use crate::trace::geth::{GethDebugBuiltInTracerType, GethDebugTracerConfig, GethTrace};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Config for the mux tracer: the built-in tracers to run, keyed by tracer type, with the config
+/// for each of them.
+///
+/// <https://github.com/ethereum/go-ethereum/blob/0b1438c9d5a1b0ebdc1dc7d5afcd3ddba2b04f6e/eth/tracers/native/mux.go#L39-L63>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MuxConfig(pub BTreeMap<GethDebugBuiltInTracerType, GethDebugTracerConfig>);
+
+/// The response object for `debug_traceTransaction` with `"tracer": "muxTracer"`
+///
+/// The result of every configured tracer, keyed by tracer type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MuxFrame(pub BTreeMap<GethDebugBuiltInTracerType, GethTrace>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::geth::*;
+
+    #[test]
+    fn test_serialize_mux_trace() {
+        let mut opts = GethDebugTracingCallOptions::default();
+        opts.tracing_options.tracer =
+            Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::MuxTracer));
+        opts.tracing_options.tracer_config = serde_json::to_value(MuxConfig(BTreeMap::from([
+            (
+                GethDebugBuiltInTracerType::CallTracer,
+                serde_json::to_value(CallConfig { only_top_call: Some(true), with_log: None })
+                    .unwrap()
+                    .into(),
+            ),
+            (GethDebugBuiltInTracerType::FourByteTracer, serde_json::Value::Null.into()),
+        ])))
+        .unwrap()
+        .into();
+
+        assert_eq!(
+            serde_json::to_string(&opts).unwrap(),
+            r#"{"tracer":"muxTracer","tracerConfig":{"4byteTracer":null,"callTracer":{"onlyTopCall":true}}}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_mux_config() {
+        let s = r#"{"callTracer":{"withLog":true},"prestateTracer":{"diffMode":true},"4byteTracer":null}"#;
+        let config: MuxConfig = serde_json::from_str(s).unwrap();
+        assert_eq!(config.0.len(), 3);
+        let call_config =
+            config.0[&GethDebugBuiltInTracerType::CallTracer].clone().into_call_config().unwrap();
+        assert_eq!(call_config.with_log, Some(true));
+        assert!(config.0[&GethDebugBuiltInTracerType::FourByteTracer].is_null());
+    }
+
+    #[test]
+    fn test_deserialize_mux_trace() {
+        let s = r#"{"4byteTracer":{"0x27dc297e-128":1},"noopTracer":{}}"#;
+        let trace: MuxFrame = serde_json::from_str(s).unwrap();
+        assert_eq!(trace.0.len(), 2);
+    }
+}
@@ -16,11 +16,13 @@
 //! Provides all relevant types for the various RPC endpoints, grouped by namespace.
 
 mod admin;
+mod anvil;
 mod eth;
 mod otterscan;
 mod rpc;
 
 pub use admin::*;
+pub use anvil::*;
 pub use eth::*;
 pub use otterscan::*;
 pub use rpc::*;
@@ -1,9 +1,11 @@
 use crate::{Block, BlockTransactions, Rich, Transaction, TransactionReceipt};
-use reth_primitives::{Address, Bytes, U256};
-use serde::{Deserialize, Serialize};
+use reth_primitives::{Address, Bytes, H256, U256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Operation type enum for `InternalOperation` struct
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Serialized as its integer discriminant, which is what Otterscan expects.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OperationType {
     /// Operation Transfer
     OpTransfer = 0,
@@ -15,67 +17,116 @@ pub enum OperationType {
     OpCreate2 = 3,
 }
 
+impl Serialize for OperationType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(OperationType::OpTransfer),
+            1 => Ok(OperationType::OpSelfDestruct),
+            2 => Ok(OperationType::OpCreate),
+            3 => Ok(OperationType::OpCreate2),
+            ty => Err(serde::de::Error::custom(format!("unknown operation type {ty}"))),
+        }
+    }
+}
+
 /// Custom struct for otterscan `getInternalOperations` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InternalOperation {
-    r#type: OperationType,
-    from: Address,
-    to: Address,
-    value: u128,
+    /// The kind of the operation
+    pub r#type: OperationType,
+    /// The address the value was moved from
+    pub from: Address,
+    /// The address the value was moved to, or the address of the created contract
+    pub to: Address,
+    /// The value that was moved
+    pub value: U256,
 }
 
 /// Custom struct for otterscan `traceTransaction` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TraceEntry {
-    r#type: String,
-    depth: u32,
-    from: Address,
-    to: Address,
-    value: u128,
-    input: Bytes,
+    /// The type of the call, e.g. `CALL`, `DELEGATECALL` or `SELFDESTRUCT`
+    pub r#type: String,
+    /// The depth of the call, the root call has a depth of `0`
+    pub depth: u32,
+    /// The caller
+    pub from: Address,
+    /// The callee, or the address of the created contract
+    pub to: Address,
+    /// The value of the call, `None` for calls that can't transfer value (`STATICCALL`,
+    /// `DELEGATECALL`)
+    pub value: Option<U256>,
+    /// The calldata, or the init code for contract creations
+    pub input: Bytes,
+    /// The return data of the call
+    pub output: Bytes,
 }
 
 /// Internal issuance struct for `BlockDetails` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalIssuance {
-    block_reward: U256,
-    uncle_reward: U256,
-    issuance: U256,
+    /// The block reward paid to the beneficiary, including the ommer inclusion reward
+    pub block_reward: U256,
+    /// The total reward paid to the beneficiaries of the ommers
+    pub uncle_reward: U256,
+    /// The total issuance of the block: `block_reward + uncle_reward`
+    pub issuance: U256,
 }
 
 /// Custom `Block` struct that includes transaction count for Otterscan responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsBlock {
+    /// The block
     #[serde(flatten)]
-    block: Block,
-    transaction_count: usize,
+    pub block: Block,
+    /// The number of transactions in the block
+    pub transaction_count: usize,
 }
 
 /// Custom struct for otterscan `getBlockDetails` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockDetails {
-    block: OtsBlock,
-    issuance: InternalIssuance,
-    total_fees: U256,
+    /// The block with its transaction count
+    pub block: OtsBlock,
+    /// The issuance of the block
+    pub issuance: InternalIssuance,
+    /// The sum of all fees paid by the transactions of the block
+    pub total_fees: U256,
 }
 
 /// Custom transaction receipt struct for otterscan `OtsBlockTransactions` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsTransactionReceipt {
+    /// The receipt
     #[serde(flatten)]
-    receipt: TransactionReceipt,
-    timestamp: u64,
+    pub receipt: TransactionReceipt,
+    /// The timestamp of the block that included the transaction
+    pub timestamp: u64,
 }
 
 /// Custom struct for otterscan `getBlockTransactions` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct OtsBlockTransactions {
-    fullblock: OtsBlock,
-    receipts: Vec<OtsTransactionReceipt>,
+    /// The block, containing only the transactions of the requested page
+    pub fullblock: OtsBlock,
+    /// The receipts of the transactions of the requested page
+    pub receipts: Vec<OtsTransactionReceipt>,
 }
 
 /// Custom struct for otterscan `searchTransactionsAfter`and `searchTransactionsBefore` RPC
@@ -83,17 +134,23 @@ pub struct OtsBlockTransactions {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsWithReceipts {
-    txs: Vec<Transaction>,
-    receipts: Vec<OtsTransactionReceipt>,
-    first_page: bool,
-    last_page: bool,
+    /// The matching transactions, newest first
+    pub txs: Vec<Transaction>,
+    /// The receipts of the matching transactions
+    pub receipts: Vec<OtsTransactionReceipt>,
+    /// Whether this page contains the most recent transactions of the address
+    pub first_page: bool,
+    /// Whether this page contains the oldest transactions of the address
+    pub last_page: bool,
 }
 
 /// Custom struct for otterscan `getContractCreator` RPC responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ContractCreator {
-    tx: Transaction,
-    creator: Address,
+    /// The hash of the transaction that created the contract
+    pub hash: H256,
+    /// The address that executed the `CREATE`/`CREATE2`, this can be a factory contract
+    pub creator: Address,
 }
 
 impl From<Block> for OtsBlock {
@@ -117,3 +174,43 @@ impl From<Rich<Block>> for BlockDetails {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_internal_operation() {
+        let op = InternalOperation {
+            r#type: OperationType::OpCreate2,
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            value: U256::from(1337),
+        };
+        let s = serde_json::to_string(&op).unwrap();
+        assert_eq!(
+            s,
+            r#"{"type":3,"from":"0x0101010101010101010101010101010101010101","to":"0x0202020202020202020202020202020202020202","value":"0x539"}"#
+        );
+        let de: InternalOperation = serde_json::from_str(&s).unwrap();
+        assert_eq!(de, op);
+    }
+
+    #[test]
+    fn serde_trace_entry() {
+        let entry = TraceEntry {
+            r#type: "STATICCALL".to_string(),
+            depth: 1,
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            value: None,
+            input: Default::default(),
+            output: Default::default(),
+        };
+        let s = serde_json::to_string(&entry).unwrap();
+        assert_eq!(
+            s,
+            r#"{"type":"STATICCALL","depth":1,"from":"0x0101010101010101010101010101010101010101","to":"0x0202020202020202020202020202020202020202","value":null,"input":"0x","output":"0x"}"#
+        );
+    }
+}
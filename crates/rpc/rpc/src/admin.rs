@@ -4,7 +4,8 @@ use jsonrpsee::core::RpcResult;
 use reth_network_api::{NetworkInfo, PeerKind, Peers};
 use reth_primitives::NodeRecord;
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_types::NodeInfo;
+use reth_rpc_types::{BanEntry, BanTarget, NodeInfo};
+use std::time::Duration;
 
 /// `admin` API implementation.
 ///
@@ -50,6 +51,23 @@ where
         Ok(true)
     }
 
+    /// Handler for `admin_bannedPeers`
+    async fn banned_peers(&self) -> RpcResult<Vec<BanEntry>> {
+        self.network.bans().await.to_rpc_result()
+    }
+
+    /// Handler for `admin_banPeer`
+    fn ban_peer(&self, target: BanTarget, duration: Option<u64>) -> RpcResult<bool> {
+        self.network.ban(target, duration.map(Duration::from_secs));
+        Ok(true)
+    }
+
+    /// Handler for `admin_unbanPeer`
+    fn unban_peer(&self, target: BanTarget) -> RpcResult<bool> {
+        self.network.unban(target);
+        Ok(true)
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
@@ -3,7 +3,8 @@ use crate::{
         error::{EthApiError, EthResult},
         revm_utils::{
             clone_into_empty_db, inspect, inspect_and_return_db, prepare_call_env,
-            replay_transactions_until, result_output, transact, EvmOverrides,
+            recover_transactions_until, replay_transactions_until, result_output, transact,
+            EvmOverrides,
         },
         EthTransactions, TransactionSource,
     },
@@ -11,15 +12,24 @@ use crate::{
     EthApiSpec, TracingCallGuard,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use reth_primitives::{Account, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSigned, H256};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    server::SubscriptionMessage,
+    types::ErrorObject,
+    PendingSubscriptionSink, SubscriptionSink,
+};
+use reth_interfaces::consensus::{BadBlock, BadBlocks};
+use reth_primitives::{
+    Account, Block, BlockId, BlockNumber, BlockNumberOrTag, BlockWithSenders, Bytes,
+    TransactionSigned, H256, U256,
+};
 use reth_provider::{BlockReaderIdExt, HeaderProvider, StateProviderBox};
 use reth_revm::{
     database::{State, SubState},
     env::tx_env_with_recovered,
     tracing::{
         js::{JsDbRequest, JsInspector},
-        FourByteInspector, TracingInspector, TracingInspectorConfig,
+        FourByteInspector, GethTraceBuilder, TracingInspector, TracingInspectorConfig,
     },
 };
 use reth_rlp::{Decodable, Encodable};
@@ -27,23 +37,26 @@ use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
     state::StateOverride,
     trace::geth::{
-        BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
-        GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
+        BlockTraceResult, DebugSubscriptionKind, FourByteFrame, GethDebugBuiltInTracerType,
+        GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions,
+        GethDefaultTracingOptions, GethTrace, MuxConfig, MuxFrame, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, CallRequest, RichBlock, StateContext,
+    BlockError, Bundle, CallRequest, RichBlock, StateContext, TransactionInfo,
 };
+use reth_rpc_types_compat::block::{from_block_full, from_block_with_tx_hashes};
 use reth_tasks::TaskSpawner;
 use revm::{
     db::{CacheDB, EmptyDB},
-    primitives::Env,
+    primitives::{Env, ResultAndState},
 };
 use revm_primitives::{
     db::{DatabaseCommit, DatabaseRef},
     BlockEnv, CfgEnv,
 };
-use std::sync::Arc;
+use std::{num::NonZeroUsize, ops::RangeInclusive, sync::Arc};
 use tokio::sync::{mpsc, AcquireError, OwnedSemaphorePermit};
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use tracing::debug;
 
 /// `debug` API implementation.
 ///
@@ -61,9 +74,15 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
         eth: Eth,
         task_spawner: Box<dyn TaskSpawner>,
         tracing_call_guard: TracingCallGuard,
+        bad_blocks: BadBlocks,
     ) -> Self {
-        let inner =
-            Arc::new(DebugApiInner { provider, eth_api: eth, task_spawner, tracing_call_guard });
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            task_spawner,
+            tracing_call_guard,
+            bad_blocks,
+        });
         Self { inner }
     }
 }
@@ -84,6 +103,7 @@ where
     async fn trace_block_with(
         &self,
         at: BlockId,
+        block_hash: H256,
         transactions: Vec<TransactionSigned>,
         cfg: CfgEnv,
         block_env: BlockEnv,
@@ -96,14 +116,27 @@ where
             .spawn_with_state_at_block(at, move |state| {
                 let mut results = Vec::with_capacity(transactions.len());
                 let mut db = SubState::new(State::new(state));
-
-                let mut transactions = transactions.into_iter().peekable();
-                while let Some(tx) = transactions.next() {
-                    let tx = tx.into_ecrecovered().ok_or(BlockError::InvalidSignature)?;
+                let block_number = block_env.number.to::<u64>();
+
+                let mut transactions = transactions.into_iter().enumerate().peekable();
+                while let Some((idx, tx)) = transactions.next() {
+                    let tx_info = TransactionInfo {
+                        hash: Some(tx.hash()),
+                        index: Some(idx as u64),
+                        block_hash: Some(block_hash),
+                        block_number: Some(block_number),
+                        base_fee: None,
+                    };
+                    let tx = this
+                        .inner
+                        .eth_api
+                        .impersonated_senders()
+                        .into_ecrecovered(tx)
+                        .ok_or(BlockError::InvalidSignature)?;
                     let tx = tx_env_with_recovered(&tx);
                     let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
                     let (result, state_changes) =
-                        this.trace_transaction(opts.clone(), env, at, &mut db)?;
+                        this.trace_transaction(opts.clone(), env, at, tx_info, &mut db)?;
                     results.push(TraceResult::Success { result });
 
                     if transactions.peek().is_some() {
@@ -135,7 +168,8 @@ where
 
         // we trace on top the block's parent block
         let parent = block.parent_hash;
-        self.trace_block_with(parent.into(), block.body, cfg, block_env, opts).await
+        let block_hash = block.header.hash_slow();
+        self.trace_block_with(parent.into(), block_hash, block.body, cfg, block_env, opts).await
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -160,7 +194,107 @@ where
         // its parent block's state
         let state_at = block.parent_hash;
 
-        self.trace_block_with(state_at.into(), block.body, cfg, block_env, opts).await
+        self.trace_block_with(state_at.into(), block_hash, block.body, cfg, block_env, opts).await
+    }
+
+    /// Resolves the block range of a `traceChain` subscription.
+    ///
+    /// The start block is excluded, so this returns the range of all blocks after the start block
+    /// up to and including the end block.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> EthResult<RangeInclusive<BlockNumber>> {
+        let start = self
+            .inner
+            .provider
+            .convert_block_number(start_exclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let end = self
+            .inner
+            .provider
+            .convert_block_number(end_inclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        if start >= end {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        Ok(start + 1..=end)
+    }
+
+    /// Replays the block with the given number and returns the traces of all its transactions.
+    async fn trace_chain_block(
+        &self,
+        number: BlockNumber,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<BlockTraceResult> {
+        let block = self
+            .inner
+            .eth_api
+            .block_by_id(number.into())
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block.hash.into()).await?;
+
+        let hash = block.hash;
+        // replay the block on top of its parent block's state
+        let traces = self
+            .trace_block_with(block.parent_hash.into(), hash, block.body, cfg, block_env, opts)
+            .await?;
+
+        Ok(BlockTraceResult { block: U256::from(number), hash, traces })
+    }
+
+    /// Traces all blocks of the range and sends a [BlockTraceResult] for every block to the
+    /// subscription sink, in ascending order.
+    ///
+    /// Up to one block per available CPU is traced in parallel, each holding a tracing permit.
+    /// Blocks are only traced ahead as long as the subscriber keeps up with receiving the results.
+    /// Tracing stops if the subscriber dropped or if a block can't be traced, in which case the
+    /// error is returned.
+    async fn pipe_trace_chain(
+        self,
+        sink: SubscriptionSink,
+        blocks: RangeInclusive<BlockNumber>,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<()> {
+        let max_concurrent_blocks =
+            std::thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1);
+
+        let blocks = futures::stream::iter(blocks).map(|number| {
+            let this = self.clone();
+            let opts = opts.clone();
+            async move {
+                let _permit = this.acquire_trace_permit().await;
+                this.trace_chain_block(number, opts).await
+            }
+        });
+        let mut traces = futures::StreamExt::buffered(blocks, max_concurrent_blocks);
+
+        loop {
+            tokio::select! {
+                _ = sink.closed() => {
+                    // subscriber dropped, this also cancels all pending traces
+                    return Ok(())
+                },
+                maybe_trace = traces.next() => {
+                    let trace = match maybe_trace {
+                        Some(Ok(trace)) => trace,
+                        Some(Err(err)) => {
+                            debug!(target: "rpc::debug", %err, "Failed to trace chain");
+                            return Err(err)
+                        }
+                        // all blocks traced
+                        None => return Ok(()),
+                    };
+                    let msg = SubscriptionMessage::from_json(&trace)
+                        .map_err(|_| EthApiError::InternalEthError)?;
+                    if sink.send(msg).await.is_err() {
+                        return Ok(())
+                    }
+                }
+            }
+        }
     }
 
     /// Trace the transaction according to the provided options.
@@ -181,16 +315,19 @@ where
         // block the transaction is included in
         let state_at: BlockId = block.parent_hash.into();
         let block_txs = block.body;
+        let impersonated_senders = self.inner.eth_api.impersonated_senders().clone();
 
         let this = self.clone();
         self.inner
             .eth_api
             .spawn_with_state_at_block(state_at, move |state| {
                 // configure env for the target transaction
-                let tx = transaction.into_recovered();
+                let (tx, tx_info) = transaction.split();
 
                 let mut db = SubState::new(State::new(state));
                 // replay all transactions prior to the targeted transaction
+                let block_txs =
+                    recover_transactions_until(&impersonated_senders, block_txs, tx.hash)?;
                 replay_transactions_until(
                     &mut db,
                     cfg.clone(),
@@ -200,7 +337,8 @@ where
                 )?;
 
                 let env = Env { cfg, block: block_env, tx: tx_env_with_recovered(&tx) };
-                this.trace_transaction(opts, env, state_at, &mut db).map(|(trace, _)| trace)
+                this.trace_transaction(opts, env, state_at, tx_info, &mut db)
+                    .map(|(trace, _)| trace)
             })
             .await
     }
@@ -279,6 +417,54 @@ where
                         return Ok(frame.into())
                     }
                     GethDebugBuiltInTracerType::NoopTracer => Ok(NoopFrame::default().into()),
+                    GethDebugBuiltInTracerType::FlatCallTracer => {
+                        let flat_call_config = tracer_config
+                            .into_flat_call_config()
+                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
+
+                        let mut inspector = TracingInspector::new(
+                            TracingInspectorConfig::from_geth_config(&config)
+                                .set_exclude_precompile_calls(
+                                    !flat_call_config.is_include_precompiles(),
+                                ),
+                        );
+
+                        let frame = self
+                            .inner
+                            .eth_api
+                            .spawn_with_call_at(call, at, overrides, move |db, env| {
+                                inspect(db, env, &mut inspector)?;
+                                let frame = inspector
+                                    .into_geth_builder()
+                                    .geth_flat_call_traces(Default::default());
+                                Ok(frame)
+                            })
+                            .await?;
+                        return Ok(frame.into())
+                    }
+                    GethDebugBuiltInTracerType::MuxTracer => {
+                        let mux_config = tracer_config
+                            .into_mux_config()
+                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                        let mut inspector =
+                            TracingInspector::new(mux_inspector_config(&config, &mux_config)?);
+
+                        let frame = self
+                            .inner
+                            .eth_api
+                            .spawn_with_call_at(call, at, overrides, move |db, env| {
+                                let (res, _, db) = inspect_and_return_db(db, env, &mut inspector)?;
+                                mux_traces(
+                                    inspector.into_geth_builder(),
+                                    &res,
+                                    mux_config,
+                                    Default::default(),
+                                    &db,
+                                )
+                            })
+                            .await?;
+                        return Ok(frame.into())
+                    }
                 },
                 GethDebugTracerType::JsTracer(code) => {
                     let config = tracer_config.into_json();
@@ -386,7 +572,12 @@ where
 
                     // Execute all transactions until index
                     for tx in transactions {
-                        let tx = tx.into_ecrecovered().ok_or(BlockError::InvalidSignature)?;
+                        let tx = this
+                            .inner
+                            .eth_api
+                            .impersonated_senders()
+                            .into_ecrecovered(tx)
+                            .ok_or(BlockError::InvalidSignature)?;
                         let tx = tx_env_with_recovered(&tx);
                         let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
                         let (res, _) = transact(&mut db, env)?;
@@ -417,6 +608,7 @@ where
                             tracing_options.clone(),
                             env,
                             target_block,
+                            Default::default(),
                             &mut db,
                         )?;
 
@@ -445,6 +637,7 @@ where
         opts: GethDebugTracingOptions,
         env: Env,
         at: BlockId,
+        tx_info: TransactionInfo,
         db: &mut SubState<StateProviderBox<'_>>,
     ) -> EthResult<(GethTrace, revm_primitives::State)> {
         let GethDebugTracingOptions { config, tracer, tracer_config, .. } = opts;
@@ -494,6 +687,42 @@ where
                     GethDebugBuiltInTracerType::NoopTracer => {
                         Ok((NoopFrame::default().into(), Default::default()))
                     }
+                    GethDebugBuiltInTracerType::FlatCallTracer => {
+                        let flat_call_config = tracer_config
+                            .into_flat_call_config()
+                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
+
+                        let mut inspector = TracingInspector::new(
+                            TracingInspectorConfig::from_geth_config(&config)
+                                .set_exclude_precompile_calls(
+                                    !flat_call_config.is_include_precompiles(),
+                                ),
+                        );
+                        let (res, _) = inspect(db, env, &mut inspector)?;
+
+                        let frame = inspector.into_geth_builder().geth_flat_call_traces(tx_info);
+
+                        return Ok((frame.into(), res.state))
+                    }
+                    GethDebugBuiltInTracerType::MuxTracer => {
+                        let mux_config = tracer_config
+                            .into_mux_config()
+                            .map_err(|_| EthApiError::InvalidTracerConfig)?;
+
+                        let mut inspector =
+                            TracingInspector::new(mux_inspector_config(&config, &mux_config)?);
+                        let (res, _) = inspect(&mut *db, env, &mut inspector)?;
+
+                        let frame = mux_traces(
+                            inspector.into_geth_builder(),
+                            &res,
+                            mux_config,
+                            tx_info,
+                            &*db,
+                        )?;
+
+                        return Ok((frame.into(), res.state))
+                    }
                 },
                 GethDebugTracerType::JsTracer(code) => {
                     let config = tracer_config.into_json();
@@ -690,16 +919,71 @@ where
 
     /// Handler for `debug_getBadBlocks`
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+        let mut blocks = Vec::with_capacity(self.inner.bad_blocks.len());
+        for BadBlock { block, reason } in self.inner.bad_blocks.all() {
+            // the bad block was never committed, so derive its total difficulty from the parent
+            let parent_td = self
+                .inner
+                .provider
+                .header_td(&block.parent_hash)
+                .to_rpc_result()?
+                .unwrap_or_default();
+            let total_difficulty = parent_td + block.difficulty;
+
+            let hash = block.hash;
+            let block = block.unseal();
+            let mut rlp = Vec::new();
+            block.encode(&mut rlp);
+
+            // a bad block can contain transactions with invalid signatures, in which case only the
+            // transaction hashes are returned for that block
+            let senders = self.inner.eth_api.impersonated_senders().recover_signers(&block.body);
+            let block = match senders {
+                Some(senders) => from_block_full(
+                    BlockWithSenders { block, senders },
+                    total_difficulty,
+                    Some(hash),
+                )
+                .map_err(EthApiError::from)?,
+                None => {
+                    debug!(target: "rpc::debug", %hash, "Failed to recover senders");
+                    from_block_with_tx_hashes(block, total_difficulty, Some(hash))
+                }
+            };
+            let mut rich: RichBlock = block.into();
+            rich.extra_info.insert("reason".to_string(), reason.into());
+            rich.extra_info.insert("rlp".to_string(), format!("0x{}", hex::encode(rlp)).into());
+            blocks.push(rich);
+        }
+        Ok(blocks)
     }
 
-    /// Handler for `debug_traceChain`
-    async fn debug_trace_chain(
+    /// Handler for `debug_subscribe`
+    async fn debug_subscribe(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        match kind {
+            DebugSubscriptionKind::TraceChain => {
+                let blocks = match self.trace_chain_range(start_exclusive, end_inclusive) {
+                    Ok(blocks) => blocks,
+                    Err(err) => {
+                        pending.reject(ErrorObject::from(err)).await;
+                        return Ok(())
+                    }
+                };
+                let sink = pending.accept().await?;
+                // if a block can't be traced, the error is sent to the subscriber before the
+                // subscription is closed
+                self.clone().pipe_trace_chain(sink, blocks, opts.unwrap_or_default()).await?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -779,6 +1063,87 @@ impl<Provider, Eth> Clone for DebugApi<Provider, Eth> {
     }
 }
 
+/// Returns the [TracingInspectorConfig] that records everything the tracers configured for the
+/// mux tracer need.
+///
+/// Calls to precompiles are only excluded if the flat call tracer is configured without them and
+/// the call tracer, which always includes them, is not configured.
+fn mux_inspector_config(
+    config: &GethDefaultTracingOptions,
+    mux_config: &MuxConfig,
+) -> EthResult<TracingInspectorConfig> {
+    let mut record_logs = false;
+    let mut exclude_precompile_calls = false;
+    let mut has_call_tracer = false;
+    for (tracer, tracer_config) in mux_config.0.iter() {
+        match tracer {
+            GethDebugBuiltInTracerType::CallTracer => {
+                let call_config = tracer_config
+                    .clone()
+                    .into_call_config()
+                    .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                record_logs |= call_config.with_log.unwrap_or_default();
+                has_call_tracer = true;
+            }
+            GethDebugBuiltInTracerType::FlatCallTracer => {
+                let flat_call_config = tracer_config
+                    .clone()
+                    .into_flat_call_config()
+                    .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                exclude_precompile_calls = !flat_call_config.is_include_precompiles();
+            }
+            // the mux tracer can't be nested
+            GethDebugBuiltInTracerType::MuxTracer => return Err(EthApiError::InvalidTracerConfig),
+            _ => {}
+        }
+    }
+
+    Ok(TracingInspectorConfig::from_geth_config(config)
+        .set_record_logs(record_logs)
+        .set_exclude_precompile_calls(exclude_precompile_calls && !has_call_tracer))
+}
+
+/// Builds the result of every tracer configured for the mux tracer from a single recorded trace.
+///
+/// Expects the `db` to hold the state before the transaction was executed.
+fn mux_traces<DB>(
+    builder: GethTraceBuilder,
+    res: &ResultAndState,
+    mux_config: MuxConfig,
+    tx_info: TransactionInfo,
+    db: DB,
+) -> EthResult<MuxFrame>
+where
+    DB: DatabaseRef + Copy,
+    EthApiError: From<DB::Error>,
+{
+    let mut frame = MuxFrame::default();
+    for (tracer, tracer_config) in mux_config.0 {
+        let trace: GethTrace = match tracer {
+            GethDebugBuiltInTracerType::FourByteTracer => builder.geth_four_byte_traces().into(),
+            GethDebugBuiltInTracerType::CallTracer => {
+                let call_config = tracer_config
+                    .into_call_config()
+                    .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                builder.geth_call_traces(call_config).into()
+            }
+            GethDebugBuiltInTracerType::PreStateTracer => {
+                let prestate_config = tracer_config
+                    .into_pre_state_config()
+                    .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                builder.geth_prestate_traces(res, prestate_config, db)?.into()
+            }
+            GethDebugBuiltInTracerType::NoopTracer => NoopFrame::default().into(),
+            GethDebugBuiltInTracerType::FlatCallTracer => {
+                builder.geth_flat_call_traces(tx_info).into()
+            }
+            GethDebugBuiltInTracerType::MuxTracer => return Err(EthApiError::InvalidTracerConfig),
+        };
+        frame.0.insert(tracer, trace);
+    }
+    Ok(frame)
+}
+
 struct DebugApiInner<Provider, Eth> {
     /// The provider that can interact with the chain.
     provider: Provider,
@@ -788,4 +1153,256 @@ struct DebugApiInner<Provider, Eth> {
     tracing_call_guard: TracingCallGuard,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// Blocks rejected by the consensus engine.
+    bad_blocks: BadBlocks,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+        EthApi, TracingCallPool,
+    };
+    use assert_matches::assert_matches;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, sign_message, Address, Header,
+        Transaction, TransactionKind, TxLegacy,
+    };
+    use reth_provider::{
+        test_utils::{MockEthProvider, NoopProvider},
+        BlockHashReader, BlockReader,
+    };
+    use reth_rpc_types::{trace::geth::CallConfig, BlockTransactions};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use revm::primitives::TransactTo;
+    use std::collections::BTreeMap;
+
+    /// Returns a provider with a genesis block and `num_blocks` blocks on top of it that each
+    /// contain a single transfer.
+    fn trace_chain_provider(num_blocks: u64) -> MockEthProvider {
+        let provider = MockEthProvider::default();
+        let mut parent_hash = H256::zero();
+        for number in 0..=num_blocks {
+            let mut body = Vec::new();
+            if number > 0 {
+                let tx = Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: TransactionKind::Call(Address::repeat_byte(number as u8)),
+                    ..Default::default()
+                });
+                let signature =
+                    sign_message(H256::from_low_u64_be(0x1337), tx.signature_hash()).unwrap();
+                body.push(TransactionSigned::from_transaction_and_signature(tx, signature));
+            }
+            let header = Header {
+                number,
+                parent_hash,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                ..Default::default()
+            };
+            parent_hash = header.hash_slow();
+            provider.add_block(parent_hash, Block { header, body, ..Default::default() });
+        }
+        provider
+    }
+
+    fn build_test_debug_api(
+        provider: MockEthProvider,
+    ) -> DebugApi<MockEthProvider, EthApi<MockEthProvider, TestPool, NoopNetwork>> {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            TracingCallPool::build().expect("failed to build tracing pool"),
+        );
+        DebugApi::new(
+            provider,
+            eth_api,
+            Box::<TokioTaskExecutor>::default(),
+            TracingCallGuard::new(10),
+            BadBlocks::new(10),
+        )
+    }
+
+    /// Subscribes to `traceChain` for the given range and returns the params of the first `count`
+    /// notifications.
+    async fn trace_chain_notifications(
+        api: DebugApi<MockEthProvider, EthApi<MockEthProvider, TestPool, NoopNetwork>>,
+        start_exclusive: u64,
+        end_inclusive: u64,
+        count: usize,
+    ) -> Vec<serde_json::Value> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "debug_subscribe",
+            "params": ["traceChain", U256::from(start_exclusive), U256::from(end_inclusive)],
+        });
+        let (response, mut rx) =
+            api.into_rpc().raw_json_request(&request.to_string(), 16).await.unwrap();
+        assert!(response.is_success(), "{}", response.result);
+
+        let mut notifications = Vec::with_capacity(count);
+        for _ in 0..count {
+            let notification = rx.recv().await.expect("subscription closed");
+            let notification: serde_json::Value = serde_json::from_str(&notification).unwrap();
+            assert_eq!(notification["method"], "debug_subscription");
+            notifications.push(notification["params"].clone());
+        }
+        notifications
+    }
+
+    #[tokio::test]
+    async fn trace_chain_subscription() {
+        let provider = trace_chain_provider(3);
+        let api = build_test_debug_api(provider.clone());
+
+        let notifications = trace_chain_notifications(api, 0, 3, 3).await;
+        for (number, notification) in (1..=3).zip(notifications) {
+            let hash = provider.block_hash(number).unwrap().unwrap();
+            assert_eq!(notification["result"]["block"], serde_json::json!(U256::from(number)));
+            assert_eq!(notification["result"]["hash"], serde_json::json!(hash));
+
+            let traces = notification["result"]["traces"].as_array().unwrap();
+            assert_eq!(traces.len(), 1);
+            assert_eq!(traces[0]["result"]["failed"], false);
+            assert_eq!(traces[0]["result"]["gas"], 21_000);
+        }
+    }
+
+    #[tokio::test]
+    async fn trace_chain_subscription_sends_error() {
+        // block 3 is missing, so tracing stops after block 2
+        let api = build_test_debug_api(trace_chain_provider(2));
+
+        let notifications = trace_chain_notifications(api, 0, 3, 3).await;
+        assert_eq!(notifications[0]["result"]["block"], "0x1");
+        assert_eq!(notifications[1]["result"]["block"], "0x2");
+        assert_eq!(notifications[2]["error"], EthApiError::UnknownBlockNumber.to_string());
+    }
+
+    #[tokio::test]
+    async fn bad_blocks_with_invalid_signature() {
+        let provider = trace_chain_provider(1);
+        let api = build_test_debug_api(provider.clone());
+
+        let valid = provider.block(1.into()).unwrap().unwrap();
+        // same block, but its transaction fails sender recovery
+        let mut invalid = valid.clone();
+        invalid.header.timestamp += 1;
+        invalid.body = vec![TransactionSigned::from_transaction_and_signature(
+            valid.body[0].transaction.clone(),
+            Default::default(),
+        )];
+        let (valid, invalid) = (valid.seal_slow(), invalid.seal_slow());
+
+        api.inner
+            .bad_blocks
+            .insert(BadBlock { block: valid.clone(), reason: "valid signature".to_string() });
+        api.inner
+            .bad_blocks
+            .insert(BadBlock { block: invalid.clone(), reason: "invalid signature".to_string() });
+
+        let blocks = DebugApiServer::bad_blocks(&api).await.unwrap();
+        assert_eq!(blocks.len(), 2);
+
+        // most recent first
+        assert_eq!(blocks[0].header.hash, Some(invalid.hash));
+        assert_eq!(blocks[0].extra_info["reason"], "invalid signature");
+        assert_eq!(blocks[0].transactions, BlockTransactions::Hashes(vec![invalid.body[0].hash]));
+
+        assert_eq!(blocks[1].header.hash, Some(valid.hash));
+        assert_eq!(blocks[1].extra_info["reason"], "valid signature");
+        assert_matches!(&blocks[1].transactions, BlockTransactions::Full(txs) if txs.len() == 1);
+    }
+
+    #[test]
+    fn mux_traces_aggregate_tracers() {
+        let call_config = CallConfig { only_top_call: Some(true), with_log: None };
+        let mux_config = MuxConfig(BTreeMap::from([
+            (GethDebugBuiltInTracerType::FourByteTracer, serde_json::Value::Null.into()),
+            (
+                GethDebugBuiltInTracerType::CallTracer,
+                serde_json::to_value(&call_config).unwrap().into(),
+            ),
+            (GethDebugBuiltInTracerType::FlatCallTracer, serde_json::Value::Null.into()),
+            (GethDebugBuiltInTracerType::PreStateTracer, serde_json::Value::Null.into()),
+            (GethDebugBuiltInTracerType::NoopTracer, serde_json::Value::Null.into()),
+        ]));
+        let mut inspector =
+            TracingInspector::new(mux_inspector_config(&Default::default(), &mux_config).unwrap());
+
+        let caller = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+        // `transfer(address,uint256)` with a single zero word
+        let mut input = hex!("a9059cbb").to_vec();
+        input.resize(36, 0);
+
+        let mut env = Env::default();
+        env.tx.caller = caller;
+        env.tx.transact_to = TransactTo::Call(to);
+        env.tx.gas_limit = 100_000;
+        env.tx.data = input.into();
+
+        let db = SubState::new(State::new(NoopProvider::default()));
+        let (res, _, db) = inspect_and_return_db(db, env, &mut inspector).unwrap();
+        let builder = inspector.into_geth_builder();
+        let tx_info = TransactionInfo {
+            hash: Some(H256::repeat_byte(3)),
+            index: Some(0),
+            ..Default::default()
+        };
+
+        let frame = mux_traces(builder.clone(), &res, mux_config, tx_info, &db).unwrap();
+        assert_eq!(frame.0.len(), 5);
+
+        assert_eq!(
+            frame.0[&GethDebugBuiltInTracerType::FourByteTracer],
+            GethTrace::from(FourByteFrame(BTreeMap::from([("0xa9059cbb-32".to_string(), 1)])))
+        );
+
+        let call_frame = builder.geth_call_traces(call_config);
+        assert_eq!(call_frame.from, caller);
+        assert_eq!(call_frame.to, Some(to));
+        assert_eq!(frame.0[&GethDebugBuiltInTracerType::CallTracer], GethTrace::from(call_frame));
+
+        let flat_call_frame = builder.geth_flat_call_traces(tx_info);
+        assert_eq!(flat_call_frame.len(), 1);
+        assert_eq!(flat_call_frame[0].transaction_hash, tx_info.hash);
+        assert_eq!(
+            frame.0[&GethDebugBuiltInTracerType::FlatCallTracer],
+            GethTrace::from(flat_call_frame)
+        );
+
+        let prestate_frame = builder.geth_prestate_traces(&res, Default::default(), &db).unwrap();
+        assert_eq!(
+            frame.0[&GethDebugBuiltInTracerType::PreStateTracer],
+            GethTrace::from(prestate_frame)
+        );
+
+        assert_eq!(
+            frame.0[&GethDebugBuiltInTracerType::NoopTracer],
+            GethTrace::from(NoopFrame::default())
+        );
+    }
+
+    #[test]
+    fn mux_tracer_cannot_be_nested() {
+        let mux_config = MuxConfig(BTreeMap::from([(
+            GethDebugBuiltInTracerType::MuxTracer,
+            serde_json::Value::Null.into(),
+        )]));
+        assert!(matches!(
+            mux_inspector_config(&Default::default(), &mux_config),
+            Err(EthApiError::InvalidTracerConfig)
+        ));
+    }
 }
@@ -8,12 +8,14 @@ use crate::{
     EthApi,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockId, BlockNumberOrTag, TransactionMeta};
+use reth_primitives::{BlockId, BlockNumberOrTag, BlockWithSenders, TransactionMeta};
 
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
-use reth_rpc_types::{Index, RichBlock, TransactionReceipt};
+use reth_rpc_types::{BlockError, Index, RichBlock, TransactionReceipt};
 
-use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
+use reth_rpc_types_compat::block::{
+    from_block_full, from_block_with_tx_hashes, uncle_block_from_header,
+};
 use reth_transaction_pool::TransactionPool;
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
 where
@@ -86,6 +88,11 @@ where
                         block_number,
                         base_fee,
                     };
+                    let tx = self
+                        .inner
+                        .impersonated_senders
+                        .into_ecrecovered(tx)
+                        .ok_or(EthApiError::InvalidTransactionSignature)?;
                     build_transaction_receipt_with_block_receipts(tx, meta, receipt, &receipts)
                 })
                 .collect::<EthResult<Vec<_>>>();
@@ -160,7 +167,21 @@ where
             .provider()
             .header_td_by_number(block.number)?
             .ok_or(EthApiError::UnknownBlockNumber)?;
-        let block = from_block(block.into(), total_difficulty, full.into(), Some(block_hash))?;
+        let block = block.unseal();
+        let block = if full {
+            let senders = self
+                .inner
+                .impersonated_senders
+                .recover_signers(&block.body)
+                .ok_or(BlockError::InvalidSignature)?;
+            from_block_full(
+                BlockWithSenders { block, senders },
+                total_difficulty,
+                Some(block_hash),
+            )?
+        } else {
+            from_block_with_tx_hashes(block, total_difficulty, Some(block_hash))
+        };
         Ok(Some(block.into()))
     }
 }
@@ -91,6 +91,7 @@ where
 
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
         let gas_limit = self.inner.gas_cap;
+        let impersonated_senders = self.inner.impersonated_senders.clone();
 
         // we're essentially replaying the transactions in the block here, hence we need the state
         // that points to the beginning of the block, which is the state at the parent block
@@ -115,7 +116,9 @@ where
 
                 // Execute all transactions until index
                 for tx in transactions {
-                    let tx = tx.into_ecrecovered().ok_or(BlockError::InvalidSignature)?;
+                    let tx = impersonated_senders
+                        .into_ecrecovered(tx)
+                        .ok_or(BlockError::InvalidSignature)?;
                     let tx = tx_env_with_recovered(&tx);
                     let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
                     let (res, _) = transact(&mut db, env)?;
@@ -17,7 +17,8 @@ use reth_primitives::{
     Address, BlockId, BlockNumberOrTag, ChainInfo, SealedBlock, H256, U256, U64,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, ImpersonatedSenders, StateProviderBox,
+    StateProviderFactory,
 };
 use reth_rpc_types::{SyncInfo, SyncStatus};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -102,6 +103,7 @@ where
             gas_cap.into().into(),
             Box::<TokioTaskExecutor>::default(),
             tracing_call_pool,
+            Default::default(),
         )
     }
 
@@ -116,6 +118,7 @@ where
         gas_cap: u64,
         task_spawner: Box<dyn TaskSpawner>,
         tracing_call_pool: TracingCallPool,
+        impersonated_senders: ImpersonatedSenders,
     ) -> Self {
         // get the block number of the latest block
         let latest_block = provider
@@ -137,6 +140,7 @@ where
             task_spawner,
             pending_block: Default::default(),
             tracing_call_pool,
+            impersonated_senders,
         };
         Self { inner: Arc::new(inner) }
     }
@@ -435,4 +439,6 @@ struct EthApiInner<Provider, Pool, Network> {
     pending_block: Mutex<Option<PendingBlock>>,
     /// A pool dedicated to tracing calls
     tracing_call_pool: TracingCallPool,
+    /// Senders of impersonated transactions of a dev chain
+    impersonated_senders: ImpersonatedSenders,
 }
@@ -5,16 +5,18 @@ use crate::{
     EthApi,
 };
 use reth_primitives::{
-    serde_helper::JsonStorageKey, Address, BlockId, BlockNumberOrTag, Bytes, H256, KECCAK_EMPTY,
-    U256,
+    serde_helper::JsonStorageKey, Address, BlockId, BlockNumberOrTag, Bytes, H256, U256,
 };
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider,
-    StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider, StateProviderFactory,
 };
-use reth_rpc_types::{EIP1186AccountProofResponse, StorageProof};
+use reth_rpc_types::EIP1186AccountProofResponse;
+use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
+/// The maximum number of blocks behind the tip for which `eth_getProof` serves proofs.
+pub(crate) const MAX_ETH_PROOF_WINDOW: u64 = 128;
+
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
 where
     Provider:
@@ -84,7 +86,12 @@ where
         Ok(H256(value.to_be_bytes()))
     }
 
-    #[allow(unused)]
+    /// Returns the account and storage values of the specified account including the merkle
+    /// proofs.
+    ///
+    /// Proofs are only served for the latest block and the [MAX_ETH_PROOF_WINDOW] blocks before
+    /// it, since historical proofs are generated by reverting the changesets of all subsequent
+    /// blocks.
     pub(crate) fn get_proof(
         &self,
         address: Address,
@@ -94,54 +101,18 @@ where
         let chain_info = self.provider().chain_info()?;
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
 
-        // if we are trying to create a proof for the latest block, but have a BlockId as input
-        // that is not BlockNumberOrTag::Latest, then we need to figure out whether or not the
-        // BlockId corresponds to the latest block
-        let is_blockid_latest = match block_id {
-            BlockId::Number(BlockNumberOrTag::Number(num)) => num == chain_info.best_number,
-            BlockId::Hash(hash) => hash == chain_info.best_hash.into(),
-            BlockId::Number(BlockNumberOrTag::Latest) => true,
-            _ => false,
-        };
-
-        // TODO: remove when HistoricalStateProviderRef::proof is implemented
-        if !is_blockid_latest {
-            return Err(EthApiError::InvalidBlockRange)
+        let block_number = self
+            .provider()
+            .block_number_for_id(block_id)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        if chain_info.best_number.saturating_sub(block_number) > MAX_ETH_PROOF_WINDOW {
+            return Err(EthApiError::ExceedsMaxProofWindow)
         }
 
         let state = self.state_at_block_id(block_id)?;
-
-        let hash_keys = keys.iter().map(|key| key.0).collect::<Vec<_>>();
-        let (account_proof, storage_hash, stg_proofs) = state.proof(address, &hash_keys)?;
-
-        let storage_proof = keys
-            .into_iter()
-            .zip(stg_proofs)
-            .map(|(key, proof)| {
-                state.storage(address, key.0).map(|op| StorageProof {
-                    key,
-                    value: op.unwrap_or_default(),
-                    proof,
-                })
-            })
-            .collect::<Result<_, _>>()?;
-
-        let mut proof = EIP1186AccountProofResponse {
-            address,
-            code_hash: KECCAK_EMPTY,
-            account_proof,
-            storage_hash,
-            storage_proof,
-            ..Default::default()
-        };
-
-        if let Some(account) = state.basic_account(proof.address)? {
-            proof.balance = account.balance;
-            proof.nonce = account.nonce.into();
-            proof.code_hash = account.get_bytecode_hash();
-        }
-
-        Ok(proof)
+        let storage_keys = keys.iter().map(|key| key.0).collect::<Vec<_>>();
+        let proof = state.proof(address, &storage_keys)?;
+        Ok(from_primitive_account_proof(proof))
     }
 }
 
@@ -4,8 +4,8 @@ use crate::{
         api::pending_block::PendingBlockEnv,
         error::{EthApiError, EthResult, SignError},
         revm_utils::{
-            inspect, inspect_and_return_db, prepare_call_env, replay_transactions_until, transact,
-            EvmOverrides,
+            inspect, inspect_and_return_db, prepare_call_env, recover_transactions_until,
+            replay_transactions_until, transact, EvmOverrides,
         },
         utils::recover_raw_transaction,
     },
@@ -14,7 +14,7 @@ use crate::{
 use async_trait::async_trait;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
-    Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredTransaction, Header,
+    Address, BlockId, BlockNumberOrTag, Bytes, ChainSpec, FromRecoveredTransaction, Header,
     IntoRecoveredTransaction, Receipt, SealedBlock,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, H256, U128, U256, U64,
@@ -22,7 +22,8 @@ use reth_primitives::{
 use reth_rpc_types_compat::from_recovered_with_block_context;
 
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, ImpersonatedSenders, StateProviderBox,
+    StateProviderFactory,
 };
 use reth_revm::{
     database::{State, SubState},
@@ -30,8 +31,8 @@ use reth_revm::{
     tracing::{TracingInspector, TracingInspectorConfig},
 };
 use reth_rpc_types::{
-    CallRequest, Index, Log, Transaction, TransactionInfo, TransactionReceipt, TransactionRequest,
-    TypedTransactionRequest,
+    BlockError, CallRequest, Index, Log, Transaction, TransactionInfo, TransactionReceipt,
+    TransactionRequest, TypedTransactionRequest,
 };
 use reth_transaction_pool::{TransactionOrigin, TransactionPool};
 use revm::{
@@ -39,7 +40,10 @@ use revm::{
     primitives::{BlockEnv, CfgEnv},
     Inspector,
 };
-use revm_primitives::{utilities::create_address, Env, ResultAndState, SpecId};
+use revm_primitives::{
+    db::DatabaseCommit, utilities::create_address, Env, ExecutionResult, ResultAndState, SpecId,
+};
+use std::sync::Arc;
 
 /// Helper alias type for the state's [CacheDB]
 pub(crate) type StateCacheDB<'r> = CacheDB<State<StateProviderBox<'r>>>;
@@ -53,6 +57,13 @@ pub trait EthTransactions: Send + Sync {
     /// Returns default gas limit to use for `eth_call` and tracing RPC methods.
     fn call_gas_limit(&self) -> u64;
 
+    /// Returns the senders of impersonated transactions, which can't be recovered from their
+    /// signature.
+    fn impersonated_senders(&self) -> &ImpersonatedSenders;
+
+    /// Returns the chain spec, e.g. to determine the hardfork of blocks that don't exist yet.
+    fn chain_spec(&self) -> Arc<ChainSpec>;
+
     /// Returns the state at the given [BlockId]
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox<'_>>;
 
@@ -234,6 +245,37 @@ pub trait EthTransactions: Send + Sync {
             + Send
             + 'static,
         R: Send + 'static;
+
+    /// Executes all transactions of a block and returns a list of callback results invoked for each
+    /// transaction in the block.
+    ///
+    /// This
+    /// 1. fetches all transactions of the block
+    /// 2. configures the EVM evn
+    /// 3. loops over all transactions and executes them
+    /// 4. calls the callback with the transaction info, the execution result, the changed state
+    /// _after_ the transaction [State](revm_primitives::State) and the database that points to the
+    /// state right _before_ the transaction.
+    ///
+    /// Returns `None` if the block does not exist.
+    async fn trace_block_with<F, R>(
+        &self,
+        block_id: BlockId,
+        config: TracingInspectorConfig,
+        f: F,
+    ) -> EthResult<Option<Vec<R>>>
+    where
+        // This is the callback that's invoked for each transaction with
+        F: for<'a> Fn(
+                TransactionInfo,
+                TracingInspector,
+                ExecutionResult,
+                &'a revm_primitives::State,
+                &'a StateCacheDB<'a>,
+            ) -> EthResult<R>
+            + Send
+            + 'static,
+        R: Send + 'static;
 }
 
 #[async_trait]
@@ -248,6 +290,14 @@ where
         self.inner.gas_cap
     }
 
+    fn impersonated_senders(&self) -> &ImpersonatedSenders {
+        &self.inner.impersonated_senders
+    }
+
+    fn chain_spec(&self) -> Arc<ChainSpec> {
+        self.provider().chain_spec()
+    }
+
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox<'_>> {
         self.state_at_block_id(at)
     }
@@ -326,8 +376,9 @@ where
                 match this.provider().transaction_by_hash_with_meta(hash)? {
                     None => Ok(None),
                     Some((tx, meta)) => {
-                        let transaction = tx
-                            .into_ecrecovered()
+                        let transaction = this
+                            .impersonated_senders()
+                            .into_ecrecovered(tx)
                             .ok_or(EthApiError::InvalidTransactionSignature)?;
 
                         let tx = TransactionSource::Block {
@@ -651,11 +702,13 @@ where
         // block the transaction is included in
         let parent_block = block.parent_hash;
         let block_txs = block.body;
+        let impersonated_senders = self.inner.impersonated_senders.clone();
 
         self.spawn_with_state_at_block(parent_block.into(), move |state| {
             let mut db = SubState::new(State::new(state));
 
             // replay all transactions prior to the targeted transaction
+            let block_txs = recover_transactions_until(&impersonated_senders, block_txs, tx.hash)?;
             replay_transactions_until(&mut db, cfg.clone(), block_env.clone(), block_txs, tx.hash)?;
 
             let env = Env { cfg, block: block_env, tx: tx_env_with_recovered(&tx) };
@@ -667,6 +720,80 @@ where
         .await
         .map(Some)
     }
+
+    async fn trace_block_with<F, R>(
+        &self,
+        block_id: BlockId,
+        config: TracingInspectorConfig,
+        f: F,
+    ) -> EthResult<Option<Vec<R>>>
+    where
+        F: for<'a> Fn(
+                TransactionInfo,
+                TracingInspector,
+                ExecutionResult,
+                &'a revm_primitives::State,
+                &'a StateCacheDB<'a>,
+            ) -> EthResult<R>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
+        let ((cfg, block_env, _), block) =
+            futures::try_join!(self.evm_env_at(block_id), self.block_by_id(block_id))?;
+
+        let block = match block {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+
+        // we need to get the state of the parent block because we're replaying this block on top of
+        // its parent block's state
+        let state_at = block.parent_hash;
+
+        let block_hash = block.hash;
+        let transactions = block.body;
+        let impersonated_senders = self.inner.impersonated_senders.clone();
+
+        // replay all transactions of the block
+        self.spawn_with_state_at_block(state_at.into(), move |state| {
+            let mut results = Vec::with_capacity(transactions.len());
+            let mut db = SubState::new(State::new(state));
+
+            let mut transactions = transactions.into_iter().enumerate().peekable();
+
+            while let Some((idx, tx)) = transactions.next() {
+                let tx = impersonated_senders
+                    .into_ecrecovered(tx)
+                    .ok_or(BlockError::InvalidSignature)?;
+                let tx_info = TransactionInfo {
+                    hash: Some(tx.hash()),
+                    index: Some(idx as u64),
+                    block_hash: Some(block_hash),
+                    block_number: Some(block_env.number.try_into().unwrap_or(u64::MAX)),
+                    base_fee: Some(block_env.basefee.try_into().unwrap_or(u64::MAX)),
+                };
+
+                let tx = tx_env_with_recovered(&tx);
+                let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
+
+                let mut inspector = TracingInspector::new(config);
+                let (res, _) = inspect(&mut db, env, &mut inspector)?;
+                let ResultAndState { result, state } = res;
+                results.push(f(tx_info, inspector, result, &state, &db)?);
+
+                // need to apply the state changes of this transaction before executing the
+                // next transaction
+                if transactions.peek().is_some() {
+                    db.commit(state)
+                }
+            }
+
+            Ok(results)
+        })
+        .await
+        .map(Some)
+    }
 }
 
 // === impl EthApi ===
@@ -691,6 +818,11 @@ where
             Some(recpts) => recpts,
             None => return Err(EthApiError::UnknownBlockNumber),
         };
+        let tx = self
+            .inner
+            .impersonated_senders
+            .into_ecrecovered(tx)
+            .ok_or(EthApiError::InvalidTransactionSignature)?;
         build_transaction_receipt_with_block_receipts(tx, meta, receipt, &all_receipts)
     }
 }
@@ -732,8 +864,11 @@ where
             let block_hash = block.hash;
             let block = block.unseal();
             if let Some(tx_signed) = block.body.into_iter().nth(index.into()) {
-                let tx =
-                    tx_signed.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+                let tx = self
+                    .inner
+                    .impersonated_senders
+                    .into_ecrecovered(tx_signed)
+                    .ok_or(EthApiError::InvalidTransactionSignature)?;
                 return Ok(Some(from_recovered_with_block_context(
                     tx,
                     block_hash,
@@ -838,14 +973,11 @@ impl From<TransactionSource> for Transaction {
 
 /// Helper function to construct a transaction receipt
 pub(crate) fn build_transaction_receipt_with_block_receipts(
-    tx: TransactionSigned,
+    transaction: TransactionSignedEcRecovered,
     meta: TransactionMeta,
     receipt: Receipt,
     all_receipts: &[Receipt],
 ) -> EthResult<TransactionReceipt> {
-    let transaction =
-        tx.clone().into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
-
     // get the previous transaction cumulative gas used
     let gas_used = if meta.index == 0 {
         receipt.cumulative_gas_used
@@ -869,17 +1001,17 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
         contract_address: None,
         logs: Vec::with_capacity(receipt.logs.len()),
         effective_gas_price: U128::from(transaction.effective_gas_price(meta.base_fee)),
-        transaction_type: tx.transaction.tx_type().into(),
+        transaction_type: transaction.transaction.tx_type().into(),
         // TODO pre-byzantium receipts have a post-transaction state root
         state_root: None,
         logs_bloom: receipt.bloom_slow(),
         status_code: if receipt.success { Some(U64::from(1)) } else { Some(U64::from(0)) },
     };
 
-    match tx.transaction.kind() {
+    match transaction.transaction.kind() {
         Create => {
             res_receipt.contract_address =
-                Some(create_address(transaction.signer(), tx.transaction.nonce()));
+                Some(create_address(transaction.signer(), transaction.transaction.nonce()));
         }
         Call(addr) => {
             res_receipt.to = Some(*addr);
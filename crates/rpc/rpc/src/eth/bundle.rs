@@ -0,0 +1,398 @@
+//! `eth_callBundle` implementation for bundle simulation.
+
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
+        revm_utils::transact,
+        utils::recover_raw_transaction,
+        EthTransactions,
+    },
+    TracingCallGuard,
+};
+use jsonrpsee::core::RpcResult;
+use reth_primitives::{
+    abi::decode_revert_reason, keccak256, BaseFeeParams, BlockId, Head, U256, U64,
+};
+use reth_revm::{
+    config::{revm_spec, revm_spec_by_timestamp_after_merge},
+    database::{State, SubState},
+    env::tx_env_with_recovered,
+    into_reth_log,
+};
+use reth_rpc_api::EthCallBundleApiServer;
+use reth_rpc_types::{CallBundle, CallBundleResponse, CallBundleTransactionResult, Log};
+use revm::{
+    db::DatabaseRef,
+    primitives::{Env, ExecutionResult, SpecId},
+    DatabaseCommit,
+};
+use std::sync::Arc;
+use tracing::trace;
+
+/// Bundle simulation API, `eth_callBundle`.
+///
+/// Executes a bundle of signed transactions on top of the state of a block, without broadcasting
+/// them, and reports what each transaction paid to the coinbase.
+pub struct EthBundle<Eth> {
+    inner: Arc<EthBundleInner<Eth>>,
+}
+
+impl<Eth> EthBundle<Eth> {
+    /// Creates a new instance of the bundle API.
+    pub fn new(eth_api: Eth, tracing_call_guard: TracingCallGuard) -> Self {
+        Self { inner: Arc::new(EthBundleInner { eth_api, tracing_call_guard }) }
+    }
+}
+
+impl<Eth> EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Simulates the bundle on top of the state of its state block.
+    ///
+    /// The transactions are executed in order in the environment of the block the bundle targets,
+    /// including the hardfork that is active at its number and timestamp.
+    /// Reverted transactions are reported, but transactions that are invalid, e.g. because of a
+    /// nonce mismatch, fail the whole simulation.
+    pub async fn call_bundle(&self, bundle: CallBundle) -> EthResult<CallBundleResponse> {
+        let CallBundle {
+            txs,
+            block_number,
+            state_block_number,
+            timestamp,
+            coinbase,
+            gas_limit,
+            difficulty,
+            base_fee,
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams("bundle missing txs".to_string()))
+        }
+        if block_number.is_zero() {
+            return Err(EthApiError::InvalidParams("bundle missing blockNumber".to_string()))
+        }
+
+        let transactions =
+            txs.into_iter().map(recover_raw_transaction).collect::<EthResult<Vec<_>>>()?;
+
+        let state_block_id = BlockId::Number(state_block_number);
+        let ((mut cfg, mut block_env, at), state_block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(state_block_id),
+            self.inner.eth_api.block_by_id(state_block_id)
+        )?;
+        let state_block = state_block.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        // the bundle is executed in the environment of the target block
+        let timestamp =
+            timestamp.map_or(state_block.timestamp + 12, |timestamp| timestamp.as_u64());
+        block_env.number = U256::from(block_number.as_u64());
+        block_env.timestamp = U256::from(timestamp);
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(gas_limit) = gas_limit {
+            block_env.gas_limit = U256::from(gas_limit.as_u64());
+        }
+        if let Some(difficulty) = difficulty {
+            block_env.difficulty = difficulty;
+        }
+
+        // the target block may activate a hardfork that is not active at the state block
+        let chain_spec = self.inner.eth_api.chain_spec();
+        cfg.spec_id = if cfg.spec_id >= SpecId::MERGE {
+            revm_spec_by_timestamp_after_merge(&chain_spec, timestamp)
+        } else {
+            // the total difficulty of the target block is unknown, so the merge is not detected
+            revm_spec(
+                &chain_spec,
+                Head {
+                    number: block_number.as_u64(),
+                    timestamp,
+                    difficulty: block_env.difficulty,
+                    ..Default::default()
+                },
+            )
+        };
+        if let Some(base_fee) = base_fee {
+            block_env.basefee = base_fee;
+        } else if let Some(base_fee) =
+            state_block.header.next_block_base_fee(BaseFeeParams::ethereum())
+        {
+            block_env.basefee = U256::from(base_fee);
+        }
+
+        let state_block_number = state_block.number;
+        let _permit = self
+            .inner
+            .tracing_call_guard
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| EthApiError::InternalEthError)?;
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(at, move |state| {
+                let coinbase = block_env.coinbase;
+                let base_fee = block_env.basefee.saturating_to::<u64>();
+                let mut db = SubState::new(State::new(state));
+
+                let initial_coinbase_balance =
+                    db.basic(coinbase)?.map(|acc| acc.balance).unwrap_or_default();
+                let mut coinbase_balance = initial_coinbase_balance;
+
+                let mut hashes = Vec::with_capacity(transactions.len() * 32);
+                let mut results = Vec::with_capacity(transactions.len());
+                let mut total_gas_used = 0u64;
+                let mut total_gas_fees = U256::ZERO;
+                let mut log_index = 0u64;
+
+                for (index, tx) in transactions.into_iter().enumerate() {
+                    let tx_hash = tx.hash();
+                    hashes.extend_from_slice(tx_hash.as_bytes());
+                    let effective_gas_price = U256::from(tx.effective_gas_price(Some(base_fee)));
+                    let gas_tip =
+                        U256::from(tx.effective_gas_tip(Some(base_fee)).unwrap_or_default());
+
+                    let env = Env {
+                        cfg: cfg.clone(),
+                        block: block_env.clone(),
+                        tx: tx_env_with_recovered(&tx),
+                    };
+                    let (res, _) = transact(&mut db, env)?;
+
+                    let gas_used = res.result.gas_used();
+                    total_gas_used += gas_used;
+                    let gas_fees = U256::from(gas_used) * gas_tip;
+                    total_gas_fees += gas_fees;
+
+                    let coinbase_balance_after_tx = res
+                        .state
+                        .get(&coinbase)
+                        .map(|acc| acc.info.balance)
+                        .unwrap_or(coinbase_balance);
+                    let coinbase_diff = coinbase_balance_after_tx.saturating_sub(coinbase_balance);
+                    coinbase_balance = coinbase_balance_after_tx;
+
+                    let mut result = CallBundleTransactionResult {
+                        tx_hash,
+                        from_address: tx.signer(),
+                        to_address: tx.to(),
+                        gas_used: U64::from(gas_used),
+                        gas_price: coinbase_diff
+                            .checked_div(U256::from(gas_used))
+                            .unwrap_or_default(),
+                        effective_gas_price,
+                        coinbase_diff,
+                        eth_sent_to_coinbase: coinbase_diff.saturating_sub(gas_fees),
+                        gas_fees,
+                        ..Default::default()
+                    };
+                    match res.result {
+                        ExecutionResult::Success { output, logs, .. } => {
+                            result.value = Some(output.into_data().into());
+                            result.logs = logs
+                                .into_iter()
+                                .map(|log| {
+                                    let mut log = Log::from_primitive(into_reth_log(log));
+                                    log.block_number = Some(block_env.number);
+                                    log.transaction_hash = Some(tx_hash);
+                                    log.transaction_index = Some(U256::from(index));
+                                    log.log_index = Some(U256::from(log_index));
+                                    log_index += 1;
+                                    log
+                                })
+                                .collect();
+                        }
+                        ExecutionResult::Revert { output, .. } => {
+                            result.revert = decode_revert_reason(&output);
+                            result.error = Some(RevertError::new(output).to_string());
+                        }
+                        ExecutionResult::Halt { reason, gas_used } => {
+                            result.error = Some(
+                                RpcInvalidTransactionError::halt(reason, gas_used).to_string(),
+                            );
+                        }
+                    }
+                    results.push(result);
+
+                    // the following transactions are executed on top of the changes of this one
+                    db.commit(res.state);
+                }
+
+                let coinbase_diff = coinbase_balance.saturating_sub(initial_coinbase_balance);
+                Ok(CallBundleResponse {
+                    bundle_hash: keccak256(hashes),
+                    bundle_gas_price: coinbase_diff
+                        .checked_div(U256::from(total_gas_used))
+                        .unwrap_or_default(),
+                    coinbase_diff,
+                    eth_sent_to_coinbase: coinbase_diff.saturating_sub(total_gas_fees),
+                    gas_fees: total_gas_fees,
+                    results,
+                    state_block_number: U64::from(state_block_number),
+                    total_gas_used: U64::from(total_gas_used),
+                })
+            })
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthCallBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Handler for `eth_callBundle`
+    async fn call_bundle(&self, bundle: CallBundle) -> RpcResult<CallBundleResponse> {
+        trace!(target: "rpc::eth", txs = bundle.txs.len(), "Serving eth_callBundle");
+        Ok(EthBundle::call_bundle(self, bundle).await?)
+    }
+}
+
+impl<Eth> std::fmt::Debug for EthBundle<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthBundle").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for EthBundle<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+/// Container type for [EthBundle] internals
+struct EthBundleInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// Restricts the number of concurrent simulations, which are as expensive as tracing calls.
+    tracing_call_guard: TracingCallGuard,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+        EthApi, TracingCallPool,
+    };
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, sign_message, Address, Block,
+        BlockNumberOrTag, Bytes, ChainSpecBuilder, ForkCondition, Hardfork, Header, Transaction,
+        TransactionKind, TransactionSigned, TxEip1559, H256,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    const GWEI: u128 = 1_000_000_000;
+    const ETHER: u128 = 1_000_000_000 * GWEI;
+
+    /// Shanghai is activated by the block the bundle targets, but not by its state block.
+    const SHANGHAI_TIMESTAMP: u64 = 1_000;
+
+    /// Returns the raw, signed transaction that calls `to` with the given value.
+    fn raw_tx(nonce: u64, to: Address, value: u128) -> Bytes {
+        let tx = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce,
+            gas_limit: 100_000,
+            max_fee_per_gas: 20 * GWEI,
+            max_priority_fee_per_gas: 2 * GWEI,
+            to: TransactionKind::Call(to),
+            value,
+            ..Default::default()
+        });
+        let signature = sign_message(H256::from_low_u64_be(0x1337), tx.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(tx, signature).envelope_encoded().into()
+    }
+
+    #[tokio::test]
+    async fn call_bundle_reports_coinbase_payments() {
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .paris_activated()
+            .with_fork(Hardfork::Shanghai, ForkCondition::Timestamp(SHANGHAI_TIMESTAMP))
+            .build();
+        let provider = MockEthProvider { chain_spec: Arc::new(chain_spec), ..Default::default() };
+
+        let header = Header {
+            number: 1,
+            timestamp: SHANGHAI_TIMESTAMP - 12,
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            base_fee_per_gas: Some(10 * GWEI as u64),
+            ..Default::default()
+        };
+        provider.add_block(header.hash_slow(), Block { header, ..Default::default() });
+
+        let sender = recover_raw_transaction(raw_tx(0, Address::zero(), 0)).unwrap().signer();
+        let coinbase = Address::repeat_byte(0xc0);
+        // PUSH0 PUSH0 RETURN, only valid from Shanghai on
+        let push0 = Address::repeat_byte(0x01);
+        // PUSH1 0 PUSH1 0 REVERT
+        let revert = Address::repeat_byte(0x02);
+        provider.extend_accounts([
+            (sender, ExtendedAccount::new(0, U256::from(10 * ETHER))),
+            (coinbase, ExtendedAccount::new(0, U256::from(ETHER))),
+            (push0, ExtendedAccount::new(0, U256::ZERO).with_bytecode(hex!("5f5ff3").into())),
+            (revert, ExtendedAccount::new(0, U256::ZERO).with_bytecode(hex!("60006000fd").into())),
+        ]);
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            TracingCallPool::build().expect("failed to build tracing pool"),
+        );
+        let bundle_api = EthBundle::new(eth_api, TracingCallGuard::new(1));
+
+        let response = bundle_api
+            .call_bundle(CallBundle {
+                txs: vec![raw_tx(0, push0, 0), raw_tx(1, coinbase, ETHER), raw_tx(2, revert, 0)],
+                block_number: U64::from(2),
+                state_block_number: BlockNumberOrTag::Latest,
+                timestamp: None,
+                coinbase: Some(coinbase),
+                gas_limit: None,
+                difficulty: None,
+                base_fee: Some(U256::from(10 * GWEI)),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.state_block_number, U64::from(1));
+        assert_eq!(response.results.len(), 3);
+
+        let tip = U256::from(2 * GWEI);
+        for result in &response.results {
+            assert_eq!(result.from_address, sender);
+            assert_eq!(result.effective_gas_price, U256::from(12 * GWEI));
+            assert_eq!(result.gas_fees, U256::from(result.gas_used.as_u64()) * tip);
+        }
+
+        let [call, transfer, reverted] = &response.results[..] else { unreachable!() };
+        assert_eq!(call.error, None);
+        assert_eq!(call.coinbase_diff, call.gas_fees);
+        assert_eq!(call.eth_sent_to_coinbase, U256::ZERO);
+
+        assert_eq!(transfer.gas_used, U64::from(21_000));
+        assert_eq!(transfer.coinbase_diff, transfer.gas_fees + U256::from(ETHER));
+        assert_eq!(transfer.eth_sent_to_coinbase, U256::from(ETHER));
+
+        // reverted transactions still pay for their gas
+        assert_eq!(reverted.error.as_deref(), Some("execution reverted"));
+        assert_eq!(reverted.revert, None);
+        assert!(reverted.gas_fees > U256::ZERO);
+        assert_eq!(reverted.coinbase_diff, reverted.gas_fees);
+        assert_eq!(reverted.eth_sent_to_coinbase, U256::ZERO);
+
+        let gas_fees = call.gas_fees + transfer.gas_fees + reverted.gas_fees;
+        assert_eq!(response.gas_fees, gas_fees);
+        assert_eq!(response.coinbase_diff, gas_fees + U256::from(ETHER));
+        assert_eq!(response.eth_sent_to_coinbase, U256::from(ETHER));
+        assert_eq!(response.total_gas_used, call.gas_used + transfer.gas_used + reverted.gas_used);
+    }
+}
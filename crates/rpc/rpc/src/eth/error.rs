@@ -8,7 +8,9 @@ use jsonrpsee::{
 use reth_primitives::{abi::decode_revert_reason, Address, Bytes, U256};
 use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError, CallInputError};
-use reth_transaction_pool::error::{InvalidPoolTransactionError, PoolError, PoolTransactionError};
+use reth_transaction_pool::error::{
+    Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolTransactionError,
+};
 use revm::primitives::{EVMError, ExecutionResult, Halt, OutOfGasError};
 use std::time::Duration;
 
@@ -36,8 +38,14 @@ pub enum EthApiError {
     UnknownSafeOrFinalizedBlock,
     #[error("Unknown block or tx index")]
     UnknownBlockOrTxIndex,
+    /// Thrown when the body or receipts of a block were requested, but removed by history expiry.
+    #[error("pruned history unavailable")]
+    PrunedHistoryUnavailable,
     #[error("Invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the target block for proof computation exceeds the maximum configured window.
+    #[error("distance to target block exceeds maximum proof window")]
+    ExceedsMaxProofWindow,
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("Prevrandao not in th EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -101,6 +109,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::InvalidTransactionSignature |
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
+            EthApiError::ExceedsMaxProofWindow |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -117,6 +126,10 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::UnknownSafeOrFinalizedBlock => {
                 rpc_error_with_code(EthRpcErrorCode::UnknownBlock.code(), error.to_string())
             }
+            EthApiError::PrunedHistoryUnavailable => rpc_error_with_code(
+                EthRpcErrorCode::PrunedHistoryUnavailable.code(),
+                error.to_string(),
+            ),
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
@@ -169,6 +182,7 @@ impl From<reth_interfaces::provider::ProviderError> for EthApiError {
             ProviderError::FinalizedBlockNotFound | ProviderError::SafeBlockNotFound => {
                 EthApiError::UnknownSafeOrFinalizedBlock
             }
+            ProviderError::BlockBodyPruned(_) => EthApiError::PrunedHistoryUnavailable,
             err => EthApiError::Internal(err.into()),
         }
     }
@@ -468,6 +482,13 @@ pub enum RpcPoolError {
     ExceedsMaxInitCodeSize,
     #[error(transparent)]
     Invalid(#[from] RpcInvalidTransactionError),
+    /// Thrown if the sender already has a pending transaction of a conflicting type (blob vs
+    /// non-blob) with the same nonce.
+    #[error("address already reserved")]
+    AddressAlreadyReserved,
+    /// Errors specific to EIP-4844 blob transactions
+    #[error(transparent)]
+    Eip4844(#[from] Eip4844PoolTransactionError),
     /// Custom pool error
     #[error("{0:?}")]
     PoolTransactionError(Box<dyn PoolTransactionError>),
@@ -491,6 +512,9 @@ impl From<PoolError> for RpcPoolError {
             PoolError::FeeCapBelowMinimumProtocolFeeCap(_, _) => RpcPoolError::Underpriced,
             PoolError::SpammerExceededCapacity(_, _) => RpcPoolError::TxPoolOverflow,
             PoolError::DiscardedOnInsert(_) => RpcPoolError::TxPoolOverflow,
+            PoolError::ExistingConflictingTransactionType(_, _) => {
+                RpcPoolError::AddressAlreadyReserved
+            }
             PoolError::InvalidTransaction(_, err) => err.into(),
             PoolError::Other(_, err) => RpcPoolError::Other(err),
             PoolError::AlreadyImported(_) => RpcPoolError::AlreadyKnown,
@@ -508,6 +532,7 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             }
             InvalidPoolTransactionError::OversizedData(_, _) => RpcPoolError::OversizedData,
             InvalidPoolTransactionError::Underpriced => RpcPoolError::Underpriced,
+            InvalidPoolTransactionError::Eip4844(err) => RpcPoolError::Eip4844(err),
             InvalidPoolTransactionError::Other(err) => RpcPoolError::PoolTransactionError(err),
         }
     }
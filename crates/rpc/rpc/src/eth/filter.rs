@@ -10,12 +10,18 @@ use crate::{
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_primitives::{BlockHashOrNumber, Receipt, SealedBlock};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider};
+use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, HistoryReader};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
-use std::{collections::HashMap, iter::StepBy, ops::RangeInclusive, sync::Arc, time::Instant};
+use std::{
+    collections::{BTreeSet, HashMap},
+    iter::StepBy,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Instant,
+};
 use tokio::sync::Mutex;
 use tracing::trace;
 
@@ -63,7 +69,7 @@ impl<Provider, Pool> EthFilter<Provider, Pool> {
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + HistoryReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns all the filter changes for the given id, if any
@@ -155,7 +161,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + HistoryReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -250,7 +256,7 @@ struct EthFilterInner<Provider, Pool> {
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + HistoryReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -325,6 +331,9 @@ where
 
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
+    /// If the log index is available, the part of the range that is covered by the index is served
+    /// from it. The remaining blocks are checked against their header blooms.
+    ///
     /// Returns an error if:
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
@@ -338,18 +347,92 @@ where
 
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
+        let is_multi_block_range = from_block != to_block;
+
+        // the index only narrows down the blocks if the filter has any address or topic
+        let is_constrained =
+            !filter.address.is_empty() || filter.topics.iter().any(|topic| !topic.is_empty());
+        let indexed_range = if is_constrained && is_multi_block_range {
+            self.provider.log_index_range()?.and_then(|index_range| {
+                let start = from_block.max(*index_range.start());
+                let end = to_block.min(*index_range.end());
+                (start <= end).then_some(start..=end)
+            })
+        } else {
+            None
+        };
+
+        let Some(indexed_range) = indexed_range else {
+            self.append_logs_in_block_range(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=to_block,
+                is_multi_block_range,
+            )
+            .await?;
+            return Ok(all_logs)
+        };
+
+        // logs must be returned in ascending block order, so the blocks before the indexed range
+        // are handled first
+        if from_block < *indexed_range.start() {
+            self.append_logs_in_block_range(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=*indexed_range.start() - 1,
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        trace!(target: "rpc::eth::filter", ?indexed_range, "using log index");
+        for block_number in self.indexed_log_blocks(filter, indexed_range.clone())? {
+            if let Some((block, receipts)) =
+                self.block_and_receipts_by_number(block_number.into()).await?
+            {
+                self.append_block_logs(
+                    &mut all_logs,
+                    &filter_params,
+                    block,
+                    receipts,
+                    is_multi_block_range,
+                )?;
+            }
+        }
 
+        if *indexed_range.end() < to_block {
+            self.append_logs_in_block_range(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                *indexed_range.end() + 1..=to_block,
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Appends all logs in the given _inclusive_ range that match the filter, checking the header
+    /// bloom of every block in the range.
+    async fn append_logs_in_block_range(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter: &Filter,
+        filter_params: &FilteredParams,
+        range: RangeInclusive<u64>,
+        is_multi_block_range: bool,
+    ) -> Result<(), FilterError> {
         // derive bloom filters from filter input
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
-        let is_multi_block_range = from_block != to_block;
-
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
             let headers = self.provider.headers_range(from..=to)?;
 
             for (idx, header) in headers.iter().enumerate() {
@@ -367,29 +450,84 @@ where
                     if let Some((block, receipts)) =
                         self.block_and_receipts_by_number(num_hash).await?
                     {
-                        let block_hash = block.hash;
-
-                        logs_utils::append_matching_block_logs(
-                            &mut all_logs,
-                            &filter_params,
-                            (block.number, block_hash).into(),
-                            block.body.into_iter().map(|tx| tx.hash()).zip(receipts),
-                            false,
-                        );
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
+                        self.append_block_logs(
+                            all_logs,
+                            filter_params,
+                            block,
+                            receipts,
+                            is_multi_block_range,
+                        )?;
                     }
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(())
+    }
+
+    /// Appends the logs of the block that match the filter.
+    ///
+    /// Returns an error if the amount of matches exceeds the configured limit.
+    fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        block: SealedBlock,
+        receipts: Vec<Receipt>,
+        is_multi_block_range: bool,
+    ) -> Result<(), FilterError> {
+        let block_hash = block.hash;
+
+        logs_utils::append_matching_block_logs(
+            all_logs,
+            filter_params,
+            (block.number, block_hash).into(),
+            block.body.into_iter().map(|tx| tx.hash()).zip(receipts),
+            false,
+        );
+
+        // size check but only if range is multiple blocks, so we always return all
+        // logs of a single block
+        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+            return Err(FilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+        }
+
+        Ok(())
+    }
+
+    /// Returns the blocks in the given range that may contain logs matching the filter, according
+    /// to the log index.
+    ///
+    /// A block is a candidate if it contains a log of any of the filter's addresses and, for every
+    /// topic position of the filter, a log with any of the position's topics. Since the index does
+    /// not record topic positions, the candidates still need to be matched against the filter.
+    fn indexed_log_blocks(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<BTreeSet<u64>, FilterError> {
+        let mut candidates: Option<BTreeSet<u64>> = None;
+
+        if !filter.address.is_empty() {
+            let mut blocks = BTreeSet::new();
+            for address in filter.address.iter() {
+                blocks.extend(self.provider.log_address_history_blocks(*address, range.clone())?);
+            }
+            candidates = Some(blocks);
+        }
+
+        for topic in filter.topics.iter().filter(|topic| !topic.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for value in topic.iter() {
+                blocks.extend(self.provider.log_topic_history_blocks(*value, range.clone())?);
+            }
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        }
+
+        Ok(candidates.unwrap_or_else(|| range.collect()))
     }
 }
 
@@ -487,6 +625,93 @@ impl Iterator for BlockRangeInclusiveIter {
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
+    use reth_interfaces::test_utils::generators::{self, random_signed_tx};
+    use reth_primitives::{bloom::logs_bloom, Address, Block, Bytes, Header, TxType, H256};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    const ADDRESS_A: Address = Address::repeat_byte(0xaa);
+    const ADDRESS_B: Address = Address::repeat_byte(0xbb);
+    const TOPIC_X: H256 = H256::repeat_byte(0x01);
+    const TOPIC_Y: H256 = H256::repeat_byte(0x02);
+
+    fn log(address: Address, topic: H256, number: u64) -> reth_primitives::Log {
+        reth_primitives::Log { address, topics: vec![topic], data: Bytes::from(vec![number as u8]) }
+    }
+
+    /// Inserts the blocks `0..=to_block` with two transactions each, whose logs vary by block.
+    fn insert_blocks_with_logs(provider: &MockEthProvider, to_block: u64) {
+        let mut rng = generators::rng();
+        let mut parent_hash = H256::zero();
+        for number in 0..=to_block {
+            let first = match number % 3 {
+                0 => vec![log(ADDRESS_A, TOPIC_X, number)],
+                1 => vec![log(ADDRESS_A, TOPIC_Y, number)],
+                _ => vec![log(ADDRESS_B, TOPIC_X, number)],
+            };
+            let second =
+                if number % 2 == 0 { vec![log(ADDRESS_B, TOPIC_Y, number)] } else { Vec::new() };
+            let receipts = [first, second]
+                .into_iter()
+                .map(|logs| Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: 0,
+                    logs,
+                })
+                .collect::<Vec<_>>();
+
+            let header = Header {
+                number,
+                parent_hash,
+                logs_bloom: logs_bloom(receipts.iter().flat_map(|receipt| receipt.logs.iter())),
+                ..Default::default()
+            };
+            let hash = header.hash_slow();
+            let body = vec![random_signed_tx(&mut rng), random_signed_tx(&mut rng)];
+            provider.add_block(hash, Block { header, body, ..Default::default() });
+            provider.add_receipts(hash, receipts);
+            parent_hash = hash;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logs_partially_indexed_range() {
+        let provider = MockEthProvider::default();
+        insert_blocks_with_logs(&provider, 9);
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_filter = EthFilter::new(
+            provider.clone(),
+            testing_pool(),
+            cache,
+            usize::MAX,
+            Box::new(TokioTaskExecutor::default()),
+        );
+
+        let filters = [
+            Filter::new().address(ADDRESS_A),
+            Filter::new().topic0(TOPIC_X),
+            Filter::new().address(ADDRESS_A).topic0(TOPIC_Y),
+            Filter::new().address(vec![ADDRESS_A, ADDRESS_B]).topic0(vec![TOPIC_X, TOPIC_Y]),
+        ];
+
+        for filter in filters {
+            let filter = filter.from_block(0u64).to_block(9u64);
+
+            provider.set_log_index(None);
+            let scanned = EthFilterApiServer::logs(&eth_filter, filter.clone()).await.unwrap();
+            assert!(!scanned.is_empty());
+
+            // the index lags behind the tip, starts after pruned blocks, or covers the whole range
+            for index in [0..=5, 3..=6, 0..=9] {
+                provider.set_log_index(Some(index.clone()));
+                let logs = EthFilterApiServer::logs(&eth_filter, filter.clone()).await.unwrap();
+                assert_eq!(logs, scanned, "index {index:?}, filter {filter:?}");
+            }
+        }
+    }
 
     #[test]
     fn test_block_range_iter() {
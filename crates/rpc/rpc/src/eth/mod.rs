@@ -1,6 +1,7 @@
 //! `eth` namespace handler implementation.
 
 mod api;
+mod bundle;
 pub mod cache;
 pub mod error;
 mod filter;
@@ -13,6 +14,7 @@ mod signer;
 pub(crate) mod utils;
 
 pub use api::{EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP};
+pub use bundle::EthBundle;
 pub use filter::EthFilter;
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pubsub::EthPubSub;
@@ -4,6 +4,7 @@ use crate::eth::error::{EthApiError, EthResult, RpcInvalidTransactionError};
 use reth_primitives::{
     AccessList, Address, TransactionSigned, TransactionSignedEcRecovered, TxHash, H256, U256,
 };
+use reth_provider::ImpersonatedSenders;
 use reth_revm::env::{fill_tx_env, fill_tx_env_with_recovered};
 use reth_rpc_types::{
     state::{AccountOverride, StateOverride},
@@ -195,6 +196,25 @@ where
     Ok(())
 }
 
+/// Recovers the senders of all transactions that precede the target transaction.
+///
+/// The senders of impersonated transactions are taken from the [ImpersonatedSenders].
+pub(crate) fn recover_transactions_until(
+    impersonated_senders: &ImpersonatedSenders,
+    transactions: Vec<TransactionSigned>,
+    target_tx_hash: H256,
+) -> EthResult<Vec<TransactionSignedEcRecovered>> {
+    transactions
+        .into_iter()
+        .take_while(|tx| tx.hash != target_tx_hash)
+        .map(|tx| {
+            impersonated_senders
+                .into_ecrecovered(tx)
+                .ok_or(EthApiError::InvalidTransactionSignature)
+        })
+        .collect()
+}
+
 /// Prepares the [Env] for execution.
 ///
 /// Does not commit any changes to the underlying database.
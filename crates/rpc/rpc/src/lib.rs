@@ -47,13 +47,15 @@ mod web3;
 pub use admin::AdminApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
+pub use eth::{EthApi, EthApiSpec, EthBundle, EthFilter, EthPubSub, EthSubscriptionIdProvider};
 pub use layers::{AuthLayer, AuthValidator, Claims, JwtAuthValidator, JwtError, JwtSecret};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
 pub use rpc::RPCApi;
-pub use trace::TraceApi;
+pub use trace::{
+    TraceApi, TraceFilterLimits, DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_MAX_TRACE_FILTER_TRACES,
+};
 pub use tracing_call::{TracingCallGuard, TracingCallPool};
 pub use txpool::TxPoolApi;
 pub use web3::Web3Api;
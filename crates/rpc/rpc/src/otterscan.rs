@@ -1,37 +1,299 @@
-#![allow(dead_code, unused_variables)]
-use crate::result::internal_rpc_err;
+use crate::eth::{
+    error::{EthApiError, EthResult},
+    EthTransactions,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, H256};
+use reth_consensus_common::calc::{base_block_reward, block_reward, ommer_reward};
+use reth_primitives::{Address, BlockId, BlockNumber, BlockNumberOrTag, Bytes, TxHash, H256, U256};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, HistoryOrder, HistoryReader, StateProviderFactory,
+};
+use reth_revm::tracing::TracingInspectorConfig;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
-    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
-    Transaction, TransactionsWithReceipts,
+    BlockDetails, BlockTransactions, ContractCreator, InternalIssuance, InternalOperation,
+    OtsBlock, OtsBlockTransactions, OtsTransactionReceipt, RichBlock, TraceEntry, Transaction,
+    TransactionReceipt, TransactionsWithReceipts,
 };
+use revm_primitives::ExecutionResult;
+use std::{collections::BTreeSet, ops::RangeInclusive, sync::Arc};
 
 const API_LEVEL: u64 = 8;
 
+/// The maximum number of blocks whose transactions are scanned for senders and recipients by a
+/// single `ots_searchTransactions*` request.
+const MAX_TRANSACTION_SCAN_BLOCKS: u64 = 1_000;
+
 /// Otterscan Api
-#[derive(Debug)]
-pub struct OtterscanApi<Eth> {
-    eth: Eth,
+///
+/// `ots_getTransactionBySenderAndNonce` and `ots_getContractCreator` are served from the account
+/// history index, which records every nonce increment and contract deployment.
+///
+/// `ots_searchTransactions*` requests re-execute candidate blocks to find the transactions that
+/// touch the address. Candidates are the blocks in which the state of the address changed,
+/// according to the account and storage history indices, and the blocks with a transaction sent
+/// from or to the address. The latter are found by scanning the transactions of the first
+/// [MAX_TRANSACTION_SCAN_BLOCKS] blocks of every request, so transactions further away that touch
+/// an address without changing its state, for example zero value calls to a contract that
+/// doesn't write storage, may not be found.
+pub struct OtterscanApi<Provider, Eth> {
+    inner: Arc<OtterscanApiInner<Provider, Eth>>,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+// === impl OtterscanApi ===
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(provider: Provider, eth: Eth) -> Self {
+        Self { inner: Arc::new(OtterscanApiInner { provider, eth }) }
+    }
+
+    /// The provider that can interact with the chain.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + HistoryReader
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
+{
+    /// Returns all blocks up to the tip in which the account of the address changed, sorted in
+    /// ascending order.
+    fn account_blocks(&self, address: Address) -> EthResult<Vec<BlockNumber>> {
+        let tip = self.provider().best_block_number()?;
+        Ok(self.provider().account_history_blocks(
+            address,
+            0..=tip,
+            usize::MAX,
+            HistoryOrder::Ascending,
+        )?)
+    }
+
+    /// Returns the next window of a transaction search that walks the range in the given order,
+    /// and the candidate blocks in the window, which may touch the address.
+    ///
+    /// The candidates are the blocks in which the state of the address changed, at most `limit`
+    /// of them from both the account and the storage history index, and the window ends where
+    /// these may be incomplete. As long as the `scan_budget` allows, the transactions of the
+    /// window are scanned and blocks with a transaction sent from or to the address are
+    /// candidates as well. The window is then bounded by the budget.
+    fn search_window(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+        scan_budget: &mut u64,
+    ) -> EthResult<(RangeInclusive<BlockNumber>, BTreeSet<BlockNumber>)> {
+        let provider = self.provider();
+        let limit = limit.max(1);
+        let lookups = [
+            provider.account_history_blocks(address, range.clone(), limit, order)?,
+            provider.storage_history_blocks(address, range.clone(), limit, order)?,
+        ];
+
+        let (mut start, mut end) = range.into_inner();
+        for blocks in lookups.iter().filter(|blocks| blocks.len() >= limit) {
+            match order {
+                HistoryOrder::Ascending => end = end.min(blocks[blocks.len() - 1]),
+                HistoryOrder::Descending => start = start.max(blocks[0]),
+            }
+        }
+
+        let mut candidates = BTreeSet::new();
+        if *scan_budget > 0 {
+            match order {
+                HistoryOrder::Ascending => end = end.min(start.saturating_add(*scan_budget - 1)),
+                HistoryOrder::Descending => start = start.max(end.saturating_sub(*scan_budget - 1)),
+            }
+            *scan_budget = scan_budget.saturating_sub(end - start + 1);
+
+            for number in start..=end {
+                let Some(block) = provider.block_with_senders(number)? else { continue };
+                let touches_address = block
+                    .body
+                    .iter()
+                    .zip(block.senders.iter())
+                    .any(|(tx, sender)| *sender == address || tx.to() == Some(address));
+                if touches_address {
+                    candidates.insert(number);
+                }
+            }
+        }
+
+        let window = start..=end;
+        candidates.extend(lookups.into_iter().flatten().filter(|block| window.contains(block)));
+        Ok((window, candidates))
+    }
+
+    /// Re-executes the block and returns the indices of all transactions that touched the
+    /// address.
+    async fn transactions_touching(
+        &self,
+        block: BlockNumber,
+        address: Address,
+    ) -> EthResult<Vec<usize>> {
+        let touched = self
+            .eth_api()
+            .trace_block_with(
+                BlockId::Number(block.into()),
+                TracingInspectorConfig::default_parity(),
+                move |_, inspector, _, _, _| {
+                    Ok(inspector.into_otterscan_builder().contains_address(address))
+                },
+            )
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        Ok(touched
+            .into_iter()
+            .enumerate()
+            .filter(|(_, touched)| *touched)
+            .map(|(i, _)| i)
+            .collect())
+    }
+
+    /// Returns the block with full transactions and the receipts of the block.
+    async fn block_with_receipts(
+        &self,
+        block: BlockNumber,
+    ) -> RpcResult<Option<(RichBlock, Vec<TransactionReceipt>)>> {
+        let number = BlockNumberOrTag::Number(block);
+        let Some(block) = self.eth_api().block_by_number(number, true).await? else {
+            return Ok(None)
+        };
+        let receipts = self.eth_api().block_receipts(number).await?.unwrap_or_default();
+        Ok(Some((block, receipts)))
+    }
+
+    /// Returns the transactions and receipts with the given indices of the block.
+    ///
+    /// Entries are returned in the order of the given indices.
+    async fn transactions_with_receipts(
+        &self,
+        block: BlockNumber,
+        indices: impl IntoIterator<Item = usize>,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let (block, receipts) =
+            self.block_with_receipts(block).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let timestamp = block.header.timestamp.to::<u64>();
+        let BlockTransactions::Full(transactions) = block.inner.transactions else {
+            return Err(EthApiError::InternalEthError.into())
+        };
+
+        let mut txs = Vec::new();
+        let mut tx_receipts = Vec::new();
+        for idx in indices {
+            let (Some(tx), Some(receipt)) = (transactions.get(idx), receipts.get(idx)) else {
+                continue
+            };
+            txs.push(tx.clone());
+            tx_receipts.push(OtsTransactionReceipt { receipt: receipt.clone(), timestamp });
+        }
+        Ok((txs, tx_receipts))
+    }
+
+    /// Computes the issuance and the fees of the block.
+    async fn block_details(&self, block: RichBlock) -> RpcResult<BlockDetails> {
+        let number = block.header.number.ok_or(EthApiError::UnknownBlockNumber)?.to::<u64>();
+        let receipts = self
+            .eth_api()
+            .block_receipts(BlockNumberOrTag::Number(number))
+            .await?
+            .unwrap_or_default();
+        let total_fees = receipts.iter().fold(U256::ZERO, |fees, receipt| {
+            let gas_used = receipt.gas_used.unwrap_or_default();
+            fees + gas_used * U256::from(receipt.effective_gas_price.to::<u128>())
+        });
+        let issuance = self.block_issuance(number, block.header.difficulty)?;
+
+        let mut block: OtsBlock = block.inner.into();
+        // the transaction count is all that's needed for the details page
+        block.block.transactions = BlockTransactions::Hashes(Vec::new());
+
+        Ok(BlockDetails { block, issuance, total_fees })
+    }
+
+    /// Returns the block and ommer rewards of the block.
+    ///
+    /// Returns zero issuance for blocks after the merge.
+    fn block_issuance(&self, number: BlockNumber, difficulty: U256) -> EthResult<InternalIssuance> {
+        let Some(td) = self.provider().header_td_by_number(number)? else {
+            return Ok(InternalIssuance::default())
+        };
+        let chain_spec = self.provider().chain_spec();
+        let Some(base_reward) = base_block_reward(&chain_spec, number, difficulty, td) else {
+            return Ok(InternalIssuance::default())
+        };
+
+        let ommers = self.provider().ommers(number.into())?.unwrap_or_default();
+        let block_reward = block_reward(base_reward, ommers.len());
+        let uncle_reward = ommers.iter().fold(U256::ZERO, |reward, ommer| {
+            reward + ommer_reward(base_reward, number, ommer.number)
+        });
+
+        Ok(InternalIssuance { block_reward, uncle_reward, issuance: block_reward + uncle_reward })
+    }
+
+    /// Returns the nonce of the account after the given block.
+    fn nonce_after(&self, address: Address, block: BlockNumber) -> EthResult<u64> {
+        let state = self.provider().history_by_block_number(block)?;
+        Ok(state.account_nonce(address)?.unwrap_or_default())
+    }
+
+    /// Returns true if the account has code after the given block.
+    fn has_code_after(&self, address: Address, block: BlockNumber) -> EthResult<bool> {
+        let state = self.provider().history_by_block_number(block)?;
+        Ok(state.account_code(address)?.is_some())
+    }
+
+    /// Returns the first block in `blocks` for which `predicate` is true.
+    ///
+    /// `blocks` must be sorted and `predicate` must be monotonic over `blocks`.
+    fn first_block_where(
+        blocks: &[BlockNumber],
+        mut predicate: impl FnMut(BlockNumber) -> EthResult<bool>,
+    ) -> EthResult<Option<BlockNumber>> {
+        let (mut low, mut high) = (0, blocks.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if predicate(blocks[mid])? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(blocks.get(low).copied())
     }
 }
 
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
-    Eth: EthApiServer,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + HistoryReader
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
 {
     /// Handler for `ots_hasCode`
     async fn has_code(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<bool> {
-        self.eth.get_code(address, block_number).await.map(|code| !code.is_empty())
+        self.eth_api().get_code(address, block_number).await.map(|code| !code.is_empty())
     }
 
     /// Handler for `ots_getApiLevel`
@@ -41,62 +303,210 @@ where
 
     /// Handler for `ots_getInternalOperations`
     async fn get_internal_operations(&self, tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>> {
-        Err(internal_rpc_err("unimplemented"))
+        let operations = self
+            .eth_api()
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_, inspector, _, _| {
+                    Ok(inspector.into_otterscan_builder().into_internal_operations())
+                },
+            )
+            .await?;
+        Ok(operations.unwrap_or_default())
     }
 
     /// Handler for `ots_getTransactionError`
-    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<String> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Bytes> {
+        let output = self
+            .eth_api()
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_, _, res, _| match res.result {
+                    ExecutionResult::Revert { output, .. } => Ok(output.into()),
+                    _ => Ok(Bytes::default()),
+                },
+            )
+            .await?;
+        Ok(output.ok_or(EthApiError::TransactionNotFound)?)
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<TraceEntry> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>> {
+        let entries = self
+            .eth_api()
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_, inspector, _, _| Ok(inspector.into_otterscan_builder().into_trace_entries()),
+            )
+            .await?;
+        Ok(entries)
     }
 
     /// Handler for `ots_getBlockDetails`
-    async fn get_block_details(
-        &self,
-        block_number: BlockNumberOrTag,
-    ) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_number(block_number, true).await?;
-        Ok(block.map(Into::into))
+    async fn get_block_details(&self, block_number: u64) -> RpcResult<Option<BlockDetails>> {
+        let block = self.eth_api().block_by_number(block_number.into(), false).await?;
+        match block {
+            Some(block) => Ok(Some(self.block_details(block).await?)),
+            None => Ok(None),
+        }
     }
 
     /// Handler for `getBlockDetailsByHash`
     async fn get_block_details_by_hash(&self, block_hash: H256) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_hash(block_hash, true).await?;
-        Ok(block.map(Into::into))
+        let block = self.eth_api().block_by_hash(block_hash, false).await?;
+        match block {
+            Some(block) => Ok(Some(self.block_details(block).await?)),
+            None => Ok(None),
+        }
     }
 
     /// Handler for `getBlockTransactions`
     async fn get_block_transactions(
         &self,
-        block_number: BlockNumberOrTag,
+        block_number: u64,
         page_number: usize,
         page_size: usize,
-    ) -> RpcResult<OtsBlockTransactions> {
-        Err(internal_rpc_err("unimplemented"))
+    ) -> RpcResult<Option<OtsBlockTransactions>> {
+        let Some((block, receipts)) = self.block_with_receipts(block_number).await? else {
+            return Ok(None)
+        };
+        let timestamp = block.header.timestamp.to::<u64>();
+        let mut fullblock: OtsBlock = block.inner.into();
+
+        // pages are counted from the end of the block, the first page holds the last transactions
+        let page_end =
+            fullblock.transaction_count.saturating_sub(page_number.saturating_mul(page_size));
+        let page_start = page_end.saturating_sub(page_size);
+
+        if let BlockTransactions::Full(transactions) = &mut fullblock.block.transactions {
+            transactions.truncate(page_end);
+            transactions.drain(..page_start.min(transactions.len()));
+        }
+
+        let receipts = receipts
+            .into_iter()
+            .skip(page_start)
+            .take(page_end - page_start)
+            .map(|mut receipt| {
+                receipt.logs.clear();
+                OtsTransactionReceipt { receipt, timestamp }
+            })
+            .collect();
+
+        Ok(Some(OtsBlockTransactions { fullblock, receipts }))
     }
 
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
         address: Address,
-        block_number: BlockNumberOrTag,
+        block_number: u64,
         page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let tip = self.provider().best_block_number().map_err(EthApiError::from)?;
+        let (end, first_page) = match block_number {
+            0 => (tip, true),
+            n if n > tip => (tip, true),
+            n => (n - 1, false),
+        };
+
+        // the search walks the blocks from `end` down to genesis in windows
+        let mut next = Some(end);
+        let mut candidates = BTreeSet::new();
+        let mut scan_budget = MAX_TRANSACTION_SCAN_BLOCKS;
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+
+        // complete blocks are returned, so a page can exceed the page size
+        while txs.len() < page_size {
+            let block = match candidates.pop_last() {
+                Some(block) => block,
+                None => {
+                    let Some(end) = next else { break };
+                    let (window, blocks) = self.search_window(
+                        address,
+                        0..=end,
+                        page_size,
+                        HistoryOrder::Descending,
+                        &mut scan_budget,
+                    )?;
+                    next = window.start().checked_sub(1);
+                    candidates = blocks;
+                    continue
+                }
+            };
+            let indices = self.transactions_touching(block, address).await?;
+            if indices.is_empty() {
+                continue
+            }
+            let (block_txs, block_receipts) =
+                self.transactions_with_receipts(block, indices.into_iter().rev()).await?;
+            txs.extend(block_txs);
+            receipts.extend(block_receipts);
+        }
+
+        let last_page = candidates.is_empty() && next.is_none();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
         address: Address,
-        block_number: BlockNumberOrTag,
+        block_number: u64,
         page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let tip = self.provider().best_block_number().map_err(EthApiError::from)?;
+        let start = block_number.saturating_add(1);
+
+        // the search walks the blocks from `start` up to the tip in windows
+        let mut next = (start <= tip).then_some(start);
+        let mut candidates = BTreeSet::new();
+        let mut scan_budget = MAX_TRANSACTION_SCAN_BLOCKS;
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+
+        // complete blocks are returned, so a page can exceed the page size
+        while txs.len() < page_size {
+            let block = match candidates.pop_first() {
+                Some(block) => block,
+                None => {
+                    let Some(start) = next else { break };
+                    let (window, blocks) = self.search_window(
+                        address,
+                        start..=tip,
+                        page_size,
+                        HistoryOrder::Ascending,
+                        &mut scan_budget,
+                    )?;
+                    next = window.end().checked_add(1).filter(|next| *next <= tip);
+                    candidates = blocks;
+                    continue
+                }
+            };
+            let indices = self.transactions_touching(block, address).await?;
+            if indices.is_empty() {
+                continue
+            }
+            let (block_txs, block_receipts) =
+                self.transactions_with_receipts(block, indices).await?;
+            txs.extend(block_txs);
+            receipts.extend(block_receipts);
+        }
+
+        // results are always returned newest first
+        txs.reverse();
+        receipts.reverse();
+
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: candidates.is_empty() && next.is_none(),
+            last_page: block_number == 0,
+        })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
@@ -104,12 +514,209 @@ where
         &self,
         sender: Address,
         nonce: u64,
-    ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+    ) -> RpcResult<Option<TxHash>> {
+        let blocks = self.account_blocks(sender)?;
+
+        // the nonce of the sender is incremented in the block that includes the transaction
+        let Some(block) =
+            Self::first_block_where(&blocks, |block| Ok(self.nonce_after(sender, block)? > nonce))?
+        else {
+            return Ok(None)
+        };
+
+        let Some(block) = self.provider().block_with_senders(block).map_err(EthApiError::from)?
+        else {
+            return Ok(None)
+        };
+        let hash = block
+            .body
+            .iter()
+            .zip(block.senders.iter())
+            .find(|(tx, signer)| **signer == sender && tx.nonce() == nonce)
+            .map(|(tx, _)| tx.hash());
+        Ok(hash)
     }
 
     /// Handler for `getContractCreator`
     async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+        let latest = self.provider().latest().map_err(EthApiError::from)?;
+        if latest.account_code(address).map_err(EthApiError::from)?.is_none() {
+            return Ok(None)
+        }
+        drop(latest);
+
+        let blocks = self.account_blocks(address)?;
+
+        let Some(block) =
+            Self::first_block_where(&blocks, |block| self.has_code_after(address, block))?
+        else {
+            return Ok(None)
+        };
+
+        let creators = self
+            .eth_api()
+            .trace_block_with(
+                BlockId::Number(block.into()),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    let creator = inspector
+                        .into_otterscan_builder()
+                        .created_contracts()
+                        .find(|(_, contract)| *contract == address)
+                        .map(|(creator, _)| creator);
+                    Ok(tx_info.hash.zip(creator))
+                },
+            )
+            .await?
+            .unwrap_or_default();
+
+        Ok(creators
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|(hash, creator)| ContractCreator { hash, creator }))
+    }
+}
+
+impl<Provider, Eth> std::fmt::Debug for OtterscanApi<Provider, Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OtterscanApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Eth> Clone for OtterscanApi<Provider, Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct OtterscanApiInner<Provider, Eth> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The implementation of `eth` API
+    eth: Eth,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+        EthApi, TracingCallPool,
+    };
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, sign_message, Block, Header, Receipt, Transaction,
+        TransactionKind, TransactionSigned, TxLegacy, TxType,
+    };
+    use reth_provider::{test_utils::MockEthProvider, BlockReader};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestOtterscanApi =
+        OtterscanApi<MockEthProvider, EthApi<MockEthProvider, TestPool, NoopNetwork>>;
+
+    /// Returns a provider with a genesis block and `num_blocks` blocks on top of it that each
+    /// contain a single zero value call, to `Address::repeat_byte(1)` in odd blocks and to
+    /// `Address::repeat_byte(2)` in even blocks.
+    fn search_provider(num_blocks: u64) -> MockEthProvider {
+        let provider = MockEthProvider::default();
+        let mut parent_hash = H256::zero();
+        for number in 0..=num_blocks {
+            let mut body = Vec::new();
+            let mut receipts = Vec::new();
+            if number > 0 {
+                let tx = Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: TransactionKind::Call(Address::repeat_byte(2 - number as u8 % 2)),
+                    ..Default::default()
+                });
+                let signature =
+                    sign_message(H256::from_low_u64_be(0x1337), tx.signature_hash()).unwrap();
+                body.push(TransactionSigned::from_transaction_and_signature(tx, signature));
+                receipts.push(Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: 21_000,
+                    logs: vec![],
+                });
+            }
+            let header = Header {
+                number,
+                parent_hash,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                ..Default::default()
+            };
+            parent_hash = header.hash_slow();
+            provider.add_block(parent_hash, Block { header, body, ..Default::default() });
+            provider.add_receipts(parent_hash, receipts);
+        }
+        provider
+    }
+
+    fn build_test_otterscan_api(provider: MockEthProvider) -> TestOtterscanApi {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            TracingCallPool::build().expect("failed to build tracing pool"),
+        );
+        OtterscanApi::new(provider, eth_api)
+    }
+
+    /// Returns the numbers of the blocks of the found transactions.
+    fn block_numbers(found: &TransactionsWithReceipts) -> Vec<u64> {
+        found.txs.iter().map(|tx| tx.block_number.unwrap().to::<u64>()).collect()
+    }
+
+    #[tokio::test]
+    async fn search_transactions_to_address_without_state_changes() {
+        // the history indices don't know about any of the calls
+        let api = build_test_otterscan_api(search_provider(5));
+        let target = Address::repeat_byte(1);
+
+        let found = api.search_transactions_before(target, 0, 10).await.unwrap();
+        assert_eq!(block_numbers(&found), vec![5, 3, 1]);
+        assert!(found.first_page && found.last_page);
+
+        let found = api.search_transactions_after(target, 0, 10).await.unwrap();
+        assert_eq!(block_numbers(&found), vec![5, 3, 1]);
+        assert!(found.first_page && found.last_page);
+    }
+
+    #[tokio::test]
+    async fn search_transactions_pages() {
+        let api = build_test_otterscan_api(search_provider(5));
+        let target = Address::repeat_byte(1);
+
+        let found = api.search_transactions_before(target, 0, 1).await.unwrap();
+        assert_eq!(block_numbers(&found), vec![5]);
+        assert!(found.first_page && !found.last_page);
+
+        let found = api.search_transactions_before(target, 5, 1).await.unwrap();
+        assert_eq!(block_numbers(&found), vec![3]);
+        assert!(!found.first_page && !found.last_page);
+
+        let found = api.search_transactions_after(target, 1, 1).await.unwrap();
+        assert_eq!(block_numbers(&found), vec![3]);
+        assert!(!found.first_page && !found.last_page);
+    }
+
+    #[tokio::test]
+    async fn search_transactions_from_history_index() {
+        // the state of the sender changes in every block
+        let provider = search_provider(4);
+        let block = provider.block_with_senders(1).unwrap().unwrap();
+        let sender = block.senders[0];
+        provider.add_account_history(sender, 1..=4);
+        let api = build_test_otterscan_api(provider);
+
+        let found = api.search_transactions_before(sender, 4, 2).await.unwrap();
+        assert_eq!(block_numbers(&found), vec![3, 2]);
+        assert!(!found.first_page && !found.last_page);
     }
 }
@@ -5,15 +5,15 @@ use crate::{
         utils::recover_raw_transaction,
         EthTransactions,
     },
-    result::internal_rpc_err,
     TracingCallGuard,
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
 use reth_consensus_common::calc::{base_block_reward, block_reward};
-use reth_primitives::{BlockId, BlockNumberOrTag, Bytes, SealedHeader, H256, U256};
+use reth_primitives::{BlockId, BlockNumber, BlockNumberOrTag, Bytes, SealedHeader, H256, U256};
 use reth_provider::{
-    BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockReader, ChainSpecProvider, EvmEnvProvider, HistoryOrder, HistoryReader,
+    StateProviderFactory,
 };
 use reth_revm::{
     database::{State, SubState},
@@ -26,11 +26,14 @@ use reth_rpc_api::TraceApiServer;
 use reth_rpc_types::{
     state::StateOverride,
     trace::{filter::TraceFilter, parity::*},
-    BlockError, BlockOverrides, CallRequest, Index, TransactionInfo,
+    BlockOverrides, CallRequest, Index,
+};
+use revm::primitives::Env;
+use revm_primitives::{db::DatabaseCommit, ResultAndState};
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::Arc,
 };
-use revm::{db::CacheDB, primitives::Env};
-use revm_primitives::{db::DatabaseCommit, ExecutionResult, ResultAndState};
-use std::{collections::HashSet, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `trace` API implementation.
@@ -49,8 +52,14 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [TraceApi]
-    pub fn new(provider: Provider, eth_api: Eth, tracing_call_guard: TracingCallGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, tracing_call_guard });
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        tracing_call_guard: TracingCallGuard,
+        filter_limits: TraceFilterLimits,
+    ) -> Self {
+        let inner =
+            Arc::new(TraceApiInner { provider, eth_api, tracing_call_guard, filter_limits });
         Self { inner }
     }
 
@@ -66,7 +75,12 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
 
 impl<Provider, Eth> TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider: BlockReader
+        + StateProviderFactory
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + HistoryReader
+        + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -268,99 +282,12 @@ where
             .await
     }
 
-    /// Executes all transactions of a block and returns a list of callback results.
-    ///
-    /// This
-    /// 1. fetches all transactions of the block
-    /// 2. configures the EVM evn
-    /// 3. loops over all transactions and executes them
-    /// 4. calls the callback with the transaction info, the execution result, the changed state
-    /// _after_ the transaction [State] and the database that points to the state right _before_ the
-    /// transaction.
-    async fn trace_block_with<F, R>(
-        &self,
-        block_id: BlockId,
-        config: TracingInspectorConfig,
-        f: F,
-    ) -> EthResult<Option<Vec<R>>>
-    where
-        // This is the callback that's invoked for each transaction with
-        F: for<'a> Fn(
-                TransactionInfo,
-                TracingInspector,
-                ExecutionResult,
-                &'a revm_primitives::State,
-                &'a CacheDB<State<StateProviderBox<'a>>>,
-            ) -> EthResult<R>
-            + Send
-            + 'static,
-        R: Send + 'static,
-    {
-        let ((cfg, block_env, _), block) = futures::try_join!(
-            self.inner.eth_api.evm_env_at(block_id),
-            self.inner.eth_api.block_by_id(block_id),
-        )?;
-
-        let block = match block {
-            Some(block) => block,
-            None => return Ok(None),
-        };
-
-        // we need to get the state of the parent block because we're replaying this block on top of
-        // its parent block's state
-        let state_at = block.parent_hash;
-
-        let block_hash = block.hash;
-        let transactions = block.body;
-
-        // replay all transactions of the block
-        self.inner
-            .eth_api
-            .spawn_with_state_at_block(state_at.into(), move |state| {
-                let mut results = Vec::with_capacity(transactions.len());
-                let mut db = SubState::new(State::new(state));
-
-                let mut transactions = transactions.into_iter().enumerate().peekable();
-
-                while let Some((idx, tx)) = transactions.next() {
-                    let tx = tx.into_ecrecovered().ok_or(BlockError::InvalidSignature)?;
-                    let tx_info = TransactionInfo {
-                        hash: Some(tx.hash()),
-                        index: Some(idx as u64),
-                        block_hash: Some(block_hash),
-                        block_number: Some(block_env.number.try_into().unwrap_or(u64::MAX)),
-                        base_fee: Some(block_env.basefee.try_into().unwrap_or(u64::MAX)),
-                    };
-
-                    let tx = tx_env_with_recovered(&tx);
-                    let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
-
-                    let mut inspector = TracingInspector::new(config);
-                    let (res, _) = inspect(&mut db, env, &mut inspector)?;
-                    let ResultAndState { result, state } = res;
-                    results.push(f(tx_info, inspector, result, &state, &db)?);
-
-                    // need to apply the state changes of this transaction before executing the
-                    // next transaction
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this transaction before executing
-                        // the next transaction
-                        db.commit(state)
-                    }
-                }
-
-                Ok(results)
-            })
-            .await
-            .map(Some)
-    }
-
     /// Returns traces created at given block.
     pub async fn trace_block(
         &self,
         block_id: BlockId,
     ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
-        let traces = self.trace_block_with(
+        let traces = self.inner.eth_api.trace_block_with(
             block_id,
             TracingInspectorConfig::default_parity(),
             |tx_info, inspector, _, _, _| {
@@ -411,44 +338,104 @@ where
         Ok(maybe_traces)
     }
 
+    /// Returns all traces of the block range that match the filter.
+    ///
+    /// If the filter doesn't specify a block range, only the latest block is traced.
+    ///
+    /// Ranges of up to `max_blocks` blocks are traced in full. For larger ranges, the filter must
+    /// contain addresses, and only the blocks in which the state of one of these addresses changed
+    /// are traced, which may miss calls to or from these addresses.
+    pub async fn trace_filter(
+        &self,
+        filter: TraceFilter,
+    ) -> EthResult<Vec<LocalizedTransactionTrace>> {
+        let TraceFilterLimits { max_blocks, max_traces } = self.inner.filter_limits;
+        if filter.count == Some(0) {
+            return Ok(Vec::new())
+        }
+
+        let best = self.provider().best_block_number()?;
+        let to = filter.to_block.unwrap_or(best).min(best);
+        let from = filter.from_block.unwrap_or(to);
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        let blocks = trace_filter_blocks(self.provider(), &filter, from, to, max_blocks)?;
+        let matcher = filter.matcher();
+        let mut skip = filter.after.unwrap_or_default() as usize;
+        let count = filter.count.map(|count| count as usize);
+
+        let mut traces = Vec::new();
+        for block in blocks {
+            let Some(block_traces) = self.trace_block(block.into()).await? else { continue };
+
+            for trace in block_traces.into_iter().filter(|trace| matcher.matches(&trace.trace)) {
+                if skip > 0 {
+                    skip -= 1;
+                    continue
+                }
+                traces.push(trace);
+
+                if count == Some(traces.len()) {
+                    return Ok(traces)
+                }
+                if traces.len() > max_traces {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "query exceeds max results {max_traces}"
+                    )))
+                }
+            }
+        }
+
+        Ok(traces)
+    }
+
     /// Replays all transactions in a block
     pub async fn replay_block_transactions(
         &self,
         block_id: BlockId,
         trace_types: HashSet<TraceType>,
     ) -> EthResult<Option<Vec<TraceResultsWithTransactionHash>>> {
-        self.trace_block_with(
-            block_id,
-            tracing_config(&trace_types),
-            move |tx_info, inspector, res, state, db| {
-                let mut full_trace =
-                    inspector.into_parity_builder().into_trace_results(res, &trace_types);
-
-                // If statediffs were requested, populate them with the account balance and nonce
-                // from pre-state
-                if let Some(ref mut state_diff) = full_trace.state_diff {
-                    populate_account_balance_nonce_diffs(
-                        state_diff,
-                        db,
-                        state.iter().map(|(addr, acc)| (*addr, acc.info.clone())),
-                    )?;
-                }
+        self.inner
+            .eth_api
+            .trace_block_with(
+                block_id,
+                tracing_config(&trace_types),
+                move |tx_info, inspector, res, state, db| {
+                    let mut full_trace =
+                        inspector.into_parity_builder().into_trace_results(res, &trace_types);
 
-                let trace = TraceResultsWithTransactionHash {
-                    transaction_hash: tx_info.hash.expect("tx hash is set"),
-                    full_trace,
-                };
-                Ok(trace)
-            },
-        )
-        .await
+                    // If statediffs were requested, populate them with the account balance and
+                    // nonce from pre-state
+                    if let Some(ref mut state_diff) = full_trace.state_diff {
+                        populate_account_balance_nonce_diffs(
+                            state_diff,
+                            db,
+                            state.iter().map(|(addr, acc)| (*addr, acc.info.clone())),
+                        )?;
+                    }
+
+                    let trace = TraceResultsWithTransactionHash {
+                        transaction_hash: tx_info.hash.expect("tx hash is set"),
+                        full_trace,
+                    };
+                    Ok(trace)
+                },
+            )
+            .await
     }
 }
 
 #[async_trait]
 impl<Provider, Eth> TraceApiServer for TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider: BlockReader
+        + StateProviderFactory
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + HistoryReader
+        + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -525,8 +512,9 @@ where
     }
 
     /// Handler for `trace_filter`
-    async fn trace_filter(&self, _filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(TraceApi::trace_filter(self, filter).await?)
     }
 
     /// Returns transaction trace at given index.
@@ -568,6 +556,91 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     tracing_call_guard: TracingCallGuard,
+    /// Limits for `trace_filter` requests
+    filter_limits: TraceFilterLimits,
+}
+
+/// The default maximum number of blocks a `trace_filter` request can trace.
+pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
+
+/// The default maximum number of traces a `trace_filter` request can return.
+pub const DEFAULT_MAX_TRACE_FILTER_TRACES: usize = 10_000;
+
+/// Limits for `trace_filter` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceFilterLimits {
+    /// The maximum number of blocks that are traced for a single request.
+    ///
+    /// Larger ranges can only be requested with addresses, in which case only the blocks in which
+    /// the state of one of the addresses changed are traced and count towards this limit.
+    pub max_blocks: u64,
+    /// The maximum number of traces that can be returned in a single response.
+    pub max_traces: usize,
+}
+
+impl Default for TraceFilterLimits {
+    fn default() -> Self {
+        Self {
+            max_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_traces: DEFAULT_MAX_TRACE_FILTER_TRACES,
+        }
+    }
+}
+
+/// Returns the blocks of the range that need to be traced for the filter, in ascending order.
+///
+/// If the range contains at most `max_blocks` blocks, all of them are traced.
+///
+/// Otherwise, the filter must contain addresses, and the blocks are prefiltered with the account
+/// and storage history indices: only the blocks in which the state of one of the addresses changed
+/// are traced. This prefilter is not exhaustive: calls that don't change the state of either the
+/// caller or the callee, for example zero value calls to a contract that doesn't write to
+/// storage, are missed.
+///
+/// Returns an error if more than `max_blocks` blocks would need to be traced.
+fn trace_filter_blocks(
+    provider: &impl HistoryReader,
+    filter: &TraceFilter,
+    from: BlockNumber,
+    to: BlockNumber,
+    max_blocks: u64,
+) -> EthResult<Vec<BlockNumber>> {
+    let exceeds_max_blocks = || {
+        EthApiError::InvalidParams(format!(
+            "query exceeds the maximum of {max_blocks} traced blocks"
+        ))
+    };
+
+    if to - from < max_blocks {
+        return Ok((from..=to).collect())
+    }
+
+    let mut addresses = filter.addresses().peekable();
+    if addresses.peek().is_none() {
+        return Err(exceeds_max_blocks())
+    }
+
+    // Every lookup is bounded, one more block than allowed is enough to detect an excess.
+    let limit = usize::try_from(max_blocks).unwrap_or(usize::MAX).saturating_add(1);
+    let mut blocks = BTreeSet::new();
+    for address in addresses {
+        blocks.extend(provider.account_history_blocks(
+            address,
+            from..=to,
+            limit,
+            HistoryOrder::Ascending,
+        )?);
+        blocks.extend(provider.storage_history_blocks(
+            address,
+            from..=to,
+            limit,
+            HistoryOrder::Ascending,
+        )?);
+        if blocks.len() as u64 > max_blocks {
+            return Err(exceeds_max_blocks())
+        }
+    }
+    Ok(blocks.into_iter().collect())
 }
 
 /// Returns the [TracingInspectorConfig] depending on the enabled [TraceType]s
@@ -600,6 +673,145 @@ fn reward_trace(header: &SealedHeader, reward: RewardAction) -> LocalizedTransac
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+        EthApi, TracingCallPool,
+    };
+    use assert_matches::assert_matches;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, sign_message, Address, Block, Header, Transaction,
+        TransactionKind, TransactionSigned, TxLegacy,
+    };
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestTraceApi = TraceApi<MockEthProvider, EthApi<MockEthProvider, TestPool, NoopNetwork>>;
+
+    /// Returns a provider with a genesis block and `num_blocks` blocks on top of it that each
+    /// contain a single call to `Address::repeat_byte(number)`.
+    fn trace_filter_provider(num_blocks: u64) -> MockEthProvider {
+        let provider = MockEthProvider::default();
+        let mut parent_hash = H256::zero();
+        for number in 0..=num_blocks {
+            let mut body = Vec::new();
+            if number > 0 {
+                let tx = Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: TransactionKind::Call(Address::repeat_byte(number as u8)),
+                    ..Default::default()
+                });
+                let signature =
+                    sign_message(H256::from_low_u64_be(0x1337), tx.signature_hash()).unwrap();
+                body.push(TransactionSigned::from_transaction_and_signature(tx, signature));
+            }
+            let header = Header {
+                number,
+                parent_hash,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                ..Default::default()
+            };
+            parent_hash = header.hash_slow();
+            provider.add_block(parent_hash, Block { header, body, ..Default::default() });
+        }
+        provider
+    }
+
+    fn build_test_trace_api(provider: MockEthProvider, max_blocks: u64) -> TestTraceApi {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            TracingCallPool::build().expect("failed to build tracing pool"),
+        );
+        TraceApi::new(
+            provider,
+            eth_api,
+            TracingCallGuard::new(10),
+            TraceFilterLimits { max_blocks, ..Default::default() },
+        )
+    }
+
+    /// Returns the recipients of the calls of the filtered traces.
+    async fn filter_call_targets(api: &TestTraceApi, filter: TraceFilter) -> Vec<Address> {
+        let traces = api.trace_filter(filter).await.unwrap();
+        traces
+            .into_iter()
+            .filter_map(|trace| match trace.trace.action {
+                Action::Call(call) => Some(call.to),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn trace_filter_count_zero() {
+        let api = build_test_trace_api(trace_filter_provider(3), 10);
+        let filter = TraceFilter {
+            from_block: Some(1),
+            to_block: Some(3),
+            count: Some(0),
+            ..Default::default()
+        };
+        assert!(api.trace_filter(filter).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn trace_filter_after_and_count() {
+        let api = build_test_trace_api(trace_filter_provider(3), 10);
+        let filter = TraceFilter {
+            from_block: Some(1),
+            to_block: Some(3),
+            to_address: Some((1..=3).map(Address::repeat_byte).collect()),
+            after: Some(1),
+            count: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(filter_call_targets(&api, filter).await, vec![Address::repeat_byte(2)]);
+    }
+
+    #[tokio::test]
+    async fn trace_filter_traces_small_ranges_in_full() {
+        // the calls don't show up in the history index
+        let api = build_test_trace_api(trace_filter_provider(3), 10);
+        let target = Address::repeat_byte(2);
+        let filter = TraceFilter {
+            from_block: Some(1),
+            to_block: Some(3),
+            to_address: Some(vec![target]),
+            ..Default::default()
+        };
+        assert_eq!(filter_call_targets(&api, filter).await, vec![target]);
+    }
+
+    #[tokio::test]
+    async fn trace_filter_prefilters_large_ranges() {
+        let provider = trace_filter_provider(5);
+        let target = Address::repeat_byte(4);
+        provider.add_account_history(target, [4]);
+        let api = build_test_trace_api(provider.clone(), 2);
+
+        let filter = TraceFilter {
+            from_block: Some(1),
+            to_block: Some(5),
+            to_address: Some(vec![target]),
+            ..Default::default()
+        };
+        assert_eq!(filter_call_targets(&api, filter.clone()).await, vec![target]);
+
+        // large ranges can't be traced in full
+        let unfiltered = TraceFilter { to_address: None, ..filter.clone() };
+        assert_matches!(api.trace_filter(unfiltered).await, Err(EthApiError::InvalidParams(_)));
+
+        // the prefiltered blocks exceed the limit
+        provider.add_account_history(target, [1, 2]);
+        assert_matches!(api.trace_filter(filter).await, Err(EthApiError::InvalidParams(_)));
+    }
 
     #[test]
     fn test_parity_config() {
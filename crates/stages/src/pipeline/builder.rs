@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageSet};
 use reth_db::database::Database;
 use reth_primitives::{stage::StageId, BlockNumber, ChainSpec, H256};
+use reth_provider::StaticFileProvider;
 use tokio::sync::watch;
 
 /// Builds a [`Pipeline`].
@@ -18,6 +19,8 @@ where
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<H256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// Static files of the frozen headers, transactions and receipts.
+    static_files: Option<StaticFileProvider>,
 }
 
 impl<DB> PipelineBuilder<DB>
@@ -67,14 +70,21 @@ where
         self
     }
 
+    /// Set the static files the stages read the frozen headers, transactions and receipts from.
+    pub fn with_static_files(mut self, static_files: StaticFileProvider) -> Self {
+        self.static_files = Some(static_files);
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     ///
     /// Note: it's expected that this is either an [Arc](std::sync::Arc) or an Arc wrapper type.
     pub fn build(self, db: DB, chain_spec: Arc<ChainSpec>) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, static_files } = self;
         Pipeline {
             db,
             chain_spec,
+            static_files,
             stages,
             max_block,
             tip_tx,
@@ -87,7 +97,13 @@ where
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            max_block: None,
+            tip_tx: None,
+            metrics_tx: None,
+            static_files: None,
+        }
     }
 }
 
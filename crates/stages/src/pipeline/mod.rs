@@ -9,7 +9,9 @@ use reth_primitives::{
     constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH, listener::EventListeners, stage::StageId,
     BlockNumber, ChainSpec, H256,
 };
-use reth_provider::{ProviderFactory, StageCheckpointReader, StageCheckpointWriter};
+use reth_provider::{
+    ProviderFactory, StageCheckpointReader, StageCheckpointWriter, StaticFileProvider,
+};
 use std::{pin::Pin, sync::Arc};
 use tokio::sync::watch;
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -96,6 +98,8 @@ pub struct Pipeline<DB: Database> {
     db: DB,
     /// Chain spec
     chain_spec: Arc<ChainSpec>,
+    /// Static files of the frozen headers, transactions and receipts.
+    static_files: Option<StaticFileProvider>,
     /// All configured stages in the order they will be executed.
     stages: Vec<BoxedStage<DB>>,
     /// The maximum block number to sync to.
@@ -140,7 +144,7 @@ where
     /// Registers progress metrics for each registered stage
     pub fn register_metrics(&mut self) -> Result<(), PipelineError> {
         let Some(metrics_tx) = &mut self.metrics_tx else { return Ok(()) };
-        let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
+        let factory = provider_factory(&self.db, &self.chain_spec, &self.static_files);
         let provider = factory.provider()?;
 
         for stage in &self.stages {
@@ -238,7 +242,7 @@ where
                 }
             }
 
-            let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
+            let factory = provider_factory(&self.db, &self.chain_spec, &self.static_files);
 
             previous_stage = Some(
                 factory
@@ -263,9 +267,12 @@ where
         // Unwind stages in reverse order of execution
         let unwind_pipeline = self.stages.iter_mut().rev();
 
-        let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
+        let factory = provider_factory(&self.db, &self.chain_spec, &self.static_files);
         let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
+        // frozen blocks can't be removed from the static files
+        provider_rw.ensure_unwind_above_static_files(to)?;
+
         for stage in unwind_pipeline {
             let stage_id = stage.id();
             let span = info_span!("Unwinding", stage = %stage_id);
@@ -335,7 +342,7 @@ where
         let mut made_progress = false;
         let target = self.max_block.or(previous_stage);
 
-        let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
+        let factory = provider_factory(&self.db, &self.chain_spec, &self.static_files);
         let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
 
         loop {
@@ -494,6 +501,22 @@ where
     }
 }
 
+/// Creates a provider factory over the database that reads the frozen data from the static files,
+/// if there are any.
+///
+/// This is a free function so it can be used while the stages of the pipeline are borrowed.
+fn provider_factory<'a, DB: Database>(
+    db: &'a DB,
+    chain_spec: &Arc<ChainSpec>,
+    static_files: &Option<StaticFileProvider>,
+) -> ProviderFactory<&'a DB> {
+    let factory = ProviderFactory::new(db, chain_spec.clone());
+    match static_files {
+        Some(static_files) => factory.with_static_files(static_files.clone()),
+        None => factory,
+    }
+}
+
 impl<DB: Database> std::fmt::Debug for Pipeline<DB> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Pipeline")
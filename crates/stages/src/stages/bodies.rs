@@ -6,14 +6,13 @@ use reth_db::{
     models::{StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals},
     tables,
     transaction::{DbTx, DbTxMut},
-    DatabaseError,
 };
 use reth_interfaces::{
     consensus::Consensus,
     p2p::bodies::{downloader::BodyDownloader, response::BlockResponse},
 };
 use reth_primitives::stage::{EntitiesCheckpoint, StageCheckpoint, StageId};
-use reth_provider::DatabaseProviderRW;
+use reth_provider::{DatabaseProviderRW, StaticFileSegment};
 use std::sync::Arc;
 use tracing::*;
 
@@ -87,8 +86,16 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
         let mut ommers_cursor = tx.cursor_write::<tables::BlockOmmers>()?;
         let mut withdrawals_cursor = tx.cursor_write::<tables::BlockWithdrawals>()?;
 
-        // Get id for the next tx_num of zero if there are no transactions.
-        let mut next_tx_num = tx_cursor.last()?.map(|(id, _)| id + 1).unwrap_or_default();
+        // Get id for the next tx_num of zero if there are no transactions. The transactions of
+        // finalized blocks may have been moved to static files, so the id is derived from the last
+        // block body instead of the last transaction.
+        let mut next_tx_num = match block_indices_cursor.last()? {
+            Some((_, block_indices)) => block_indices.next_tx_num(),
+            None => provider
+                .static_files()
+                .and_then(|static_files| static_files.highest_key(StaticFileSegment::Transactions))
+                .map_or(0, |tx_num| tx_num + 1),
+        };
 
         debug!(target: "sync::stages::bodies", stage_progress = from_block, target = to_block, start_tx_id = next_tx_num, "Commencing sync");
 
@@ -222,10 +229,10 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
 //  progress in gas as a proxy to size. Execution stage uses a similar approach.
 fn stage_checkpoint<DB: Database>(
     provider: &DatabaseProviderRW<'_, DB>,
-) -> Result<EntitiesCheckpoint, DatabaseError> {
+) -> Result<EntitiesCheckpoint, StageError> {
     Ok(EntitiesCheckpoint {
         processed: provider.tx_ref().entries::<tables::BlockBodyIndices>()? as u64,
-        total: provider.tx_ref().entries::<tables::Headers>()? as u64,
+        total: provider.static_or_db_entries::<tables::Headers>(StaticFileSegment::Headers)? as u64,
     })
 }
 
@@ -376,6 +383,52 @@ mod tests {
         );
     }
 
+    /// Checks that transaction numbers continue after the last block body, even if the
+    /// transactions were moved out of the database.
+    #[tokio::test]
+    async fn sync_after_transactions_moved() {
+        let (stage_progress, previous_stage) = (1, 21);
+
+        // Set up test runner
+        let mut runner = BodyTestRunner::default();
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        runner.seed_execution(input).expect("failed to seed execution");
+        runner.set_batch_size(10);
+
+        let first_run = runner.execute(input).await.unwrap();
+        assert_matches!(first_run, Ok(ExecOutput { done: false, .. }));
+        let first_run_checkpoint = first_run.unwrap().checkpoint;
+
+        // Remove all transactions, as if they were moved to static files
+        runner
+            .tx()
+            .commit(|tx| tx.clear::<tables::Transactions>())
+            .expect("Could not delete transactions");
+
+        let input =
+            ExecInput { target: Some(previous_stage), checkpoint: Some(first_run_checkpoint) };
+        let output = runner.execute(input).await.unwrap();
+        assert_matches!(output, Ok(ExecOutput { done: true, .. }));
+
+        // Check that the transaction numbers of all blocks are contiguous
+        let bodies = runner
+            .tx()
+            .query(|tx| {
+                tx.cursor_read::<tables::BlockBodyIndices>()?
+                    .walk(None)?
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .expect("Could not read block bodies");
+        assert!(bodies.len() > 1);
+        for window in bodies.windows(2) {
+            let ((_, prev), (number, body)) = (&window[0], &window[1]);
+            assert_eq!(body.first_tx_num, prev.next_tx_num(), "Block {number} tx numbers");
+        }
+    }
+
     /// Checks that the stage unwinds correctly, even if a transaction in a block is missing.
     #[tokio::test]
     async fn unwind_missing_tx() {
@@ -10,7 +10,6 @@ use reth_db::{
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_interfaces::db::DatabaseError;
 use reth_primitives::{
     stage::{
         CheckpointBlockRange, EntitiesCheckpoint, ExecutionCheckpoint, StageCheckpoint, StageId,
@@ -19,7 +18,7 @@ use reth_primitives::{
 };
 use reth_provider::{
     post_state::PostState, BlockExecutor, BlockReader, DatabaseProviderRW, ExecutorFactory,
-    HeaderProvider, LatestStateProviderRef, ProviderError,
+    HeaderProvider, LatestStateProviderRef, ProviderError, StaticFileSegment,
 };
 use std::{ops::RangeInclusive, time::Instant};
 use tracing::*;
@@ -218,7 +217,7 @@ fn execution_checkpoint<DB: Database>(
     start_block: BlockNumber,
     max_block: BlockNumber,
     checkpoint: StageCheckpoint,
-) -> Result<ExecutionCheckpoint, DatabaseError> {
+) -> Result<ExecutionCheckpoint, StageError> {
     Ok(match checkpoint.execution_stage_checkpoint() {
         // If checkpoint block range fully matches our range,
         // we take the previously used stage checkpoint as-is.
@@ -283,14 +282,13 @@ fn execution_checkpoint<DB: Database>(
 fn calculate_gas_used_from_headers<DB: Database>(
     provider: &DatabaseProviderRW<'_, &DB>,
     range: RangeInclusive<BlockNumber>,
-) -> Result<u64, DatabaseError> {
-    let mut gas_total = 0;
-
+) -> Result<u64, StageError> {
     let start = Instant::now();
-    for entry in provider.tx_ref().cursor_read::<tables::Headers>()?.walk_range(range.clone())? {
-        let (_, Header { gas_used, .. }) = entry?;
-        gas_total += gas_used;
-    }
+    let gas_total = provider
+        .walk_static_or_db::<tables::Headers>(StaticFileSegment::Headers, range.clone())?
+        .into_iter()
+        .map(|(_, Header { gas_used, .. })| gas_used)
+        .sum();
 
     let duration = start.elapsed();
     trace!(target: "sync::stages::execution", ?range, ?duration, "Time elapsed in calculate_gas_used_from_headers");
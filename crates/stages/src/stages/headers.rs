@@ -19,7 +19,7 @@ use reth_primitives::{
     },
     BlockHashOrNumber, BlockNumber, SealedHeader, H256,
 };
-use reth_provider::DatabaseProviderRW;
+use reth_provider::{DatabaseProviderRW, StaticFileSegment};
 use tokio::sync::watch;
 use tracing::*;
 
@@ -89,7 +89,6 @@ where
     ) -> Result<SyncGap, StageError> {
         // Create a cursor over canonical header hashes
         let mut cursor = provider.tx_ref().cursor_read::<tables::CanonicalHeaders>()?;
-        let mut header_cursor = provider.tx_ref().cursor_read::<tables::Headers>()?;
 
         // Get head hash and reposition the cursor
         let (head_num, head_hash) = cursor
@@ -97,8 +96,8 @@ where
             .ok_or_else(|| ProviderError::HeaderNotFound(checkpoint.into()))?;
 
         // Construct head
-        let (_, head) = header_cursor
-            .seek_exact(head_num)?
+        let head = provider
+            .get_static_or_db::<tables::Headers>(StaticFileSegment::Headers, head_num)?
             .ok_or_else(|| ProviderError::HeaderNotFound(head_num.into()))?;
         let local_head = head.seal(head_hash);
 
@@ -106,8 +105,8 @@ where
         let next_header = cursor
             .next()?
             .map(|(next_num, next_hash)| -> Result<SealedHeader, StageError> {
-                let (_, next) = header_cursor
-                    .seek_exact(next_num)?
+                let next = provider
+                    .get_static_or_db::<tables::Headers>(StaticFileSegment::Headers, next_num)?
                     .ok_or_else(|| ProviderError::HeaderNotFound(next_num.into()))?;
                 Ok(next.seal(next_hash))
             })
@@ -0,0 +1,192 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_db::database::Database;
+use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_provider::{DatabaseProviderRW, HistoryWriter};
+use std::fmt::Debug;
+
+/// Stage is indexing the addresses and topics of the logs in the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. The index is used to speed up log queries
+/// over large block ranges.
+///
+/// This stage is optional and not part of the default stage sets. For more information on index
+/// sharding take a look at [`reth_db::tables::LogAddressHistory`].
+#[derive(Debug)]
+pub struct IndexLogHistoryStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexLogHistoryStage {
+    /// Create new instance of [IndexLogHistoryStage].
+    pub fn new(commit_threshold: u64) -> Self {
+        Self { commit_threshold }
+    }
+}
+
+impl Default for IndexLogHistoryStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+#[async_trait::async_trait]
+impl<DB: Database> Stage<DB> for IndexLogHistoryStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogHistory
+    }
+
+    /// Execute the stage.
+    async fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<'_, &DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        provider.calculate_log_history_indices(range.clone())?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    async fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<'_, &DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_history_indices(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestTransaction;
+    use reth_db::{models::ShardedKey, tables, BlockNumberList};
+    use reth_interfaces::test_utils::{
+        generators,
+        generators::{random_block_range, random_log},
+    };
+    use reth_primitives::{hex_literal::hex, Address, Receipt, TxType, H160, H256, MAINNET};
+    use reth_provider::{HistoryReader, ProviderFactory, StageCheckpointWriter};
+    use std::collections::BTreeMap;
+
+    const ADDRESS: H160 = H160(hex!("0000000000000000000000000000000000000001"));
+    const TOPIC: H256 =
+        H256(hex!("0000000000000000000000000000000000000000000000000000000000000002"));
+
+    fn cast<K: Ord>(
+        table: Vec<(ShardedKey<K>, BlockNumberList)>,
+    ) -> BTreeMap<(K, u64), Vec<usize>> {
+        table
+            .into_iter()
+            .map(|(k, v)| ((k.key, k.highest_block_number), v.iter(0).collect()))
+            .collect()
+    }
+
+    /// Inserts blocks `0..=10` where every odd block has a log of [ADDRESS] with [TOPIC] in the
+    /// receipt of its first transaction.
+    fn setup(tx: &TestTransaction) {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=10, H256::zero(), 1..3);
+        tx.insert_blocks(blocks.iter(), None).unwrap();
+
+        let mut tx_num = 0;
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for (idx, _) in block.body.iter().enumerate() {
+                let mut logs = Vec::new();
+                if idx == 0 && block.number % 2 == 1 {
+                    let mut log = random_log(&mut rng, Some(ADDRESS), Some(0));
+                    log.topics = vec![TOPIC, TOPIC];
+                    logs.push(log);
+                }
+                receipts.push((
+                    tx_num,
+                    Receipt {
+                        tx_type: TxType::EIP1559,
+                        success: true,
+                        cumulative_gas_used: 0,
+                        logs,
+                    },
+                ));
+                tx_num += 1;
+            }
+        }
+        tx.insert_receipts(receipts).unwrap();
+    }
+
+    async fn run(tx: &TestTransaction, run_to: u64) {
+        let input = ExecInput { target: Some(run_to), ..Default::default() };
+        let mut stage = IndexLogHistoryStage::default();
+        let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let out = stage.execute(&provider, input).await.unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.save_stage_checkpoint(StageId::IndexLogHistory, out.checkpoint).unwrap();
+        provider.commit().unwrap();
+    }
+
+    async fn unwind(tx: &TestTransaction, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexLogHistoryStage::default();
+        let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).await.unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.save_stage_checkpoint(StageId::IndexLogHistory, out.checkpoint).unwrap();
+        provider.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn index_and_unwind_logs() {
+        let tx = TestTransaction::default();
+        setup(&tx);
+
+        // the index is not available before the stage ran
+        assert_eq!(tx.inner().log_index_range().unwrap(), None);
+
+        run(&tx, 10).await;
+
+        let table = cast::<Address>(tx.table::<tables::LogAddressHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([((ADDRESS, u64::MAX), vec![1, 3, 5, 7, 9])]));
+        // the topic is indexed once per block, even if it appears multiple times
+        let table = cast::<H256>(tx.table::<tables::LogTopicHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([((TOPIC, u64::MAX), vec![1, 3, 5, 7, 9])]));
+
+        let provider = tx.inner();
+        assert_eq!(provider.log_index_range().unwrap(), Some(0..=10));
+        assert_eq!(provider.log_address_history_blocks(ADDRESS, 2..=7).unwrap(), vec![3, 5, 7]);
+        assert_eq!(provider.log_topic_history_blocks(TOPIC, 8..=10).unwrap(), vec![9]);
+        assert!(provider.log_topic_history_blocks(H256::zero(), 0..=10).unwrap().is_empty());
+        drop(provider);
+
+        unwind(&tx, 10, 4).await;
+
+        let table = cast::<Address>(tx.table::<tables::LogAddressHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([((ADDRESS, u64::MAX), vec![1, 3])]));
+        let table = cast::<H256>(tx.table::<tables::LogTopicHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([((TOPIC, u64::MAX), vec![1, 3])]));
+        assert_eq!(tx.inner().log_index_range().unwrap(), Some(0..=4));
+
+        unwind(&tx, 4, 0).await;
+
+        assert!(tx.table_is_empty::<tables::LogAddressHistory>().unwrap());
+        assert!(tx.table_is_empty::<tables::LogTopicHistory>().unwrap());
+    }
+}
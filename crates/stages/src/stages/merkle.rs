@@ -15,7 +15,7 @@ use reth_primitives::{
 use reth_provider::{
     DatabaseProviderRW, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
 };
-use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress};
+use reth_trie::{IntermediateStateRootState, ParallelStateRoot, StateRoot, StateRootProgress};
 use std::fmt::Debug;
 use tracing::*;
 
@@ -51,6 +51,9 @@ pub enum MerkleStage {
         /// The threshold (in number of blocks) for switching from incremental trie building
         /// of changes to whole rebuild.
         clean_threshold: u64,
+        /// Whether the storage roots of changed accounts are computed in parallel when the trie
+        /// is updated incrementally, see [ParallelStateRoot].
+        parallel_storage_roots: bool,
     },
     /// The unwind portion of the merkle stage.
     Unwind,
@@ -58,13 +61,13 @@ pub enum MerkleStage {
     /// Able to execute and unwind. Used for tests
     #[cfg(any(test, feature = "test-utils"))]
     #[allow(missing_docs)]
-    Both { clean_threshold: u64 },
+    Both { clean_threshold: u64, parallel_storage_roots: bool },
 }
 
 impl MerkleStage {
     /// Stage default for the [MerkleStage::Execution].
     pub fn default_execution() -> Self {
-        Self::Execution { clean_threshold: MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD }
+        Self::new_execution(MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD)
     }
 
     /// Stage default for the [MerkleStage::Unwind].
@@ -74,7 +77,25 @@ impl MerkleStage {
 
     /// Create new instance of [MerkleStage::Execution].
     pub fn new_execution(clean_threshold: u64) -> Self {
-        Self::Execution { clean_threshold }
+        Self::Execution { clean_threshold, parallel_storage_roots: false }
+    }
+
+    /// Configures whether the storage roots of changed accounts are computed in parallel when the
+    /// trie is updated incrementally. Has no effect on [MerkleStage::Unwind].
+    ///
+    /// The storage roots are computed with read-only transactions, which only observe the
+    /// committed state. This is the case in the pipeline, where the hashing stages commit their
+    /// changes before the merkle stage is executed.
+    pub fn with_parallel_storage_roots(mut self, enabled: bool) -> Self {
+        match &mut self {
+            MerkleStage::Execution { parallel_storage_roots, .. } => {
+                *parallel_storage_roots = enabled
+            }
+            MerkleStage::Unwind => {}
+            #[cfg(any(test, feature = "test-utils"))]
+            MerkleStage::Both { parallel_storage_roots, .. } => *parallel_storage_roots = enabled,
+        }
+        self
     }
 
     /// Check that the computed state root matches the root in the expected header.
@@ -152,14 +173,18 @@ impl<DB: Database> Stage<DB> for MerkleStage {
         provider: &DatabaseProviderRW<'_, &DB>,
         input: ExecInput,
     ) -> Result<ExecOutput, StageError> {
-        let threshold = match self {
+        let (threshold, parallel_storage_roots) = match self {
             MerkleStage::Unwind => {
                 info!(target: "sync::stages::merkle::unwind", "Stage is always skipped");
                 return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
             }
-            MerkleStage::Execution { clean_threshold } => *clean_threshold,
+            MerkleStage::Execution { clean_threshold, parallel_storage_roots } => {
+                (*clean_threshold, *parallel_storage_roots)
+            }
             #[cfg(any(test, feature = "test-utils"))]
-            MerkleStage::Both { clean_threshold } => *clean_threshold,
+            MerkleStage::Both { clean_threshold, parallel_storage_roots } => {
+                (*clean_threshold, *parallel_storage_roots)
+            }
         };
 
         let range = input.next_block_range();
@@ -248,10 +273,14 @@ impl<DB: Database> Stage<DB> for MerkleStage {
                 }
             }
         } else {
-            debug!(target: "sync::stages::merkle::exec", current = ?current_block_number, target = ?to_block, "Updating trie");
-            let (root, updates) =
+            debug!(target: "sync::stages::merkle::exec", current = ?current_block_number, target = ?to_block, parallel_storage_roots, "Updating trie");
+            let (root, updates) = if parallel_storage_roots {
+                ParallelStateRoot::incremental_root_calculator(provider.db(), range)
+                    .and_then(|calculator| calculator.root_with_updates())
+            } else {
                 StateRoot::incremental_root_with_updates(provider.tx_ref(), range)
-                    .map_err(|e| StageError::Fatal(Box::new(e)))?;
+            }
+            .map_err(|e| StageError::Fatal(Box::new(e)))?;
             updates.flush(provider.tx_ref())?;
 
             let total_hashed_entries = (provider.tx_ref().entries::<tables::HashedAccount>()? +
@@ -356,8 +385,9 @@ mod tests {
         },
     };
     use reth_primitives::{
-        keccak256, stage::StageUnitCheckpoint, SealedBlock, StorageEntry, H256, U256,
+        keccak256, stage::StageUnitCheckpoint, SealedBlock, StorageEntry, H256, MAINNET, U256,
     };
+    use reth_provider::ProviderFactory;
     use reth_trie::test_utils::{state_root, state_root_prehashed};
     use std::collections::BTreeMap;
 
@@ -444,6 +474,54 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    /// The incremental trie update yields the same result with parallel storage roots
+    #[tokio::test]
+    async fn execute_incremental_merkle_parallel_storage_roots() {
+        let (previous_stage, stage_progress) = (10, 1);
+
+        // Set up the runner
+        let mut runner = MerkleTestRunner::default();
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        let db = runner.tx.inner_raw();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let mut results = Vec::new();
+        for parallel_storage_roots in [false, true] {
+            let mut stage = MerkleStage::Both {
+                clean_threshold: runner.clean_threshold,
+                parallel_storage_roots,
+            };
+            // the provider is dropped without committing, so both runs start from the same state
+            let provider = factory.provider_rw().unwrap();
+            let output = stage.execute(&provider, input).await;
+            assert_matches!(output, Ok(ExecOutput { done: true, .. }));
+
+            let tx = provider.tx_ref();
+            let accounts_trie = tx
+                .cursor_read::<tables::AccountsTrie>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let storages_trie = tx
+                .cursor_dup_read::<tables::StoragesTrie>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            results.push((output.unwrap(), accounts_trie, storages_trie));
+        }
+
+        assert_eq!(results[0], results[1]);
+    }
+
     struct MerkleTestRunner {
         tx: TestTransaction,
         clean_threshold: u64,
@@ -12,12 +12,16 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index history of log addresses and topics
+mod index_log_history;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
 mod merkle;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The stage that downloads the state over the `snap` protocol.
+mod snap_state;
 /// The total difficulty stage
 mod total_difficulty;
 /// The transaction lookup stage
@@ -30,9 +34,11 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_log_history::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use sender_recovery::*;
+pub use snap_state::*;
 pub use total_difficulty::*;
 pub use tx_lookup::*;
 
@@ -1,11 +1,10 @@
 use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
 use itertools::Itertools;
 use reth_db::{
-    cursor::{DbCursorRO, DbCursorRW},
+    cursor::DbCursorRW,
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
-    DatabaseError, RawKey, RawTable, RawValue,
 };
 use reth_interfaces::consensus;
 use reth_primitives::{
@@ -15,6 +14,7 @@ use reth_primitives::{
 };
 use reth_provider::{
     BlockReader, DatabaseProviderRW, HeaderProvider, ProviderError, PruneCheckpointReader,
+    StaticFileSegment,
 };
 use std::fmt::Debug;
 use thiserror::Error;
@@ -84,11 +84,12 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
         // Acquire the cursor for inserting elements
         let mut senders_cursor = tx.cursor_write::<tables::TxSenders>()?;
 
-        // Acquire the cursor over the transactions
-        let mut tx_cursor = tx.cursor_read::<RawTable<tables::Transactions>>()?;
-        // Walk the transactions from start to end index (inclusive)
-        let raw_tx_range = RawKey::new(tx_range.start)..RawKey::new(tx_range.end);
-        let tx_walker = tx_cursor.walk_range(raw_tx_range)?;
+        // Walk the transactions from start to end index, reading the frozen part from the static
+        // files
+        let transactions = provider.walk_static_or_db::<tables::Transactions>(
+            StaticFileSegment::Transactions,
+            tx_range.clone(),
+        )?;
 
         // Iterate over transactions in chunks
         info!(target: "sync::stages::sender_recovery", ?tx_range, "Recovering senders");
@@ -108,7 +109,7 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
         // to gain anything from using more than 1 thread
         let chunk_size = chunk_size.max(16);
 
-        for chunk in &tx_walker.chunks(chunk_size) {
+        for chunk in &transactions.into_iter().chunks(chunk_size) {
             // An _unordered_ channel to receive results from a rayon job
             let (recovered_senders_tx, recovered_senders_rx) = mpsc::unbounded_channel();
             channels.push(recovered_senders_rx);
@@ -189,14 +190,9 @@ impl<DB: Database> Stage<DB> for SenderRecoveryStage {
 }
 
 fn recover_sender(
-    entry: Result<(RawKey<TxNumber>, RawValue<TransactionSignedNoHash>), DatabaseError>,
+    (tx_id, tx): (TxNumber, TransactionSignedNoHash),
     rlp_buf: &mut Vec<u8>,
 ) -> Result<(u64, H160), Box<SenderRecoveryStageError>> {
-    let (tx_id, transaction) =
-        entry.map_err(|e| Box::new(SenderRecoveryStageError::StageError(e.into())))?;
-    let tx_id = tx_id.key().expect("key to be formated");
-
-    let tx = transaction.value().expect("value to be formated");
     tx.transaction.encode_without_signature(rlp_buf);
 
     let sender = tx
@@ -223,7 +219,9 @@ fn stage_checkpoint<DB: Database>(
         // the actual number of processed transactions. To fix that, we add the number of pruned
         // `TxSenders` entries.
         processed: provider.tx_ref().entries::<tables::TxSenders>()? as u64 + pruned_entries,
-        total: provider.tx_ref().entries::<tables::Transactions>()? as u64,
+        total: provider
+            .static_or_db_entries::<tables::Transactions>(StaticFileSegment::Transactions)?
+            as u64,
     })
 }
 
@@ -262,6 +260,7 @@ mod tests {
         stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, TestRunnerError,
         TestTransaction, UnwindStageTestRunner,
     };
+    use reth_db::cursor::DbCursorRO;
 
     stage_test_suite_ext!(SenderRecoveryTestRunner, sender_recovery);
 
@@ -0,0 +1,272 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use futures_util::StreamExt;
+use reth_db::{cursor::DbCursorRW, database::Database, tables, transaction::DbTxMut};
+use reth_interfaces::{
+    consensus,
+    p2p::snap::downloader::{SnapStateChunk, StateDownloader},
+};
+use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_provider::{DatabaseProviderRW, HeaderProvider, ProviderError};
+use reth_trie::StateRoot;
+use tracing::*;
+
+/// The snap state stage downloads the state of the target block over the `snap` protocol.
+///
+/// The downloaded state is verified by rebuilding the state trie and comparing its root against
+/// the state root of the target header.
+///
+/// # Tables
+///
+/// The state of the previous target is discarded and the downloaded state is inserted into these
+/// tables:
+///
+/// - [`HashedAccount`][reth_db::tables::HashedAccount]
+/// - [`HashedStorage`][reth_db::tables::HashedStorage]
+/// - [`Bytecodes`][reth_db::tables::Bytecodes]
+/// - [`AccountsTrie`][reth_db::tables::AccountsTrie]
+/// - [`StoragesTrie`][reth_db::tables::StoragesTrie]
+///
+/// # Limitations
+///
+/// The `snap` protocol does not carry the preimages of the hashed addresses and storage keys, so
+/// the plain state is not filled and blocks can't be executed on top of the downloaded state. The
+/// stage therefore replaces the execution, hashing and merkle stages, and the state is downloaded
+/// from scratch whenever the target changes.
+///
+/// This stage is optional and not part of the default stage sets.
+#[derive(Debug)]
+pub struct SnapStateStage<D: StateDownloader> {
+    /// The state downloader.
+    pub downloader: D,
+}
+
+impl<D: StateDownloader> SnapStateStage<D> {
+    /// Create new instance of [SnapStateStage].
+    pub fn new(downloader: D) -> Self {
+        Self { downloader }
+    }
+}
+
+#[async_trait::async_trait]
+impl<DB: Database, D: StateDownloader> Stage<DB> for SnapStateStage<D> {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::SnapState
+    }
+
+    /// Download the state of the target block.
+    async fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<'_, &DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let target = input.target();
+        let target_header = provider
+            .sealed_header(target)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(target.into()))?;
+
+        let tx = provider.tx_ref();
+        tx.clear::<tables::HashedAccount>()?;
+        tx.clear::<tables::HashedStorage>()?;
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+
+        info!(
+            target: "sync::stages::snap_state",
+            target,
+            state_root = ?target_header.state_root,
+            "Downloading state"
+        );
+        self.downloader.set_state_root(target_header.state_root);
+
+        let mut account_cursor = tx.cursor_write::<tables::HashedAccount>()?;
+        let mut storage_cursor = tx.cursor_dup_write::<tables::HashedStorage>()?;
+        let mut bytecode_cursor = tx.cursor_write::<tables::Bytecodes>()?;
+        let mut accounts = 0;
+        while let Some(chunk) = self.downloader.next().await {
+            match chunk {
+                SnapStateChunk::Accounts(chunk) => {
+                    accounts += chunk.len();
+                    for (hashed_address, account) in chunk {
+                        account_cursor.upsert(hashed_address, account)?;
+                    }
+                    debug!(target: "sync::stages::snap_state", accounts, "Downloaded accounts");
+                }
+                SnapStateChunk::Storage { hashed_address, entries } => {
+                    for entry in entries.into_iter().filter(|entry| !entry.value.is_zero()) {
+                        storage_cursor.upsert(hashed_address, entry)?;
+                    }
+                }
+                SnapStateChunk::ByteCodes(codes) => {
+                    for (hash, bytecode) in codes {
+                        bytecode_cursor.upsert(hash, bytecode)?;
+                    }
+                }
+            }
+        }
+
+        let (state_root, updates) =
+            StateRoot::new(tx).root_with_updates().map_err(|e| StageError::Fatal(Box::new(e)))?;
+        if state_root != target_header.state_root {
+            warn!(
+                target: "sync::stages::snap_state",
+                target,
+                got = ?state_root,
+                expected = ?target_header.state_root,
+                "Failed to verify downloaded state"
+            );
+            return Err(StageError::Validation {
+                block: target_header.clone(),
+                error: consensus::ConsensusError::BodyStateRootDiff {
+                    got: state_root,
+                    expected: target_header.state_root,
+                },
+            })
+        }
+        updates.flush(tx)?;
+
+        info!(target: "sync::stages::snap_state", target, accounts, "Downloaded state");
+        Ok(ExecOutput::done(StageCheckpoint::new(target)))
+    }
+
+    /// Unwind the stage.
+    ///
+    /// The state is not unwound, it is replaced once the stage runs again.
+    async fn unwind(
+        &mut self,
+        _provider: &DatabaseProviderRW<'_, &DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestTransaction;
+    use futures_util::stream::{self, Stream};
+    use reth_interfaces::test_utils::generators::{self, random_header};
+    use reth_primitives::{
+        keccak256, Account, Bytecode, SealedHeader, StorageEntry, H256, MAINNET, U256,
+    };
+    use reth_provider::ProviderFactory;
+    use reth_trie::test_utils::state_root_prehashed;
+    use std::{
+        collections::BTreeMap,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    /// Yields the chunks of a single state.
+    struct TestStateDownloader {
+        chunks: Vec<SnapStateChunk>,
+        stream: stream::Iter<std::vec::IntoIter<SnapStateChunk>>,
+    }
+
+    impl TestStateDownloader {
+        fn new(chunks: Vec<SnapStateChunk>) -> Self {
+            Self { chunks, stream: stream::iter(Vec::new()) }
+        }
+    }
+
+    impl StateDownloader for TestStateDownloader {
+        fn set_state_root(&mut self, _state_root: H256) {
+            self.stream = stream::iter(self.chunks.clone());
+        }
+    }
+
+    impl Stream for TestStateDownloader {
+        type Item = SnapStateChunk;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.stream.poll_next_unpin(cx)
+        }
+    }
+
+    /// Returns the chunks of a state with a contract and an externally owned account, and a
+    /// header at block 10 with its state root.
+    fn test_state(valid: bool) -> (Vec<SnapStateChunk>, SealedHeader) {
+        let raw = vec![0x60, 0x00];
+        let code_hash = keccak256(&raw);
+        let code = Bytecode::new_raw_with_hash(raw.into(), code_hash);
+        let contract = (
+            keccak256(H256::from_low_u64_be(1)),
+            Account { nonce: 1, balance: U256::from(1), bytecode_hash: Some(code_hash) },
+        );
+        let eoa = (
+            keccak256(H256::from_low_u64_be(2)),
+            Account { nonce: 2, balance: U256::from(2), bytecode_hash: None },
+        );
+        let slot = StorageEntry { key: keccak256(H256::zero()), value: U256::from(3) };
+
+        let mut accounts = BTreeMap::from([
+            (contract.0, (contract.1, vec![(slot.key, slot.value)])),
+            (eoa.0, (eoa.1, vec![])),
+        ]);
+        if !valid {
+            accounts.get_mut(&eoa.0).unwrap().0.balance = U256::from(3);
+        }
+        let state_root = state_root_prehashed(accounts.clone().into_iter());
+
+        let mut header = random_header(&mut generators::rng(), 10, None).unseal();
+        header.state_root = state_root;
+
+        let mut sorted = vec![contract, eoa];
+        sorted.sort_by_key(|(hashed_address, _)| *hashed_address);
+        let chunks = vec![
+            SnapStateChunk::Accounts(sorted),
+            SnapStateChunk::Storage { hashed_address: contract.0, entries: vec![slot] },
+            SnapStateChunk::ByteCodes(vec![(code_hash, code)]),
+        ];
+        (chunks, header.seal_slow())
+    }
+
+    #[tokio::test]
+    async fn download_state() {
+        let tx = TestTransaction::default();
+        let (chunks, header) = test_state(true);
+        tx.insert_headers(std::iter::once(&header)).unwrap();
+        // leftovers of a previous target are discarded
+        tx.commit(|tx| tx.put::<tables::HashedAccount>(H256::random(), Account::default()))
+            .unwrap();
+
+        let mut stage = SnapStateStage::new(TestStateDownloader::new(chunks));
+        let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), checkpoint: None };
+        let out = stage.execute(&provider, input).await.unwrap();
+        assert_eq!(out, ExecOutput::done(StageCheckpoint::new(10)));
+        provider.commit().unwrap();
+
+        assert_eq!(tx.table::<tables::HashedAccount>().unwrap().len(), 2);
+        assert_eq!(tx.table::<tables::HashedStorage>().unwrap().len(), 1);
+        assert_eq!(tx.table::<tables::Bytecodes>().unwrap().len(), 1);
+        assert!(!tx.table_is_empty::<tables::AccountsTrie>().unwrap());
+        assert_eq!(
+            tx.query(|tx| Ok(StateRoot::new(tx).root().unwrap())).unwrap(),
+            header.state_root
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_state_with_wrong_root() {
+        let tx = TestTransaction::default();
+        let (chunks, _) = test_state(true);
+        let (_, header) = test_state(false);
+        tx.insert_headers(std::iter::once(&header)).unwrap();
+
+        let mut stage = SnapStateStage::new(TestStateDownloader::new(chunks));
+        let factory = ProviderFactory::new(tx.tx.as_ref(), MAINNET.clone());
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), checkpoint: None };
+        assert_matches::assert_matches!(
+            stage.execute(&provider, input).await,
+            Err(StageError::Validation { .. })
+        );
+    }
+}
@@ -4,14 +4,13 @@ use reth_db::{
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
-    DatabaseError,
 };
 use reth_interfaces::{consensus::Consensus, provider::ProviderError};
 use reth_primitives::{
     stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
     U256,
 };
-use reth_provider::DatabaseProviderRW;
+use reth_provider::{DatabaseProviderRW, StaticFileSegment};
 use std::sync::Arc;
 use tracing::*;
 
@@ -64,9 +63,8 @@ impl<DB: Database> Stage<DB> for TotalDifficultyStage {
 
         debug!(target: "sync::stages::total_difficulty", start_block, end_block, "Commencing sync");
 
-        // Acquire cursor over total difficulty and headers tables
+        // Acquire cursor over total difficulty table
         let mut cursor_td = tx.cursor_write::<tables::HeaderTD>()?;
-        let mut cursor_headers = tx.cursor_read::<tables::Headers>()?;
 
         // Get latest total difficulty
         let last_header_number = input.checkpoint().block_number;
@@ -78,8 +76,9 @@ impl<DB: Database> Stage<DB> for TotalDifficultyStage {
         debug!(target: "sync::stages::total_difficulty", ?td, block_number = last_header_number, "Last total difficulty entry");
 
         // Walk over newly inserted headers, update & insert td
-        for entry in cursor_headers.walk_range(range)? {
-            let (block_number, header) = entry?;
+        for (block_number, header) in
+            provider.walk_static_or_db::<tables::Headers>(StaticFileSegment::Headers, range)?
+        {
             td += header.difficulty;
 
             self.consensus
@@ -114,10 +113,10 @@ impl<DB: Database> Stage<DB> for TotalDifficultyStage {
 
 fn stage_checkpoint<DB: Database>(
     provider: &DatabaseProviderRW<'_, DB>,
-) -> Result<EntitiesCheckpoint, DatabaseError> {
+) -> Result<EntitiesCheckpoint, StageError> {
     Ok(EntitiesCheckpoint {
         processed: provider.tx_ref().entries::<tables::HeaderTD>()? as u64,
-        total: provider.tx_ref().entries::<tables::Headers>()? as u64,
+        total: provider.static_or_db_entries::<tables::Headers>(StaticFileSegment::Headers)? as u64,
     })
 }
 
@@ -130,8 +129,11 @@ mod tests {
         generators::{random_header, random_header_range},
         TestConsensus,
     };
-    use reth_primitives::{stage::StageUnitCheckpoint, BlockNumber, SealedHeader};
-    use reth_provider::HeaderProvider;
+    use reth_primitives::{stage::StageUnitCheckpoint, BlockNumber, SealedHeader, H256, MAINNET};
+    use reth_provider::{
+        HeaderProvider, ProviderFactory, StageCheckpointWriter, StaticFileProducer,
+        StaticFileProvider,
+    };
 
     use super::*;
     use crate::test_utils::{
@@ -193,6 +195,61 @@ mod tests {
         assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
     }
 
+    #[tokio::test]
+    async fn execute_over_static_files() {
+        let tx = TestTransaction::default();
+        let mut rng = generators::rng();
+        let headers = random_header_range(&mut rng, 0..10, H256::zero());
+        tx.insert_headers(headers.iter()).expect("failed to insert headers");
+        tx.commit(|tx| tx.put::<tables::HeaderTD>(0, headers[0].difficulty.into()))
+            .expect("failed to insert genesis total difficulty");
+
+        let provider = tx.factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(9)).unwrap();
+        provider.commit().unwrap();
+
+        // Move all headers out of the database
+        let static_files_dir = tempfile::tempdir().unwrap();
+        let static_files = StaticFileProvider::new(static_files_dir.path()).unwrap();
+        let db = tx.inner_raw();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone())
+            .with_static_files(static_files.clone());
+        assert_matches!(StaticFileProducer::new(factory.clone(), static_files, 5).run(9), Ok(2));
+        assert!(tx.table_is_empty::<tables::Headers>().unwrap());
+
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(9), checkpoint: Some(StageCheckpoint::new(0)) };
+        let mut stage = TotalDifficultyStage::new(Arc::new(TestConsensus::default()));
+        let result = stage.execute(&provider, input).await;
+        assert_matches!(
+            result,
+            Ok(ExecOutput {
+                checkpoint: StageCheckpoint {
+                    block_number: 9,
+                    stage_checkpoint: Some(StageUnitCheckpoint::Entities(EntitiesCheckpoint {
+                        processed: 10,
+                        total: 10
+                    }))
+                },
+                done: true
+            })
+        );
+        provider.commit().unwrap();
+
+        let expected = headers
+            .iter()
+            .scan(U256::ZERO, |td, header| {
+                *td += header.difficulty;
+                Some((header.number, *td))
+            })
+            .collect::<Vec<_>>();
+        let actual = tx.table::<tables::HeaderTD>().unwrap();
+        assert_eq!(
+            actual.into_iter().map(|(number, td)| (number, td.0)).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
     struct TotalDifficultyTestRunner {
         tx: TestTransaction,
         consensus: Arc<TestConsensus>,
@@ -6,14 +6,13 @@ use reth_db::{
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
-    DatabaseError,
 };
 use reth_primitives::{
     keccak256,
     stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
     PrunePart, TransactionSignedNoHash, TxNumber, H256,
 };
-use reth_provider::{BlockReader, DatabaseProviderRW, PruneCheckpointReader};
+use reth_provider::{BlockReader, DatabaseProviderRW, PruneCheckpointReader, StaticFileSegment};
 use tokio::sync::mpsc;
 use tracing::*;
 
@@ -65,14 +64,14 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
         debug!(target: "sync::stages::transaction_lookup", ?tx_range, "Updating transaction lookup");
 
         let tx = provider.tx_ref();
-        let mut tx_cursor = tx.cursor_read::<tables::Transactions>()?;
-        let tx_walker = tx_cursor.walk_range(tx_range)?;
+        let transactions = provider
+            .walk_static_or_db::<tables::Transactions>(StaticFileSegment::Transactions, tx_range)?;
 
         let chunk_size = (tx_range_size / rayon::current_num_threads()).max(1);
         let mut channels = Vec::with_capacity(chunk_size);
         let mut transaction_count = 0;
 
-        for chunk in &tx_walker.chunks(chunk_size) {
+        for chunk in &transactions.into_iter().chunks(chunk_size) {
             let (tx, rx) = mpsc::unbounded_channel();
             channels.push(rx);
 
@@ -95,8 +94,7 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
 
         // Iterate over channels and append the tx hashes to be sorted out later
         for mut channel in channels {
-            while let Some(tx) = channel.recv().await {
-                let (tx_hash, tx_id) = tx.map_err(|boxed| *boxed)?;
+            while let Some((tx_hash, tx_id)) = channel.recv().await {
                 tx_list.push((tx_hash, tx_id));
             }
         }
@@ -173,12 +171,11 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
 /// Calculates the hash of the given transaction
 #[inline]
 fn calculate_hash(
-    entry: Result<(TxNumber, TransactionSignedNoHash), DatabaseError>,
+    (tx_id, tx): (TxNumber, TransactionSignedNoHash),
     rlp_buf: &mut Vec<u8>,
-) -> Result<(H256, TxNumber), Box<StageError>> {
-    let (tx_id, tx) = entry.map_err(|e| Box::new(e.into()))?;
+) -> (H256, TxNumber) {
     tx.transaction.encode_with_signature(&tx.signature, rlp_buf, false);
-    Ok((keccak256(rlp_buf), tx_id))
+    (keccak256(rlp_buf), tx_id)
 }
 
 fn stage_checkpoint<DB: Database>(
@@ -197,7 +194,9 @@ fn stage_checkpoint<DB: Database>(
         // the actual number of processed transactions. To fix that, we add the number of pruned
         // `TxHashNumber` entries.
         processed: provider.tx_ref().entries::<tables::TxHashNumber>()? as u64 + pruned_entries,
-        total: provider.tx_ref().entries::<tables::Transactions>()? as u64,
+        total: provider
+            .static_or_db_entries::<tables::Transactions>(StaticFileSegment::Transactions)?
+            as u64,
     })
 }
 
@@ -51,7 +51,7 @@ pub enum TableType {
 }
 
 /// Number of tables that should be present inside database.
-pub const NUM_TABLES: usize = 26;
+pub const NUM_TABLES: usize = 28;
 
 /// The general purpose of this is to use with a combination of Tables enum,
 /// by implementing a `TableViewer` trait you can operate on db tables in an abstract way.
@@ -184,7 +184,9 @@ tables!([
     (TxSenders, TableType::Table),
     (SyncStage, TableType::Table),
     (SyncStageProgress, TableType::Table),
-    (PruneCheckpoints, TableType::Table)
+    (PruneCheckpoints, TableType::Table),
+    (LogAddressHistory, TableType::Table),
+    (LogTopicHistory, TableType::Table)
 ]);
 
 #[macro_export]
@@ -421,6 +423,25 @@ table!(
     ( PruneCheckpoints ) PrunePart | PruneCheckpoint
 );
 
+table!(
+    /// Stores pointers to the blocks that contain at least one log emitted by an address.
+    ///
+    /// The table is only populated if the optional log index stage is enabled. Shards are laid
+    /// out the same way as in [`AccountHistory`], with the last shard of every address keyed with
+    /// `u64::MAX`.
+    ( LogAddressHistory ) ShardedKey<Address> | BlockNumberList
+);
+
+table!(
+    /// Stores pointers to the blocks that contain at least one log with a given topic, regardless
+    /// of the topic position.
+    ///
+    /// The table is only populated if the optional log index stage is enabled. Shards are laid
+    /// out the same way as in [`AccountHistory`], with the last shard of every topic keyed with
+    /// `u64::MAX`.
+    ( LogTopicHistory ) ShardedKey<H256> | BlockNumberList
+);
+
 /// Alias Types
 
 /// List with transaction numbers.
@@ -461,6 +482,8 @@ mod tests {
         (TableType::Table, SyncStage::const_name()),
         (TableType::Table, SyncStageProgress::const_name()),
         (TableType::Table, PruneCheckpoints::const_name()),
+        (TableType::Table, LogAddressHistory::const_name()),
+        (TableType::Table, LogTopicHistory::const_name()),
     ];
 
     #[test]
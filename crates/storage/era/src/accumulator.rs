@@ -0,0 +1,144 @@
+//! The accumulator of an era1 file.
+//!
+//! The accumulator root is the SSZ `hash_tree_root` of a `List[HeaderRecord, 8192]`, where a
+//! `HeaderRecord` is the container of the block hash and the total difficulty of a block.
+//!
+//! The root is computed from the file itself, so it only proves that the file is consistent.
+//! Archives from untrusted sources are verified against [TrustedAccumulators], a list of the known
+//! roots of all epochs of the chain.
+
+use crate::{Era1, Era1Error, MAX_BLOCKS_PER_ERA1};
+use reth_primitives::{H256, U256};
+use sha2::{Digest, Sha256};
+use std::{path::Path, str::FromStr};
+
+/// Depth of the merkle tree of the header records.
+const DEPTH: u32 = MAX_BLOCKS_PER_ERA1.trailing_zeros();
+
+/// A leaf of the accumulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// The hash of the block.
+    pub block_hash: H256,
+    /// The total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Returns the SSZ `hash_tree_root` of the record.
+    pub fn hash_tree_root(&self) -> [u8; 32] {
+        hash_pair(&self.block_hash.0, &self.total_difficulty.to_le_bytes::<32>())
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new().chain_update(left).chain_update(right).finalize().into()
+}
+
+/// Computes the accumulator root of the given header records.
+///
+/// # Panics
+///
+/// If more than [MAX_BLOCKS_PER_ERA1] records are given.
+pub fn accumulator_root(records: &[HeaderRecord]) -> H256 {
+    assert!(records.len() <= MAX_BLOCKS_PER_ERA1, "too many header records");
+
+    let mut layer = records.iter().map(HeaderRecord::hash_tree_root).collect::<Vec<_>>();
+    let mut zero_hash = [0u8; 32];
+    for _ in 0..DEPTH {
+        if layer.len() % 2 == 1 {
+            layer.push(zero_hash);
+        }
+        layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        zero_hash = hash_pair(&zero_hash, &zero_hash);
+    }
+    let root = layer.first().copied().unwrap_or(zero_hash);
+
+    // mix in the length of the list
+    let mut length = [0u8; 32];
+    length[..8].copy_from_slice(&(records.len() as u64).to_le_bytes());
+    H256(hash_pair(&root, &length))
+}
+
+/// The trusted accumulator roots of the epochs of a chain, in the order of the epochs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedAccumulators {
+    roots: Vec<H256>,
+}
+
+impl TrustedAccumulators {
+    /// Creates a new list from the roots of the epochs, starting at epoch 0.
+    pub fn new(roots: Vec<H256>) -> Self {
+        Self { roots }
+    }
+
+    /// Reads the list from a file, see [TrustedAccumulators::parse].
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Era1Error> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parses a list with one hex encoded root per line, starting with the root of epoch 0.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(s: &str) -> Result<Self, Era1Error> {
+        let mut roots = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue
+            }
+            let root =
+                H256::from_str(line).map_err(|_| Era1Error::InvalidTrustedRoot { line: i + 1 })?;
+            roots.push(root);
+        }
+        Ok(Self { roots })
+    }
+
+    /// Returns the trusted root of the epoch, if known.
+    pub fn get(&self, epoch: u64) -> Option<H256> {
+        self.roots.get(usize::try_from(epoch).ok()?).copied()
+    }
+
+    /// Verifies that the file covers a whole epoch and its accumulator root is the trusted root of
+    /// that epoch.
+    ///
+    /// Returns the epoch of the file.
+    pub fn verify(&self, era1: &Era1) -> Result<u64, Era1Error> {
+        let first = era1.blocks.first().ok_or(Era1Error::NoBlocks)?.header.number;
+        if first % MAX_BLOCKS_PER_ERA1 as u64 != 0 {
+            return Err(Era1Error::UnalignedEpoch(first))
+        }
+        let epoch = first / MAX_BLOCKS_PER_ERA1 as u64;
+        if self.get(epoch) != Some(era1.accumulator_root) {
+            return Err(Era1Error::UntrustedAccumulator { epoch, root: era1.accumulator_root })
+        }
+        Ok(epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_and_single_record() {
+        // the root of an empty list only depends on the depth of the tree
+        let mut zero_hash = [0u8; 32];
+        for _ in 0..DEPTH {
+            zero_hash = hash_pair(&zero_hash, &zero_hash);
+        }
+        assert_eq!(accumulator_root(&[]), H256(hash_pair(&zero_hash, &[0u8; 32])));
+
+        let record =
+            HeaderRecord { block_hash: H256::repeat_byte(1), total_difficulty: U256::from(2) };
+        let mut root = record.hash_tree_root();
+        let mut zero_hash = [0u8; 32];
+        for _ in 0..DEPTH {
+            root = hash_pair(&root, &zero_hash);
+            zero_hash = hash_pair(&zero_hash, &zero_hash);
+        }
+        let mut length = [0u8; 32];
+        length[0] = 1;
+        assert_eq!(accumulator_root(&[record]), H256(hash_pair(&root, &length)));
+    }
+}
@@ -0,0 +1,98 @@
+//! The e2store container format.
+//!
+//! An e2store file is a sequence of entries, each consisting of an 8 byte header followed by the
+//! entry data. The header contains the entry type (2 bytes, little-endian), the length of the
+//! data (4 bytes, little-endian) and 2 reserved bytes which have to be zero.
+
+use crate::Era1Error;
+use std::io::Write;
+
+/// Size of the header of an entry.
+pub const HEADER_SIZE: usize = 8;
+
+/// A single e2store entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The type of the entry.
+    pub entry_type: u16,
+    /// The entry data.
+    pub data: Vec<u8>,
+}
+
+impl Entry {
+    /// Creates a new entry.
+    pub fn new(entry_type: u16, data: Vec<u8>) -> Self {
+        Self { entry_type, data }
+    }
+
+    /// Returns the number of bytes of the encoded entry.
+    pub fn encoded_len(&self) -> usize {
+        HEADER_SIZE + self.data.len()
+    }
+
+    /// Writes the encoded entry.
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), Era1Error> {
+        let len = u32::try_from(self.data.len()).map_err(|_| Era1Error::InvalidEntry {
+            entry: "e2store",
+            reason: format!("data of {} bytes exceeds the maximum length", self.data.len()),
+        })?;
+        let mut header = [0u8; HEADER_SIZE];
+        header[..2].copy_from_slice(&self.entry_type.to_le_bytes());
+        header[2..6].copy_from_slice(&len.to_le_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+
+    /// Decodes the next entry from the buffer and advances it. Returns `None` if the buffer is
+    /// empty.
+    pub fn decode(buf: &mut &[u8]) -> Result<Option<Self>, Era1Error> {
+        if buf.is_empty() {
+            return Ok(None)
+        }
+        if buf.len() < HEADER_SIZE {
+            return Err(Era1Error::TruncatedEntry)
+        }
+
+        let entry_type = u16::from_le_bytes([buf[0], buf[1]]);
+        let len = u32::from_le_bytes([buf[2], buf[3], buf[4], buf[5]]) as usize;
+        if buf[6..HEADER_SIZE] != [0, 0] {
+            return Err(Era1Error::InvalidEntry {
+                entry: "e2store",
+                reason: "reserved header bytes are not zero".to_string(),
+            })
+        }
+        if buf.len() < HEADER_SIZE + len {
+            return Err(Era1Error::TruncatedEntry)
+        }
+
+        let data = buf[HEADER_SIZE..HEADER_SIZE + len].to_vec();
+        *buf = &buf[HEADER_SIZE + len..];
+        Ok(Some(Self { entry_type, data }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_roundtrip() {
+        let entries = [Entry::new(0x3265, vec![]), Entry::new(0x03, vec![1, 2, 3])];
+        let mut encoded = Vec::new();
+        for entry in &entries {
+            entry.write(&mut encoded).unwrap();
+        }
+        assert_eq!(&encoded[..HEADER_SIZE], &[0x65, 0x32, 0, 0, 0, 0, 0, 0]);
+
+        let mut buf = &encoded[..];
+        for entry in &entries {
+            assert_eq!(Entry::decode(&mut buf).unwrap().as_ref(), Some(entry));
+        }
+        assert_eq!(Entry::decode(&mut buf).unwrap(), None);
+
+        let mut truncated = &encoded[..encoded.len() - 1];
+        Entry::decode(&mut truncated).unwrap();
+        assert!(matches!(Entry::decode(&mut truncated), Err(Era1Error::TruncatedEntry)));
+    }
+}
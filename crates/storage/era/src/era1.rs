@@ -0,0 +1,632 @@
+use crate::{
+    accumulator::{accumulator_root, HeaderRecord},
+    e2s::Entry,
+    Era1Error,
+};
+use reth_primitives::{Block, BlockBody, BlockNumber, Header, Log, Receipt, TxType, H256, U256};
+use reth_rlp::{Decodable, DecodeError, Encodable, Header as RlpHeader};
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+/// Type of the version entry, which is the first entry of every era1 file.
+pub const VERSION: u16 = 0x3265;
+/// Type of the entry containing the snappy compressed RLP encoded header of a block.
+pub const COMPRESSED_HEADER: u16 = 0x03;
+/// Type of the entry containing the snappy compressed RLP encoded body of a block.
+pub const COMPRESSED_BODY: u16 = 0x04;
+/// Type of the entry containing the snappy compressed RLP encoded receipts of a block.
+pub const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// Type of the entry containing the total difficulty of a block as little-endian `uint256`.
+pub const TOTAL_DIFFICULTY: u16 = 0x06;
+/// Type of the entry containing the accumulator root of the file.
+pub const ACCUMULATOR: u16 = 0x07;
+/// Type of the entry containing the offsets of the header entries of all blocks.
+pub const BLOCK_INDEX: u16 = 0x3266;
+
+/// Maximum number of blocks in a single era1 file.
+pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
+
+/// File extension of era1 files.
+pub const ERA1_EXTENSION: &str = "era1";
+
+/// Returns the canonical name of the era1 file of the given epoch, e.g.
+/// `mainnet-00000-5ec1ffb8.era1`.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator_root: H256) -> String {
+    let short_root =
+        accumulator_root.0[..4].iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+    format!("{network}-{epoch:05}-{short_root}.{ERA1_EXTENSION}")
+}
+
+/// A block as stored in an era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1Block {
+    /// The header of the block.
+    pub header: Header,
+    /// The transactions and ommers of the block.
+    pub body: BlockBody,
+    /// The receipts of the transactions of the block.
+    pub receipts: Vec<Receipt>,
+    /// The total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl Era1Block {
+    /// Returns the header record of the block that is committed to by the accumulator.
+    fn header_record(&self) -> HeaderRecord {
+        HeaderRecord {
+            block_hash: self.header.hash_slow(),
+            total_difficulty: self.total_difficulty,
+        }
+    }
+
+    /// Converts the era1 block into a [Block].
+    pub fn into_block(self) -> Block {
+        Block {
+            header: self.header,
+            body: self.body.transactions,
+            ommers: self.body.ommers,
+            withdrawals: self.body.withdrawals,
+        }
+    }
+}
+
+/// Receipt in the storage encoding used by era1 files: `[status, cumulative_gas_used, logs]`,
+/// without the transaction type and the bloom filter.
+struct StorageReceipt<'a>(&'a Receipt);
+
+impl StorageReceipt<'_> {
+    fn status(&self) -> &'static [u8] {
+        if self.0.success {
+            &[1]
+        } else {
+            &[]
+        }
+    }
+
+    fn rlp_header(&self) -> RlpHeader {
+        let payload_length =
+            self.status().length() + self.0.cumulative_gas_used.length() + self.0.logs.length();
+        RlpHeader { list: true, payload_length }
+    }
+
+    /// Decodes a receipt of a transaction of the given type.
+    ///
+    /// Pre-Byzantium receipts contain the intermediate state root instead of the status, they are
+    /// decoded as successful.
+    fn decode(buf: &mut &[u8], tx_type: TxType) -> Result<Receipt, DecodeError> {
+        let header = RlpHeader::decode(buf)?;
+        if !header.list {
+            return Err(DecodeError::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let status = RlpHeader::decode(buf)?;
+        if status.list {
+            return Err(DecodeError::UnexpectedList)
+        }
+        if buf.len() < status.payload_length {
+            return Err(DecodeError::InputTooShort)
+        }
+        let success = match &buf[..status.payload_length] {
+            [] => false,
+            [1] => true,
+            post_state if post_state.len() == 32 => true,
+            _ => return Err(DecodeError::Custom("invalid receipt status")),
+        };
+        *buf = &buf[status.payload_length..];
+
+        let cumulative_gas_used = u64::decode(buf)?;
+        let logs = Vec::<Log>::decode(buf)?;
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(DecodeError::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+
+        Ok(Receipt { tx_type, success, cumulative_gas_used, logs })
+    }
+}
+
+impl Encodable for StorageReceipt<'_> {
+    fn encode(&self, out: &mut dyn reth_rlp::BufMut) {
+        self.rlp_header().encode(out);
+        self.status().encode(out);
+        self.0.cumulative_gas_used.encode(out);
+        self.0.logs.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.rlp_header().payload_length;
+        payload_length + reth_rlp::length_of_length(payload_length)
+    }
+}
+
+fn compress(data: &[u8]) -> Result<Vec<u8>, Era1Error> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(data)?;
+    encoder.into_inner().map_err(|err| Era1Error::Io(err.into_error()))
+}
+
+fn decompress(data: &[u8]) -> Result<Vec<u8>, Era1Error> {
+    let mut decompressed = Vec::new();
+    snap::read::FrameDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+fn rlp_encode<T: Encodable + ?Sized>(value: &T) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(value.length());
+    value.encode(&mut buf);
+    buf
+}
+
+/// Decodes the value and ensures that the whole buffer was consumed.
+fn rlp_decode_exact<T: Decodable>(entry: &'static str, data: &[u8]) -> Result<T, Era1Error> {
+    let buf = &mut &data[..];
+    let value = T::decode(buf)?;
+    if !buf.is_empty() {
+        return Err(Era1Error::InvalidEntry {
+            entry,
+            reason: format!("{} trailing bytes", buf.len()),
+        })
+    }
+    Ok(value)
+}
+
+/// Writer of an era1 file.
+///
+/// Blocks have to be appended in ascending order without gaps.
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    /// The underlying writer.
+    writer: W,
+    /// The number of bytes written so far.
+    position: u64,
+    /// The number of the first block.
+    start_number: Option<BlockNumber>,
+    /// The positions of the header entries of all blocks.
+    header_positions: Vec<u64>,
+    /// The header records of all blocks.
+    records: Vec<HeaderRecord>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer and writes the version entry.
+    pub fn new(writer: W) -> Result<Self, Era1Error> {
+        let mut this = Self {
+            writer,
+            position: 0,
+            start_number: None,
+            header_positions: Vec::new(),
+            records: Vec::new(),
+        };
+        this.write_entry(Entry::new(VERSION, Vec::new()))?;
+        Ok(this)
+    }
+
+    /// Returns the number of blocks written so far.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no blocks were written yet.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    fn write_entry(&mut self, entry: Entry) -> Result<(), Era1Error> {
+        entry.write(&mut self.writer)?;
+        self.position += entry.encoded_len() as u64;
+        Ok(())
+    }
+
+    /// Appends the next block.
+    pub fn append(&mut self, block: &Era1Block) -> Result<(), Era1Error> {
+        if self.records.len() == MAX_BLOCKS_PER_ERA1 {
+            return Err(Era1Error::TooManyBlocks(MAX_BLOCKS_PER_ERA1))
+        }
+        if let Some(start_number) = self.start_number {
+            let expected = start_number + self.records.len() as u64;
+            if block.header.number != expected {
+                return Err(Era1Error::NonConsecutiveBlock { expected, got: block.header.number })
+            }
+        } else {
+            self.start_number = Some(block.header.number);
+        }
+
+        let receipts = block.receipts.iter().map(StorageReceipt).collect::<Vec<_>>();
+
+        self.header_positions.push(self.position);
+        self.write_entry(Entry::new(COMPRESSED_HEADER, compress(&rlp_encode(&block.header))?))?;
+        self.write_entry(Entry::new(COMPRESSED_BODY, compress(&rlp_encode(&block.body))?))?;
+        self.write_entry(Entry::new(COMPRESSED_RECEIPTS, compress(&rlp_encode(&receipts))?))?;
+        self.write_entry(Entry::new(
+            TOTAL_DIFFICULTY,
+            block.total_difficulty.to_le_bytes::<32>().to_vec(),
+        ))?;
+        self.records.push(block.header_record());
+
+        Ok(())
+    }
+
+    /// Writes the accumulator and the block index entries.
+    ///
+    /// Returns the underlying writer and the accumulator root of the file.
+    pub fn finish(mut self) -> Result<(W, H256), Era1Error> {
+        let root = accumulator_root(&self.records);
+        self.write_entry(Entry::new(ACCUMULATOR, root.0.to_vec()))?;
+
+        // offsets are relative to the start of the block index entry
+        let index_position = self.position as i64;
+        let mut index = Vec::with_capacity(16 + self.header_positions.len() * 8);
+        index.extend_from_slice(&self.start_number.unwrap_or_default().to_le_bytes());
+        for position in &self.header_positions {
+            index.extend_from_slice(&(*position as i64 - index_position).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.header_positions.len() as u64).to_le_bytes());
+        self.write_entry(Entry::new(BLOCK_INDEX, index))?;
+
+        self.writer.flush()?;
+        Ok((self.writer, root))
+    }
+}
+
+/// The decoded content of an era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1 {
+    /// The blocks of the file.
+    pub blocks: Vec<Era1Block>,
+    /// The accumulator root of the file.
+    pub accumulator_root: H256,
+}
+
+impl Era1 {
+    /// Reads and decodes the era1 file at the given path.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Era1Error> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    /// Decodes an era1 file.
+    ///
+    /// The blocks are verified against the accumulator root and the block index of the file.
+    pub fn decode(mut buf: &[u8]) -> Result<Self, Era1Error> {
+        let total_len = buf.len();
+        let next_entry = |buf: &mut &[u8], expected: u16| -> Result<(u64, Entry), Era1Error> {
+            let position = (total_len - buf.len()) as u64;
+            let entry = Entry::decode(buf)?.ok_or(Era1Error::TruncatedEntry)?;
+            if entry.entry_type != expected {
+                return Err(Era1Error::UnexpectedEntry { expected, got: entry.entry_type })
+            }
+            Ok((position, entry))
+        };
+
+        next_entry(&mut buf, VERSION)?;
+
+        let mut blocks = Vec::new();
+        let mut header_positions = Vec::new();
+        while buf.len() >= 2 && u16::from_le_bytes([buf[0], buf[1]]) == COMPRESSED_HEADER {
+            if blocks.len() == MAX_BLOCKS_PER_ERA1 {
+                return Err(Era1Error::TooManyBlocks(MAX_BLOCKS_PER_ERA1))
+            }
+
+            let (position, header) = next_entry(&mut buf, COMPRESSED_HEADER)?;
+            let header: Header = rlp_decode_exact("header", &decompress(&header.data)?)?;
+            if let Some(previous) = blocks.last().map(|block: &Era1Block| block.header.number) {
+                if header.number != previous + 1 {
+                    return Err(Era1Error::NonConsecutiveBlock {
+                        expected: previous + 1,
+                        got: header.number,
+                    })
+                }
+            }
+
+            let (_, body) = next_entry(&mut buf, COMPRESSED_BODY)?;
+            let body: BlockBody = rlp_decode_exact("body", &decompress(&body.data)?)?;
+
+            let (_, receipts) = next_entry(&mut buf, COMPRESSED_RECEIPTS)?;
+            let receipts = Self::decode_receipts(&decompress(&receipts.data)?, &body)?;
+
+            let (_, total_difficulty) = next_entry(&mut buf, TOTAL_DIFFICULTY)?;
+            let total_difficulty: [u8; 32] =
+                total_difficulty.data.try_into().map_err(|data: Vec<u8>| {
+                    Era1Error::InvalidEntry {
+                        entry: "total difficulty",
+                        reason: format!("expected 32 bytes, got {}", data.len()),
+                    }
+                })?;
+
+            header_positions.push(position);
+            blocks.push(Era1Block {
+                header,
+                body,
+                receipts,
+                total_difficulty: U256::from_le_bytes(total_difficulty),
+            });
+        }
+
+        let (_, accumulator) = next_entry(&mut buf, ACCUMULATOR)?;
+        if accumulator.data.len() != 32 {
+            return Err(Era1Error::InvalidEntry {
+                entry: "accumulator",
+                reason: format!("expected 32 bytes, got {}", accumulator.data.len()),
+            })
+        }
+        let expected_root = H256::from_slice(&accumulator.data);
+        let records = blocks.iter().map(Era1Block::header_record).collect::<Vec<_>>();
+        let root = accumulator_root(&records);
+        if root != expected_root {
+            return Err(Era1Error::AccumulatorMismatch { expected: expected_root, got: root })
+        }
+
+        let (index_position, index) = next_entry(&mut buf, BLOCK_INDEX)?;
+        Self::verify_block_index(&index.data, index_position, &blocks, &header_positions)?;
+
+        Ok(Self { blocks, accumulator_root: root })
+    }
+
+    fn decode_receipts(data: &[u8], body: &BlockBody) -> Result<Vec<Receipt>, Era1Error> {
+        let buf = &mut &data[..];
+        let header = RlpHeader::decode(buf)?;
+        if !header.list {
+            return Err(DecodeError::UnexpectedString.into())
+        }
+
+        let mut receipts = Vec::with_capacity(body.transactions.len());
+        for transaction in &body.transactions {
+            receipts.push(StorageReceipt::decode(buf, transaction.transaction.tx_type())?);
+        }
+        if !buf.is_empty() {
+            return Err(Era1Error::InvalidEntry {
+                entry: "receipts",
+                reason: format!(
+                    "receipts do not match the {} transactions of the block",
+                    body.transactions.len()
+                ),
+            })
+        }
+        Ok(receipts)
+    }
+
+    fn verify_block_index(
+        index: &[u8],
+        index_position: u64,
+        blocks: &[Era1Block],
+        header_positions: &[u64],
+    ) -> Result<(), Era1Error> {
+        let invalid = |reason: String| Era1Error::InvalidEntry { entry: "block index", reason };
+
+        if index.len() != 16 + blocks.len() * 8 {
+            return Err(invalid(format!(
+                "expected {} bytes for {} blocks, got {}",
+                16 + blocks.len() * 8,
+                blocks.len(),
+                index.len()
+            )))
+        }
+        let word = |i: usize| -> [u8; 8] { index[i * 8..i * 8 + 8].try_into().expect("8 bytes") };
+
+        let start_number = u64::from_le_bytes(word(0));
+        let count = u64::from_le_bytes(word(blocks.len() + 1));
+        if count != blocks.len() as u64 {
+            return Err(invalid(format!("expected {} blocks, got {count}", blocks.len())))
+        }
+        if let Some(first) = blocks.first() {
+            if first.header.number != start_number {
+                return Err(invalid(format!(
+                    "starting number {start_number} does not match block #{}",
+                    first.header.number
+                )))
+            }
+        }
+        for (i, position) in header_positions.iter().enumerate() {
+            let offset = i64::from_le_bytes(word(i + 1));
+            if index_position as i64 + offset != *position as i64 {
+                return Err(invalid(format!("wrong offset of block #{}", start_number + i as u64)))
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrustedAccumulators;
+    use reth_interfaces::test_utils::generators::{self, random_block_range, random_receipt};
+    use reth_primitives::{
+        hex_literal::hex,
+        proofs::{calculate_ommers_root, calculate_receipt_root, calculate_transaction_root},
+        Hardfork, MAINNET,
+    };
+    use std::path::PathBuf;
+
+    fn era1_blocks() -> Vec<Era1Block> {
+        let mut rng = generators::rng();
+        let mut total_difficulty = U256::ZERO;
+        random_block_range(&mut rng, 0..=9, H256::zero(), 0..3)
+            .into_iter()
+            .map(|block| {
+                total_difficulty += block.header.difficulty;
+                let receipts =
+                    block.body.iter().map(|tx| random_receipt(&mut rng, tx, Some(2))).collect();
+                let block = block.unseal();
+                Era1Block {
+                    header: block.header,
+                    body: BlockBody {
+                        transactions: block.body,
+                        ommers: block.ommers,
+                        withdrawals: None,
+                    },
+                    receipts,
+                    total_difficulty,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let blocks = era1_blocks();
+
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in &blocks {
+            writer.append(block).unwrap();
+        }
+        // blocks have to be consecutive
+        assert!(matches!(
+            writer.append(&blocks[0]),
+            Err(Era1Error::NonConsecutiveBlock { expected: 10, got: 0 })
+        ));
+        let (encoded, root) = writer.finish().unwrap();
+
+        let records = blocks.iter().map(Era1Block::header_record).collect::<Vec<_>>();
+        assert_eq!(root, accumulator_root(&records));
+
+        let era1 = Era1::decode(&encoded).unwrap();
+        assert_eq!(era1.accumulator_root, root);
+        assert_eq!(era1.blocks, blocks);
+
+        let [a, b, c, d, ..] = root.0;
+        assert_eq!(
+            era1_file_name("mainnet", 0, root),
+            format!("mainnet-00000-{a:02x}{b:02x}{c:02x}{d:02x}.era1")
+        );
+    }
+
+    #[test]
+    fn era1_tampered_block() {
+        let mut blocks = era1_blocks();
+
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in &blocks {
+            writer.append(block).unwrap();
+        }
+        let (_, root) = writer.finish().unwrap();
+
+        // a file with the same accumulator but a different block
+        blocks[5].header.gas_used += 1;
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in &blocks {
+            writer.append(block).unwrap();
+        }
+        let (mut encoded, _) = writer.finish().unwrap();
+        let index_len = 8 + 16 + blocks.len() * 8;
+        let accumulator_start = encoded.len() - index_len - 32;
+        encoded[accumulator_start..accumulator_start + 32].copy_from_slice(&root.0);
+
+        assert!(matches!(
+            Era1::decode(&encoded),
+            Err(Era1Error::AccumulatorMismatch { expected, .. }) if expected == root
+        ));
+    }
+
+    #[test]
+    fn era1_trusted_accumulators() {
+        let blocks = era1_blocks();
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in &blocks {
+            writer.append(block).unwrap();
+        }
+        let (encoded, root) = writer.finish().unwrap();
+        let era1 = Era1::decode(&encoded).unwrap();
+
+        let trusted =
+            TrustedAccumulators::parse(&format!("# mainnet\n{root:?}\n\n{:?}\n", H256::zero()))
+                .unwrap();
+        assert_eq!(trusted.get(1), Some(H256::zero()));
+        assert_eq!(trusted.verify(&era1).unwrap(), 0);
+
+        // the file's own accumulator is consistent, but not the trusted one
+        let trusted = TrustedAccumulators::new(vec![H256::repeat_byte(1)]);
+        assert!(matches!(
+            trusted.verify(&era1),
+            Err(Era1Error::UntrustedAccumulator { epoch: 0, root: got }) if got == root
+        ));
+
+        // files have to start at an epoch boundary
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in &blocks[1..] {
+            writer.append(block).unwrap();
+        }
+        let (encoded, _) = writer.finish().unwrap();
+        assert!(matches!(
+            trusted.verify(&Era1::decode(&encoded).unwrap()),
+            Err(Era1Error::UnalignedEpoch(1))
+        ));
+
+        assert!(matches!(
+            TrustedAccumulators::parse("0x1234"),
+            Err(Era1Error::InvalidTrustedRoot { line: 1 })
+        ));
+    }
+
+    #[test]
+    fn era1_mainnet_genesis() {
+        let genesis = MAINNET.genesis_header();
+        let total_difficulty = genesis.difficulty;
+        let block = Era1Block {
+            header: genesis,
+            body: BlockBody::default(),
+            receipts: Vec::new(),
+            total_difficulty,
+        };
+
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        writer.append(&block).unwrap();
+        let (encoded, _) = writer.finish().unwrap();
+        let era1 = Era1::decode(&encoded).unwrap();
+
+        let header = &era1.blocks[0].header;
+        assert_eq!(
+            header.hash_slow(),
+            H256(hex!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"))
+        );
+        assert_eq!(era1.blocks[0].total_difficulty, U256::from(0x400000000u64));
+    }
+
+    /// Decodes an era1 file of mainnet, e.g. `mainnet-00000-5ec1ffb8.era1`, whose path is given by
+    /// the `ERA1_FILE` environment variable, and checks the blocks against their headers.
+    ///
+    /// The files are available at <https://era1.ethportal.net>.
+    #[test]
+    #[ignore]
+    fn era1_mainnet_fixture() {
+        let path = PathBuf::from(std::env::var("ERA1_FILE").unwrap());
+        let era1 = Era1::read(&path).unwrap();
+
+        let first = era1.blocks[0].header.number;
+        let epoch = first / MAX_BLOCKS_PER_ERA1 as u64;
+        assert_eq!(first % MAX_BLOCKS_PER_ERA1 as u64, 0);
+
+        // the file name contains the first bytes of the accumulator root
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert_eq!(name, era1_file_name("mainnet", epoch, era1.accumulator_root));
+        if epoch == 0 {
+            assert_eq!(era1.accumulator_root.0[..4], hex!("5ec1ffb8"));
+            assert_eq!(era1.blocks[0].header.hash_slow(), MAINNET.genesis_hash());
+        }
+
+        for block in &era1.blocks {
+            let header = &block.header;
+            assert_eq!(
+                calculate_transaction_root(&block.body.transactions),
+                header.transactions_root
+            );
+            assert_eq!(calculate_ommers_root(&block.body.ommers), header.ommers_hash);
+
+            // pre-Byzantium receipts commit to the intermediate state root, which isn't decoded
+            if MAINNET.fork(Hardfork::Byzantium).active_at_block(header.number) {
+                let receipts = block
+                    .receipts
+                    .iter()
+                    .map(|receipt| receipt.clone().with_bloom())
+                    .collect::<Vec<_>>();
+                assert_eq!(calculate_receipt_root(&receipts), header.receipts_root);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use reth_primitives::{BlockNumber, H256};
+
+/// Errors that can occur when reading or writing era1 files.
+#[derive(Debug, thiserror::Error)]
+pub enum Era1Error {
+    /// An error occurred when reading or writing the file.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// An entry could not be decoded.
+    #[error(transparent)]
+    Rlp(#[from] reth_rlp::DecodeError),
+    /// The file ended in the middle of an entry.
+    #[error("truncated e2store entry")]
+    TruncatedEntry,
+    /// An entry of a different type was expected.
+    #[error("unexpected e2store entry type {got:#06x}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// The expected entry type.
+        expected: u16,
+        /// The entry type found in the file.
+        got: u16,
+    },
+    /// An entry has an invalid length or content.
+    #[error("invalid {entry} entry: {reason}")]
+    InvalidEntry {
+        /// The name of the entry.
+        entry: &'static str,
+        /// Why the entry is invalid.
+        reason: String,
+    },
+    /// The blocks of the file are not consecutive.
+    #[error("expected block #{expected}, got #{got}")]
+    NonConsecutiveBlock {
+        /// The expected block number.
+        expected: BlockNumber,
+        /// The block number found.
+        got: BlockNumber,
+    },
+    /// More blocks than fit into a single era1 file were added.
+    #[error("an era1 file can not contain more than {0} blocks")]
+    TooManyBlocks(usize),
+    /// The accumulator root stored in the file does not commit to its blocks.
+    #[error("accumulator root mismatch: expected {expected:?}, got {got:?}")]
+    AccumulatorMismatch {
+        /// The root stored in the file.
+        expected: H256,
+        /// The root computed from the blocks.
+        got: H256,
+    },
+    /// The file does not start at the first block of an epoch.
+    #[error("era1 file starts at block #{0}, which is not the first block of an epoch")]
+    UnalignedEpoch(BlockNumber),
+    /// The file does not contain any blocks.
+    #[error("era1 file does not contain any blocks")]
+    NoBlocks,
+    /// The accumulator root of the file is not in the list of trusted roots.
+    #[error("accumulator root {root:?} of epoch {epoch} is not trusted")]
+    UntrustedAccumulator {
+        /// The epoch of the file.
+        epoch: u64,
+        /// The accumulator root of the file.
+        root: H256,
+    },
+    /// A line of a list of trusted accumulator roots is not a valid root.
+    #[error("invalid accumulator root on line {line}")]
+    InvalidTrustedRoot {
+        /// The line number, starting at 1.
+        line: usize,
+    },
+}
@@ -0,0 +1,34 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxzy/reth/issues/"
+)]
+#![warn(missing_docs, unused_crate_dependencies)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![doc(test(
+    no_crate_inject,
+    attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
+))]
+
+//! Reading and writing of `.era1` history archives.
+//!
+//! An era1 file contains up to [MAX_BLOCKS_PER_ERA1] consecutive blocks together with their
+//! receipts and total difficulties. The entries are framed in the [e2store](e2s) format and
+//! compressed with snappy. Each file commits to its blocks with an
+//! [accumulator root](accumulator::accumulator_root), which allows verifying the archive against
+//! a trusted list of roots.
+
+pub mod accumulator;
+pub use accumulator::TrustedAccumulators;
+pub mod e2s;
+
+mod era1;
+pub use era1::{
+    era1_file_name, Era1, Era1Block, Era1Writer, ACCUMULATOR, BLOCK_INDEX, COMPRESSED_BODY,
+    COMPRESSED_HEADER, COMPRESSED_RECEIPTS, ERA1_EXTENSION, MAX_BLOCKS_PER_ERA1, TOTAL_DIFFICULTY,
+    VERSION,
+};
+
+mod error;
+pub use error::Era1Error;
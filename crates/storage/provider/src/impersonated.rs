@@ -0,0 +1,51 @@
+//! Senders of transactions that were included without a valid signature.
+
+use parking_lot::RwLock;
+use reth_primitives::{Address, TransactionSigned, TransactionSignedEcRecovered, TxHash};
+use std::{collections::HashMap, sync::Arc};
+
+/// A shareable map of transaction hashes to the senders of impersonated transactions.
+///
+/// Dev chains can include transactions of impersonated accounts that carry a placeholder
+/// signature, so their sender can't be recovered from the signature. The sender is recorded here
+/// instead and consulted whenever the sender of such a transaction is recovered.
+#[derive(Debug, Clone, Default)]
+pub struct ImpersonatedSenders {
+    inner: Arc<RwLock<HashMap<TxHash, Address>>>,
+}
+
+// === impl ImpersonatedSenders ===
+
+impl ImpersonatedSenders {
+    /// Records the sender of an impersonated transaction.
+    pub fn insert(&self, hash: TxHash, sender: Address) {
+        self.inner.write().insert(hash, sender);
+    }
+
+    /// Returns the recorded sender of the transaction, if any.
+    pub fn get(&self, hash: &TxHash) -> Option<Address> {
+        self.inner.read().get(hash).copied()
+    }
+
+    /// Returns the sender of the transaction.
+    ///
+    /// The recorded sender takes precedence over the signer recovered from the signature.
+    pub fn recover_signer(&self, transaction: &TransactionSigned) -> Option<Address> {
+        self.get(&transaction.hash()).or_else(|| transaction.recover_signer())
+    }
+
+    /// Returns the senders of all transactions, or `None` if any sender can't be recovered.
+    pub fn recover_signers(&self, transactions: &[TransactionSigned]) -> Option<Vec<Address>> {
+        transactions.iter().map(|tx| self.recover_signer(tx)).collect()
+    }
+
+    /// Consumes the transaction and returns it with its sender, see
+    /// [`recover_signer`](Self::recover_signer).
+    pub fn into_ecrecovered(
+        &self,
+        transaction: TransactionSigned,
+    ) -> Option<TransactionSignedEcRecovered> {
+        let signer = self.recover_signer(&transaction)?;
+        Some(TransactionSignedEcRecovered::from_signed_transaction(transaction, signer))
+    }
+}
@@ -21,15 +21,16 @@
 /// Various provider traits.
 mod traits;
 pub use traits::{
-    AccountExtReader, AccountReader, BlockExecutionWriter, BlockExecutor, BlockHashReader,
-    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter,
-    BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotification,
+    AccountExtReader, AccountRangeWithProof, AccountReader, BlockExecutionWriter, BlockExecutor,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    BlockWriter, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotification,
     CanonStateNotificationSender, CanonStateNotifications, CanonStateSubscriptions,
     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, ExecutorFactory, HashingWriter,
-    HeaderProvider, HistoryWriter, PostStateDataProvider, PruneCheckpointReader,
-    PruneCheckpointWriter, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
-    StageCheckpointWriter, StateProvider, StateProviderBox, StateProviderFactory,
-    StateRootProvider, StorageReader, TransactionsProvider, WithdrawalsProvider,
+    HeaderProvider, HistoryOrder, HistoryReader, HistoryWriter, PostStateDataProvider,
+    PruneCheckpointReader, PruneCheckpointWriter, ReceiptProvider, ReceiptProviderIdExt,
+    SnapStateReader, StageCheckpointReader, StageCheckpointWriter, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, StorageRangesWithProof, StorageReader,
+    TransactionsProvider, WithdrawalsProvider,
 };
 
 /// Provider trait implementations.
@@ -37,6 +38,7 @@ pub mod providers;
 pub use providers::{
     DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, HistoricalStateProvider,
     HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, ProviderFactory,
+    StaticFileProducer, StaticFileProvider, StaticFileSegment,
 };
 
 /// Execution result
@@ -52,3 +54,6 @@ pub use reth_interfaces::provider::ProviderError;
 
 pub mod chain;
 pub use chain::{Chain, DisplayBlocksChain};
+
+mod impersonated;
+pub use impersonated::ImpersonatedSenders;
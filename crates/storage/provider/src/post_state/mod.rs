@@ -1,6 +1,7 @@
 //! Output of execution.
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
     models::{AccountBeforeTx, BlockNumberAddress},
     tables,
     transaction::{DbTx, DbTxMut},
@@ -14,7 +15,7 @@ use reth_primitives::{
 };
 use reth_trie::{
     hashed_cursor::{HashedPostState, HashedPostStateCursorFactory, HashedStorage},
-    StateRoot, StateRootError,
+    ParallelStateRoot, StateRoot, StateRootError,
 };
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -277,6 +278,27 @@ impl PostState {
             .root()
     }
 
+    /// Calculate the state root for this [PostState], computing the storage roots of the changed
+    /// accounts in parallel.
+    ///
+    /// Every storage root is calculated in its own read-only transaction, so the post state must
+    /// apply on top of the latest committed database state.
+    ///
+    /// See also [PostState::state_root_slow].
+    ///
+    /// # Returns
+    ///
+    /// The state root for this [PostState].
+    pub fn state_root_parallel<DB: Database>(&self, db: &DB) -> Result<H256, StateRootError> {
+        let hashed_post_state = self.hash_state_slow().sorted();
+        let (account_prefix_set, storage_prefix_set) = hashed_post_state.construct_prefix_sets();
+        ParallelStateRoot::new(db)
+            .with_hashed_post_state(&hashed_post_state)
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_set)
+            .root()
+    }
+
     // todo: note overwrite behavior, i.e. changes in `other` take precedent
     /// Extend this [PostState] with the changes in another [PostState].
     pub fn extend(&mut self, mut other: PostState) {
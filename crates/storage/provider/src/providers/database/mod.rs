@@ -1,15 +1,20 @@
 use crate::{
-    providers::state::{historical::HistoricalStateProvider, latest::LatestStateProvider},
+    providers::{
+        state::{historical::HistoricalStateProvider, latest::LatestStateProvider},
+        StaticFileProvider,
+    },
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider,
-    HeaderProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
-    TransactionsProvider, WithdrawalsProvider,
+    AccountRangeWithProof, BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    EvmEnvProvider, HeaderProvider, ProviderError, PruneCheckpointReader, SnapStateReader,
+    StageCheckpointReader, StateProviderBox, StorageRangesWithProof, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_interfaces::Result;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
+    trie::Nibbles,
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes, ChainInfo,
     ChainSpec, Header, PruneCheckpoint, PrunePart, Receipt, SealedBlock, SealedHeader,
     TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
     H256, U256,
@@ -31,6 +36,8 @@ pub struct ProviderFactory<DB> {
     db: DB,
     /// Chain spec
     chain_spec: Arc<ChainSpec>,
+    /// Static files of frozen headers, transactions and receipts.
+    static_files: Option<StaticFileProvider>,
 }
 
 impl<DB: Database> ProviderFactory<DB> {
@@ -38,7 +45,8 @@ impl<DB: Database> ProviderFactory<DB> {
     /// database using different types of providers. Example: [`HeaderProvider`]
     /// [`BlockHashReader`]. This may fail if the inner read database transaction fails to open.
     pub fn provider(&self) -> Result<DatabaseProviderRO<'_, DB>> {
-        Ok(DatabaseProvider::new(self.db.tx()?, self.chain_spec.clone()))
+        Ok(DatabaseProvider::new(self.db.tx()?, self.chain_spec.clone())
+            .with_static_files(self.static_files.clone()))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -46,14 +54,30 @@ impl<DB: Database> ProviderFactory<DB> {
     /// [`BlockHashReader`].  This may fail if the inner read/write database transaction fails to
     /// open.
     pub fn provider_rw(&self) -> Result<DatabaseProviderRW<'_, DB>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(self.db.tx_mut()?, self.chain_spec.clone())))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(self.db.tx_mut()?, self.chain_spec.clone())
+                .with_static_files(self.static_files.clone()),
+            &self.db,
+        ))
     }
 }
 
 impl<DB> ProviderFactory<DB> {
     /// create new database provider
     pub fn new(db: DB, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { db, chain_spec }
+        Self { db, chain_spec, static_files: None }
+    }
+
+    /// Reads the headers, transactions and receipts that were moved out of the database from the
+    /// given static files.
+    pub fn with_static_files(mut self, static_files: StaticFileProvider) -> Self {
+        self.static_files = Some(static_files);
+        self
+    }
+
+    /// Returns the static files of the factory, if any.
+    pub fn static_files(&self) -> Option<&StaticFileProvider> {
+        self.static_files.as_ref()
     }
 }
 
@@ -69,13 +93,18 @@ impl<DB: Database> ProviderFactory<DB> {
             db: init_db(path, log_level)
                 .map_err(|e| reth_interfaces::Error::Custom(e.to_string()))?,
             chain_spec,
+            static_files: None,
         })
     }
 }
 
 impl<DB: Clone> Clone for ProviderFactory<DB> {
     fn clone(&self) -> Self {
-        Self { db: self.db.clone(), chain_spec: Arc::clone(&self.chain_spec) }
+        Self {
+            db: self.db.clone(),
+            chain_spec: Arc::clone(&self.chain_spec),
+            static_files: self.static_files.clone(),
+        }
     }
 }
 
@@ -380,6 +409,46 @@ where
     }
 }
 
+impl<DB: Database> SnapStateReader for ProviderFactory<DB> {
+    fn account_range(
+        &self,
+        root: H256,
+        start: H256,
+        limit: H256,
+        max_accounts: usize,
+    ) -> Result<Option<AccountRangeWithProof>> {
+        self.provider()?.account_range(root, start, limit, max_accounts)
+    }
+
+    fn storage_ranges(
+        &self,
+        root: H256,
+        accounts: &[H256],
+        start: H256,
+        limit: H256,
+        max_slots: usize,
+    ) -> Result<Option<StorageRangesWithProof>> {
+        self.provider()?.storage_ranges(root, accounts, start, limit, max_slots)
+    }
+
+    fn bytecodes(&self, hashes: &[H256], max_bytes: usize) -> Result<Vec<Bytes>> {
+        self.provider()?.bytecodes(hashes, max_bytes)
+    }
+
+    fn account_trie_nodes(&self, root: H256, paths: Vec<Nibbles>) -> Result<Option<Vec<Bytes>>> {
+        self.provider()?.account_trie_nodes(root, paths)
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        root: H256,
+        hashed_address: H256,
+        paths: Vec<Nibbles>,
+    ) -> Result<Option<Vec<Bytes>>> {
+        self.provider()?.storage_trie_nodes(root, hashed_address, paths)
+    }
+}
+
 impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     fn get_prune_checkpoint(&self, part: PrunePart) -> Result<Option<PruneCheckpoint>> {
         self.provider()?.get_prune_checkpoint(part)
@@ -389,12 +458,22 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
-    use crate::{BlockHashReader, BlockNumReader};
+    use crate::{
+        BlockExecutionWriter, BlockHashReader, BlockNumReader, HeaderProvider, HistoryOrder,
+        HistoryReader, ProviderError, ReceiptProvider, StageCheckpointWriter, StaticFileProducer,
+        StaticFileProvider, StaticFileSegment,
+    };
     use reth_db::{
+        models::{storage_sharded_key::StorageShardedKey, ShardedKey, StoredBlockBodyIndices},
+        tables,
         test_utils::{create_test_rw_db, ERROR_TEMPDIR},
-        DatabaseEnv,
+        transaction::{DbTx, DbTxMut},
+        BlockNumberList, DatabaseEnv,
+    };
+    use reth_primitives::{
+        stage::{StageCheckpoint, StageId},
+        Address, ChainSpecBuilder, Header, Receipt, TransactionSignedNoHash, TxType, H256,
     };
-    use reth_primitives::{ChainSpecBuilder, H256};
     use std::sync::Arc;
 
     #[test]
@@ -445,4 +524,181 @@ mod tests {
         provider_rw.block_hash(0).unwrap();
         provider.block_hash(0).unwrap();
     }
+
+    #[test]
+    fn provider_reads_static_files() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let static_files_dir = tempfile::tempdir().expect(ERROR_TEMPDIR);
+        let static_files = StaticFileProvider::new(static_files_dir.path()).unwrap();
+        let factory = ProviderFactory::new(create_test_rw_db(), Arc::new(chain_spec))
+            .with_static_files(static_files.clone());
+
+        let headers = (0..4u64)
+            .map(|number| Header { number, gas_limit: number, ..Default::default() })
+            .collect::<Vec<_>>();
+        let receipts = (0..4u64)
+            .map(|tx| Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: tx,
+                logs: vec![],
+            })
+            .collect::<Vec<_>>();
+
+        let provider = factory.provider_rw().unwrap();
+        for (number, (header, receipt)) in headers.iter().zip(&receipts).enumerate() {
+            let number = number as u64;
+            let tx = provider.tx_ref();
+            tx.put::<tables::Headers>(number, header.clone()).unwrap();
+            tx.put::<tables::BlockBodyIndices>(
+                number,
+                StoredBlockBodyIndices { first_tx_num: number, tx_count: 1 },
+            )
+            .unwrap();
+            tx.put::<tables::Receipts>(number, receipt.clone()).unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(3)).unwrap();
+        provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(3)).unwrap();
+        provider.commit().unwrap();
+
+        let producer = StaticFileProducer::new(factory.clone(), static_files.clone(), 2);
+        // transactions are not frozen, because the bodies stage has not run yet
+        assert_eq!(producer.run(3).unwrap(), 4);
+        assert_eq!(producer.run(3).unwrap(), 0);
+        assert_eq!(static_files.highest_block(StaticFileSegment::Headers), Some(3));
+        assert_eq!(static_files.highest_key(StaticFileSegment::Receipts), Some(3));
+        assert_eq!(static_files.highest_block(StaticFileSegment::Transactions), None);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::Headers>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::Receipts>().unwrap(), 0);
+        assert_eq!(provider.header_by_number(2).unwrap().as_ref(), Some(&headers[2]));
+        assert_eq!(provider.headers_range(1..).unwrap(), headers[1..]);
+        assert_eq!(provider.receipt(3).unwrap().as_ref(), Some(&receipts[3]));
+        assert_eq!(provider.receipts_by_block(1.into()).unwrap(), Some(vec![receipts[1].clone()]));
+        assert_eq!(provider.header_by_number(4).unwrap(), None);
+
+        // frozen ranges are picked up again after a restart
+        let static_files = StaticFileProvider::new(static_files_dir.path()).unwrap();
+        assert_eq!(static_files.highest_block(StaticFileSegment::Headers), Some(3));
+        assert_eq!(
+            static_files.range::<Receipt>(StaticFileSegment::Receipts, 1..=2).unwrap(),
+            vec![(1, receipts[1].clone()), (2, receipts[2].clone())]
+        );
+    }
+
+    #[test]
+    fn provider_rejects_unwind_into_static_files() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let static_files_dir = tempfile::tempdir().expect(ERROR_TEMPDIR);
+        let static_files = StaticFileProvider::new(static_files_dir.path()).unwrap();
+        let factory = ProviderFactory::new(create_test_rw_db(), Arc::new(chain_spec.clone()))
+            .with_static_files(static_files.clone());
+
+        // four blocks with a single transaction each
+        let provider = factory.provider_rw().unwrap();
+        for number in 0..4u64 {
+            let tx = provider.tx_ref();
+            let header = Header { number, ..Default::default() };
+            tx.put::<tables::CanonicalHeaders>(number, header.hash_slow()).unwrap();
+            tx.put::<tables::Headers>(number, header).unwrap();
+            tx.put::<tables::BlockBodyIndices>(
+                number,
+                StoredBlockBodyIndices { first_tx_num: number, tx_count: 1 },
+            )
+            .unwrap();
+            tx.put::<tables::Transactions>(number, TransactionSignedNoHash::default()).unwrap();
+            tx.put::<tables::TxSenders>(number, Address::from_low_u64_be(number)).unwrap();
+            tx.put::<tables::Receipts>(
+                number,
+                Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: number,
+                    logs: vec![],
+                },
+            )
+            .unwrap();
+        }
+        for stage in [StageId::Headers, StageId::Bodies, StageId::Execution] {
+            provider.save_stage_checkpoint(stage, StageCheckpoint::new(3)).unwrap();
+        }
+        provider.commit().unwrap();
+
+        // blocks 0 and 1 are moved to static files
+        let producer = StaticFileProducer::new(factory.clone(), static_files.clone(), 2);
+        assert_eq!(producer.run(1).unwrap(), 3);
+
+        // reads of the block range are served from static files and the database
+        let provider = factory.provider_rw().unwrap();
+        let blocks = provider.get_block_and_execution_range(&chain_spec, 0..=3).unwrap();
+        assert_eq!(blocks.len(), 4);
+        for (number, (block, state)) in blocks.iter().enumerate() {
+            let number = number as u64;
+            assert_eq!(block.number, number);
+            assert_eq!(block.senders, vec![Address::from_low_u64_be(number)]);
+            assert_eq!(state.receipts(number)[0].cumulative_gas_used, number);
+        }
+
+        // frozen blocks can't be unwound
+        assert!(matches!(
+            provider.take_block_and_execution_range(&chain_spec, 1..=3),
+            Err(reth_interfaces::Error::Provider(ProviderError::UnwindBelowStaticFiles {
+                unwind_to: 0,
+                highest_static_block: 1
+            }))
+        ));
+        assert!(provider.ensure_unwind_above_static_files(1).is_ok());
+    }
+
+    #[test]
+    fn history_blocks_with_limit() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let factory = ProviderFactory::new(create_test_rw_db(), Arc::new(chain_spec));
+        let address = Address::from_low_u64_be(1);
+        let other = Address::from_low_u64_be(2);
+
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        let list = |blocks: &[usize]| BlockNumberList::new(blocks).unwrap();
+        tx.put::<tables::AccountHistory>(ShardedKey::new(address, 5), list(&[1, 3, 5])).unwrap();
+        tx.put::<tables::AccountHistory>(ShardedKey::new(address, u64::MAX), list(&[7, 9]))
+            .unwrap();
+        // two shards of the first slot, a single one of the second and another address
+        for (address, slot, highest, blocks) in [
+            (address, 1, 4, list(&[2, 4])),
+            (address, 1, u64::MAX, list(&[6, 8])),
+            (address, 2, u64::MAX, list(&[3, 5, 7])),
+            (other, 1, u64::MAX, list(&[1, 2, 3])),
+        ] {
+            let key = StorageShardedKey::new(address, H256::from_low_u64_be(slot), highest);
+            tx.put::<tables::StorageHistory>(key, blocks).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let account_blocks = |range, limit, order| {
+            provider.account_history_blocks(address, range, limit, order).unwrap()
+        };
+        let storage_blocks = |range, limit, order| {
+            provider.storage_history_blocks(address, range, limit, order).unwrap()
+        };
+        assert_eq!(
+            account_blocks(0..=10, usize::MAX, HistoryOrder::Ascending),
+            vec![1, 3, 5, 7, 9]
+        );
+        assert_eq!(account_blocks(2..=10, 3, HistoryOrder::Ascending), vec![3, 5, 7]);
+        assert_eq!(account_blocks(2..=8, 3, HistoryOrder::Descending), vec![3, 5, 7]);
+        assert_eq!(account_blocks(0..=6, 2, HistoryOrder::Descending), vec![3, 5]);
+        assert_eq!(
+            storage_blocks(0..=10, usize::MAX, HistoryOrder::Ascending),
+            vec![2, 3, 4, 5, 6, 7, 8]
+        );
+        assert_eq!(storage_blocks(5..=7, usize::MAX, HistoryOrder::Descending), vec![5, 6, 7]);
+        assert_eq!(storage_blocks(0..=10, 3, HistoryOrder::Ascending), vec![2, 3, 4]);
+        assert_eq!(storage_blocks(5..=10, 2, HistoryOrder::Ascending), vec![5, 6]);
+        assert_eq!(storage_blocks(0..=10, 3, HistoryOrder::Descending), vec![6, 7, 8]);
+        assert_eq!(storage_blocks(0..=4, 2, HistoryOrder::Descending), vec![3, 4]);
+        assert!(storage_blocks(0..=10, 0, HistoryOrder::Ascending).is_empty());
+    }
 }
@@ -1,12 +1,14 @@
 use crate::{
     post_state::StorageChangeset,
+    providers::{StaticFileProvider, StaticFileSegment},
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, AccountRangeWithProof, BlockSource, ChangeSetReader, ReceiptProvider,
+        StageCheckpointWriter, StorageRangesWithProof,
     },
     AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    EvmEnvProvider, HashingWriter, HeaderProvider, HistoryWriter, PostState, ProviderError,
-    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StorageReader,
-    TransactionsProvider, WithdrawalsProvider,
+    EvmEnvProvider, HashingWriter, HeaderProvider, HistoryOrder, HistoryReader, HistoryWriter,
+    PostState, ProviderError, PruneCheckpointReader, PruneCheckpointWriter, SnapStateReader,
+    StageCheckpointReader, StorageReader, TransactionsProvider, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -30,7 +32,7 @@ use reth_primitives::{
     keccak256,
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes,
     ChainInfo, ChainSpec, Hardfork, Head, Header, PruneCheckpoint, PrunePart, Receipt, SealedBlock,
     SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta, TransactionSigned,
     TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, H256,
@@ -41,11 +43,11 @@ use reth_revm_primitives::{
     env::{fill_block_env, fill_cfg_and_block_env, fill_cfg_env},
     primitives::{BlockEnv, CfgEnv, SpecId},
 };
-use reth_trie::{prefix_set::PrefixSetMut, StateRoot};
+use reth_trie::{prefix_set::PrefixSetMut, Proof, StateRoot, StorageRoot};
 use std::{
     collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::Debug,
-    ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeInclusive},
     sync::Arc,
 };
 
@@ -59,6 +61,8 @@ pub type DatabaseProviderRO<'this, DB> = DatabaseProvider<'this, <DB as Database
 #[derive(Debug)]
 pub struct DatabaseProviderRW<'this, DB: Database>(
     pub DatabaseProvider<'this, <DB as DatabaseGAT<'this>>::TXMut>,
+    /// The database the transaction was opened on.
+    &'this DB,
 );
 
 impl<'this, DB: Database> Deref for DatabaseProviderRW<'this, DB> {
@@ -85,6 +89,12 @@ impl<'this, DB: Database> DatabaseProviderRW<'this, DB> {
     pub fn into_tx(self) -> <DB as DatabaseGAT<'this>>::TXMut {
         self.0.into_tx()
     }
+
+    /// Returns the database the transaction was opened on, e.g. to open additional read-only
+    /// transactions. Those only observe the committed state of the database.
+    pub fn db(&self) -> &'this DB {
+        self.1
+    }
 }
 
 /// A provider struct that fetchs data from the database.
@@ -98,13 +108,15 @@ where
     tx: TX,
     /// Chain spec
     chain_spec: Arc<ChainSpec>,
+    /// Static files of frozen headers, transactions and receipts.
+    static_files: Option<StaticFileProvider>,
     _phantom_data: std::marker::PhantomData<&'this TX>,
 }
 
 impl<'this, TX: DbTxMut<'this>> DatabaseProvider<'this, TX> {
     /// Creates a provider with an inner read-write transaction.
     pub fn new_rw(tx: TX, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { tx, chain_spec, _phantom_data: std::marker::PhantomData }
+        Self { tx, chain_spec, static_files: None, _phantom_data: std::marker::PhantomData }
     }
 }
 
@@ -156,10 +168,77 @@ where
     Ok(Vec::new())
 }
 
+/// Returns the block number at which a history walk over the range starts.
+fn history_walk_start(range: &RangeInclusive<BlockNumber>, order: HistoryOrder) -> BlockNumber {
+    match order {
+        HistoryOrder::Ascending => *range.start(),
+        HistoryOrder::Descending => *range.end(),
+    }
+}
+
+/// Adds the block numbers of a history shard that are within the range to `blocks`, keeping at
+/// most `limit` of them in the given order.
+///
+/// Shards must be visited in walk order. Returns `false` if no further shard of the same key can
+/// contribute any blocks.
+fn collect_history_shard(
+    blocks: &mut BTreeSet<BlockNumber>,
+    list: &BlockNumberList,
+    range: &RangeInclusive<BlockNumber>,
+    limit: usize,
+    order: HistoryOrder,
+) -> bool {
+    if limit == 0 {
+        return false
+    }
+
+    let mut shard = list.iter(0).map(|n| n as u64).collect::<Vec<_>>();
+    if order == HistoryOrder::Descending {
+        shard.reverse();
+    }
+    for block in shard {
+        let (before_range, after_range, after_collected) = match order {
+            HistoryOrder::Ascending => {
+                (block < *range.start(), block > *range.end(), blocks.last() <= Some(&block))
+            }
+            HistoryOrder::Descending => {
+                (block > *range.end(), block < *range.start(), blocks.first() >= Some(&block))
+            }
+        };
+        if before_range {
+            continue
+        }
+        // Blocks are visited in walk order, so none of the remaining blocks can be collected once
+        // the walk left the range or the collected blocks are complete.
+        if after_range || (blocks.len() == limit && after_collected) {
+            return false
+        }
+        blocks.insert(block);
+        if blocks.len() > limit {
+            match order {
+                HistoryOrder::Ascending => blocks.pop_last(),
+                HistoryOrder::Descending => blocks.pop_first(),
+            };
+        }
+    }
+    true
+}
+
 impl<'this, TX: DbTx<'this>> DatabaseProvider<'this, TX> {
     /// Creates a provider with an inner read-only transaction.
     pub fn new(tx: TX, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { tx, chain_spec, _phantom_data: std::marker::PhantomData }
+        Self { tx, chain_spec, static_files: None, _phantom_data: std::marker::PhantomData }
+    }
+
+    /// Reads the entries that were moved out of the database from the given static files.
+    pub fn with_static_files(mut self, static_files: Option<StaticFileProvider>) -> Self {
+        self.static_files = static_files;
+        self
+    }
+
+    /// Returns the static files of frozen headers, transactions and receipts, if any.
+    pub fn static_files(&self) -> Option<&StaticFileProvider> {
+        self.static_files.as_ref()
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -167,6 +246,126 @@ impl<'this, TX: DbTx<'this>> DatabaseProvider<'this, TX> {
         self.tx
     }
 
+    /// Returns the static files of the segment if they contain the given key.
+    fn static_files_with_key(
+        &self,
+        segment: StaticFileSegment,
+        key: u64,
+    ) -> Option<&StaticFileProvider> {
+        self.static_files
+            .as_ref()
+            .filter(|static_files| static_files.highest_key(segment).map_or(false, |k| key <= k))
+    }
+
+    /// Returns the value of the key, reading it from the static files of the segment if it was
+    /// moved out of the database.
+    pub fn get_static_or_db<T: Table<Key = u64>>(
+        &self,
+        segment: StaticFileSegment,
+        key: u64,
+    ) -> Result<Option<T::Value>> {
+        match self.static_files_with_key(segment, key) {
+            Some(static_files) => static_files.get(segment, key),
+            None => Ok(self.tx.get::<T>(key)?),
+        }
+    }
+
+    /// Returns the number of entries of the table, including the entries that were moved out of
+    /// the database into the static files of the segment.
+    pub fn static_or_db_entries<T: Table<Key = u64>>(
+        &self,
+        segment: StaticFileSegment,
+    ) -> Result<usize> {
+        let frozen = self
+            .static_files
+            .as_ref()
+            .and_then(|static_files| static_files.highest_key(segment))
+            .map_or(0, |key| key as usize + 1);
+        Ok(self.tx.entries::<T>()? + frozen)
+    }
+
+    /// Returns all entries in the range, reading the part that was moved out of the database from
+    /// the static files of the segment.
+    pub fn walk_static_or_db<T: Table<Key = u64>>(
+        &self,
+        segment: StaticFileSegment,
+        range: impl RangeBounds<u64>,
+    ) -> Result<Vec<KeyValue<T>>> {
+        let mut start = match range.start_bound() {
+            Bound::Included(key) => *key,
+            Bound::Excluded(key) => key.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => Some(*key),
+            Bound::Excluded(0) => return Ok(Vec::new()),
+            Bound::Excluded(key) => Some(key - 1),
+            Bound::Unbounded => None,
+        };
+        if end.map_or(false, |end| start > end) {
+            return Ok(Vec::new())
+        }
+
+        let mut entries = Vec::new();
+        if let Some(static_files) = self.static_files_with_key(segment, start) {
+            let highest = static_files.highest_key(segment).unwrap_or_default();
+            let static_end = end.map_or(highest, |end| end.min(highest));
+            entries = static_files.range(segment, start..=static_end)?;
+            if end == Some(static_end) {
+                return Ok(entries)
+            }
+            start = static_end + 1;
+        }
+
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let walker = match end {
+            Some(end) => cursor.walk_range(start..=end)?,
+            None => cursor.walk_range(start..)?,
+        };
+        for entry in walker {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+
+    /// Returns an error if unwinding to the given block would remove blocks that were moved to
+    /// static files, since the static files are append-only and would keep serving them.
+    pub fn ensure_unwind_above_static_files(&self, unwind_to: BlockNumber) -> Result<()> {
+        let highest_static_block = self.static_files.as_ref().and_then(|static_files| {
+            StaticFileSegment::ALL
+                .into_iter()
+                .filter_map(|segment| static_files.highest_block(segment))
+                .max()
+        });
+        match highest_static_block {
+            Some(highest_static_block) if highest_static_block > unwind_to => {
+                Err(ProviderError::UnwindBelowStaticFiles { unwind_to, highest_static_block }
+                    .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the body indices of the block.
+    ///
+    /// Returns [ProviderError::BlockBodyPruned] instead of `None` if the body was removed by
+    /// [PrunePart::Bodies], so callers can tell expired history apart from unknown blocks.
+    fn block_body_indices_or_pruned(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<StoredBlockBodyIndices>> {
+        if let Some(body) = self.tx.get::<tables::BlockBodyIndices>(number)? {
+            return Ok(Some(body))
+        }
+        if self
+            .get_prune_checkpoint(PrunePart::Bodies)?
+            .map_or(false, |checkpoint| number <= checkpoint.block_number)
+        {
+            return Err(ProviderError::BlockBodyPruned(number).into())
+        }
+        Ok(None)
+    }
+
     /// Pass `DbTx` or `DbTxMut` mutable reference.
     pub fn tx_mut(&mut self) -> &mut TX {
         &mut self.tx
@@ -187,6 +386,86 @@ impl<'this, TX: DbTx<'this>> DatabaseProvider<'this, TX> {
             .walk(Some(T::Key::default()))?
             .collect::<std::result::Result<Vec<_>, DatabaseError>>()
     }
+
+    /// Returns the root of the latest state trie.
+    ///
+    /// This only reads the root node of the account trie if the intermediate nodes are up to date.
+    fn latest_state_root(&self) -> Result<H256> {
+        Ok(StateRoot::new(&self.tx).root().map_err(Into::<DatabaseError>::into)?)
+    }
+
+    /// Returns the block numbers in the given range that are indexed for the given key in a
+    /// history table sharded by [ShardedKey], at most `limit` of them in the given order.
+    ///
+    /// The returned block numbers are sorted in ascending order.
+    fn history_blocks<T, K>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<u64>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: PartialEq + Clone,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut blocks = BTreeSet::new();
+
+        // Shards are keyed by the highest block number they contain and the last shard of a key
+        // by `u64::MAX`, so seeking the first block of the walk lands in the shard containing it.
+        let mut item =
+            cursor.seek(ShardedKey::new(key.clone(), history_walk_start(&range, order)))?;
+        while let Some((sharded_key, list)) = item {
+            if sharded_key.key != key ||
+                !collect_history_shard(&mut blocks, &list, &range, limit, order)
+            {
+                break
+            }
+            item = match order {
+                HistoryOrder::Ascending => cursor.next()?,
+                HistoryOrder::Descending => cursor.prev()?,
+            };
+        }
+
+        Ok(blocks.into_iter().collect())
+    }
+
+    /// Walks the receipts of the given block range and collects the blocks in which each log
+    /// address and each log topic appear.
+    ///
+    /// Block numbers are collected in ascending order without duplicates.
+    #[allow(clippy::type_complexity)]
+    fn log_addresses_and_topics_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(BTreeMap<Address, Vec<u64>>, BTreeMap<H256, Vec<u64>>)> {
+        let mut addresses: BTreeMap<Address, Vec<u64>> = BTreeMap::new();
+        let mut topics: BTreeMap<H256, Vec<u64>> = BTreeMap::new();
+
+        for entry in self.tx.cursor_read::<tables::BlockBodyIndices>()?.walk_range(range)? {
+            let (block_number, body) = entry?;
+            for (_, receipt) in self.walk_static_or_db::<tables::Receipts>(
+                StaticFileSegment::Receipts,
+                body.tx_num_range(),
+            )? {
+                for log in receipt.logs {
+                    let blocks = addresses.entry(log.address).or_default();
+                    if blocks.last() != Some(&block_number) {
+                        blocks.push(block_number);
+                    }
+                    for topic in log.topics {
+                        let blocks = topics.entry(topic).or_default();
+                        if blocks.last() != Some(&block_number) {
+                            blocks.push(block_number);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((addresses, topics))
+    }
 }
 
 impl<'this, TX: DbTxMut<'this> + DbTx<'this>> DatabaseProvider<'this, TX> {
@@ -236,8 +515,10 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> DatabaseProvider<'this, TX> {
             block_bodies.first().expect("already checked if there are blocks").1.first_tx_num();
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
-        let receipts =
-            self.get_or_take::<tables::Receipts, TAKE>(from_transaction_num..=to_transaction_num)?;
+        let receipts = self.get_or_take_static_or_db::<tables::Receipts, TAKE>(
+            StaticFileSegment::Receipts,
+            from_transaction_num..=to_transaction_num,
+        )?;
 
         let storage_range = BlockNumberAddress::range(range.clone());
 
@@ -403,6 +684,22 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> DatabaseProvider<'this, TX> {
         }
     }
 
+    /// Return list of entries from a table that is moved to the static files of the segment.
+    ///
+    /// Entries are read through the static files, unless TAKE is true. Taking frozen entries is
+    /// rejected by [Self::ensure_unwind_above_static_files] before.
+    fn get_or_take_static_or_db<T: Table<Key = u64>, const TAKE: bool>(
+        &self,
+        segment: StaticFileSegment,
+        range: impl RangeBounds<u64>,
+    ) -> Result<Vec<KeyValue<T>>> {
+        if TAKE {
+            Ok(self.get_or_take::<T, TAKE>(range)?)
+        } else {
+            self.walk_static_or_db::<T>(segment, range)
+        }
+    }
+
     /// Get requested blocks transaction with signer
     fn get_take_block_transaction_range<const TAKE: bool>(
         &self,
@@ -426,7 +723,10 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> DatabaseProvider<'this, TX> {
 
         // Get transactions and senders
         let transactions = self
-            .get_or_take::<tables::Transactions, TAKE>(first_transaction..=last_transaction)?
+            .get_or_take_static_or_db::<tables::Transactions, TAKE>(
+                StaticFileSegment::Transactions,
+                first_transaction..=last_transaction,
+            )?
             .into_iter()
             .map(|(id, tx)| (id, tx.into()))
             .collect::<Vec<(u64, TransactionSigned)>>();
@@ -489,7 +789,10 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> DatabaseProvider<'this, TX> {
     ) -> Result<Vec<SealedBlockWithSenders>> {
         // For block we need Headers, Bodies, Uncles, withdrawals, Transactions, Signers
 
-        let block_headers = self.get_or_take::<tables::Headers, TAKE>(range.clone())?;
+        let block_headers = self.get_or_take_static_or_db::<tables::Headers, TAKE>(
+            StaticFileSegment::Headers,
+            range.clone(),
+        )?;
         if block_headers.is_empty() {
             return Ok(Vec::new())
         }
@@ -835,7 +1138,7 @@ impl<'this, TX: DbTx<'this>> HeaderProvider for DatabaseProvider<'this, TX> {
     }
 
     fn header_by_number(&self, num: BlockNumber) -> Result<Option<Header>> {
-        Ok(self.tx.get::<tables::Headers>(num)?)
+        self.get_static_or_db::<tables::Headers>(StaticFileSegment::Headers, num)
     }
 
     fn header_td(&self, block_hash: &BlockHash) -> Result<Option<U256>> {
@@ -857,11 +1160,11 @@ impl<'this, TX: DbTx<'this>> HeaderProvider for DatabaseProvider<'this, TX> {
     }
 
     fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> Result<Vec<Header>> {
-        let mut cursor = self.tx.cursor_read::<tables::Headers>()?;
-        cursor
-            .walk_range(range)?
-            .map(|result| result.map(|(_, header)| header).map_err(Into::into))
-            .collect::<Result<Vec<_>>>()
+        Ok(self
+            .walk_static_or_db::<tables::Headers>(StaticFileSegment::Headers, range)?
+            .into_iter()
+            .map(|(_, header)| header)
+            .collect())
     }
 
     fn sealed_headers_range(
@@ -869,8 +1172,9 @@ impl<'this, TX: DbTx<'this>> HeaderProvider for DatabaseProvider<'this, TX> {
         range: impl RangeBounds<BlockNumber>,
     ) -> Result<Vec<SealedHeader>> {
         let mut headers = vec![];
-        for entry in self.tx.cursor_read::<tables::Headers>()?.walk_range(range)? {
-            let (number, header) = entry?;
+        for (number, header) in
+            self.walk_static_or_db::<tables::Headers>(StaticFileSegment::Headers, range)?
+        {
             let hash = self
                 .block_hash(number)?
                 .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
@@ -997,7 +1301,7 @@ impl<'this, TX: DbTx<'this>> BlockReader for DatabaseProvider<'this, TX> {
 
         // Get the block body
         let body = self
-            .block_body_indices(block_number)?
+            .block_body_indices_or_pruned(block_number)?
             .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
         let tx_range = body.tx_num_range();
 
@@ -1030,11 +1334,11 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
     }
 
     fn transaction_by_id(&self, id: TxNumber) -> Result<Option<TransactionSigned>> {
-        Ok(self.tx.get::<tables::Transactions>(id)?.map(Into::into))
+        Ok(self.transaction_by_id_no_hash(id)?.map(Into::into))
     }
 
     fn transaction_by_id_no_hash(&self, id: TxNumber) -> Result<Option<TransactionSignedNoHash>> {
-        Ok(self.tx.get::<tables::Transactions>(id)?)
+        self.get_static_or_db::<tables::Transactions>(StaticFileSegment::Transactions, id)
     }
 
     fn transaction_by_hash(&self, hash: TxHash) -> Result<Option<TransactionSigned>> {
@@ -1101,17 +1405,17 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
         &self,
         id: BlockHashOrNumber,
     ) -> Result<Option<Vec<TransactionSigned>>> {
-        let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
         if let Some(block_number) = self.convert_hash_or_number(id)? {
-            if let Some(body) = self.block_body_indices(block_number)? {
+            if let Some(body) = self.block_body_indices_or_pruned(block_number)? {
                 let tx_range = body.tx_num_range();
                 return if tx_range.is_empty() {
                     Ok(Some(Vec::new()))
                 } else {
-                    let transactions = tx_cursor
-                        .walk_range(tx_range)?
-                        .map(|result| result.map(|(_, tx)| tx.into()))
-                        .collect::<std::result::Result<Vec<_>, _>>()?;
+                    let transactions = self
+                        .transactions_by_tx_range(tx_range)?
+                        .into_iter()
+                        .map(Into::into)
+                        .collect();
                     Ok(Some(transactions))
                 }
             }
@@ -1125,7 +1429,6 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
     ) -> Result<Vec<Vec<TransactionSigned>>> {
         let mut results = Vec::new();
         let mut body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
-        let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
         for entry in body_cursor.walk_range(range)? {
             let (_, body) = entry?;
             let tx_num_range = body.tx_num_range();
@@ -1133,10 +1436,10 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
                 results.push(Vec::new());
             } else {
                 results.push(
-                    tx_cursor
-                        .walk_range(tx_num_range)?
-                        .map(|result| result.map(|(_, tx)| tx.into()))
-                        .collect::<std::result::Result<Vec<_>, _>>()?,
+                    self.transactions_by_tx_range(tx_num_range)?
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
                 );
             }
         }
@@ -1148,11 +1451,10 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
         range: impl RangeBounds<TxNumber>,
     ) -> Result<Vec<TransactionSignedNoHash>> {
         Ok(self
-            .tx
-            .cursor_read::<tables::Transactions>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|tx| tx.1))
-            .collect::<std::result::Result<Vec<_>, _>>()?)
+            .walk_static_or_db::<tables::Transactions>(StaticFileSegment::Transactions, range)?
+            .into_iter()
+            .map(|(_, tx)| tx)
+            .collect())
     }
 
     fn senders_by_tx_range(&self, range: impl RangeBounds<TxNumber>) -> Result<Vec<Address>> {
@@ -1171,7 +1473,7 @@ impl<'this, TX: DbTx<'this>> TransactionsProvider for DatabaseProvider<'this, TX
 
 impl<'this, TX: DbTx<'this>> ReceiptProvider for DatabaseProvider<'this, TX> {
     fn receipt(&self, id: TxNumber) -> Result<Option<Receipt>> {
-        Ok(self.tx.get::<tables::Receipts>(id)?)
+        self.get_static_or_db::<tables::Receipts>(StaticFileSegment::Receipts, id)
     }
 
     fn receipt_by_hash(&self, hash: TxHash) -> Result<Option<Receipt>> {
@@ -1184,16 +1486,19 @@ impl<'this, TX: DbTx<'this>> ReceiptProvider for DatabaseProvider<'this, TX> {
 
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>> {
         if let Some(number) = self.convert_hash_or_number(block)? {
-            if let Some(body) = self.block_body_indices(number)? {
+            if let Some(body) = self.block_body_indices_or_pruned(number)? {
                 let tx_range = body.tx_num_range();
                 return if tx_range.is_empty() {
                     Ok(Some(Vec::new()))
                 } else {
-                    let mut receipts_cursor = self.tx.cursor_read::<tables::Receipts>()?;
-                    let receipts = receipts_cursor
-                        .walk_range(tx_range)?
-                        .map(|result| result.map(|(_, receipt)| receipt))
-                        .collect::<std::result::Result<Vec<_>, _>>()?;
+                    let receipts = self
+                        .walk_static_or_db::<tables::Receipts>(
+                            StaticFileSegment::Receipts,
+                            tx_range,
+                        )?
+                        .into_iter()
+                        .map(|(_, receipt)| receipt)
+                        .collect();
                     Ok(Some(receipts))
                 }
             }
@@ -1404,6 +1709,249 @@ impl<'this, TX: DbTx<'this>> StorageReader for DatabaseProvider<'this, TX> {
     }
 }
 
+impl<'this, TX: DbTx<'this>> SnapStateReader for DatabaseProvider<'this, TX> {
+    fn account_range(
+        &self,
+        root: H256,
+        start: H256,
+        limit: H256,
+        max_accounts: usize,
+    ) -> Result<Option<AccountRangeWithProof>> {
+        if self.latest_state_root()? != root {
+            return Ok(None)
+        }
+
+        let mut accounts = Vec::new();
+        for entry in self.tx.cursor_read::<tables::HashedAccount>()?.walk_range(start..)? {
+            let (hashed_address, account) = entry?;
+            let storage_root = StorageRoot::new_hashed(&self.tx, hashed_address)
+                .root()
+                .map_err(Into::<DatabaseError>::into)?;
+            accounts.push((hashed_address, account, storage_root));
+            if hashed_address >= limit || accounts.len() >= max_accounts {
+                break
+            }
+        }
+
+        // prove the origin and the last account of the range, an empty range only needs the
+        // origin to prove that there are no accounts after it
+        let mut targets = vec![Nibbles::unpack(start)];
+        targets
+            .extend(accounts.last().map(|(hashed_address, _, _)| Nibbles::unpack(hashed_address)));
+        let (_, proof) = Proof::new(&self.tx)
+            .account_multiproof(targets)
+            .map_err(Into::<DatabaseError>::into)?;
+
+        Ok(Some(AccountRangeWithProof { accounts, proof: proof.into_values().collect() }))
+    }
+
+    fn storage_ranges(
+        &self,
+        root: H256,
+        accounts: &[H256],
+        start: H256,
+        limit: H256,
+        max_slots: usize,
+    ) -> Result<Option<StorageRangesWithProof>> {
+        if self.latest_state_root()? != root {
+            return Ok(None)
+        }
+
+        let mut cursor = self.tx.cursor_dup_read::<tables::HashedStorage>()?;
+        let mut ranges = StorageRangesWithProof::default();
+        let mut total_slots = 0;
+        for (idx, hashed_address) in accounts.iter().enumerate() {
+            let origin = if idx == 0 { start } else { H256::zero() };
+            let limit = if idx == accounts.len() - 1 { limit } else { H256::repeat_byte(0xff) };
+
+            let mut slots = Vec::new();
+            let mut aborted = false;
+            for entry in cursor.walk_dup(Some(*hashed_address), Some(origin))? {
+                if total_slots >= max_slots {
+                    aborted = true;
+                    break
+                }
+                let (_, StorageEntry { key, value }) = entry?;
+                slots.push((key, value));
+                total_slots += 1;
+                if key > limit {
+                    aborted = true;
+                    break
+                }
+            }
+
+            // an incomplete range or one that doesn't start at the beginning of the storage trie
+            // must be proven, this is only allowed for the last served account
+            if origin != H256::zero() || aborted {
+                let mut targets = vec![Nibbles::unpack(origin)];
+                targets.extend(slots.last().map(|(key, _)| Nibbles::unpack(key)));
+                let (_, proof) = Proof::new(&self.tx)
+                    .storage_multiproof(*hashed_address, targets)
+                    .map_err(Into::<DatabaseError>::into)?;
+                ranges.proof = proof.into_values().collect();
+                ranges.slots.push(slots);
+                break
+            }
+
+            ranges.slots.push(slots);
+            if total_slots >= max_slots {
+                break
+            }
+        }
+
+        Ok(Some(ranges))
+    }
+
+    fn bytecodes(&self, hashes: &[H256], max_bytes: usize) -> Result<Vec<Bytes>> {
+        let mut codes = Vec::new();
+        let mut total_bytes = 0;
+        for hash in hashes {
+            if total_bytes >= max_bytes {
+                break
+            }
+            if let Some(code) = self.tx.get::<tables::Bytecodes>(*hash)? {
+                let code = Bytes::from(code.original_bytes());
+                total_bytes += code.len();
+                codes.push(code);
+            }
+        }
+        Ok(codes)
+    }
+
+    fn account_trie_nodes(&self, root: H256, paths: Vec<Nibbles>) -> Result<Option<Vec<Bytes>>> {
+        let (state_root, mut nodes) = Proof::new(&self.tx)
+            .account_multiproof(paths.clone())
+            .map_err(Into::<DatabaseError>::into)?;
+        if state_root != root {
+            return Ok(None)
+        }
+        Ok(Some(paths.iter().map_while(|path| nodes.remove(path)).collect()))
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        root: H256,
+        hashed_address: H256,
+        paths: Vec<Nibbles>,
+    ) -> Result<Option<Vec<Bytes>>> {
+        if self.latest_state_root()? != root {
+            return Ok(None)
+        }
+        let (_, mut nodes) = Proof::new(&self.tx)
+            .storage_multiproof(hashed_address, paths.clone())
+            .map_err(Into::<DatabaseError>::into)?;
+        Ok(Some(paths.iter().map_while(|path| nodes.remove(path)).collect()))
+    }
+}
+
+impl<'this, TX: DbTx<'this>> HistoryReader for DatabaseProvider<'this, TX> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        self.history_blocks::<tables::AccountHistory, _>(address, range, limit, order)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        if limit == 0 {
+            return Ok(Vec::new())
+        }
+        let mut cursor = self.tx.cursor_read::<tables::StorageHistory>()?;
+        let mut blocks = BTreeSet::new();
+        let walk_start = history_walk_start(&range, order);
+
+        // All shards of all storage slots of the address are adjacent in the table. The shards of
+        // a slot are keyed by the highest block number they contain and the last shard of a slot
+        // by `u64::MAX`, so seeking the first block of the walk lands in the shard containing it.
+        let mut slot_item =
+            cursor.seek(StorageShardedKey::new(address, H256::zero(), walk_start))?;
+        while let Some((storage_sharded_key, _)) = &slot_item {
+            if storage_sharded_key.address != address {
+                break
+            }
+            let slot = storage_sharded_key.sharded_key.key;
+
+            // The seek lands in the first shard of the slot if the slot follows the sought one.
+            let mut item = if storage_sharded_key.sharded_key.highest_block_number < walk_start {
+                cursor.seek(StorageShardedKey::new(address, slot, walk_start))?
+            } else {
+                slot_item
+            };
+            while let Some((storage_sharded_key, list)) = item {
+                if storage_sharded_key.address != address ||
+                    storage_sharded_key.sharded_key.key != slot ||
+                    !collect_history_shard(&mut blocks, &list, &range, limit, order)
+                {
+                    break
+                }
+                item = match order {
+                    HistoryOrder::Ascending => cursor.next()?,
+                    HistoryOrder::Descending => cursor.prev()?,
+                };
+            }
+
+            let Some(next_slot) = U256::from_be_bytes(slot.0).checked_add(U256::from(1)) else {
+                break
+            };
+            slot_item = cursor.seek(StorageShardedKey::new(
+                address,
+                H256::from(next_slot.to_be_bytes()),
+                walk_start,
+            ))?;
+        }
+
+        Ok(blocks.into_iter().collect())
+    }
+
+    fn log_index_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>> {
+        // The index is only maintained once the stage has been run.
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogHistory)? else {
+            return Ok(None)
+        };
+        let start = self
+            .get_prune_checkpoint(PrunePart::LogHistory)?
+            .map(|checkpoint| checkpoint.block_number + 1)
+            .unwrap_or_default();
+
+        Ok((start <= checkpoint.block_number).then_some(start..=checkpoint.block_number))
+    }
+
+    fn log_address_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        self.history_blocks::<tables::LogAddressHistory, _>(
+            address,
+            range,
+            usize::MAX,
+            HistoryOrder::Ascending,
+        )
+    }
+
+    fn log_topic_history_blocks(
+        &self,
+        topic: H256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        self.history_blocks::<tables::LogTopicHistory, _>(
+            topic,
+            range,
+            usize::MAX,
+            HistoryOrder::Ascending,
+        )
+    }
+}
+
 impl<'this, TX: DbTxMut<'this> + DbTx<'this>> HashingWriter for DatabaseProvider<'this, TX> {
     fn insert_hashes(
         &self,
@@ -1763,6 +2311,68 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> HistoryWriter for DatabaseProvider
 
         Ok(changesets)
     }
+
+    fn unwind_log_history_indices(&self, range: RangeInclusive<BlockNumber>) -> Result<usize> {
+        let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+        let keys = addresses.len() + topics.len();
+
+        // Block numbers are collected in ascending order, so the first one is the lowest block
+        // number where we need to unwind the key.
+        let mut cursor = self.tx.cursor_write::<tables::LogAddressHistory>()?;
+        for (address, blocks) in addresses {
+            let partial_shard = unwind_history_shards::<_, tables::LogAddressHistory, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                blocks[0],
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogTopicHistory>()?;
+        for (topic, blocks) in topics {
+            let partial_shard = unwind_history_shards::<_, tables::LogTopicHistory, _>(
+                &mut cursor,
+                ShardedKey::last(topic),
+                blocks[0],
+                |sharded_key| sharded_key.key == topic,
+            )?;
+
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(topic),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(keys)
+    }
+
+    fn insert_log_history_index(
+        &self,
+        address_transitions: BTreeMap<Address, Vec<u64>>,
+        topic_transitions: BTreeMap<H256, Vec<u64>>,
+    ) -> Result<()> {
+        self.append_history_index::<_, tables::LogAddressHistory>(
+            address_transitions,
+            ShardedKey::new,
+        )?;
+        self.append_history_index::<_, tables::LogTopicHistory>(topic_transitions, ShardedKey::new)
+    }
+
+    fn calculate_log_history_indices(&self, range: RangeInclusive<BlockNumber>) -> Result<()> {
+        let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+        self.insert_log_history_index(addresses, topics)
+    }
 }
 
 impl<'this, TX: DbTxMut<'this> + DbTx<'this>> BlockExecutionWriter for DatabaseProvider<'this, TX> {
@@ -1772,6 +2382,8 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> BlockExecutionWriter for DatabaseP
         range: RangeInclusive<BlockNumber>,
     ) -> Result<Vec<(SealedBlockWithSenders, PostState)>> {
         if TAKE {
+            self.ensure_unwind_above_static_files(range.start().saturating_sub(1))?;
+
             let storage_range = BlockNumberAddress::range(range.clone());
 
             // Initialize prefix sets.
@@ -1807,6 +2419,19 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> BlockExecutionWriter for DatabaseP
             // Unwind storage history indices.
             self.unwind_storage_history_indices(storage_range)?;
 
+            // Unwind log history indices if the optional index is maintained. Must happen before
+            // the receipts are removed.
+            if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogHistory)? {
+                if checkpoint.block_number >= *range.start() {
+                    let unwind_range = *range.start()..=checkpoint.block_number.min(*range.end());
+                    self.unwind_log_history_indices(unwind_range)?;
+                    self.save_stage_checkpoint(
+                        StageId::IndexLogHistory,
+                        StageCheckpoint::new(range.start().saturating_sub(1)),
+                    )?;
+                }
+            }
+
             // Calculate the reverted merkle root.
             // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
             // are pre-loaded.
@@ -1901,7 +2526,12 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> BlockWriter for DatabaseProvider<'
             .tx
             .cursor_read::<tables::Transactions>()?
             .last()?
-            .map(|(n, _)| n + 1)
+            .map(|(n, _)| n)
+            .or_else(|| {
+                // all transactions may have been moved to static files
+                self.static_files.as_ref()?.highest_key(StaticFileSegment::Transactions)
+            })
+            .map(|n| n + 1)
             .unwrap_or_default();
         let first_tx_num = next_tx_num;
 
@@ -1977,6 +2607,19 @@ impl<'this, TX: DbTxMut<'this> + DbTx<'this>> BlockWriter for DatabaseProvider<'
 
         self.calculate_history_indices(first_number..=last_block_number)?;
 
+        // Extend the optional log index, but only if it is up to date with the parent block.
+        // Otherwise the pipeline stage is responsible for catching up.
+        if self
+            .get_stage_checkpoint(StageId::IndexLogHistory)?
+            .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number)
+        {
+            self.calculate_log_history_indices(first_number..=last_block_number)?;
+            self.save_stage_checkpoint(
+                StageId::IndexLogHistory,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
+
         // Update pipeline progress
         self.update_pipeline_stages(new_tip_number, false)?;
 
@@ -1,9 +1,10 @@
 use crate::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider,
-    PostStateDataProvider, ProviderError, PruneCheckpointReader, ReceiptProvider,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    AccountRangeWithProof, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
+    BlockReaderIdExt, BlockchainTreePendingStateProvider, CanonChainTracker,
+    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+    EvmEnvProvider, HeaderProvider, HistoryOrder, HistoryReader, PostStateDataProvider,
+    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, SnapStateReader,
+    StageCheckpointReader, StateProviderBox, StateProviderFactory, StorageRangesWithProof,
     TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, models::StoredBlockBodyIndices};
@@ -14,10 +15,11 @@ use reth_interfaces::{
 };
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
+    trie::Nibbles,
     Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, PruneCheckpoint, PrunePart,
-    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, H256, U256,
+    BlockNumberOrTag, BlockWithSenders, Bytes, ChainInfo, ChainSpec, Header, PruneCheckpoint,
+    PrunePart, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, H256, U256,
 };
 use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
 pub use state::{
@@ -26,7 +28,7 @@ pub use state::{
 };
 use std::{
     collections::{BTreeMap, HashSet},
-    ops::RangeBounds,
+    ops::{RangeBounds, RangeInclusive},
     sync::Arc,
     time::Instant,
 };
@@ -36,6 +38,7 @@ mod chain_info;
 mod database;
 mod post_state_provider;
 mod state;
+mod static_file;
 use crate::{providers::chain_info::ChainInfoTracker, traits::BlockSource};
 pub use database::*;
 pub use post_state_provider::PostStateProvider;
@@ -43,6 +46,9 @@ use reth_db::models::AccountBeforeTx;
 use reth_interfaces::blockchain_tree::{
     error::InsertBlockError, CanonicalOutcome, InsertPayloadOk,
 };
+pub use static_file::{
+    SegmentFile, SegmentFileWriter, StaticFileProducer, StaticFileProvider, StaticFileSegment,
+};
 
 /// The main type for interacting with the blockchain.
 ///
@@ -438,6 +444,50 @@ where
     }
 }
 
+impl<DB, Tree> SnapStateReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn account_range(
+        &self,
+        root: H256,
+        start: H256,
+        limit: H256,
+        max_accounts: usize,
+    ) -> Result<Option<AccountRangeWithProof>> {
+        self.database.provider()?.account_range(root, start, limit, max_accounts)
+    }
+
+    fn storage_ranges(
+        &self,
+        root: H256,
+        accounts: &[H256],
+        start: H256,
+        limit: H256,
+        max_slots: usize,
+    ) -> Result<Option<StorageRangesWithProof>> {
+        self.database.provider()?.storage_ranges(root, accounts, start, limit, max_slots)
+    }
+
+    fn bytecodes(&self, hashes: &[H256], max_bytes: usize) -> Result<Vec<Bytes>> {
+        self.database.provider()?.bytecodes(hashes, max_bytes)
+    }
+
+    fn account_trie_nodes(&self, root: H256, paths: Vec<Nibbles>) -> Result<Option<Vec<Bytes>>> {
+        self.database.provider()?.account_trie_nodes(root, paths)
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        root: H256,
+        hashed_address: H256,
+        paths: Vec<Nibbles>,
+    ) -> Result<Option<Vec<Bytes>>> {
+        self.database.provider()?.storage_trie_nodes(root, hashed_address, paths)
+    }
+}
+
 impl<DB, Tree> PruneCheckpointReader for BlockchainProvider<DB, Tree>
 where
     DB: Database,
@@ -837,3 +887,49 @@ where
         self.database.provider()?.account_block_changeset(block_number)
     }
 }
+
+impl<DB, Tree> HistoryReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        self.database.provider()?.account_history_blocks(address, range, limit, order)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        self.database.provider()?.storage_history_blocks(address, range, limit, order)
+    }
+
+    fn log_index_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.log_index_range()
+    }
+
+    fn log_address_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        self.database.provider()?.log_address_history_blocks(address, range)
+    }
+
+    fn log_topic_history_blocks(
+        &self,
+        topic: H256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        self.database.provider()?.log_topic_history_blocks(topic, range)
+    }
+}
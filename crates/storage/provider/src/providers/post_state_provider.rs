@@ -3,7 +3,7 @@ use crate::{
     StateRootProvider,
 };
 use reth_interfaces::{provider::ProviderError, Result};
-use reth_primitives::{Account, Address, BlockNumber, Bytecode, Bytes, H256, U256};
+use reth_primitives::{trie::AccountProof, Account, Address, BlockNumber, Bytecode, H256, U256};
 
 /// A state provider that either resolves to data in a wrapped [`crate::PostState`], or an
 /// underlying state provider.
@@ -87,11 +87,7 @@ impl<SP: StateProvider, PSDP: PostStateDataProvider> StateProvider for PostState
         self.state_provider.bytecode_by_hash(code_hash)
     }
 
-    fn proof(
-        &self,
-        _address: Address,
-        _keys: &[H256],
-    ) -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
+    fn proof(&self, _address: Address, _keys: &[H256]) -> Result<AccountProof> {
         Err(ProviderError::StateRootNotAvailableForHistoricalBlock.into())
     }
 }
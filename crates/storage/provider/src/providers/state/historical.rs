@@ -12,7 +12,11 @@ use reth_db::{
 };
 use reth_interfaces::Result;
 use reth_primitives::{
-    Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, H256,
+    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, H256,
+};
+use reth_trie::{
+    hashed_cursor::{HashedPostState, HashedPostStateCursorFactory},
+    Proof,
 };
 use std::marker::PhantomData;
 
@@ -239,12 +243,25 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for HistoricalStateProviderRef<'a, 'b,
     }
 
     /// Get account and storage proofs.
-    fn proof(
-        &self,
-        _address: Address,
-        _keys: &[H256],
-    ) -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock.into())
+    ///
+    /// The proofs are generated against the state at the start of the block by reverting the
+    /// changesets of this and all subsequent blocks on top of the latest hashed state and trie.
+    fn proof(&self, address: Address, keys: &[H256]) -> Result<AccountProof> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
+            !self.lowest_available_blocks.is_storage_history_available(self.block_number)
+        {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number).into())
+        }
+
+        let revert_state = HashedPostState::from_reverts(self.tx, self.block_number)?;
+        let (account_prefix_set, storage_prefix_sets) = revert_state.construct_prefix_sets_mut();
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(self.tx, &revert_state);
+        Proof::new(self.tx)
+            .with_hashed_cursor_factory(&hashed_cursor_factory)
+            .with_changed_account_prefixes(account_prefix_set)
+            .with_changed_storage_prefixes(storage_prefix_sets)
+            .account_proof(address, keys)
+            .map_err(|err| reth_interfaces::Error::Database(err.into()))
     }
 }
 
@@ -7,10 +7,11 @@ use reth_db::{
     tables,
     transaction::DbTx,
 };
-use reth_interfaces::{provider::ProviderError, Result};
+use reth_interfaces::Result;
 use reth_primitives::{
-    keccak256, Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, H256,
+    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, H256,
 };
+use reth_trie::Proof;
 use std::marker::PhantomData;
 
 /// State provider over latest state that takes tx reference.
@@ -80,21 +81,10 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for LatestStateProviderRef<'a, 'b, TX>
         self.db.get::<tables::Bytecodes>(code_hash).map_err(Into::into)
     }
 
-    fn proof(
-        &self,
-        address: Address,
-        _keys: &[H256],
-    ) -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
-        let _hashed_address = keccak256(address);
-        let _root = self
-            .db
-            .cursor_read::<tables::Headers>()?
-            .last()?
-            .ok_or_else(|| ProviderError::HeaderNotFound(0.into()))?
-            .1
-            .state_root;
-
-        unimplemented!()
+    fn proof(&self, address: Address, keys: &[H256]) -> Result<AccountProof> {
+        Proof::new(self.db)
+            .account_proof(address, keys)
+            .map_err(|err| reth_interfaces::Error::Database(err.into()))
     }
 }
 
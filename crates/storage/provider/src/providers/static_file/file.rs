@@ -0,0 +1,408 @@
+//! Flat file format of a single static file segment.
+//!
+//! A segment file consists of two files:
+//!
+//! - the data file (e.g. `headers_0_499999`) containing the concatenated values in the same
+//!   compressed form they are stored with in the database, and
+//! - the offsets file (e.g. `headers_0_499999.off`) starting with the first key and the number of
+//!   entries of the segment, followed by `len + 1` offsets into the data file. The value of the
+//!   `n`-th key is in the data file between the offsets `n` and `n + 1`.
+//!
+//! All integers are encoded as little-endian `u64`. A value with a length of zero marks a key
+//! without a value, e.g. a receipt that was pruned before the segment was frozen.
+//!
+//! Both files are written to temporary files first and only moved into place once they are
+//! complete, the offsets file last. A segment is only picked up if its offsets file exists.
+//!
+//! Pruning a segment file deletes the data file and truncates the offsets file to its header, so
+//! that the key range of the segment is still known and all of its keys are read without a value.
+
+use super::StaticFileSegment;
+use reth_db::table::{Compress, Decompress};
+use reth_interfaces::{provider::ProviderError, Result};
+use reth_primitives::BlockNumber;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Seek, SeekFrom, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// Extension of the offsets file.
+const OFFSETS_EXTENSION: &str = "off";
+
+/// Extension of files that are still being written.
+const TMP_EXTENSION: &str = "tmp";
+
+/// Size of the offsets file header: first key and number of entries.
+const OFFSETS_HEADER_SIZE: u64 = 16;
+
+/// Converts an IO error into a [ProviderError::StaticFile].
+pub(crate) fn static_file_error(err: impl std::fmt::Display) -> reth_interfaces::Error {
+    ProviderError::StaticFile(err.to_string()).into()
+}
+
+/// Returns the path of the offsets file that belongs to the given data file.
+fn offsets_path(data_path: &Path) -> PathBuf {
+    data_path.with_extension(OFFSETS_EXTENSION)
+}
+
+/// Returns the path of the temporary file that is renamed to `path` once written.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(TMP_EXTENSION);
+    path.with_file_name(name)
+}
+
+/// Reads exactly `buf.len()` bytes at the given offset without moving a shared cursor.
+#[cfg(unix)]
+fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+/// Reads exactly `buf.len()` bytes at the given offset without moving a shared cursor.
+#[cfg(windows)]
+fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
+    while !buf.is_empty() {
+        match std::os::windows::fs::FileExt::seek_read(file, buf, offset) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => {
+                buf = &mut buf[n..];
+                offset += n as u64;
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+/// An immutable static file of a segment covering a range of blocks.
+#[derive(Debug)]
+pub struct SegmentFile {
+    /// The segment of the file.
+    segment: StaticFileSegment,
+    /// The range of blocks covered by this file.
+    block_range: RangeInclusive<BlockNumber>,
+    /// The key of the first entry.
+    first_key: u64,
+    /// The number of entries.
+    len: u64,
+    /// The data file, `None` if the file was pruned.
+    data: Option<File>,
+    /// The offsets file.
+    offsets: File,
+}
+
+impl SegmentFile {
+    /// Opens the segment file for the given block range in the directory.
+    pub fn open(
+        dir: &Path,
+        segment: StaticFileSegment,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> Result<Self> {
+        let data_path = dir.join(segment.file_name(&block_range));
+        let offsets = File::open(offsets_path(&data_path)).map_err(static_file_error)?;
+
+        let mut header = [0u8; OFFSETS_HEADER_SIZE as usize];
+        read_exact_at(&offsets, &mut header, 0).map_err(static_file_error)?;
+        let first_key = u64::from_le_bytes(header[..8].try_into().expect("8 bytes"));
+        let len = u64::from_le_bytes(header[8..].try_into().expect("8 bytes"));
+
+        let offsets_len = offsets.metadata().map_err(static_file_error)?.len();
+        if offsets_len == OFFSETS_HEADER_SIZE {
+            // the file was pruned, the data file may be left over if the removal was interrupted
+            remove_if_exists(&data_path)?;
+            return Ok(Self { segment, block_range, first_key, len, data: None, offsets })
+        }
+
+        let expected_len = OFFSETS_HEADER_SIZE + (len + 1) * 8;
+        if offsets_len != expected_len {
+            return Err(static_file_error(format!(
+                "{}: offsets file has {offsets_len} bytes, expected {expected_len}",
+                data_path.display()
+            )))
+        }
+
+        let data = File::open(&data_path).map_err(static_file_error)?;
+        Ok(Self { segment, block_range, first_key, len, data: Some(data), offsets })
+    }
+
+    /// Deletes the data of the segment file for the given block range in the directory and
+    /// returns the pruned file.
+    ///
+    /// The offsets file is atomically replaced with its header before the data file is removed, so
+    /// an interrupted prune is completed the next time the file is opened.
+    pub fn prune(
+        dir: &Path,
+        segment: StaticFileSegment,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> Result<Self> {
+        let file = Self::open(dir, segment, block_range)?;
+        if file.is_pruned() {
+            return Ok(file)
+        }
+
+        let data_path = dir.join(segment.file_name(file.block_range()));
+        let offsets_path = offsets_path(&data_path);
+        let mut offsets = File::create(tmp_path(&offsets_path)).map_err(static_file_error)?;
+        offsets.write_all(&file.first_key.to_le_bytes()).map_err(static_file_error)?;
+        offsets.write_all(&file.len.to_le_bytes()).map_err(static_file_error)?;
+        offsets.sync_all().map_err(static_file_error)?;
+        fs::rename(tmp_path(&offsets_path), &offsets_path).map_err(static_file_error)?;
+        remove_if_exists(&data_path)?;
+
+        Self::open(dir, segment, file.block_range)
+    }
+
+    /// Returns `true` if the data of the file was pruned.
+    pub fn is_pruned(&self) -> bool {
+        self.data.is_none()
+    }
+
+    /// The segment of the file.
+    pub fn segment(&self) -> StaticFileSegment {
+        self.segment
+    }
+
+    /// The range of blocks covered by this file.
+    pub fn block_range(&self) -> &RangeInclusive<BlockNumber> {
+        &self.block_range
+    }
+
+    /// The range of keys stored in this file, `None` if the file is empty.
+    pub fn key_range(&self) -> Option<RangeInclusive<u64>> {
+        (self.len > 0).then(|| self.first_key..=self.first_key + self.len - 1)
+    }
+
+    /// Returns the raw value of the key, `None` if the key is not part of the file, has no value
+    /// or the file was pruned.
+    pub fn get_raw(&self, key: u64) -> Result<Option<Vec<u8>>> {
+        let Some(data) = &self.data else { return Ok(None) };
+        if key < self.first_key || key - self.first_key >= self.len {
+            return Ok(None)
+        }
+
+        let mut offsets = [0u8; 16];
+        read_exact_at(
+            &self.offsets,
+            &mut offsets,
+            OFFSETS_HEADER_SIZE + (key - self.first_key) * 8,
+        )
+        .map_err(static_file_error)?;
+        let start = u64::from_le_bytes(offsets[..8].try_into().expect("8 bytes"));
+        let end = u64::from_le_bytes(offsets[8..].try_into().expect("8 bytes"));
+        if end <= start {
+            return Ok(None)
+        }
+
+        let mut value = vec![0u8; (end - start) as usize];
+        read_exact_at(data, &mut value, start).map_err(static_file_error)?;
+        Ok(Some(value))
+    }
+
+    /// Returns the decompressed value of the key.
+    pub fn get<V: Decompress>(&self, key: u64) -> Result<Option<V>> {
+        Ok(self.get_raw(key)?.map(V::decompress).transpose()?)
+    }
+}
+
+/// Writer of a new [SegmentFile].
+///
+/// Keys have to be appended in ascending order without gaps.
+#[derive(Debug)]
+pub struct SegmentFileWriter {
+    /// Path of the data file.
+    data_path: PathBuf,
+    /// Temporary data file.
+    data: BufWriter<File>,
+    /// Temporary offsets file.
+    offsets: BufWriter<File>,
+    /// The key of the first entry.
+    first_key: u64,
+    /// The key expected to be appended next.
+    next_key: u64,
+    /// Number of bytes written to the data file.
+    written: u64,
+}
+
+impl SegmentFileWriter {
+    /// Creates a writer for the segment file of the given block range in the directory. The first
+    /// appended entry has to have the key `first_key`.
+    pub fn new(
+        dir: &Path,
+        segment: StaticFileSegment,
+        block_range: &RangeInclusive<BlockNumber>,
+        first_key: u64,
+    ) -> Result<Self> {
+        let data_path = dir.join(segment.file_name(block_range));
+        let data = File::create(tmp_path(&data_path)).map_err(static_file_error)?;
+        let mut offsets =
+            File::create(tmp_path(&offsets_path(&data_path))).map_err(static_file_error)?;
+        // header is written on finish
+        offsets.write_all(&[0u8; OFFSETS_HEADER_SIZE as usize]).map_err(static_file_error)?;
+        let mut offsets = BufWriter::new(offsets);
+        offsets.write_all(&0u64.to_le_bytes()).map_err(static_file_error)?;
+
+        Ok(Self {
+            data_path,
+            data: BufWriter::new(data),
+            offsets,
+            first_key,
+            next_key: first_key,
+            written: 0,
+        })
+    }
+
+    /// Appends the value of the next key.
+    pub fn append<V: Compress>(&mut self, key: u64, value: V) -> Result<()> {
+        self.ensure_next_key(key)?;
+        match value.uncompressable_ref() {
+            Some(value) => self.write_raw(value),
+            None => self.write_raw(value.compress().as_ref()),
+        }
+    }
+
+    /// Appends an entry without a value for the next key.
+    pub fn append_empty(&mut self, key: u64) -> Result<()> {
+        self.ensure_next_key(key)?;
+        self.write_raw(&[])
+    }
+
+    fn ensure_next_key(&self, key: u64) -> Result<()> {
+        if key != self.next_key {
+            return Err(static_file_error(format!(
+                "{}: expected key {}, got {key}",
+                self.data_path.display(),
+                self.next_key
+            )))
+        }
+        Ok(())
+    }
+
+    fn write_raw(&mut self, value: &[u8]) -> Result<()> {
+        self.data.write_all(value).map_err(static_file_error)?;
+        self.written += value.len() as u64;
+        self.offsets.write_all(&self.written.to_le_bytes()).map_err(static_file_error)?;
+        self.next_key += 1;
+        Ok(())
+    }
+
+    /// Writes the header, syncs both files to disk and moves them into place.
+    pub fn finish(self) -> Result<()> {
+        let Self { data_path, data, offsets, first_key, next_key, .. } = self;
+
+        let data = data.into_inner().map_err(|err| static_file_error(err.error()))?;
+        data.sync_all().map_err(static_file_error)?;
+
+        let mut offsets = offsets.into_inner().map_err(|err| static_file_error(err.error()))?;
+        offsets.seek(SeekFrom::Start(0)).map_err(static_file_error)?;
+        offsets.write_all(&first_key.to_le_bytes()).map_err(static_file_error)?;
+        offsets.write_all(&(next_key - first_key).to_le_bytes()).map_err(static_file_error)?;
+        offsets.sync_all().map_err(static_file_error)?;
+
+        let offsets_path = offsets_path(&data_path);
+        fs::rename(tmp_path(&data_path), &data_path).map_err(static_file_error)?;
+        fs::rename(tmp_path(&offsets_path), &offsets_path).map_err(static_file_error)?;
+        Ok(())
+    }
+}
+
+/// Removes the file, if it exists.
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(static_file_error(err)),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the segments and block ranges of all complete segment files in the directory.
+pub(crate) fn list_segment_files(
+    dir: &Path,
+) -> Result<Vec<(StaticFileSegment, RangeInclusive<BlockNumber>)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(static_file_error)? {
+        let path = entry.map_err(static_file_error)?.path();
+        if path.extension().map_or(true, |ext| ext != OFFSETS_EXTENSION) {
+            continue
+        }
+        if let Some(parsed) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(StaticFileSegment::parse_file_name)
+        {
+            files.push(parsed);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+
+    #[test]
+    fn write_and_read_segment_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let segment = StaticFileSegment::Headers;
+
+        let headers = (10..15u64)
+            .map(|number| Header { number, gas_limit: number * 1000, ..Default::default() })
+            .collect::<Vec<_>>();
+
+        let mut writer = SegmentFileWriter::new(dir.path(), segment, &(10..=15), 10).unwrap();
+        for header in &headers {
+            writer.append(header.number, header.clone()).unwrap();
+        }
+        // keys have to be contiguous
+        assert!(writer.append(17, Header::default()).is_err());
+        writer.append_empty(15).unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(list_segment_files(dir.path()).unwrap(), vec![(segment, 10..=15)]);
+
+        let file = SegmentFile::open(dir.path(), segment, 10..=15).unwrap();
+        assert_eq!(file.key_range(), Some(10..=15));
+        for header in &headers {
+            assert_eq!(file.get::<Header>(header.number).unwrap().as_ref(), Some(header));
+        }
+        assert_eq!(file.get::<Header>(15).unwrap(), None);
+        assert_eq!(file.get::<Header>(9).unwrap(), None);
+        assert_eq!(file.get::<Header>(16).unwrap(), None);
+    }
+
+    #[test]
+    fn prune_segment_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let segment = StaticFileSegment::Headers;
+        let data_path = dir.path().join(segment.file_name(&(0..=9)));
+
+        let mut writer = SegmentFileWriter::new(dir.path(), segment, &(0..=9), 0).unwrap();
+        for number in 0..10 {
+            writer.append(number, Header { number, ..Default::default() }).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let file = SegmentFile::prune(dir.path(), segment, 0..=9).unwrap();
+        assert!(file.is_pruned());
+        assert!(!data_path.exists());
+        assert_eq!(fs::metadata(offsets_path(&data_path)).unwrap().len(), OFFSETS_HEADER_SIZE);
+
+        // the key range is kept, but all keys are read without a value
+        let file = SegmentFile::open(dir.path(), segment, 0..=9).unwrap();
+        assert!(file.is_pruned());
+        assert_eq!(file.key_range(), Some(0..=9));
+        assert_eq!(file.get::<Header>(5).unwrap(), None);
+        assert_eq!(list_segment_files(dir.path()).unwrap(), vec![(segment, 0..=9)]);
+
+        // a data file left over by an interrupted prune is removed when the file is opened
+        File::create(&data_path).unwrap();
+        assert!(SegmentFile::open(dir.path(), segment, 0..=9).unwrap().is_pruned());
+        assert!(!data_path.exists());
+
+        // pruning is idempotent
+        assert!(SegmentFile::prune(dir.path(), segment, 0..=9).unwrap().is_pruned());
+    }
+}
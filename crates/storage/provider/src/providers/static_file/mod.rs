@@ -0,0 +1,212 @@
+//! Immutable static file storage of finalized headers, transactions and receipts.
+//!
+//! Finalized block ranges of the `Headers`, `Transactions` and `Receipts` tables are moved out of
+//! the database into append-only [segment files](SegmentFile) by the [StaticFileProducer]. The
+//! [StaticFileProvider] keeps track of the frozen ranges and is used by the
+//! [DatabaseProvider](crate::DatabaseProvider) to read frozen entries transparently.
+
+use parking_lot::RwLock;
+use reth_db::table::Decompress;
+use reth_interfaces::Result;
+use reth_primitives::BlockNumber;
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::debug;
+
+mod file;
+pub(crate) use file::static_file_error;
+pub use file::{SegmentFile, SegmentFileWriter};
+
+mod producer;
+pub use producer::StaticFileProducer;
+
+mod segment;
+pub use segment::StaticFileSegment;
+
+/// Provides access to the static files of all segments.
+///
+/// The provider is cheap to clone and shared between all database providers of a node.
+#[derive(Debug, Clone)]
+pub struct StaticFileProvider(Arc<StaticFileProviderInner>);
+
+#[derive(Debug)]
+struct StaticFileProviderInner {
+    /// Directory of the static files.
+    path: PathBuf,
+    /// Index of the static files of each segment.
+    segments: RwLock<HashMap<StaticFileSegment, SegmentIndex>>,
+}
+
+/// The static files of a single segment.
+#[derive(Debug, Default)]
+struct SegmentIndex {
+    /// Highest block covered by the static files.
+    highest_block: Option<BlockNumber>,
+    /// Highest key stored in the static files.
+    highest_key: Option<u64>,
+    /// Non-empty static files by their highest key.
+    files: BTreeMap<u64, Arc<SegmentFile>>,
+}
+
+impl SegmentIndex {
+    /// Adds the next static file of the segment. Files have to be added in order and without gaps.
+    fn insert(&mut self, file: SegmentFile) -> Result<()> {
+        let expected_block = self.highest_block.map_or(0, |block| block + 1);
+        let expected_key = self.highest_key.map_or(0, |key| key + 1);
+        if *file.block_range().start() != expected_block {
+            return Err(static_file_error(format!(
+                "{} static file for blocks {:?} does not start at block {expected_block}",
+                file.segment(),
+                file.block_range()
+            )))
+        }
+
+        self.highest_block = Some(*file.block_range().end());
+        if let Some(key_range) = file.key_range() {
+            if *key_range.start() != expected_key {
+                return Err(static_file_error(format!(
+                    "{} static file for blocks {:?} does not start at key {expected_key}",
+                    file.segment(),
+                    file.block_range()
+                )))
+            }
+            self.highest_key = Some(*key_range.end());
+            self.files.insert(*key_range.end(), Arc::new(file));
+        }
+        Ok(())
+    }
+
+    /// Returns the static file containing the key.
+    fn file(&self, key: u64) -> Option<&Arc<SegmentFile>> {
+        self.files
+            .range(key..)
+            .next()
+            .map(|(_, file)| file)
+            .filter(|file| file.key_range().map_or(false, |range| range.contains(&key)))
+    }
+}
+
+impl StaticFileProvider {
+    /// Opens the static files in the given directory, creating the directory if it does not exist.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        std::fs::create_dir_all(&path).map_err(static_file_error)?;
+
+        let mut files = file::list_segment_files(&path)?;
+        files.sort_by_key(|(segment, range)| (*segment, *range.start()));
+
+        let mut segments = HashMap::<StaticFileSegment, SegmentIndex>::new();
+        for (segment, block_range) in files {
+            let file = SegmentFile::open(&path, segment, block_range)?;
+            segments.entry(segment).or_default().insert(file)?;
+        }
+
+        for (segment, index) in &segments {
+            debug!(
+                target: "provider::static_file",
+                %segment,
+                highest_block = ?index.highest_block,
+                highest_key = ?index.highest_key,
+                "Opened static files"
+            );
+        }
+
+        Ok(Self(Arc::new(StaticFileProviderInner { path, segments: RwLock::new(segments) })))
+    }
+
+    /// Returns the directory of the static files.
+    pub fn path(&self) -> &Path {
+        &self.0.path
+    }
+
+    /// Returns the highest block covered by the static files of the segment.
+    pub fn highest_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.0.segments.read().get(&segment).and_then(|index| index.highest_block)
+    }
+
+    /// Returns the highest key stored in the static files of the segment. All keys up to and
+    /// including it are no longer stored in the database.
+    pub fn highest_key(&self, segment: StaticFileSegment) -> Option<u64> {
+        self.0.segments.read().get(&segment).and_then(|index| index.highest_key)
+    }
+
+    /// Returns the value of the key from the static files of the segment.
+    pub fn get<V: Decompress>(&self, segment: StaticFileSegment, key: u64) -> Result<Option<V>> {
+        let file = self.0.segments.read().get(&segment).and_then(|index| index.file(key).cloned());
+        match file {
+            Some(file) => file.get(key),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns all key-value pairs in the range from the static files of the segment. Keys without
+    /// a value are skipped.
+    pub fn range<V: Decompress>(
+        &self,
+        segment: StaticFileSegment,
+        range: RangeInclusive<u64>,
+    ) -> Result<Vec<(u64, V)>> {
+        let mut entries = Vec::new();
+        let (mut key, end) = range.into_inner();
+        while key <= end {
+            let Some(file) =
+                self.0.segments.read().get(&segment).and_then(|index| index.file(key).cloned())
+            else {
+                break
+            };
+            let file_end = file.key_range().map_or(key, |range| *range.end()).min(end);
+            if file.is_pruned() {
+                key = file_end + 1;
+                continue
+            }
+            for key in key..=file_end {
+                if let Some(value) = file.get(key)? {
+                    entries.push((key, value));
+                }
+            }
+            key = file_end + 1;
+        }
+        Ok(entries)
+    }
+
+    /// Prunes the data of all static files of the segment whose blocks are all at or below the
+    /// given block and returns the number of pruned files.
+    ///
+    /// The key ranges of pruned files, and with them the highest block and key of the segment,
+    /// remain known, so their keys are still not looked up in the database and are read without
+    /// a value.
+    pub fn prune(&self, segment: StaticFileSegment, to_block: BlockNumber) -> Result<usize> {
+        let mut segments = self.0.segments.write();
+        let Some(index) = segments.get_mut(&segment) else { return Ok(0) };
+
+        let mut pruned = 0;
+        // files are ordered by their keys and therefore by their blocks
+        for file in index.files.values_mut() {
+            if *file.block_range().end() > to_block {
+                break
+            }
+            if file.is_pruned() {
+                continue
+            }
+            let block_range = file.block_range().clone();
+            *file = Arc::new(SegmentFile::prune(&self.0.path, segment, block_range.clone())?);
+            pruned += 1;
+            debug!(target: "provider::static_file", %segment, ?block_range, "Pruned static file");
+        }
+        Ok(pruned)
+    }
+
+    /// Opens a newly written static file of the segment and makes it available for reads.
+    pub(crate) fn register(
+        &self,
+        segment: StaticFileSegment,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> Result<()> {
+        let file = SegmentFile::open(&self.0.path, segment, block_range)?;
+        self.0.segments.write().entry(segment).or_default().insert(file)
+    }
+}
@@ -0,0 +1,185 @@
+use super::{static_file_error, SegmentFileWriter, StaticFileProvider, StaticFileSegment};
+use crate::{ProviderFactory, PruneCheckpointReader, StageCheckpointReader};
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    table::Table,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_interfaces::{provider::ProviderError, Result};
+use reth_primitives::{stage::StageId, BlockNumber, PrunePart};
+use std::ops::{Range, RangeInclusive};
+use tracing::{debug, trace};
+
+/// Moves finalized block ranges of the `Headers`, `Transactions` and `Receipts` tables from the
+/// database into static files.
+///
+/// Each static file covers exactly `blocks_per_file` blocks, so a range is only moved once all of
+/// its blocks are finalized and the data is available in the database.
+#[derive(Debug)]
+pub struct StaticFileProducer<DB> {
+    /// Factory of the database providers.
+    factory: ProviderFactory<DB>,
+    /// Static files the data is moved to.
+    static_files: StaticFileProvider,
+    /// Number of blocks covered by a single static file.
+    blocks_per_file: u64,
+}
+
+impl<DB: Database> StaticFileProducer<DB> {
+    /// Creates a new producer.
+    pub fn new(
+        factory: ProviderFactory<DB>,
+        static_files: StaticFileProvider,
+        blocks_per_file: u64,
+    ) -> Self {
+        Self { factory, static_files, blocks_per_file: blocks_per_file.max(1) }
+    }
+
+    /// Moves all complete block ranges up to and including the finalized block into static files.
+    ///
+    /// Returns the number of static files written.
+    ///
+    /// Transaction based segments stop at the first range with pruned block bodies, since the
+    /// transaction numbers of those blocks are unknown.
+    pub fn run(&self, finalized_block: BlockNumber) -> Result<usize> {
+        let pruned_bodies = self
+            .factory
+            .get_prune_checkpoint(PrunePart::Bodies)?
+            .map(|checkpoint| checkpoint.block_number);
+
+        let mut written = 0;
+        for segment in StaticFileSegment::ALL {
+            let highest_block = finalized_block.min(self.highest_available_block(segment)?);
+            while let Some(block_range) = self.next_block_range(segment, highest_block) {
+                if segment.is_tx_based() &&
+                    pruned_bodies.map_or(false, |pruned| *block_range.start() <= pruned)
+                {
+                    debug!(
+                        target: "provider::static_file",
+                        %segment,
+                        ?block_range,
+                        "Block bodies are pruned, skipping segment"
+                    );
+                    break
+                }
+                self.freeze(segment, block_range)?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Returns the next block range of the segment to freeze if all of its blocks are at or
+    /// below the given block.
+    fn next_block_range(
+        &self,
+        segment: StaticFileSegment,
+        highest_block: BlockNumber,
+    ) -> Option<RangeInclusive<BlockNumber>> {
+        let start = self.static_files.highest_block(segment).map_or(0, |block| block + 1);
+        let end = start + self.blocks_per_file - 1;
+        (end <= highest_block).then_some(start..=end)
+    }
+
+    /// Returns the highest block for which the data of the segment has been written by the
+    /// pipeline.
+    fn highest_available_block(&self, segment: StaticFileSegment) -> Result<BlockNumber> {
+        let stage = match segment {
+            StaticFileSegment::Headers => StageId::Headers,
+            StaticFileSegment::Transactions => StageId::Bodies,
+            StaticFileSegment::Receipts => StageId::Execution,
+        };
+        Ok(self
+            .factory
+            .provider()?
+            .get_stage_checkpoint(stage)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default())
+    }
+
+    /// Writes the block range of the segment into a new static file and removes it from the
+    /// database.
+    fn freeze(
+        &self,
+        segment: StaticFileSegment,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> Result<()> {
+        let provider = self.factory.provider_rw()?;
+        let tx = provider.tx_ref();
+
+        let keys = if segment.is_tx_based() {
+            let body_indices = |block| -> Result<_> {
+                Ok(tx
+                    .get::<tables::BlockBodyIndices>(block)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?)
+            };
+            let first = body_indices(*block_range.start())?;
+            let last = body_indices(*block_range.end())?;
+            first.first_tx_num()..last.next_tx_num()
+        } else {
+            *block_range.start()..*block_range.end() + 1
+        };
+
+        let expected_key = self.static_files.highest_key(segment).map_or(0, |key| key + 1);
+        if !keys.is_empty() && keys.start != expected_key {
+            return Err(static_file_error(format!(
+                "{segment} static file for blocks {block_range:?} starts at key {}, expected \
+                 {expected_key}",
+                keys.start
+            )))
+        }
+
+        debug!(
+            target: "provider::static_file",
+            %segment,
+            ?block_range,
+            ?keys,
+            "Moving block range to static file"
+        );
+
+        let mut writer =
+            SegmentFileWriter::new(self.static_files.path(), segment, &block_range, keys.start)?;
+        match segment {
+            StaticFileSegment::Headers => take_into::<_, tables::Headers>(tx, &mut writer, keys)?,
+            StaticFileSegment::Transactions => {
+                take_into::<_, tables::Transactions>(tx, &mut writer, keys)?
+            }
+            StaticFileSegment::Receipts => take_into::<_, tables::Receipts>(tx, &mut writer, keys)?,
+        }
+        writer.finish()?;
+
+        // Make the file available before the entries are removed from the database, so the
+        // entries can always be read from at least one of them.
+        self.static_files.register(segment, block_range)?;
+        provider.commit()?;
+
+        Ok(())
+    }
+}
+
+/// Appends all entries of the table in the key range to the writer and removes them from the
+/// database. Missing entries, e.g. pruned receipts, are appended without a value.
+fn take_into<'a, TX, T>(tx: &TX, writer: &mut SegmentFileWriter, keys: Range<u64>) -> Result<()>
+where
+    TX: DbTxMut<'a> + DbTx<'a>,
+    T: Table<Key = u64>,
+{
+    let mut cursor = tx.cursor_write::<T>()?;
+    let mut walker = cursor.walk_range(keys.clone())?;
+    let mut next_key = keys.start;
+    while let Some((key, value)) = walker.next().transpose()? {
+        for missing in next_key..key {
+            writer.append_empty(missing)?;
+        }
+        writer.append(key, value)?;
+        walker.delete_current()?;
+        next_key = key + 1;
+    }
+    for missing in next_key..keys.end {
+        writer.append_empty(missing)?;
+    }
+    trace!(target: "provider::static_file", table = T::NAME, ?keys, "Moved table entries");
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use reth_primitives::BlockNumber;
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+/// Segment of the chain data that can be moved to static files.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum StaticFileSegment {
+    /// Static files of the `Headers` table, keyed by block number.
+    Headers,
+    /// Static files of the `Transactions` table, keyed by transaction number.
+    Transactions,
+    /// Static files of the `Receipts` table, keyed by transaction number.
+    Receipts,
+}
+
+impl StaticFileSegment {
+    /// All static file segments.
+    pub const ALL: [StaticFileSegment; 3] =
+        [StaticFileSegment::Headers, StaticFileSegment::Transactions, StaticFileSegment::Receipts];
+
+    /// Returns the segment as a string.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            StaticFileSegment::Headers => "headers",
+            StaticFileSegment::Transactions => "transactions",
+            StaticFileSegment::Receipts => "receipts",
+        }
+    }
+
+    /// Returns `true` if the segment is keyed by transaction number instead of block number.
+    pub const fn is_tx_based(&self) -> bool {
+        matches!(self, StaticFileSegment::Transactions | StaticFileSegment::Receipts)
+    }
+
+    /// Returns the file name of the segment file for the given block range, e.g.
+    /// `headers_0_499999`.
+    pub fn file_name(&self, block_range: &RangeInclusive<BlockNumber>) -> String {
+        format!("{}_{}_{}", self.as_str(), block_range.start(), block_range.end())
+    }
+
+    /// Parses a file name created by [StaticFileSegment::file_name] into the segment and its block
+    /// range.
+    pub fn parse_file_name(name: &str) -> Option<(Self, RangeInclusive<BlockNumber>)> {
+        let mut parts = name.split('_');
+        let segment = parts.next()?.parse().ok()?;
+        let start = parts.next()?.parse().ok()?;
+        let end = parts.next()?.parse().ok()?;
+        if parts.next().is_some() || start > end {
+            return None
+        }
+        Some((segment, start..=end))
+    }
+}
+
+impl fmt::Display for StaticFileSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StaticFileSegment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "headers" => StaticFileSegment::Headers,
+            "transactions" => StaticFileSegment::Transactions,
+            "receipts" => StaticFileSegment::Receipts,
+            _ => return Err(format!("unknown static file segment: {s}")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_roundtrip() {
+        for segment in StaticFileSegment::ALL {
+            let name = segment.file_name(&(500_000..=999_999));
+            assert_eq!(
+                StaticFileSegment::parse_file_name(&name),
+                Some((segment, 500_000..=999_999))
+            );
+        }
+        assert_eq!(StaticFileSegment::parse_file_name("headers_10_1"), None);
+        assert_eq!(StaticFileSegment::parse_file_name("headers_0_1.off"), None);
+        assert_eq!(StaticFileSegment::parse_file_name("bodies_0_1"), None);
+    }
+}
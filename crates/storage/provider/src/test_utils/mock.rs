@@ -1,27 +1,32 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, EvmEnvProvider, HeaderProvider, PostState, PostStateDataProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionsProvider, WithdrawalsProvider,
+    ChainSpecProvider, EvmEnvProvider, HeaderProvider, HistoryOrder, HistoryReader, PostState,
+    PostStateDataProvider, ReceiptProviderIdExt, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_db::models::StoredBlockBodyIndices;
 use reth_interfaces::{provider::ProviderError, Result};
 use reth_primitives::{
-    keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
-    BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt, SealedBlock,
-    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
+    keccak256, trie::AccountProof, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId,
+    BlockNumber, BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, Log, Receipt,
+    SealedBlock, SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
     TransactionSignedNoHash, TxHash, TxNumber, H256, U256,
 };
-use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
+use reth_revm_primitives::{
+    env::fill_cfg_and_block_env,
+    primitives::{BlockEnv, CfgEnv},
+};
 use std::{
-    collections::{BTreeMap, HashMap},
-    ops::RangeBounds,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
 
 /// A mock implementation for Provider interfaces.
+///
+/// Only a single state is tracked, which is returned for every block.
 #[derive(Debug, Clone)]
 pub struct MockEthProvider {
     /// Local block store
@@ -30,6 +35,12 @@ pub struct MockEthProvider {
     pub headers: Arc<Mutex<HashMap<H256, Header>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
+    /// Local receipt store, by block hash
+    pub receipts: Arc<Mutex<HashMap<H256, Vec<Receipt>>>>,
+    /// The range of blocks covered by the log index, which is derived from the receipt store
+    pub log_index: Arc<Mutex<Option<RangeInclusive<BlockNumber>>>>,
+    /// Local account history index, the blocks at which an account changed
+    pub account_history: Arc<Mutex<HashMap<Address, BTreeSet<BlockNumber>>>>,
     /// Local chain spec
     pub chain_spec: Arc<ChainSpec>,
 }
@@ -40,6 +51,9 @@ impl Default for MockEthProvider {
             blocks: Default::default(),
             headers: Default::default(),
             accounts: Default::default(),
+            receipts: Default::default(),
+            log_index: Default::default(),
+            account_history: Default::default(),
             chain_spec: Arc::new(reth_primitives::ChainSpecBuilder::mainnet().build()),
         }
     }
@@ -114,6 +128,43 @@ impl MockEthProvider {
         self.accounts.lock().insert(address, account);
     }
 
+    /// Add receipts of the block to local receipt store
+    pub fn add_receipts(&self, block_hash: H256, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(block_hash, receipts);
+    }
+
+    /// Sets the range of blocks covered by the log index, or disables the index if `None`
+    pub fn set_log_index(&self, range: Option<RangeInclusive<BlockNumber>>) {
+        *self.log_index.lock() = range;
+    }
+
+    /// Adds the blocks at which the account changed to the local account history index
+    pub fn add_account_history(
+        &self,
+        address: Address,
+        blocks: impl IntoIterator<Item = BlockNumber>,
+    ) {
+        self.account_history.lock().entry(address).or_default().extend(blocks);
+    }
+
+    /// Returns the indexed blocks in the given range that contain a log matching the predicate
+    fn indexed_log_blocks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        f: impl Fn(&Log) -> bool,
+    ) -> Result<Vec<BlockNumber>> {
+        let Some(index) = self.log_index.lock().clone() else { return Ok(Vec::new()) };
+
+        let mut blocks = Vec::new();
+        for number in *range.start().max(index.start())..=*range.end().min(index.end()) {
+            let logs = self.receipts_by_block(number.into())?.unwrap_or_default();
+            if logs.iter().flat_map(|receipt| receipt.logs.iter()).any(&f) {
+                blocks.push(number);
+            }
+        }
+        Ok(blocks)
+    }
+
     /// Add account to local account store
     pub fn extend_accounts(&self, iter: impl IntoIterator<Item = (Address, ExtendedAccount)>) {
         for (address, account) in iter.into_iter() {
@@ -258,8 +309,12 @@ impl ReceiptProvider for MockEthProvider {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>> {
-        Ok(None)
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> Result<Option<Vec<Receipt>>> {
+        let hash = match block {
+            BlockHashOrNumber::Hash(hash) => Some(hash),
+            BlockHashOrNumber::Number(number) => self.block_hash(number)?,
+        };
+        Ok(hash.and_then(|hash| self.receipts.lock().get(&hash).cloned()))
     }
 }
 
@@ -360,8 +415,10 @@ impl BlockReader for MockEthProvider {
         Ok(None)
     }
 
-    fn block_with_senders(&self, _number: BlockNumber) -> Result<Option<BlockWithSenders>> {
-        Ok(None)
+    fn block_with_senders(&self, number: BlockNumber) -> Result<Option<BlockWithSenders>> {
+        let Some(block) = self.block(number.into())? else { return Ok(None) };
+        let senders = TransactionSigned::recover_signers(&block.body, block.body.len());
+        Ok(senders.and_then(|senders| BlockWithSenders::new(block, senders)))
     }
 }
 
@@ -398,6 +455,55 @@ impl AccountReader for MockEthProvider {
     }
 }
 
+impl HistoryReader for MockEthProvider {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        let account_history = self.account_history.lock();
+        let Some(blocks) = account_history.get(&address) else { return Ok(Vec::new()) };
+        let mut blocks: Vec<_> = match order {
+            HistoryOrder::Ascending => blocks.range(range).copied().take(limit).collect(),
+            HistoryOrder::Descending => blocks.range(range).rev().copied().take(limit).collect(),
+        };
+        blocks.sort_unstable();
+        Ok(blocks)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+        _order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_index_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>> {
+        Ok(self.log_index.lock().clone())
+    }
+
+    fn log_address_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        self.indexed_log_blocks(range, |log| log.address == address)
+    }
+
+    fn log_topic_history_blocks(
+        &self,
+        topic: H256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        self.indexed_log_blocks(range, |log| log.topics.contains(&topic))
+    }
+}
+
 impl StateRootProvider for MockEthProvider {
     fn state_root(&self, _post_state: PostState) -> Result<H256> {
         todo!()
@@ -422,11 +528,7 @@ impl StateProvider for MockEthProvider {
         }))
     }
 
-    fn proof(
-        &self,
-        _address: Address,
-        _keys: &[H256],
-    ) -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
+    fn proof(&self, _address: Address, _keys: &[H256]) -> Result<AccountProof> {
         todo!()
     }
 }
@@ -434,40 +536,45 @@ impl StateProvider for MockEthProvider {
 impl EvmEnvProvider for MockEthProvider {
     fn fill_env_at(
         &self,
-        _cfg: &mut CfgEnv,
-        _block_env: &mut BlockEnv,
-        _at: BlockHashOrNumber,
+        cfg: &mut CfgEnv,
+        block_env: &mut BlockEnv,
+        at: BlockHashOrNumber,
     ) -> Result<()> {
-        unimplemented!()
+        let header = self.header_by_hash_or_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_env_with_header(cfg, block_env, &header)
     }
 
     fn fill_env_with_header(
         &self,
-        _cfg: &mut CfgEnv,
-        _block_env: &mut BlockEnv,
-        _header: &Header,
+        cfg: &mut CfgEnv,
+        block_env: &mut BlockEnv,
+        header: &Header,
     ) -> Result<()> {
-        unimplemented!()
+        let total_difficulty = self
+            .header_td_by_number(header.number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?;
+        fill_cfg_and_block_env(cfg, block_env, &self.chain_spec, header, total_difficulty);
+        Ok(())
     }
 
-    fn fill_block_env_at(&self, _block_env: &mut BlockEnv, _at: BlockHashOrNumber) -> Result<()> {
-        unimplemented!()
+    fn fill_block_env_at(&self, block_env: &mut BlockEnv, at: BlockHashOrNumber) -> Result<()> {
+        let header = self.header_by_hash_or_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_block_env_with_header(block_env, &header)
     }
 
-    fn fill_block_env_with_header(
-        &self,
-        _block_env: &mut BlockEnv,
-        _header: &Header,
-    ) -> Result<()> {
-        unimplemented!()
+    fn fill_block_env_with_header(&self, block_env: &mut BlockEnv, header: &Header) -> Result<()> {
+        let mut cfg = CfgEnv::default();
+        self.fill_env_with_header(&mut cfg, block_env, header)
     }
 
-    fn fill_cfg_env_at(&self, _cfg: &mut CfgEnv, _at: BlockHashOrNumber) -> Result<()> {
-        unimplemented!()
+    fn fill_cfg_env_at(&self, cfg: &mut CfgEnv, at: BlockHashOrNumber) -> Result<()> {
+        let header = self.header_by_hash_or_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_cfg_env_with_header(cfg, &header)
     }
 
-    fn fill_cfg_env_with_header(&self, _cfg: &mut CfgEnv, _header: &Header) -> Result<()> {
-        unimplemented!()
+    fn fill_cfg_env_with_header(&self, cfg: &mut CfgEnv, header: &Header) -> Result<()> {
+        let mut block_env = BlockEnv::default();
+        self.fill_env_with_header(cfg, &mut block_env, header)
     }
 }
 
@@ -477,15 +584,15 @@ impl StateProviderFactory for MockEthProvider {
     }
 
     fn history_by_block_number(&self, _block: BlockNumber) -> Result<StateProviderBox<'_>> {
-        todo!()
+        self.latest()
     }
 
     fn history_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+        self.latest()
     }
 
     fn state_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+        self.latest()
     }
 
     fn pending(&self) -> Result<StateProviderBox<'_>> {
@@ -510,15 +617,15 @@ impl StateProviderFactory for Arc<MockEthProvider> {
     }
 
     fn history_by_block_number(&self, _block: BlockNumber) -> Result<StateProviderBox<'_>> {
-        todo!()
+        self.latest()
     }
 
     fn history_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+        self.latest()
     }
 
     fn state_by_block_hash(&self, _block: BlockHash) -> Result<StateProviderBox<'_>> {
-        todo!()
+        self.latest()
     }
 
     fn pending(&self) -> Result<StateProviderBox<'_>> {
@@ -1,22 +1,27 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PostState,
-    PruneCheckpointReader, ReceiptProviderIdExt, StageCheckpointReader, StateProvider,
-    StateProviderBox, StateProviderFactory, StateRootProvider, TransactionsProvider,
+    AccountRangeWithProof, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
+    HeaderProvider, HistoryOrder, HistoryReader, PostState, PruneCheckpointReader,
+    ReceiptProviderIdExt, SnapStateReader, StageCheckpointReader, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, StorageRangesWithProof, TransactionsProvider,
     WithdrawalsProvider,
 };
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_interfaces::Result;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
+    trie::{AccountProof, Nibbles},
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode, Bytes,
     ChainInfo, ChainSpec, Header, PruneCheckpoint, PrunePart, Receipt, SealedBlock, SealedHeader,
     StorageKey, StorageValue, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash,
-    TxNumber, H256, KECCAK_EMPTY, MAINNET, U256,
+    TxNumber, H256, MAINNET, U256,
 };
 use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
-use std::{ops::RangeBounds, sync::Arc};
+use std::{
+    ops::{RangeBounds, RangeInclusive},
+    sync::Arc,
+};
 
 /// Supports various api interfaces for testing purposes.
 #[derive(Debug, Clone, Default, Copy)]
@@ -242,6 +247,48 @@ impl ChangeSetReader for NoopProvider {
     }
 }
 
+impl HistoryReader for NoopProvider {
+    fn account_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+        _order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn storage_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+        _order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_index_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_address_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_topic_history_blocks(
+        &self,
+        _topic: H256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
 impl StateRootProvider for NoopProvider {
     fn state_root(&self, _post_state: PostState) -> Result<H256> {
         todo!()
@@ -257,12 +304,8 @@ impl StateProvider for NoopProvider {
         Ok(None)
     }
 
-    fn proof(
-        &self,
-        _address: Address,
-        _keys: &[H256],
-    ) -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
-        Ok((vec![], KECCAK_EMPTY, vec![]))
+    fn proof(&self, address: Address, _keys: &[H256]) -> Result<AccountProof> {
+        Ok(AccountProof::new(address))
     }
 }
 
@@ -367,3 +410,43 @@ impl PruneCheckpointReader for NoopProvider {
         Ok(None)
     }
 }
+
+impl SnapStateReader for NoopProvider {
+    fn account_range(
+        &self,
+        _root: H256,
+        _start: H256,
+        _limit: H256,
+        _max_accounts: usize,
+    ) -> Result<Option<AccountRangeWithProof>> {
+        Ok(None)
+    }
+
+    fn storage_ranges(
+        &self,
+        _root: H256,
+        _accounts: &[H256],
+        _start: H256,
+        _limit: H256,
+        _max_slots: usize,
+    ) -> Result<Option<StorageRangesWithProof>> {
+        Ok(None)
+    }
+
+    fn bytecodes(&self, _hashes: &[H256], _max_bytes: usize) -> Result<Vec<Bytes>> {
+        Ok(Vec::new())
+    }
+
+    fn account_trie_nodes(&self, _root: H256, _paths: Vec<Nibbles>) -> Result<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+
+    fn storage_trie_nodes(
+        &self,
+        _root: H256,
+        _hashed_address: H256,
+        _paths: Vec<Nibbles>,
+    ) -> Result<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+}
@@ -7,6 +7,75 @@ use std::{
     ops::{Range, RangeInclusive},
 };
 
+/// The end of the block range from which history lookups with a limit collect block numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryOrder {
+    /// Collect the lowest block numbers of the range.
+    Ascending,
+    /// Collect the highest block numbers of the range.
+    Descending,
+}
+
+/// History Reader
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryReader: Send + Sync {
+    /// Returns the block numbers in the given range at which the account of the given address was
+    /// changed, according to the [AccountHistory](reth_db::tables::AccountHistory) index.
+    ///
+    /// At most `limit` block numbers are returned, the lowest or the highest ones of the range
+    /// depending on `order`. The returned block numbers are sorted in ascending order.
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>>;
+
+    /// Returns the block numbers in the given range at which any storage slot of the given address
+    /// was changed, according to the [StorageHistory](reth_db::tables::StorageHistory) index.
+    ///
+    /// At most `limit` block numbers are returned, the lowest or the highest ones of the range
+    /// depending on `order`. The returned block numbers are sorted in ascending order and
+    /// deduplicated.
+    ///
+    /// Note: this visits the history of every storage slot of the account, but stops walking the
+    /// history of a slot once it can't contribute any of the `limit` returned blocks.
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        order: HistoryOrder,
+    ) -> Result<Vec<BlockNumber>>;
+
+    /// Returns the range of blocks that is covered by the log index, if the index is maintained.
+    ///
+    /// The range starts after the highest block pruned from the index and ends at the checkpoint
+    /// of the [IndexLogHistory](reth_primitives::stage::StageId::IndexLogHistory) stage.
+    fn log_index_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns all block numbers in the given range that contain a log emitted by the given
+    /// address, according to the [LogAddressHistory](reth_db::tables::LogAddressHistory) index.
+    ///
+    /// The returned block numbers are sorted in ascending order.
+    fn log_address_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>>;
+
+    /// Returns all block numbers in the given range that contain a log with the given topic at
+    /// any position, according to the [LogTopicHistory](reth_db::tables::LogTopicHistory) index.
+    ///
+    /// The returned block numbers are sorted in ascending order.
+    fn log_topic_history_blocks(
+        &self,
+        topic: H256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<BlockNumber>>;
+}
+
 /// History Writer
 #[auto_impl(&, Arc, Box)]
 pub trait HistoryWriter: Send + Sync {
@@ -34,4 +103,19 @@ pub trait HistoryWriter: Send + Sync {
 
     /// Read account/storage changesets and update account/storage history indices.
     fn calculate_history_indices(&self, range: RangeInclusive<BlockNumber>) -> Result<()>;
+
+    /// Unwind and clear log address and topic indices.
+    ///
+    /// Returns number of log addresses and topics unwound.
+    fn unwind_log_history_indices(&self, range: RangeInclusive<BlockNumber>) -> Result<usize>;
+
+    /// Insert log address and topic indices to database. Used inside IndexLogHistory stage
+    fn insert_log_history_index(
+        &self,
+        address_transitions: BTreeMap<Address, Vec<u64>>,
+        topic_transitions: BTreeMap<H256, Vec<u64>>,
+    ) -> Result<()>;
+
+    /// Read receipts and update log address and topic indices.
+    fn calculate_log_history_indices(&self, range: RangeInclusive<BlockNumber>) -> Result<()>;
 }
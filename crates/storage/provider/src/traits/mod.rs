@@ -58,7 +58,10 @@ mod hashing;
 pub use hashing::HashingWriter;
 
 mod history;
-pub use history::HistoryWriter;
+pub use history::{HistoryOrder, HistoryReader, HistoryWriter};
+
+mod snap;
+pub use snap::{AccountRangeWithProof, SnapStateReader, StorageRangesWithProof};
 
 mod prune_checkpoint;
 pub use prune_checkpoint::{PruneCheckpointReader, PruneCheckpointWriter};
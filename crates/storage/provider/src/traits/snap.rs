@@ -0,0 +1,73 @@
+use auto_impl::auto_impl;
+use reth_interfaces::Result;
+use reth_primitives::{trie::Nibbles, Account, Bytes, H256, U256};
+
+/// A consecutive range of accounts of the hashed state together with the account trie nodes that
+/// prove its bounds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountRangeWithProof {
+    /// The hashed address, account and storage root of every account in the range.
+    pub accounts: Vec<(H256, Account, H256)>,
+    /// The account trie nodes on the paths to the first and the last account of the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// Consecutive ranges of storage slots of multiple accounts together with the storage trie nodes
+/// that prove the bounds of the last range, if it is incomplete.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageRangesWithProof {
+    /// The hashed slots and values, one list per served account.
+    pub slots: Vec<Vec<(H256, U256)>>,
+    /// The storage trie nodes proving the last range.
+    pub proof: Vec<Bytes>,
+}
+
+/// Reads ranges of the latest hashed state and nodes of the state trie, as served over the `snap`
+/// protocol.
+///
+/// All methods that take a state `root` return `None` if the root is not the root of the latest
+/// state. Historical states are not served by design, since the trie tables only hold the nodes of
+/// the latest state.
+#[auto_impl(&, Arc)]
+pub trait SnapStateReader: Send + Sync {
+    /// Returns the accounts with a hashed address of at least `start`, stopping after the first
+    /// account at or past `limit`, or after `max_accounts` accounts.
+    fn account_range(
+        &self,
+        root: H256,
+        start: H256,
+        limit: H256,
+        max_accounts: usize,
+    ) -> Result<Option<AccountRangeWithProof>>;
+
+    /// Returns the storage slots of the given accounts, in order, until `max_slots` slots have
+    /// been collected.
+    ///
+    /// `start` only applies to the first and `limit` only to the last account. The last range
+    /// includes the first slot past the limit, if any.
+    fn storage_ranges(
+        &self,
+        root: H256,
+        accounts: &[H256],
+        start: H256,
+        limit: H256,
+        max_slots: usize,
+    ) -> Result<Option<StorageRangesWithProof>>;
+
+    /// Returns the bytecodes with the given hashes, in order, until `max_bytes` have been
+    /// collected. Unknown bytecodes are skipped.
+    fn bytecodes(&self, hashes: &[H256], max_bytes: usize) -> Result<Vec<Bytes>>;
+
+    /// Returns the account trie nodes at the given paths, in order, stopping at the first missing
+    /// node.
+    fn account_trie_nodes(&self, root: H256, paths: Vec<Nibbles>) -> Result<Option<Vec<Bytes>>>;
+
+    /// Returns the storage trie nodes of the given account at the given paths, in order, stopping
+    /// at the first missing node.
+    fn storage_trie_nodes(
+        &self,
+        root: H256,
+        hashed_address: H256,
+        paths: Vec<Nibbles>,
+    ) -> Result<Option<Vec<Bytes>>>;
+}
@@ -3,8 +3,8 @@ use crate::{post_state::PostState, BlockHashReader, BlockIdReader};
 use auto_impl::auto_impl;
 use reth_interfaces::{provider::ProviderError, Result};
 use reth_primitives::{
-    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, Bytes,
-    StorageKey, StorageValue, H256, KECCAK_EMPTY, U256,
+    trie::AccountProof, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag,
+    Bytecode, StorageKey, StorageValue, H256, KECCAK_EMPTY, U256,
 };
 
 /// Type alias of boxed [StateProvider].
@@ -20,8 +20,7 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     fn bytecode_by_hash(&self, code_hash: H256) -> Result<Option<Bytecode>>;
 
     /// Get account and storage proofs.
-    fn proof(&self, address: Address, keys: &[H256])
-        -> Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)>;
+    fn proof(&self, address: Address, keys: &[H256]) -> Result<AccountProof>;
 
     /// Get account code by its address.
     ///
@@ -15,7 +15,7 @@
 
 use crate::{
     metrics::TaskExecutorMetrics,
-    shutdown::{signal, Shutdown, Signal},
+    shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
 };
 use dyn_clone::DynClone;
 use futures_util::{
@@ -26,14 +26,19 @@ use std::{
     any::Any,
     fmt::{Display, Formatter},
     pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::{
     runtime::Handle,
     sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     task::JoinHandle,
 };
-use tracing::error;
+use tracing::{debug, error};
 use tracing_futures::Instrument;
 
 pub mod metrics;
@@ -151,9 +156,11 @@ pub struct TaskManager {
     /// The [Signal] to fire when all tasks should be shutdown.
     ///
     /// This is fired on drop.
-    _signal: Signal,
+    signal: Option<Signal>,
     /// Receiver of the shutdown signal.
     on_shutdown: Shutdown,
+    /// Number of tasks that are still shutting down gracefully.
+    graceful_tasks: Arc<AtomicUsize>,
 }
 
 // === impl TaskManager ===
@@ -162,8 +169,15 @@ impl TaskManager {
     /// Create a new instance connected to the given handle's tokio runtime.
     pub fn new(handle: Handle) -> Self {
         let (panicked_tasks_tx, panicked_tasks_rx) = unbounded_channel();
-        let (_signal, on_shutdown) = signal();
-        Self { handle, panicked_tasks_tx, panicked_tasks_rx, _signal, on_shutdown }
+        let (signal, on_shutdown) = signal();
+        Self {
+            handle,
+            panicked_tasks_tx,
+            panicked_tasks_rx,
+            signal: Some(signal),
+            on_shutdown,
+            graceful_tasks: Arc::new(AtomicUsize::new(0)),
+        }
     }
 
     /// Returns a new [`TaskExecutor`] that can spawn new tasks onto the tokio runtime this type is
@@ -174,8 +188,36 @@ impl TaskManager {
             on_shutdown: self.on_shutdown.clone(),
             panicked_tasks_tx: self.panicked_tasks_tx.clone(),
             metrics: Default::default(),
+            graceful_tasks: Arc::clone(&self.graceful_tasks),
         }
     }
+
+    /// Fires the shutdown signal and waits until all tasks spawned with
+    /// [TaskExecutor::spawn_critical_with_graceful_shutdown_signal] have finished, or until the
+    /// timeout elapsed.
+    ///
+    /// Returns `true` if all tasks finished in time.
+    ///
+    /// Note: this blocks the current thread, so it must not be called from within the runtime.
+    pub fn graceful_shutdown_with_timeout(mut self, timeout: Duration) -> bool {
+        if let Some(signal) = self.signal.take() {
+            signal.fire();
+        }
+        let deadline = Instant::now() + timeout;
+        while self.graceful_tasks.load(Ordering::SeqCst) > 0 {
+            if Instant::now() > deadline {
+                debug!(
+                    target: "reth::tasks",
+                    remaining = self.graceful_tasks.load(Ordering::SeqCst),
+                    "Graceful shutdown timed out"
+                );
+                return false
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        debug!(target: "reth::tasks", "Gracefully shut down");
+        true
+    }
 }
 
 /// An endless future that resolves if a critical task panicked.
@@ -235,6 +277,8 @@ pub struct TaskExecutor {
     panicked_tasks_tx: UnboundedSender<PanickedTaskError>,
     // Task Executor Metrics
     metrics: TaskExecutorMetrics,
+    /// Number of tasks that are still shutting down gracefully.
+    graceful_tasks: Arc<AtomicUsize>,
 }
 
 // === impl TaskExecutor ===
@@ -399,6 +443,41 @@ impl TaskExecutor {
 
         self.handle.spawn(task)
     }
+
+    /// This spawns a critical task onto the runtime that is shut down gracefully.
+    ///
+    /// The [GracefulShutdown] resolves with a guard once the shutdown signal is received, and
+    /// [TaskManager::graceful_shutdown_with_timeout] waits until the guard is dropped.
+    ///
+    /// If this task panics, the [`TaskManager`] is notified.
+    pub fn spawn_critical_with_graceful_shutdown_signal<F>(
+        &self,
+        name: &'static str,
+        f: impl FnOnce(GracefulShutdown) -> F,
+    ) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
+        let on_shutdown = GracefulShutdown::new(
+            self.on_shutdown.clone(),
+            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks)),
+        );
+        let fut = f(on_shutdown);
+
+        // wrap the task in catch unwind
+        let task = std::panic::AssertUnwindSafe(fut)
+            .catch_unwind()
+            .map_err(move |error| {
+                let task_error = PanickedTaskError::new(name, error);
+                error!("{task_error}");
+                let _ = panicked_tasks_tx.send(task_error);
+            })
+            .map(|_| ())
+            .in_current_span();
+
+        self.handle.spawn(task)
+    }
 }
 
 impl TaskSpawner for TaskExecutor {
@@ -436,7 +515,6 @@ enum TaskKind {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
 
     #[test]
     fn test_cloneable() {
@@ -513,4 +591,28 @@ mod tests {
 
         handle.block_on(shutdown);
     }
+
+    #[test]
+    fn test_graceful_shutdown_triggered_by_executor() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor = manager.executor();
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let num = 10;
+        for _ in 0..num {
+            let c = counter.clone();
+            executor.spawn_critical_with_graceful_shutdown_signal(
+                "grace",
+                move |shutdown| async move {
+                    let _guard = shutdown.await;
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    c.fetch_add(1, Ordering::SeqCst);
+                },
+            );
+        }
+
+        assert!(manager.graceful_shutdown_with_timeout(Duration::from_secs(5)));
+        assert_eq!(counter.load(Ordering::SeqCst), num);
+    }
 }
@@ -7,7 +7,11 @@ use futures_util::{
 use std::{
     future::Future,
     pin::Pin,
-    task::{Context, Poll},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
 };
 use tokio::sync::oneshot;
 
@@ -28,6 +32,50 @@ impl Future for Shutdown {
     }
 }
 
+/// A Future that resolves when the shutdown event has been fired.
+///
+/// The returned [GracefulShutdownGuard] delays the graceful shutdown of the
+/// [TaskManager](crate::TaskManager) until it is dropped, so the task can finish its work.
+#[derive(Debug)]
+pub struct GracefulShutdown {
+    shutdown: Shutdown,
+    guard: Option<GracefulShutdownGuard>,
+}
+
+impl GracefulShutdown {
+    pub(crate) fn new(shutdown: Shutdown, guard: GracefulShutdownGuard) -> Self {
+        Self { shutdown, guard: Some(guard) }
+    }
+}
+
+impl Future for GracefulShutdown {
+    type Output = GracefulShutdownGuard;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        ready!(this.shutdown.poll_unpin(cx));
+        Poll::Ready(this.guard.take().expect("Future polled after completion"))
+    }
+}
+
+/// A guard that keeps track of a task that is still shutting down.
+#[derive(Debug)]
+#[must_use = "if unused the graceful shutdown is not awaited"]
+pub struct GracefulShutdownGuard(Arc<AtomicUsize>);
+
+impl GracefulShutdownGuard {
+    pub(crate) fn new(counter: Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for GracefulShutdownGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// Shutdown signal that fires either manually or on drop by closing the channel
 #[derive(Debug)]
 pub struct Signal(oneshot::Sender<()>);
@@ -0,0 +1,228 @@
+//! A simple diskstore for blobs
+
+use crate::blobstore::{BlobStore, BlobStoreError};
+use parking_lot::RwLock;
+use reth_primitives::{BlobTransactionSidecar, H256};
+use std::{collections::HashMap, fs, io, path::PathBuf, sync::Arc};
+use tracing::{debug, trace};
+
+/// A blob store that stores blob data on disk.
+///
+/// Every sidecar is stored in a separate file named after the hash of the transaction it belongs
+/// to. The store keeps an in-memory index of all stored sidecars and their encoded size.
+#[derive(Clone, Debug)]
+pub struct DiskFileBlobStore {
+    inner: Arc<DiskFileBlobStoreInner>,
+}
+
+impl DiskFileBlobStore {
+    /// Opens and initializes a new disk file blob store.
+    ///
+    /// Blobs are only kept for transactions that are currently in the pool, which is not
+    /// persisted, hence any existing blob data in the directory is removed.
+    pub fn open(blob_dir: impl Into<PathBuf>) -> Result<Self, DiskFileBlobStoreError> {
+        let blob_dir = blob_dir.into();
+        let inner = DiskFileBlobStoreInner::new(blob_dir);
+        inner.delete_all()?;
+        inner.create_blob_dir()?;
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Returns the directory the blobs are stored in.
+    pub fn blob_dir(&self) -> &PathBuf {
+        &self.inner.blob_dir
+    }
+}
+
+impl BlobStore for DiskFileBlobStore {
+    fn insert(&self, tx: H256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+        self.inner.insert_one(tx, data)
+    }
+
+    fn insert_all(&self, txs: Vec<(H256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+        txs.into_iter().try_for_each(|(tx, data)| self.inner.insert_one(tx, data))
+    }
+
+    fn delete(&self, tx: H256) -> Result<(), BlobStoreError> {
+        self.inner.delete_one(tx)
+    }
+
+    fn delete_all(&self, txs: Vec<H256>) -> Result<(), BlobStoreError> {
+        txs.into_iter().try_for_each(|tx| self.inner.delete_one(tx))
+    }
+
+    fn get(&self, tx: H256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        self.inner.get_one(tx)
+    }
+
+    fn get_all(
+        &self,
+        txs: Vec<H256>,
+    ) -> Result<Vec<(H256, BlobTransactionSidecar)>, BlobStoreError> {
+        let mut res = Vec::with_capacity(txs.len());
+        for tx in txs {
+            if let Some(data) = self.inner.get_one(tx)? {
+                res.push((tx, data))
+            }
+        }
+        Ok(res)
+    }
+
+    fn get_exact(&self, txs: Vec<H256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+        txs.into_iter()
+            .map(|tx| self.inner.get_one(tx)?.ok_or(BlobStoreError::MissingSidecar(tx)))
+            .collect()
+    }
+
+    fn data_size_hint(&self) -> Option<usize> {
+        Some(self.inner.index.read().values().sum())
+    }
+
+    fn blobs_len(&self) -> usize {
+        self.inner.index.read().len()
+    }
+}
+
+#[derive(Debug)]
+struct DiskFileBlobStoreInner {
+    /// The directory the blob files are stored in.
+    blob_dir: PathBuf,
+    /// All sidecars that are currently stored on disk, with their encoded size in bytes.
+    index: RwLock<HashMap<H256, usize>>,
+}
+
+impl DiskFileBlobStoreInner {
+    /// Creates a new empty disk file blob store with the given directory.
+    fn new(blob_dir: PathBuf) -> Self {
+        Self { blob_dir, index: Default::default() }
+    }
+
+    /// Creates the directory where blobs will be stored on disk.
+    fn create_blob_dir(&self) -> Result<(), DiskFileBlobStoreError> {
+        debug!(target: "txpool::blob", blob_dir = ?self.blob_dir, "Creating blob store");
+        fs::create_dir_all(&self.blob_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))
+    }
+
+    /// Deletes the entire blob store.
+    fn delete_all(&self) -> Result<(), DiskFileBlobStoreError> {
+        match fs::remove_dir_all(&self.blob_dir) {
+            Ok(_) => {
+                debug!(target: "txpool::blob", blob_dir = ?self.blob_dir, "Removed blob store directory");
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(DiskFileBlobStoreError::Open(self.blob_dir.clone(), err)),
+        }
+        self.index.write().clear();
+        Ok(())
+    }
+
+    /// Returns the path to the blob file for the given transaction hash.
+    #[inline]
+    fn blob_disk_file(&self, tx: H256) -> PathBuf {
+        self.blob_dir.join(format!("{tx:x}"))
+    }
+
+    /// Encodes the sidecar and writes it to its file.
+    fn insert_one(&self, tx: H256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+        let encoded = serde_json::to_vec(&data)?;
+        let size = encoded.len();
+        let path = self.blob_disk_file(tx);
+        trace!(target: "txpool::blob", ?tx, ?path, "Writing blob file");
+        fs::write(&path, encoded).map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
+        self.index.write().insert(tx, size);
+        Ok(())
+    }
+
+    /// Removes the file of the sidecar, if it exists.
+    fn delete_one(&self, tx: H256) -> Result<(), BlobStoreError> {
+        if self.index.write().remove(&tx).is_none() {
+            return Ok(())
+        }
+        let path = self.blob_disk_file(tx);
+        trace!(target: "txpool::blob", ?tx, ?path, "Deleting blob file");
+        match fs::remove_file(&path) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(DiskFileBlobStoreError::DeleteFile(tx, path, err).into()),
+        }
+    }
+
+    /// Reads and decodes the sidecar from its file.
+    fn get_one(&self, tx: H256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        if !self.index.read().contains_key(&tx) {
+            return Ok(None)
+        }
+        let path = self.blob_disk_file(tx);
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(DiskFileBlobStoreError::ReadFile(tx, path, err).into()),
+        };
+        Ok(Some(serde_json::from_slice(&data)?))
+    }
+}
+
+/// Errors that can occur when interacting with a disk file blob store.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum DiskFileBlobStoreError {
+    /// Thrown during [DiskFileBlobStore::open] if the blob store directory cannot be opened.
+    #[error("failed to open blobstore at {0}: {1}")]
+    Open(PathBuf, io::Error),
+    #[error("[{0:?}] failed to read blob file at {1}: {2}")]
+    ReadFile(H256, PathBuf, io::Error),
+    #[error("[{0:?}] failed to write blob file at {1}: {2}")]
+    WriteFile(H256, PathBuf, io::Error),
+    #[error("[{0:?}] failed to delete blob file at {1}: {2}")]
+    DeleteFile(H256, PathBuf, io::Error),
+}
+
+impl From<DiskFileBlobStoreError> for BlobStoreError {
+    fn from(value: DiskFileBlobStoreError) -> Self {
+        BlobStoreError::Other(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tmp_store() -> (DiskFileBlobStore, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(dir.path().join("blobs")).unwrap();
+        (store, dir)
+    }
+
+    #[test]
+    fn disk_insert_get_delete() {
+        let (store, _dir) = tmp_store();
+        let tx = H256::random();
+        let sidecar = BlobTransactionSidecar::default();
+
+        store.insert(tx, sidecar.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 1);
+        assert!(store.blob_dir().join(format!("{tx:x}")).exists());
+        assert_eq!(store.get(tx).unwrap(), Some(sidecar.clone()));
+        assert_eq!(store.get_exact(vec![tx]).unwrap(), vec![sidecar]);
+
+        let missing = H256::random();
+        assert!(store.get(missing).unwrap().is_none());
+        assert!(store.get_exact(vec![missing]).is_err());
+
+        store.delete_all(vec![tx, missing]).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert!(store.get(tx).unwrap().is_none());
+    }
+
+    #[test]
+    fn open_clears_existing_blobs() {
+        let (store, dir) = tmp_store();
+        let tx = H256::random();
+        store.insert(tx, BlobTransactionSidecar::default()).unwrap();
+
+        let store = DiskFileBlobStore::open(dir.path().join("blobs")).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert!(!store.blob_dir().join(format!("{tx:x}")).exists());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::blobstore::{BlobStore, BlobStoreError};
+use parking_lot::RwLock;
+use reth_primitives::{BlobTransactionSidecar, H256};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// An in-memory blob store.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryBlobStore {
+    inner: Arc<InMemoryBlobStoreInner>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryBlobStoreInner {
+    /// Storage for all blob data.
+    store: RwLock<HashMap<H256, BlobTransactionSidecar>>,
+    /// Tracks the combined size of all stored sidecars.
+    data_size: AtomicUsize,
+}
+
+impl InMemoryBlobStoreInner {
+    /// Adds the size of an inserted sidecar.
+    fn add_size(&self, add: usize) {
+        self.data_size.fetch_add(add, Ordering::Relaxed);
+    }
+
+    /// Subtracts the size of the removed sidecar.
+    fn sub_size(&self, sub: usize) {
+        let _ = self.data_size.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |size| {
+            Some(size.saturating_sub(sub))
+        });
+    }
+}
+
+impl BlobStore for InMemoryBlobStore {
+    fn insert(&self, tx: H256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+        let mut store = self.inner.store.write();
+        self.inner.add_size(data.size());
+        if let Some(replaced) = store.insert(tx, data) {
+            self.inner.sub_size(replaced.size());
+        }
+        Ok(())
+    }
+
+    fn insert_all(&self, txs: Vec<(H256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+        if txs.is_empty() {
+            return Ok(())
+        }
+        let mut store = self.inner.store.write();
+        for (tx, data) in txs {
+            self.inner.add_size(data.size());
+            if let Some(replaced) = store.insert(tx, data) {
+                self.inner.sub_size(replaced.size());
+            }
+        }
+        Ok(())
+    }
+
+    fn delete(&self, tx: H256) -> Result<(), BlobStoreError> {
+        let mut store = self.inner.store.write();
+        if let Some(removed) = store.remove(&tx) {
+            self.inner.sub_size(removed.size());
+        }
+        Ok(())
+    }
+
+    fn delete_all(&self, txs: Vec<H256>) -> Result<(), BlobStoreError> {
+        if txs.is_empty() {
+            return Ok(())
+        }
+        let mut store = self.inner.store.write();
+        for tx in txs {
+            if let Some(removed) = store.remove(&tx) {
+                self.inner.sub_size(removed.size());
+            }
+        }
+        Ok(())
+    }
+
+    fn get(&self, tx: H256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        let store = self.inner.store.read();
+        Ok(store.get(&tx).cloned())
+    }
+
+    fn get_all(
+        &self,
+        txs: Vec<H256>,
+    ) -> Result<Vec<(H256, BlobTransactionSidecar)>, BlobStoreError> {
+        let store = self.inner.store.read();
+        Ok(txs.into_iter().filter_map(|tx| store.get(&tx).map(|item| (tx, item.clone()))).collect())
+    }
+
+    fn get_exact(&self, txs: Vec<H256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+        let store = self.inner.store.read();
+        txs.into_iter()
+            .map(|tx| store.get(&tx).cloned().ok_or(BlobStoreError::MissingSidecar(tx)))
+            .collect()
+    }
+
+    fn data_size_hint(&self) -> Option<usize> {
+        Some(self.inner.data_size.load(Ordering::Relaxed))
+    }
+
+    fn blobs_len(&self) -> usize {
+        self.inner.store.read().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_get_delete() {
+        let store = InMemoryBlobStore::default();
+        let tx = H256::random();
+        let sidecar = BlobTransactionSidecar::default();
+
+        store.insert(tx, sidecar.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 1);
+        assert_eq!(store.get(tx).unwrap(), Some(sidecar.clone()));
+        assert_eq!(store.get_exact(vec![tx]).unwrap(), vec![sidecar]);
+
+        let missing = H256::random();
+        assert!(store.get(missing).unwrap().is_none());
+        assert_eq!(store.get_all(vec![tx, missing]).unwrap().len(), 1);
+        assert!(matches!(
+            store.get_exact(vec![tx, missing]),
+            Err(BlobStoreError::MissingSidecar(hash)) if hash == missing
+        ));
+
+        store.delete(tx).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert_eq!(store.data_size_hint(), Some(0));
+    }
+}
@@ -1,6 +1,13 @@
+use crate::TransactionOrigin;
+use reth_primitives::{Address, EIP4844_TX_TYPE_ID};
+use std::collections::HashSet;
+
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// The default maximum number of transactions of one sender in the queued sub-pool.
+pub const TXPOOL_MAX_QUEUED_ACCOUNT_SLOTS_PER_SENDER: usize = 8;
+
 /// The default maximum allowed number of transactions in the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 
@@ -22,10 +29,19 @@ pub struct PoolConfig {
     pub basefee_limit: SubPoolLimit,
     /// Max number of transaction in the queued sub-pool
     pub queued_limit: SubPoolLimit,
+    /// Max number of transaction in the blob sub-pool
+    pub blob_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Max number of transactions per account in the queued sub-pool.
+    ///
+    /// If a sender exceeds this limit, its queued transactions with the highest nonces are
+    /// evicted.
+    pub max_queued_account_slots: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
-    pub price_bump: u128,
+    pub price_bumps: PriceBumpConfig,
+    /// How local transactions are exempt from the pool limits.
+    pub local_transactions_config: LocalTransactionConfig,
 }
 
 impl Default for PoolConfig {
@@ -34,8 +50,11 @@ impl Default for PoolConfig {
             pending_limit: Default::default(),
             basefee_limit: Default::default(),
             queued_limit: Default::default(),
+            blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-            price_bump: PriceBumpConfig::default().default_price_bump,
+            max_queued_account_slots: TXPOOL_MAX_QUEUED_ACCOUNT_SLOTS_PER_SENDER,
+            price_bumps: Default::default(),
+            local_transactions_config: Default::default(),
         }
     }
 }
@@ -76,6 +95,17 @@ pub struct PriceBumpConfig {
     pub replace_blob_tx_price_bump: u128,
 }
 
+impl PriceBumpConfig {
+    /// Returns the price bump required to replace the given transaction type.
+    #[inline]
+    pub(crate) fn price_bump(&self, tx_type: u8) -> u128 {
+        if tx_type == EIP4844_TX_TYPE_ID {
+            return self.replace_blob_tx_price_bump
+        }
+        self.default_price_bump
+    }
+}
+
 impl Default for PriceBumpConfig {
     fn default() -> Self {
         Self {
@@ -84,3 +114,27 @@ impl Default for PriceBumpConfig {
         }
     }
 }
+
+/// Configuration of the exemptions of local transactions from the pool limits.
+///
+/// Local transactions are neither limited by the account slots of their sender nor evicted when a
+/// sub-pool exceeds its limits.
+#[derive(Debug, Clone, Default)]
+pub struct LocalTransactionConfig {
+    /// Treat local transactions like external ones, without any exemptions.
+    pub no_exemptions: bool,
+    /// Addresses whose transactions are treated as local, regardless of their origin.
+    pub local_addresses: HashSet<Address>,
+}
+
+impl LocalTransactionConfig {
+    /// Returns whether a transaction of the sender with the given origin is exempt from the pool
+    /// limits.
+    #[inline]
+    pub fn is_local(&self, origin: TransactionOrigin, sender: &Address) -> bool {
+        if self.no_exemptions {
+            return false
+        }
+        origin.is_local() || self.local_addresses.contains(sender)
+    }
+}
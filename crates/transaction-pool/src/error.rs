@@ -1,6 +1,6 @@
 //! Transaction pool errors
 
-use reth_primitives::{Address, InvalidTransactionError, TxHash};
+use reth_primitives::{Address, BlobTransactionValidationError, InvalidTransactionError, TxHash};
 
 /// Transaction pool result type.
 pub type PoolResult<T> = Result<T, PoolError>;
@@ -36,6 +36,10 @@ pub enum PoolError {
     /// respect the size limits of the pool.
     #[error("[{0:?}] Transaction discarded outright due to pool size constraints.")]
     DiscardedOnInsert(TxHash),
+    /// Thrown when a new transaction would replace an existing transaction of the sender with the
+    /// same nonce, but one of them is an EIP-4844 blob transaction and the other is not.
+    #[error("[{0:?}] Transaction conflicts with an existing transaction of type {1}.")]
+    ExistingConflictingTransactionType(TxHash, u8),
     /// Thrown when the transaction is considered invalid.
     #[error("[{0:?}] {1:?}")]
     InvalidTransaction(TxHash, InvalidPoolTransactionError),
@@ -56,6 +60,7 @@ impl PoolError {
             PoolError::FeeCapBelowMinimumProtocolFeeCap(hash, _) => hash,
             PoolError::SpammerExceededCapacity(_, hash) => hash,
             PoolError::DiscardedOnInsert(hash) => hash,
+            PoolError::ExistingConflictingTransactionType(hash, _) => hash,
             PoolError::InvalidTransaction(hash, _) => hash,
             PoolError::Other(hash, _) => hash,
         }
@@ -102,6 +107,10 @@ impl PoolError {
                 // valid tx but dropped due to size constraints
                 false
             }
+            PoolError::ExistingConflictingTransactionType(_, _) => {
+                // depends on the existing transactions of the sender in the pool
+                false
+            }
             PoolError::InvalidTransaction(_, err) => {
                 // transaction rejected because it violates constraints
                 err.is_bad_transaction()
@@ -114,6 +123,22 @@ impl PoolError {
     }
 }
 
+/// Represents all errors that can happen when validating transactions for the pool for EIP-4844
+/// transactions
+#[derive(Debug, thiserror::Error)]
+pub enum Eip4844PoolTransactionError {
+    /// Thrown if an EIP-4844 transaction was submitted without its blob sidecar.
+    #[error("blob sidecar is missing")]
+    MissingEip4844BlobSidecar,
+    /// Thrown if the KZG proofs of the blob sidecar don't verify or the commitments don't match
+    /// the transaction's versioned hashes.
+    #[error("blob sidecar does not match the transaction")]
+    InvalidEip4844Blob,
+    /// Thrown if the blob sidecar could not be validated.
+    #[error("failed to validate blob sidecar: {0:?}")]
+    InvalidEip4844BlobSidecar(BlobTransactionValidationError),
+}
+
 /// Represents errors that can happen when validating transactions for the pool
 ///
 /// See [TransactionValidator](crate::TransactionValidator).
@@ -138,6 +163,9 @@ pub enum InvalidPoolTransactionError {
     /// Thrown if the transaction's fee is below the minimum fee
     #[error("transaction underpriced")]
     Underpriced,
+    /// Thrown if the EIP-4844 transaction is invalid, e.g. due to an invalid blob sidecar.
+    #[error(transparent)]
+    Eip4844(#[from] Eip4844PoolTransactionError),
     /// Any other error that occurred while inserting/validating that is transaction specific
     #[error("{0:?}")]
     Other(Box<dyn PoolTransactionError>),
@@ -193,6 +221,17 @@ impl InvalidPoolTransactionError {
                 // local setting
                 false
             }
+            InvalidPoolTransactionError::Eip4844(err) => match err {
+                Eip4844PoolTransactionError::MissingEip4844BlobSidecar => {
+                    // the sidecar could have been dropped before it reached the pool
+                    false
+                }
+                Eip4844PoolTransactionError::InvalidEip4844Blob |
+                Eip4844PoolTransactionError::InvalidEip4844BlobSidecar(_) => {
+                    // the sidecar does not belong to the transaction
+                    true
+                }
+            },
             InvalidPoolTransactionError::Other(err) => err.is_bad_transaction(),
         }
     }
@@ -64,6 +64,14 @@
 //!   - remove mined transactions
 //!   - update using account changes: balance changes
 //!   - base fee updates
+//!   - blob fee updates
+//!
+//! ### Blob transactions
+//!
+//! The sidecars of EIP-4844 blob transactions are not kept in memory alongside the transaction,
+//! but are moved into a separate [`BlobStore`](crate::blobstore::BlobStore) when the transaction is
+//! added to the pool. The blobs can be retrieved from the pool by transaction hash, for example
+//! when building a payload.
 //!
 //! ## Implementation details
 //!
@@ -95,9 +103,11 @@
 //! use reth_provider::{ChainSpecProvider, StateProviderFactory};
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::{EthTransactionValidator, Pool, TransactionPool};
+//! use reth_transaction_pool::blobstore::InMemoryBlobStore;
 //!  async fn t<C>(client: C)  where C: StateProviderFactory + ChainSpecProvider + Clone + 'static{
 //!     let pool = Pool::eth_pool(
 //!         EthTransactionValidator::new(client, MAINNET.clone(), TokioTaskExecutor::default()),
+//!         InMemoryBlobStore::default(),
 //!         Default::default(),
 //!     );
 //!   let mut transactions = pool.pending_transactions_listener();
@@ -120,6 +130,7 @@
 //! use reth_provider::{BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, StateProviderFactory};
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::{EthTransactionValidator, Pool};
+//! use reth_transaction_pool::blobstore::InMemoryBlobStore;
 //! use reth_transaction_pool::maintain::maintain_transaction_pool_future;
 //!  async fn t<C, St>(client: C, stream: St)
 //!    where C: StateProviderFactory + BlockReaderIdExt + ChainSpecProvider + Clone + 'static,
@@ -127,6 +138,7 @@
 //!     {
 //!     let pool = Pool::eth_pool(
 //!         EthTransactionValidator::new(client.clone(), MAINNET.clone(), TokioTaskExecutor::default()),
+//!         InMemoryBlobStore::default(),
 //!         Default::default(),
 //!     );
 //!
@@ -140,9 +152,12 @@
 //!
 //! - `serde` (default): Enable serde support
 //! - `test-utils`: Export utilities for testing
-use crate::pool::PoolInner;
+use crate::{
+    blobstore::{BlobStore, BlobStoreError},
+    pool::PoolInner,
+};
 use aquamarine as _;
-use reth_primitives::{Address, TxHash, U256};
+use reth_primitives::{Address, BlobTransactionSidecar, TxHash, U256};
 use reth_provider::StateProviderFactory;
 use std::{
     collections::{HashMap, HashSet},
@@ -153,15 +168,20 @@ use tracing::{instrument, trace};
 
 pub use crate::{
     config::{
-        PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP,
-        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        TXPOOL_MAX_QUEUED_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
         TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
-        state::SubPool, AllTransactionsEvents, FullTransactionEvent, TransactionEvent,
-        TransactionEvents,
+        state::SubPool, AllTransactionsEvents, DiscardReason, FullTransactionEvent,
+        TransactionEvent, TransactionEvents,
+    },
+    selection::{
+        select_best_transactions, BestTransactionsFor, ConfiguredSelection, InclusionList,
+        PoolOrderSelection, SelectedTransactions, TransactionSelection,
     },
     traits::{
         AllPoolTransactions, BestTransactions, BlockInfo, CanonicalStateUpdate, ChangedAccount,
@@ -175,6 +195,7 @@ pub use crate::{
     },
 };
 
+pub mod blobstore;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
@@ -185,6 +206,7 @@ pub mod validate;
 mod config;
 mod identifier;
 mod ordering;
+mod selection;
 mod traits;
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -193,25 +215,26 @@ pub mod test_utils;
 
 /// A shareable, generic, customizable `TransactionPool` implementation.
 #[derive(Debug)]
-pub struct Pool<V: TransactionValidator, T: TransactionOrdering> {
+pub struct Pool<V, T: TransactionOrdering, S> {
     /// Arc'ed instance of the pool internals
-    pool: Arc<PoolInner<V, T>>,
+    pool: Arc<PoolInner<V, T, S>>,
 }
 
 // === impl Pool ===
 
-impl<V, T> Pool<V, T>
+impl<V, T, S> Pool<V, T, S>
 where
     V: TransactionValidator,
     T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+    S: BlobStore,
 {
     /// Create a new transaction pool instance.
-    pub fn new(validator: V, ordering: T, config: PoolConfig) -> Self {
-        Self { pool: Arc::new(PoolInner::new(validator, ordering, config)) }
+    pub fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
+        Self { pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)) }
     }
 
     /// Returns the wrapped pool.
-    pub(crate) fn inner(&self) -> &PoolInner<V, T> {
+    pub(crate) fn inner(&self) -> &PoolInner<V, T, S> {
         &self.pool
     }
 
@@ -260,10 +283,15 @@ where
     }
 }
 
-impl<Client>
-    Pool<EthTransactionValidator<Client, PooledTransaction>, CoinbaseTipOrdering<PooledTransaction>>
+impl<Client, S>
+    Pool<
+        EthTransactionValidator<Client, PooledTransaction>,
+        CoinbaseTipOrdering<PooledTransaction>,
+        S,
+    >
 where
     Client: StateProviderFactory + Clone + 'static,
+    S: BlobStore,
 {
     /// Returns a new [Pool] that uses the default [EthTransactionValidator] when validating
     /// [PooledTransaction]s and ords via [CoinbaseTipOrdering]
@@ -275,27 +303,31 @@ where
     /// use reth_primitives::MAINNET;
     /// use reth_tasks::TokioTaskExecutor;
     /// use reth_transaction_pool::{EthTransactionValidator, Pool};
+    /// use reth_transaction_pool::blobstore::InMemoryBlobStore;
     /// # fn t<C>(client: C)  where C: StateProviderFactory + Clone + 'static{
     ///     let pool = Pool::eth_pool(
     ///         EthTransactionValidator::new(client, MAINNET.clone(), TokioTaskExecutor::default()),
+    ///         InMemoryBlobStore::default(),
     ///         Default::default(),
     ///     );
     /// # }
     /// ```
     pub fn eth_pool(
         validator: EthTransactionValidator<Client, PooledTransaction>,
+        blob_store: S,
         config: PoolConfig,
     ) -> Self {
-        Self::new(validator, CoinbaseTipOrdering::default(), config)
+        Self::new(validator, CoinbaseTipOrdering::default(), blob_store, config)
     }
 }
 
 /// implements the `TransactionPool` interface for various transaction pool API consumers.
 #[async_trait::async_trait]
-impl<V, T> TransactionPool for Pool<V, T>
+impl<V, T, S> TransactionPool for Pool<V, T, S>
 where
     V: TransactionValidator,
     T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+    S: BlobStore,
 {
     type Transaction = T::Transaction;
 
@@ -432,12 +464,31 @@ where
     fn unique_senders(&self) -> HashSet<Address> {
         self.pool.unique_senders()
     }
+
+    fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        self.pool.blob_store().get(tx_hash)
+    }
+
+    fn get_all_blobs(
+        &self,
+        tx_hashes: Vec<TxHash>,
+    ) -> Result<Vec<(TxHash, BlobTransactionSidecar)>, BlobStoreError> {
+        self.pool.blob_store().get_all(tx_hashes)
+    }
+
+    fn get_all_blobs_exact(
+        &self,
+        tx_hashes: Vec<TxHash>,
+    ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+        self.pool.blob_store().get_exact(tx_hashes)
+    }
 }
 
-impl<V: TransactionValidator, T: TransactionOrdering> TransactionPoolExt for Pool<V, T>
+impl<V: TransactionValidator, T: TransactionOrdering, S> TransactionPoolExt for Pool<V, T, S>
 where
     V: TransactionValidator,
     T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+    S: BlobStore,
 {
     #[instrument(skip(self), target = "txpool")]
     fn set_block_info(&self, info: BlockInfo) {
@@ -454,7 +505,7 @@ where
     }
 }
 
-impl<V: TransactionValidator, T: TransactionOrdering> Clone for Pool<V, T> {
+impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
     fn clone(&self) -> Self {
         Self { pool: Arc::clone(&self.pool) }
     }
@@ -3,27 +3,33 @@
 use crate::{
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPoolExt},
-    BlockInfo, TransactionPool,
+    BlockInfo, PoolTransaction, TransactionOrigin, TransactionPool,
 };
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
 };
-use reth_primitives::{Address, BlockHash, BlockNumberOrTag, FromRecoveredTransaction};
+use reth_primitives::{
+    Address, BlockHash, BlockNumberOrTag, FromRecoveredTransaction, IntoRecoveredTransaction,
+    TransactionSigned, TransactionSignedEcRecovered,
+};
 use reth_provider::{
     BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, PostState, StateProviderFactory,
 };
-use reth_tasks::TaskSpawner;
+use reth_rlp::{Decodable, Encodable};
+use reth_tasks::{shutdown::GracefulShutdown, TaskSpawner};
 use std::{
     borrow::Borrow,
     collections::HashSet,
     hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::Duration,
 };
 use tokio::sync::oneshot;
-use tracing::{debug, trace};
+use tracing::{debug, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MaintainPoolConfig {
     /// Maximum (reorg) depth we handle when updating the transaction pool: `new.number -
     /// last_seen.number`
@@ -34,11 +40,15 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 250
     pub max_reload_accounts: usize,
+    /// Settings for the journal of local transactions, if enabled.
+    ///
+    /// Default: None
+    pub local_transactions_journal: Option<LocalTransactionJournalConfig>,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 250 }
+        Self { max_update_depth: 64, max_reload_accounts: 250, local_transactions_journal: None }
     }
 }
 
@@ -65,6 +75,10 @@ where
 /// Maintains the state of the transaction pool by handling new blocks and reorgs.
 ///
 /// This listens for any new blocks and reorgs and updates the transaction pool's state accordingly
+///
+/// If the [LocalTransactionJournalConfig] is set, the journaled transactions are re-inserted into
+/// the pool on startup and the journal is rewritten every
+/// [LocalTransactionJournalConfig::rejournal_interval].
 pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     client: Client,
     pool: P,
@@ -78,7 +92,8 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, local_transactions_journal } =
+        config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.block_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = latest.seal_slow();
@@ -89,10 +104,42 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
             pending_basefee: latest
                 .next_block_base_fee(chain_spec.base_fee_params)
                 .unwrap_or_default(),
+            pending_blob_fee: latest.next_block_blob_fee().map(|fee| fee as u128),
         };
         pool.set_block_info(info);
     }
 
+    // restore the local transactions of the previous run, after the pool points to latest state so
+    // they are validated against it
+    if let Some(journal) = &local_transactions_journal {
+        match reinsert_journaled_transactions(&pool, &journal.journal_path).await {
+            Ok(inserted) => {
+                info!(
+                    target: "txpool",
+                    journal=?journal.journal_path,
+                    inserted,
+                    "Loaded transactions from journal"
+                )
+            }
+            Err(err) => {
+                warn!(
+                    target: "txpool",
+                    ?err,
+                    journal=?journal.journal_path,
+                    "Failed to load transactions from journal"
+                )
+            }
+        }
+    }
+
+    // rewrites the journal periodically if enabled
+    let mut rejournal_interval = local_transactions_journal.as_ref().map(|journal| {
+        tokio::time::interval_at(
+            tokio::time::Instant::now() + journal.rejournal_interval,
+            journal.rejournal_interval,
+        )
+    });
+
     // keeps track of any dirty accounts that we know of are out of sync with the pool
     let mut dirty_addresses = HashSet::new();
 
@@ -167,6 +214,33 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 }
                 event = ev;
             }
+            _ = tick_rejournal_interval(rejournal_interval.as_mut()) => {
+                if let Some(journal) = &local_transactions_journal {
+                    // collect the transactions here and write the file in a separate task
+                    let (written, buf) = encode_local_transactions(&pool);
+                    let path = journal.journal_path.clone();
+                    task_spawner.spawn(
+                        async move {
+                            if let Err(err) = write_journal_file(&path, buf).await {
+                                warn!(
+                                    target: "txpool",
+                                    ?err,
+                                    journal=?path,
+                                    "Failed to write transactions journal"
+                                );
+                            } else {
+                                trace!(
+                                    target: "txpool",
+                                    journal=?path,
+                                    written,
+                                    "wrote transactions journal"
+                                );
+                            }
+                        }
+                        .boxed(),
+                    );
+                }
+            }
         }
 
         // handle the result of the account reload
@@ -214,6 +288,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 // base fee for the next block: `new_tip+1`
                 let pending_block_base_fee =
                     new_tip.next_block_base_fee(chain_spec.base_fee_params).unwrap_or_default();
+                let pending_block_blob_fee = new_tip.next_block_blob_fee().map(|fee| fee as u128);
 
                 // we know all changed account in the new chain
                 let new_changed_accounts: HashSet<_> =
@@ -270,6 +345,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     hash: new_tip.hash,
                     number: new_tip.number,
                     pending_block_base_fee,
+                    pending_block_blob_fee,
                     changed_accounts,
                     // all transactions mined in the new chain need to be removed from the pool
                     mined_transactions: new_mined_transactions.into_iter().collect(),
@@ -292,6 +368,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 // base fee for the next block: `tip+1`
                 let pending_block_base_fee =
                     tip.next_block_base_fee(chain_spec.base_fee_params).unwrap_or_default();
+                let pending_block_blob_fee = tip.next_block_blob_fee().map(|fee| fee as u128);
 
                 let first_block = blocks.first();
                 trace!(
@@ -312,6 +389,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                         last_seen_block_hash: tip.hash,
                         last_seen_block_number: tip.number,
                         pending_basefee: pending_block_base_fee,
+                        pending_blob_fee: pending_block_blob_fee,
                     };
                     pool.set_block_info(info);
                     continue
@@ -339,6 +417,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     hash: tip.hash,
                     number: tip.number,
                     pending_block_base_fee,
+                    pending_block_blob_fee,
                     changed_accounts,
                     mined_transactions,
                     timestamp: tip.timestamp,
@@ -349,6 +428,186 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     }
 }
 
+/// Settings for the journal of local transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalTransactionJournalConfig {
+    /// Path to the journal file.
+    pub journal_path: PathBuf,
+    /// Interval at which the journal is rewritten.
+    ///
+    /// Default: 1h
+    pub rejournal_interval: Duration,
+}
+
+impl LocalTransactionJournalConfig {
+    /// Creates a new config that journals the local transactions to the given file.
+    pub fn with_journal_path(journal_path: impl Into<PathBuf>) -> Self {
+        Self { journal_path: journal_path.into(), rejournal_interval: Duration::from_secs(60 * 60) }
+    }
+}
+
+/// Returns a spawnable future that writes the journal of local transactions on shutdown.
+///
+/// See [write_journal_on_shutdown].
+pub fn write_journal_on_shutdown_future<P>(
+    pool: P,
+    config: LocalTransactionJournalConfig,
+    shutdown: GracefulShutdown,
+) -> BoxFuture<'static, ()>
+where
+    P: TransactionPool + 'static,
+{
+    async move {
+        write_journal_on_shutdown(pool, config, shutdown).await;
+    }
+    .boxed()
+}
+
+/// Waits for the [GracefulShutdown] signal and writes the local transactions of the pool to the
+/// journal before the node exits.
+///
+/// The shutdown is delayed until the journal is written.
+pub async fn write_journal_on_shutdown<P>(
+    pool: P,
+    config: LocalTransactionJournalConfig,
+    shutdown: GracefulShutdown,
+) where
+    P: TransactionPool + 'static,
+{
+    let LocalTransactionJournalConfig { journal_path, .. } = config;
+
+    let guard = shutdown.await;
+    match write_journal(&pool, &journal_path).await {
+        Ok(written) => {
+            info!(target: "txpool", journal=?journal_path, written, "Wrote transactions journal")
+        }
+        Err(err) => {
+            warn!(
+                target: "txpool",
+                ?err,
+                journal=?journal_path,
+                "Failed to write transactions journal"
+            )
+        }
+    }
+    drop(guard);
+}
+
+/// Loads the transactions from the journal at the given path and adds them to the pool as local
+/// transactions, so they are validated against the current state again.
+///
+/// Returns the number of transactions that were inserted.
+pub async fn reinsert_journaled_transactions<P>(pool: &P, path: &Path) -> std::io::Result<usize>
+where
+    P: TransactionPool,
+{
+    let transactions = load_journal(path)
+        .await?
+        .into_iter()
+        .map(<P as TransactionPool>::Transaction::from_recovered_transaction)
+        .collect::<Vec<_>>();
+    if transactions.is_empty() {
+        return Ok(0)
+    }
+
+    let inserted = match pool.add_transactions(TransactionOrigin::Local, transactions).await {
+        Ok(results) => results.into_iter().filter(Result::is_ok).count(),
+        Err(err) => {
+            debug!(target: "txpool", ?err, "failed to reinsert journaled transactions");
+            0
+        }
+    };
+    Ok(inserted)
+}
+
+/// Writes the local transactions of the pool to the journal at the given path.
+///
+/// Blob transactions are not journaled because their sidecars are not part of the journal.
+///
+/// Returns the number of transactions that were written.
+pub async fn write_journal<P>(pool: &P, path: &Path) -> std::io::Result<usize>
+where
+    P: TransactionPool,
+{
+    let (written, buf) = encode_local_transactions(pool);
+    write_journal_file(path, buf).await?;
+    Ok(written)
+}
+
+/// Returns the number of local transactions of the pool and their RLP encoding.
+fn encode_local_transactions<P>(pool: &P) -> (usize, Vec<u8>)
+where
+    P: TransactionPool,
+{
+    let transactions = pool
+        .pooled_transactions()
+        .into_iter()
+        .filter(|tx| !tx.transaction.is_eip4844())
+        .filter(|tx| tx.origin.is_local())
+        .collect::<Vec<_>>();
+
+    let mut buf = Vec::new();
+    for tx in &transactions {
+        tx.transaction.to_recovered_transaction().into_signed().encode(&mut buf);
+    }
+    (transactions.len(), buf)
+}
+
+/// Waits for the next tick of the rejournal interval, or forever if the journal is disabled.
+async fn tick_rejournal_interval(interval: Option<&mut tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Writes the encoded transactions to the journal at the given path.
+async fn write_journal_file(path: &Path, buf: Vec<u8>) -> std::io::Result<()> {
+    // write to a temporary file first so a crash can't leave a truncated journal behind
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, buf).await?;
+    tokio::fs::rename(tmp_path, path).await
+}
+
+/// Loads all transactions from the journal at the given path.
+///
+/// A missing journal is treated as empty. If the journal is corrupted, all transactions up to the
+/// corrupted entry are returned.
+pub async fn load_journal(path: &Path) -> std::io::Result<Vec<TransactionSignedEcRecovered>> {
+    let data = match tokio::fs::read(path).await {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut buf = data.as_slice();
+    let mut transactions = Vec::new();
+    while !buf.is_empty() {
+        match TransactionSigned::decode(&mut buf) {
+            Ok(tx) => {
+                if let Some(tx) = tx.into_ecrecovered() {
+                    transactions.push(tx);
+                }
+            }
+            Err(err) => {
+                warn!(
+                    target: "txpool",
+                    ?err,
+                    journal=?path,
+                    "Failed to decode journaled transaction"
+                );
+                break
+            }
+        }
+    }
+    Ok(transactions)
+}
+
 /// Keeps track of the pool's state, whether the accounts in the pool are in sync with the actual
 /// state.
 #[derive(Debug, Eq, PartialEq)]
@@ -444,6 +703,40 @@ fn changed_accounts_iter(state: &PostState) -> impl Iterator<Item = ChangedAccou
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        blobstore::InMemoryBlobStore, noop::MockTransactionValidator, CoinbaseTipOrdering, Pool,
+        PooledTransaction,
+    };
+    use reth_primitives::{sign_message, Transaction, TransactionKind, TxEip1559, H256};
+    use reth_tasks::TaskManager;
+
+    fn journal_pool() -> Pool<
+        MockTransactionValidator<PooledTransaction>,
+        CoinbaseTipOrdering<PooledTransaction>,
+        InMemoryBlobStore,
+    > {
+        Pool::new(
+            MockTransactionValidator::default(),
+            CoinbaseTipOrdering::default(),
+            InMemoryBlobStore::default(),
+            Default::default(),
+        )
+    }
+
+    fn signed_transaction(nonce: u64) -> TransactionSignedEcRecovered {
+        let tx = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 20_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TransactionKind::Call(Address::random()),
+            value: 1,
+            ..Default::default()
+        });
+        let signature = sign_message(H256::from_low_u64_be(0x1337), tx.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(tx, signature).into_ecrecovered().unwrap()
+    }
 
     #[test]
     fn changed_acc_entry() {
@@ -452,4 +745,69 @@ mod tests {
         copy.nonce = 10;
         assert!(changed_acc.eq(&ChangedAccountEntry(copy)));
     }
+
+    #[tokio::test]
+    async fn missing_journal_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_journal(&dir.path().join("transactions.rlp")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn restart_pool_from_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transactions.rlp");
+
+        let local = signed_transaction(0);
+        let external = signed_transaction(1);
+
+        let pool = journal_pool();
+        pool.add_transaction(
+            TransactionOrigin::Local,
+            PooledTransaction::from_recovered_transaction(local.clone()),
+        )
+        .await
+        .unwrap();
+        pool.add_external_transaction(PooledTransaction::from_recovered_transaction(
+            external.clone(),
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(write_journal(&pool, &path).await.unwrap(), 1);
+        assert_eq!(load_journal(&path).await.unwrap(), vec![local.clone()]);
+
+        // restart with a fresh pool
+        let pool = journal_pool();
+        assert_eq!(reinsert_journaled_transactions(&pool, &path).await.unwrap(), 1);
+        let restored = pool.get(&local.hash()).unwrap();
+        assert!(restored.origin.is_local());
+        assert!(!pool.contains(&external.hash()));
+    }
+
+    #[test]
+    fn write_journal_on_graceful_shutdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transactions.rlp");
+        let local = signed_transaction(0);
+
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor = manager.executor();
+
+        let pool = journal_pool();
+        runtime
+            .block_on(pool.add_transaction(
+                TransactionOrigin::Local,
+                PooledTransaction::from_recovered_transaction(local.clone()),
+            ))
+            .unwrap();
+
+        let config = LocalTransactionJournalConfig::with_journal_path(&path);
+        executor.spawn_critical_with_graceful_shutdown_signal("journal", |shutdown| {
+            write_journal_on_shutdown_future(pool, config, shutdown)
+        });
+
+        assert!(manager.graceful_shutdown_with_timeout(Duration::from_secs(5)));
+        assert_eq!(runtime.block_on(load_journal(&path)).unwrap(), vec![local]);
+    }
 }
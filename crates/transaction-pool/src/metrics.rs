@@ -15,6 +15,10 @@ pub struct TxPoolMetrics {
     pub(crate) invalid_transactions: Counter,
     /// Number of removed transactions from the pool
     pub(crate) removed_transactions: Counter,
+    /// Number of transactions evicted because their sub-pool exceeded its limits
+    pub(crate) sub_pool_limit_evicted_transactions: Counter,
+    /// Number of transactions evicted because their sender exceeded its queued slots
+    pub(crate) sender_limit_evicted_transactions: Counter,
 
     /// Number of transactions in the pending sub-pool
     pub(crate) pending_pool_transactions: Gauge,
@@ -31,7 +35,12 @@ pub struct TxPoolMetrics {
     /// Total amount of memory used by the transactions in the queued sub-pool in bytes
     pub(crate) queued_pool_size_bytes: Gauge,
 
-    /// Number of all transactions of all sub-pools: pending + basefee + queued
+    /// Number of transactions in the blob sub-pool
+    pub(crate) blob_pool_transactions: Gauge,
+    /// Total amount of memory used by the transactions in the blob sub-pool in bytes
+    pub(crate) blob_pool_size_bytes: Gauge,
+
+    /// Number of all transactions of all sub-pools: pending + basefee + queued + blob
     pub(crate) total_transactions: Gauge,
 
     /// How often the pool was updated after the canonical state changed
@@ -0,0 +1,319 @@
+use crate::{
+    identifier::TransactionId, pool::size::SizeTracker, PoolTransaction, ValidPoolTransaction,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+/// A set of validated blob transactions in the pool that are __not pending__.
+///
+/// The purpose of this pool is keep track of blob transactions that are queued and to evict the
+/// worst blob transactions once the sub-pool is full.
+///
+/// This expects that certain constraints are met:
+///   - blob transactions are always gap less
+pub(crate) struct BlobTransactions<T: PoolTransaction> {
+    /// Keeps track of transactions inserted in the pool.
+    ///
+    /// This way we can determine when transactions were submitted to the pool.
+    submission_id: u64,
+    /// _All_ Transactions that are currently inside the pool grouped by their identifier.
+    by_id: BTreeMap<TransactionId, BlobTransaction<T>>,
+    /// _All_ transactions sorted by blob priority.
+    all: BTreeSet<BlobTransaction<T>>,
+    /// Keeps track of the size of this pool.
+    ///
+    /// See also [`PoolTransaction::size`](crate::traits::PoolTransaction::size).
+    size_of: SizeTracker,
+}
+
+// === impl BlobTransactions ===
+
+impl<T: PoolTransaction> BlobTransactions<T> {
+    /// Adds a new blob transaction to the sub-pool.
+    ///
+    /// # Panics
+    ///
+    ///   - If the transaction is not a blob tx.
+    ///   - If the transaction is already included.
+    pub(crate) fn add_transaction(&mut self, tx: Arc<ValidPoolTransaction<T>>) {
+        assert!(tx.is_eip4844(), "transaction is not a blob tx");
+        let id = *tx.id();
+        assert!(!self.by_id.contains_key(&id), "transaction already included {:?}", id);
+        let submission_id = self.next_id();
+
+        // keep track of size
+        self.size_of += tx.size();
+
+        let ord = BlobOrd {
+            submission_id,
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas().unwrap_or_default(),
+            max_fee_per_gas: tx.max_fee_per_gas(),
+        };
+        let transaction = BlobTransaction { ord, transaction: tx };
+
+        self.by_id.insert(id, transaction.clone());
+        self.all.insert(transaction);
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.submission_id;
+        self.submission_id = self.submission_id.wrapping_add(1);
+        id
+    }
+
+    /// Removes the transaction from the pool
+    pub(crate) fn remove_transaction(
+        &mut self,
+        id: &TransactionId,
+    ) -> Option<Arc<ValidPoolTransaction<T>>> {
+        // remove from queues
+        let tx = self.by_id.remove(id)?;
+
+        self.all.remove(&tx);
+
+        // keep track of size
+        self.size_of -= tx.transaction.size();
+
+        Some(tx.transaction)
+    }
+
+    /// Returns the ids of all transactions that satisfy the given blob fee.
+    ///
+    /// Since blob transactions are gapless, a transaction that does not satisfy the blob fee blocks
+    /// all descendants of the same sender.
+    fn satisfy_blob_fee_ids(&self, blob_fee: u128) -> Vec<TransactionId> {
+        let mut transactions = Vec::new();
+        {
+            let mut iter = self.by_id.iter().peekable();
+
+            while let Some((id, tx)) = iter.next() {
+                if tx.ord.max_fee_per_blob_gas < blob_fee {
+                    // still parked -> skip descendant transactions
+                    'this: while let Some((peek, _)) = iter.peek() {
+                        if peek.sender != id.sender {
+                            break 'this
+                        }
+                        iter.next();
+                    }
+                } else {
+                    transactions.push(*id);
+                }
+            }
+        }
+        transactions
+    }
+
+    /// Removes all transactions and their dependent transaction from the subpool that now satisfy
+    /// the given blob fee.
+    ///
+    /// Note: the transactions are not returned in a particular order.
+    pub(crate) fn enforce_blob_fee(&mut self, blob_fee: u128) -> Vec<Arc<ValidPoolTransaction<T>>> {
+        let to_remove = self.satisfy_blob_fee_ids(blob_fee);
+
+        let mut removed = Vec::with_capacity(to_remove.len());
+        for id in to_remove {
+            removed.push(self.remove_transaction(&id).expect("transaction exists"));
+        }
+
+        removed
+    }
+
+    /// Removes the worst transaction from this pool.
+    ///
+    /// The worst transaction is the one with the lowest blob fee cap, which is the furthest away
+    /// from the current blob fee.
+    #[cfg(test)]
+    pub(crate) fn pop_worst(&mut self) -> Option<Arc<ValidPoolTransaction<T>>> {
+        self.pop_worst_evictable(|_| false)
+    }
+
+    /// Removes the worst transaction from this pool that is not exempt from eviction.
+    pub(crate) fn pop_worst_evictable(
+        &mut self,
+        is_exempt: impl Fn(&ValidPoolTransaction<T>) -> bool,
+    ) -> Option<Arc<ValidPoolTransaction<T>>> {
+        let worst = self
+            .all
+            .iter()
+            .find(|tx| !is_exempt(&tx.transaction))
+            .map(|tx| *tx.transaction.id())?;
+        self.remove_transaction(&worst)
+    }
+
+    /// Returns an iterator over all transactions in the pool
+    pub(crate) fn all(&self) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + '_ {
+        self.by_id.values().map(|tx| tx.transaction.clone())
+    }
+
+    /// The reported size of all transactions in this pool.
+    pub(crate) fn size(&self) -> usize {
+        self.size_of.into()
+    }
+
+    /// Number of transactions in the entire pool
+    pub(crate) fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    /// Whether the pool is empty
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    /// Returns `true` if the transaction with the given id is already included in this pool.
+    #[cfg(test)]
+    pub(crate) fn contains(&self, id: &TransactionId) -> bool {
+        self.by_id.contains_key(id)
+    }
+}
+
+impl<T: PoolTransaction> Default for BlobTransactions<T> {
+    fn default() -> Self {
+        Self {
+            submission_id: 0,
+            by_id: Default::default(),
+            all: Default::default(),
+            size_of: Default::default(),
+        }
+    }
+}
+
+/// A blob transaction that currently resides in the blob sub-pool.
+struct BlobTransaction<T: PoolTransaction> {
+    /// Actual blob transaction.
+    transaction: Arc<ValidPoolTransaction<T>>,
+    /// The value that determines the order of this transaction.
+    ord: BlobOrd,
+}
+
+impl<T: PoolTransaction> Clone for BlobTransaction<T> {
+    fn clone(&self) -> Self {
+        Self { transaction: self.transaction.clone(), ord: self.ord.clone() }
+    }
+}
+
+impl<T: PoolTransaction> Eq for BlobTransaction<T> {}
+
+impl<T: PoolTransaction> PartialEq<Self> for BlobTransaction<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PoolTransaction> PartialOrd<Self> for BlobTransaction<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PoolTransaction> Ord for BlobTransaction<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ord.cmp(&other.ord)
+    }
+}
+
+/// The values that determine the order of a blob transaction in the pool.
+///
+/// "better" transactions are Greater
+#[derive(Debug, Clone)]
+struct BlobOrd {
+    /// Identifier that tags when transaction was submitted in the pool.
+    submission_id: u64,
+    /// The blob fee cap of the transaction.
+    max_fee_per_blob_gas: u128,
+    /// The fee cap of the transaction.
+    max_fee_per_gas: u128,
+}
+
+impl Eq for BlobOrd {}
+
+impl PartialEq<Self> for BlobOrd {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd<Self> for BlobOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BlobOrd {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Higher blob fee cap is better, then higher fee cap, and only if both are equal the
+        // older transaction is better.
+        self.max_fee_per_blob_gas
+            .cmp(&other.max_fee_per_blob_gas)
+            .then_with(|| self.max_fee_per_gas.cmp(&other.max_fee_per_gas))
+            .then_with(|| other.submission_id.cmp(&self.submission_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn test_enforce_blob_fee() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = BlobTransactions::default();
+        let tx = f.validated_arc(MockTransaction::eip4844().with_blob_fee(10));
+        pool.add_transaction(tx.clone());
+
+        assert!(pool.contains(tx.id()));
+        assert_eq!(pool.len(), 1);
+
+        let removed = pool.enforce_blob_fee(u128::MAX);
+        assert!(removed.is_empty());
+
+        let removed = pool.enforce_blob_fee(10);
+        assert_eq!(removed.len(), 1);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_enforce_blob_fee_descendant() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = BlobTransactions::default();
+        let t = MockTransaction::eip4844().with_blob_fee(10);
+        let root_tx = f.validated_arc(t.clone());
+        pool.add_transaction(root_tx.clone());
+
+        let descendant_tx = f.validated_arc(t.inc_nonce().with_blob_fee(20));
+        pool.add_transaction(descendant_tx.clone());
+        assert_eq!(pool.len(), 2);
+
+        // root transaction still blocks its descendant
+        let removed = pool.enforce_blob_fee(20);
+        assert!(removed.is_empty());
+        assert_eq!(pool.len(), 2);
+
+        let removed = pool.enforce_blob_fee(10);
+        assert_eq!(removed.len(), 2);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_pop_worst_blob_fee() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = BlobTransactions::default();
+
+        let low = f.validated_arc(MockTransaction::eip4844().with_blob_fee(1));
+        let high = f.validated_arc(MockTransaction::eip4844().with_blob_fee(100));
+        let mid = f.validated_arc(MockTransaction::eip4844().with_blob_fee(50));
+        pool.add_transaction(high.clone());
+        pool.add_transaction(low.clone());
+        pool.add_transaction(mid.clone());
+
+        assert_eq!(pool.pop_worst().unwrap().hash(), low.hash());
+        assert_eq!(pool.pop_worst().unwrap().hash(), mid.hash());
+        assert_eq!(pool.pop_worst().unwrap().hash(), high.hash());
+        assert!(pool.pop_worst().is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
+use crate::{traits::PropagateKind, PoolTransaction, SubPool, ValidPoolTransaction};
 use reth_primitives::{TxHash, H256};
 use std::sync::Arc;
 
@@ -29,7 +29,12 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
         replaced_by: TxHash,
     },
     /// Transaction was dropped due to configured limits.
-    Discarded(TxHash),
+    Discarded {
+        /// The hash of the discarded transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was discarded.
+        reason: DiscardReason,
+    },
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
     /// Transaction was propagated to peers.
@@ -47,7 +52,9 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             Self::Replaced { transaction, replaced_by } => {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
-            Self::Discarded(hash) => Self::Discarded(*hash),
+            Self::Discarded { tx_hash, reason } => {
+                Self::Discarded { tx_hash: *tx_hash, reason: *reason }
+            }
             Self::Invalid(hash) => Self::Invalid(*hash),
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
@@ -69,7 +76,7 @@ pub enum TransactionEvent {
     /// E.g. same (sender + nonce) pair
     Replaced(TxHash),
     /// Transaction was dropped due to configured limits.
-    Discarded,
+    Discarded(DiscardReason),
     /// Transaction became invalid indefinitely.
     Invalid,
     /// Transaction was propagated to peers.
@@ -84,7 +91,25 @@ impl TransactionEvent {
             self,
             TransactionEvent::Replaced(_) |
                 TransactionEvent::Mined(_) |
-                TransactionEvent::Discarded
+                TransactionEvent::Discarded(_)
         )
     }
 }
+
+/// The reason a transaction was discarded from the pool.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiscardReason {
+    /// The transaction failed validation and was never added to the pool.
+    Invalid,
+    /// The nonce of the transaction is below the nonce of its sender on the current state.
+    NonceTooLow,
+    /// The sub-pool exceeded its limits and the transaction, or one of its ancestors, was among
+    /// the lowest paying transactions of the sub-pool.
+    SubPoolLimit(SubPool),
+    /// The sender exceeded its slots in the queued sub-pool and the transaction had one of the
+    /// highest nonces of the sender.
+    SenderQueuedLimit,
+    /// The transaction was removed from the pool on request.
+    Removed,
+}
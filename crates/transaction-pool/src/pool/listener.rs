@@ -1,7 +1,7 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::events::{DiscardReason, FullTransactionEvent, TransactionEvent},
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
@@ -161,8 +161,12 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded(reason),
+            FullTransactionEvent::Discarded { tx_hash: *tx, reason },
+        );
     }
 
     /// Notify listeners that the transaction was mined
@@ -31,8 +31,11 @@
 //!  b) Transaction does not meet the dynamic fee cap requirement introduced by EIP-1559: The
 //! fee cap of the transaction needs to be no less than the base fee of block.
 //!
+//!  c) EIP-4844 blob transaction does not meet the dynamic blob fee cap requirement: The blob fee
+//! cap of the transaction needs to be no less than the blob fee of the block.
 //!
-//! In essence the transaction pool is made of three separate sub-pools:
+//!
+//! In essence the transaction pool is made of four separate sub-pools:
 //!
 //!  - Pending Pool: Contains all transactions that are valid on the current state and satisfy
 //! (3. a)(1): _No_ nonce gaps. A _pending_ transaction is considered _ready_ when it has the lowest
@@ -45,6 +48,12 @@
 //!  - Basefee Pool: To account for the dynamic base fee requirement (3. b) which could render
 //! an EIP-1559 and all subsequent transactions of the sender currently invalid.
 //!
+//!  - Blob Pool: To account for the dynamic blob fee requirement (3. c) which could render an
+//! EIP-4844 blob transaction and all subsequent transactions of the sender currently invalid.
+//!
+//! The blob sidecars of EIP-4844 transactions are not kept inside the sub-pools, instead they are
+//! moved into a [`BlobStore`](crate::blobstore::BlobStore) when the transaction is added.
+//!
 //! The classification of transactions is always dependent on the current state that is changed as
 //! soon as a new block is mined. Once a new block is mined, the account changeset must be applied
 //! to the transaction pool.
@@ -66,6 +75,7 @@
 //!    category (2.) and become pending.
 
 use crate::{
+    blobstore::BlobStore,
     error::{PoolError, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::{
@@ -90,16 +100,17 @@ use std::{
     time::Instant,
 };
 use tokio::sync::mpsc;
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 mod events;
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, FullTransactionEvent, TransactionEvent};
 
 mod listener;
 use crate::{pool::txpool::UpdateOutcome, traits::PendingTransactionListenerKind};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
 
 mod best;
+mod blob;
 mod parked;
 pub(crate) mod pending;
 pub(crate) mod size;
@@ -108,11 +119,16 @@ pub mod txpool;
 mod update;
 
 /// Transaction pool internals.
-pub struct PoolInner<V: TransactionValidator, T: TransactionOrdering> {
+pub struct PoolInner<V, T, S>
+where
+    T: TransactionOrdering,
+{
     /// Internal mapping of addresses to plain ints.
     identifiers: RwLock<SenderIdentifiers>,
     /// Transaction validation.
     validator: V,
+    /// Storage for blob transactions
+    blob_store: S,
     /// The internal pool that manages all transactions.
     pool: RwLock<TxPool<T>>,
     /// Pool settings.
@@ -127,16 +143,18 @@ pub struct PoolInner<V: TransactionValidator, T: TransactionOrdering> {
 
 // === impl PoolInner ===
 
-impl<V, T> PoolInner<V, T>
+impl<V, T, S> PoolInner<V, T, S>
 where
     V: TransactionValidator,
     T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+    S: BlobStore,
 {
     /// Create a new transaction pool instance.
-    pub(crate) fn new(validator: V, ordering: T, config: PoolConfig) -> Self {
+    pub(crate) fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
         Self {
             identifiers: Default::default(),
             validator,
+            blob_store,
             event_listener: Default::default(),
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
             pending_transaction_listener: Default::default(),
@@ -195,6 +213,11 @@ where
         &self.validator
     }
 
+    /// Returns the configured blob store.
+    pub(crate) fn blob_store(&self) -> &S {
+        &self.blob_store
+    }
+
     /// Adds a new transaction listener to the pool that gets notified about every new _pending_
     /// transaction inserted into the pool
     pub fn add_pending_listener(
@@ -259,6 +282,7 @@ where
             hash,
             number,
             pending_block_base_fee,
+            pending_block_blob_fee,
             changed_accounts,
             mined_transactions,
             timestamp: _,
@@ -268,12 +292,17 @@ where
             last_seen_block_hash: hash,
             last_seen_block_number: number,
             pending_basefee: pending_block_base_fee,
+            pending_blob_fee: pending_block_blob_fee,
         };
         let outcome = self.pool.write().on_canonical_state_change(
             block_info,
             mined_transactions,
             changed_senders,
         );
+
+        // blobs of mined and discarded transactions are no longer needed
+        self.delete_blobs(outcome.mined.iter().chain(outcome.discarded.iter()).copied().collect());
+
         self.notify_on_new_state(outcome);
     }
 
@@ -286,7 +315,9 @@ where
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
         promoted.iter().for_each(|tx| listener.pending(tx, None));
-        discarded.iter().for_each(|tx| listener.discarded(tx));
+        discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::NonceTooLow));
+
+        self.delete_blobs(discarded);
     }
 
     /// Add a single validated transaction into the pool.
@@ -302,13 +333,26 @@ where
             TransactionValidationOutcome::Valid {
                 balance,
                 state_nonce,
-                transaction,
+                mut transaction,
                 propagate,
             } => {
                 let sender_id = self.get_sender_id(transaction.sender());
                 let transaction_id = TransactionId::new(sender_id, transaction.nonce());
                 let encoded_length = transaction.encoded_length();
 
+                // split off the blob sidecar, it's kept in the blob store
+                let maybe_sidecar = transaction.take_blob_sidecar();
+                let tx_hash = *transaction.hash();
+
+                // the sidecar is stored before the transaction is inserted, so that a pending blob
+                // transaction always has its sidecar in the blob store
+                let has_sidecar = maybe_sidecar.is_some();
+                if let Some(sidecar) = maybe_sidecar {
+                    self.blob_store
+                        .insert(tx_hash, sidecar)
+                        .map_err(|err| PoolError::Other(tx_hash, Box::new(err)))?;
+                }
+
                 let tx = ValidPoolTransaction {
                     transaction,
                     transaction_id,
@@ -318,9 +362,24 @@ where
                     encoded_length,
                 };
 
-                let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
+                let added = match self.pool.write().add_transaction(tx, balance, state_nonce) {
+                    Ok(added) => added,
+                    Err(err) => {
+                        // the sidecar of an already imported transaction belongs to the pooled
+                        // transaction
+                        if has_sidecar && !matches!(err, PoolError::AlreadyImported(_)) {
+                            self.delete_blob(tx_hash);
+                        }
+                        return Err(err)
+                    }
+                };
                 let hash = *added.hash();
 
+                if let Some(replaced) = added.replaced_blob_transaction() {
+                    // delete the replaced transaction from the blob store
+                    self.delete_blob(replaced);
+                }
+
                 // Notify about new pending transactions
                 if let Some(pending) = added.as_pending() {
                     self.on_new_pending_transaction(pending);
@@ -336,12 +395,12 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Invalid);
                 Err(PoolError::InvalidTransaction(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DiscardReason::Invalid);
                 Err(PoolError::Other(tx_hash, err))
             }
         }
@@ -378,14 +437,14 @@ where
         }
 
         let mut listener = self.event_listener.write();
-        discarded.iter().for_each(|tx| listener.discarded(tx));
+        discarded.iter().for_each(|(tx, reason)| listener.discarded(tx, *reason));
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
         // adjust the result here
         added
             .into_iter()
             .map(|res| match res {
-                Ok(ref hash) if discarded.contains(hash) => {
+                Ok(ref hash) if discarded.contains_key(hash) => {
                     Err(PoolError::DiscardedOnInsert(*hash))
                 }
                 other => other,
@@ -455,7 +514,7 @@ where
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx, None));
-        discarded.iter().for_each(|tx| listener.discarded(tx));
+        discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::NonceTooLow));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -468,7 +527,7 @@ where
 
                 listener.pending(transaction.hash(), replaced.clone());
                 promoted.iter().for_each(|tx| listener.pending(tx, None));
-                discarded.iter().for_each(|tx| listener.discarded(tx));
+                discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::NonceTooLow));
             }
             AddedTransaction::Parked { transaction, replaced, .. } => {
                 listener.queued(transaction.hash());
@@ -522,7 +581,11 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
+
+        self.delete_blobs(
+            removed.iter().filter(|tx| tx.is_eip4844()).map(|tx| *tx.hash()).collect(),
+        );
 
         removed
     }
@@ -577,13 +640,37 @@ where
         self.pool.read().is_empty()
     }
 
-    /// Enforces the size limits of pool and returns the discarded transactions if violated.
-    pub(crate) fn discard_worst(&self) -> HashSet<TxHash> {
-        self.pool.write().discard_worst().into_iter().map(|tx| *tx.hash()).collect()
+    /// Enforces the size limits of pool and returns the discarded transactions and the reason of
+    /// their eviction if violated.
+    ///
+    /// This also removes the blobs of discarded blob transactions from the blob store.
+    pub(crate) fn discard_worst(&self) -> HashMap<TxHash, DiscardReason> {
+        let discarded = self.pool.write().discard_worst();
+        self.delete_blobs(
+            discarded.iter().filter(|(tx, _)| tx.is_eip4844()).map(|(tx, _)| *tx.hash()).collect(),
+        );
+        discarded.into_iter().map(|(tx, reason)| (*tx.hash(), reason)).collect()
+    }
+
+    /// Delete a blob from the blob store
+    fn delete_blob(&self, blob: TxHash) {
+        if let Err(err) = self.blob_store.delete(blob) {
+            warn!(target: "txpool", ?err, "[{:?}] failed to delete blobs", blob);
+        }
+    }
+
+    /// Deletes all blob transactions that were discarded.
+    fn delete_blobs(&self, txs: Vec<TxHash>) {
+        if txs.is_empty() {
+            return
+        }
+        if let Err(err) = self.blob_store.delete_all(txs) {
+            warn!(target: "txpool", ?err, "failed to delete blobs");
+        }
     }
 }
 
-impl<V: TransactionValidator, T: TransactionOrdering> fmt::Debug for PoolInner<V, T> {
+impl<V, T: TransactionOrdering, S> fmt::Debug for PoolInner<V, T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PoolInner").field("config", &self.config).finish_non_exhaustive()
     }
@@ -656,6 +743,19 @@ impl<T: PoolTransaction> AddedTransaction<T> {
         }
     }
 
+    /// Returns the hash of the replaced transaction if it is a blob transaction.
+    pub(crate) fn replaced_blob_transaction(&self) -> Option<TxHash> {
+        self.replaced().filter(|tx| tx.is_eip4844()).map(|tx| *tx.hash())
+    }
+
+    /// Returns the replaced transaction if there was one
+    pub(crate) fn replaced(&self) -> Option<&Arc<ValidPoolTransaction<T>>> {
+        match self {
+            AddedTransaction::Pending(tx) => tx.replaced.as_ref(),
+            AddedTransaction::Parked { replaced, .. } => replaced.as_ref(),
+        }
+    }
+
     /// Converts this type into the event type for listeners
     pub(crate) fn into_new_transaction_event(self) -> NewTransactionEvent<T> {
         match self {
@@ -1,8 +1,15 @@
 use crate::{
-    identifier::TransactionId, pool::size::SizeTracker, PoolTransaction, ValidPoolTransaction,
+    identifier::{SenderId, TransactionId},
+    pool::size::SizeTracker,
+    PoolTransaction, ValidPoolTransaction,
 };
 use fnv::FnvHashMap;
-use std::{cmp::Ordering, collections::BTreeSet, ops::Deref, sync::Arc};
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BTreeSet},
+    ops::Deref,
+    sync::Arc,
+};
 
 /// A pool of transactions that are currently parked and are waiting for external changes (e.g.
 /// basefee, ancestor transactions, balance) that eventually move the transaction into the pending
@@ -25,6 +32,8 @@ pub(crate) struct ParkedPool<T: ParkedOrd> {
     ///
     /// The higher, the better.
     best: BTreeSet<ParkedPoolTransaction<T>>,
+    /// Number of transactions in the pool per sender.
+    sender_transaction_count: FnvHashMap<SenderId, usize>,
     /// Keeps track of the size of this pool.
     ///
     /// See also [`PoolTransaction::size`].
@@ -50,6 +59,7 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        *self.sender_transaction_count.entry(id.sender).or_default() += 1;
 
         let transaction = ParkedPoolTransaction { submission_id, transaction: tx.into() };
 
@@ -75,16 +85,50 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         // keep track of size
         self.size_of -= tx.transaction.size();
+        if let Entry::Occupied(mut count) = self.sender_transaction_count.entry(id.sender) {
+            *count.get_mut() -= 1;
+            if *count.get() == 0 {
+                count.remove();
+            }
+        }
 
         Some(tx.transaction.into())
     }
 
     /// Removes the worst transaction from this pool.
+    #[cfg(test)]
     pub(crate) fn pop_worst(&mut self) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let worst = self.best.iter().next().map(|tx| *tx.transaction.id())?;
+        self.pop_worst_evictable(|_| false)
+    }
+
+    /// Removes the worst transaction from this pool that is not exempt from eviction.
+    pub(crate) fn pop_worst_evictable(
+        &mut self,
+        is_exempt: impl Fn(&ValidPoolTransaction<T::Transaction>) -> bool,
+    ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let worst = self
+            .best
+            .iter()
+            .map(|tx| &tx.transaction)
+            .find(|tx| !is_exempt(tx))
+            .map(|tx| *tx.id())?;
         self.remove_transaction(&worst)
     }
 
+    /// Returns the number of transactions of the sender in this pool.
+    pub(crate) fn sender_transaction_count(&self, sender: SenderId) -> usize {
+        self.sender_transaction_count.get(&sender).copied().unwrap_or_default()
+    }
+
+    /// Returns all senders with more than `max` transactions in this pool.
+    pub(crate) fn senders_exceeding(&self, max: usize) -> Vec<SenderId> {
+        self.sender_transaction_count
+            .iter()
+            .filter(|(_, count)| **count > max)
+            .map(|(sender, _)| *sender)
+            .collect()
+    }
+
     fn next_id(&mut self) -> u64 {
         let id = self.submission_id;
         self.submission_id = self.submission_id.wrapping_add(1);
@@ -170,6 +214,7 @@ impl<T: ParkedOrd> Default for ParkedPool<T> {
             submission_id: 0,
             by_id: Default::default(),
             best: Default::default(),
+            sender_transaction_count: Default::default(),
             size_of: Default::default(),
         }
     }
@@ -310,7 +355,7 @@ impl_ord_wrapper!(QueuedOrd);
 impl<T: PoolTransaction> Ord for QueuedOrd<T> {
     fn cmp(&self, other: &Self) -> Ordering {
         // Higher price is better
-        self.max_fee_per_gas().cmp(&self.max_fee_per_gas()).then_with(||
+        self.max_fee_per_gas().cmp(&other.max_fee_per_gas()).then_with(||
             // Lower timestamp is better
             other.timestamp.cmp(&self.timestamp))
     }
@@ -190,6 +190,49 @@ impl<T: TransactionOrdering> PendingPool<T> {
         removed
     }
 
+    /// Updates the pool with the new blob fee. Removes
+    /// from the subpool all blob transactions and their dependents that no longer satisfy the given
+    /// blob fee (`tx.blob_fee < blob_fee`).
+    ///
+    /// Note: the transactions are not returned in a particular order.
+    ///
+    /// # Returns
+    ///
+    /// Removed transactions that no longer satisfy the blob fee.
+    pub(crate) fn update_blob_fee(
+        &mut self,
+        blob_fee: u128,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        // Create a collection for txs to remove .
+        let mut to_remove = Vec::new();
+
+        // Iterate over transactions, find the ones we need to remove.
+        {
+            let mut iter = self.by_id.iter().peekable();
+            while let Some((id, tx)) = iter.next() {
+                if tx.transaction.max_fee_per_blob_gas().map_or(false, |fee| fee < blob_fee) {
+                    // This transaction no longer satisfies the blob fee: remove it and all its
+                    // descendants.
+                    to_remove.push(*id);
+                    'this: while let Some((peek, _)) = iter.peek() {
+                        if peek.sender != id.sender {
+                            break 'this
+                        }
+                        to_remove.push(**peek);
+                        iter.next();
+                    }
+                }
+            }
+        }
+
+        let mut removed = Vec::with_capacity(to_remove.len());
+        for id in to_remove {
+            removed.push(self.remove_transaction(&id).expect("transaction exists"));
+        }
+
+        removed
+    }
+
     /// Returns the ancestor the given transaction, the transaction with `nonce - 1`.
     ///
     /// Note: for a transaction with nonce higher than the current on chain nonce this will always
@@ -273,8 +316,21 @@ impl<T: TransactionOrdering> PendingPool<T> {
     }
 
     /// Removes the worst transaction from this pool.
+    #[cfg(test)]
     pub(crate) fn pop_worst(&mut self) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let worst = self.all.iter().next().map(|tx| *tx.transaction.id())?;
+        self.pop_worst_evictable(|_| false)
+    }
+
+    /// Removes the worst transaction from this pool that is not exempt from eviction.
+    pub(crate) fn pop_worst_evictable(
+        &mut self,
+        is_exempt: impl Fn(&ValidPoolTransaction<T::Transaction>) -> bool,
+    ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let worst = self
+            .all
+            .iter()
+            .find(|tx| !is_exempt(&tx.transaction))
+            .map(|tx| *tx.transaction.id())?;
         self.remove_transaction(&worst)
     }
 
@@ -411,6 +467,31 @@ mod tests {
         assert!(pool.is_empty());
     }
 
+    #[test]
+    fn test_enforce_blob_fee_descendant() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let t = MockTransaction::eip4844().with_blob_fee(10);
+        let root_tx = f.validated_arc(t.clone());
+        pool.add_transaction(root_tx.clone(), 0);
+
+        let descendant_tx = f.validated_arc(t.inc_nonce().with_blob_fee(20));
+        pool.add_transaction(descendant_tx.clone(), 0);
+
+        let other_tx = f.validated_arc(MockTransaction::eip1559());
+        pool.add_transaction(other_tx.clone(), 0);
+        assert_eq!(pool.len(), 3);
+
+        let removed = pool.update_blob_fee(10);
+        assert!(removed.is_empty());
+
+        // root transaction no longer satisfies the blob fee, descendant is removed as well
+        let removed = pool.update_blob_fee(11);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.by_id.contains_key(other_tx.id()));
+    }
+
     #[test]
     fn evict_worst() {
         let mut f = MockTransactionFactory::default();
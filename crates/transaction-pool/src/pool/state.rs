@@ -5,22 +5,31 @@ bitflags::bitflags! {
      #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
     pub(crate) struct TxState: u8 {
         /// Set to `1` if all ancestor transactions are pending.
-        const NO_PARKED_ANCESTORS = 0b100000;
+        const NO_PARKED_ANCESTORS = 0b1000000;
         /// Set to `1` of the transaction is either the next transaction of the sender (on chain nonce == tx.nonce) or all prior transactions are also present in the pool.
-        const NO_NONCE_GAPS = 0b010000;
+        const NO_NONCE_GAPS = 0b0100000;
         /// Bit derived from the sender's balance.
         ///
         /// Set to `1` if the sender's balance can cover the maximum cost for this transaction (`feeCap * gasLimit + value`).
         /// This includes cumulative costs of prior transactions, which ensures that the sender has enough funds for all max cost of prior transactions.
-        const ENOUGH_BALANCE = 0b001000;
+        const ENOUGH_BALANCE = 0b0010000;
         /// Bit set to true if the transaction has a lower gas limit than the block's gas limit
-        const NOT_TOO_MUCH_GAS = 0b000100;
+        const NOT_TOO_MUCH_GAS = 0b0001000;
         /// Covers the Dynamic fee requirement.
         ///
         /// Set to 1 if `feeCap` of the transaction meets the requirement of the pending block.
-        const ENOUGH_FEE_CAP_BLOCK = 0b000010;
+        const ENOUGH_FEE_CAP_BLOCK = 0b0000100;
+        /// Covers the dynamic blob fee requirement of EIP-4844 transactions.
+        ///
+        /// Set to 1 if `maxFeePerBlobGas` of the transaction meets the requirement of the pending block.
+        /// This is always set for non-EIP-4844 transactions.
+        const ENOUGH_BLOB_FEE_CAP_BLOCK = 0b0000010;
+        /// Marks blob transactions.
+        ///
+        /// Set to 1 if the transaction is an EIP-4844 transaction.
+        const BLOB_TRANSACTION = 0b0000001;
 
-        const PENDING_POOL_BITS = Self::NO_PARKED_ANCESTORS.bits()| Self::NO_NONCE_GAPS.bits() | Self::ENOUGH_BALANCE.bits() | Self::NOT_TOO_MUCH_GAS.bits() |  Self::ENOUGH_FEE_CAP_BLOCK.bits();
+        const PENDING_POOL_BITS = Self::NO_PARKED_ANCESTORS.bits()| Self::NO_NONCE_GAPS.bits() | Self::ENOUGH_BALANCE.bits() | Self::NOT_TOO_MUCH_GAS.bits() |  Self::ENOUGH_FEE_CAP_BLOCK.bits() | Self::ENOUGH_BLOB_FEE_CAP_BLOCK.bits();
 
         const BASE_FEE_POOL_BITS = Self::NO_PARKED_ANCESTORS.bits() | Self::NO_NONCE_GAPS.bits() | Self::ENOUGH_BALANCE.bits() | Self::NOT_TOO_MUCH_GAS.bits();
 
@@ -36,6 +45,7 @@ impl TxState {
     ///   - _No_ parked ancestors
     ///   - enough balance
     ///   - enough fee cap
+    ///   - enough blob fee cap
     #[inline]
     pub(crate) fn is_pending(&self) -> bool {
         *self >= TxState::PENDING_POOL_BITS
@@ -46,15 +56,32 @@ impl TxState {
     pub(crate) fn has_nonce_gap(&self) -> bool {
         !self.intersects(TxState::NO_NONCE_GAPS)
     }
+
+    /// Returns `true` if the transaction is an EIP-4844 transaction.
+    #[inline]
+    pub(crate) fn is_blob(&self) -> bool {
+        self.contains(TxState::BLOB_TRANSACTION)
+    }
+
+    /// Returns `true` if the transaction's blob fee cap does not satisfy the blob fee of the
+    /// pending block.
+    #[inline]
+    pub(crate) fn has_insufficient_blob_fee(&self) -> bool {
+        !self.contains(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK)
+    }
 }
 
 /// Identifier for the transaction Sub-pool
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum SubPool {
     /// The queued sub-pool contains transactions that are not ready to be included in the next
     /// block because they have missing or queued ancestors.
     Queued = 0,
+    /// The blob sub-pool contains EIP-4844 transactions that are not ready to be included in the
+    /// next block because their blob fee cap is below the blob fee of the pending block.
+    Blob,
     /// The base-fee sub-pool contains transactions that are not ready to be included in the next
     /// block because they don't meet the base fee requirement.
     BaseFee,
@@ -83,6 +110,12 @@ impl SubPool {
         matches!(self, SubPool::BaseFee)
     }
 
+    /// Whether this transaction is in the blob pool.
+    #[inline]
+    pub fn is_blob(&self) -> bool {
+        matches!(self, SubPool::Blob)
+    }
+
     /// Returns whether this is a promotion depending on the current sub-pool location.
     #[inline]
     pub fn is_promoted(&self, other: SubPool) -> bool {
@@ -98,6 +131,9 @@ impl From<TxState> for SubPool {
         if value < TxState::BASE_FEE_POOL_BITS {
             return SubPool::Queued
         }
+        if value.is_blob() && value.has_insufficient_blob_fee() {
+            return SubPool::Blob
+        }
         SubPool::BaseFee
     }
 }
@@ -113,6 +149,9 @@ mod tests {
         assert!(SubPool::Pending.is_promoted(SubPool::Queued));
         assert!(!SubPool::BaseFee.is_promoted(SubPool::Pending));
         assert!(!SubPool::Queued.is_promoted(SubPool::BaseFee));
+        assert!(SubPool::BaseFee.is_promoted(SubPool::Blob));
+        assert!(SubPool::Pending.is_promoted(SubPool::Blob));
+        assert!(!SubPool::Blob.is_promoted(SubPool::Pending));
     }
 
     #[test]
@@ -140,14 +179,38 @@ mod tests {
         assert_eq!(SubPool::Pending, state.into());
         assert!(state.is_pending());
 
-        let bits = 0b111110;
+        let bits = 0b1111110;
         let state = TxState::from_bits(bits).unwrap();
         assert_eq!(SubPool::Pending, state.into());
         assert!(state.is_pending());
 
-        let bits = 0b111110;
+        let bits = 0b1111111;
         let state = TxState::from_bits(bits).unwrap();
         assert_eq!(SubPool::Pending, state.into());
         assert!(state.is_pending());
     }
+
+    #[test]
+    fn test_tx_blob() {
+        let state = TxState::BASE_FEE_POOL_BITS | TxState::BLOB_TRANSACTION;
+        assert_eq!(SubPool::Blob, state.into());
+        assert!(!state.is_pending());
+
+        let state =
+            TxState::BASE_FEE_POOL_BITS | TxState::ENOUGH_FEE_CAP_BLOCK | TxState::BLOB_TRANSACTION;
+        assert_eq!(SubPool::Blob, state.into());
+
+        // blob fee is satisfied, but the base fee is not
+        let state = TxState::BASE_FEE_POOL_BITS |
+            TxState::ENOUGH_BLOB_FEE_CAP_BLOCK |
+            TxState::BLOB_TRANSACTION;
+        assert_eq!(SubPool::BaseFee, state.into());
+
+        let state = TxState::PENDING_POOL_BITS | TxState::BLOB_TRANSACTION;
+        assert_eq!(SubPool::Pending, state.into());
+
+        // a blob transaction with a nonce gap is always queued
+        let state = TxState::NO_PARKED_ANCESTORS | TxState::BLOB_TRANSACTION;
+        assert_eq!(SubPool::Queued, state.into());
+    }
 }
@@ -6,18 +6,22 @@ use crate::{
     metrics::TxPoolMetrics,
     pool::{
         best::BestTransactions,
+        blob::BlobTransactions,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
         state::{SubPool, TxState},
         update::{Destination, PoolUpdate},
-        AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
+        AddedPendingTransaction, AddedTransaction, DiscardReason, OnNewCanonicalStateOutcome,
     },
     traits::{BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, TransactionOrdering, ValidPoolTransaction, U256,
+    LocalTransactionConfig, PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig,
+    TransactionOrdering, ValidPoolTransaction, U256,
 };
 use fnv::FnvHashMap;
 use reth_primitives::{
-    constants::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE},
+    constants::{
+        eip4844::BLOB_TX_MIN_BLOB_GASPRICE, ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE,
+    },
     Address, TxHash, H256,
 };
 use std::{
@@ -43,6 +47,7 @@ use std::{
 ///         B3[(Queued)]
 ///         B1[(Pending)]
 ///         B2[(Basefee)]
+///         B4[(Blob)]
 ///     end
 ///   end
 ///   discard([discard])
@@ -51,15 +56,20 @@ use std::{
 ///   A[Incoming Tx] --> B[Validation] -->|insert| pool
 ///   pool --> |if ready| B1
 ///   pool --> |if ready + basfee too low| B2
+///   pool --> |if ready + blob fee too low| B4
 ///   pool --> |nonce gap or lack of funds| B3
 ///   pool --> |update| pool
 ///   B1 --> |best| production
 ///   B2 --> |worst| discard
 ///   B3 --> |worst| discard
+///   B4 --> |worst| discard
 ///   B1 --> |increased fee| B2
 ///   B2 --> |decreased fee| B1
+///   B1 --> |increased blob fee| B4
+///   B4 --> |decreased blob fee| B1
 ///   B3 --> |promote| B1
 ///   B3 -->  |promote| B2
+///   B3 -->  |promote| B4
 ///   new -->  |apply state changes| pool
 /// ```
 pub struct TxPool<T: TransactionOrdering> {
@@ -83,6 +93,12 @@ pub struct TxPool<T: TransactionOrdering> {
     /// Holds all parked transactions that currently violate the dynamic fee requirement but could
     /// be moved to pending if the base fee changes in their favor (decreases) in future blocks.
     basefee_pool: ParkedPool<BasefeeOrd<T::Transaction>>,
+    /// Blob transactions in the pool that are __not pending__.
+    ///
+    /// Holds all EIP-4844 transactions that currently violate the dynamic blob fee requirement but
+    /// could be moved to pending if the blob fee changes in their favor (decreases) in future
+    /// blocks.
+    blob_transactions: BlobTransactions<T::Transaction>,
     /// All transactions in the pool.
     all_transactions: AllTransactions<T::Transaction>,
     /// Transaction pool metrics
@@ -99,7 +115,8 @@ impl<T: TransactionOrdering> TxPool<T> {
             pending_pool: PendingPool::new(ordering),
             queued_pool: Default::default(),
             basefee_pool: Default::default(),
-            all_transactions: AllTransactions::new(config.max_account_slots),
+            blob_transactions: Default::default(),
+            all_transactions: AllTransactions::new(&config),
             config,
             metrics: Default::default(),
         }
@@ -124,6 +141,8 @@ impl<T: TransactionOrdering> TxPool<T> {
             basefee_size: self.basefee_pool.size(),
             queued: self.queued_pool.len(),
             queued_size: self.queued_pool.size(),
+            blob: self.blob_transactions.len(),
+            blob_size: self.blob_transactions.size(),
             total: self.all_transactions.len(),
         }
     }
@@ -134,6 +153,57 @@ impl<T: TransactionOrdering> TxPool<T> {
             last_seen_block_hash: self.all_transactions.last_seen_block_hash,
             last_seen_block_number: self.all_transactions.last_seen_block_number,
             pending_basefee: self.all_transactions.pending_basefee,
+            pending_blob_fee: Some(self.all_transactions.pending_blob_fee),
+        }
+    }
+
+    /// Updates the tracked blob fee
+    ///
+    /// Depending on the change in direction of the blob fee, this will promote or demote
+    /// transactions from the blob pool.
+    fn update_blob_fee(&mut self, pending_blob_fee: u128) {
+        match pending_blob_fee.cmp(&self.all_transactions.pending_blob_fee) {
+            Ordering::Equal => {
+                // fee unchanged, nothing to update
+            }
+            Ordering::Greater => {
+                // increased blob fee: recheck pending pool and remove all that are no longer valid
+                let removed = self.pending_pool.update_blob_fee(pending_blob_fee);
+                for tx in removed {
+                    let to = {
+                        let tx =
+                            self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
+                        if tx.transaction.max_fee_per_blob_gas().unwrap_or_default() <
+                            pending_blob_fee
+                        {
+                            tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+                        } else {
+                            // descendant of a transaction that no longer satisfies the blob fee
+                            tx.state.remove(TxState::NO_PARKED_ANCESTORS);
+                        }
+                        tx.subpool = tx.state.into();
+                        tx.subpool
+                    };
+                    self.add_transaction_to_subpool(to, tx);
+                }
+            }
+            Ordering::Less => {
+                // decreased blob fee: recheck blob pool and promote all that are now valid
+                let pending_basefee = self.all_transactions.pending_basefee;
+                let removed = self.blob_transactions.enforce_blob_fee(pending_blob_fee);
+                for tx in removed {
+                    let to = {
+                        let tx =
+                            self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
+                        tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+                        // the base fee may have changed while the transaction was parked
+                        AllTransactions::update_tx_base_fee(pending_basefee, tx);
+                        tx.subpool = tx.state.into();
+                        tx.subpool
+                    };
+                    self.add_transaction_to_subpool(to, tx);
+                }
+            }
         }
     }
 
@@ -181,11 +251,21 @@ impl<T: TransactionOrdering> TxPool<T> {
     ///
     /// This will also apply updates to the pool based on the new base fee
     pub(crate) fn set_block_info(&mut self, info: BlockInfo) {
-        let BlockInfo { last_seen_block_hash, last_seen_block_number, pending_basefee } = info;
+        let BlockInfo {
+            last_seen_block_hash,
+            last_seen_block_number,
+            pending_basefee,
+            pending_blob_fee,
+        } = info;
         self.all_transactions.last_seen_block_hash = last_seen_block_hash;
         self.all_transactions.last_seen_block_number = last_seen_block_number;
         self.all_transactions.pending_basefee = pending_basefee;
-        self.update_basefee(pending_basefee)
+        self.update_basefee(pending_basefee);
+
+        if let Some(blob_fee) = pending_blob_fee {
+            self.update_blob_fee(blob_fee);
+            self.all_transactions.pending_blob_fee = blob_fee;
+        }
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block.
@@ -230,6 +310,7 @@ impl<T: TransactionOrdering> TxPool<T> {
     pub(crate) fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let mut queued = self.basefee_pool.all().collect::<Vec<_>>();
         queued.extend(self.queued_pool.all());
+        queued.extend(self.blob_transactions.all());
         queued
     }
 
@@ -316,12 +397,14 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.metrics.basefee_pool_size_bytes.set(stats.basefee_size as f64);
         self.metrics.queued_pool_transactions.set(stats.queued as f64);
         self.metrics.queued_pool_size_bytes.set(stats.queued_size as f64);
+        self.metrics.blob_pool_transactions.set(stats.blob as f64);
+        self.metrics.blob_pool_size_bytes.set(stats.blob_size as f64);
         self.metrics.total_transactions.set(stats.total as f64);
     }
 
     /// Adds the transaction into the pool.
     ///
-    /// This pool consists of two three-pools: `Queued`, `Pending` and `BaseFee`.
+    /// This pool consists of four sub-pools: `Queued`, `Pending`, `BaseFee` and `Blob`.
     ///
     /// The `Queued` pool contains transactions with gaps in its dependency tree: It requires
     /// additional transactions that are note yet present in the pool. And transactions that the
@@ -339,6 +422,9 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// the sender's balance or nonce and instead their `feeCap` determines whether the
     /// transaction is _currently_ (on the current state) ready or needs to be parked until the
     /// `feeCap` satisfies the block's `baseFee`.
+    ///
+    /// The `Blob` pool contains EIP-4844 transactions that currently can't satisfy the dynamic
+    /// blob fee requirement: their `maxFeePerBlobGas` is below the blob fee of the pending block.
     pub(crate) fn add_transaction(
         &mut self,
         tx: ValidPoolTransaction<T::Transaction>,
@@ -401,6 +487,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                         *transaction.hash(),
                         InvalidPoolTransactionError::ExceedsGasLimit(block_gas_limit, tx_gas_limit),
                     )),
+                    InsertErr::TxTypeConflict { transaction } => {
+                        Err(PoolError::ExistingConflictingTransactionType(
+                            *transaction.hash(),
+                            transaction.tx_type(),
+                        ))
+                    }
                 }
             }
         }
@@ -500,6 +592,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             SubPool::Queued => self.queued_pool.remove_transaction(tx),
             SubPool::Pending => self.pending_pool.remove_transaction(tx),
             SubPool::BaseFee => self.basefee_pool.remove_transaction(tx),
+            SubPool::Blob => self.blob_transactions.remove_transaction(tx),
         }
     }
 
@@ -514,6 +607,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             SubPool::Queued => self.queued_pool.remove_transaction(tx),
             SubPool::Pending => self.pending_pool.prune_transaction(tx),
             SubPool::BaseFee => self.basefee_pool.remove_transaction(tx),
+            SubPool::Blob => self.blob_transactions.remove_transaction(tx),
         }
     }
 
@@ -558,6 +652,9 @@ impl<T: TransactionOrdering> TxPool<T> {
             SubPool::BaseFee => {
                 self.basefee_pool.add_transaction(tx);
             }
+            SubPool::Blob => {
+                self.blob_transactions.add_transaction(tx);
+            }
         }
     }
 
@@ -579,25 +676,72 @@ impl<T: TransactionOrdering> TxPool<T> {
 
     /// Ensures that the transactions in the sub-pools are within the given bounds.
     ///
-    /// If the current size exceeds the given bounds, the worst transactions are evicted from the
-    /// pool and returned.
-    pub(crate) fn discard_worst(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+    /// First, the queued transactions with the highest nonces of senders that exceed their queued
+    /// slots are evicted. Then, if the current size of a sub-pool exceeds its bounds, the worst
+    /// transactions across all senders are evicted together with their descendants.
+    ///
+    /// Local transactions are exempt from eviction, see [LocalTransactionConfig].
+    ///
+    /// All evicted transactions are returned with the reason of their eviction.
+    pub(crate) fn discard_worst(
+        &mut self,
+    ) -> Vec<(Arc<ValidPoolTransaction<T::Transaction>>, DiscardReason)> {
         let mut removed = Vec::new();
 
+        let max_queued = self.config.max_queued_account_slots;
+        for sender in self.queued_pool.senders_exceeding(max_queued) {
+            let excess = self.queued_pool.sender_transaction_count(sender) - max_queued;
+            let locals = &self.config.local_transactions_config;
+            // the queued transactions of a sender are always its transactions with the highest
+            // nonces, so these can be removed without affecting other transactions
+            let evicted = self
+                .all_transactions
+                .txs
+                .range(TransactionId::new(sender, 0)..=TransactionId::new(sender, u64::MAX))
+                .rev()
+                .take_while(|(_, tx)| {
+                    tx.subpool.is_queued() &&
+                        !locals.is_local(tx.transaction.origin, &tx.transaction.sender())
+                })
+                .take(excess)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            for id in evicted {
+                if let Some(tx) = self.remove_transaction(&id) {
+                    removed.push((tx, DiscardReason::SenderQueuedLimit));
+                    self.metrics.sender_limit_evicted_transactions.increment(1);
+                }
+            }
+        }
+
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
-            ($this:ident, $removed:ident,  [$($limit:ident => $pool:ident),*]  ) => {
+            ($this:ident, $removed:ident, [$($limit:ident => ($pool:ident, $subpool:expr)),*]) => {
                 $ (
                 while $this
                         .config
                         .$limit
                         .is_exceeded($this.$pool.len(), $this.$pool.size())
                     {
-                        if let Some(tx) = $this.$pool.pop_worst() {
-                            let id = tx.transaction_id;
-                            removed.push(tx);
-                            $this.remove_descendants(&id, &mut $removed);
-                        }
+                        let locals = &$this.config.local_transactions_config;
+                        let Some(tx) = $this
+                            .$pool
+                            .pop_worst_evictable(|tx| locals.is_local(tx.origin, &tx.sender()))
+                        else {
+                            // only exempt transactions are left
+                            break
+                        };
+                        let id = tx.transaction_id;
+                        $this.all_transactions.remove_transaction(&id);
+
+                        let mut evicted = vec![tx];
+                        $this.remove_descendants(&id, &mut evicted);
+                        $this
+                            .metrics
+                            .sub_pool_limit_evicted_transactions
+                            .increment(evicted.len() as u64);
+                        let reason = DiscardReason::SubPoolLimit($subpool);
+                        $removed.extend(evicted.into_iter().map(|tx| (tx, reason)));
                     }
 
                 )*
@@ -606,9 +750,10 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         discard_worst!(
             self, removed, [
-                pending_limit  => pending_pool,
-                basefee_limit  => basefee_pool,
-                queued_limit  => queued_pool
+                pending_limit  => (pending_pool, SubPool::Pending),
+                basefee_limit  => (basefee_pool, SubPool::BaseFee),
+                blob_limit  => (blob_transactions, SubPool::Blob),
+                queued_limit  => (queued_pool, SubPool::Queued)
             ]
         );
 
@@ -641,6 +786,10 @@ impl<T: TransactionOrdering> TxPool<T> {
     pub(crate) fn queued(&self) -> &ParkedPool<QueuedOrd<T::Transaction>> {
         &self.queued_pool
     }
+
+    pub(crate) fn blob(&self) -> &BlobTransactions<T::Transaction> {
+        &self.blob_transactions
+    }
 }
 
 impl<T: TransactionOrdering> fmt::Debug for TxPool<T> {
@@ -662,6 +811,10 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
+    /// Price bump settings for replacement transactions.
+    price_bumps: PriceBumpConfig,
+    /// How local transactions are exempt from the account slots limit.
+    local_transactions_config: LocalTransactionConfig,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
@@ -674,12 +827,19 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     last_seen_block_hash: H256,
     /// Expected base fee for the pending block.
     pending_basefee: u64,
+    /// Expected blob fee for the pending block.
+    pending_blob_fee: u128,
 }
 
 impl<T: PoolTransaction> AllTransactions<T> {
     /// Create a new instance
-    fn new(max_account_slots: usize) -> Self {
-        Self { max_account_slots, ..Default::default() }
+    fn new(config: &PoolConfig) -> Self {
+        Self {
+            max_account_slots: config.max_account_slots,
+            price_bumps: config.price_bumps.clone(),
+            local_transactions_config: config.local_transactions_config.clone(),
+            ..Default::default()
+        }
     }
 
     /// Returns an iterator over all _unique_ hashes in the pool
@@ -726,11 +886,18 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
     /// Updates the block specific info
     fn set_block_info(&mut self, block_info: BlockInfo) {
-        let BlockInfo { last_seen_block_hash, last_seen_block_number, pending_basefee } =
-            block_info;
+        let BlockInfo {
+            last_seen_block_hash,
+            last_seen_block_number,
+            pending_basefee,
+            pending_blob_fee,
+        } = block_info;
         self.last_seen_block_number = last_seen_block_number;
         self.last_seen_block_hash = last_seen_block_hash;
         self.pending_basefee = pending_basefee;
+        if let Some(pending_blob_fee) = pending_blob_fee {
+            self.pending_blob_fee = pending_blob_fee;
+        }
     }
 
     /// Rechecks all transactions in the pool against the changes.
@@ -740,6 +907,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
     /// For all transactions:
     ///   - decreased basefee: promotes from `basefee` to `pending` sub-pool.
     ///   - increased basefee: demotes from `pending` to `basefee` sub-pool.
+    ///   - decreased blob fee: promotes from `blob` to `pending` sub-pool.
+    ///   - increased blob fee: demotes from `pending` to `blob` sub-pool.
     /// Individually:
     ///   - decreased sender allowance: demote from (`basefee`|`pending`) to `queued`.
     ///   - increased sender allowance: promote from `queued` to
@@ -821,6 +990,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
             // Update the first transaction of this sender.
             Self::update_tx_base_fee(self.pending_basefee, tx);
+            Self::update_tx_blob_fee(self.pending_blob_fee, tx);
             // Track if the transaction's sub-pool changed.
             Self::record_subpool_update(&mut updates, tx);
 
@@ -867,6 +1037,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
                 // Update and record sub-pool changes.
                 Self::update_tx_base_fee(self.pending_basefee, tx);
+                Self::update_tx_blob_fee(self.pending_blob_fee, tx);
                 Self::record_subpool_update(&mut updates, tx);
 
                 // Advance iterator
@@ -907,6 +1078,18 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Rechecks the transaction's blob fee condition.
+    ///
+    /// This is a noop for non-EIP-4844 transactions.
+    fn update_tx_blob_fee(pending_block_blob_fee: u128, tx: &mut PoolInternalTransaction<T>) {
+        let Some(max_fee_per_blob_gas) = tx.transaction.max_fee_per_blob_gas() else { return };
+        if max_fee_per_blob_gas >= pending_block_blob_fee {
+            tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+        } else {
+            tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+        }
+    }
+
     /// Returns an iterator over all transactions for the given sender, starting with the lowest
     /// nonce
     pub(crate) fn txs_iter(
@@ -1004,7 +1187,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         &self,
         transaction: ValidPoolTransaction<T>,
     ) -> Result<ValidPoolTransaction<T>, InsertErr<T>> {
-        if !transaction.origin.is_local() {
+        if !self.local_transactions_config.is_local(transaction.origin, &transaction.sender()) {
             let current_txs =
                 self.tx_counter.get(&transaction.sender_id()).copied().unwrap_or_default();
             if current_txs >= self.max_account_slots {
@@ -1024,6 +1207,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
     }
 
     /// Returns true if `transaction_a` is underpriced compared to `transaction_B`.
+    ///
+    /// For EIP-4844 transactions the blob fee cap must be bumped as well.
     fn is_underpriced(
         transaction_a: &ValidPoolTransaction<T>,
         transaction_b: &ValidPoolTransaction<T>,
@@ -1034,6 +1219,14 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let tx_b_max_priority_fee_per_gas =
             transaction_b.transaction.max_priority_fee_per_gas().unwrap_or(0);
 
+        if let (Some(tx_a_blob_fee), Some(tx_b_blob_fee)) =
+            (transaction_a.max_fee_per_blob_gas(), transaction_b.max_fee_per_blob_gas())
+        {
+            if tx_a_blob_fee <= tx_b_blob_fee * (100 + price_bump) / 100 {
+                return true
+            }
+        }
+
         transaction_a.max_fee_per_gas() <=
             transaction_b.max_fee_per_gas() * (100 + price_bump) / 100 ||
             (tx_a_max_priority_fee_per_gas <=
@@ -1090,6 +1283,17 @@ impl<T: PoolTransaction> AllTransactions<T> {
             state.insert(TxState::NOT_TOO_MUCH_GAS);
         }
 
+        // Check the blob fee of EIP-4844 transactions, this is always satisfied for all other
+        // transaction types
+        if let Some(blob_fee_cap) = transaction.max_fee_per_blob_gas() {
+            state.insert(TxState::BLOB_TRANSACTION);
+            if blob_fee_cap >= self.pending_blob_fee {
+                state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+            }
+        } else {
+            state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+        }
+
         let mut replaced_tx = None;
 
         let pool_tx = PoolInternalTransaction {
@@ -1108,12 +1312,18 @@ impl<T: PoolTransaction> AllTransactions<T> {
             }
             Entry::Occupied(mut entry) => {
                 // Transaction already exists
-                // Ensure the new transaction is not underpriced
 
+                // Blob transactions can only be replaced by blob transactions and vice versa,
+                // because their sidecars are tracked separately.
+                if transaction.is_eip4844() != entry.get().transaction.is_eip4844() {
+                    return Err(InsertErr::TxTypeConflict { transaction: pool_tx.transaction })
+                }
+
+                // Ensure the new transaction is not underpriced
                 if Self::is_underpriced(
                     transaction.as_ref(),
                     entry.get().transaction.as_ref(),
-                    PoolConfig::default().price_bump,
+                    self.price_bumps.price_bump(transaction.tx_type()),
                 ) {
                     return Err(InsertErr::Underpriced {
                         transaction: pool_tx.transaction,
@@ -1241,6 +1451,8 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            price_bumps: Default::default(),
+            local_transactions_config: Default::default(),
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             by_hash: Default::default(),
@@ -1249,6 +1461,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             last_seen_block_number: 0,
             last_seen_block_hash: Default::default(),
             pending_basefee: Default::default(),
+            pending_blob_fee: BLOB_TX_MIN_BLOB_GASPRICE as u128,
         }
     }
 }
@@ -1279,6 +1492,9 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
         block_gas_limit: u64,
         tx_gas_limit: u64,
     },
+    /// Attempted to replace a transaction with a transaction of a conflicting type: EIP-4844
+    /// transactions can only replace and be replaced by EIP-4844 transactions.
+    TxTypeConflict { transaction: Arc<ValidPoolTransaction<T>> },
 }
 
 /// Transaction was successfully inserted into the pool
@@ -1712,4 +1928,70 @@ mod tests {
         assert_eq!(outcome.discarded.len(), 1);
         assert_eq!(pool.pending_pool.len(), 1);
     }
+
+    #[test]
+    fn evict_sender_queued_limit() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig { max_queued_account_slots: 2, ..Default::default() };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        // nonce gap, so all transactions are queued
+        let tx = MockTransaction::eip1559();
+        let txs = (1..=4)
+            .map(|nonce| f.validated(tx.clone().rng_hash().with_nonce(nonce)))
+            .collect::<Vec<_>>();
+        // local transactions are exempt from the limit
+        let local = MockTransaction::eip1559().with_nonce(1);
+        let local = [local.clone(), local.next(), local.next().next()]
+            .map(|tx| f.validated_with_origin(TransactionOrigin::Local, tx));
+        for tx in txs.iter().chain(local.iter()) {
+            pool.add_transaction(tx.clone(), U256::from(1_000), 0).unwrap();
+        }
+        assert_eq!(pool.queued_pool.len(), 7);
+
+        let removed = pool.discard_worst();
+        assert_eq!(
+            removed.iter().map(|(tx, reason)| (*tx.hash(), *reason)).collect::<Vec<_>>(),
+            vec![
+                (*txs[3].hash(), DiscardReason::SenderQueuedLimit),
+                (*txs[2].hash(), DiscardReason::SenderQueuedLimit)
+            ]
+        );
+        assert_eq!(pool.queued_pool.len(), 5);
+        assert_eq!(pool.len(), 5);
+    }
+
+    #[test]
+    fn evict_lowest_fee_across_senders() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            queued_limit: SubPoolLimit { max_txs: 2, ..Default::default() },
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        let worst = f.validated(MockTransaction::eip1559().with_nonce(1).with_max_fee(60));
+        let worst_next = f.validated(worst.transaction.next().with_max_fee(300));
+        let other = f.validated(MockTransaction::eip1559().with_nonce(1).with_max_fee(100));
+        let local = f.validated_with_origin(
+            TransactionOrigin::Local,
+            MockTransaction::eip1559().with_nonce(1).with_max_fee(50),
+        );
+        for tx in [&worst, &worst_next, &other, &local] {
+            pool.add_transaction(tx.clone(), U256::from(1_000), 0).unwrap();
+        }
+
+        // the local transaction is exempt and the descendant of the lowest paying transaction is
+        // evicted along with it
+        let removed = pool.discard_worst();
+        let reason = DiscardReason::SubPoolLimit(SubPool::Queued);
+        assert_eq!(
+            removed.iter().map(|(tx, reason)| (*tx.hash(), *reason)).collect::<Vec<_>>(),
+            vec![(*worst.hash(), reason), (*worst_next.hash(), reason)]
+        );
+        assert_eq!(pool.queued_pool.len(), 2);
+        assert_eq!(pool.len(), 2);
+        assert!(pool.contains(other.hash()));
+        assert!(pool.contains(local.hash()));
+    }
 }
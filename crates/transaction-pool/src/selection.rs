@@ -0,0 +1,417 @@
+//! Strategies for selecting the transactions of a new block from the best transactions of the
+//! pool.
+//!
+//! The pool yields its best transactions in the order of its [TransactionOrdering]. A
+//! [TransactionSelection] is applied on top of that order by the block builder: it can yield
+//! transactions ahead of others, for example for priority senders or an inclusion list, and cap
+//! the number of transactions per sender.
+//!
+//! [TransactionOrdering]: crate::TransactionOrdering
+
+use crate::{traits::BestTransactions, PoolTransaction, TransactionPool, ValidPoolTransaction};
+use parking_lot::RwLock;
+use reth_primitives::{Address, TxHash};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::Arc,
+};
+
+/// The best transactions iterator of a pool with transactions of type `T`.
+pub type BestTransactionsFor<T> = Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>;
+
+/// A strategy for selecting the transactions of a new block from the best transactions of the
+/// pool.
+pub trait TransactionSelection: fmt::Debug + Send + Sync + 'static {
+    /// Returns the transactions that are yielded before all others.
+    ///
+    /// Each transaction is preceded by its ancestors, so the nonce order of every sender is kept.
+    /// This is invoked once per built payload.
+    fn inclusion_list(&self) -> Vec<TxHash> {
+        Vec::new()
+    }
+
+    /// Returns the senders whose transactions are yielded after the inclusion list, but before
+    /// the transactions of all other senders.
+    ///
+    /// This is invoked once per built payload.
+    fn priority_senders(&self) -> Vec<Address> {
+        Vec::new()
+    }
+
+    /// Returns the maximum number of transactions of the sender to include in a block.
+    fn max_transactions_per_sender(&self, _sender: &Address) -> Option<usize> {
+        None
+    }
+}
+
+/// Applies the [TransactionSelection] to the best transactions of the pool.
+///
+/// The transactions of the inclusion list and the priority senders are looked up in the pool, all
+/// other transactions are yielded in the order of `best`.
+pub fn select_best_transactions<P: TransactionPool>(
+    selection: Arc<dyn TransactionSelection>,
+    pool: &P,
+    best: BestTransactionsFor<P::Transaction>,
+) -> SelectedTransactions<P::Transaction> {
+    let mut prioritized = VecDeque::new();
+    let mut prioritized_hashes = HashSet::new();
+    let mut push_sender_transactions = |sender: Address, max_nonce: u64| {
+        let mut transactions = pool.get_transactions_by_sender(sender);
+        transactions.retain(|tx| tx.nonce() <= max_nonce);
+        transactions.sort_by_key(|tx| tx.nonce());
+        for tx in transactions {
+            if prioritized_hashes.insert(*tx.hash()) {
+                prioritized.push_back(tx);
+            }
+        }
+    };
+
+    for tx in pool.get_all(selection.inclusion_list()) {
+        push_sender_transactions(tx.sender(), tx.nonce());
+    }
+    for sender in selection.priority_senders() {
+        push_sender_transactions(sender, u64::MAX);
+    }
+
+    SelectedTransactions {
+        prioritized,
+        prioritized_hashes,
+        best,
+        selection,
+        invalid: HashMap::new(),
+        included: HashMap::new(),
+    }
+}
+
+/// Keeps the order of the pool without any caps.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct PoolOrderSelection;
+
+impl TransactionSelection for PoolOrderSelection {}
+
+/// A list of transactions that are yielded before all others.
+///
+/// The list is shared by all clones, so it can be updated at runtime, for example right before a
+/// new payload is built.
+#[derive(Debug, Clone, Default)]
+pub struct InclusionList {
+    hashes: Arc<RwLock<Vec<TxHash>>>,
+}
+
+// === impl InclusionList ===
+
+impl InclusionList {
+    /// Creates a new list with the given transactions.
+    pub fn new(hashes: impl IntoIterator<Item = TxHash>) -> Self {
+        let list = Self::default();
+        list.extend(hashes);
+        list
+    }
+
+    /// Replaces the transactions of the list.
+    pub fn set(&self, hashes: impl IntoIterator<Item = TxHash>) {
+        let mut list = self.hashes.write();
+        list.clear();
+        for hash in hashes {
+            if !list.contains(&hash) {
+                list.push(hash);
+            }
+        }
+    }
+
+    /// Appends the transactions to the list.
+    pub fn extend(&self, hashes: impl IntoIterator<Item = TxHash>) {
+        let mut list = self.hashes.write();
+        for hash in hashes {
+            if !list.contains(&hash) {
+                list.push(hash);
+            }
+        }
+    }
+
+    /// Removes all transactions from the list.
+    pub fn clear(&self) {
+        self.hashes.write().clear()
+    }
+
+    /// Returns the transactions of the list.
+    pub fn hashes(&self) -> Vec<TxHash> {
+        self.hashes.read().clone()
+    }
+
+    /// Returns `true` if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.read().is_empty()
+    }
+}
+
+/// A configurable [TransactionSelection] with per-sender caps, an inclusion list and priority
+/// senders.
+///
+/// Transactions on the inclusion list are yielded first, followed by the transactions of priority
+/// senders and then all other transactions.
+#[derive(Debug, Clone, Default)]
+pub struct ConfiguredSelection {
+    /// Maximum number of transactions per sender, unlimited if not set.
+    pub max_transactions_per_sender: Option<usize>,
+    /// Senders whose transactions are yielded before the transactions of other senders.
+    ///
+    /// The per-sender cap does not apply to these senders.
+    pub priority_senders: HashSet<Address>,
+    /// Transactions that are yielded before all others.
+    ///
+    /// Keep a clone of the list to update it after the selection was handed to the payload
+    /// builder.
+    pub inclusion_list: InclusionList,
+}
+
+// === impl ConfiguredSelection ===
+
+impl ConfiguredSelection {
+    /// Sets the maximum number of transactions per sender.
+    pub fn with_max_transactions_per_sender(mut self, max: usize) -> Self {
+        self.max_transactions_per_sender = Some(max);
+        self
+    }
+
+    /// Adds senders whose transactions are yielded first.
+    pub fn with_priority_senders(mut self, senders: impl IntoIterator<Item = Address>) -> Self {
+        self.priority_senders.extend(senders);
+        self
+    }
+
+    /// Adds transactions that are yielded before all others.
+    pub fn with_inclusion_list(self, hashes: impl IntoIterator<Item = TxHash>) -> Self {
+        self.inclusion_list.extend(hashes);
+        self
+    }
+}
+
+impl TransactionSelection for ConfiguredSelection {
+    fn inclusion_list(&self) -> Vec<TxHash> {
+        self.inclusion_list.hashes()
+    }
+
+    fn priority_senders(&self) -> Vec<Address> {
+        self.priority_senders.iter().copied().collect()
+    }
+
+    fn max_transactions_per_sender(&self, sender: &Address) -> Option<usize> {
+        if self.priority_senders.contains(sender) {
+            return None
+        }
+        self.max_transactions_per_sender
+    }
+}
+
+/// The best transactions of the pool with a [TransactionSelection] applied, see
+/// [select_best_transactions].
+///
+/// The per-sender cap of the selection counts the transactions that were included in the block,
+/// so the block builder must report every included transaction via
+/// [SelectedTransactions::mark_included].
+pub struct SelectedTransactions<T: PoolTransaction> {
+    /// The transactions of the inclusion list and the priority senders, yielded first.
+    prioritized: VecDeque<Arc<ValidPoolTransaction<T>>>,
+    /// The hashes of all prioritized transactions, these are skipped in `best`.
+    prioritized_hashes: HashSet<TxHash>,
+    /// The best transactions of the pool.
+    best: BestTransactionsFor<T>,
+    selection: Arc<dyn TransactionSelection>,
+    /// The lowest invalid nonce of senders with invalid transactions.
+    invalid: HashMap<Address, u64>,
+    /// Number of transactions included per sender.
+    included: HashMap<Address, usize>,
+}
+
+impl<T: PoolTransaction> SelectedTransactions<T> {
+    /// Marks the transaction as included in the block, which counts towards the cap of its
+    /// sender.
+    pub fn mark_included(&mut self, tx: &Arc<ValidPoolTransaction<T>>) {
+        *self.included.entry(tx.sender()).or_default() += 1;
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for SelectedTransactions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectedTransactions")
+            .field("prioritized", &self.prioritized.len())
+            .field("selection", &self.selection)
+            .field("included", &self.included)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: PoolTransaction> BestTransactions for SelectedTransactions<T> {
+    fn mark_invalid(&mut self, tx: &Self::Item) {
+        let nonce = self.invalid.entry(tx.sender()).or_insert(u64::MAX);
+        *nonce = (*nonce).min(tx.nonce());
+        self.best.mark_invalid(tx)
+    }
+}
+
+impl<T: PoolTransaction> Iterator for SelectedTransactions<T> {
+    type Item = Arc<ValidPoolTransaction<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tx = match self.prioritized.pop_front() {
+                Some(tx) => tx,
+                None => {
+                    let tx = self.best.next()?;
+                    if self.prioritized_hashes.contains(tx.hash()) {
+                        continue
+                    }
+                    tx
+                }
+            };
+
+            let sender = tx.sender();
+            // skip the descendants of invalid transactions
+            if self.invalid.get(&sender).map_or(false, |nonce| tx.nonce() >= *nonce) {
+                continue
+            }
+
+            let included = self.included.get(&sender).copied().unwrap_or_default();
+            if self
+                .selection
+                .max_transactions_per_sender(&sender)
+                .map_or(false, |max| included >= max)
+            {
+                // the sender reached its cap, this also skips all remaining transactions of the
+                // sender
+                self.mark_invalid(&tx);
+                continue
+            }
+            return Some(tx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{testing_pool, MockTransaction, TestPool},
+        TransactionOrigin,
+    };
+
+    /// Returns a pool with three transactions of a high paying sender and two transactions of a
+    /// low paying sender.
+    async fn pool() -> (TestPool, Vec<MockTransaction>, Vec<MockTransaction>) {
+        let pool = testing_pool();
+
+        let high = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(10);
+        let high =
+            (0..3).map(|nonce| high.clone().rng_hash().with_nonce(nonce)).collect::<Vec<_>>();
+        let low = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(5);
+        let low = (0..2).map(|nonce| low.clone().rng_hash().with_nonce(nonce)).collect::<Vec<_>>();
+
+        let txs = high.iter().chain(low.iter()).cloned().collect();
+        for res in pool.add_transactions(TransactionOrigin::External, txs).await.unwrap() {
+            res.unwrap();
+        }
+        (pool, high, low)
+    }
+
+    fn select(
+        pool: &TestPool,
+        selection: impl TransactionSelection,
+    ) -> SelectedTransactions<MockTransaction> {
+        select_best_transactions(Arc::new(selection), pool, pool.best_transactions())
+    }
+
+    fn hashes<'a>(txs: impl IntoIterator<Item = &'a MockTransaction>) -> Vec<TxHash> {
+        txs.into_iter().map(|tx| tx.get_hash()).collect()
+    }
+
+    /// Includes every yielded transaction and returns their hashes.
+    fn include_all(mut best: SelectedTransactions<MockTransaction>) -> Vec<TxHash> {
+        let mut included = Vec::new();
+        while let Some(tx) = best.next() {
+            best.mark_included(&tx);
+            included.push(*tx.hash());
+        }
+        included
+    }
+
+    #[tokio::test]
+    async fn pool_order() {
+        let (pool, high, low) = pool().await;
+        let best = select(&pool, PoolOrderSelection);
+        let expected = hashes(high.iter().chain(low.iter()));
+        assert_eq!(include_all(best), expected);
+    }
+
+    #[tokio::test]
+    async fn caps_transactions_per_sender() {
+        let (pool, high, low) = pool().await;
+        let best =
+            select(&pool, ConfiguredSelection::default().with_max_transactions_per_sender(2));
+        let expected = hashes(high[..2].iter().chain(low.iter()));
+        assert_eq!(include_all(best), expected);
+    }
+
+    #[tokio::test]
+    async fn cap_counts_included_transactions() {
+        let (pool, high, low) = pool().await;
+        let mut best =
+            select(&pool, ConfiguredSelection::default().with_max_transactions_per_sender(1));
+
+        // the first transaction is skipped without being invalidated, e.g. because its nonce is
+        // too low, so it does not count towards the cap
+        assert_eq!(*best.next().unwrap().hash(), high[0].get_hash());
+
+        let expected = hashes([&high[1], &low[0]]);
+        assert_eq!(include_all(best), expected);
+    }
+
+    #[tokio::test]
+    async fn priority_senders_first() {
+        let (pool, high, low) = pool().await;
+        let selection = ConfiguredSelection::default()
+            .with_max_transactions_per_sender(1)
+            .with_priority_senders([low[0].get_sender()]);
+        let best = select(&pool, selection);
+
+        // the cap does not apply to priority senders
+        let expected = hashes(low.iter().chain(high[..1].iter()));
+        assert_eq!(include_all(best), expected);
+    }
+
+    #[tokio::test]
+    async fn inclusion_list_includes_ancestors() {
+        let (pool, high, low) = pool().await;
+        let selection = ConfiguredSelection::default().with_inclusion_list([low[1].get_hash()]);
+        let mut best = select(&pool, selection);
+
+        let first = best.next().unwrap();
+        assert_eq!(*first.hash(), low[0].get_hash());
+        // invalidating the ancestor skips the included transaction
+        best.mark_invalid(&first);
+
+        let expected = hashes(high.iter());
+        assert_eq!(include_all(best), expected);
+    }
+
+    #[tokio::test]
+    async fn inclusion_list_updated_at_runtime() {
+        let (pool, high, low) = pool().await;
+        let selection = ConfiguredSelection::default();
+        let inclusion_list = selection.inclusion_list.clone();
+        let selection: Arc<dyn TransactionSelection> = Arc::new(selection);
+
+        let best = select_best_transactions(selection.clone(), &pool, pool.best_transactions());
+        assert_eq!(include_all(best), hashes(high.iter().chain(low.iter())));
+
+        // the next payload yields the updated inclusion list first
+        inclusion_list.set([low[0].get_hash()]);
+        let best = select_best_transactions(selection, &pool, pool.best_transactions());
+        assert_eq!(
+            include_all(best),
+            hashes([&low[0]].into_iter().chain(high.iter()).chain([&low[1]]))
+        );
+    }
+}
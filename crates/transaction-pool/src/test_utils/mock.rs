@@ -12,7 +12,10 @@ use rand::{
     prelude::Distribution,
 };
 use reth_primitives::{
-    constants::MIN_PROTOCOL_BASE_FEE, hex, Address, FromRecoveredTransaction,
+    constants::{eip4844::DATA_GAS_PER_BLOB, MIN_PROTOCOL_BASE_FEE},
+    hex,
+    kzg::KzgSettings,
+    Address, BlobTransactionSidecar, BlobTransactionValidationError, FromRecoveredTransaction,
     IntoRecoveredTransaction, Signature, Transaction, TransactionKind, TransactionSigned,
     TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxEip4844, TxHash, TxLegacy, TxType, H256,
     U128, U256,
@@ -110,9 +113,11 @@ pub enum MockTransaction {
         nonce: u64,
         max_fee_per_gas: u128,
         max_priority_fee_per_gas: u128,
+        max_fee_per_blob_gas: u128,
         gas_limit: u64,
         to: TransactionKind,
         value: U256,
+        sidecar: Option<BlobTransactionSidecar>,
     },
 }
 
@@ -154,6 +159,60 @@ impl MockTransaction {
         }
     }
 
+    /// Returns a new EIP4844 transaction with random address and hash and empty values
+    pub fn eip4844() -> Self {
+        MockTransaction::Eip4844 {
+            hash: H256::random(),
+            sender: Address::random(),
+            nonce: 0,
+            max_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
+            max_priority_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
+            max_fee_per_blob_gas: 1,
+            gas_limit: 0,
+            to: TransactionKind::Call(Address::random()),
+            value: Default::default(),
+            sidecar: None,
+        }
+    }
+
+    /// Returns a new EIP4844 transaction with the given sidecar
+    pub fn eip4844_with_sidecar(sidecar: BlobTransactionSidecar) -> Self {
+        let mut transaction = Self::eip4844();
+        if let MockTransaction::Eip4844 { sidecar: ref mut existing_sidecar, .. } = transaction {
+            *existing_sidecar = Some(sidecar);
+        }
+        transaction
+    }
+
+    pub fn set_blob_fee(&mut self, val: u128) -> &mut Self {
+        if let MockTransaction::Eip4844 { max_fee_per_blob_gas, .. } = self {
+            *max_fee_per_blob_gas = val;
+        }
+        self
+    }
+
+    pub fn with_blob_fee(mut self, val: u128) -> Self {
+        self.set_blob_fee(val);
+        self
+    }
+
+    pub fn get_blob_fee(&self) -> Option<u128> {
+        if let MockTransaction::Eip4844 { max_fee_per_blob_gas, .. } = self {
+            Some(*max_fee_per_blob_gas)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a new transaction with a higher blob fee
+    pub fn inc_blob_fee_by(&self, value: u128) -> Self {
+        let mut next = self.clone();
+        if let Some(fee) = self.get_blob_fee() {
+            next.set_blob_fee(fee.checked_add(value).unwrap());
+        }
+        next
+    }
+
     pub fn set_priority_fee(&mut self, val: u128) -> &mut Self {
         if let (MockTransaction::Eip1559 { max_priority_fee_per_gas, .. } |
         MockTransaction::Eip4844 { max_priority_fee_per_gas, .. }) = self
@@ -334,6 +393,10 @@ impl MockTransaction {
     pub fn is_eip1559(&self) -> bool {
         matches!(self, MockTransaction::Eip1559 { .. })
     }
+
+    pub fn is_eip4844(&self) -> bool {
+        matches!(self, MockTransaction::Eip4844 { .. })
+    }
 }
 
 impl PoolTransaction for MockTransaction {
@@ -369,8 +432,19 @@ impl PoolTransaction for MockTransaction {
             MockTransaction::Eip1559 { max_fee_per_gas, value, gas_limit, .. } => {
                 U256::from(*gas_limit) * U256::from(*max_fee_per_gas) + *value
             }
-            MockTransaction::Eip4844 { max_fee_per_gas, value, gas_limit, .. } => {
-                U256::from(*gas_limit) * U256::from(*max_fee_per_gas) + *value
+            MockTransaction::Eip4844 {
+                max_fee_per_gas,
+                max_fee_per_blob_gas,
+                value,
+                gas_limit,
+                sidecar,
+                ..
+            } => {
+                let blob_gas =
+                    sidecar.as_ref().map_or(0, |s| s.blobs.len() as u64) * DATA_GAS_PER_BLOB;
+                U256::from(*gas_limit) * U256::from(*max_fee_per_gas) +
+                    U256::from(*max_fee_per_blob_gas) * U256::from(blob_gas) +
+                    *value
             }
         }
     }
@@ -399,6 +473,10 @@ impl PoolTransaction for MockTransaction {
         }
     }
 
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.get_blob_fee()
+    }
+
     fn effective_tip_per_gas(&self, base_fee: u64) -> Option<u128> {
         let base_fee = base_fee as u128;
         let max_fee_per_gas = self.max_fee_per_gas();
@@ -449,6 +527,28 @@ impl PoolTransaction for MockTransaction {
     fn chain_id(&self) -> Option<u64> {
         Some(1)
     }
+
+    fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
+        match self {
+            MockTransaction::Eip4844 { sidecar, .. } => sidecar.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn take_blob_sidecar(&mut self) -> Option<BlobTransactionSidecar> {
+        match self {
+            MockTransaction::Eip4844 { sidecar, .. } => sidecar.take(),
+            _ => None,
+        }
+    }
+
+    fn validate_blob(
+        &self,
+        _blob: &BlobTransactionSidecar,
+        _settings: &KzgSettings,
+    ) -> Result<bool, BlobTransactionValidationError> {
+        Ok(self.is_eip4844())
+    }
 }
 
 impl FromRecoveredTransaction for MockTransaction {
@@ -505,16 +605,18 @@ impl FromRecoveredTransaction for MockTransaction {
                 input,
                 access_list,
                 blob_versioned_hashes: _,
-                max_fee_per_blob_gas: _,
+                max_fee_per_blob_gas,
             }) => MockTransaction::Eip4844 {
                 hash,
                 sender,
                 nonce,
                 max_fee_per_gas,
                 max_priority_fee_per_gas,
+                max_fee_per_blob_gas,
                 gas_limit,
                 to,
                 value: U256::from(value),
+                sidecar: None,
             },
             Transaction::Eip2930 { .. } => {
                 unimplemented!()
@@ -607,6 +709,7 @@ impl proptest::arbitrary::Arbitrary for MockTransaction {
                     gas_limit,
                     max_fee_per_gas,
                     max_priority_fee_per_gas,
+                    max_fee_per_blob_gas,
                     to,
                     value,
                     input,
@@ -617,9 +720,11 @@ impl proptest::arbitrary::Arbitrary for MockTransaction {
                     nonce: *nonce,
                     max_fee_per_gas: *max_fee_per_gas,
                     max_priority_fee_per_gas: *max_priority_fee_per_gas,
+                    max_fee_per_blob_gas: *max_fee_per_blob_gas,
                     gas_limit: *gas_limit,
                     to: *to,
                     value: U256::from(*value),
+                    sidecar: None,
                 },
             })
             .boxed()
@@ -5,15 +5,16 @@ mod mock;
 mod pool;
 
 use crate::{
-    noop::MockTransactionValidator, Pool, PoolTransaction, TransactionOrigin,
-    TransactionValidationOutcome, TransactionValidator,
+    blobstore::InMemoryBlobStore, noop::MockTransactionValidator, Pool, PoolTransaction,
+    TransactionOrigin, TransactionValidationOutcome, TransactionValidator,
 };
 use async_trait::async_trait;
 pub use mock::*;
 use std::{marker::PhantomData, sync::Arc};
 
 /// A [Pool] used for testing
-pub type TestPool = Pool<MockTransactionValidator<MockTransaction>, MockOrdering>;
+pub type TestPool =
+    Pool<MockTransactionValidator<MockTransaction>, MockOrdering, InMemoryBlobStore>;
 
 /// Returns a new [Pool] used for testing purposes
 pub fn testing_pool() -> TestPool {
@@ -23,5 +24,5 @@ pub fn testing_pool() -> TestPool {
 pub fn testing_pool_with_validator(
     validator: MockTransactionValidator<MockTransaction>,
 ) -> TestPool {
-    Pool::new(validator, MockOrdering::default(), Default::default())
+    Pool::new(validator, MockOrdering::default(), InMemoryBlobStore::default(), Default::default())
 }
@@ -1,4 +1,5 @@
 use crate::{
+    blobstore::BlobStoreError,
     error::PoolResult,
     pool::{state::SubPool, TransactionEvents},
     validate::ValidPoolTransaction,
@@ -6,9 +7,9 @@ use crate::{
 };
 use futures_util::{ready, Stream};
 use reth_primitives::{
-    Address, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, Transaction,
-    TransactionKind, TransactionSignedEcRecovered, TxHash, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
-    H256, U256,
+    kzg::KzgSettings, Address, BlobTransactionSidecar, BlobTransactionValidationError,
+    FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, Transaction, TransactionKind,
+    TransactionSignedEcRecovered, TxHash, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, H256, U256,
 };
 use reth_rlp::Encodable;
 use std::{
@@ -265,6 +266,29 @@ pub trait TransactionPool: Send + Sync + Clone {
 
     /// Returns a set of all senders of transactions in the pool
     fn unique_senders(&self) -> HashSet<Address>;
+
+    /// Returns the [BlobTransactionSidecar] for the given transaction hash if it exists in the blob
+    /// store.
+    fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError>;
+
+    /// Returns all [BlobTransactionSidecar] for the given transaction hashes if they exists in the
+    /// blob store.
+    ///
+    /// This only returns the blobs that were found in the store.
+    /// If there's no blob it will not be returned.
+    fn get_all_blobs(
+        &self,
+        tx_hashes: Vec<TxHash>,
+    ) -> Result<Vec<(TxHash, BlobTransactionSidecar)>, BlobStoreError>;
+
+    /// Returns the exact [BlobTransactionSidecar] for the given transaction hashes in the order
+    /// they were requested.
+    ///
+    /// Returns an error if any of the blobs are not found in the blob store.
+    fn get_all_blobs_exact(
+        &self,
+        tx_hashes: Vec<TxHash>,
+    ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
@@ -436,6 +460,10 @@ pub struct CanonicalStateUpdate {
     ///
     /// The base fee of a block depends on the utilization of the last block and its base fee.
     pub pending_block_base_fee: u64,
+    /// EIP-4844 blob fee of the _next_ (pending) block
+    ///
+    /// Only after Cancun
+    pub pending_block_blob_fee: Option<u128>,
     /// A set of changed accounts across a range of blocks.
     pub changed_accounts: Vec<ChangedAccount>,
     /// All mined transactions in the block range.
@@ -446,8 +474,8 @@ pub struct CanonicalStateUpdate {
 
 impl fmt::Display for CanonicalStateUpdate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{{ hash: {}, number: {}, pending_block_base_fee: {}, changed_accounts: {}, mined_transactions: {} }}",
-            self.hash, self.number, self.pending_block_base_fee, self.changed_accounts.len(), self.mined_transactions.len())
+        write!(f, "{{ hash: {}, number: {}, pending_block_base_fee: {}, pending_block_blob_fee: {:?}, changed_accounts: {}, mined_transactions: {} }}",
+            self.hash, self.number, self.pending_block_base_fee, self.pending_block_blob_fee, self.changed_accounts.len(), self.mined_transactions.len())
     }
 }
 
@@ -527,6 +555,11 @@ pub trait PoolTransaction:
     /// This will return `None` for non-EIP1559 transactions
     fn max_priority_fee_per_gas(&self) -> Option<u128>;
 
+    /// Returns the EIP-4844 max fee per data gas
+    ///
+    /// This will return `None` for non-EIP4844 transactions
+    fn max_fee_per_blob_gas(&self) -> Option<u128>;
+
     /// Returns the effective tip for this transaction.
     ///
     /// For EIP-1559 transactions: `min(max_fee_per_gas - base_fee, max_priority_fee_per_gas)`.
@@ -562,20 +595,44 @@ pub trait PoolTransaction:
 
     /// Returns chain_id
     fn chain_id(&self) -> Option<u64>;
+
+    /// Returns the blob sidecar of the transaction, if it is an EIP-4844 transaction that still
+    /// carries its sidecar.
+    fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar>;
+
+    /// Removes the blob sidecar from the transaction and returns it.
+    ///
+    /// The pool keeps sidecars in a separate [BlobStore](crate::blobstore::BlobStore), so that the
+    /// in-memory transaction stays small.
+    fn take_blob_sidecar(&mut self) -> Option<BlobTransactionSidecar>;
+
+    /// Validates the blob sidecar of the transaction with the given settings.
+    ///
+    /// This returns `Ok(false)` if the transaction is not an EIP-4844 transaction.
+    fn validate_blob(
+        &self,
+        blob: &BlobTransactionSidecar,
+        settings: &KzgSettings,
+    ) -> Result<bool, BlobTransactionValidationError>;
 }
 
 /// The default [PoolTransaction] for the [Pool](crate::Pool).
 ///
 /// This type is essentially a wrapper around [TransactionSignedEcRecovered] with additional fields
 /// derived from the transaction that are frequently used by the pools for ordering.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PooledTransaction {
     /// EcRecovered transaction info
     pub(crate) transaction: TransactionSignedEcRecovered,
 
     /// For EIP-1559 transactions: `max_fee_per_gas * gas_limit + tx_value`.
     /// For legacy transactions: `gas_price * gas_limit + tx_value`.
+    /// For EIP-4844 transactions: `max_fee_per_gas * gas_limit + tx_value +
+    /// max_fee_per_blob_gas * blob_gas`.
     pub(crate) cost: U256,
+
+    /// The blob sidecar of an EIP-4844 transaction, until it is moved into the blob store.
+    pub(crate) blob_sidecar: Option<BlobTransactionSidecar>,
 }
 
 impl PooledTransaction {
@@ -585,11 +642,22 @@ impl PooledTransaction {
             Transaction::Legacy(t) => U256::from(t.gas_price) * U256::from(t.gas_limit),
             Transaction::Eip2930(t) => U256::from(t.gas_price) * U256::from(t.gas_limit),
             Transaction::Eip1559(t) => U256::from(t.max_fee_per_gas) * U256::from(t.gas_limit),
-            Transaction::Eip4844(t) => U256::from(t.max_fee_per_gas) * U256::from(t.gas_limit),
+            Transaction::Eip4844(t) => {
+                U256::from(t.max_fee_per_gas) * U256::from(t.gas_limit) +
+                    U256::from(t.max_fee_per_blob_gas) * U256::from(t.blob_gas())
+            }
         };
         let cost = gas_cost + U256::from(transaction.value());
 
-        Self { transaction, cost }
+        Self { transaction, cost, blob_sidecar: None }
+    }
+
+    /// Create new instance of [Self] for an EIP-4844 transaction with its blob sidecar.
+    pub fn with_blob_sidecar(
+        transaction: TransactionSignedEcRecovered,
+        sidecar: BlobTransactionSidecar,
+    ) -> Self {
+        Self { blob_sidecar: Some(sidecar), ..Self::new(transaction) }
     }
 
     /// Return the reference to the underlying transaction.
@@ -653,6 +721,16 @@ impl PoolTransaction for PooledTransaction {
         }
     }
 
+    /// Returns the EIP-4844 max fee per data gas
+    ///
+    /// This will return `None` for non-EIP4844 transactions
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        match &self.transaction.transaction {
+            Transaction::Eip4844(tx) => Some(tx.max_fee_per_blob_gas),
+            _ => None,
+        }
+    }
+
     /// Returns the effective tip for this transaction.
     ///
     /// For EIP-1559 transactions: `min(max_fee_per_gas - base_fee, max_priority_fee_per_gas)`.
@@ -692,6 +770,25 @@ impl PoolTransaction for PooledTransaction {
     fn chain_id(&self) -> Option<u64> {
         self.transaction.chain_id()
     }
+
+    fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
+        self.blob_sidecar.as_ref()
+    }
+
+    fn take_blob_sidecar(&mut self) -> Option<BlobTransactionSidecar> {
+        self.blob_sidecar.take()
+    }
+
+    fn validate_blob(
+        &self,
+        sidecar: &BlobTransactionSidecar,
+        settings: &KzgSettings,
+    ) -> Result<bool, BlobTransactionValidationError> {
+        match &self.transaction.transaction {
+            Transaction::Eip4844(tx) => tx.validate_blob(sidecar, settings),
+            _ => Ok(false),
+        }
+    }
 }
 
 impl FromRecoveredTransaction for PooledTransaction {
@@ -721,9 +818,13 @@ pub struct PoolSize {
     pub queued: usize,
     /// Reported size of transactions in the _queued_ sub-pool.
     pub queued_size: usize,
+    /// Number of transactions in the _blob_ sub-pool.
+    pub blob: usize,
+    /// Reported size of transactions in the _blob_ sub-pool.
+    pub blob_size: usize,
     /// Number of all transactions of all sub-pools
     ///
-    /// Note: this is the sum of ```pending + basefee + queued```
+    /// Note: this is the sum of ```pending + basefee + queued + blob```
     pub total: usize,
 }
 
@@ -739,6 +840,11 @@ pub struct BlockInfo {
     /// Note: this is the derived base fee of the _next_ block that builds on the clock the pool is
     /// currently tracking.
     pub pending_basefee: u64,
+    /// Currently enforced blob fee: the threshold for eip-4844 blob transactions.
+    ///
+    /// Note: this is the derived blob fee of the _next_ block that builds on the block the pool is
+    /// currently tracking, this is only `Some` after Cancun.
+    pub pending_blob_fee: Option<u128>,
 }
 
 /// A Stream that yields full transactions the subpool
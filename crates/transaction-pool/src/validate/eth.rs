@@ -1,7 +1,7 @@
 //! Ethereum transaction validator.
 
 use crate::{
-    error::InvalidPoolTransactionError,
+    error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     traits::{PoolTransaction, TransactionOrigin},
     validate::{
         task::ValidationJobSender, TransactionValidatorError, ValidationTask, MAX_INIT_CODE_SIZE,
@@ -10,8 +10,10 @@ use crate::{
     TransactionValidationOutcome, TransactionValidator,
 };
 use reth_primitives::{
-    constants::ETHEREUM_BLOCK_GAS_LIMIT, ChainSpec, InvalidTransactionError, EIP1559_TX_TYPE_ID,
-    EIP2930_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
+    constants::{eip4844::KZG_TRUSTED_SETUP, ETHEREUM_BLOCK_GAS_LIMIT},
+    kzg::KzgSettings,
+    ChainSpec, InvalidTransactionError, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    LEGACY_TX_TYPE_ID,
 };
 use reth_provider::{AccountReader, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -132,10 +134,14 @@ pub struct EthTransactionValidatorBuilder {
     eip2718: bool,
     /// Fork indicator whether we are using EIP-1559 type transactions.
     eip1559: bool,
+    /// Fork indicator whether we are in the Cancun hardfork.
+    cancun: bool,
     /// The current max gas limit
     block_gas_limit: u64,
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
+    /// Stores the setup and parameters needed for validating KZG proofs.
+    kzg_settings: Arc<KzgSettings>,
     /// Determines how many additional tasks to spawn
     ///
     /// Default is 1
@@ -152,8 +158,10 @@ impl EthTransactionValidatorBuilder {
             shanghai: true,
             eip2718: true,
             eip1559: true,
+            cancun: false,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             minimum_priority_fee: None,
+            kzg_settings: KZG_TRUSTED_SETUP.clone(),
             additional_tasks: 1,
             // default to true, can potentially take this as a param in the future
             propagate_local_transactions: true,
@@ -171,6 +179,23 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Enables the Cancun fork.
+    pub fn cancun(self) -> Self {
+        self.set_cancun(true)
+    }
+
+    /// Set the Cancun fork.
+    pub fn set_cancun(mut self, cancun: bool) -> Self {
+        self.cancun = cancun;
+        self
+    }
+
+    /// Sets the [KzgSettings] to use for validating KZG proofs of blob transactions.
+    pub fn with_kzg_settings(mut self, kzg_settings: Arc<KzgSettings>) -> Self {
+        self.kzg_settings = kzg_settings;
+        self
+    }
+
     /// Disables the eip2718 support.
     pub fn no_eip2718(self) -> Self {
         self.set_eip2718(false)
@@ -240,8 +265,10 @@ impl EthTransactionValidatorBuilder {
             shanghai,
             eip2718,
             eip1559,
+            cancun,
             block_gas_limit,
             minimum_priority_fee,
+            kzg_settings,
             additional_tasks,
             propagate_local_transactions,
         } = self;
@@ -252,8 +279,10 @@ impl EthTransactionValidatorBuilder {
             shanghai,
             eip2718,
             eip1559,
+            cancun,
             block_gas_limit,
             minimum_priority_fee,
+            kzg_settings,
             propagate_local_transactions,
             _marker: Default::default(),
         };
@@ -294,10 +323,14 @@ struct EthTransactionValidatorInner<Client, T> {
     eip2718: bool,
     /// Fork indicator whether we are using EIP-1559 type transactions.
     eip1559: bool,
+    /// Fork indicator whether we are in the Cancun hardfork.
+    cancun: bool,
     /// The current max gas limit
     block_gas_limit: u64,
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
+    /// Stores the setup and parameters needed for validating KZG proofs.
+    kzg_settings: Arc<KzgSettings>,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
     /// Toggle to determine if a local transaction should be propagated
@@ -351,6 +384,16 @@ where
                 }
             }
 
+            EIP4844_TX_TYPE_ID => {
+                // Reject blob transactions until Cancun activates.
+                if !self.cancun {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::TxTypeNotSupported.into(),
+                    )
+                }
+            }
+
             _ => {
                 return TransactionValidationOutcome::Invalid(
                     transaction,
@@ -455,6 +498,31 @@ where
             )
         }
 
+        // Blob transactions must carry a sidecar that matches the versioned hashes and KZG proofs
+        if transaction.is_eip4844() {
+            let Some(sidecar) = transaction.blob_sidecar() else {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    Eip4844PoolTransactionError::MissingEip4844BlobSidecar.into(),
+                )
+            };
+            match transaction.validate_blob(sidecar, &self.kzg_settings) {
+                Ok(true) => {}
+                Ok(false) => {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        Eip4844PoolTransactionError::InvalidEip4844Blob.into(),
+                    )
+                }
+                Err(err) => {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        Eip4844PoolTransactionError::InvalidEip4844BlobSidecar(err).into(),
+                    )
+                }
+            }
+        }
+
         // Return the valid transaction
         TransactionValidationOutcome::Valid {
             balance: account.balance,
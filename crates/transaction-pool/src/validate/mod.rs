@@ -175,6 +175,13 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction.max_fee_per_gas()
     }
 
+    /// Returns the EIP-4844 max fee per data gas
+    ///
+    /// This will return `None` for non-EIP4844 transactions
+    pub fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.transaction.max_fee_per_blob_gas()
+    }
+
     /// Returns the effective tip for this transaction.
     ///
     /// For EIP-1559 transactions: `min(max_fee_per_gas - base_fee, max_priority_fee_per_gas)`.
@@ -194,6 +201,11 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction.gas_limit()
     }
 
+    /// Returns true if the transaction is an EIP-4844 transaction.
+    pub fn is_eip4844(&self) -> bool {
+        self.transaction.is_eip4844()
+    }
+
     /// Whether the transaction originated locally.
     pub fn is_local(&self) -> bool {
         self.origin.is_local()
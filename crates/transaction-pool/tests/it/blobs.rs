@@ -0,0 +1,89 @@
+use assert_matches::assert_matches;
+use reth_primitives::{BlobTransactionSidecar, H256};
+use reth_transaction_pool::{
+    blobstore::{BlobStore, BlobStoreError, InMemoryBlobStore},
+    error::PoolError,
+    test_utils::{testing_pool, MockOrdering, MockTransaction, MockTransactionValidator},
+    Pool, TransactionOrigin, TransactionPool,
+};
+
+/// A blob store that fails to insert any sidecar.
+#[derive(Debug, Default)]
+struct FailingBlobStore(InMemoryBlobStore);
+
+impl BlobStore for FailingBlobStore {
+    fn insert(&self, tx: H256, _data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+        Err(BlobStoreError::MissingSidecar(tx))
+    }
+
+    fn insert_all(&self, txs: Vec<(H256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+        match txs.first() {
+            Some((tx, _)) => Err(BlobStoreError::MissingSidecar(*tx)),
+            None => Ok(()),
+        }
+    }
+
+    fn delete(&self, tx: H256) -> Result<(), BlobStoreError> {
+        self.0.delete(tx)
+    }
+
+    fn delete_all(&self, txs: Vec<H256>) -> Result<(), BlobStoreError> {
+        self.0.delete_all(txs)
+    }
+
+    fn get(&self, tx: H256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+        self.0.get(tx)
+    }
+
+    fn get_all(
+        &self,
+        txs: Vec<H256>,
+    ) -> Result<Vec<(H256, BlobTransactionSidecar)>, BlobStoreError> {
+        self.0.get_all(txs)
+    }
+
+    fn get_exact(&self, txs: Vec<H256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+        self.0.get_exact(txs)
+    }
+
+    fn data_size_hint(&self) -> Option<usize> {
+        self.0.data_size_hint()
+    }
+
+    fn blobs_len(&self) -> usize {
+        self.0.blobs_len()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_stores_blob_sidecar() {
+    let txpool = testing_pool();
+    let sidecar = BlobTransactionSidecar::default();
+    let transaction = MockTransaction::eip4844_with_sidecar(sidecar.clone());
+    let hash = transaction.get_hash();
+
+    let added = txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await;
+    assert_matches!(added, Ok(added) if added == hash);
+    assert_eq!(txpool.get_blob(hash).unwrap(), Some(sidecar));
+
+    // importing the transaction again keeps the sidecar of the pooled transaction
+    let added = txpool.add_transaction(TransactionOrigin::External, transaction).await;
+    assert_matches!(added, Err(PoolError::AlreadyImported(_)));
+    assert!(txpool.get_blob(hash).unwrap().is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_rejects_blob_transaction_without_stored_sidecar() {
+    let txpool = Pool::new(
+        MockTransactionValidator::default(),
+        MockOrdering::default(),
+        FailingBlobStore::default(),
+        Default::default(),
+    );
+    let transaction = MockTransaction::eip4844_with_sidecar(Default::default());
+    let hash = transaction.get_hash();
+
+    let added = txpool.add_transaction(TransactionOrigin::External, transaction).await;
+    assert_matches!(added, Err(PoolError::Other(err_hash, _)) if err_hash == hash);
+    assert!(!txpool.contains(&hash));
+}
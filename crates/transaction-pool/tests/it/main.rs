@@ -1,5 +1,7 @@
 //! transaction-pool integration tests
 
+#[cfg(feature = "test-utils")]
+mod blobs;
 #[cfg(feature = "test-utils")]
 mod listeners;
 #[cfg(feature = "test-utils")]
@@ -27,3 +27,33 @@ pub enum StorageRootError {
     #[error(transparent)]
     DB(#[from] reth_db::DatabaseError),
 }
+
+impl From<StorageRootError> for reth_db::DatabaseError {
+    fn from(err: StorageRootError) -> Self {
+        match err {
+            StorageRootError::DB(err) => err,
+        }
+    }
+}
+
+/// Range proof verification error.
+#[derive(Error, PartialEq, Eq, Clone, Debug)]
+pub enum RangeProofError {
+    /// The keys of the range are not strictly increasing or precede the origin.
+    #[error("range keys are not in increasing order")]
+    UnorderedKeys,
+    /// A node required for verification is missing from the proof.
+    #[error("missing proof node {0:?}")]
+    MissingNode(reth_primitives::H256),
+    /// A proof node could not be decoded.
+    #[error("invalid proof node: {0}")]
+    InvalidNode(#[from] reth_rlp::DecodeError),
+    /// The root reconstructed from the range and the proof doesn't match the expected root.
+    #[error("range proof root mismatch: expected {expected:?}, got {got:?}")]
+    RootMismatch {
+        /// The expected root.
+        expected: reth_primitives::H256,
+        /// The reconstructed root.
+        got: reth_primitives::H256,
+    },
+}
@@ -2,11 +2,15 @@ use super::{HashedAccountCursor, HashedCursorFactory, HashedStorageCursor};
 use crate::prefix_set::{PrefixSet, PrefixSetMut};
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
+    models::{AccountBeforeTx, BlockNumberAddress},
     tables,
     transaction::{DbTx, DbTxGAT},
+    DatabaseError,
 };
-use reth_primitives::{trie::Nibbles, Account, StorageEntry, H256, U256};
-use std::collections::{HashMap, HashSet};
+use reth_primitives::{
+    keccak256, trie::Nibbles, Account, Address, BlockNumber, StorageEntry, H256, U256,
+};
+use std::collections::{hash_map, HashMap, HashSet};
 
 /// The post state account storage with hashed slots.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -78,6 +82,63 @@ impl Default for HashedPostState {
 }
 
 impl HashedPostState {
+    /// Initialize [HashedPostState] from the account and storage changesets starting at the
+    /// given block number.
+    ///
+    /// The resulting state reverts the current database state to the state at the start of
+    /// the block, i.e. for each changed key it contains the value before the first change
+    /// at or after `from`.
+    pub fn from_reverts<'a, TX: DbTx<'a>>(
+        tx: &TX,
+        from: BlockNumber,
+    ) -> Result<Self, DatabaseError> {
+        // Iterate over account changesets and record the earliest value of each account.
+        let mut accounts = HashMap::<Address, Option<Account>>::default();
+        let mut account_changesets_cursor = tx.cursor_read::<tables::AccountChangeSet>()?;
+        for entry in account_changesets_cursor.walk_range(from..)? {
+            let (_, AccountBeforeTx { address, info }) = entry?;
+            accounts.entry(address).or_insert(info);
+        }
+
+        // Iterate over storage changesets and record the earliest value of each storage slot.
+        let mut storages = HashMap::<Address, HashMap<H256, U256>>::default();
+        let mut storage_changesets_cursor = tx.cursor_read::<tables::StorageChangeSet>()?;
+        for entry in
+            storage_changesets_cursor.walk_range(BlockNumberAddress((from, Address::zero()))..)?
+        {
+            let (BlockNumberAddress((_, address)), StorageEntry { key, value }) = entry?;
+            if let hash_map::Entry::Vacant(entry) = storages.entry(address).or_default().entry(key)
+            {
+                entry.insert(value);
+            }
+        }
+
+        let mut this = Self::default();
+        for (address, account) in accounts {
+            let hashed_address = keccak256(address);
+            if let Some(account) = account {
+                this.insert_account(hashed_address, account);
+            } else {
+                this.insert_cleared_account(hashed_address);
+            }
+        }
+
+        for (address, storage) in storages {
+            let mut hashed_storage = HashedStorage::new(false);
+            for (slot, value) in storage {
+                let hashed_slot = keccak256(slot);
+                if value == U256::ZERO {
+                    hashed_storage.insert_zero_valued_slot(hashed_slot);
+                } else {
+                    hashed_storage.insert_non_zero_valued_storage(hashed_slot, value);
+                }
+            }
+            this.insert_hashed_storage(keccak256(address), hashed_storage);
+        }
+
+        Ok(this.sorted())
+    }
+
     /// Sort and return self.
     pub fn sorted(mut self) -> Self {
         self.sort();
@@ -117,6 +178,17 @@ impl HashedPostState {
     /// The prefix sets contain the hashed account and storage keys that have been changed in the
     /// post state.
     pub fn construct_prefix_sets(&self) -> (PrefixSet, HashMap<H256, PrefixSet>) {
+        let (account_prefix_set, storage_prefix_set) = self.construct_prefix_sets_mut();
+        (
+            account_prefix_set.freeze(),
+            storage_prefix_set.into_iter().map(|(k, v)| (k, v.freeze())).collect(),
+        )
+    }
+
+    /// Construct mutable [PrefixSetMut]s from hashed post state.
+    ///
+    /// See [Self::construct_prefix_sets] for more info.
+    pub fn construct_prefix_sets_mut(&self) -> (PrefixSetMut, HashMap<H256, PrefixSetMut>) {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_set: HashMap<H256, PrefixSetMut> = HashMap::default();
@@ -142,10 +214,7 @@ impl HashedPostState {
             }
         }
 
-        (
-            account_prefix_set.freeze(),
-            storage_prefix_set.into_iter().map(|(k, v)| (k, v.freeze())).collect(),
-        )
+        (account_prefix_set, storage_prefix_set)
     }
 }
 
@@ -36,12 +36,24 @@ pub mod hashed_cursor;
 pub mod walker;
 
 mod errors;
-pub use errors::{StateRootError, StorageRootError};
+pub use errors::{RangeProofError, StateRootError, StorageRootError};
 
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
 
+/// The implementation of the state root calculation with storage roots computed in parallel.
+mod parallel;
+pub use parallel::ParallelStateRoot;
+
+/// Merkle proof generation.
+mod proof;
+pub use proof::Proof;
+
+/// Merkle range proof verification.
+mod range_proof;
+pub use range_proof::verify_range_proof;
+
 /// Buffer for trie updates.
 pub mod updates;
 
@@ -0,0 +1,285 @@
+use crate::{
+    hashed_cursor::{HashedCursorFactory, HashedPostState, HashedPostStateCursorFactory},
+    prefix_set::{PrefixSet, PrefixSetLoader, PrefixSetMut},
+    updates::TrieUpdates,
+    StateRoot, StateRootError, StorageRoot, StorageRootError,
+};
+use rayon::prelude::*;
+use reth_db::{database::Database, transaction::DbTx};
+use reth_primitives::{BlockNumber, H256};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
+
+/// Precomputed storage roots with the hashed address as key and the storage root, the number of
+/// walked storage slots and the storage trie updates as the value.
+type StorageRoots = HashMap<H256, (H256, usize, TrieUpdates)>;
+
+/// ParallelStateRoot is used to compute the root node of a state trie, calculating the storage
+/// roots of the changed accounts in parallel.
+///
+/// Each storage root is computed on the global rayon thread pool using its own read-only database
+/// transaction. The results are merged into the walk of the account trie afterwards.
+///
+/// Since every worker opens a new transaction, the calculator only observes committed database
+/// state. Uncommitted changes must be supplied through
+/// [ParallelStateRoot::with_hashed_post_state].
+#[derive(Debug)]
+pub struct ParallelStateRoot<'a, DB> {
+    /// The database used to open read-only transactions.
+    db: &'a DB,
+    /// The hashed state on top of the database state.
+    hashed_post_state: Option<&'a HashedPostState>,
+    /// A set of account prefixes that have changed.
+    changed_account_prefixes: PrefixSet,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    changed_storage_prefixes: HashMap<H256, PrefixSet>,
+    /// A map containing keys of accounts that were destroyed.
+    destroyed_accounts: HashSet<H256>,
+}
+
+impl<'a, DB: Database> ParallelStateRoot<'a, DB> {
+    /// Create a new [ParallelStateRoot] instance.
+    pub fn new(db: &'a DB) -> Self {
+        Self {
+            db,
+            hashed_post_state: None,
+            changed_account_prefixes: PrefixSetMut::default().freeze(),
+            changed_storage_prefixes: HashMap::default(),
+            destroyed_accounts: HashSet::default(),
+        }
+    }
+
+    /// Given a block number range, identifies all the accounts and storage keys that
+    /// have changed.
+    ///
+    /// # Returns
+    ///
+    /// An instance of parallel state root calculator with account and storage prefixes loaded.
+    pub fn incremental_root_calculator(
+        db: &'a DB,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Self, StateRootError> {
+        let tx = db.tx()?;
+        let loaded_prefix_sets = PrefixSetLoader::new(&tx).load(range)?;
+        Ok(Self::new(db)
+            .with_changed_account_prefixes(loaded_prefix_sets.account_prefix_set.freeze())
+            .with_changed_storage_prefixes(
+                loaded_prefix_sets
+                    .storage_prefix_sets
+                    .into_iter()
+                    .map(|(k, v)| (k, v.freeze()))
+                    .collect(),
+            )
+            .with_destroyed_accounts(loaded_prefix_sets.destroyed_accounts))
+    }
+
+    /// Set the hashed post state to overlay on top of the database state.
+    pub fn with_hashed_post_state(mut self, hashed_post_state: &'a HashedPostState) -> Self {
+        self.hashed_post_state = Some(hashed_post_state);
+        self
+    }
+
+    /// Set the changed account prefixes.
+    pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSet) -> Self {
+        self.changed_account_prefixes = prefixes;
+        self
+    }
+
+    /// Set the changed storage prefixes.
+    pub fn with_changed_storage_prefixes(mut self, prefixes: HashMap<H256, PrefixSet>) -> Self {
+        self.changed_storage_prefixes = prefixes;
+        self
+    }
+
+    /// Set the destroyed accounts.
+    pub fn with_destroyed_accounts(mut self, accounts: HashSet<H256>) -> Self {
+        self.destroyed_accounts = accounts;
+        self
+    }
+
+    /// Computes the storage roots of the changed accounts in parallel, then walks the intermediate
+    /// nodes of existing state trie (if any) and hashed entries. Collects the updates in the
+    /// process.
+    ///
+    /// # Returns
+    ///
+    /// The state root hash and the trie updates.
+    pub fn root_with_updates(self) -> Result<(H256, TrieUpdates), StateRootError> {
+        self.calculate(true)
+    }
+
+    /// Computes the storage roots of the changed accounts in parallel, then walks the intermediate
+    /// nodes of existing state trie (if any) and hashed entries.
+    ///
+    /// # Returns
+    ///
+    /// The state root hash.
+    pub fn root(self) -> Result<H256, StateRootError> {
+        let (root, _) = self.calculate(false)?;
+        Ok(root)
+    }
+
+    fn calculate(self, retain_updates: bool) -> Result<(H256, TrieUpdates), StateRootError> {
+        let storage_roots = self.storage_roots(retain_updates)?;
+        tracing::debug!(
+            target: "trie::parallel_state_root",
+            storage_roots = storage_roots.len(),
+            "calculated storage roots"
+        );
+
+        let tx = self.db.tx()?;
+        let state_root = StateRoot::new(&tx)
+            .with_changed_account_prefixes(self.changed_account_prefixes)
+            .with_changed_storage_prefixes(self.changed_storage_prefixes)
+            .with_destroyed_accounts(self.destroyed_accounts)
+            .with_storage_roots(storage_roots);
+        match self.hashed_post_state {
+            Some(hashed_post_state) => {
+                let hashed_cursor_factory =
+                    HashedPostStateCursorFactory::new(&tx, hashed_post_state);
+                let state_root = state_root.with_hashed_cursor_factory(&hashed_cursor_factory);
+                if retain_updates {
+                    state_root.root_with_updates()
+                } else {
+                    Ok((state_root.root()?, TrieUpdates::default()))
+                }
+            }
+            None => {
+                if retain_updates {
+                    state_root.root_with_updates()
+                } else {
+                    Ok((state_root.root()?, TrieUpdates::default()))
+                }
+            }
+        }
+    }
+
+    /// Computes the storage roots for all accounts with changed storage, opening one read-only
+    /// transaction per rayon job.
+    fn storage_roots(&self, retain_updates: bool) -> Result<StorageRoots, StorageRootError> {
+        self.changed_storage_prefixes
+            .par_iter()
+            .map_init(
+                || self.db.tx(),
+                |tx, (hashed_address, prefixes)| {
+                    let tx = tx.as_ref().map_err(|error| StorageRootError::DB(error.clone()))?;
+                    let result = match self.hashed_post_state {
+                        Some(hashed_post_state) => {
+                            let hashed_cursor_factory =
+                                HashedPostStateCursorFactory::new(tx, hashed_post_state);
+                            let calculator = StorageRoot::new_hashed_with_factory(
+                                tx,
+                                &hashed_cursor_factory,
+                                *hashed_address,
+                            )
+                            .with_changed_prefixes(prefixes.clone());
+                            storage_root(&calculator, retain_updates)?
+                        }
+                        None => {
+                            let calculator = StorageRoot::new_hashed(tx, *hashed_address)
+                                .with_changed_prefixes(prefixes.clone());
+                            storage_root(&calculator, retain_updates)?
+                        }
+                    };
+                    Ok((*hashed_address, result))
+                },
+            )
+            .collect()
+    }
+}
+
+/// Computes the storage root, only collecting the walked slots and trie updates if the updates
+/// are retained.
+fn storage_root<'a, 'b, 'tx, TX, H>(
+    calculator: &StorageRoot<'a, 'b, TX, H>,
+    retain_updates: bool,
+) -> Result<(H256, usize, TrieUpdates), StorageRootError>
+where
+    TX: DbTx<'tx>,
+    H: HashedCursorFactory<'b>,
+{
+    if retain_updates {
+        calculator.root_with_updates()
+    } else {
+        Ok((calculator.root()?, 0, TrieUpdates::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hashed_cursor::HashedStorage, test_utils::state_root_prehashed};
+    use proptest::{prelude::ProptestConfig, proptest};
+    use reth_db::{tables, test_utils::create_test_rw_db, transaction::DbTxMut};
+    use reth_primitives::{keccak256, trie::Nibbles, Account, StorageEntry, MAINNET, U256};
+    use reth_provider::ProviderFactory;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn parallel_root_matches_sequential() {
+        proptest!(ProptestConfig::with_cases(10), |(state: BTreeMap<H256, (Account, BTreeMap<H256, U256>)>)| {
+            let db = create_test_rw_db();
+            let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+            let provider = factory.provider_rw().unwrap();
+            let mut account_prefixes = PrefixSetMut::default();
+            let mut storage_prefixes = HashMap::<H256, PrefixSetMut>::default();
+            for (hashed_address, (account, storage)) in &state {
+                provider.tx_ref().put::<tables::HashedAccount>(*hashed_address, *account).unwrap();
+                account_prefixes.insert(Nibbles::unpack(hashed_address));
+                for (hashed_slot, value) in storage.iter().filter(|(_, value)| *value != &U256::ZERO) {
+                    provider.tx_ref().put::<tables::HashedStorage>(
+                        *hashed_address,
+                        StorageEntry { key: *hashed_slot, value: *value },
+                    )
+                    .unwrap();
+                    storage_prefixes.entry(*hashed_address).or_default().insert(Nibbles::unpack(hashed_slot));
+                }
+            }
+            provider.commit().unwrap();
+
+            let storage_prefixes: HashMap<_, _> =
+                storage_prefixes.into_iter().map(|(k, v)| (k, v.freeze())).collect();
+            let (parallel_root, _) = ParallelStateRoot::new(db.as_ref())
+                .with_changed_account_prefixes(account_prefixes.freeze())
+                .with_changed_storage_prefixes(storage_prefixes)
+                .root_with_updates()
+                .unwrap();
+
+            let tx = db.tx().unwrap();
+            assert_eq!(parallel_root, StateRoot::new(&tx).root().unwrap());
+            let expected = state_root_prehashed(state.into_iter().map(|(hashed_address, (account, storage))| {
+                (hashed_address, (account, storage.into_iter().filter(|(_, value)| value != &U256::ZERO)))
+            }));
+            assert_eq!(parallel_root, expected);
+        });
+    }
+
+    #[test]
+    fn parallel_root_with_post_state() {
+        let db = create_test_rw_db();
+        let hashed_address = keccak256(H256::random());
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let storage = BTreeMap::from([(H256::random(), U256::from(1))]);
+
+        let mut hashed_post_state = HashedPostState::default();
+        hashed_post_state.insert_account(hashed_address, account);
+        let mut hashed_storage = HashedStorage::new(false);
+        for (slot, value) in &storage {
+            hashed_storage.insert_non_zero_valued_storage(*slot, *value);
+        }
+        hashed_post_state.insert_hashed_storage(hashed_address, hashed_storage);
+        let hashed_post_state = hashed_post_state.sorted();
+        let (account_prefixes, storage_prefixes) = hashed_post_state.construct_prefix_sets();
+
+        let root = ParallelStateRoot::new(db.as_ref())
+            .with_hashed_post_state(&hashed_post_state)
+            .with_changed_account_prefixes(account_prefixes)
+            .with_changed_storage_prefixes(storage_prefixes)
+            .root()
+            .unwrap();
+        assert_eq!(root, state_root_prehashed([(hashed_address, (account, storage))].into_iter()));
+    }
+}
@@ -0,0 +1,514 @@
+use crate::{
+    account::EthAccount,
+    hashed_cursor::{HashedAccountCursor, HashedCursorFactory, HashedStorageCursor},
+    prefix_set::PrefixSetMut,
+    trie_cursor::{AccountTrieCursor, StorageTrieCursor},
+    walker::TrieWalker,
+    StateRootError, StorageRoot, StorageRootError,
+};
+use reth_db::{tables, transaction::DbTx};
+use reth_primitives::{
+    keccak256,
+    proofs::EMPTY_ROOT,
+    trie::{AccountProof, HashBuilder, Nibbles, ProofRetainer, StorageProof},
+    Account, Address, Bytes, StorageEntry, H256, U256,
+};
+use reth_rlp::Encodable;
+use std::collections::{BTreeMap, HashMap};
+
+/// A struct for generating merkle proofs.
+///
+/// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
+/// on the hash builder and follows the same algorithm as the state root calculator.
+/// See [StateRoot](crate::StateRoot) for more info.
+pub struct Proof<'a, 'b, TX, H> {
+    /// A reference to the database transaction.
+    tx: &'a TX,
+    /// The factory for hashed cursors.
+    hashed_cursor_factory: &'b H,
+    /// A set of account prefixes that have changed.
+    changed_account_prefixes: PrefixSetMut,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    changed_storage_prefixes: HashMap<H256, PrefixSetMut>,
+}
+
+impl<'a, 'tx, TX> Proof<'a, 'a, TX, TX>
+where
+    TX: DbTx<'tx> + HashedCursorFactory<'a>,
+{
+    /// Create a new [Proof] instance.
+    pub fn new(tx: &'a TX) -> Self {
+        Self {
+            tx,
+            hashed_cursor_factory: tx,
+            changed_account_prefixes: PrefixSetMut::default(),
+            changed_storage_prefixes: HashMap::default(),
+        }
+    }
+}
+
+impl<'a, 'b, TX, H> Proof<'a, 'b, TX, H> {
+    /// Set the changed account prefixes.
+    ///
+    /// The prefix sets must contain the keys that differ between the hashed state and the
+    /// intermediate trie nodes in the database, i.e. when the hashed cursor factory overlays
+    /// the database state with in-memory changes.
+    pub fn with_changed_account_prefixes(mut self, prefixes: PrefixSetMut) -> Self {
+        self.changed_account_prefixes = prefixes;
+        self
+    }
+
+    /// Set the changed storage prefixes.
+    pub fn with_changed_storage_prefixes(mut self, prefixes: HashMap<H256, PrefixSetMut>) -> Self {
+        self.changed_storage_prefixes = prefixes;
+        self
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<'c, HF>(
+        self,
+        hashed_cursor_factory: &'c HF,
+    ) -> Proof<'a, 'c, TX, HF> {
+        Proof {
+            tx: self.tx,
+            hashed_cursor_factory,
+            changed_account_prefixes: self.changed_account_prefixes,
+            changed_storage_prefixes: self.changed_storage_prefixes,
+        }
+    }
+}
+
+impl<'a, 'b, 'tx, TX, H> Proof<'a, 'b, TX, H>
+where
+    TX: DbTx<'tx>,
+    H: HashedCursorFactory<'b>,
+{
+    /// Generate an account proof from intermediate nodes.
+    pub fn account_proof(
+        &self,
+        address: Address,
+        slots: &[H256],
+    ) -> Result<AccountProof, StateRootError> {
+        let target_hashed_address = keccak256(address);
+        let target_nibbles = Nibbles::unpack(target_hashed_address);
+        let mut account_proof = AccountProof::new(address);
+
+        let (_, proofs) =
+            self.account_trie_proofs(vec![target_nibbles], |hashed_address, account| {
+                if hashed_address == target_hashed_address {
+                    let (storage_root, storage_proofs) =
+                        self.storage_root_with_proofs(hashed_address, slots)?;
+                    account_proof.set_account(account, storage_root, storage_proofs);
+                    Ok(storage_root)
+                } else {
+                    self.storage_root(hashed_address)
+                }
+            })?;
+        account_proof.set_proof(proofs.into_values().collect());
+
+        // The storage proofs of a missing account prove the absence of the requested slots.
+        if account_proof.info.is_none() {
+            account_proof.storage_proofs = slots.iter().copied().map(StorageProof::new).collect();
+        }
+
+        Ok(account_proof)
+    }
+
+    /// Generate a multiproof of the account trie for the given target paths.
+    ///
+    /// Returns the state root and all nodes on the paths from the root to the targets, keyed and
+    /// ordered by their path. A target does not need to be a full account path: if it is a
+    /// partial path, the node located at that path is included as well.
+    pub fn account_multiproof(
+        &self,
+        targets: Vec<Nibbles>,
+    ) -> Result<(H256, BTreeMap<Nibbles, Bytes>), StateRootError> {
+        self.account_trie_proofs(targets, |hashed_address, _| self.storage_root(hashed_address))
+    }
+
+    /// Generate a multiproof of the storage trie of the given account for the given target paths.
+    ///
+    /// Returns the storage root and all nodes on the paths from the root to the targets, keyed and
+    /// ordered by their path. See [Proof::account_multiproof].
+    pub fn storage_multiproof(
+        &self,
+        hashed_address: H256,
+        targets: Vec<Nibbles>,
+    ) -> Result<(H256, BTreeMap<Nibbles, Bytes>), StorageRootError> {
+        self.storage_trie_proofs(hashed_address, targets, |_, _| {})
+    }
+
+    /// Walk the account trie, retaining the nodes on the paths to the given targets.
+    ///
+    /// The storage root of every visited account is provided by the `storage_root` closure.
+    fn account_trie_proofs<F>(
+        &self,
+        targets: Vec<Nibbles>,
+        mut storage_root: F,
+    ) -> Result<(H256, BTreeMap<Nibbles, Bytes>), StateRootError>
+    where
+        F: FnMut(H256, Account) -> Result<H256, StateRootError>,
+    {
+        let mut hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let mut trie_cursor =
+            AccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
+
+        // Create the walker.
+        let mut prefix_set = self.changed_account_prefixes.clone();
+        for target in &targets {
+            prefix_set.insert(target.clone());
+        }
+        let mut walker = TrieWalker::new(&mut trie_cursor, prefix_set.freeze());
+
+        // Create a hash builder to rebuild the root node since it is not available in the database.
+        let retainer = ProofRetainer::from_iter(targets);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+
+        let mut account_rlp = Vec::with_capacity(128);
+        while let Some(key) = walker.key() {
+            if walker.can_skip_current_node {
+                let value = walker.hash().unwrap();
+                let is_in_db_trie = walker.children_are_in_trie();
+                hash_builder.add_branch(key.clone(), value, is_in_db_trie);
+            }
+
+            let seek_key = match walker.next_unprocessed_key() {
+                Some(key) => key,
+                None => break, // no more keys
+            };
+
+            let next_key = walker.advance()?;
+            let mut next_account_entry = hashed_account_cursor.seek(seek_key)?;
+            while let Some((hashed_address, account)) = next_account_entry {
+                let account_nibbles = Nibbles::unpack(hashed_address);
+
+                if let Some(ref key) = next_key {
+                    if key < &account_nibbles {
+                        break
+                    }
+                }
+
+                let storage_root = storage_root(hashed_address, account)?;
+
+                account_rlp.clear();
+                let account = EthAccount::from(account).with_storage_root(storage_root);
+                account.encode(&mut &mut account_rlp);
+
+                hash_builder.add_leaf(account_nibbles, &account_rlp);
+
+                // Move the next account entry
+                next_account_entry = hashed_account_cursor.next()?;
+            }
+        }
+
+        let root = hash_builder.root();
+
+        Ok((root, hash_builder.take_proofs()))
+    }
+
+    /// Compute the storage root of the given account.
+    fn storage_root(&self, hashed_address: H256) -> Result<H256, StateRootError> {
+        let root = StorageRoot::new_hashed_with_factory(
+            self.tx,
+            self.hashed_cursor_factory,
+            hashed_address,
+        )
+        .with_changed_prefixes(self.storage_prefix_set(&hashed_address).freeze())
+        .root()?;
+        Ok(root)
+    }
+
+    /// Compute storage root and generate the proofs for the requested slots.
+    fn storage_root_with_proofs(
+        &self,
+        hashed_address: H256,
+        slots: &[H256],
+    ) -> Result<(H256, Vec<StorageProof>), StorageRootError> {
+        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
+
+        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
+        let (root, all_proof_nodes) =
+            self.storage_trie_proofs(hashed_address, target_nibbles, |nibbles, value| {
+                if let Some(proof) = proofs.iter_mut().find(|p| &p.nibbles == nibbles) {
+                    proof.set_value(value);
+                }
+            })?;
+
+        // The retained nodes are sorted by their path, so the nodes on the path to each slot
+        // are ordered from the root node down.
+        for proof in proofs.iter_mut() {
+            let nodes = all_proof_nodes
+                .iter()
+                .filter(|(path, _)| proof.nibbles.has_prefix(path))
+                .map(|(_, node)| node.clone())
+                .collect();
+            proof.set_proof(nodes);
+        }
+
+        Ok((root, proofs))
+    }
+
+    /// Walk the storage trie of the given account, retaining the nodes on the paths to the given
+    /// targets.
+    ///
+    /// Every visited storage slot is passed to the `on_slot` closure.
+    fn storage_trie_proofs<F>(
+        &self,
+        hashed_address: H256,
+        targets: Vec<Nibbles>,
+        mut on_slot: F,
+    ) -> Result<(H256, BTreeMap<Nibbles, Bytes>), StorageRootError>
+    where
+        F: FnMut(&Nibbles, U256),
+    {
+        let mut hashed_storage_cursor = self.hashed_cursor_factory.hashed_storage_cursor()?;
+
+        // short circuit on empty storage
+        if hashed_storage_cursor.is_storage_empty(hashed_address)? {
+            return Ok((EMPTY_ROOT, BTreeMap::default()))
+        }
+
+        let mut trie_cursor = StorageTrieCursor::new(
+            self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
+            hashed_address,
+        );
+
+        let mut prefix_set = self.storage_prefix_set(&hashed_address);
+        for target in &targets {
+            prefix_set.insert(target.clone());
+        }
+        let mut walker = TrieWalker::new(&mut trie_cursor, prefix_set.freeze());
+
+        let retainer = ProofRetainer::from_iter(targets);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        while let Some(key) = walker.key() {
+            if walker.can_skip_current_node {
+                hash_builder.add_branch(key, walker.hash().unwrap(), walker.children_are_in_trie());
+            }
+
+            let seek_key = match walker.next_unprocessed_key() {
+                Some(key) => key,
+                None => break, // no more keys
+            };
+
+            let next_key = walker.advance()?;
+            let mut storage = hashed_storage_cursor.seek(hashed_address, seek_key)?;
+            while let Some(StorageEntry { key: hashed_key, value }) = storage {
+                let storage_key_nibbles = Nibbles::unpack(hashed_key);
+                if let Some(ref key) = next_key {
+                    if key < &storage_key_nibbles {
+                        break
+                    }
+                }
+
+                on_slot(&storage_key_nibbles, value);
+
+                hash_builder
+                    .add_leaf(storage_key_nibbles, reth_rlp::encode_fixed_size(&value).as_ref());
+                storage = hashed_storage_cursor.next()?;
+            }
+        }
+
+        let root = hash_builder.root();
+
+        Ok((root, hash_builder.take_proofs()))
+    }
+
+    fn storage_prefix_set(&self, hashed_address: &H256) -> PrefixSetMut {
+        self.changed_storage_prefixes.get(hashed_address).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hashed_cursor::{HashedPostState, HashedPostStateCursorFactory},
+        StateRoot,
+    };
+    use reth_db::{
+        cursor::{DbCursorRW, DbDupCursorRO},
+        models::{AccountBeforeTx, BlockNumberAddress},
+        test_utils::create_test_rw_db,
+        transaction::DbTxMut,
+    };
+    use reth_primitives::MAINNET;
+    use reth_provider::ProviderFactory;
+
+    fn insert_state<'a, TX: DbTxMut<'a>>(tx: &TX) -> Vec<(Address, Vec<(H256, U256)>)> {
+        let state = (1..=20u64)
+            .map(|i| {
+                let storage = (1..=i)
+                    .map(|slot| (H256::from_low_u64_be(slot), U256::from(slot * 10)))
+                    .collect::<Vec<_>>();
+                (Address::from_low_u64_be(i), storage)
+            })
+            .collect::<Vec<_>>();
+
+        let mut hashed_accounts = tx.cursor_write::<tables::HashedAccount>().unwrap();
+        let mut hashed_storage = tx.cursor_dup_write::<tables::HashedStorage>().unwrap();
+        for (address, storage) in &state {
+            let hashed_address = keccak256(address);
+            let account = Account { nonce: 1, balance: U256::from(100), bytecode_hash: None };
+            hashed_accounts.upsert(hashed_address, account).unwrap();
+            for (slot, value) in storage {
+                hashed_storage
+                    .upsert(hashed_address, StorageEntry { key: keccak256(slot), value: *value })
+                    .unwrap();
+            }
+        }
+
+        state
+    }
+
+    fn assert_proof(proof: &AccountProof, root: H256, storage: &[(H256, U256)]) {
+        assert!(proof.info.is_some());
+        assert!(!proof.proof.is_empty());
+        assert_eq!(keccak256(&proof.proof[0]), root);
+
+        assert_eq!(proof.storage_proofs.len(), storage.len());
+        for (storage_proof, (slot, value)) in proof.storage_proofs.iter().zip(storage) {
+            assert_eq!(storage_proof.key, *slot);
+            assert_eq!(storage_proof.value, *value);
+            assert!(!storage_proof.proof.is_empty());
+            assert_eq!(keccak256(&storage_proof.proof[0]), proof.storage_root);
+        }
+    }
+
+    #[test]
+    fn account_and_storage_proofs() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let tx = factory.provider_rw().unwrap();
+
+        let state = insert_state(tx.tx_ref());
+        let (root, updates) = StateRoot::new(tx.tx_ref()).root_with_updates().unwrap();
+
+        // Proofs generated without intermediate nodes.
+        let mut proofs = Vec::with_capacity(state.len());
+        for (address, storage) in &state {
+            let slots = storage.iter().map(|(slot, _)| *slot).collect::<Vec<_>>();
+            let proof = Proof::new(tx.tx_ref()).account_proof(*address, &slots).unwrap();
+            assert_proof(&proof, root, storage);
+            proofs.push(proof);
+        }
+
+        // Proofs generated from the intermediate nodes must match.
+        updates.flush(tx.tx_ref()).unwrap();
+        for ((address, storage), expected) in state.iter().zip(proofs) {
+            let slots = storage.iter().map(|(slot, _)| *slot).collect::<Vec<_>>();
+            let proof = Proof::new(tx.tx_ref()).account_proof(*address, &slots).unwrap();
+            assert_eq!(proof, expected);
+        }
+    }
+
+    #[test]
+    fn missing_account_proof() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let tx = factory.provider_rw().unwrap();
+
+        insert_state(tx.tx_ref());
+        let root = StateRoot::new(tx.tx_ref()).root().unwrap();
+
+        let slot = H256::from_low_u64_be(1);
+        let proof =
+            Proof::new(tx.tx_ref()).account_proof(Address::from_low_u64_be(100), &[slot]).unwrap();
+        assert_eq!(proof.info, None);
+        assert_eq!(proof.storage_root, EMPTY_ROOT);
+        assert_eq!(keccak256(&proof.proof[0]), root);
+        assert_eq!(proof.storage_proofs, vec![StorageProof::new(slot)]);
+    }
+
+    #[test]
+    fn historical_proofs_from_reverts() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let tx = factory.provider_rw().unwrap();
+
+        // The state as of block 0.
+        let state = insert_state(tx.tx_ref());
+        let (root, updates) = StateRoot::new(tx.tx_ref()).root_with_updates().unwrap();
+        updates.flush(tx.tx_ref()).unwrap();
+
+        // Block 1 updates an account and one of its slots, adds a new slot and creates an account.
+        let changed = Address::from_low_u64_be(5);
+        let created = Address::from_low_u64_be(100);
+        let slot = H256::from_low_u64_be(1);
+        let new_slot = H256::from_low_u64_be(100);
+        {
+            let hashed_changed = keccak256(changed);
+            let account = Account { nonce: 2, balance: U256::from(50), bytecode_hash: None };
+            let mut hashed_accounts = tx.tx_ref().cursor_write::<tables::HashedAccount>().unwrap();
+            hashed_accounts.upsert(hashed_changed, account).unwrap();
+            hashed_accounts.upsert(keccak256(created), account).unwrap();
+
+            let mut hashed_storage =
+                tx.tx_ref().cursor_dup_write::<tables::HashedStorage>().unwrap();
+            if hashed_storage
+                .seek_by_key_subkey(hashed_changed, keccak256(slot))
+                .unwrap()
+                .filter(|entry| entry.key == keccak256(slot))
+                .is_some()
+            {
+                hashed_storage.delete_current().unwrap();
+            }
+            for (key, value) in [(slot, U256::from(1234)), (new_slot, U256::from(1))] {
+                hashed_storage
+                    .upsert(hashed_changed, StorageEntry { key: keccak256(key), value })
+                    .unwrap();
+            }
+
+            let mut account_changesets =
+                tx.tx_ref().cursor_dup_write::<tables::AccountChangeSet>().unwrap();
+            let previous = Account { nonce: 1, balance: U256::from(100), bytecode_hash: None };
+            account_changesets
+                .upsert(1, AccountBeforeTx { address: changed, info: Some(previous) })
+                .unwrap();
+            account_changesets.upsert(1, AccountBeforeTx { address: created, info: None }).unwrap();
+
+            let mut storage_changesets =
+                tx.tx_ref().cursor_dup_write::<tables::StorageChangeSet>().unwrap();
+            for (key, value) in [(slot, U256::from(10)), (new_slot, U256::ZERO)] {
+                storage_changesets
+                    .upsert(BlockNumberAddress((1, changed)), StorageEntry { key, value })
+                    .unwrap();
+            }
+        }
+
+        // Rebuild the trie for the latest state.
+        tx.tx_ref().clear::<tables::AccountsTrie>().unwrap();
+        tx.tx_ref().clear::<tables::StoragesTrie>().unwrap();
+        let (latest_root, updates) = StateRoot::new(tx.tx_ref()).root_with_updates().unwrap();
+        updates.flush(tx.tx_ref()).unwrap();
+        assert_ne!(latest_root, root);
+
+        // Proofs with the block 1 changes reverted must match the state as of block 0.
+        let revert_state = HashedPostState::from_reverts(tx.tx_ref(), 1).unwrap();
+        let (account_prefix_set, storage_prefix_sets) = revert_state.construct_prefix_sets_mut();
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(tx.tx_ref(), &revert_state);
+        let historical_proof = |address: Address, slots: &[H256]| {
+            Proof::new(tx.tx_ref())
+                .with_hashed_cursor_factory(&hashed_cursor_factory)
+                .with_changed_account_prefixes(account_prefix_set.clone())
+                .with_changed_storage_prefixes(storage_prefix_sets.clone())
+                .account_proof(address, slots)
+                .unwrap()
+        };
+
+        for (address, storage) in &state {
+            let slots = storage.iter().map(|(slot, _)| *slot).collect::<Vec<_>>();
+            let proof = historical_proof(*address, &slots);
+            assert_proof(&proof, root, storage);
+        }
+
+        let proof = historical_proof(changed, &[new_slot]);
+        assert_eq!(keccak256(&proof.proof[0]), root);
+        assert_eq!(proof.storage_proofs[0].value, U256::ZERO);
+
+        let proof = historical_proof(created, &[]);
+        assert_eq!(proof.info, None);
+        assert_eq!(keccak256(&proof.proof[0]), root);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::RangeProofError;
+use reth_primitives::{
+    keccak256,
+    trie::{HashBuilder, Nibbles},
+    Bytes, H256,
+};
+use reth_rlp::Header;
+use std::collections::HashMap;
+
+/// Verifies that the given leaves form the complete, consecutive range of the trie with the given
+/// root that starts at `origin` and ends at the last leaf.
+///
+/// The proof consists of the trie nodes on the paths from the root to `origin` and to the last
+/// leaf of the range. If the proof is empty, the leaves must make up the entire trie.
+///
+/// The leaf values are the RLP encoded values as they are stored in the trie, i.e. the encoded
+/// account for the account trie and the encoded slot value for storage tries.
+///
+/// Returns `true` if the trie contains more leaves after the last leaf of the range.
+pub fn verify_range_proof<V: AsRef<[u8]>>(
+    root: H256,
+    origin: H256,
+    leaves: &[(H256, V)],
+    proof: &[Bytes],
+) -> Result<bool, RangeProofError> {
+    if leaves.first().map_or(false, |(key, _)| *key < origin) ||
+        leaves.windows(2).any(|pair| pair[0].0 >= pair[1].0)
+    {
+        return Err(RangeProofError::UnorderedKeys)
+    }
+
+    let mut elements = leaves
+        .iter()
+        .map(|(key, value)| (Nibbles::unpack(key), Element::Leaf(value.as_ref().to_vec())))
+        .collect::<Vec<_>>();
+
+    // Without a proof the range must be the whole trie.
+    let mut has_more = false;
+    if !proof.is_empty() {
+        let mut collector = BoundaryCollector {
+            nodes: proof.iter().map(|node| (keccak256(node), &node[..])).collect(),
+            left: Nibbles::unpack(origin),
+            right: leaves.last().map(|(key, _)| Nibbles::unpack(key)),
+            elements: Vec::new(),
+            has_more: false,
+        };
+        let root_node = collector.resolve(NodeRef::Hash(root))?;
+        collector.walk(root_node, Nibbles::default())?;
+
+        has_more = collector.has_more;
+        elements.extend(collector.elements);
+        elements.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    let mut hash_builder = HashBuilder::default();
+    for (path, element) in elements {
+        match element {
+            Element::Leaf(value) => hash_builder.add_leaf(path, &value),
+            Element::Hash(hash) => hash_builder.add_branch(path, hash, false),
+        }
+    }
+
+    let got = hash_builder.root();
+    if got != root {
+        return Err(RangeProofError::RootMismatch { expected: root, got })
+    }
+    Ok(has_more)
+}
+
+/// An element of the trie that is fed to the hash builder.
+enum Element {
+    /// A leaf with its value.
+    Leaf(Vec<u8>),
+    /// The hash of a subtree.
+    Hash(H256),
+}
+
+/// A reference to a child node.
+#[derive(Clone, Copy)]
+enum NodeRef<'a> {
+    /// The child is referenced by the hash of its encoding.
+    Hash(H256),
+    /// The child is embedded in its parent, because its encoding is shorter than 32 bytes.
+    Inline(&'a [u8]),
+}
+
+/// A decoded trie node.
+enum TrieNode<'a> {
+    Branch(Vec<Option<NodeRef<'a>>>),
+    Extension(Nibbles, NodeRef<'a>),
+    Leaf(Nibbles, &'a [u8]),
+}
+
+/// Collects all subtrees of the proven trie that lie outside of the range bounds.
+struct BoundaryCollector<'a> {
+    /// The proof nodes by their hash.
+    nodes: HashMap<H256, &'a [u8]>,
+    /// The path of the range origin.
+    left: Nibbles,
+    /// The path of the last leaf of the range, unbounded if the range is empty.
+    right: Option<Nibbles>,
+    /// The collected out of range elements.
+    elements: Vec<(Nibbles, Element)>,
+    /// Whether an element to the right of the range has been found.
+    has_more: bool,
+}
+
+impl<'a> BoundaryCollector<'a> {
+    fn resolve(&self, node: NodeRef<'a>) -> Result<TrieNode<'a>, RangeProofError> {
+        match node {
+            NodeRef::Hash(hash) => {
+                let rlp: &'a [u8] =
+                    self.nodes.get(&hash).copied().ok_or(RangeProofError::MissingNode(hash))?;
+                decode_node(rlp)
+            }
+            NodeRef::Inline(rlp) => decode_node(rlp),
+        }
+    }
+
+    /// Returns true if the subtree at the given path lies entirely before the origin.
+    fn is_left(&self, path: &Nibbles) -> bool {
+        path < &self.left && !self.left.has_prefix(path)
+    }
+
+    /// Returns true if the subtree at the given path lies entirely after the range.
+    fn is_right(&self, path: &Nibbles) -> bool {
+        self.right.as_ref().map_or(false, |right| path > right && !right.has_prefix(path))
+    }
+
+    /// Returns true if the given path is on the path to one of the range bounds.
+    fn is_boundary(&self, path: &Nibbles) -> bool {
+        self.left.has_prefix(path) || self.right.as_ref().map_or(false, |r| r.has_prefix(path))
+    }
+
+    /// Walks a node on the boundary paths.
+    fn walk(&mut self, node: TrieNode<'a>, path: Nibbles) -> Result<(), RangeProofError> {
+        match node {
+            TrieNode::Branch(children) => {
+                for (nibble, child) in children.into_iter().enumerate() {
+                    if let Some(child) = child {
+                        let mut child_path = path.clone();
+                        child_path.extend([nibble as u8]);
+                        self.visit(child, child_path)?;
+                    }
+                }
+            }
+            TrieNode::Extension(key, child) => self.visit(child, path.join(&key))?,
+            TrieNode::Leaf(key, value) => {
+                // leaves within the range are provided by the range itself
+                let path = path.join(&key);
+                if self.is_left(&path) || self.is_right(&path) {
+                    self.has_more |= self.is_right(&path);
+                    self.elements.push((path, Element::Leaf(value.to_vec())));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Visits the child node at the given path.
+    fn visit(&mut self, child: NodeRef<'a>, path: Nibbles) -> Result<(), RangeProofError> {
+        if self.is_left(&path) || self.is_right(&path) {
+            self.has_more |= self.is_right(&path);
+            self.add_subtree(child, path)?;
+        } else if self.is_boundary(&path) {
+            let node = self.resolve(child)?;
+            self.walk(node, path)?;
+        }
+        Ok(())
+    }
+
+    /// Adds all elements of an out of range subtree that is embedded in its parent.
+    fn add_inline(&mut self, node: TrieNode<'a>, path: Nibbles) -> Result<(), RangeProofError> {
+        match node {
+            TrieNode::Branch(children) => {
+                for (nibble, child) in children.into_iter().enumerate() {
+                    if let Some(child) = child {
+                        let mut child_path = path.clone();
+                        child_path.extend([nibble as u8]);
+                        self.add_subtree(child, child_path)?;
+                    }
+                }
+            }
+            TrieNode::Extension(key, child) => self.add_subtree(child, path.join(&key))?,
+            TrieNode::Leaf(key, value) => {
+                self.elements.push((path.join(&key), Element::Leaf(value.to_vec())))
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds an out of range subtree at the given path.
+    fn add_subtree(&mut self, child: NodeRef<'a>, path: Nibbles) -> Result<(), RangeProofError> {
+        match child {
+            NodeRef::Hash(hash) => self.elements.push((path, Element::Hash(hash))),
+            NodeRef::Inline(rlp) => self.add_inline(decode_node(rlp)?, path)?,
+        }
+        Ok(())
+    }
+}
+
+/// Decodes an RLP encoded trie node.
+fn decode_node(rlp: &[u8]) -> Result<TrieNode<'_>, RangeProofError> {
+    let mut buf = rlp;
+    let header = Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(reth_rlp::DecodeError::UnexpectedString.into())
+    }
+    if buf.len() < header.payload_length {
+        return Err(reth_rlp::DecodeError::InputTooShort.into())
+    }
+    let mut payload = &buf[..header.payload_length];
+
+    // Split the list into its items, embedded nodes are kept with their header.
+    let mut items = Vec::with_capacity(17);
+    while !payload.is_empty() {
+        let mut rest = payload;
+        let item_header = Header::decode(&mut rest)?;
+        if rest.len() < item_header.payload_length {
+            return Err(reth_rlp::DecodeError::InputTooShort.into())
+        }
+        let item_length = payload.len() - rest.len() + item_header.payload_length;
+        let item = if item_header.list {
+            NodeItem::List(&payload[..item_length])
+        } else {
+            NodeItem::String(&rest[..item_header.payload_length])
+        };
+        items.push(item);
+        payload = &payload[item_length..];
+    }
+
+    let node_ref = |item: NodeItem<'_>| match item {
+        NodeItem::String(hash) if hash.len() == 32 => {
+            Ok(Some(NodeRef::Hash(H256::from_slice(hash))))
+        }
+        NodeItem::String(empty) if empty.is_empty() => Ok(None),
+        NodeItem::List(rlp) => Ok(Some(NodeRef::Inline(rlp))),
+        NodeItem::String(_) => Err(reth_rlp::DecodeError::Custom("invalid node reference")),
+    };
+
+    match items.len() {
+        17 => {
+            let children =
+                items.into_iter().take(16).map(node_ref).collect::<Result<Vec<_>, _>>()?;
+            Ok(TrieNode::Branch(children))
+        }
+        2 => {
+            let (path, is_leaf) = match items[0] {
+                NodeItem::String(encoded) => Nibbles::decode_path(encoded)
+                    .ok_or(reth_rlp::DecodeError::Custom("invalid node path"))?,
+                NodeItem::List(_) => return Err(reth_rlp::DecodeError::UnexpectedList.into()),
+            };
+            if is_leaf {
+                match items[1] {
+                    NodeItem::String(value) => Ok(TrieNode::Leaf(path, value)),
+                    NodeItem::List(_) => Err(reth_rlp::DecodeError::UnexpectedList.into()),
+                }
+            } else {
+                let child = node_ref(items[1])?
+                    .ok_or(reth_rlp::DecodeError::Custom("empty extension child"))?;
+                Ok(TrieNode::Extension(path, child))
+            }
+        }
+        _ => Err(reth_rlp::DecodeError::Custom("invalid number of node items").into()),
+    }
+}
+
+/// An item of an RLP encoded trie node.
+#[derive(Clone, Copy)]
+enum NodeItem<'a> {
+    /// The payload of a string item.
+    String(&'a [u8]),
+    /// The full encoding of a list item.
+    List(&'a [u8]),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account::EthAccount, Proof, StateRoot};
+    use reth_db::{
+        cursor::DbCursorRW, tables, test_utils::create_test_rw_db, transaction::DbTxMut,
+    };
+    use reth_primitives::{Account, MAINNET, U256};
+    use reth_provider::ProviderFactory;
+    use reth_rlp::Encodable;
+
+    fn account_leaves(count: u64) -> Vec<(H256, Account, Vec<u8>)> {
+        let mut leaves = (0..count)
+            .map(|i| {
+                let account =
+                    Account { nonce: i, balance: U256::from(i * 100), bytecode_hash: None };
+                let mut rlp = Vec::new();
+                EthAccount::from(account).encode(&mut rlp);
+                (keccak256(H256::from_low_u64_be(i)), account, rlp)
+            })
+            .collect::<Vec<_>>();
+        leaves.sort_by_key(|(key, _, _)| *key);
+        leaves
+    }
+
+    #[test]
+    fn verify_account_ranges() {
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db.as_ref(), MAINNET.clone());
+        let tx = factory.provider_rw().unwrap();
+
+        let leaves = account_leaves(100);
+        let mut cursor = tx.tx_ref().cursor_write::<tables::HashedAccount>().unwrap();
+        for (key, account, _) in &leaves {
+            cursor.upsert(*key, *account).unwrap();
+        }
+        let root = StateRoot::new(tx.tx_ref()).root().unwrap();
+
+        let range = |start: usize, end: usize| {
+            leaves[start..end].iter().map(|(key, _, rlp)| (*key, rlp.clone())).collect::<Vec<_>>()
+        };
+        let prove = |origin: H256, last: Option<H256>| {
+            let mut targets = vec![Nibbles::unpack(origin)];
+            targets.extend(last.map(Nibbles::unpack));
+            let (proof_root, proof) = Proof::new(tx.tx_ref()).account_multiproof(targets).unwrap();
+            assert_eq!(proof_root, root);
+            proof.into_values().collect::<Vec<_>>()
+        };
+
+        // the whole trie without a proof
+        assert_eq!(verify_range_proof(root, H256::zero(), &range(0, 100), &[]), Ok(false));
+
+        // a range in the middle of the trie
+        let proof = prove(leaves[10].0, Some(leaves[49].0));
+        assert_eq!(verify_range_proof(root, leaves[10].0, &range(10, 50), &proof), Ok(true));
+
+        // the tail of the trie, starting at an origin that is not part of the trie
+        let origin = H256::from_low_u64_be(1);
+        let proof = prove(origin, Some(leaves[99].0));
+        assert_eq!(verify_range_proof(root, origin, &range(0, 100), &proof), Ok(false));
+
+        // omitting a leaf within the range must be detected
+        let proof = prove(leaves[10].0, Some(leaves[49].0));
+        let mut incomplete = range(10, 50);
+        incomplete.remove(20);
+        assert!(matches!(
+            verify_range_proof(root, leaves[10].0, &incomplete, &proof),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+
+        // an empty range proves that there are no leaves after the origin
+        let origin = H256::repeat_byte(0xff);
+        let proof = prove(origin, None);
+        assert_eq!(verify_range_proof::<Vec<u8>>(root, origin, &[], &proof), Ok(false));
+        let proof = prove(leaves[50].0, None);
+        assert!(verify_range_proof::<Vec<u8>>(root, leaves[50].0, &[], &proof).is_err());
+
+        // unordered keys are rejected
+        let mut unordered = range(10, 50);
+        unordered.swap(0, 1);
+        assert_eq!(
+            verify_range_proof(root, leaves[10].0, &unordered, &proof),
+            Err(RangeProofError::UnorderedKeys)
+        );
+    }
+}
@@ -34,6 +34,11 @@ pub struct StateRoot<'a, 'b, TX, H> {
     pub changed_storage_prefixes: HashMap<H256, PrefixSet>,
     /// A map containing keys of accounts that were destroyed.
     pub destroyed_accounts: HashSet<H256>,
+    /// A map of precomputed storage roots with the hashed address as key and the storage root,
+    /// the number of walked storage slots and the storage trie updates as the value.
+    ///
+    /// Accounts present in this map are not re-walked when computing the state root.
+    pub storage_roots: HashMap<H256, (H256, usize, TrieUpdates)>,
     /// Previous intermediate state.
     previous_state: Option<IntermediateStateRootState>,
     /// The number of updates after which the intermediate progress should be returned.
@@ -59,6 +64,17 @@ impl<'a, 'b, TX, H> StateRoot<'a, 'b, TX, H> {
         self
     }
 
+    /// Set the precomputed storage roots.
+    ///
+    /// The storage trie updates are only merged if the updates are retained.
+    pub fn with_storage_roots(
+        mut self,
+        storage_roots: HashMap<H256, (H256, usize, TrieUpdates)>,
+    ) -> Self {
+        self.storage_roots = storage_roots;
+        self
+    }
+
     /// Set the threshold.
     pub fn with_threshold(mut self, threshold: u64) -> Self {
         self.threshold = threshold;
@@ -87,6 +103,7 @@ impl<'a, 'b, TX, H> StateRoot<'a, 'b, TX, H> {
             changed_account_prefixes: self.changed_account_prefixes,
             changed_storage_prefixes: self.changed_storage_prefixes,
             destroyed_accounts: self.destroyed_accounts,
+            storage_roots: self.storage_roots,
             threshold: self.threshold,
             previous_state: self.previous_state,
             hashed_cursor_factory,
@@ -105,6 +122,7 @@ where
             changed_account_prefixes: PrefixSetMut::default().freeze(),
             changed_storage_prefixes: HashMap::default(),
             destroyed_accounts: HashSet::default(),
+            storage_roots: HashMap::default(),
             previous_state: None,
             threshold: 100_000,
             hashed_cursor_factory: tx,
@@ -225,6 +243,7 @@ where
     fn calculate(self, retain_updates: bool) -> Result<StateRootProgress, StateRootError> {
         tracing::debug!(target: "loader", "calculating state root");
         let mut trie_updates = TrieUpdates::default();
+        let mut storage_roots = self.storage_roots;
 
         let mut hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let mut trie_cursor =
@@ -291,30 +310,40 @@ where
                     }
                 }
 
-                // We assume we can always calculate a storage root without
-                // OOMing. This opens us up to a potential DOS vector if
-                // a contract had too many storage entries and they were
-                // all buffered w/o us returning and committing our intermeditate
-                // progress.
-                // TODO: We can consider introducing the TrieProgress::Progress/Complete
-                // abstraction inside StorageRoot, but let's give it a try as-is for now.
-                let storage_root_calculator = StorageRoot::new_hashed(self.tx, hashed_address)
-                    .with_hashed_cursor_factory(self.hashed_cursor_factory)
-                    .with_changed_prefixes(
-                        self.changed_storage_prefixes
-                            .get(&hashed_address)
-                            .cloned()
-                            .unwrap_or_default(),
-                    );
-
-                let storage_root = if retain_updates {
-                    let (root, storage_slots_walked, updates) =
-                        storage_root_calculator.root_with_updates()?;
-                    hashed_entries_walked += storage_slots_walked;
-                    trie_updates.extend(updates.into_iter());
+                let storage_root = if let Some((root, storage_slots_walked, updates)) =
+                    storage_roots.remove(&hashed_address)
+                {
+                    if retain_updates {
+                        hashed_entries_walked += storage_slots_walked;
+                        trie_updates.extend(updates.into_iter());
+                    }
                     root
                 } else {
-                    storage_root_calculator.root()?
+                    // We assume we can always calculate a storage root without
+                    // OOMing. This opens us up to a potential DOS vector if
+                    // a contract had too many storage entries and they were
+                    // all buffered w/o us returning and committing our intermeditate
+                    // progress.
+                    // TODO: We can consider introducing the TrieProgress::Progress/Complete
+                    // abstraction inside StorageRoot, but let's give it a try as-is for now.
+                    let storage_root_calculator = StorageRoot::new_hashed(self.tx, hashed_address)
+                        .with_hashed_cursor_factory(self.hashed_cursor_factory)
+                        .with_changed_prefixes(
+                            self.changed_storage_prefixes
+                                .get(&hashed_address)
+                                .cloned()
+                                .unwrap_or_default(),
+                        );
+
+                    if retain_updates {
+                        let (root, storage_slots_walked, updates) =
+                            storage_root_calculator.root_with_updates()?;
+                        hashed_entries_walked += storage_slots_walked;
+                        trie_updates.extend(updates.into_iter());
+                        root
+                    } else {
+                        storage_root_calculator.root()?
+                    }
                 };
 
                 let account = EthAccount::from(account).with_storage_root(storage_root);